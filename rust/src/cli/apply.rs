@@ -6,9 +6,16 @@ use std::io::{stdin, stdout, Read, Write};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
 
-use nmstate::{NetworkPolicy, NetworkState};
+use nmstate::{Ipv6LinkLocalVerify, NetworkPolicy, NetworkState};
 
-use crate::error::CliError;
+use crate::{
+    error::CliError,
+    identity::store_applied_identity,
+    restore::store_last_good_state,
+    stage::{
+        arm_activation_timer, store_staged_state, validate_activation_time,
+    },
+};
 
 const DEFAULT_TIMEOUT: u32 = 60;
 
@@ -42,6 +49,15 @@ where
     let kernel_only = matches.try_contains_id("KERNEL").unwrap_or_default();
     let no_verify = matches.try_contains_id("NO_VERIFY").unwrap_or_default();
     let no_commit = matches.try_contains_id("NO_COMMIT").unwrap_or_default();
+    let no_checkpoint =
+        matches.try_contains_id("NO_CHECKPOINT").unwrap_or_default();
+    let activate_at = matches
+        .try_get_one::<String>("ACTIVATE_AT")
+        .ok()
+        .flatten()
+        .cloned();
+    let stage = activate_at.is_some()
+        || matches.try_contains_id("STAGE").unwrap_or_default();
     let timeout = if matches.try_contains_id("TIMEOUT").unwrap_or_default() {
         match matches.try_get_one::<String>("TIMEOUT") {
             Ok(Some(t)) => match u32::from_str(t) {
@@ -97,17 +113,56 @@ where
     net_state.set_kernel_only(kernel_only);
     net_state.set_verify_change(!no_verify);
     net_state.set_commit(!no_commit);
+    net_state.set_checkpoint(!no_checkpoint);
     net_state.set_timeout(timeout);
-    net_state.set_memory_only(
-        matches.try_contains_id("MEMORY_ONLY").unwrap_or_default(),
-    );
+    let memory_only =
+        matches.try_contains_id("MEMORY_ONLY").unwrap_or_default();
+    net_state.set_memory_only(memory_only);
+    if let Some(mode) = matches
+        .try_get_one::<String>("IPV6_LINK_LOCAL_VERIFY")
+        .ok()
+        .flatten()
+    {
+        net_state
+            .set_ipv6_link_local_verify(Ipv6LinkLocalVerify::from_str(mode)?);
+    }
+
+    net_state.set_stage_only(stage);
+
+    if matches.try_contains_id("DRY_RUN").unwrap_or_default() {
+        return Ok(serde_yaml::to_string(&net_state.gen_plan()?)?);
+    }
+
+    if let Some(time) = activate_at.as_deref() {
+        validate_activation_time(time)?;
+    }
 
     net_state.apply()?;
+    if stage {
+        let index = store_staged_state(&net_state)?;
+        if let Some(time) = activate_at.as_deref() {
+            arm_activation_timer(time, index)?;
+        }
+    } else if !no_commit {
+        // Uncommitted change might be rolled back, hence not considered as
+        // applied state.
+        store_applied_identity(&net_state);
+        if !kernel_only && !memory_only {
+            store_last_good_state();
+        }
+    }
     if !matches.try_contains_id("SHOW_SECRETS").unwrap_or_default() {
         net_state.hide_secrets();
     }
+    // Deprecation warnings are included as YAML comments, so the output is
+    // still valid state for applying.
+    let mut output = String::new();
+    for warning in net_state.deprecation_warnings() {
+        output += &format!("# DEPRECATED: {warning}\n");
+    }
     let sorted_net_state = crate::query::sort_netstate(net_state)?;
-    Ok(serde_yaml::to_string(&sorted_net_state)?)
+    output += &serde_yaml::to_string(&sorted_net_state)?;
+    Ok(output)
 }
 
 pub(crate) fn commit(checkpoint: &str) -> Result<String, CliError> {
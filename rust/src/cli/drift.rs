@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkDrift;
+
+use crate::error::CliError;
+
+// Show the difference between live network state and the configuration
+// saved in NetworkManager connection profiles, optionally persisting or
+// reverting it via the normal apply.
+pub(crate) fn drift(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let drift = NetworkDrift::retrieve()?;
+    let output = if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&drift)?
+    } else {
+        serde_yaml::to_string(&drift)?
+    };
+    if drift.is_empty() {
+        log::info!("Live network state is identical to saved configuration");
+        return Ok(output);
+    }
+    if matches.is_present("PERSIST") {
+        log::info!("Persisting live network state to saved configuration");
+        drift.persist_state().apply()?;
+    } else if matches.is_present("REVERT") {
+        log::info!("Reverting live network state to saved configuration");
+        drift.revert_state().apply()?;
+    }
+    Ok(output)
+}
@@ -1,19 +1,65 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
 
 use nmstate::NetworkState;
 
 use crate::error::CliError;
 
-pub(crate) fn gen_conf(file_path: &str) -> Result<String, CliError> {
+const EARLY_BOOT_BACKEND: &str = "iproute";
+const EARLY_BOOT_SCRIPT_FOLDER: &str = "/usr/libexec";
+const EARLY_BOOT_UNIT_FOLDER: &str = "/etc/systemd/system";
+const EARLY_BOOT_SCRIPT_MODE: u32 = 0o755;
+
+pub(crate) fn gen_conf(
+    file_path: &str,
+    early_boot: bool,
+    install: bool,
+) -> Result<String, CliError> {
     let mut fd = std::fs::File::open(file_path)?;
     let mut content = String::new();
     // Replace non-breaking space '\u{A0}'  to normal space
     fd.read_to_string(&mut content)?;
     let content = content.replace('\u{A0}', " ");
-    let net_state: NetworkState = serde_yaml::from_str(&content)?;
+    let mut net_state: NetworkState = serde_yaml::from_str(&content)?;
+    net_state.set_gen_early_boot(early_boot);
     let confs = net_state.gen_conf()?;
+    if install {
+        for (file_name, content) in confs
+            .get(EARLY_BOOT_BACKEND)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            install_early_boot_file(file_name, content)?;
+        }
+    }
     let escaped_string = serde_yaml::to_string(&confs)?;
     Ok(escaped_string.replace("\\n", "\n\n"))
 }
+
+// The systemd unit is invoking the script directly, hence the script should
+// be executable.
+fn install_early_boot_file(
+    file_name: &str,
+    content: &str,
+) -> Result<(), CliError> {
+    let is_script = file_name.ends_with(".sh");
+    let file_path = format!(
+        "{}/{file_name}",
+        if is_script {
+            EARLY_BOOT_SCRIPT_FOLDER
+        } else {
+            EARLY_BOOT_UNIT_FOLDER
+        }
+    );
+    std::fs::write(&file_path, content)?;
+    if is_script {
+        std::fs::set_permissions(
+            &file_path,
+            std::fs::Permissions::from_mode(EARLY_BOOT_SCRIPT_MODE),
+        )?;
+    }
+    log::info!("Early boot file installed to {file_path}");
+    Ok(())
+}
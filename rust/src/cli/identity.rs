@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::path::Path;
+
+use nmstate::NetworkState;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::CliError;
+
+const IDENTITY_FOLDER: &str = "/var/lib/nmstate";
+const IDENTITY_FILE_NAME: &str = "applied_identity.yml";
+const CHECKSUM_ALGORITHM: &str = "sha256";
+
+// Identity of last successfully applied network state, allowing compliance
+// tools to attest that host is running the approved network configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct AppliedIdentity {
+    algorithm: String,
+    // Checksum of canonicalized applied state with secrets hidden.
+    state_checksum: String,
+    // Checksum of the NetworkManager keyfiles generated for applied state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keyfile_checksum: Option<String>,
+    applied_time: String,
+    nmstate_version: String,
+}
+
+impl AppliedIdentity {
+    fn new(net_state: &NetworkState) -> Result<Self, CliError> {
+        let applied_state = retrieve_applied_state(net_state)?;
+        Ok(Self {
+            algorithm: CHECKSUM_ALGORITHM.to_string(),
+            state_checksum: gen_state_checksum(&applied_state)?,
+            keyfile_checksum: gen_keyfile_checksum(net_state),
+            applied_time: chrono::Local::now().to_rfc3339(),
+            nmstate_version: clap::crate_version!().to_string(),
+        })
+    }
+}
+
+// Store identity of applied state. Failure is only logged as warning, as
+// applied state is already in effect.
+pub(crate) fn store_applied_identity(net_state: &NetworkState) {
+    if let Err(e) = _store_applied_identity(net_state) {
+        log::warn!("Failed to store identity of applied state: {e}");
+    }
+}
+
+fn _store_applied_identity(net_state: &NetworkState) -> Result<(), CliError> {
+    let identity = AppliedIdentity::new(net_state)?;
+    std::fs::create_dir_all(IDENTITY_FOLDER)?;
+    let file_path = Path::new(IDENTITY_FOLDER).join(IDENTITY_FILE_NAME);
+    let mut fd = std::fs::File::create(&file_path)?;
+    fd.write_all(serde_yaml::to_string(&identity)?.as_bytes())?;
+    log::info!(
+        "Stored identity of applied state to {}: {} {}",
+        file_path.display(),
+        identity.algorithm,
+        identity.state_checksum
+    );
+    Ok(())
+}
+
+pub(crate) fn show_identity(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let file_path = Path::new(IDENTITY_FOLDER).join(IDENTITY_FILE_NAME);
+    if !file_path.exists() {
+        return Err(CliError::from(format!(
+            "No identity of applied state found in {}, please apply \
+            network state via nmstatectl first",
+            file_path.display()
+        )));
+    }
+    let fd = std::fs::File::open(&file_path)?;
+    let identity: AppliedIdentity = serde_yaml::from_reader(fd)?;
+    Ok(if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&identity)?
+    } else {
+        serde_yaml::to_string(&identity)?
+    })
+}
+
+// The running configuration of the interfaces, routes, route rules and DNS
+// touched by desired state, so the identity reflects what was actually
+// applied instead of how user wrote the desired state.
+fn retrieve_applied_state(
+    desired: &NetworkState,
+) -> Result<NetworkState, CliError> {
+    let mut current = NetworkState::new();
+    current.set_running_config_only(true);
+    current.retrieve()?;
+    Ok(filter_applied_state(desired, &current))
+}
+
+pub(crate) fn filter_applied_state(
+    desired: &NetworkState,
+    current: &NetworkState,
+) -> NetworkState {
+    let mut applied = NetworkState::new();
+    let mut iface_names: Vec<&str> = Vec::new();
+    for iface in desired.interfaces.iter().filter(|i| !i.is_absent()) {
+        if let Some(cur_iface) = current
+            .interfaces
+            .get_iface(iface.name(), iface.iface_type())
+        {
+            applied.append_interface_data(cur_iface.clone());
+            iface_names.push(cur_iface.name());
+        }
+    }
+    // Routes of touched interfaces, or all routes when routes are desired.
+    if desired.routes.config.is_some() || !iface_names.is_empty() {
+        applied.routes.config = current.routes.config.as_ref().map(|rts| {
+            rts.iter()
+                .filter(|rt| {
+                    desired.routes.config.is_some()
+                        || rt
+                            .next_hop_iface
+                            .as_deref()
+                            .map(|n| iface_names.contains(&n))
+                            .unwrap_or_default()
+                })
+                .cloned()
+                .collect()
+        });
+    }
+    if desired.rules.config.is_some() {
+        applied.rules.config = current.rules.config.clone();
+    }
+    if desired.dns.config.is_some() {
+        applied.dns.config = current.dns.config.clone();
+    }
+    applied
+}
+
+// Canonicalize the state with secrets hidden, so equivalent states always
+// produce the same checksum regardless of the order or text form.
+pub(crate) fn gen_state_checksum(
+    net_state: &NetworkState,
+) -> Result<String, CliError> {
+    let mut net_state = net_state.clone();
+    net_state.hide_secrets();
+    net_state.canonicalize();
+    let sorted_state = crate::query::sort_netstate(net_state)?;
+    let content = serde_json::to_string(&sorted_state)?;
+    Ok(to_hex(&Sha256::digest(content.as_bytes())))
+}
+
+#[cfg(feature = "gen_conf")]
+fn gen_keyfile_checksum(net_state: &NetworkState) -> Option<String> {
+    let confs = match net_state.gen_conf() {
+        Ok(c) => c,
+        Err(e) => {
+            log::debug!("Skipping keyfile checksum: {e}");
+            return None;
+        }
+    };
+    let mut backends: Vec<&String> = confs.keys().collect();
+    backends.sort_unstable();
+    let mut hasher = Sha256::new();
+    for backend in backends {
+        let mut files = confs[backend].clone();
+        files.sort_unstable();
+        for (file_name, content) in files {
+            for data in [backend.as_str(), file_name.as_str(), content.as_str()]
+            {
+                hasher.update(data.as_bytes());
+                hasher.update(b"\0");
+            }
+        }
+    }
+    Some(to_hex(&hasher.finalize()))
+}
+
+#[cfg(not(feature = "gen_conf"))]
+fn gen_keyfile_checksum(_net_state: &NetworkState) -> Option<String> {
+    None
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
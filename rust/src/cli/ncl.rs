@@ -4,16 +4,34 @@
 mod apply;
 #[cfg(feature = "query_apply")]
 mod autoconf;
+#[cfg(feature = "query_apply")]
+mod drift;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
 #[cfg(feature = "query_apply")]
+mod identity;
+#[cfg(feature = "query_apply")]
 mod policy;
 #[cfg(feature = "query_apply")]
 mod query;
+#[cfg(feature = "query_apply")]
+mod restore;
 mod result;
 #[cfg(feature = "query_apply")]
+mod self_test;
+#[cfg(feature = "query_apply")]
 mod service;
+#[cfg(feature = "query_apply")]
+mod stage;
+#[cfg(feature = "query_apply")]
+mod state_crypt;
+#[cfg(feature = "query_apply")]
+mod team;
+#[cfg(test)]
+mod unit_tests;
+#[cfg(feature = "query_apply")]
+mod validate;
 
 use env_logger::Builder;
 use log::LevelFilter;
@@ -24,18 +42,38 @@ use crate::apply::{
 };
 #[cfg(feature = "query_apply")]
 use crate::autoconf::autoconf;
+#[cfg(feature = "query_apply")]
+use crate::drift::drift;
 #[cfg(feature = "gen_conf")]
 use crate::gen_conf::gen_conf;
 #[cfg(feature = "query_apply")]
+use crate::identity::show_identity;
+#[cfg(feature = "query_apply")]
 use crate::policy::policy;
 #[cfg(feature = "query_apply")]
-use crate::query::show;
+use crate::query::{show, show_capabilities};
+#[cfg(feature = "query_apply")]
+use crate::restore::restore;
 use crate::result::print_result_and_exit;
 #[cfg(feature = "query_apply")]
+use crate::self_test::self_test;
+#[cfg(feature = "query_apply")]
 use crate::service::ncl_service;
+#[cfg(feature = "query_apply")]
+use crate::stage::activate;
+#[cfg(feature = "query_apply")]
+use crate::state_crypt::gen_host_key;
+#[cfg(feature = "query_apply")]
+use crate::team::team_to_bond;
+#[cfg(feature = "query_apply")]
+use crate::validate::validate;
 
 pub(crate) const DEFAULT_SERVICE_FOLDER: &str = "/etc/nmstate";
 pub(crate) const CONFIG_FOLDER_KEY: &str = "CONFIG_FOLDER";
+pub(crate) const SHOW_SECTION_ROUTES: &str = "routes";
+pub(crate) const SHOW_SECTION_DNS: &str = "dns";
+pub(crate) const SHOW_SECTION_HOSTNAME: &str = "hostname";
+pub(crate) const SHOW_SECTION_OVSDB: &str = "ovsdb";
 
 const APP_NAME: &str = "nmstatectl";
 
@@ -49,6 +87,14 @@ const SUB_CMD_VERSION: &str = "version";
 const SUB_CMD_AUTOCONF: &str = "autoconf";
 const SUB_CMD_SERVICE: &str = "service";
 const SUB_CMD_POLICY: &str = "policy";
+const SUB_CMD_SELF_TEST: &str = "self-test";
+const SUB_CMD_DRIFT: &str = "drift";
+const SUB_CMD_VALIDATE: &str = "validate";
+const SUB_CMD_TEAM_TO_BOND: &str = "team-to-bond";
+const SUB_CMD_MIGRATE_TEAM: &str = "migrate-team";
+const SUB_CMD_ACTIVATE: &str = "activate";
+const SUB_CMD_RESTORE: &str = "restore";
+const SUB_CMD_GEN_HOST_KEY: &str = "gen-host-key";
 
 fn main() {
     let argv: Vec<String> = std::env::args().collect();
@@ -97,19 +143,35 @@ fn main() {
                         .long("kernel")
                         .takes_value(false)
                         .help("Show kernel network state only")
+                        .global(true)
                 )
                 .arg(
                     clap::Arg::new("JSON")
                         .long("json")
                         .takes_value(false)
-                        .help("Show state in json format"),
+                        .help("Show state in json format")
+                        .global(true),
                 )
                 .arg(
                     clap::Arg::new("RUNNING_CONFIG_ONLY")
                         .short('r')
                         .long("running-config")
                         .takes_value(false)
-                        .help("Show running configuration only"),
+                        .help("Show running configuration only")
+                        .global(true),
+                )
+                .arg(
+                    clap::Arg::new("SAVED_CONFIG_ONLY")
+                        .long("saved")
+                        .takes_value(false)
+                        .conflicts_with_all(&[
+                            "KERNEL",
+                            "RUNNING_CONFIG_ONLY",
+                        ])
+                        .help(
+                            "Show configuration saved in NetworkManager \
+                            profiles instead of the live network state",
+                        ),
                 )
                 .arg(
                     clap::Arg::new("SHOW_SECRETS")
@@ -118,6 +180,107 @@ fn main() {
                         .takes_value(false)
                         .help("Show secrets(hide by default)"),
                 )
+                .arg(
+                    clap::Arg::new("PRESERVE_KERNEL_ORDER")
+                        .long("preserve-kernel-order")
+                        .takes_value(false)
+                        .help(
+                            "Show routes and route rules in the order \
+                            provided by kernel instead of sorting them",
+                        )
+                        .global(true),
+                )
+                .arg(
+                    clap::Arg::new("MINIMAL")
+                        .long("minimal")
+                        .takes_value(false)
+                        .conflicts_with("PRESERVE_KERNEL_ORDER")
+                        .help(
+                            "Omit status only properties and properties \
+                            holding the default value",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("FDB")
+                        .long("fdb")
+                        .takes_value(false)
+                        .conflicts_with("SAVED_CONFIG_ONLY")
+                        .help(
+                            "Include forwarding database entries of linux \
+                            bridges",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("NETNS")
+                        .long("netns")
+                        .takes_value(true)
+                        .value_name("NAME|PID")
+                        .conflicts_with("SAVED_CONFIG_ONLY")
+                        .help(
+                            "Show kernel network state of specified network \
+                            namespace by name or by PID of process inside it",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("IDENTITY")
+                        .long("identity")
+                        .takes_value(false)
+                        .conflicts_with_all(&[
+                            "IFNAME",
+                            "KERNEL",
+                            "RUNNING_CONFIG_ONLY",
+                            "SAVED_CONFIG_ONLY",
+                            "SHOW_SECRETS",
+                            "PRESERVE_KERNEL_ORDER",
+                            "MINIMAL",
+                            "FDB",
+                            "NETNS",
+                        ])
+                        .help(
+                            "Show checksum of the last applied network state",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("CAPABILITIES")
+                        .long("capabilities")
+                        .takes_value(false)
+                        .conflicts_with_all(&[
+                            "IFNAME",
+                            "KERNEL",
+                            "RUNNING_CONFIG_ONLY",
+                            "SAVED_CONFIG_ONLY",
+                            "SHOW_SECRETS",
+                            "PRESERVE_KERNEL_ORDER",
+                            "MINIMAL",
+                            "FDB",
+                            "NETNS",
+                            "IDENTITY",
+                        ])
+                        .help(
+                            "Show versions of nmstate and NetworkManager, \
+                            NetworkManager plugins and kernel features \
+                            available on this host",
+                        ),
+                )
+                .subcommand(
+                    clap::Command::new(SHOW_SECTION_ROUTES)
+                        .about("Show routes only")
+                )
+                .subcommand(
+                    clap::Command::new(SHOW_SECTION_DNS)
+                        .about("Show DNS resolver state only")
+                )
+                .subcommand(
+                    clap::Command::new(SHOW_SECTION_HOSTNAME)
+                        .about("Show hostname only")
+                )
+                .subcommand(
+                    clap::Command::new(SHOW_SECTION_OVSDB)
+                        .about(
+                            "Show global OpenvSwitch database configuration \
+                            only"
+                        )
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_APPLY)
@@ -154,6 +317,17 @@ fn main() {
                         "Do not commit new state after verification"
                       ),
                 )
+                .arg(
+                    clap::Arg::new("NO_CHECKPOINT")
+                        .long("no-checkpoint")
+                        .takes_value(false)
+                        .conflicts_with_all(&["KERNEL", "NO_COMMIT"])
+                        .help(
+                            "Apply without NetworkManager checkpoint for \
+                            NetworkManager built without checkpoint support. \
+                            WARNING: no rollback on failure",
+                        ),
+                )
                 .arg(
                     clap::Arg::new("TIMEOUT")
                       .long("timeout")
@@ -175,6 +349,122 @@ fn main() {
                         .takes_value(false)
                         .help("Do not make the state persistent"),
                 )
+                .arg(
+                    clap::Arg::new("IPV6_LINK_LOCAL_VERIFY")
+                        .long("ipv6-link-local-verify")
+                        .takes_value(true)
+                        .possible_values(["auto", "skip", "require"])
+                        .help(
+                            "How to verify the IPv6 link local addresses \
+                            generated by kernel",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("STAGE")
+                        .long("stage")
+                        .takes_value(false)
+                        .conflicts_with_all(&["KERNEL", "MEMORY_ONLY"])
+                        .help(
+                            "Only save the profiles, use \
+                            'nmstatectl activate' to activate them",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("ACTIVATE_AT")
+                        .long("at")
+                        .takes_value(true)
+                        .conflicts_with_all(&["KERNEL", "MEMORY_ONLY"])
+                        .help(
+                            "Save the profiles now and activate them at \
+                            specified time in systemd calendar event \
+                            format, e.g. '2026-10-20 02:00:00'",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("DRY_RUN")
+                        .long("dry-run")
+                        .takes_value(false)
+                        .conflicts_with_all(&["STAGE", "ACTIVATE_AT"])
+                        .help(
+                            "Do not apply, only show the planned changes \
+                            of each interface classified by disruption \
+                            risk: reapply, flap, reactivate or delete",
+                        ),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_ACTIVATE)
+                .about("Activate network states staged by 'apply --stage'")
+                .arg(
+                    clap::Arg::new("INDEX")
+                        .long("index")
+                        .takes_value(true)
+                        .help(
+                            "Only activate the staged state of specified \
+                            index, used by the timer of 'apply --at'",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("NO_VERIFY")
+                        .long("no-verify")
+                        .takes_value(false)
+                        .help(
+                            "Do not verify that the state was completely set \
+                            and disable rollback to previous state.",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("TIMEOUT")
+                      .long("timeout")
+                      .takes_value(true)
+                      .help(
+                        "Timeout in seconds before reverting uncommited changes."
+                      ),
+                )
+                .arg(
+                    clap::Arg::new("SHOW_SECRETS")
+                        .short('s')
+                        .long("show-secrets")
+                        .takes_value(false)
+                        .help("Show secrets(hide by default)"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_RESTORE)
+                .about(
+                    "Apply the last known good network state stored after \
+                    successful apply",
+                )
+                .arg(
+                    clap::Arg::new("PREVIOUS")
+                        .long("previous")
+                        .takes_value(false)
+                        .help("Apply the known good state before the last one"),
+                )
+                .arg(
+                    clap::Arg::new("NO_VERIFY")
+                        .long("no-verify")
+                        .takes_value(false)
+                        .help(
+                            "Do not verify that the state was completely set \
+                            and disable rollback to previous state.",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("TIMEOUT")
+                      .long("timeout")
+                      .takes_value(true)
+                      .help(
+                        "Timeout in seconds before reverting uncommited changes."
+                      ),
+                )
+                .arg(
+                    clap::Arg::new("SHOW_SECRETS")
+                        .short('s')
+                        .long("show-secrets")
+                        .takes_value(false)
+                        .help("Show secrets(hide by default)"),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_GEN_CONF)
@@ -184,6 +474,27 @@ fn main() {
                         .required(true)
                         .index(1)
                         .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("EARLY_BOOT")
+                        .long("early-boot")
+                        .takes_value(false)
+                        .help(
+                            "Also generate a script using `ip` command and a \
+                            systemd unit applying the state before \
+                            NetworkManager starts",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("INSTALL")
+                        .long("install")
+                        .takes_value(false)
+                        .requires("EARLY_BOOT")
+                        .help(
+                            "Install the early boot script as executable \
+                            to /usr/libexec and the systemd unit to \
+                            /etc/systemd/system",
+                        ),
                 ),
         )
         .subcommand(
@@ -300,6 +611,157 @@ fn main() {
                         .help("Show state in json format"),
                 )
         )
+        .subcommand(
+            clap::Command::new(SUB_CMD_SELF_TEST)
+                .about(
+                    "Validate nmstate and NetworkManager on this host by \
+                    applying, verifying and rolling back test interfaces",
+                )
+                .arg(
+                    clap::Arg::new("TOPOLOGY")
+                        .required(false)
+                        .multiple_occurrences(true)
+                        .index(1)
+                        .help(
+                            "Test specified topologies only: dummy, veth, \
+                            bond, netdevsim",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show result in json format"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_DRIFT)
+                .about(
+                    "Show the difference between live network state and \
+                    configuration saved in NetworkManager profiles",
+                )
+                .arg(
+                    clap::Arg::new("PERSIST")
+                        .long("persist")
+                        .takes_value(false)
+                        .conflicts_with("REVERT")
+                        .help("Save the live network state to profiles"),
+                )
+                .arg(
+                    clap::Arg::new("REVERT")
+                        .long("revert")
+                        .takes_value(false)
+                        .help(
+                            "Restore the live network state to saved \
+                            configuration",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show difference in json format"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_VALIDATE)
+                .about(
+                    "Check network state against validation profile, \
+                    for example configurations incompatible with IPv6-only \
+                    network or preventing OVS-DPDK from using the NICs",
+                )
+                .arg(
+                    clap::Arg::new("STATE_FILE")
+                        .required(false)
+                        .index(1)
+                        .help(
+                            "Network state file, use running network \
+                            configuration if not defined",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("PROFILE")
+                        .long("profile")
+                        .takes_value(true)
+                        .required(true)
+                        .possible_values([
+                            "ipv6-only",
+                            "dual-stack",
+                            "dpdk-ready",
+                        ])
+                        .help("Validation profile"),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show violations in json format"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_TEAM_TO_BOND)
+                .about(
+                    "Show the bond configuration equivalent to \
+                    NetworkManager team profiles",
+                )
+                .arg(
+                    clap::Arg::new("APPLY")
+                        .long("apply")
+                        .takes_value(false)
+                        .help(
+                            "Replace team with bond within single \
+                            checkpoint",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("NO_VERIFY")
+                        .long("no-verify")
+                        .takes_value(false)
+                        .requires("APPLY")
+                        .help(
+                            "Do not verify that the state was completely set \
+                            and disable rollback to previous state.",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show result in json format"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_MIGRATE_TEAM)
+                .about("Alias of `team-to-bond --apply`")
+                .arg(
+                    clap::Arg::new("NO_VERIFY")
+                        .long("no-verify")
+                        .takes_value(false)
+                        .help(
+                            "Do not verify that the state was completely set \
+                            and disable rollback to previous state.",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show applied bond configuration in json format"),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_GEN_HOST_KEY)
+                .about(
+                    "Generate host key for encrypting the network states \
+                    stored by restore, apply --stage and service",
+                )
+                .arg(
+                    clap::Arg::new("FORCE")
+                        .long("force")
+                        .takes_value(false)
+                        .help("Overwrite existing host key"),
+                )
+        )
         .subcommand(
             clap::Command::new(SUB_CMD_VERSION)
             .about("Show version")
@@ -325,9 +787,19 @@ fn main() {
 
     if let Some(matches) = matches.subcommand_matches(SUB_CMD_GEN_CONF) {
         if let Some(file_path) = matches.value_of("STATE_FILE") {
-            print_result_and_exit(gen_conf(file_path));
+            print_result_and_exit(gen_conf(
+                file_path,
+                matches.is_present("EARLY_BOOT"),
+                matches.is_present("INSTALL"),
+            ));
         }
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SHOW) {
+        if matches.is_present("IDENTITY") {
+            print_result_and_exit(show_identity(matches));
+        }
+        if matches.is_present("CAPABILITIES") {
+            print_result_and_exit(show_capabilities(matches));
+        }
         print_result_and_exit(show(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_APPLY) {
         if argv.get(1) == Some(&"set".to_string()) {
@@ -362,6 +834,32 @@ fn main() {
         print_result_and_exit(ncl_service(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_POLICY) {
         print_result_and_exit(policy(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SELF_TEST)
+    {
+        print_result_and_exit(self_test(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_DRIFT) {
+        print_result_and_exit(drift(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_VALIDATE) {
+        print_result_and_exit(validate(matches));
+    } else if let Some(matches) =
+        matches.subcommand_matches(SUB_CMD_TEAM_TO_BOND)
+    {
+        print_result_and_exit(team_to_bond(
+            matches,
+            matches.is_present("APPLY"),
+        ));
+    } else if let Some(matches) =
+        matches.subcommand_matches(SUB_CMD_MIGRATE_TEAM)
+    {
+        print_result_and_exit(team_to_bond(matches, true));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_ACTIVATE) {
+        print_result_and_exit(activate(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_RESTORE) {
+        print_result_and_exit(restore(matches));
+    } else if let Some(matches) =
+        matches.subcommand_matches(SUB_CMD_GEN_HOST_KEY)
+    {
+        print_result_and_exit(gen_host_key(matches));
     } else if matches.subcommand_matches(SUB_CMD_VERSION).is_some() {
         print_result_and_exit(Ok(format!(
             "{} {}",
@@ -372,7 +870,11 @@ fn main() {
 }
 
 #[cfg(not(feature = "gen_conf"))]
-fn gen_conf(_file_path: &str) -> Result<String, crate::error::CliError> {
+fn gen_conf(
+    _file_path: &str,
+    _early_boot: bool,
+    _install: bool,
+) -> Result<String, crate::error::CliError> {
     Err("The gc sub-command require `gen_conf` feature been \
         enabled during compiling"
         .into())
@@ -385,6 +887,24 @@ fn show(_matches: &clap::ArgMatches) -> Result<String, crate::error::CliError> {
         .into())
 }
 
+#[cfg(not(feature = "query_apply"))]
+fn show_identity(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err("The show sub-command require `query_apply` feature been \
+        enabled during compiling"
+        .into())
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn show_capabilities(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err("The show sub-command require `query_apply` feature been \
+        enabled during compiling"
+        .into())
+}
+
 #[cfg(not(feature = "query_apply"))]
 fn apply_from_stdin(
     _matches: &clap::ArgMatches,
@@ -457,3 +977,79 @@ fn policy(
         enabled during compiling"
         .into())
 }
+
+#[cfg(not(feature = "query_apply"))]
+fn self_test(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The self-test sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn drift(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err("The drift sub-command require `query_apply` feature been \
+        enabled during compiling"
+        .into())
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn validate(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The validate sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn team_to_bond(
+    _matches: &clap::ArgMatches,
+    _apply: bool,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The team-to-bond sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn activate(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The activate sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn restore(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The restore sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn gen_host_key(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The gen-host-key sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
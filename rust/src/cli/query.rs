@@ -1,13 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nmstate::{
-    DnsState, HostNameState, NetworkState, OvsDbGlobalConfig, RouteRules,
-    Routes,
+    ConnectivityState, DnsState, HostNameState, NetworkManagerConfig,
+    NetworkState, NetworkStateSection, OvnConfiguration, OvsDbGlobalConfig,
+    PciDeviceConfig, RouteRules, Routes,
 };
 use serde::Serialize;
 use serde_yaml::Value;
 
 use crate::error::CliError;
+use crate::{
+    SHOW_SECTION_DNS, SHOW_SECTION_HOSTNAME, SHOW_SECTION_OVSDB,
+    SHOW_SECTION_ROUTES,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub(crate) struct SortedNetworkState {
@@ -21,12 +26,23 @@ pub(crate) struct SortedNetworkState {
     interfaces: Vec<Value>,
     #[serde(rename = "ovs-db")]
     ovsdb: OvsDbGlobalConfig,
+    #[serde(skip_serializing_if = "OvnConfiguration::is_none")]
+    ovn: OvnConfiguration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connectivity: Option<ConnectivityState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    networkmanager: Option<NetworkManagerConfig>,
+    #[serde(rename = "pci-devices", skip_serializing_if = "Option::is_none")]
+    pci_devices: Option<Vec<PciDeviceConfig>>,
 }
 
 const IFACE_TOP_PRIORTIES: [&str; 2] = ["name", "type"];
 
 // Ordering the outputs
 pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    if let Some((section, matches)) = matches.subcommand() {
+        return show_section(section, matches);
+    }
     let mut net_state = NetworkState::new();
     if matches.is_present("KERNEL") {
         net_state.set_kernel_only(true);
@@ -34,11 +50,24 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
     if matches.is_present("RUNNING_CONFIG_ONLY") {
         net_state.set_running_config_only(true);
     }
+    if matches.is_present("SAVED_CONFIG_ONLY") {
+        net_state.set_saved_config_only(true);
+    }
     net_state.set_include_secrets(matches.is_present("SHOW_SECRETS"));
+    net_state
+        .set_preserve_kernel_order(matches.is_present("PRESERVE_KERNEL_ORDER"));
+    net_state.set_minimal(matches.is_present("MINIMAL"));
+    net_state.set_include_fdb(matches.is_present("FDB"));
+    if let Some(netns) = matches.value_of("NETNS") {
+        net_state.set_kernel_only(true);
+        net_state.set_netns(Some(netns));
+    }
     net_state.retrieve()?;
     Ok(if let Some(ifname) = matches.value_of("IFNAME") {
         let mut new_net_state = filter_net_state_with_iface(&net_state, ifname);
-        new_net_state.set_kernel_only(matches.is_present("KERNEL"));
+        new_net_state.set_kernel_only(
+            matches.is_present("KERNEL") || matches.is_present("NETNS"),
+        );
         if matches.is_present("JSON") {
             serde_json::to_string_pretty(&new_net_state)?
         } else {
@@ -51,6 +80,64 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
     })
 }
 
+// Show the features supported by this host, so support engineers can compare
+// hosts from single command output.
+pub(crate) fn show_capabilities(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let caps = nmstate::capabilities()?;
+    Ok(if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&caps)?
+    } else {
+        serde_yaml::to_string(&caps)?
+    })
+}
+
+// Only retrieve and print the specified section, skipping the expensive
+// enumeration of interfaces.
+fn show_section(
+    section: &str,
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let mut net_state = NetworkState::new();
+    net_state.set_kernel_only(matches.is_present("KERNEL"));
+    net_state
+        .set_running_config_only(matches.is_present("RUNNING_CONFIG_ONLY"));
+    net_state
+        .set_preserve_kernel_order(matches.is_present("PRESERVE_KERNEL_ORDER"));
+
+    let (key, value) = match section {
+        SHOW_SECTION_ROUTES => {
+            net_state.retrieve_section(NetworkStateSection::Routes)?;
+            ("routes", serde_yaml::to_value(&net_state.routes)?)
+        }
+        SHOW_SECTION_DNS => {
+            net_state.retrieve_section(NetworkStateSection::Dns)?;
+            ("dns-resolver", serde_yaml::to_value(&net_state.dns)?)
+        }
+        SHOW_SECTION_HOSTNAME => {
+            net_state.retrieve_section(NetworkStateSection::HostName)?;
+            ("hostname", serde_yaml::to_value(&net_state.hostname)?)
+        }
+        SHOW_SECTION_OVSDB => {
+            net_state.retrieve_section(NetworkStateSection::OvsDb)?;
+            ("ovs-db", serde_yaml::to_value(&net_state.ovsdb)?)
+        }
+        _ => {
+            return Err(CliError::from(format!(
+                "Unsupported section {section}"
+            )));
+        }
+    };
+    let mut output = serde_yaml::Mapping::new();
+    output.insert(Value::String(key.to_string()), value);
+    Ok(if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&output)?
+    } else {
+        serde_yaml::to_string(&output)?
+    })
+}
+
 pub(crate) fn sort_netstate(
     net_state: NetworkState,
 ) -> Result<SortedNetworkState, CliError> {
@@ -91,6 +178,10 @@ pub(crate) fn sort_netstate(
             rules: net_state.rules,
             dns: net_state.dns,
             ovsdb: net_state.ovsdb,
+            ovn: net_state.ovn,
+            connectivity: net_state.connectivity,
+            networkmanager: net_state.networkmanager,
+            pci_devices: net_state.pci_devices,
         });
     }
 
@@ -101,6 +192,10 @@ pub(crate) fn sort_netstate(
         rules: net_state.rules,
         dns: net_state.dns,
         ovsdb: net_state.ovsdb,
+        ovn: net_state.ovn,
+        connectivity: net_state.connectivity,
+        networkmanager: net_state.networkmanager,
+        pci_devices: net_state.pci_devices,
     })
 }
 
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use nmstate::NetworkState;
+
+use crate::{
+    apply::apply,
+    error::CliError,
+    state_crypt::{encrypt_state, read_state_file},
+};
+
+const LAST_GOOD_FOLDER: &str = "/var/lib/nmstate";
+const LAST_GOOD_FILE_NAME: &str = "last_good_state.yml";
+const PREVIOUS_GOOD_FILE_NAME: &str = "previous_good_state.yml";
+
+// Snapshot the full running network state after successful apply, the
+// former snapshot is kept as previous one. Failure is only logged as warning,
+// as applied state is already in effect.
+pub(crate) fn store_last_good_state() {
+    if let Err(e) = _store_last_good_state() {
+        log::warn!("Failed to store last known good network state: {e}");
+    }
+}
+
+fn _store_last_good_state() -> Result<(), CliError> {
+    let mut net_state = NetworkState::new();
+    net_state.set_running_config_only(true);
+    net_state.set_include_secrets(true);
+    net_state.retrieve()?;
+    let content = serde_yaml::to_string(&net_state)?;
+
+    std::fs::create_dir_all(LAST_GOOD_FOLDER)?;
+    let last_good_path = last_good_file_path(false);
+    if last_good_path.exists() {
+        // Restoring the last good state should not overwrite the previous
+        // one.
+        if read_state_file(&last_good_path)? == content.as_bytes() {
+            return Ok(());
+        }
+        std::fs::rename(&last_good_path, last_good_file_path(true))?;
+    }
+    // Holding secrets, hence only readable by owner.
+    let mut fd = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&last_good_path)?;
+    fd.write_all(&encrypt_state(&content)?)?;
+    log::info!(
+        "Stored last known good network state to {}",
+        last_good_path.display()
+    );
+    Ok(())
+}
+
+pub(crate) fn restore(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let file_path = last_good_file_path(matches.is_present("PREVIOUS"));
+    if !file_path.exists() {
+        return Err(CliError::from(format!(
+            "No known good network state found in {}, please apply \
+            network state via nmstatectl first",
+            file_path.display()
+        )));
+    }
+    log::info!("Restoring network state from {}", file_path.display());
+    apply(&mut read_state_file(&file_path)?.as_slice(), matches)
+}
+
+fn last_good_file_path(previous: bool) -> PathBuf {
+    Path::new(LAST_GOOD_FOLDER).join(if previous {
+        PREVIOUS_GOOD_FILE_NAME
+    } else {
+        LAST_GOOD_FILE_NAME
+    })
+}
@@ -0,0 +1,488 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use nmstate::{InterfaceType, NetworkState};
+use serde::Serialize;
+
+use crate::error::CliError;
+
+const SELF_TEST_TIMEOUT: u32 = 30;
+
+const NETDEVSIM_NEW_DEVICE_PATH: &str = "/sys/bus/netdevsim/new_device";
+const NETDEVSIM_DEL_DEVICE_PATH: &str = "/sys/bus/netdevsim/del_device";
+const NETDEVSIM_DEVICES_FOLDER: &str = "/sys/bus/netdevsim/devices";
+const NETDEVSIM_DEVICE_ID: u32 = 9527;
+const NETDEVSIM_WAIT_COUNT: u32 = 50;
+const NETDEVSIM_WAIT_INTERVAL_MILLISECONDS: u64 = 100;
+
+const TOPOLOGIES: [SelfTestTopology; 4] = [
+    SelfTestTopology::Dummy,
+    SelfTestTopology::Veth,
+    SelfTestTopology::Bond,
+    SelfTestTopology::Netdevsim,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SelfTestTopology {
+    Dummy,
+    Veth,
+    Bond,
+    Netdevsim,
+}
+
+impl std::fmt::Display for SelfTestTopology {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Dummy => "dummy",
+                Self::Veth => "veth",
+                Self::Bond => "bond",
+                Self::Netdevsim => "netdevsim",
+            }
+        )
+    }
+}
+
+impl SelfTestTopology {
+    // Return the reason when host does not support this topology
+    fn skip_reason(&self) -> Option<String> {
+        match self {
+            Self::Veth => match nmstate::capabilities() {
+                Ok(caps) if caps.veth => None,
+                Ok(_) => Some(
+                    "NetworkManager does not support veth interface"
+                        .to_string(),
+                ),
+                Err(e) => Some(e.to_string()),
+            },
+            Self::Netdevsim => {
+                if !Path::new(NETDEVSIM_NEW_DEVICE_PATH).exists() {
+                    // Try to load the kernel module, failure is reported
+                    // as skip below.
+                    std::process::Command::new("modprobe")
+                        .arg("netdevsim")
+                        .output()
+                        .ok();
+                }
+                if Path::new(NETDEVSIM_NEW_DEVICE_PATH).exists() {
+                    None
+                } else {
+                    Some("Kernel module netdevsim is not loaded".to_string())
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // Create the kernel devices not managed by nmstate and return the
+    // desired state
+    fn setup(&self) -> Result<NetworkState, CliError> {
+        let yaml = match self {
+            Self::Dummy => r#"---
+                interfaces:
+                - name: nmst-dummy0
+                  type: dummy
+                  state: up
+                  ipv4:
+                    enabled: true
+                    dhcp: false
+                    address:
+                    - ip: 192.0.2.251
+                      prefix-length: 24
+                  ipv6:
+                    enabled: false
+                "#
+            .to_string(),
+            Self::Veth => r#"---
+                interfaces:
+                - name: nmst-veth0
+                  type: veth
+                  state: up
+                  veth:
+                    peer: nmst-veth0p
+                  ipv4:
+                    enabled: true
+                    dhcp: false
+                    address:
+                    - ip: 192.0.2.252
+                      prefix-length: 24
+                  ipv6:
+                    enabled: false
+                "#
+            .to_string(),
+            Self::Bond => r#"---
+                interfaces:
+                - name: nmst-bport0
+                  type: dummy
+                  state: up
+                - name: nmst-bport1
+                  type: dummy
+                  state: up
+                - name: nmst-bond0
+                  type: bond
+                  state: up
+                  link-aggregation:
+                    mode: active-backup
+                    port:
+                    - nmst-bport0
+                    - nmst-bport1
+                  ipv4:
+                    enabled: true
+                    dhcp: false
+                    address:
+                    - ip: 192.0.2.253
+                      prefix-length: 24
+                  ipv6:
+                    enabled: false
+                "#
+            .to_string(),
+            Self::Netdevsim => {
+                let iface_name = create_netdevsim()?;
+                format!(
+                    r#"---
+                    interfaces:
+                    - name: {iface_name}
+                      type: ethernet
+                      state: up
+                      mtu: 1400
+                      ipv4:
+                        enabled: true
+                        dhcp: false
+                        address:
+                        - ip: 192.0.2.254
+                          prefix-length: 24
+                      ipv6:
+                        enabled: false
+                    "#
+                )
+            }
+        };
+        Ok(serde_yaml::from_str(&yaml)?)
+    }
+
+    fn teardown(&self, desired: Option<&NetworkState>) -> Result<(), CliError> {
+        let mut errors = Vec::new();
+        if let Some(desired) = desired {
+            if let Err(e) = gen_absent_state(desired).apply() {
+                errors.push(e.to_string());
+            }
+        }
+        if self == &Self::Netdevsim {
+            if let Err(e) = remove_netdevsim() {
+                errors.push(e.to_string());
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; ").into())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum SelfTestStatus {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Default for SelfTestStatus {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+impl std::fmt::Display for SelfTestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Pass => "pass",
+                Self::Fail => "fail",
+                Self::Skip => "skip",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct SelfTestResult {
+    topology: String,
+    apply: SelfTestStatus,
+    verify: SelfTestStatus,
+    rollback: SelfTestStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    messages: Vec<String>,
+}
+
+impl SelfTestResult {
+    fn new(topology: SelfTestTopology) -> Self {
+        Self {
+            topology: topology.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn is_failed(&self) -> bool {
+        [self.apply, self.verify, self.rollback].contains(&SelfTestStatus::Fail)
+    }
+
+    fn record(
+        &mut self,
+        phase: &str,
+        result: Result<(), CliError>,
+    ) -> SelfTestStatus {
+        match result {
+            Ok(()) => SelfTestStatus::Pass,
+            Err(e) => {
+                self.messages.push(format!("{phase}: {e}"));
+                SelfTestStatus::Fail
+            }
+        }
+    }
+}
+
+pub(crate) fn self_test(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let selected: Vec<&str> = matches
+        .values_of("TOPOLOGY")
+        .map(|v| v.collect())
+        .unwrap_or_default();
+    for name in selected.as_slice() {
+        if !TOPOLOGIES.iter().any(|t| &t.to_string() == name) {
+            return Err(format!(
+                "Unknown topology {name}, supported: {}",
+                TOPOLOGIES
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+            .into());
+        }
+    }
+
+    let mut results = Vec::new();
+    for topology in TOPOLOGIES.iter().filter(|t| {
+        selected.is_empty() || selected.contains(&t.to_string().as_str())
+    }) {
+        log::info!("Testing topology {topology}");
+        let result = run_topology(*topology);
+        if result.is_failed() {
+            log::warn!("Topology {topology} failed");
+        } else {
+            log::info!("Topology {topology} done");
+        }
+        results.push(result);
+    }
+
+    let output = if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&results)?
+    } else {
+        gen_matrix(results.as_slice())
+    };
+    if results.iter().any(|r| r.is_failed()) {
+        Err(output.into())
+    } else {
+        Ok(output)
+    }
+}
+
+fn run_topology(topology: SelfTestTopology) -> SelfTestResult {
+    let mut result = SelfTestResult::new(topology);
+    if let Some(reason) = topology.skip_reason() {
+        result.messages.push(reason);
+        return result;
+    }
+    let desired = match topology.setup() {
+        Ok(d) => d,
+        Err(e) => {
+            result.messages.push(format!("setup: {e}"));
+            result.apply = SelfTestStatus::Fail;
+            topology.teardown(None).ok();
+            return result;
+        }
+    };
+
+    result.apply = result.record("apply", apply_state(&desired));
+    if result.apply == SelfTestStatus::Pass {
+        result.verify = result.record("verify", verify_state(&desired));
+        result.rollback = result.record("rollback", check_rollback(&desired));
+    }
+
+    if let Err(e) = topology.teardown(Some(&desired)) {
+        result.messages.push(format!("teardown: {e}"));
+    }
+    result
+}
+
+fn apply_state(desired: &NetworkState) -> Result<(), CliError> {
+    let mut desired = desired.clone();
+    desired.set_timeout(SELF_TEST_TIMEOUT);
+    Ok(desired.apply()?)
+}
+
+// Query current network state and check whether desired interfaces are up
+// with desired IPv4 addresses.
+fn verify_state(desired: &NetworkState) -> Result<(), CliError> {
+    let mut current = NetworkState::new();
+    current.retrieve()?;
+    for des_iface in desired.interfaces.to_vec() {
+        let cur_iface = match current
+            .interfaces
+            .get_iface(des_iface.name(), InterfaceType::Unknown)
+        {
+            Some(i) => i,
+            None => {
+                return Err(format!(
+                    "Interface {} not found in current state",
+                    des_iface.name()
+                )
+                .into());
+            }
+        };
+        if !cur_iface.is_up() {
+            return Err(
+                format!("Interface {} is not up", des_iface.name()).into()
+            );
+        }
+        let cur_addrs = cur_iface
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.addresses.as_deref())
+            .unwrap_or_default();
+        for des_addr in des_iface
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.addresses.as_deref())
+            .unwrap_or_default()
+        {
+            if !cur_addrs.iter().any(|a| {
+                a.ip == des_addr.ip && a.prefix_length == des_addr.prefix_length
+            }) {
+                return Err(format!(
+                    "Interface {} has no IPv4 address {}/{}",
+                    des_iface.name(),
+                    des_addr.ip,
+                    des_addr.prefix_length
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Remove the interfaces without commit, then rollback the checkpoint and
+// check whether the interfaces are restored.
+fn check_rollback(desired: &NetworkState) -> Result<(), CliError> {
+    let mut absent_state = gen_absent_state(desired);
+    absent_state.set_commit(false);
+    absent_state.set_timeout(SELF_TEST_TIMEOUT);
+    match absent_state.apply_and_get_checkpoint()? {
+        Some(checkpoint) => NetworkState::checkpoint_rollback(&checkpoint)?,
+        None => {
+            return Err("No checkpoint created for uncommitted change".into())
+        }
+    }
+    verify_state(desired)
+}
+
+fn gen_absent_state(desired: &NetworkState) -> NetworkState {
+    let mut state = NetworkState::new();
+    for iface in desired.interfaces.to_vec() {
+        let mut iface = iface.clone();
+        iface.base_iface_mut().state = nmstate::InterfaceState::Absent;
+        state.append_interface_data(iface);
+    }
+    state
+}
+
+fn gen_matrix(results: &[SelfTestResult]) -> String {
+    let mut lines = vec![format!(
+        "{:<12}{:<8}{:<8}{:<8}",
+        "TOPOLOGY", "APPLY", "VERIFY", "ROLLBACK"
+    )];
+    for result in results {
+        lines.push(format!(
+            "{:<12}{:<8}{:<8}{:<8}",
+            result.topology,
+            result.apply.to_string(),
+            result.verify.to_string(),
+            result.rollback.to_string()
+        ));
+    }
+    for result in results.iter().filter(|r| !r.messages.is_empty()) {
+        lines.push(String::new());
+        lines.push(format!("{}:", result.topology));
+        for msg in result.messages.as_slice() {
+            lines.push(format!("  {msg}"));
+        }
+    }
+    lines
+        .iter()
+        .map(|l| l.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+// Create netdevsim device with single port and return its interface name
+fn create_netdevsim() -> Result<String, CliError> {
+    let dev_folder = format!(
+        "{NETDEVSIM_DEVICES_FOLDER}/netdevsim{NETDEVSIM_DEVICE_ID}/net"
+    );
+    if Path::new(&dev_folder).exists() {
+        return Err(format!(
+            "netdevsim device {NETDEVSIM_DEVICE_ID} already exists"
+        )
+        .into());
+    }
+    std::fs::write(
+        NETDEVSIM_NEW_DEVICE_PATH,
+        format!("{NETDEVSIM_DEVICE_ID} 1"),
+    )?;
+    // The interface might be renamed by udev, wait its name to settle down
+    let mut iface_name = String::new();
+    for _ in 0..NETDEVSIM_WAIT_COUNT {
+        std::thread::sleep(std::time::Duration::from_millis(
+            NETDEVSIM_WAIT_INTERVAL_MILLISECONDS,
+        ));
+        if let Some(name) = std::fs::read_dir(&dev_folder)
+            .ok()
+            .and_then(|mut d| d.next())
+            .and_then(|e| e.ok())
+            .and_then(|e| e.file_name().into_string().ok())
+        {
+            if name == iface_name {
+                return Ok(name);
+            }
+            iface_name = name;
+        }
+    }
+    if iface_name.is_empty() {
+        Err(format!(
+            "Timeout on waiting interface of netdevsim device \
+            {NETDEVSIM_DEVICE_ID}"
+        )
+        .into())
+    } else {
+        Ok(iface_name)
+    }
+}
+
+fn remove_netdevsim() -> Result<(), CliError> {
+    Ok(std::fs::write(
+        NETDEVSIM_DEL_DEVICE_PATH,
+        NETDEVSIM_DEVICE_ID.to_string(),
+    )?)
+}
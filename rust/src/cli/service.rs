@@ -1,9 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::ffi::OsStr;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
 use std::path::{Path, PathBuf};
 
-use crate::{apply::apply, error::CliError};
+use crate::{
+    apply::apply,
+    error::CliError,
+    state_crypt::{
+        encrypt_state, is_encrypted_state_file, is_encryption_enabled,
+        read_state_file,
+    },
+};
 
 const CONFIG_FILE_EXTENTION: &str = "yml";
 const RELOCATE_FILE_EXTENTION: &str = "applied";
@@ -30,8 +39,8 @@ pub(crate) fn ncl_service(
     std::thread::sleep(std::time::Duration::from_secs(2));
 
     for file_path in config_files {
-        let mut fd = match std::fs::File::open(&file_path) {
-            Ok(fd) => fd,
+        let content = match read_state_file(&file_path) {
+            Ok(c) => c,
             Err(e) => {
                 log::error!(
                     "Failed to read config file {}: {e}",
@@ -40,7 +49,7 @@ pub(crate) fn ncl_service(
                 continue;
             }
         };
-        match apply(&mut fd, matches) {
+        match apply(&mut content.as_slice(), matches) {
             Ok(_) => {
                 log::info!("Applied nmstate config: {}", file_path.display());
                 if let Err(e) = relocate_file(&file_path) {
@@ -78,10 +87,23 @@ fn get_config_files(folder: &str) -> Result<Vec<PathBuf>, CliError> {
     Ok(ret)
 }
 
-// rename file by adding a suffix `.applied`.
+// rename file by adding a suffix `.applied`. When host key exists, the
+// plain text state file is encrypted as it might hold secrets.
 fn relocate_file(file_path: &Path) -> Result<(), CliError> {
     let new_path = file_path.with_extension(RELOCATE_FILE_EXTENTION);
-    std::fs::rename(file_path, &new_path)?;
+    if is_encryption_enabled() && !is_encrypted_state_file(file_path) {
+        let content = std::fs::read_to_string(file_path)?;
+        let mut fd = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&new_path)?;
+        fd.write_all(&encrypt_state(&content)?)?;
+        std::fs::remove_file(file_path)?;
+    } else {
+        std::fs::rename(file_path, &new_path)?;
+    }
     log::info!(
         "Renamed applied config {} to {}",
         file_path.display(),
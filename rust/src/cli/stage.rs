@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::OsStr;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use nmstate::NetworkState;
+
+use crate::{
+    apply::apply,
+    error::CliError,
+    state_crypt::{encrypt_state, read_state_file},
+};
+
+const STAGED_STATE_FOLDER: &str = "/var/lib/nmstate/staged";
+const STAGED_STATE_FILE_EXTENTION: &str = "yml";
+const ACTIVATION_UNIT_NAME: &str = "nmstate-activate";
+
+// Store the state staged by `nmstatectl apply --stage`, the file holds
+// secrets, hence only readable by owner. Return the index of staged state.
+pub(crate) fn store_staged_state(
+    net_state: &NetworkState,
+) -> Result<u32, CliError> {
+    std::fs::create_dir_all(STAGED_STATE_FOLDER)?;
+    // Activated files are removed, hence use the biggest index plus one
+    let index = get_staged_files(Path::new(STAGED_STATE_FOLDER))?
+        .iter()
+        .filter_map(|f| staged_file_index(f))
+        .max()
+        .unwrap_or_default()
+        + 1;
+    let file_path = Path::new(STAGED_STATE_FOLDER)
+        .join(format!("{index:04}.{STAGED_STATE_FILE_EXTENTION}"));
+    let mut fd = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&file_path)?;
+    fd.write_all(&encrypt_state(&serde_yaml::to_string(net_state)?)?)?;
+    log::info!("Stored staged state to {}", file_path.display());
+    Ok(index)
+}
+
+// Apply staged states in the order of staging, each with its own
+// checkpoint. When `--index` is specified, only the staged state of that
+// index is applied, which is used by the activation timer so timers of
+// other staged states are not preempted. Staged state is removed once
+// applied, the remaining ones are kept on failure.
+pub(crate) fn activate(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let index = match matches.try_get_one::<String>("INDEX") {
+        Ok(Some(i)) => Some(i.parse::<u32>().map_err(|e| CliError {
+            code: crate::error::EX_DATAERR,
+            error_msg: format!("Invalid staged state index '{i}': {e}"),
+        })?),
+        _ => None,
+    };
+    let staged_files = filter_staged_files(
+        get_staged_files(Path::new(STAGED_STATE_FOLDER))?,
+        index,
+    );
+    if staged_files.is_empty() {
+        return Err(CliError::from(if let Some(index) = index {
+            format!(
+                "No staged state of index {index} found in \
+                {STAGED_STATE_FOLDER}, it might be activated already"
+            )
+        } else {
+            format!(
+                "No staged state found in {STAGED_STATE_FOLDER}, please use \
+                'nmstatectl apply --stage' first"
+            )
+        }));
+    }
+    let mut ret = String::new();
+    for file_path in staged_files {
+        ret += &apply(&mut read_state_file(&file_path)?.as_slice(), matches)?;
+        log::info!("Activated staged state {}", file_path.display());
+        std::fs::remove_file(&file_path)?;
+    }
+    Ok(ret)
+}
+
+// Check the activation time is valid systemd calendar event before anything
+// been staged.
+pub(crate) fn validate_activation_time(time: &str) -> Result<(), CliError> {
+    let output = Command::new("systemd-analyze")
+        .arg("calendar")
+        .arg(time)
+        .output()
+        .map_err(|e| {
+            CliError::from(format!("Failed to invoke systemd-analyze: {e}"))
+        })?;
+    if !output.status.success() {
+        return Err(CliError {
+            code: crate::error::EX_DATAERR,
+            error_msg: format!(
+                "Invalid activation time '{time}', expecting systemd \
+                calendar event format, e.g. '2026-10-20 02:00:00': {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+    Ok(())
+}
+
+// Create transient systemd timer invoking `nmstatectl activate --index
+// <INDEX>` at specified time in the format of systemd calendar event, for
+// example `2026-10-20 02:00:00`. The unit name is suffixed by the index of
+// staged state, so staging again will not conflict with the pending timer.
+pub(crate) fn arm_activation_timer(
+    time: &str,
+    index: u32,
+) -> Result<(), CliError> {
+    let unit_name = activation_unit_name(index);
+    let nmstatectl = std::env::current_exe()?;
+    let output = Command::new("systemd-run")
+        .args(gen_activation_timer_args(time, index))
+        .arg(nmstatectl)
+        .args(gen_activation_cmd_args(index))
+        .output()
+        .map_err(|e| {
+            CliError::from(format!("Failed to invoke systemd-run: {e}"))
+        })?;
+    if !output.status.success() {
+        return Err(CliError::from(format!(
+            "Failed to create {unit_name}.timer for activating \
+            at {time}: {}. The staged state could be activated by \
+            'nmstatectl activate --index {index}'",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    log::info!("Staged state will be activated at {time} by {unit_name}.timer");
+    Ok(())
+}
+
+fn activation_unit_name(index: u32) -> String {
+    format!("{ACTIVATION_UNIT_NAME}-{index:04}")
+}
+
+pub(crate) fn gen_activation_timer_args(time: &str, index: u32) -> Vec<String> {
+    vec![
+        format!("--unit={}", activation_unit_name(index)),
+        format!("--on-calendar={time}"),
+        "--timer-property=AccuracySec=1s".to_string(),
+    ]
+}
+
+pub(crate) fn gen_activation_cmd_args(index: u32) -> Vec<String> {
+    vec![
+        "activate".to_string(),
+        "--index".to_string(),
+        index.to_string(),
+    ]
+}
+
+fn staged_file_index(file_path: &Path) -> Option<u32> {
+    file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.parse::<u32>().ok())
+}
+
+// Only keep the staged file of specified index, all when index is None.
+pub(crate) fn filter_staged_files(
+    staged_files: Vec<PathBuf>,
+    index: Option<u32>,
+) -> Vec<PathBuf> {
+    match index {
+        Some(index) => staged_files
+            .into_iter()
+            .filter(|f| staged_file_index(f) == Some(index))
+            .collect(),
+        None => staged_files,
+    }
+}
+
+pub(crate) fn get_staged_files(
+    folder: &Path,
+) -> Result<Vec<PathBuf>, CliError> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+    let mut ret = Vec::new();
+    for entry in folder.read_dir()? {
+        let file = entry?.path();
+        if file.extension() == Some(OsStr::new(STAGED_STATE_FILE_EXTENTION)) {
+            ret.push(file);
+        }
+    }
+    ret.sort_unstable();
+    Ok(ret)
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+
+use crate::error::CliError;
+
+// The stored network states might hold secrets. Once the host key is
+// generated by `nmstatectl gen-host-key`, the states stored by `restore`,
+// `apply --stage` and `service` are encrypted by AES-256-GCM using this key.
+// The host key should never be included in shared OS image, each host
+// should generate its own.
+const HOST_KEY_PATH: &str = "/var/lib/nmstate/host.key";
+const HOST_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+// Encrypted file layout: magic, 12 bytes nonce, ciphertext with tag
+const ENCRYPTED_MAGIC: &[u8] = b"NMSTATE-AES256GCM-V1\n";
+
+pub(crate) fn gen_host_key(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let key_path = Path::new(HOST_KEY_PATH);
+    if key_path.exists() && !matches.is_present("FORCE") {
+        return Err(CliError::from(format!(
+            "Host key {HOST_KEY_PATH} already exists, states encrypted by it \
+            cannot be decrypted once regenerated, use --force to overwrite"
+        )));
+    }
+    if let Some(folder) = key_path.parent() {
+        std::fs::create_dir_all(folder)?;
+    }
+    let key = Aes256Gcm::generate_key(OsRng);
+    let mut fd = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o400)
+        .open(key_path)?;
+    fd.write_all(key.as_slice())?;
+    Ok(format!(
+        "Generated host key {HOST_KEY_PATH}, network states stored \
+        afterwards will be encrypted\n"
+    ))
+}
+
+pub(crate) fn is_encryption_enabled() -> bool {
+    Path::new(HOST_KEY_PATH).exists()
+}
+
+// Return the encrypted content if host key exists, otherwise plain content.
+pub(crate) fn encrypt_state(content: &str) -> Result<Vec<u8>, CliError> {
+    let cipher = match read_host_key()? {
+        Some(c) => c,
+        None => return Ok(content.as_bytes().to_vec()),
+    };
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let encrypted =
+        cipher.encrypt(&nonce, content.as_bytes()).map_err(|e| {
+            CliError::from(format!("Failed to encrypt network state: {e}"))
+        })?;
+    let mut ret = ENCRYPTED_MAGIC.to_vec();
+    ret.extend_from_slice(nonce.as_slice());
+    ret.extend_from_slice(&encrypted);
+    Ok(ret)
+}
+
+// Read the stored network state, decrypt it if encrypted.
+pub(crate) fn read_state_file(file_path: &Path) -> Result<Vec<u8>, CliError> {
+    let content = std::fs::read(file_path)?;
+    let data = match content.strip_prefix(ENCRYPTED_MAGIC) {
+        Some(d) if d.len() > NONCE_LEN => d,
+        Some(_) => {
+            return Err(CliError::from(format!(
+                "Encrypted network state file {} is truncated",
+                file_path.display()
+            )));
+        }
+        None => return Ok(content),
+    };
+    let cipher = match read_host_key()? {
+        Some(c) => c,
+        None => {
+            return Err(CliError::from(format!(
+                "Network state file {} is encrypted but host key \
+                {HOST_KEY_PATH} not found",
+                file_path.display()
+            )));
+        }
+    };
+    let (nonce, encrypted) = data.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), encrypted)
+        .map_err(|e| {
+            CliError::from(format!(
+                "Failed to decrypt network state file {} with host key \
+                {HOST_KEY_PATH}: {e}",
+                file_path.display()
+            ))
+        })
+}
+
+pub(crate) fn is_encrypted_state_file(file_path: &Path) -> bool {
+    std::fs::read(file_path)
+        .map(|c| c.starts_with(ENCRYPTED_MAGIC))
+        .unwrap_or_default()
+}
+
+fn read_host_key() -> Result<Option<Aes256Gcm>, CliError> {
+    let key_path = Path::new(HOST_KEY_PATH);
+    if !key_path.exists() {
+        return Ok(None);
+    }
+    let key = std::fs::read(key_path)?;
+    if key.len() != HOST_KEY_LEN {
+        return Err(CliError::from(format!(
+            "Invalid host key {HOST_KEY_PATH}: expecting {HOST_KEY_LEN} \
+            bytes, but got {}",
+            key.len()
+        )));
+    }
+    Ok(Some(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))))
+}
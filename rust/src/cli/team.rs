@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+// Show the bond configuration equivalent to NetworkManager team profiles,
+// optionally migrating team to bond within single checkpoint via the normal
+// apply. The `migrate-team` sub-command is alias of `team-to-bond --apply`.
+pub(crate) fn team_to_bond(
+    matches: &clap::ArgMatches,
+    apply: bool,
+) -> Result<String, CliError> {
+    let mut net_state = NetworkState::team_to_bond()?;
+    let output = if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&net_state)?
+    } else {
+        serde_yaml::to_string(&net_state)?
+    };
+    if net_state.interfaces.is_empty() {
+        log::info!("No team profile found in NetworkManager");
+        return Ok(output);
+    }
+    if apply {
+        net_state.set_verify_change(
+            !matches.try_contains_id("NO_VERIFY").unwrap_or_default(),
+        );
+        log::info!("Migrating team to bond");
+        net_state.apply()?;
+    }
+    Ok(output)
+}
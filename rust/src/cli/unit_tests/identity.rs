@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::{InterfaceType, NetworkState};
+
+use crate::identity::{filter_applied_state, gen_state_checksum};
+
+#[test]
+fn test_identity_of_equivalent_desired_states() {
+    let desired1 = NetworkState::new_from_yaml(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8:0::1
+      prefix-length: 64
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+",
+    )
+    .unwrap();
+    let desired2 = NetworkState::new_from_yaml(
+        r"---
+routes:
+  config:
+  - next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
+    destination: 203.0.113.0/24
+  - next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
+    destination: 198.51.100.0/24
+interfaces:
+- type: ethernet
+  name: eth1
+  state: up
+  mac-address: 00:23:45:67:89:1a
+  ipv6:
+    address:
+    - prefix-length: 64
+      ip: 2001:db8::1
+    enabled: true
+",
+    )
+    .unwrap();
+    let current = NetworkState::new_from_yaml(
+        r"---
+interfaces:
+- name: eth0
+  type: ethernet
+  state: up
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8::1
+      prefix-length: 64
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        gen_state_checksum(&desired1).unwrap(),
+        gen_state_checksum(&desired2).unwrap()
+    );
+
+    let applied1 = filter_applied_state(&desired1, &current);
+    let applied2 = filter_applied_state(&desired2, &current);
+    assert!(applied1
+        .interfaces
+        .get_iface("eth0", InterfaceType::Unknown)
+        .is_none());
+    assert_eq!(
+        gen_state_checksum(&applied1).unwrap(),
+        gen_state_checksum(&applied2).unwrap()
+    );
+    assert_eq!(
+        gen_state_checksum(&applied1).unwrap(),
+        gen_state_checksum(&desired1).unwrap()
+    );
+}
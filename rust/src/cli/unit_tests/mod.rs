@@ -0,0 +1,4 @@
+#[cfg(feature = "query_apply")]
+mod identity;
+#[cfg(feature = "query_apply")]
+mod stage;
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use crate::stage::{
+    filter_staged_files, gen_activation_cmd_args, gen_activation_timer_args,
+    get_staged_files,
+};
+
+#[test]
+fn test_activation_timer_only_activate_its_own_stage() {
+    let folder = std::env::temp_dir()
+        .join(format!("nmstate_test_staged_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    for file_name in ["0001.yml", "0002.yml", "note.txt"] {
+        std::fs::write(folder.join(file_name), "").unwrap();
+    }
+    let staged_files = get_staged_files(&folder);
+    std::fs::remove_dir_all(&folder).unwrap();
+    let staged_files = staged_files.unwrap();
+    assert_eq!(
+        staged_files,
+        vec![folder.join("0001.yml"), folder.join("0002.yml")]
+    );
+
+    // Two stages scheduled at different time
+    let timer1 = gen_activation_timer_args("2026-10-20 02:00:00", 1);
+    let timer2 = gen_activation_timer_args("2026-10-21 02:00:00", 2);
+    assert_eq!(timer1[0], "--unit=nmstate-activate-0001");
+    assert_eq!(timer1[1], "--on-calendar=2026-10-20 02:00:00");
+    assert_eq!(timer2[0], "--unit=nmstate-activate-0002");
+    assert_eq!(timer2[1], "--on-calendar=2026-10-21 02:00:00");
+
+    let cmd1 = gen_activation_cmd_args(1);
+    let cmd2 = gen_activation_cmd_args(2);
+    assert_eq!(cmd1, vec!["activate", "--index", "1"]);
+    assert_eq!(cmd2, vec!["activate", "--index", "2"]);
+
+    // The first timer should not activate the state staged for second one
+    assert_eq!(
+        filter_staged_files(staged_files.clone(), cmd1[2].parse().ok()),
+        vec![folder.join("0001.yml")]
+    );
+    assert_eq!(
+        filter_staged_files(staged_files.clone(), cmd2[2].parse().ok()),
+        vec![folder.join("0002.yml")]
+    );
+    assert_eq!(
+        filter_staged_files(staged_files.clone(), None),
+        staged_files
+    );
+    assert_eq!(
+        filter_staged_files(staged_files, Some(3)),
+        Vec::<PathBuf>::new()
+    );
+}
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::str::FromStr;
+
+use nmstate::{NetworkState, ValidationProfile};
+
+use crate::error::CliError;
+
+// Check the state file or the running network configuration against
+// specified validation profile, fail if any violation found.
+pub(crate) fn validate(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let profile = ValidationProfile::from_str(
+        matches.value_of("PROFILE").unwrap_or_default(),
+    )?;
+    let net_state = if let Some(file_path) = matches.value_of("STATE_FILE") {
+        let mut fd = std::fs::File::open(file_path)?;
+        let mut content = String::new();
+        fd.read_to_string(&mut content)?;
+        serde_yaml::from_str::<NetworkState>(&content)?
+    } else {
+        let mut net_state = NetworkState::new();
+        net_state.set_running_config_only(true);
+        net_state.retrieve()?;
+        net_state
+    };
+
+    let violations = net_state.validate_profile(profile);
+    let output = if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&violations)?
+    } else {
+        serde_yaml::to_string(&violations)?
+    };
+    if violations.is_empty() {
+        log::info!("No configuration incompatible with {profile} found");
+        Ok(output)
+    } else {
+        Err(output.into())
+    }
+}
@@ -9,7 +9,8 @@ use crate::{
     init_logger,
     query::{
         NMSTATE_FLAG_KERNEL_ONLY, NMSTATE_FLAG_MEMORY_ONLY,
-        NMSTATE_FLAG_NO_COMMIT, NMSTATE_FLAG_NO_VERIFY,
+        NMSTATE_FLAG_NO_CHECKPOINT, NMSTATE_FLAG_NO_COMMIT,
+        NMSTATE_FLAG_NO_VERIFY,
     },
     NMSTATE_FAIL, NMSTATE_PASS,
 };
@@ -102,6 +103,10 @@ pub extern "C" fn nmstate_net_state_apply(
         net_state.set_memory_only(true);
     }
 
+    if (flags & NMSTATE_FLAG_NO_CHECKPOINT) > 0 {
+        net_state.set_checkpoint(false);
+    }
+
     net_state.set_timeout(rollback_timeout);
 
     let result = net_state.apply();
@@ -15,6 +15,8 @@ pub(crate) const NMSTATE_FLAG_NO_COMMIT: u32 = 1 << 5;
 pub(crate) const NMSTATE_FLAG_MEMORY_ONLY: u32 = 1 << 6;
 pub(crate) const NMSTATE_FLAG_RUNNING_CONFIG_ONLY: u32 = 1 << 7;
 pub(crate) const NMSTATE_FLAG_YAML_OUTPUT: u32 = 1 << 8;
+pub(crate) const NMSTATE_FLAG_SAVED_CONFIG_ONLY: u32 = 1 << 9;
+pub(crate) const NMSTATE_FLAG_NO_CHECKPOINT: u32 = 1 << 10;
 
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
@@ -67,6 +69,10 @@ pub extern "C" fn nmstate_net_state_retrieve(
         net_state.set_running_config_only(true);
     }
 
+    if (flags & NMSTATE_FLAG_SAVED_CONFIG_ONLY) > 0 {
+        net_state.set_saved_config_only(true);
+    }
+
     let result = net_state.retrieve();
     unsafe {
         *log = CString::new(logger.drain(now)).unwrap().into_raw();
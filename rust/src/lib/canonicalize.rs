@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use crate::{
+    ip::sanitize_ip_network, BaseInterface, DnsClientState, EthernetConfig,
+    Interface, InterfaceType, MultiConnect, NetworkState, RouteEntry,
+    RouteRuleEntry, WaitIp,
+};
+
+const DEFAULT_MTU: u64 = 1500;
+
+// Interface types using DEFAULT_MTU as kernel default MTU
+const DEFAULT_MTU_IFACE_TYPES: [InterfaceType; 10] = [
+    InterfaceType::Ethernet,
+    InterfaceType::Veth,
+    InterfaceType::Bond,
+    InterfaceType::LinuxBridge,
+    InterfaceType::Vlan,
+    InterfaceType::Dummy,
+    InterfaceType::MacVlan,
+    InterfaceType::MacVtap,
+    InterfaceType::IpVlan,
+    InterfaceType::OvsInterface,
+];
+
+impl NetworkState {
+    /// Convert the state into deterministic canonical form, so the diff
+    /// between states queried from different hosts or at different time is
+    /// meaningful:
+    ///  * Routes and route rules are sorted.
+    ///  * Ports of bond, linux bridge and OVS bridge are sorted by name.
+    ///  * IP address in text form is normalized, e.g. `2001:db8:0::1` to
+    ///    `2001:db8::1`.
+    ///  * MAC address is lowercased.
+    ///  * Properties holding the default value are removed.
+    ///
+    /// The order of DNS servers and IP addresses is preserved as it is
+    /// meaningful. Interfaces and keys of maps are always serialized in
+    /// sorted order.
+    pub fn canonicalize(&mut self) {
+        for iface in self
+            .interfaces
+            .kernel_ifaces
+            .values_mut()
+            .chain(self.interfaces.user_ifaces.values_mut())
+        {
+            iface.canonicalize();
+        }
+        if let Some(rts) = self.routes.config.as_mut() {
+            canonicalize_routes(rts);
+        }
+        if let Some(rts) = self.routes.running.as_mut() {
+            canonicalize_routes(rts);
+        }
+        if let Some(rules) = self.rules.config.as_mut() {
+            rules.iter_mut().for_each(RouteRuleEntry::canonicalize);
+            rules.sort_unstable();
+            rules.dedup();
+        }
+        if let Some(dns_conf) = self.dns.config.as_mut() {
+            dns_conf.canonicalize();
+        }
+        if let Some(dns_conf) = self.dns.running.as_mut() {
+            dns_conf.canonicalize();
+        }
+    }
+
+    // Canonicalize the state and remove properties holding the default
+    // value of backend or status only properties, so the output is concise
+    // enough for checking into git.
+    pub(crate) fn hide_defaults(&mut self) {
+        self.canonicalize();
+        for iface in self
+            .interfaces
+            .kernel_ifaces
+            .values_mut()
+            .chain(self.interfaces.user_ifaces.values_mut())
+        {
+            iface.hide_defaults();
+        }
+        self.routes.running = None;
+        self.dns.running = None;
+        self.connectivity = None;
+        if let Some(pci_devs) = self.pci_devices.as_mut() {
+            for pci_dev in pci_devs {
+                pci_dev.driver = None;
+            }
+        }
+    }
+}
+
+fn canonicalize_routes(rts: &mut Vec<RouteEntry>) {
+    rts.iter_mut().for_each(RouteEntry::canonicalize);
+    rts.sort_unstable();
+    rts.dedup();
+}
+
+impl Interface {
+    pub(crate) fn canonicalize(&mut self) {
+        self.base_iface_mut().canonicalize();
+        match self {
+            Self::Bond(iface) => iface.sort_ports(),
+            Self::LinuxBridge(iface) => {
+                iface.sort_ports();
+                iface.sort_port_vlans();
+            }
+            Self::OvsBridge(iface) => iface.sort_ports(),
+            _ => (),
+        }
+    }
+
+    fn hide_defaults(&mut self) {
+        let iface_type = self.iface_type();
+        let base_iface = self.base_iface_mut();
+        // Status only properties
+        base_iface.min_mtu = None;
+        base_iface.max_mtu = None;
+        base_iface.link_stats = None;
+        base_iface.pci_hints = None;
+        base_iface.connectivity = None;
+        base_iface.ethtool = None;
+        if let Some(ipv4) = base_iface.ipv4.as_mut() {
+            ipv4.dhcp_lease = None;
+        }
+        if let Some(ipv6) = base_iface.ipv6.as_mut() {
+            ipv6.dhcp_lease = None;
+        }
+        if base_iface.mtu == Some(DEFAULT_MTU)
+            && DEFAULT_MTU_IFACE_TYPES.contains(&iface_type)
+        {
+            base_iface.mtu = None;
+        }
+        if base_iface.wait_ip == Some(WaitIp::Any) {
+            base_iface.wait_ip = None;
+        }
+        if let Self::Ethernet(iface) = self {
+            // The speed and duplex are negotiated result
+            if let Some(eth_conf) = iface.ethernet.as_mut() {
+                if eth_conf.auto_neg != Some(false) {
+                    eth_conf.auto_neg = None;
+                    eth_conf.speed = None;
+                    eth_conf.duplex = None;
+                }
+                eth_conf.usb_role = None;
+                if let Some(vfs) =
+                    eth_conf.sr_iov.as_mut().and_then(|s| s.vfs.as_mut())
+                {
+                    for vf in vfs {
+                        vf.remove_query_only_props();
+                    }
+                }
+                if eth_conf == &EthernetConfig::default() {
+                    iface.ethernet = None;
+                }
+            }
+        }
+    }
+}
+
+impl BaseInterface {
+    fn canonicalize(&mut self) {
+        if let Some(mac) = self.mac_address.as_mut() {
+            mac.make_ascii_lowercase();
+        }
+        if let Some(mac) = self.permanent_mac_address.as_mut() {
+            mac.make_ascii_lowercase();
+        }
+        if self.description.as_deref() == Some("") {
+            self.description = None;
+        }
+        if self.accept_all_mac_addresses == Some(false) {
+            self.accept_all_mac_addresses = None;
+        }
+        if self.multi_connect == Some(MultiConnect::Default) {
+            self.multi_connect = None;
+        }
+        if self
+            .lldp
+            .as_ref()
+            .map(|l| !l.enabled && l.neighbors.is_empty())
+            == Some(true)
+        {
+            self.lldp = None;
+        }
+        if self
+            .mptcp
+            .as_ref()
+            .map(|m| m.address_flags.as_deref().unwrap_or_default().is_empty())
+            == Some(true)
+        {
+            self.mptcp = None;
+        }
+    }
+}
+
+impl RouteEntry {
+    fn canonicalize(&mut self) {
+        if let Some(dst) = self.destination.as_mut() {
+            canonicalize_ip_network(dst);
+        }
+        if let Some(via) = self.next_hop_addr.as_mut() {
+            canonicalize_ip(via);
+        }
+    }
+}
+
+impl RouteRuleEntry {
+    fn canonicalize(&mut self) {
+        if let Some(ip) = self.ip_from.as_mut() {
+            canonicalize_ip_network(ip);
+        }
+        if let Some(ip) = self.ip_to.as_mut() {
+            canonicalize_ip_network(ip);
+        }
+    }
+}
+
+impl DnsClientState {
+    fn canonicalize(&mut self) {
+        if let Some(srvs) = self.server.as_mut() {
+            srvs.iter_mut().for_each(canonicalize_ip);
+        }
+    }
+}
+
+// Keep the original string if failed to parse, the canonicalization should
+// never fail.
+fn canonicalize_ip_network(ip_net: &mut String) {
+    if let Ok(new_ip_net) = sanitize_ip_network(ip_net) {
+        *ip_net = new_ip_net;
+    }
+}
+
+// The IPv6 link-local address might hold scope ID like `fe80::1%eth1`.
+fn canonicalize_ip(ip: &mut String) {
+    let (addr, scope) = match ip.split_once('%') {
+        Some((addr, scope)) => (addr, Some(scope)),
+        None => (ip.as_str(), None),
+    };
+    if let Ok(addr) = addr.parse::<IpAddr>() {
+        *ip = match scope {
+            Some(scope) => format!("{addr}%{scope}"),
+            None => addr.to_string(),
+        };
+    }
+}
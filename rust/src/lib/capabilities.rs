@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "query_apply"))]
+use crate::{ErrorKind, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Features supported by the running host, returned by [capabilities()].
+///
+/// Example yaml output:
+/// ```yml
+/// nmstate-version: 2.2.20
+/// nm-version: 1.42.2-1.fc38
+/// nm-device-plugins:
+/// - ovs
+/// - team
+/// - wifi
+/// nm-settings-plugins:
+/// - ifcfg-rh
+/// - keyfile
+/// kernel-version: 6.5.6-300.fc39.x86_64
+/// kernel-features:
+/// - bonding
+/// - mptcp
+/// mptcp: true
+/// ovs: true
+/// global-dns: true
+/// veth: true
+/// ```
+pub struct NmstateCapabilities {
+    #[serde(default)]
+    /// Version of nmstate library.
+    pub nmstate_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Version of NetworkManager daemon. `None` when NetworkManager is not
+    /// running or not reachable.
+    pub nm_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// NetworkManager device plugins installed for the running
+    /// NetworkManager daemon, for example `ovs`, `team` and `wifi`.
+    /// `None` when NetworkManager plugin folder not found.
+    pub nm_device_plugins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// NetworkManager settings plugins installed for the running
+    /// NetworkManager daemon, the built-in `keyfile` is always included.
+    /// `None` when NetworkManager plugin folder not found.
+    pub nm_settings_plugins: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Release of running kernel.
+    pub kernel_version: Option<String>,
+    #[serde(default)]
+    /// Kernel features used by nmstate kernel backend (via nispor) and
+    /// available on running kernel, for example `mptcp`, `bonding`,
+    /// `openvswitch`.
+    pub kernel_features: Vec<String>,
+    #[serde(default)]
+    /// Whether MPTCP flags can be applied through NetworkManager.
+    pub mptcp: bool,
+    #[serde(default)]
+    /// Whether OpenvSwitch database is running and reachable.
+    pub ovs: bool,
+    #[serde(default)]
+    /// Whether NetworkManager supports global DNS configuration API.
+    pub global_dns: bool,
+    #[serde(default)]
+    /// Whether NetworkManager supports managing veth interfaces.
+    pub veth: bool,
+}
+
+impl NmstateCapabilities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(not(feature = "query_apply"))]
+/// Probe features supported by the running host.
+/// Only available for feature `query_apply`.
+pub fn capabilities() -> Result<NmstateCapabilities, NmstateError> {
+    Err(NmstateError::new(
+        ErrorKind::DependencyError,
+        "nmstate::capabilities() need `query_apply` feature enabled".into(),
+    ))
+}
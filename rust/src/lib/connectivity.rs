@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Internet connectivity state checked by NetworkManager.
+/// Please refer to `[connectivity]` section of `NetworkManager.conf(5)`
+/// for how connectivity is checked.
+pub enum ConnectivityState {
+    /// Connectivity check is disabled or not finished yet.
+    Unknown,
+    /// Not connected to any network.
+    None,
+    /// Internet connection is hijacked by a captive portal.
+    Portal,
+    /// Connected to network, but no access to internet.
+    Limited,
+    /// Connected to network with full access to internet.
+    Full,
+}
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+/// Internet connectivity state of interface. Ignored during apply.
+///
+/// Example yaml output of interface connectivity:
+/// ```yml
+/// connectivity:
+///   ipv4: full
+///   ipv6: limited
+/// ```
+pub struct InterfaceConnectivity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Connectivity state of IPv4 stack.
+    pub ipv4: Option<ConnectivityState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Connectivity state of IPv6 stack.
+    pub ipv6: Option<ConnectivityState>,
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+// Deprecated properties of interface, tuple of path to the deprecated
+// property and the new property name in the same parent. The `*` in path
+// matches all items in list.
+// Please append new deprecations here instead of using serde alias, so user
+// could be notified.
+const DEPRECATED_IFACE_PROPS: &[(&[&str], &str)] = &[
+    (&["link-aggregation", "ports"], "port"),
+    (&["bridge", "ports"], "port"),
+    (
+        &["bridge", "port", "*", "link-aggregation", "ports"],
+        "port",
+    ),
+    (&["vrf", "ports"], "port"),
+    (&["mac-vlan", "accept-all-mac"], "promiscuous"),
+    (&["mac-vtap", "accept-all-mac"], "promiscuous"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Deprecated property found in desired state. The deprecated property is
+/// still honored as its replacement.
+pub struct DeprecationWarning {
+    /// Path to the deprecated property, for example
+    /// `interfaces[bond0].link-aggregation.ports`.
+    pub path: String,
+    /// Path to the property replacing the deprecated one, for example
+    /// `interfaces[bond0].link-aggregation.port`.
+    pub replacement: String,
+}
+
+impl std::fmt::Display for DeprecationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Property {} is deprecated, please use {} instead",
+            self.path, self.replacement
+        )
+    }
+}
+
+// Rename deprecated properties in the `interfaces` section to their
+// replacement. When both deprecated and new properties are defined, they are
+// untouched and left to deserializer to complain.
+pub(crate) fn rename_deprecated_iface_props(
+    ifaces_value: &mut serde_json::Value,
+) -> Vec<DeprecationWarning> {
+    let mut ret = Vec::new();
+    if let Some(ifaces) = ifaces_value.as_array_mut() {
+        for (index, iface) in ifaces.iter_mut().enumerate() {
+            let iface_path = match iface.get("name").and_then(|n| n.as_str()) {
+                Some(name) => format!("interfaces[{name}]"),
+                None => format!("interfaces[{index}]"),
+            };
+            for (path, replacement) in DEPRECATED_IFACE_PROPS {
+                rename_prop(iface, path, replacement, &iface_path, &mut ret);
+            }
+        }
+    }
+    for warning in &ret {
+        log::warn!("{}", warning);
+    }
+    ret
+}
+
+fn rename_prop(
+    value: &mut serde_json::Value,
+    path: &[&str],
+    replacement: &str,
+    cur_path: &str,
+    warnings: &mut Vec<DeprecationWarning>,
+) {
+    match path {
+        [] => (),
+        ["*", remains @ ..] => {
+            if let Some(items) = value.as_array_mut() {
+                for (index, item) in items.iter_mut().enumerate() {
+                    rename_prop(
+                        item,
+                        remains,
+                        replacement,
+                        &format!("{cur_path}[{index}]"),
+                        warnings,
+                    );
+                }
+            }
+        }
+        [name] => {
+            if let Some(obj) = value.as_object_mut() {
+                if obj.contains_key(*name) {
+                    if !obj.contains_key(replacement) {
+                        if let Some(v) = obj.remove(*name) {
+                            obj.insert(replacement.to_string(), v);
+                        }
+                    }
+                    warnings.push(DeprecationWarning {
+                        path: format!("{cur_path}.{name}"),
+                        replacement: format!("{cur_path}.{replacement}"),
+                    });
+                }
+            }
+        }
+        [name, remains @ ..] => {
+            if let Some(v) = value.get_mut(*name) {
+                rename_prop(
+                    v,
+                    remains,
+                    replacement,
+                    &format!("{cur_path}.{name}"),
+                    warnings,
+                );
+            }
+        }
+    }
+}
@@ -5,7 +5,10 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ip::is_ipv6_addr, ErrorKind, MergedNetworkState, NmstateError};
+use crate::{
+    ip::is_ipv6_addr, ErrorKind, MergedInterfaces, MergedNetworkState,
+    NmstateError,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -60,6 +63,29 @@ impl DnsState {
         }
         Ok(())
     }
+
+    // Resolve the interface reference used in IPv6 link local name server
+    // into interface name.
+    pub(crate) fn resolve_iface_refs(
+        &mut self,
+        merged_ifaces: &MergedInterfaces,
+    ) -> Result<(), NmstateError> {
+        for srv in self
+            .config
+            .iter_mut()
+            .filter_map(|c| c.server.as_mut())
+            .flatten()
+        {
+            if let Some((addr, scope)) = srv.split_once('%') {
+                if let Some(iface_name) =
+                    merged_ifaces.resolve_iface_ref(scope)?
+                {
+                    *srv = format!("{addr}%{iface_name}");
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -71,6 +97,10 @@ pub struct DnsClientState {
     /// Name server IP address list.
     /// To remove all existing servers, please use `Some(Vec::new())`.
     /// If undefined(set to `None`), will preserve current config.
+    /// The interface of IPv6 link local name server, for example
+    /// `fe80::deef:1%eth1`, could also be referred by MAC address or label
+    /// like `fe80::deef:1%mac:00:23:45:67:89:1a` or
+    /// `fe80::deef:1%label:role=storage` when applying.
     pub server: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Search list for host-name lookup.
@@ -210,7 +240,7 @@ impl MergedDnsState {
 }
 
 impl MergedNetworkState {
-    // * Specified interface is valid for hold IPv6 DNS config.
+    // * Specified interface is valid for hold IPv6 DNS config and not ignored.
     // * Cannot have more than one IPv6 link-local DNS interface.
     pub(crate) fn validate_ipv6_link_local_address_dns_srv(
         &self,
@@ -222,6 +252,20 @@ impl MergedNetworkState {
                     self.interfaces.kernel_ifaces.get(iface_name)
                 {
                     iface
+                } else if self
+                    .interfaces
+                    .ignored_ifaces
+                    .iter()
+                    .any(|(n, t)| n == iface_name && !t.is_userspace())
+                {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Desired IPv6 link local DNS server {srv} is \
+                            pointing to interface {iface_name} which is \
+                            ignored, please manage this interface first"
+                        ),
+                    ));
                 } else {
                     return Err(NmstateError::new(
                         ErrorKind::InvalidArgument,
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ip::is_ipv6_unicast_link_local, Interface, InterfaceIpAddr, InterfaceType,
+    NetworkState, RouteEntry, RouteState, Routes,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Difference between live network state and the configuration saved by
+/// backend, for example IP address added by `ip addr add` command without
+/// updating NetworkManager connection profile.
+///
+/// Only interfaces holding saved configuration and in up state are checked.
+/// Use [NetworkDrift::persist_state()] or [NetworkDrift::revert_state()] to
+/// get the desired state for persisting or reverting the drift.
+pub struct NetworkDrift {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    /// Interfaces with IP addresses differ from saved configuration.
+    pub interfaces: Vec<InterfaceDrift>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    /// Routes only exist in live network state.
+    pub unsaved_routes: Vec<RouteEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    /// Routes only exist in saved configuration.
+    pub missing_routes: Vec<RouteEntry>,
+    #[serde(skip)]
+    persist_state: NetworkState,
+    #[serde(skip)]
+    revert_state: NetworkState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct InterfaceDrift {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub iface_type: InterfaceType,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    /// IP addresses only exist in live network state.
+    pub unsaved_addresses: Vec<InterfaceIpAddr>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    /// IP addresses only exist in saved configuration.
+    pub missing_addresses: Vec<InterfaceIpAddr>,
+}
+
+impl NetworkDrift {
+    /// Compare the running configuration(see
+    /// [NetworkState::set_running_config_only()]) with saved configuration
+    /// (see [NetworkState::set_saved_config_only()]).
+    pub fn new(live: &NetworkState, saved: &NetworkState) -> Self {
+        let mut live = live.clone();
+        let mut saved = saved.clone();
+        live.canonicalize();
+        saved.canonicalize();
+
+        let mut ret = Self::default();
+        let mut checked_ifaces: Vec<&str> = Vec::new();
+        for saved_iface in saved
+            .interfaces
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_up())
+        {
+            let live_iface = match live
+                .interfaces
+                .kernel_ifaces
+                .get(saved_iface.name())
+                .filter(|i| i.is_up())
+            {
+                Some(i) => i,
+                None => continue,
+            };
+            checked_ifaces.push(saved_iface.name());
+            let live_addrs = get_ip_addrs(live_iface);
+            let saved_addrs = get_ip_addrs(saved_iface);
+            let unsaved_addresses: Vec<InterfaceIpAddr> = live_addrs
+                .iter()
+                .filter(|a| !contains_ip_addr(&saved_addrs, a))
+                .cloned()
+                .collect();
+            let missing_addresses: Vec<InterfaceIpAddr> = saved_addrs
+                .iter()
+                .filter(|a| !contains_ip_addr(&live_addrs, a))
+                .cloned()
+                .collect();
+            if unsaved_addresses.is_empty() && missing_addresses.is_empty() {
+                continue;
+            }
+            ret.interfaces.push(InterfaceDrift {
+                name: saved_iface.name().to_string(),
+                iface_type: saved_iface.iface_type(),
+                unsaved_addresses,
+                missing_addresses,
+            });
+            ret.persist_state
+                .interfaces
+                .push(clone_name_type_ip_only(live_iface));
+            ret.revert_state
+                .interfaces
+                .push(clone_name_type_ip_only(saved_iface));
+        }
+
+        let live_rts = get_routes(&live.routes, checked_ifaces.as_slice());
+        let saved_rts = get_routes(&saved.routes, checked_ifaces.as_slice());
+        ret.unsaved_routes = live_rts
+            .iter()
+            .filter(|r| !saved_rts.iter().any(|s| s.is_match(r)))
+            .map(|r| (*r).clone())
+            .collect();
+        ret.missing_routes = saved_rts
+            .iter()
+            .filter(|s| !live_rts.iter().any(|r| s.is_match(r)))
+            .map(|r| (*r).clone())
+            .collect();
+        if !ret.unsaved_routes.is_empty() || !ret.missing_routes.is_empty() {
+            ret.persist_state.routes.config = Some(gen_routes_for_apply(
+                ret.unsaved_routes.as_slice(),
+                ret.missing_routes.as_slice(),
+            ));
+            ret.revert_state.routes.config = Some(gen_routes_for_apply(
+                ret.missing_routes.as_slice(),
+                ret.unsaved_routes.as_slice(),
+            ));
+        }
+        ret
+    }
+
+    /// Whether live network state is identical to saved configuration.
+    pub fn is_empty(&self) -> bool {
+        self.interfaces.is_empty()
+            && self.unsaved_routes.is_empty()
+            && self.missing_routes.is_empty()
+    }
+
+    /// Desired state for saving the live network state to backend
+    /// configuration.
+    pub fn persist_state(&self) -> &NetworkState {
+        &self.persist_state
+    }
+
+    /// Desired state for restoring the live network state to saved
+    /// configuration.
+    pub fn revert_state(&self) -> &NetworkState {
+        &self.revert_state
+    }
+}
+
+// IPv6 link-local address is generated by kernel
+fn get_ip_addrs(iface: &Interface) -> Vec<InterfaceIpAddr> {
+    let base_iface = iface.base_iface();
+    let mut addrs: Vec<InterfaceIpAddr> = Vec::new();
+    if let Some(ipv4_addrs) = base_iface
+        .ipv4
+        .as_ref()
+        .filter(|i| i.enabled)
+        .and_then(|i| i.addresses.as_ref())
+    {
+        addrs.extend_from_slice(ipv4_addrs);
+    }
+    if let Some(ipv6_addrs) = base_iface
+        .ipv6
+        .as_ref()
+        .filter(|i| i.enabled)
+        .and_then(|i| i.addresses.as_ref())
+    {
+        addrs.extend(
+            ipv6_addrs
+                .iter()
+                .filter(|a| match a.ip {
+                    IpAddr::V6(ip) => !is_ipv6_unicast_link_local(&ip),
+                    IpAddr::V4(_) => true,
+                })
+                .cloned(),
+        );
+    }
+    addrs
+}
+
+fn contains_ip_addr(addrs: &[InterfaceIpAddr], addr: &InterfaceIpAddr) -> bool {
+    addrs
+        .iter()
+        .any(|a| a.ip == addr.ip && a.prefix_length == addr.prefix_length)
+}
+
+fn clone_name_type_ip_only(iface: &Interface) -> Interface {
+    let mut new_iface = iface.clone_name_type_only();
+    new_iface.base_iface_mut().ipv4 = iface.base_iface().ipv4.clone();
+    new_iface.base_iface_mut().ipv6 = iface.base_iface().ipv6.clone();
+    new_iface
+}
+
+fn get_routes<'a>(
+    routes: &'a Routes,
+    iface_names: &[&str],
+) -> Vec<&'a RouteEntry> {
+    routes
+        .config
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| {
+            r.next_hop_iface
+                .as_deref()
+                .map(|i| iface_names.contains(&i))
+                == Some(true)
+        })
+        .collect()
+}
+
+fn gen_routes_for_apply(
+    routes_to_add: &[RouteEntry],
+    routes_to_remove: &[RouteEntry],
+) -> Vec<RouteEntry> {
+    let mut ret = routes_to_add.to_vec();
+    for route in routes_to_remove {
+        let mut route = route.clone();
+        route.state = Some(RouteState::Absent);
+        ret.push(route);
+    }
+    ret
+}
@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Write;
+
+use crate::{
+    ErrorKind, Interface, InterfaceIpAddr, InterfaceType, MergedNetworkState,
+    NmstateError,
+};
+
+pub(crate) const EARLY_BOOT_BACKEND: &str = "iproute";
+const EARLY_BOOT_SCRIPT_NAME: &str = "nmstate-early-boot.sh";
+const EARLY_BOOT_UNIT_NAME: &str = "nmstate-early-boot.service";
+const EARLY_BOOT_SCRIPT_PATH: &str = "/usr/libexec/nmstate-early-boot.sh";
+
+// Generate a shell script using `ip` command of iproute2 realizing the
+// kernel side of desired state, and a systemd unit invoking this script
+// before NetworkManager starts. Only static kernel configurations are
+// included, NetworkManager will take over these interfaces once started.
+pub(crate) fn early_boot_gen_conf(
+    merged_state: &MergedNetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ifaces: Vec<&Interface> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up())
+        .filter(|i| {
+            if is_early_boot_supported(i) {
+                true
+            } else {
+                log::warn!(
+                    "Interface {} with type {} is not supported by \
+                    early boot script, ignoring",
+                    i.name(),
+                    i.iface_type()
+                );
+                false
+            }
+        })
+        .collect();
+    ifaces.sort_unstable_by_key(|i| i.name());
+
+    for iface in ifaces.iter() {
+        validate_iface_shell_safe(iface)?;
+    }
+
+    let mut script = String::new();
+    writeln!(script, "#!/bin/sh").ok();
+    writeln!(
+        script,
+        "# Generated by nmstate, bringing up network before NetworkManager"
+    )
+    .ok();
+    writeln!(script, "set -e").ok();
+
+    // VLAN should be created after its base interface
+    for iface in ifaces
+        .iter()
+        .filter(|i| i.iface_type() != InterfaceType::Vlan)
+        .chain(
+            ifaces
+                .iter()
+                .filter(|i| i.iface_type() == InterfaceType::Vlan),
+        )
+    {
+        gen_iface_create_cmd(&mut script, iface);
+    }
+
+    for iface in ifaces.iter() {
+        gen_iface_link_cmds(&mut script, iface);
+    }
+
+    // Controller should be up before its ports
+    for iface in ifaces
+        .iter()
+        .filter(|i| i.is_controller())
+        .chain(ifaces.iter().filter(|i| !i.is_controller()))
+    {
+        writeln!(script, "ip link set {} up", iface.name()).ok();
+    }
+
+    for iface in ifaces.iter() {
+        gen_iface_ip_cmds(&mut script, iface);
+    }
+
+    for iface in ifaces.iter() {
+        if let Some(rts) = merged_state.routes.indexed.get(iface.name()) {
+            for rt in rts {
+                let dst = if let Some(d) = rt.destination.as_deref() {
+                    d
+                } else {
+                    log::warn!(
+                        "Route without destination of interface {} is not \
+                        supported by early boot script, ignoring",
+                        iface.name()
+                    );
+                    continue;
+                };
+                check_shell_safe("route destination", dst, ADDR_ALLOWED_PUNCT)?;
+                let mut cmd = format!(
+                    "ip {}route replace {dst}",
+                    if rt.is_ipv6() { "-6 " } else { "" },
+                );
+                if let Some(via) = rt.next_hop_addr.as_deref() {
+                    check_shell_safe(
+                        "route next hop address",
+                        via,
+                        ADDR_ALLOWED_PUNCT,
+                    )?;
+                    write!(cmd, " via {via}").ok();
+                }
+                write!(cmd, " dev {}", iface.name()).ok();
+                if let Some(metric) = rt.metric {
+                    write!(cmd, " metric {metric}").ok();
+                }
+                if let Some(table_id) = rt.table_id {
+                    write!(cmd, " table {table_id}").ok();
+                }
+                writeln!(script, "{cmd}").ok();
+            }
+        }
+    }
+
+    Ok(vec![
+        (EARLY_BOOT_SCRIPT_NAME.to_string(), script),
+        (EARLY_BOOT_UNIT_NAME.to_string(), gen_systemd_unit()),
+    ])
+}
+
+// The generated script is executed by root during boot, hence every string
+// placed into the script should not contain shell special characters.
+const NAME_ALLOWED_PUNCT: &str = "_.-";
+const ADDR_ALLOWED_PUNCT: &str = "_.-:/";
+
+fn validate_iface_shell_safe(iface: &Interface) -> Result<(), NmstateError> {
+    let base_iface = iface.base_iface();
+    check_shell_safe("interface name", iface.name(), NAME_ALLOWED_PUNCT)?;
+    if let Some(ctrl) = base_iface.controller.as_deref() {
+        check_shell_safe("controller name", ctrl, NAME_ALLOWED_PUNCT)?;
+    }
+    if let Some(mac) = base_iface.mac_address.as_deref() {
+        check_shell_safe("MAC address", mac, ADDR_ALLOWED_PUNCT)?;
+    }
+    if let Interface::Vlan(vlan_iface) = iface {
+        if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+            check_shell_safe(
+                "VLAN base interface",
+                &vlan_conf.base_iface,
+                NAME_ALLOWED_PUNCT,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn check_shell_safe(
+    prop_name: &str,
+    value: &str,
+    allowed_punct: &str,
+) -> Result<(), NmstateError> {
+    if value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || allowed_punct.contains(c))
+    {
+        Ok(())
+    } else {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "The {prop_name} '{value}' contains characters not allowed \
+                in early boot script, only ASCII alphanumeric and \
+                '{allowed_punct}' are allowed"
+            ),
+        );
+        log::error!("{}", e);
+        Err(e)
+    }
+}
+
+fn is_early_boot_supported(iface: &Interface) -> bool {
+    matches!(
+        iface,
+        Interface::Ethernet(_)
+            | Interface::Bond(_)
+            | Interface::LinuxBridge(_)
+            | Interface::Vlan(_)
+            | Interface::Dummy(_)
+    )
+}
+
+fn gen_iface_create_cmd(script: &mut String, iface: &Interface) {
+    let link_type = match iface {
+        Interface::Bond(bond_iface) => {
+            match bond_iface.bond.as_ref().and_then(|b| b.mode) {
+                Some(mode) => format!("bond mode {mode}"),
+                None => "bond".to_string(),
+            }
+        }
+        Interface::LinuxBridge(_) => "bridge".to_string(),
+        Interface::Dummy(_) => "dummy".to_string(),
+        Interface::Vlan(vlan_iface) => {
+            if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+                writeln!(
+                    script,
+                    "ip link show {name} >/dev/null 2>&1 || \
+                    ip link add link {base} name {name} type vlan id {id}",
+                    name = iface.name(),
+                    base = vlan_conf.base_iface,
+                    id = vlan_conf.id,
+                )
+                .ok();
+            }
+            return;
+        }
+        _ => return,
+    };
+    writeln!(
+        script,
+        "ip link show {name} >/dev/null 2>&1 || \
+        ip link add {name} type {link_type}",
+        name = iface.name(),
+    )
+    .ok();
+}
+
+fn gen_iface_link_cmds(script: &mut String, iface: &Interface) {
+    let base_iface = iface.base_iface();
+    if let Some(mtu) = base_iface.mtu {
+        writeln!(script, "ip link set {} mtu {mtu}", iface.name()).ok();
+    }
+    if let Some(mac) = base_iface.mac_address.as_ref() {
+        writeln!(script, "ip link set {} address {mac}", iface.name()).ok();
+    }
+    if let Some(ctrl) = base_iface.controller.as_deref() {
+        if !ctrl.is_empty() {
+            // Bond port should be down before attaching to bond
+            writeln!(script, "ip link set {} down", iface.name()).ok();
+            writeln!(script, "ip link set {} master {ctrl}", iface.name()).ok();
+        }
+    }
+}
+
+fn gen_iface_ip_cmds(script: &mut String, iface: &Interface) {
+    let base_iface = iface.base_iface();
+    if let Some(ipv4) = base_iface.ipv4.as_ref().filter(|i| i.enabled) {
+        if ipv4.is_auto() {
+            log::warn!(
+                "DHCPv4 of interface {} is not supported by early boot \
+                script, ignoring",
+                iface.name()
+            );
+        }
+        for addr in ipv4.addresses.as_deref().unwrap_or_default() {
+            gen_ip_addr_cmd(script, iface.name(), addr);
+        }
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_ref().filter(|i| i.enabled) {
+        if ipv6.is_auto() {
+            log::warn!(
+                "DHCPv6 or IPv6 autoconf of interface {} is not supported \
+                by early boot script, ignoring",
+                iface.name()
+            );
+        }
+        for addr in ipv6.addresses.as_deref().unwrap_or_default() {
+            gen_ip_addr_cmd(script, iface.name(), addr);
+        }
+    }
+}
+
+fn gen_ip_addr_cmd(
+    script: &mut String,
+    iface_name: &str,
+    addr: &InterfaceIpAddr,
+) {
+    writeln!(
+        script,
+        "ip addr replace {}/{} dev {iface_name}",
+        addr.ip, addr.prefix_length
+    )
+    .ok();
+}
+
+fn gen_systemd_unit() -> String {
+    format!(
+        "[Unit]
+Description=Nmstate early boot network configuration
+DefaultDependencies=no
+Wants=network-pre.target
+Before=network-pre.target NetworkManager.service
+
+[Service]
+Type=oneshot
+RemainAfterExit=yes
+ExecStart={EARLY_BOOT_SCRIPT_PATH}
+
+[Install]
+WantedBy=network.target
+"
+    )
+}
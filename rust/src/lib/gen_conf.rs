@@ -2,7 +2,11 @@
 
 use std::collections::HashMap;
 
-use crate::{nm::nm_gen_conf, MergedNetworkState, NetworkState, NmstateError};
+use crate::{
+    early_boot::{early_boot_gen_conf, EARLY_BOOT_BACKEND},
+    nm::nm_gen_conf,
+    MergedNetworkState, NetworkState, NmstateError,
+};
 
 impl NetworkState {
     /// Generate offline network configurations.
@@ -13,6 +17,10 @@ impl NetworkState {
     /// `Vec<(config_file_name, config_content>)>` as value.
     ///
     /// The backend name for NetworkManager is `NetworkManager`.
+    ///
+    /// When [NetworkState::set_gen_early_boot()] set to true, the
+    /// `iproute` backend will also be included holding
+    /// `nmstate-early-boot.sh` and `nmstate-early-boot.service`.
     pub fn gen_conf(
         &self,
     ) -> Result<HashMap<String, Vec<(String, String)>>, NmstateError> {
@@ -24,13 +32,21 @@ impl NetworkState {
             false, // memory only
         )?;
         ret.insert("NetworkManager".to_string(), nm_gen_conf(&merged_state)?);
+        if self.gen_early_boot {
+            ret.insert(
+                EARLY_BOOT_BACKEND.to_string(),
+                early_boot_gen_conf(&merged_state)?,
+            );
+        }
         Ok(ret)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Interface, InterfaceType, Interfaces};
+    use crate::{
+        ErrorKind, Interface, InterfaceType, Interfaces, NetworkState,
+    };
 
     #[test]
     fn test_gen_conf_change_unknown_to_eth() {
@@ -56,4 +72,78 @@ mod tests {
             panic!("Expecting ethernet interface");
         }
     }
+
+    #[test]
+    fn test_gen_conf_early_boot() {
+        let mut net_state: NetworkState = serde_yaml::from_str(
+            r#"---
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  mtu: 9000
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+- name: eth1
+  type: ethernet
+  state: up
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond99
+    next-hop-address: 192.0.2.254
+"#,
+        )
+        .unwrap();
+        net_state.set_gen_early_boot(true);
+
+        let confs = net_state.gen_conf().unwrap();
+        let early_boot_confs = confs.get("iproute").unwrap();
+        let script = &early_boot_confs[0].1;
+
+        assert_eq!(early_boot_confs[0].0, "nmstate-early-boot.sh");
+        assert_eq!(early_boot_confs[1].0, "nmstate-early-boot.service");
+        assert!(script.contains(
+            "ip link show bond99 >/dev/null 2>&1 || \
+            ip link add bond99 type bond mode active-backup"
+        ));
+        assert!(script.contains("ip link set bond99 mtu 9000"));
+        assert!(script.contains("ip link set eth1 master bond99"));
+        assert!(script.contains("ip addr replace 192.0.2.1/24 dev bond99"));
+        assert!(script
+            .contains("ip route replace 0.0.0.0/0 via 192.0.2.254 dev bond99"));
+        let bond_up = script.find("ip link set bond99 up").unwrap();
+        let eth_up = script.find("ip link set eth1 up").unwrap();
+        assert!(bond_up < eth_up);
+    }
+
+    #[test]
+    fn test_gen_conf_early_boot_shell_unsafe_name() {
+        let mut net_state: NetworkState = serde_yaml::from_str(
+            r#"---
+interfaces:
+- name: "dummy0;reboot"
+  type: dummy
+  state: up
+"#,
+        )
+        .unwrap();
+        net_state.set_gen_early_boot(true);
+
+        let result = net_state.gen_conf();
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+            assert!(e.msg().contains("dummy0;reboot"));
+        }
+    }
 }
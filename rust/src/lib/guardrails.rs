@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, MergedInterfaces, NmstateError};
+
+#[cfg(feature = "query_apply")]
+const HOST_GUARDRAILS_FILE: &str = "/etc/nmstate/guardrails.yml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Host level constraints enforced against desired state when merging with
+/// current state, any violation fails the apply.
+/// When not defined by [crate::NetworkState::set_guardrails()],
+/// [crate::NetworkState::apply()] loads them from
+/// `/etc/nmstate/guardrails.yml` if exists.
+///
+/// Example yaml output of guardrails:
+/// ```yml
+/// interfaces:
+/// - name: mgmt0
+///   deny-delete: true
+/// - name: uplink*
+///   min-mtu: 1500
+/// ```
+pub struct Guardrails {
+    #[serde(default)]
+    /// Constraints of interfaces.
+    pub interfaces: Vec<InterfaceGuardrail>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Constraints of interfaces matching specified name.
+pub struct InterfaceGuardrail {
+    /// Interface name. The `*` matches any characters, for example
+    /// `uplink*` matches `uplink0` and `uplink1`.
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When set to true, marking existing interface as `absent` or `down`
+    /// is refused.
+    pub deny_delete: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Minimum MTU allowed in desired state.
+    pub min_mtu: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Maximum MTU allowed in desired state.
+    pub max_mtu: Option<u64>,
+}
+
+impl Guardrails {
+    /// Wrapping function of [serde_yaml::from_str()] with error mapped to
+    /// [NmstateError].
+    pub fn new_from_yaml(content: &str) -> Result<Self, NmstateError> {
+        match serde_yaml::from_str(content) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid guardrails: {e}"),
+            )),
+        }
+    }
+
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn load_host_guardrails() -> Result<Option<Self>, NmstateError> {
+        let content = match std::fs::read_to_string(HOST_GUARDRAILS_FILE) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(e) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Failed to read {HOST_GUARDRAILS_FILE}: {e}"),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!("Loading guardrails from {HOST_GUARDRAILS_FILE}");
+        Self::new_from_yaml(&content).map(Some)
+    }
+
+    pub(crate) fn check(
+        &self,
+        merged_ifaces: &MergedInterfaces,
+    ) -> Result<(), NmstateError> {
+        let mut violations = Vec::new();
+        let mut ifaces: Vec<_> = merged_ifaces
+            .kernel_ifaces
+            .values()
+            .chain(merged_ifaces.user_ifaces.values())
+            .filter_map(|i| {
+                i.desired.as_ref().map(|d| (d, i.current.is_some()))
+            })
+            .collect();
+        ifaces.sort_unstable_by_key(|(i, _)| (i.name(), i.iface_type()));
+
+        for (iface, exists) in ifaces {
+            for rule in self
+                .interfaces
+                .iter()
+                .filter(|r| name_match(r.name.as_str(), iface.name()))
+            {
+                if rule.deny_delete == Some(true)
+                    && exists
+                    && (iface.is_absent() || iface.is_down())
+                {
+                    violations.push(format!(
+                        "interface {} is protected from deletion by \
+                        guardrail {}",
+                        iface.name(),
+                        rule.name
+                    ));
+                }
+                if !iface.is_up() {
+                    continue;
+                }
+                if let Some(mtu) = iface.base_iface().mtu {
+                    if let Some(min_mtu) = rule.min_mtu {
+                        if mtu < min_mtu {
+                            violations.push(format!(
+                                "interface {} MTU {mtu} is smaller than \
+                                {min_mtu} required by guardrail {}",
+                                iface.name(),
+                                rule.name
+                            ));
+                        }
+                    }
+                    if let Some(max_mtu) = rule.max_mtu {
+                        if mtu > max_mtu {
+                            violations.push(format!(
+                                "interface {} MTU {mtu} is bigger than \
+                                {max_mtu} allowed by guardrail {}",
+                                iface.name(),
+                                rule.name
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Guardrail violation: {}", violations.join("; ")),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+// Match name against pattern where `*` matches any characters including
+// empty.
+fn name_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut remain = match name.strip_prefix(first) {
+        Some(r) => r,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    match parts.split_last() {
+        None => remain.is_empty(),
+        Some((last, middles)) => {
+            for middle in middles {
+                match remain.find(middle) {
+                    Some(pos) => remain = &remain[pos + middle.len()..],
+                    None => return false,
+                }
+            }
+            remain.len() >= last.len() && remain.ends_with(last)
+        }
+    }
+}
@@ -21,6 +21,17 @@ use crate::NetworkState;
 ///       identity: client.example.org
 ///       private-key: /etc/pki/802-1x-test/client.example.org.key
 ///       private-key-password: password
+///   - name: eth2
+///     type: ethernet
+///     state: up
+///     802.1x:
+///       ca-cert: /etc/pki/802-1x-test/ca.crt
+///       client-cert: pkcs11:token=nmstate;object=client
+///       eap-methods:
+///         - tls
+///       identity: client.example.org
+///       private-key: pkcs11:token=nmstate;object=client
+///       enrollment-command: /usr/libexec/enroll-8021x
 /// ```
 pub struct Ieee8021XConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -29,18 +40,30 @@ pub struct Ieee8021XConfig {
     /// Deserialize and serialize from/to `eap-methods`.
     pub eap: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// File path or PKCS#11 URI(starting with `pkcs11:`) of private key.
     /// Deserialize and serialize from/to `private-key`.
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// File path or PKCS#11 URI(starting with `pkcs11:`) of client
+    /// certificate.
     /// Deserialize and serialize from/to `client-cert`.
     pub client_cert: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// File path or PKCS#11 URI(starting with `pkcs11:`) of CA certificate.
     /// Deserialize and serialize from/to `ca-cert`.
     pub ca_cert: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Deserialize and serialize from/to `private-key-password`.
     /// Replaced to `<_password_hid_by_nmstate>` when querying.
     pub private_key_password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Shell command executed before creating the connection profile when
+    /// applying, intended to enroll the certificate and private key files
+    /// referred above. The interface name is stored in `NMSTATE_INTERFACE`
+    /// environment variable, apply fails if command exits with non-zero.
+    /// Not stored by backend, hence always `None` when querying.
+    /// Deserialize and serialize from/to `enrollment-command`.
+    pub enrollment_command: Option<String>,
 }
 
 impl Ieee8021XConfig {
@@ -6,10 +6,15 @@ use std::iter::FromIterator;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    BaseInterface, BondInterface, DummyInterface, ErrorKind, EthernetInterface,
-    InfiniBandInterface, LinuxBridgeInterface, LoopbackInterface,
-    MacVlanInterface, MacVtapInterface, NmstateError, OvsBridgeInterface,
-    OvsInterface, VlanInterface, VrfInterface, VxlanInterface,
+    BaseInterface, BatadvInterface, BondInterface, CanInterface,
+    DummyInterface, ErrorKind, EthernetInterface, GeneveInterface,
+    GreInterface, GreTapInterface, GsmInterface, GtpInterface,
+    InfiniBandInterface, IpIpInterface, IpVlanInterface, L2tpv3Interface,
+    LinuxBridgeInterface, LoopbackInterface, MacSecInterface, MacVlanInterface,
+    MacVtapInterface, NetkitInterface, NmstateError, OvsBridgeInterface,
+    OvsInterface, PppoeInterface, SitInterface, TunInterface, VcanInterface,
+    VlanInterface, VrfInterface, Vti6Interface, VtiInterface, VxlanInterface,
+    WifiInterface, WireGuardInterface, XfrmInterface,
 };
 
 use crate::state::merge_json_value;
@@ -60,6 +65,69 @@ pub enum InterfaceType {
     /// [IP over InfiniBand interface](https://docs.kernel.org/infiniband/ipoib.html)
     /// Deserialize and serialize from/to 'infiniband'.
     InfiniBand,
+    /// [WireGuard tunnel interface](https://www.wireguard.com/)
+    /// Deserialize and serialize from/to 'wireguard'.
+    WireGuard,
+    /// [MACsec interface](https://en.wikipedia.org/wiki/IEEE_802.1AE)
+    /// Deserialize and serialize from/to 'macsec'.
+    MacSec,
+    /// [GRE tunnel interface](https://en.wikipedia.org/wiki/Generic_Routing_Encapsulation)
+    /// Deserialize and serialize from/to 'gre'.
+    Gre,
+    /// GRE tunnel interface carrying ethernet frames.
+    /// Deserialize and serialize from/to 'gretap'.
+    GreTap,
+    /// IPv4 over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'ipip'.
+    IpIp,
+    /// IPv6 over IPv4 tunnel interface.
+    /// Deserialize and serialize from/to 'sit'.
+    Sit,
+    /// [Geneve tunnel interface](https://datatracker.ietf.org/doc/html/rfc8926)
+    /// Deserialize and serialize from/to 'geneve'.
+    Geneve,
+    /// GPRS Tunnelling Protocol User Plane(GTP-U) interface.
+    /// Deserialize and serialize from/to 'gtp'.
+    Gtp,
+    /// Linux kernel XFRM interface for route-based IPsec.
+    /// Deserialize and serialize from/to 'xfrm'.
+    Xfrm,
+    /// Linux kernel TUN/TAP interface.
+    /// Deserialize and serialize from/to 'tun'.
+    Tun,
+    /// Controller Area Network(CAN) interface.
+    /// Deserialize and serialize from/to 'can'.
+    Can,
+    /// Virtual Controller Area Network(vcan) interface.
+    /// Deserialize and serialize from/to 'vcan'.
+    Vcan,
+    /// Point-to-Point Protocol over Ethernet(PPPoE) interface.
+    /// Deserialize and serialize from/to 'pppoe'.
+    Pppoe,
+    /// Wi-Fi interface in station mode.
+    /// Deserialize and serialize from/to 'wifi'.
+    Wifi,
+    /// GSM/UMTS/LTE mobile broadband(WWAN) modem.
+    /// Deserialize from 'gsm' or 'wwan', serialize to 'gsm'.
+    Gsm,
+    /// IPv4 virtual tunnel interface.
+    /// Deserialize and serialize from/to 'vti'.
+    Vti,
+    /// IPv6 virtual tunnel interface.
+    /// Deserialize and serialize from/to 'vti6'.
+    Vti6,
+    /// Linux kernel IP VLAN interface.
+    /// Deserialize and serialize from/to 'ipvlan'.
+    IpVlan,
+    /// L2TPv3 ethernet pseudowire interface.
+    /// Deserialize and serialize from/to 'l2tpv3'.
+    L2tpv3,
+    /// Linux kernel netkit interface.
+    /// Deserialize and serialize from/to 'netkit'.
+    Netkit,
+    /// B.A.T.M.A.N. advanced mesh interface.
+    /// Deserialize and serialize from/to 'batadv'.
+    Batadv,
     /// Unknown interface.
     Unknown,
     /// Reserved for future use.
@@ -89,6 +157,27 @@ impl From<&str> for InterfaceType {
             "vrf" => InterfaceType::Vrf,
             "vxlan" => InterfaceType::Vxlan,
             "infiniband" => InterfaceType::InfiniBand,
+            "wireguard" => InterfaceType::WireGuard,
+            "macsec" => InterfaceType::MacSec,
+            "gre" => InterfaceType::Gre,
+            "gretap" => InterfaceType::GreTap,
+            "ipip" => InterfaceType::IpIp,
+            "sit" => InterfaceType::Sit,
+            "geneve" => InterfaceType::Geneve,
+            "gtp" => InterfaceType::Gtp,
+            "xfrm" => InterfaceType::Xfrm,
+            "tun" => InterfaceType::Tun,
+            "can" => InterfaceType::Can,
+            "vcan" => InterfaceType::Vcan,
+            "pppoe" => InterfaceType::Pppoe,
+            "wifi" => InterfaceType::Wifi,
+            "gsm" | "wwan" => InterfaceType::Gsm,
+            "vti" => InterfaceType::Vti,
+            "vti6" => InterfaceType::Vti6,
+            "ipvlan" => InterfaceType::IpVlan,
+            "l2tpv3" => InterfaceType::L2tpv3,
+            "netkit" => InterfaceType::Netkit,
+            "batadv" => InterfaceType::Batadv,
             "unknown" => InterfaceType::Unknown,
             _ => InterfaceType::Other(s.to_string()),
         }
@@ -115,6 +204,27 @@ impl std::fmt::Display for InterfaceType {
                 InterfaceType::Vrf => "vrf",
                 InterfaceType::Vxlan => "vxlan",
                 InterfaceType::InfiniBand => "infiniband",
+                InterfaceType::WireGuard => "wireguard",
+                InterfaceType::MacSec => "macsec",
+                InterfaceType::Gre => "gre",
+                InterfaceType::GreTap => "gretap",
+                InterfaceType::IpIp => "ipip",
+                InterfaceType::Sit => "sit",
+                InterfaceType::Geneve => "geneve",
+                InterfaceType::Gtp => "gtp",
+                InterfaceType::Xfrm => "xfrm",
+                InterfaceType::Tun => "tun",
+                InterfaceType::Can => "can",
+                InterfaceType::Vcan => "vcan",
+                InterfaceType::Pppoe => "pppoe",
+                InterfaceType::Wifi => "wifi",
+                InterfaceType::Gsm => "gsm",
+                InterfaceType::Vti => "vti",
+                InterfaceType::Vti6 => "vti6",
+                InterfaceType::IpVlan => "ipvlan",
+                InterfaceType::L2tpv3 => "l2tpv3",
+                InterfaceType::Netkit => "netkit",
+                InterfaceType::Batadv => "batadv",
                 InterfaceType::Unknown => "unknown",
                 InterfaceType::Other(ref s) => s,
             }
@@ -169,7 +279,7 @@ impl InterfaceType {
 /// The state of interface
 pub enum InterfaceState {
     /// Interface is up and running.
-    /// Deserialize and serialize from/to 'down'.
+    /// Deserialize and serialize from/to 'up'.
     Up,
     /// For apply action, down means configuration still exist but
     /// deactivate. The virtual interface will be removed and other interface
@@ -177,13 +287,19 @@ pub enum InterfaceState {
     /// Deserialize and serialize from/to 'down'.
     Down,
     /// Only for apply action to remove configuration and deactivate the
-    /// interface.
+    /// interface. Unlike `down`, the persistent configuration is also
+    /// removed.
+    /// Deserialize and serialize from/to 'absent'.
     Absent,
     /// Unknown state.
     Unknown,
     /// Interface is not managed by backend. For apply action, interface marked
     /// as ignore will not be changed and will not cause verification failure
-    /// neither.
+    /// neither. Ignored interface is never deleted, never chosen to hold DNS
+    /// configuration and its backend configuration (e.g. NetworkManager
+    /// profile) is left untouched. Desired routes and route rules pointing to
+    /// ignored interface are not applied, desired IPv6 link local DNS server
+    /// pointing to ignored interface is rejected.
     /// When desired controller listed currently ignored interfaces as its
     /// port, nmstate will automatically convert these ignored interfaces from
     /// 'state: ignore' to 'state: up' only when:
@@ -290,6 +406,48 @@ pub enum Interface {
     InfiniBand(InfiniBandInterface),
     /// Linux loopback interface
     Loopback(LoopbackInterface),
+    /// [WireGuard tunnel interface](https://www.wireguard.com/)
+    WireGuard(WireGuardInterface),
+    /// [MACsec interface](https://en.wikipedia.org/wiki/IEEE_802.1AE)
+    MacSec(MacSecInterface),
+    /// [GRE tunnel interface](https://en.wikipedia.org/wiki/Generic_Routing_Encapsulation)
+    Gre(GreInterface),
+    /// GRE tunnel interface carrying ethernet frames
+    GreTap(GreTapInterface),
+    /// IPv4 over IPv4 tunnel interface
+    IpIp(IpIpInterface),
+    /// IPv6 over IPv4 tunnel interface
+    Sit(SitInterface),
+    /// [Geneve tunnel interface](https://datatracker.ietf.org/doc/html/rfc8926)
+    Geneve(GeneveInterface),
+    /// GPRS Tunnelling Protocol User Plane(GTP-U) interface
+    Gtp(GtpInterface),
+    /// Linux kernel XFRM interface for route-based IPsec
+    Xfrm(XfrmInterface),
+    /// Linux kernel TUN/TAP interface
+    Tun(TunInterface),
+    /// Controller Area Network(CAN) interface
+    Can(CanInterface),
+    /// Virtual Controller Area Network(vcan) interface
+    Vcan(VcanInterface),
+    /// Point-to-Point Protocol over Ethernet(PPPoE) interface
+    Pppoe(PppoeInterface),
+    /// Wi-Fi interface in station mode
+    Wifi(WifiInterface),
+    /// GSM/UMTS/LTE mobile broadband(WWAN) modem
+    Gsm(GsmInterface),
+    /// IPv4 virtual tunnel interface
+    Vti(VtiInterface),
+    /// IPv6 virtual tunnel interface
+    Vti6(Vti6Interface),
+    /// Linux kernel IP VLAN interface
+    IpVlan(IpVlanInterface),
+    /// L2TPv3 ethernet pseudowire interface
+    L2tpv3(L2tpv3Interface),
+    /// Linux kernel netkit interface
+    Netkit(NetkitInterface),
+    /// B.A.T.M.A.N. advanced mesh interface
+    Batadv(BatadvInterface),
 }
 
 impl<'de> Deserialize<'de> for Interface {
@@ -391,6 +549,111 @@ impl<'de> Deserialize<'de> for Interface {
                     .map_err(serde::de::Error::custom)?;
                 Ok(Interface::Loopback(inner))
             }
+            Some(InterfaceType::WireGuard) => {
+                let inner = WireGuardInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::WireGuard(inner))
+            }
+            Some(InterfaceType::MacSec) => {
+                let inner = MacSecInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::MacSec(inner))
+            }
+            Some(InterfaceType::Gre) => {
+                let inner = GreInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Gre(inner))
+            }
+            Some(InterfaceType::GreTap) => {
+                let inner = GreTapInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::GreTap(inner))
+            }
+            Some(InterfaceType::IpIp) => {
+                let inner = IpIpInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpIp(inner))
+            }
+            Some(InterfaceType::Sit) => {
+                let inner = SitInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Sit(inner))
+            }
+            Some(InterfaceType::Geneve) => {
+                let inner = GeneveInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Geneve(inner))
+            }
+            Some(InterfaceType::Gtp) => {
+                let inner = GtpInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Gtp(inner))
+            }
+            Some(InterfaceType::Xfrm) => {
+                let inner = XfrmInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Xfrm(inner))
+            }
+            Some(InterfaceType::Tun) => {
+                let inner = TunInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Tun(inner))
+            }
+            Some(InterfaceType::Can) => {
+                let inner = CanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Can(inner))
+            }
+            Some(InterfaceType::Vcan) => {
+                let inner = VcanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Vcan(inner))
+            }
+            Some(InterfaceType::Pppoe) => {
+                let inner = PppoeInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Pppoe(inner))
+            }
+            Some(InterfaceType::Wifi) => {
+                let inner = WifiInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Wifi(inner))
+            }
+            Some(InterfaceType::Gsm) => {
+                let inner = GsmInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Gsm(inner))
+            }
+            Some(InterfaceType::Vti) => {
+                let inner = VtiInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Vti(inner))
+            }
+            Some(InterfaceType::Vti6) => {
+                let inner = Vti6Interface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Vti6(inner))
+            }
+            Some(InterfaceType::IpVlan) => {
+                let inner = IpVlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpVlan(inner))
+            }
+            Some(InterfaceType::L2tpv3) => {
+                let inner = L2tpv3Interface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::L2tpv3(inner))
+            }
+            Some(InterfaceType::Netkit) => {
+                let inner = NetkitInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Netkit(inner))
+            }
+            Some(InterfaceType::Batadv) => {
+                let inner = BatadvInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Batadv(inner))
+            }
             Some(iface_type) => {
                 log::warn!("Unsupported interface type {}", iface_type);
                 let inner = UnknownInterface::deserialize(v)
@@ -449,6 +712,111 @@ impl Interface {
                 new_iface.base = iface.base.clone_name_type_only();
                 Self::Vxlan(new_iface)
             }
+            Self::WireGuard(iface) => {
+                let mut new_iface = WireGuardInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::WireGuard(new_iface)
+            }
+            Self::MacSec(iface) => {
+                let mut new_iface = MacSecInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::MacSec(new_iface)
+            }
+            Self::Gre(iface) => {
+                let mut new_iface = GreInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Gre(new_iface)
+            }
+            Self::GreTap(iface) => {
+                let mut new_iface = GreTapInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::GreTap(new_iface)
+            }
+            Self::IpIp(iface) => {
+                let mut new_iface = IpIpInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::IpIp(new_iface)
+            }
+            Self::Sit(iface) => {
+                let mut new_iface = SitInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Sit(new_iface)
+            }
+            Self::Geneve(iface) => {
+                let mut new_iface = GeneveInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Geneve(new_iface)
+            }
+            Self::Gtp(iface) => {
+                let mut new_iface = GtpInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Gtp(new_iface)
+            }
+            Self::Xfrm(iface) => {
+                let mut new_iface = XfrmInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Xfrm(new_iface)
+            }
+            Self::Tun(iface) => {
+                let mut new_iface = TunInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Tun(new_iface)
+            }
+            Self::Can(iface) => {
+                let mut new_iface = CanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Can(new_iface)
+            }
+            Self::Vcan(iface) => {
+                let mut new_iface = VcanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Vcan(new_iface)
+            }
+            Self::Pppoe(iface) => {
+                let mut new_iface = PppoeInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Pppoe(new_iface)
+            }
+            Self::Wifi(iface) => {
+                let mut new_iface = WifiInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Wifi(new_iface)
+            }
+            Self::Gsm(iface) => {
+                let mut new_iface = GsmInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Gsm(new_iface)
+            }
+            Self::Vti(iface) => {
+                let mut new_iface = VtiInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Vti(new_iface)
+            }
+            Self::Vti6(iface) => {
+                let mut new_iface = Vti6Interface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Vti6(new_iface)
+            }
+            Self::IpVlan(iface) => {
+                let mut new_iface = IpVlanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::IpVlan(new_iface)
+            }
+            Self::L2tpv3(iface) => {
+                let mut new_iface = L2tpv3Interface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::L2tpv3(new_iface)
+            }
+            Self::Netkit(iface) => {
+                let mut new_iface = NetkitInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Netkit(new_iface)
+            }
+            Self::Batadv(iface) => {
+                let mut new_iface = BatadvInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Batadv(new_iface)
+            }
             Self::Dummy(iface) => {
                 let mut new_iface = DummyInterface::new();
                 new_iface.base = iface.base.clone_name_type_only();
@@ -557,14 +925,21 @@ impl Interface {
     /// Since loopback interface should not be deleted from system,
     /// hence we consider loopback interface as __not__ virtual interface.
     /// Unknown interface is considered as __not__ virtual interface.
+    /// InfiniBand interface is virtual only when it is a P-Key partition.
+    /// CAN interface is considered as __not__ virtual interface.
     pub fn is_virtual(&self) -> bool {
-        !matches!(
-            self,
+        match self {
+            // The P-Key partition is a virtual interface on top of the
+            // base InfiniBand interface.
+            Self::InfiniBand(iface) => iface.is_pkey_iface(),
             Self::Ethernet(_)
-                | Self::Unknown(_)
-                | Self::InfiniBand(_)
-                | Self::Loopback(_)
-        )
+            | Self::Wifi(_)
+            | Self::Gsm(_)
+            | Self::Can(_)
+            | Self::Unknown(_)
+            | Self::Loopback(_) => false,
+            _ => true,
+        }
     }
 
     /// Whether current interface only lives when its control exists.
@@ -590,6 +965,27 @@ impl Interface {
             Self::Vrf(iface) => &iface.base,
             Self::InfiniBand(iface) => &iface.base,
             Self::Loopback(iface) => &iface.base,
+            Self::WireGuard(iface) => &iface.base,
+            Self::MacSec(iface) => &iface.base,
+            Self::Gre(iface) => &iface.base,
+            Self::GreTap(iface) => &iface.base,
+            Self::IpIp(iface) => &iface.base,
+            Self::Sit(iface) => &iface.base,
+            Self::Geneve(iface) => &iface.base,
+            Self::Gtp(iface) => &iface.base,
+            Self::Xfrm(iface) => &iface.base,
+            Self::Tun(iface) => &iface.base,
+            Self::Can(iface) => &iface.base,
+            Self::Vcan(iface) => &iface.base,
+            Self::Pppoe(iface) => &iface.base,
+            Self::Wifi(iface) => &iface.base,
+            Self::Gsm(iface) => &iface.base,
+            Self::Vti(iface) => &iface.base,
+            Self::Vti6(iface) => &iface.base,
+            Self::IpVlan(iface) => &iface.base,
+            Self::L2tpv3(iface) => &iface.base,
+            Self::Netkit(iface) => &iface.base,
+            Self::Batadv(iface) => &iface.base,
             Self::Unknown(iface) => &iface.base,
         }
     }
@@ -609,6 +1005,27 @@ impl Interface {
             Self::Vrf(iface) => &mut iface.base,
             Self::InfiniBand(iface) => &mut iface.base,
             Self::Loopback(iface) => &mut iface.base,
+            Self::WireGuard(iface) => &mut iface.base,
+            Self::MacSec(iface) => &mut iface.base,
+            Self::Gre(iface) => &mut iface.base,
+            Self::GreTap(iface) => &mut iface.base,
+            Self::IpIp(iface) => &mut iface.base,
+            Self::Sit(iface) => &mut iface.base,
+            Self::Geneve(iface) => &mut iface.base,
+            Self::Gtp(iface) => &mut iface.base,
+            Self::Xfrm(iface) => &mut iface.base,
+            Self::Tun(iface) => &mut iface.base,
+            Self::Can(iface) => &mut iface.base,
+            Self::Vcan(iface) => &mut iface.base,
+            Self::Pppoe(iface) => &mut iface.base,
+            Self::Wifi(iface) => &mut iface.base,
+            Self::Gsm(iface) => &mut iface.base,
+            Self::Vti(iface) => &mut iface.base,
+            Self::Vti6(iface) => &mut iface.base,
+            Self::IpVlan(iface) => &mut iface.base,
+            Self::L2tpv3(iface) => &mut iface.base,
+            Self::Netkit(iface) => &mut iface.base,
+            Self::Batadv(iface) => &mut iface.base,
             Self::Unknown(iface) => &mut iface.base,
         }
     }
@@ -635,6 +1052,18 @@ impl Interface {
         }
     }
 
+    pub(crate) fn include_current_ports(&mut self, current: &Self) {
+        match (self, current) {
+            (Self::LinuxBridge(iface), Self::LinuxBridge(cur_iface)) => {
+                iface.include_current_ports(cur_iface)
+            }
+            (Self::OvsBridge(iface), Self::OvsBridge(cur_iface)) => {
+                iface.include_current_ports(cur_iface)
+            }
+            _ => (),
+        }
+    }
+
     // This function is for pre-edit clean up and check on current, `for_apply`,
     // `for_verify` states.
     //
@@ -657,6 +1086,27 @@ impl Interface {
             Interface::MacVlan(iface) => iface.sanitize(is_desired)?,
             Interface::MacVtap(iface) => iface.sanitize(is_desired)?,
             Interface::Loopback(iface) => iface.sanitize(is_desired)?,
+            Interface::InfiniBand(iface) => iface.sanitize(is_desired)?,
+            Interface::Vxlan(iface) => iface.sanitize(),
+            Interface::WireGuard(iface) => iface.sanitize(is_desired)?,
+            Interface::MacSec(iface) => iface.sanitize(is_desired)?,
+            Interface::Gre(iface) => iface.sanitize(is_desired)?,
+            Interface::GreTap(iface) => iface.sanitize(is_desired)?,
+            Interface::IpIp(iface) => iface.sanitize(is_desired)?,
+            Interface::Sit(iface) => iface.sanitize(is_desired)?,
+            Interface::Geneve(iface) => iface.sanitize(is_desired)?,
+            Interface::Gtp(iface) => iface.sanitize(is_desired)?,
+            Interface::Xfrm(iface) => iface.sanitize(is_desired)?,
+            Interface::Batadv(iface) => iface.sanitize(is_desired)?,
+            Interface::Netkit(iface) => iface.sanitize(is_desired)?,
+            Interface::L2tpv3(iface) => iface.sanitize(is_desired)?,
+            Interface::IpVlan(iface) => iface.sanitize(is_desired)?,
+            Interface::Vti6(iface) => iface.sanitize(is_desired)?,
+            Interface::Vti(iface) => iface.sanitize(is_desired)?,
+            Interface::Gsm(iface) => iface.sanitize(is_desired)?,
+            Interface::Wifi(iface) => iface.sanitize(is_desired)?,
+            Interface::Pppoe(iface) => iface.sanitize(is_desired)?,
+            Interface::Can(iface) => iface.sanitize(is_desired)?,
             _ => (),
         }
         Ok(())
@@ -669,7 +1119,11 @@ impl Interface {
             Interface::OvsInterface(ovs) => ovs.parent(),
             Interface::MacVlan(vlan) => vlan.parent(),
             Interface::MacVtap(vtap) => vtap.parent(),
+            Interface::IpVlan(ipvlan) => ipvlan.parent(),
             Interface::InfiniBand(ib) => ib.parent(),
+            Interface::MacSec(macsec) => macsec.parent(),
+            Interface::Xfrm(xfrm) => xfrm.parent(),
+            Interface::Pppoe(pppoe) => pppoe.parent(),
             _ => None,
         }
     }
@@ -719,9 +1173,14 @@ pub(crate) struct MergedInterface {
 
 impl MergedInterface {
     pub(crate) fn new(
-        desired: Option<Interface>,
+        mut desired: Option<Interface>,
         current: Option<Interface>,
     ) -> Result<Self, NmstateError> {
+        if let (Some(des_iface), Some(cur_iface)) =
+            (desired.as_mut(), current.as_ref())
+        {
+            des_iface.include_current_ports(cur_iface);
+        }
         let mut ret = Self {
             for_verify: desired.clone(),
             for_apply: desired.clone(),
@@ -772,6 +1231,7 @@ impl MergedInterface {
         self.preserve_current_controller_info();
         self.post_inter_ifaces_process_base_iface()?;
         self.post_inter_ifaces_process_sriov()?;
+        self.post_inter_ifaces_process_usb_gadget();
         self.post_inter_ifaces_process_vrf()?;
         self.post_inter_ifaces_process_bond()?;
 
@@ -834,6 +1294,17 @@ impl MergedInterface {
         }
     }
 
+    pub(crate) fn set_inherited_mtu(&mut self, mtu: u64) {
+        self.mark_as_changed();
+        self.merged.base_iface_mut().mtu = Some(mtu);
+        if let Some(apply_iface) = self.for_apply.as_mut() {
+            apply_iface.base_iface_mut().mtu = Some(mtu);
+        }
+        if let Some(verify_iface) = self.for_verify.as_mut() {
+            verify_iface.base_iface_mut().mtu = Some(mtu);
+        }
+    }
+
     // Return two list, first is changed port attached to specified interface,
     // second is changed port detached from specified interface.
     pub(crate) fn get_changed_ports(&self) -> Option<(Vec<&str>, Vec<&str>)> {
@@ -892,20 +1363,25 @@ impl MergedInterface {
             .cloned()
             .collect();
 
-        // Linux Bridge might have changed configure its port configuration with
-        // port name list unchanged.
-        // In this case, we should ask LinuxBridgeInterface to generate a list
-        // of configure changed port.
-        if let (
-            Interface::LinuxBridge(des_br_iface),
-            Some(Interface::LinuxBridge(cur_br_iface)),
-        ) = (desired_iface, self.current.as_ref())
+        // Linux Bridge and Bond might have changed configure its port
+        // configuration with port name list unchanged.
+        // In this case, we should ask LinuxBridgeInterface or BondInterface to
+        // generate a list of configure changed port.
+        let config_changed_ports = match (desired_iface, self.current.as_ref())
         {
-            for port_name in des_br_iface.get_config_changed_ports(cur_br_iface)
-            {
-                if !chg_attached_ports.contains(&port_name) {
-                    chg_attached_ports.push(port_name);
-                }
+            (
+                Interface::LinuxBridge(des_br_iface),
+                Some(Interface::LinuxBridge(cur_br_iface)),
+            ) => des_br_iface.get_config_changed_ports(cur_br_iface),
+            (
+                Interface::Bond(des_bond_iface),
+                Some(Interface::Bond(cur_bond_iface)),
+            ) => des_bond_iface.get_config_changed_ports(cur_bond_iface),
+            _ => Vec::new(),
+        };
+        for port_name in config_changed_ports {
+            if !chg_attached_ports.contains(&port_name) {
+                chg_attached_ports.push(port_name);
             }
         }
 
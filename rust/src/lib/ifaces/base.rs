@@ -1,14 +1,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ErrorKind, EthtoolConfig, Ieee8021XConfig, InterfaceIpv4, InterfaceIpv6,
-    InterfaceState, InterfaceType, LldpConfig, MergedInterface, MptcpConfig,
-    NmstateError, OvsDbIfaceConfig, RouteEntry, WaitIp,
+    ErrorKind, EthtoolConfig, Ieee8021XConfig, InterfaceConnectivity,
+    InterfaceIpv4, InterfaceIpv6, InterfaceState, InterfaceType, LinkStats,
+    LldpConfig, MergedInterface, MptcpConfig, NmstateError, OvsDbIfaceConfig,
+    PciHints, RouteEntry, WaitIp,
 };
 
 const MINIMUM_IPV6_MTU: u64 = 1280;
+// NetworkManager limits the user data key to 255 bytes including the
+// `nmstate.interface.label.` prefix.
+const LABEL_KEY_MAX_LEN: usize = 200;
+// Kernel stores `napi_defer_hard_irqs` as signed integer.
+const NAPI_DEFER_HARD_IRQS_MAX: u32 = i32::MAX as u32;
 
 // TODO: Use prop_list to Serialize like InterfaceIpv4 did
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -49,20 +57,72 @@ pub struct BaseInterface {
     )]
     /// Maximum transmission unit.
     pub mtu: Option<u64>,
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Propagate the MTU of this interface to its ports and to the VLAN,
+    /// MAC VLAN, MAC VTAP and IP VLAN interfaces using it as parent. Interfaces with
+    /// MTU defined in desired state are not overridden.
+    /// Ignored during serializing, querying will show the effective MTU of
+    /// each interface instead.
+    /// Deserialize from `mtu-propagate`.
+    pub mtu_propagate: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    /// Minimum MTU allowed. Ignored during apply.
+    /// Minimum MTU allowed by kernel. Ignored during apply, desired MTU is
+    /// validated against it.
     /// Serialize and deserialize to/from `min-mtu`.
     pub min_mtu: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    /// Maximum MTU allowed. Ignored during apply.
+    /// Maximum MTU allowed by kernel. Ignored during apply, desired MTU is
+    /// validated against it. Not shown when kernel has no maximum MTU
+    /// limitation on this interface.
     /// Serialize and deserialize to/from `max-mtu`.
     pub max_mtu: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Link status and statistics. Ignored during apply.
+    /// Serialize and deserialize to/from `link-stats`.
+    pub link_stats: Option<LinkStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// NUMA and IRQ placement hints of the backing PCI device. Ignored during
+    /// apply.
+    /// Serialize and deserialize to/from `pci-hints`.
+    pub pci_hints: Option<PciHints>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Internet connectivity state checked by NetworkManager.
+    /// Ignored during apply.
+    pub connectivity: Option<InterfaceConnectivity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// Whether system should wait certain IP stack before considering
     /// network interface activated.
     /// Serialize and deserialize to/from `wait-ip`.
     pub wait_ip: Option<WaitIp>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Whether the network backend profile of this interface can be
+    /// activated on multiple devices at the same time.
+    /// Only supported by NetworkManager backend.
+    /// Serialize and deserialize to/from `multi-connect`.
+    pub multi_connect: Option<MultiConnect>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Names of users allowed to activate the network backend profile of
+    /// this interface, other users cannot see or use it. Empty list means
+    /// all users are allowed.
+    /// Only supported by NetworkManager backend.
+    pub permissions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// User data attached to this interface by upper level tools.
+    /// Stored in network backend profile, not available for kernel only mode.
+    pub metadata: Option<InterfaceMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// UUID of the network backend profile of this interface. When applying
+    /// with UUID different from current one, the profile is recreated with
+    /// the desired UUID, so that external tools referring the profile by UUID
+    /// are not affected by later applying.
+    /// Only supported by NetworkManager backend.
+    /// Serialize and deserialize to/from `profile-uuid`.
+    pub profile_uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// IPv4 information.
     /// Hided if interface is not allowed to hold IP information(e.g. port of
     /// bond is not allowed to hold IP information).
@@ -78,6 +138,12 @@ pub struct BaseInterface {
     /// and dynamic).
     pub mptcp: Option<MptcpConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Static IPv4(IGMP) or IPv6(MLD) multicast groups joined by this
+    /// interface. When applying, the desired list overrides the current one,
+    /// empty list means leaving all static joined groups.
+    /// Serialize and deserialize to/from `multicast-groups`.
+    pub multicast_groups: Option<Vec<std::net::IpAddr>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// Controller of the specified interface.
     /// Only valid for applying, `None` means no change, empty string means
     /// detach from current controller, please be advise, an error will trigger
@@ -91,13 +157,56 @@ pub struct BaseInterface {
     )]
     /// Whether kernel should skip check on package targeting MAC address and
     /// accept all packages, also known as promiscuous mode.
-    /// Serialize and deserialize to/from `accpet-all-mac-addresses`.
+    /// Serialize and deserialize to/from `accept-all-mac-addresses`.
     pub accept_all_mac_addresses: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Length of the transmit queue of this interface in packets.
+    /// Applied via netlink after the interface activated.
+    /// Serialize and deserialize to/from `txqueuelen`.
+    pub txqueuelen: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
+    /// Nanoseconds to defer the flushing of GRO packets and the re-enabling
+    /// of hardware interrupts after NAPI poll. 0 means flush at the end of
+    /// every NAPI poll. Applied via sysfs after the interface activated.
+    /// The on/off of GRO, GSO and TSO are controlled by `ethtool.feature`.
+    /// Serialize and deserialize to/from `gro-flush-timeout`.
+    pub gro_flush_timeout: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Number of consecutive empty NAPI polls before re-enabling hardware
+    /// interrupts, only effective when `gro-flush-timeout` is not 0.
+    /// Applied via sysfs after the interface activated.
+    /// Serialize and deserialize to/from `napi-defer-hard-irqs`.
+    pub napi_defer_hard_irqs: Option<u32>,
     #[serde(skip_serializing)]
     /// Copy the MAC address from specified interface.
     /// Ignored during serializing.
     /// Deserialize from `copy-mac-from`.
     pub copy_mac_from: Option<String>,
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Seconds to wait before deactivating or deleting this interface when
+    /// changing it to down or absent state. During the wait, the metric of
+    /// routes on this interface is raised to the highest so that traffic
+    /// could move to other paths(e.g. removed from ECMP routes) first.
+    /// Only supported by NetworkManager backend.
+    /// Ignored during serializing.
+    /// Deserialize from `drain-delay`.
+    pub drain_delay: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "ovs-db")]
     /// Interface specific OpenvSwitch database configurations.
     pub ovsdb: Option<OvsDbIfaceConfig>,
@@ -115,8 +224,8 @@ pub struct BaseInterface {
     /// TODO: internal use, hide it.
     pub controller_type: Option<InterfaceType>,
     // The interface lowest up_priority will be activated first.
-    // The up_priority should be its controller's up_priority
-    // plus one.
+    // The up_priority should be the maximum up_priority of its controller
+    // and parent plus one.
     // The 0 means top controller or no controller.
     #[serde(skip)]
     pub(crate) up_priority: u32,
@@ -130,6 +239,8 @@ impl BaseInterface {
     // Besides normal HashMap merging:
     //  * the IP stacks need extra care
     //  * `copy_mac_from` is skip_serializing
+    //  * `drain_delay` is skip_serializing
+    //  * `mtu_propagate` is skip_serializing
     //  * `permanent_mac_address` is skip_serializing
     pub(crate) fn special_merge(&mut self, desired: &Self, current: &Self) {
         if let Some(ipv4) = self.ipv4.as_mut() {
@@ -150,6 +261,8 @@ impl BaseInterface {
             self.permanent_mac_address = current.permanent_mac_address.clone();
         }
         self.copy_mac_from = desired.copy_mac_from.clone();
+        self.drain_delay = desired.drain_delay;
+        self.mtu_propagate = desired.mtu_propagate;
     }
 
     fn has_controller(&self) -> bool {
@@ -167,14 +280,6 @@ impl BaseInterface {
             || self.controller_type == Some(InterfaceType::Vrf)
     }
 
-    pub(crate) fn is_up_priority_valid(&self) -> bool {
-        if self.has_controller() {
-            self.up_priority != 0
-        } else {
-            true
-        }
-    }
-
     /// Create empty [BaseInterface] with state set to [InterfaceState::Up]
     pub fn new() -> Self {
         Self {
@@ -217,7 +322,11 @@ impl BaseInterface {
         self.permanent_mac_address = None;
         self.max_mtu = None;
         self.min_mtu = None;
+        self.link_stats = None;
+        self.pci_hints = None;
+        self.connectivity = None;
         self.copy_mac_from = None;
+        self.mtu_propagate = None;
 
         if let Some(ipv4_conf) = self.ipv4.as_mut() {
             ipv4_conf.sanitize(is_desired)?;
@@ -243,6 +352,85 @@ impl BaseInterface {
         if let Some(lldp_conf) = self.lldp.as_mut() {
             lldp_conf.sanitize();
         }
+        if let Some(groups) = self.multicast_groups.as_mut() {
+            if is_desired {
+                if let Some(group) = groups.iter().find(|g| !g.is_multicast()) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Multicast group {group} of interface {} is not \
+                            a multicast address",
+                            self.name.as_str()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            groups.sort_unstable();
+            groups.dedup();
+        }
+        if let Some(users) = self.permissions.as_mut() {
+            if is_desired {
+                if let Some(user) =
+                    users.iter().find(|u| u.is_empty() || u.contains(':'))
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid user name '{user}' in permissions of \
+                            interface {}",
+                            self.name.as_str()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            users.sort_unstable();
+            users.dedup();
+        }
+        if let Some(metadata) = self.metadata.as_ref() {
+            if is_desired {
+                metadata.sanitize(self.name.as_str())?;
+            }
+        }
+        if let Some(uuid) = self.profile_uuid.as_mut() {
+            match uuid::Uuid::parse_str(uuid) {
+                Ok(u) => *uuid = u.hyphenated().to_string(),
+                Err(e) => {
+                    if is_desired {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Invalid profile UUID '{uuid}' of interface \
+                                {}: {e}",
+                                self.name.as_str()
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        if is_desired {
+            if let Some(v) = self
+                .napi_defer_hard_irqs
+                .filter(|v| *v > NAPI_DEFER_HARD_IRQS_MAX)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The napi-defer-hard-irqs {v} of interface {} \
+                        exceeds the maximum value {NAPI_DEFER_HARD_IRQS_MAX}",
+                        self.name.as_str()
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
 
         if !self.can_have_ip() {
             self.wait_ip = None;
@@ -251,6 +439,104 @@ impl BaseInterface {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// How many devices a single network backend profile could be activated on.
+pub enum MultiConnect {
+    /// Use global default of network backend, normally [MultiConnect::Single].
+    /// Serialize and deserialize to/from `default`.
+    Default,
+    /// Profile can only be active on a single device at a time.
+    /// Serialize and deserialize to/from `single`.
+    Single,
+    /// Profile can be activated on multiple devices, but only when
+    /// activated manually, autoconnect will only activate it once.
+    /// Serialize and deserialize to/from `manual-multiple`.
+    ManualMultiple,
+    /// Profile can be activated on multiple devices, both manually and via
+    /// autoconnect.
+    /// Serialize and deserialize to/from `multiple`.
+    Multiple,
+}
+
+impl std::fmt::Display for MultiConnect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Default => "default",
+                Self::Single => "single",
+                Self::ManualMultiple => "manual-multiple",
+                Self::Multiple => "multiple",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// User data of interface which is only meaningful to upper level tools,
+/// nmstate just store and retrieve it.
+/// The example yaml output of [crate::NetworkState] with interface labels
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   metadata:
+///     labels:
+///       cluster.example.org/role: storage
+///       owner: operator-a
+/// ```
+pub struct InterfaceMetadata {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::serializer::option_map_sorted"
+    )]
+    /// Freeform key value pairs. When applying, the desired labels override
+    /// all the current labels, empty map means removing all labels.
+    /// The key should only contain ASCII alphanumeric, `-`, `_`, `.` and
+    /// `/`, and should not start or end with `.`.
+    pub labels: Option<HashMap<String, String>>,
+}
+
+impl InterfaceMetadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sanitize(&self, iface_name: &str) -> Result<(), NmstateError> {
+        for key in self.labels.iter().flat_map(|l| l.keys()) {
+            if key.is_empty()
+                || key.len() > LABEL_KEY_MAX_LEN
+                || key.starts_with('.')
+                || key.ends_with('.')
+                || key.contains("..")
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid label key '{key}' of interface {iface_name}, \
+                        only ASCII alphanumeric, '-', '_', '.' and '/' are \
+                        allowed with at most {LABEL_KEY_MAX_LEN} characters, \
+                        '.' is not allowed at the start or end or repeated"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
 fn default_state() -> InterfaceState {
     InterfaceState::Up
 }
@@ -266,38 +552,62 @@ impl MergedInterface {
         self.post_inter_ifaces_process_ip()?;
         self.post_inter_ifaces_process_mptcp()?;
         self.post_inter_ifaces_process_ethtool();
+        self.validate_ethtool_channels()?;
+        self.validate_ethtool_coalesce_queues()?;
+        self.sanitize_ethtool_flow_rules()?;
         self.validate_mtu()?;
         self.validate_can_have_ip()?;
         Ok(())
     }
 
+    // Validate the MTU to apply against the MTU range reported by kernel, the
+    // MTU inherited via `mtu-propagate` is also included.
     fn validate_mtu(&self) -> Result<(), NmstateError> {
-        if let (Some(desired), Some(current)) = (
-            self.desired.as_ref().map(|i| i.base_iface()),
+        if let (Some(apply_iface), Some(current)) = (
+            self.for_apply
+                .as_ref()
+                .filter(|i| !i.is_absent())
+                .map(|i| i.base_iface()),
             self.current.as_ref().map(|i| i.base_iface()),
         ) {
-            if let (Some(desire_mtu), Some(min_mtu), Some(max_mtu)) =
-                (desired.mtu, current.min_mtu, current.max_mtu)
-            {
-                if desire_mtu > max_mtu {
-                    return Err(NmstateError::new(
-                        ErrorKind::InvalidArgument,
-                        format!(
-                            "Desired MTU {} for interface {} \
-                            is bigger than maximum allowed MTU {}",
-                            desire_mtu, desired.name, max_mtu
-                        ),
-                    ));
-                } else if desire_mtu < min_mtu {
-                    return Err(NmstateError::new(
-                        ErrorKind::InvalidArgument,
-                        format!(
-                            "Desired MTU {} for interface {} \
-                            is smaller than minimum allowed MTU {}",
-                            desire_mtu, desired.name, min_mtu
-                        ),
-                    ));
-                }
+            let desire_mtu = match apply_iface.mtu {
+                Some(m) => m,
+                None => return Ok(()),
+            };
+            // Kernel use 0 for no maximum MTU limitation
+            let max_mtu = current.max_mtu.filter(|m| *m > 0);
+            let range = format!(
+                "[{}, {}]",
+                current.min_mtu.unwrap_or_default(),
+                max_mtu
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "unlimited".to_string())
+            );
+            if let Some(max_mtu) = max_mtu.filter(|m| desire_mtu > *m) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Desired MTU {} for interface {} \
+                        is bigger than maximum allowed MTU {}, the MTU range \
+                        supported by kernel is {}",
+                        desire_mtu, apply_iface.name, max_mtu, range
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let Some(min_mtu) = current.min_mtu.filter(|m| desire_mtu < *m) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Desired MTU {} for interface {} \
+                        is smaller than minimum allowed MTU {}, the MTU range \
+                        supported by kernel is {}",
+                        desire_mtu, apply_iface.name, min_mtu, range
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
             }
         }
         Ok(())
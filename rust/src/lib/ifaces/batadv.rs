@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// B.A.T.M.A.N. advanced(batman-adv) mesh interface used by community mesh
+/// networks. The hard interfaces are the links carrying the mesh traffic,
+/// kernel attaches them to the batman-adv interface like bond ports.
+/// Kernel does not support changing the routing algorithm of existing
+/// batman-adv interface, hence nmstate will delete and recreate the
+/// interface when routing algorithm changed.
+/// NetworkManager does not support batman-adv interface, hence it is created
+/// via netlink and not persisted by NetworkManager backend.
+/// The example yaml output of [crate::NetworkState] with a batman-adv
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: bat0
+///   type: batadv
+///   state: up
+///   batadv:
+///     routing-algorithm: batman-v
+///     gw-mode: client
+///     hard-ifaces:
+///     - eth1
+///     - mesh0
+/// ```
+pub struct BatadvInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batadv: Option<BatadvConfig>,
+}
+
+impl Default for BatadvInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Batadv,
+                ..Default::default()
+            },
+            batadv: None,
+        }
+    }
+}
+
+impl BatadvInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(hard_ifaces) = self
+            .batadv
+            .as_ref()
+            .and_then(|c| c.hard_ifaces.as_ref())
+            .filter(|_| is_desired)
+        {
+            for (i, hard_iface) in hard_ifaces.iter().enumerate() {
+                let e = if hard_iface == &self.base.name {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The batman-adv interface {} cannot use itself \
+                            as hard interface",
+                            self.base.name
+                        ),
+                    )
+                } else if hard_ifaces[..i].contains(hard_iface) {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The hard interface {hard_iface} is defined \
+                            more than once in batman-adv interface {}",
+                            self.base.name
+                        ),
+                    )
+                } else {
+                    continue;
+                };
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hard_ifaces(&self) -> Option<Vec<&str>> {
+        self.batadv
+            .as_ref()
+            .and_then(|c| c.hard_ifaces.as_ref())
+            .map(|h| h.iter().map(|i| i.as_str()).collect())
+    }
+
+    // Whether kernel need to recreate the interface for desired config
+    pub(crate) fn is_link_changed(&self, current: &Self) -> bool {
+        if let (Some(des), Some(cur)) = (
+            self.batadv.as_ref().and_then(|c| c.routing_algorithm),
+            current.batadv.as_ref().and_then(|c| c.routing_algorithm),
+        ) {
+            des != cur
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct BatadvConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Routing algorithm of the mesh. Default to
+    /// [BatadvRoutingAlgorithm::BatmanIv] when creating.
+    /// Serialize and deserialize to/from `routing-algorithm`.
+    pub routing_algorithm: Option<BatadvRoutingAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Gateway mode of this mesh node.
+    /// Serialize and deserialize to/from `gw-mode`.
+    pub gw_mode: Option<BatadvGwMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The interfaces attached to this mesh. Set to empty list to detach
+    /// all hard interfaces.
+    /// Serialize and deserialize to/from `hard-ifaces`.
+    pub hard_ifaces: Option<Vec<String>>,
+}
+
+impl BatadvConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum BatadvRoutingAlgorithm {
+    /// Deserialize and serialize from/to `batman-iv`.
+    BatmanIv,
+    /// Deserialize and serialize from/to `batman-v`.
+    BatmanV,
+}
+
+impl Default for BatadvRoutingAlgorithm {
+    fn default() -> Self {
+        Self::BatmanIv
+    }
+}
+
+impl std::fmt::Display for BatadvRoutingAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::BatmanIv => "BATMAN_IV",
+                Self::BatmanV => "BATMAN_V",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum BatadvGwMode {
+    /// Do not announce or use gateway.
+    /// Deserialize and serialize from/to `off`.
+    Off,
+    /// Select gateway announced by other mesh nodes.
+    /// Deserialize and serialize from/to `client`.
+    Client,
+    /// Announce this node as gateway.
+    /// Deserialize and serialize from/to `server`.
+    Server,
+}
+
+impl Default for BatadvGwMode {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl From<BatadvGwMode> for u8 {
+    fn from(v: BatadvGwMode) -> u8 {
+        match v {
+            BatadvGwMode::Off => 0,
+            BatadvGwMode::Client => 1,
+            BatadvGwMode::Server => 2,
+        }
+    }
+}
@@ -49,6 +49,13 @@ use crate::{
 ///     port:
 ///     - eth1
 ///     - eth2
+///     ports-config:
+///     - name: eth1
+///       priority: 10
+///       queue-id: 0
+///     - name: eth2
+///       priority: 0
+///       queue-id: 1
 /// ```
 pub struct BondInterface {
     #[serde(flatten)]
@@ -72,6 +79,8 @@ impl Default for BondInterface {
 
 impl BondInterface {
     // * Do not merge bond options from current when bond mode is changing
+    // * Use port list of desired `ports-config` when `port` not desired
+    // * Do not merge ports configuration of detached ports from current
     pub(crate) fn special_merge(&mut self, desired: &Self, current: &Self) {
         if let Some(bond_conf) = self.bond.as_mut() {
             if let (Some(des_bond_conf), Some(cur_bond_conf)) =
@@ -81,6 +90,15 @@ impl BondInterface {
                     bond_conf.options = des_bond_conf.options.clone();
                 }
             }
+            if let Some(des_ports) = desired.ports() {
+                bond_conf.port =
+                    Some(des_ports.iter().map(|p| p.to_string()).collect());
+            }
+            if let (Some(ports), Some(port_confs)) =
+                (bond_conf.port.as_ref(), bond_conf.ports_config.as_mut())
+            {
+                port_confs.retain(|p| ports.contains(&p.name));
+            }
         }
     }
 
@@ -96,7 +114,7 @@ impl BondInterface {
         }
     }
 
-    fn sort_ports(&mut self) {
+    pub(crate) fn sort_ports(&mut self) {
         if let Some(ref mut bond_conf) = self.bond {
             if let Some(ref mut port_conf) = &mut bond_conf.port {
                 port_conf.sort_unstable_by_key(|p| p.clone())
@@ -104,25 +122,126 @@ impl BondInterface {
         }
     }
 
+    // The `ports-config` also defines the port list, fail if it conflicts
+    // with `port`, or fill `port` with it when `port` is not defined.
+    fn sanitize_ports_config(&mut self) -> Result<(), NmstateError> {
+        let bond_conf = match self.bond.as_mut() {
+            Some(b) => b,
+            None => return Ok(()),
+        };
+        let port_confs = match bond_conf.ports_config.as_ref() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let mut conf_port_names: Vec<&str> =
+            port_confs.iter().map(|p| p.name.as_str()).collect();
+        conf_port_names.sort_unstable();
+        if let Some(dup_name) = conf_port_names
+            .windows(2)
+            .find(|w| w[0] == w[1])
+            .map(|w| w[0])
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Bond {} has duplicate port {dup_name} defined in \
+                    ports-config",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(ports) = bond_conf.port.as_ref() {
+            let mut port_names: Vec<&str> =
+                ports.iter().map(|p| p.as_str()).collect();
+            port_names.sort_unstable();
+            if port_names != conf_port_names {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Bond {} has conflicting port list: port {:?}, \
+                        ports-config {:?}",
+                        self.base.name, port_names, conf_port_names
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        } else {
+            bond_conf.port =
+                Some(conf_port_names.iter().map(|n| n.to_string()).collect());
+        }
+        Ok(())
+    }
+
     pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
+        self.sanitize_ports_config()?;
         self.sort_ports();
         self.drop_empty_arp_ip_target();
         self.make_ad_actor_system_mac_upper_case();
+        if let Some(bond_opts) =
+            self.bond.as_mut().and_then(|b| b.options.as_mut())
+        {
+            bond_opts.sanitize_ns_ip6_target(self.base.name.as_str())?;
+        }
         Ok(())
     }
 
     // Return None when desire state does not mention ports
     pub(crate) fn ports(&self) -> Option<Vec<&str>> {
-        self.bond
-            .as_ref()
-            .and_then(|bond_conf| bond_conf.port.as_ref())
-            .map(|ports| ports.as_slice().iter().map(|p| p.as_str()).collect())
+        let bond_conf = self.bond.as_ref()?;
+        if let Some(ports) = bond_conf.port.as_ref() {
+            Some(ports.as_slice().iter().map(|p| p.as_str()).collect())
+        } else {
+            bond_conf.ports_config.as_ref().map(|port_confs| {
+                port_confs.iter().map(|p| p.name.as_str()).collect()
+            })
+        }
     }
 
     pub(crate) fn mode(&self) -> Option<BondMode> {
         self.bond.as_ref().and_then(|bond_conf| bond_conf.mode)
     }
 
+    pub(crate) fn get_port_conf(
+        &self,
+        port_name: &str,
+    ) -> Option<&BondPortConfig> {
+        self.bond
+            .as_ref()
+            .and_then(|bond_conf| bond_conf.ports_config.as_ref())
+            .and_then(|port_confs| {
+                port_confs
+                    .iter()
+                    .find(|port_conf| port_conf.name == port_name)
+            })
+    }
+
+    // Port name list change is not this function's responsibility, top level
+    // code will take care of it.
+    // This function only find out those port which has changed priority or
+    // queue ID.
+    pub(crate) fn get_config_changed_ports(&self, current: &Self) -> Vec<&str> {
+        let mut ret: Vec<&str> = Vec::new();
+        if let Some(port_confs) = self
+            .bond
+            .as_ref()
+            .and_then(|bond_conf| bond_conf.ports_config.as_ref())
+        {
+            for port_conf in port_confs {
+                if let Some(cur_port_conf) =
+                    current.get_port_conf(port_conf.name.as_str())
+                {
+                    if port_conf.is_changed(cur_port_conf) {
+                        ret.push(port_conf.name.as_str());
+                    }
+                }
+            }
+        }
+        ret
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
@@ -240,6 +359,13 @@ impl BondInterface {
                 .and_then(|bond_conf| bond_conf.port.as_mut())
                 .map(|ports| ports.remove(index));
         }
+        if let Some(port_confs) = self
+            .bond
+            .as_mut()
+            .and_then(|bond_conf| bond_conf.ports_config.as_mut())
+        {
+            port_confs.retain(|p| p.name != port_to_remove);
+        }
     }
 
     pub(crate) fn change_port_name(
@@ -260,9 +386,19 @@ impl BondInterface {
                 .as_mut()
                 .and_then(|bond_conf| bond_conf.port.as_mut())
             {
-                ports[index] = new_name;
+                ports[index] = new_name.clone();
             }
         }
+        if let Some(port_conf) = self
+            .bond
+            .as_mut()
+            .and_then(|bond_conf| bond_conf.ports_config.as_mut())
+            .and_then(|port_confs| {
+                port_confs.iter_mut().find(|p| p.name == origin_name)
+            })
+        {
+            port_conf.name = new_name;
+        }
     }
 }
 
@@ -362,9 +498,17 @@ pub struct BondConfig {
     pub options: Option<BondOptions>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "ports")]
     /// Deserialize and serialize from/to `port`.
-    /// You can also use `ports` for deserializing.
+    /// You can also use deprecated `ports` for deserializing.
     /// When applying, if defined, it will override current port list.
     pub port: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Deserialize and serialize from/to `ports-config`.
+    /// Per-port configuration. When applying, if defined, it will override
+    /// current port list just like `port` does, hence it is not required to
+    /// define `port` along with it. Error will be raised if port names
+    /// in `port` and `ports-config` are not identical.
+    /// The verification follows the order of desired list.
+    pub ports_config: Option<Vec<BondPortConfig>>,
 }
 
 impl BondConfig {
@@ -373,6 +517,41 @@ impl BondConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct BondPortConfig {
+    /// The kernel interface name of this bond port.
+    pub name: String,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_i32_or_string"
+    )]
+    /// The port priority used for selecting active port in
+    /// `active-backup`, `balance-tlb` and `balance-alb` modes. Port with
+    /// higher value is preferred.
+    pub priority: Option<i32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// The transmit queue ID of this port, 0 means not assigned.
+    pub queue_id: Option<u16>,
+}
+
+impl BondPortConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_changed(&self, current: &Self) -> bool {
+        (self.priority.is_some() && self.priority != current.priority)
+            || (self.queue_id.is_some() && self.queue_id != current.queue_id)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
 #[serde(rename_all = "kebab-case")]
@@ -956,6 +1135,17 @@ pub struct BondOptions {
     /// function. The maximum number of targets that can be specified is 16.
     /// The default value is no IP addresses.
     pub arp_ip_target: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Specifies the number of arp_interval monitor checks that must fail in
+    /// order for an interface to be marked down by the ARP monitor. This
+    /// also applies to the NS monitor using ns_ip6_target.
+    ///
+    /// The valid range is 1 - 255; the default value is 2.
+    pub arp_missed_max: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Specifies whether or not ARP probes and replies should be validated in
     /// any mode that supports arp monitoring, or whether non-ARP traffic
@@ -1026,6 +1216,14 @@ pub struct BondOptions {
     /// aggregator cannot be active without at least one available link,
     /// setting this option to 0 or to 1 has the exact same effect.
     pub min_links: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Specifies the IPv6 addresses to use as IPv6 monitoring peers when
+    /// arp_interval is > 0. These are the targets of the NS request sent to
+    /// determine the health of the link to the targets, which allows link
+    /// monitoring on IPv6 only network. Multiple IPv6 addresses must be
+    /// separated by a comma. The maximum number of targets that can be
+    /// specified is 16. The default value is no IPv6 addresses.
+    pub ns_ip6_target: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -1193,6 +1391,54 @@ impl BondOptions {
         Ok(())
     }
 
+    // * Drop empty ns_ip6_target
+    // * Use the compressed form of IPv6 addresses
+    fn sanitize_ns_ip6_target(
+        &mut self,
+        iface_name: &str,
+    ) -> Result<(), NmstateError> {
+        let ns_ip6_target = match self.ns_ip6_target.as_deref() {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        if ns_ip6_target.is_empty() {
+            self.ns_ip6_target = None;
+            return Ok(());
+        }
+        let mut addrs: Vec<String> = Vec::new();
+        for addr_str in ns_ip6_target.split(',').map(|a| a.trim()) {
+            match addr_str.parse::<std::net::Ipv6Addr>() {
+                Ok(addr) => addrs.push(addr.to_string()),
+                Err(e) => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid IPv6 address '{addr_str}' in bond \
+                            ns_ip6_target of interface {iface_name}: {e}"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        self.ns_ip6_target = Some(addrs.join(","));
+        Ok(())
+    }
+
+    fn validate_arp_missed_max(&self) -> Result<(), NmstateError> {
+        if self.arp_missed_max == Some(0) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Bond arp_missed_max should be in the range of 1 - 255"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     fn validate_miimon_and_arp_interval(&self) -> Result<(), NmstateError> {
         if let (Some(miimon), Some(arp_interval)) =
             (self.miimon, self.arp_interval)
@@ -1252,6 +1498,7 @@ impl MergedInterface {
             {
                 bond_opts.validate_ad_actor_system_mac_address()?;
                 bond_opts.validate_miimon_and_arp_interval()?;
+                bond_opts.validate_arp_missed_max()?;
 
                 if let Interface::Bond(merged_iface) = &self.merged {
                     if let Some(mode) =
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+// The sample point is in one-tenth of a percent
+const CAN_SAMPLE_POINT_MAX: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Controller Area Network(CAN) interface used by embedded and automotive
+/// systems. CAN interface cannot be created or deleted by nmstate.
+/// NetworkManager does not support CAN interface, hence the bit timing and
+/// control mode are applied via netlink and not persisted by
+/// NetworkManager backend.
+/// The example yaml output of [crate::NetworkState] with a CAN interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: can0
+///   type: can
+///   state: up
+///   can:
+///     bitrate: 500000
+///     sample-point: 875
+///     restart-ms: 100
+///     fd: true
+///     data-bitrate: 2000000
+///     data-sample-point: 750
+/// ```
+pub struct CanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can: Option<CanConfig>,
+}
+
+impl Default for CanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Can,
+                ..Default::default()
+            },
+            can: None,
+        }
+    }
+}
+
+impl CanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.can.as_ref().filter(|_| is_desired) {
+            if let Some(sample_point) =
+                [conf.sample_point, conf.data_sample_point]
+                    .iter()
+                    .flatten()
+                    .find(|s| **s >= CAN_SAMPLE_POINT_MAX)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The sample point {sample_point} of CAN interface {} \
+                        is invalid, it should be in one-tenth of a percent \
+                        and less than {CAN_SAMPLE_POINT_MAX}",
+                        self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if conf.fd != Some(true)
+                && (conf.data_bitrate.is_some()
+                    || conf.data_sample_point.is_some())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The data-bitrate and data-sample-point of CAN \
+                        interface {} require `fd: true`",
+                        self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct CanConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Bit-rate in bits per second.
+    pub bitrate: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Sample point in one-tenth of a percent, e.g. `875` for 87.5%.
+    /// Kernel will choose the sample point when not defined.
+    /// Serialize and deserialize to/from `sample-point`.
+    pub sample_point: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Milliseconds to wait before restarting the CAN controller
+    /// automatically after bus-off, `0` means disabled.
+    /// Serialize and deserialize to/from `restart-ms`.
+    pub restart_ms: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to enable CAN FD(Flexible Data-Rate) mode.
+    pub fd: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Bit-rate of data phase in CAN FD mode.
+    /// Serialize and deserialize to/from `data-bitrate`.
+    pub data_bitrate: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Sample point of data phase in CAN FD mode, in one-tenth of a percent.
+    /// Serialize and deserialize to/from `data-sample-point`.
+    pub data_sample_point: Option<u32>,
+}
+
+impl CanConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    BaseInterface, ErrorKind, Interface, InterfaceType, Interfaces,
-    MergedInterfaces, NmstateError, SrIovConfig,
+    BaseInterface, ErrorKind, Interface, InterfaceIpv4, InterfaceIpv6,
+    InterfaceState, InterfaceType, Interfaces, MergedInterface,
+    MergedInterfaces, NetworkState, NmstateError, SrIovConfig,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -92,15 +93,95 @@ impl EthernetInterface {
         if let Some(sriov_conf) =
             self.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
         {
-            sriov_conf.sanitize();
+            sriov_conf.sanitize(self.base.name.as_str())?;
+        }
+        if let Some(eth_conf) = self.ethernet.as_mut() {
+            if eth_conf.usb_role.is_some() {
+                log::debug!(
+                    "Ignoring query only property usb-role of interface {}",
+                    self.base.name
+                );
+                eth_conf.usb_role = None;
+            }
+        }
+        if let Some(wol_conf) =
+            self.ethernet.as_mut().and_then(|e| e.wake_on_lan.as_mut())
+        {
+            wol_conf.sanitize(self.base.name.as_str())?;
         }
 
         Ok(())
     }
 
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(wol_conf) =
+            self.ethernet.as_mut().and_then(|e| e.wake_on_lan.as_mut())
+        {
+            if wol_conf.password.is_some() {
+                wol_conf.password =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
+
+    // Merge veth peer properties defined in the other end of veth pair.
+    fn merge_veth_peer_conf(
+        &mut self,
+        peer_iface: EthernetInterface,
+    ) -> Result<(), NmstateError> {
+        let end_name = peer_iface
+            .veth
+            .as_ref()
+            .map(|v| v.peer.clone())
+            .unwrap_or_default();
+        let name = self.base.name.clone();
+        let conflict = |prop: &str| {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Veth interface {} has conflicting {} defined by \
+                    its peer {}",
+                    name, prop, end_name
+                ),
+            );
+            log::error!("{}", e);
+            e
+        };
+        match self.veth.as_ref() {
+            Some(v) if v.peer != end_name => {
+                return Err(conflict("veth peer"));
+            }
+            Some(_) => (),
+            None => self.veth = peer_iface.veth,
+        }
+        if let Some(mtu) = peer_iface.base.mtu {
+            if self.base.mtu.is_some() && self.base.mtu != Some(mtu) {
+                return Err(conflict("MTU"));
+            }
+            self.base.mtu = Some(mtu);
+        }
+        if let Some(ipv4) = peer_iface.base.ipv4 {
+            if self.base.ipv4.is_some()
+                && self.base.ipv4.as_ref() != Some(&ipv4)
+            {
+                return Err(conflict("IPv4 configuration"));
+            }
+            self.base.ipv4 = Some(ipv4);
+        }
+        if let Some(ipv6) = peer_iface.base.ipv6 {
+            if self.base.ipv6.is_some()
+                && self.base.ipv6.as_ref() != Some(&ipv6)
+            {
+                return Err(conflict("IPv6 configuration"));
+            }
+            self.base.ipv6 = Some(ipv6);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -126,6 +207,34 @@ impl std::fmt::Display for EthernetDuplex {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Role of USB networking interface detected by kernel driver.
+pub enum EthernetUsbRole {
+    /// Host side of USB networking, using drivers like `cdc_ether`,
+    /// `cdc_ncm` or `rndis_host`.
+    /// Deserialize and serialize from/to `host`.
+    Host,
+    /// Device side of USB networking, created by USB gadget drivers like
+    /// `g_ether` or configfs gadget `ecm`/`ncm`/`rndis` functions.
+    /// Deserialize and serialize from/to `gadget`.
+    Gadget,
+}
+
+impl std::fmt::Display for EthernetUsbRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Host => "host",
+                Self::Gadget => "gadget",
+            }
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
@@ -150,6 +259,15 @@ pub struct EthernetConfig {
     pub speed: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duplex: Option<EthernetDuplex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. Set when interface is USB networking interface.
+    /// Ignored when applying.
+    /// Deserialize and serialize from/to `usb-role`.
+    pub usb_role: Option<EthernetUsbRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Wake-on-LAN configuration.
+    /// Deserialize and serialize from/to `wake-on-lan`.
+    pub wake_on_lan: Option<EthernetWakeOnLanConfig>,
 }
 
 impl EthernetConfig {
@@ -159,10 +277,144 @@ impl EthernetConfig {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
+/// Wake-on-LAN configuration of ethernet interface. Undefined wake up
+/// method is treated as disabled when applying. Example yaml:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   ethernet:
+///     wake-on-lan:
+///       magic: true
+///       unicast: false
+///       broadcast: false
+///       password: "00:11:22:33:44:55"
+/// ```
+pub struct EthernetWakeOnLanConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Wake up on magic packet.
+    pub magic: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Wake up on unicast packet.
+    pub unicast: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Wake up on broadcast packet.
+    pub broadcast: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// SecureOn password of magic packet in MAC address format. Only valid
+    /// when `magic` is enabled. Hidden by
+    /// [NetworkState::PASSWORD_HID_BY_NMSTATE] unless querying with secrets
+    /// included.
+    pub password: Option<String>,
+}
+
+impl EthernetWakeOnLanConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sanitize(&mut self, iface_name: &str) -> Result<(), NmstateError> {
+        if let Some(password) = self
+            .password
+            .as_mut()
+            .filter(|p| p.as_str() != NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            password.make_ascii_uppercase();
+            let is_valid_mac = password.split(':').count() == 6
+                && password.split(':').all(|s| {
+                    s.len() == 2 && s.chars().all(|c| c.is_ascii_hexdigit())
+                });
+            if !is_valid_mac {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The wake-on-lan password of interface {iface_name} \
+                        should be in MAC address format like \
+                        00:11:22:33:44:55"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if self.magic != Some(true) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The wake-on-lan password of interface {iface_name} \
+                        requires magic packet wake up enabled"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Veth pair. Both ends of veth pair could be declared in single interface
+/// via `peer-mtu`, `peer-ipv4` and `peer-ipv6`, for example:
+/// ```yml
+/// interfaces:
+/// - name: veth1
+///   type: veth
+///   state: up
+///   veth:
+///     peer: veth1.ep
+///     peer-mtu: 9000
+///     peer-ipv4:
+///       enabled: true
+///       address:
+///       - ip: 192.0.2.2
+///         prefix-length: 24
+/// ```
+/// Marking either end as absent will remove the whole veth pair.
 pub struct VethConfig {
     /// The name of veth peer.
     pub peer: String,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
+    /// MTU of veth peer. Only used when applying, the veth peer will be
+    /// included into desired state as standalone interface.
+    /// Deserialize and serialize from/to `peer-mtu`.
+    pub peer_mtu: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IPv4 configuration of veth peer. Only used when applying.
+    /// Deserialize and serialize from/to `peer-ipv4`.
+    pub peer_ipv4: Option<Box<InterfaceIpv4>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IPv6 configuration of veth peer. Only used when applying.
+    /// Deserialize and serialize from/to `peer-ipv6`.
+    pub peer_ipv6: Option<Box<InterfaceIpv6>>,
+}
+
+impl VethConfig {
+    fn has_peer_conf(&self) -> bool {
+        self.peer_mtu.is_some()
+            || self.peer_ipv4.is_some()
+            || self.peer_ipv6.is_some()
+    }
 }
 
 impl MergedInterfaces {
@@ -254,6 +506,113 @@ impl MergedInterfaces {
 }
 
 impl Interfaces {
+    // Include the veth peer defined by `peer-mtu`, `peer-ipv4` or `peer-ipv6`
+    // as desired interface, so both ends are applied and verified as single
+    // unit. Raise error if veth peer is also defined with conflicting
+    // properties.
+    pub(crate) fn include_veth_peer_ifaces(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        let mut peer_ifaces: Vec<EthernetInterface> = Vec::new();
+        for iface in self.kernel_ifaces.values_mut() {
+            let is_up = iface.is_up();
+            let eth_iface = if let Interface::Ethernet(i) = iface {
+                i
+            } else {
+                continue;
+            };
+            let veth_conf = match eth_iface.veth.as_mut() {
+                Some(v) if v.has_peer_conf() => v,
+                _ => continue,
+            };
+            let mut peer_iface = EthernetInterface::new();
+            peer_iface.base.name = veth_conf.peer.clone();
+            peer_iface.base.iface_type = InterfaceType::Veth;
+            peer_iface.base.mtu = veth_conf.peer_mtu.take();
+            peer_iface.base.ipv4 = veth_conf.peer_ipv4.take().map(|i| *i);
+            peer_iface.base.ipv6 = veth_conf.peer_ipv6.take().map(|i| *i);
+            peer_iface.veth = Some(VethConfig {
+                peer: eth_iface.base.name.clone(),
+                ..Default::default()
+            });
+            if is_up {
+                peer_ifaces.push(peer_iface);
+            }
+        }
+
+        for peer_iface in peer_ifaces {
+            match self.kernel_ifaces.get_mut(peer_iface.base.name.as_str()) {
+                Some(Interface::Ethernet(des_peer))
+                    if des_peer.base.state == InterfaceState::Up =>
+                {
+                    des_peer.merge_veth_peer_conf(peer_iface)?;
+                }
+                Some(iface) => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Veth peer {} is configured by veth interface \
+                            {} but also desired as {} interface in {:?} state",
+                            iface.name(),
+                            peer_iface
+                                .veth
+                                .as_ref()
+                                .map(|v| v.peer.as_str())
+                                .unwrap_or_default(),
+                            iface.iface_type(),
+                            iface.base_iface().state,
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                None => self.push(Interface::Ethernet(peer_iface)),
+            }
+        }
+        Ok(())
+    }
+
+    // Removing either end of veth pair means removing the whole pair, hence
+    // not allowing veth peer desired as up when other end is marked as
+    // absent.
+    pub(crate) fn validate_veth_pair_absent(
+        &self,
+        current: &Self,
+    ) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values().filter(|i| {
+            i.is_absent()
+                && (i.iface_type() == InterfaceType::Veth
+                    || i.iface_type() == InterfaceType::Ethernet)
+        }) {
+            let peer = if let Some(Interface::Ethernet(cur_iface)) =
+                current.kernel_ifaces.get(iface.name())
+            {
+                match cur_iface.veth.as_ref() {
+                    Some(v) => v.peer.as_str(),
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+            if let Some(peer_iface) =
+                self.kernel_ifaces.get(peer).filter(|i| i.is_up())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Veth interface {} is marked as absent, its peer {} \
+                        will be removed also, but it is desired as up",
+                        iface.name(),
+                        peer_iface.name(),
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
     // Not allowing changing veth peer away from ignored peer unless previous
     // peer changed from ignore to managed
     pub(crate) fn validate_change_veth_ignored_peer(
@@ -356,3 +715,32 @@ impl Interfaces {
         Ok(())
     }
 }
+
+impl MergedInterface {
+    // USB gadget drivers generate random MAC address on every load unless
+    // module parameter or configfs defined one. Pin the current MAC address
+    // into the configuration so the USB host could identify this device
+    // consistently after reboot.
+    pub(crate) fn post_inter_ifaces_process_usb_gadget(&mut self) {
+        if let (
+            Some(Interface::Ethernet(apply_iface)),
+            Some(Interface::Ethernet(cur_iface)),
+        ) = (self.for_apply.as_mut(), self.current.as_ref())
+        {
+            if apply_iface.base.state == InterfaceState::Up
+                && apply_iface.base.mac_address.is_none()
+                && cur_iface.ethernet.as_ref().and_then(|e| e.usb_role)
+                    == Some(EthernetUsbRole::Gadget)
+            {
+                if let Some(mac) = cur_iface.base.mac_address.as_ref() {
+                    log::info!(
+                        "Pinning MAC address {} of USB gadget interface {}",
+                        mac,
+                        apply_iface.base.name
+                    );
+                    apply_iface.base.mac_address = Some(mac.to_string());
+                }
+            }
+        }
+    }
+}
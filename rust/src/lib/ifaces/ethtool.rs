@@ -1,13 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ipnet::IpNet;
 
 use serde::{
     de, de::MapAccess, de::Visitor, Deserialize, Deserializer, Serialize,
 };
 
-use crate::MergedInterface;
+use crate::{ErrorKind, MergedInterface, NmstateError};
 
 const ETHTOOL_FEATURE_CLI_ALIAS: [(&str, &str); 17] = [
     ("rx", "rx-checksum"),
@@ -58,11 +62,36 @@ pub type EthtoolFeatureConfig = HashMap<String, bool>;
 ///     coalesce:
 ///       rx-frames: 1
 ///       tx-frames: 1
+///       queues:
+///       - queue: 0
+///         rx-usecs: 8
+///         rx-frames: 16
 ///     ring:
 ///       rx: 256
 ///       rx-max: 256
 ///       tx: 256
 ///       tx-max: 256
+///     channels:
+///       combined: 4
+///       combined-max: 8
+///       other: 1
+///       other-max: 1
+///     eee:
+///       enabled: true
+///       advertise:
+///       - 1000baseT/Full
+///       - 100baseT/Full
+///     flow-rules:
+///     - id: 1
+///       flow-type: tcp4
+///       dst-ip: 192.0.2.1
+///       dst-port: 80
+///       action: queue
+///       queue: 2
+///     - id: 2
+///       flow-type: udp6
+///       src-ip: 2001:db8::/64
+///       action: drop
 /// ```
 pub struct EthtoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,7 +100,8 @@ pub struct EthtoolConfig {
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
-        deserialize_with = "parse_ethtool_feature"
+        deserialize_with = "parse_ethtool_feature",
+        serialize_with = "crate::serializer::option_map_sorted"
     )]
     /// The protocol offload and other features of specified network device.
     /// Only changeable features are included when querying.
@@ -82,6 +112,22 @@ pub struct EthtoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The rx/tx ring parameters of the specified network device.
     pub ring: Option<EthtoolRingConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The number of queues(channels) of the specified network device.
+    pub channels: Option<EthtoolChannelsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The Energy Efficient Ethernet (IEEE 802.3az) settings of the specified
+    /// network device.
+    pub eee: Option<EthtoolEeeConfig>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "flow-rules")]
+    /// The ntuple(flow director) receive flow steering rules of the specified
+    /// network device. Once defined, this list holds all the rules of the
+    /// device: rules not listed will be removed. Set to empty list to remove
+    /// all rules. The `rx-ntuple-filter` feature should be enabled.
+    /// Neither nispor nor NetworkManager support flow rules, hence they
+    /// are changed via ethtool ioctl directly and not persisted by
+    /// NetworkManager backend.
+    pub flow_rules: Option<Vec<EthtoolFlowRule>>,
 }
 
 impl EthtoolConfig {
@@ -133,9 +179,7 @@ impl EthtoolPauseConfig {
     }
 }
 
-#[derive(
-    Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default, Copy,
-)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct EthtoolCoalesceConfig {
@@ -293,6 +337,14 @@ pub struct EthtoolCoalesceConfig {
     )]
     /// Deserialize and serialize from/to `tx-usecs-low`.
     pub tx_usecs_low: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Per-queue overrides of the coalescing settings above. Queues not
+    /// listed are untouched. When querying, only queues with settings
+    /// different from the device-wide ones are included.
+    /// Neither nispor nor NetworkManager support per-queue coalescing, hence
+    /// they are changed via ethtool ioctl directly and not persisted by
+    /// NetworkManager backend.
+    pub queues: Option<Vec<EthtoolQueueCoalesceConfig>>,
 }
 
 impl EthtoolCoalesceConfig {
@@ -301,6 +353,52 @@ impl EthtoolCoalesceConfig {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct EthtoolQueueCoalesceConfig {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// The index of the queue.
+    pub queue: u32,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `rx-usecs`.
+    pub rx_usecs: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `rx-frames`.
+    pub rx_frames: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `tx-usecs`.
+    pub tx_usecs: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `tx-frames`.
+    pub tx_frames: Option<u32>,
+}
+
+impl EthtoolQueueCoalesceConfig {
+    pub fn new(queue: u32) -> Self {
+        Self {
+            queue,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
@@ -367,6 +465,296 @@ impl EthtoolRingConfig {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// The queue counts of network device. The `*-max` properties are the
+/// maximum counts supported by device which are ignored when applying.
+pub struct EthtoolChannelsConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub rx: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `rx-max`.
+    pub rx_max: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub tx: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `tx-max`.
+    pub tx_max: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub other: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `other-max`.
+    pub other_max: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub combined: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Deserialize and serialize from/to `combined-max`.
+    pub combined_max: Option<u32>,
+}
+
+impl EthtoolChannelsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Return list of (name, desired_count, maximum_count)
+    fn counts(&self) -> [(&'static str, Option<u32>, Option<u32>); 4] {
+        [
+            ("rx", self.rx, self.rx_max),
+            ("tx", self.tx, self.tx_max),
+            ("other", self.other, self.other_max),
+            ("combined", self.combined, self.combined_max),
+        ]
+    }
+
+    // Each combined channel holds a rx queue and a tx queue.
+    pub(crate) fn queue_count(&self) -> u32 {
+        self.combined.unwrap_or_default()
+            + std::cmp::max(
+                self.rx.unwrap_or_default(),
+                self.tx.unwrap_or_default(),
+            )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct EthtoolEeeConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The link modes advertised as EEE capable in the format of ethtool
+    /// link mode name, for example `1000baseT/Full`. Link modes not listed
+    /// will not be advertised. Set to empty list to advertise no link mode.
+    pub advertise: Option<Vec<String>>,
+}
+
+impl EthtoolEeeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct EthtoolFlowRule {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// The location of this rule in the rule table of device, also used as
+    /// identifier of this rule.
+    pub id: u32,
+    /// Deserialize and serialize from/to `flow-type`.
+    pub flow_type: EthtoolFlowType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Source IP address to match, optionally with prefix length, for
+    /// example `192.0.2.0/24`.
+    /// Deserialize and serialize from/to `src-ip`.
+    pub src_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Destination IP address to match, optionally with prefix length.
+    /// Deserialize and serialize from/to `dst-ip`.
+    pub dst_ip: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Deserialize and serialize from/to `src-port`.
+    pub src_port: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Deserialize and serialize from/to `dst-port`.
+    pub dst_port: Option<u16>,
+    pub action: EthtoolFlowAction,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// The receive queue to steer matched packets to, only valid for
+    /// [EthtoolFlowAction::Queue].
+    pub queue: Option<u32>,
+}
+
+impl EthtoolFlowRule {
+    pub fn new(
+        id: u32,
+        flow_type: EthtoolFlowType,
+        action: EthtoolFlowAction,
+    ) -> Self {
+        Self {
+            id,
+            flow_type,
+            src_ip: None,
+            dst_ip: None,
+            src_port: None,
+            dst_port: None,
+            action,
+            queue: None,
+        }
+    }
+
+    fn sanitize(&mut self, iface_name: &str) -> Result<(), NmstateError> {
+        match (self.action, self.queue) {
+            (EthtoolFlowAction::Queue, None) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The queue is required by ethtool flow rule {} of \
+                        interface {iface_name} with queue action",
+                        self.id
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            (EthtoolFlowAction::Drop, Some(_)) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The queue is not allowed in ethtool flow rule {} \
+                        of interface {iface_name} with drop action",
+                        self.id
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            _ => (),
+        }
+        let id = self.id;
+        let is_ipv6 = self.flow_type.is_ipv6();
+        for ip in self.src_ip.iter_mut().chain(self.dst_ip.iter_mut()) {
+            let (addr, prefix) = match parse_flow_rule_ip(ip.as_str())
+                .filter(|(a, _)| a.is_ipv6() == is_ipv6)
+            {
+                Some(i) => i,
+                None => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid IP {ip} in ethtool flow rule {id} of \
+                            interface {iface_name}, should be {} address \
+                            with optional prefix length",
+                            if is_ipv6 { "IPv6" } else { "IPv4" }
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            };
+            *ip = flow_rule_ip_to_string(addr, prefix);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum EthtoolFlowType {
+    /// TCP over IPv4.
+    /// Deserialize and serialize from/to `tcp4`.
+    Tcp4,
+    /// UDP over IPv4.
+    /// Deserialize and serialize from/to `udp4`.
+    Udp4,
+    /// SCTP over IPv4.
+    /// Deserialize and serialize from/to `sctp4`.
+    Sctp4,
+    /// TCP over IPv6.
+    /// Deserialize and serialize from/to `tcp6`.
+    Tcp6,
+    /// UDP over IPv6.
+    /// Deserialize and serialize from/to `udp6`.
+    Udp6,
+    /// SCTP over IPv6.
+    /// Deserialize and serialize from/to `sctp6`.
+    Sctp6,
+}
+
+impl EthtoolFlowType {
+    pub(crate) fn is_ipv6(&self) -> bool {
+        matches!(self, Self::Tcp6 | Self::Udp6 | Self::Sctp6)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum EthtoolFlowAction {
+    /// Drop the matched packets.
+    /// Deserialize and serialize from/to `drop`.
+    Drop,
+    /// Steer the matched packets to the queue defined in
+    /// [EthtoolFlowRule::queue].
+    /// Deserialize and serialize from/to `queue`.
+    Queue,
+}
+
+// Return the network address with prefix length, the host bits are cleared.
+pub(crate) fn parse_flow_rule_ip(ip: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = match ip.split_once('/') {
+        Some((a, p)) => (IpAddr::from_str(a).ok()?, u8::from_str(p).ok()?),
+        None => {
+            let addr = IpAddr::from_str(ip).ok()?;
+            (addr, if addr.is_ipv6() { 128 } else { 32 })
+        }
+    };
+    let net = IpNet::new(addr, prefix).ok()?;
+    Some((net.network(), prefix))
+}
+
+// The prefix length is omitted for host address.
+pub(crate) fn flow_rule_ip_to_string(addr: IpAddr, prefix: u8) -> String {
+    if prefix == if addr.is_ipv6() { 128 } else { 32 } {
+        addr.to_string()
+    } else {
+        format!("{addr}/{prefix}")
+    }
+}
+
 fn parse_ethtool_feature<'de, D>(
     deserializer: D,
 ) -> Result<Option<EthtoolFeatureConfig>, D::Error>
@@ -446,6 +834,205 @@ impl MergedInterface {
             .and_then(|b| b.ethtool.as_mut())
         {
             ethtool_conf.apply_feature_alias();
+            // The EEE link modes queried are sorted
+            if let Some(advertise) =
+                ethtool_conf.eee.as_mut().and_then(|e| e.advertise.as_mut())
+            {
+                advertise.sort_unstable();
+                advertise.dedup();
+            }
+            // The flow rules queried are sorted by id
+            if let Some(rules) = ethtool_conf.flow_rules.as_mut() {
+                rules.sort_unstable_by_key(|r| r.id);
+            }
+            // The coalesce queues queried are sorted by queue index
+            if let Some(queues) = ethtool_conf
+                .coalesce
+                .as_mut()
+                .and_then(|c| c.queues.as_mut())
+            {
+                queues.sort_unstable_by_key(|q| q.queue);
+            }
+        }
+    }
+
+    pub(crate) fn sanitize_ethtool_flow_rules(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        for iface in self.for_apply.iter_mut().chain(self.for_verify.iter_mut())
+        {
+            let iface_name = iface.name().to_string();
+            if let Some(rules) = iface
+                .base_iface_mut()
+                .ethtool
+                .as_mut()
+                .and_then(|e| e.flow_rules.as_mut())
+            {
+                let mut ids = HashSet::new();
+                for rule in rules.iter_mut() {
+                    if !ids.insert(rule.id) {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "The ethtool flow rule id {} is defined more \
+                                than once in interface {iface_name}",
+                                rule.id
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                    rule.sanitize(iface_name.as_str())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Validate the desired channel counts against the maximum counts
+    // reported by current device.
+    pub(crate) fn validate_ethtool_channels(&self) -> Result<(), NmstateError> {
+        let (apply_iface, cur_iface) = match (
+            self.for_apply
+                .as_ref()
+                .filter(|i| i.is_up())
+                .map(|i| i.base_iface()),
+            self.current.as_ref().map(|i| i.base_iface()),
+        ) {
+            (Some(a), Some(c)) => (a, c),
+            _ => return Ok(()),
+        };
+        let des_channels = match apply_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.channels.as_ref())
+        {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+        if des_channels.counts().iter().all(|(_, c, _)| c.is_none()) {
+            return Ok(());
+        }
+        let cur_channels = match cur_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.channels.as_ref())
+        {
+            Some(c) => c,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface {} does not support changing ethtool \
+                        channels",
+                        apply_iface.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        for ((name, des_count, _), (_, _, max_count)) in des_channels
+            .counts()
+            .iter()
+            .zip(cur_channels.counts().iter())
+        {
+            if let Some(des_count) =
+                des_count.filter(|c| *c > max_count.unwrap_or_default())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Desired ethtool {name} channel count {des_count} of \
+                        interface {} exceeds the maximum {} supported by \
+                        device",
+                        apply_iface.name,
+                        max_count.unwrap_or_default()
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // Validate the desired queue index against the queue count of desired
+    // or current channels.
+    pub(crate) fn validate_ethtool_coalesce_queues(
+        &self,
+    ) -> Result<(), NmstateError> {
+        let apply_iface = match self
+            .for_apply
+            .as_ref()
+            .filter(|i| i.is_up())
+            .map(|i| i.base_iface())
+        {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+        let queues = match apply_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.coalesce.as_ref())
+            .and_then(|c| c.queues.as_ref())
+        {
+            Some(q) => q,
+            None => return Ok(()),
+        };
+        let mut indexes = HashSet::new();
+        for queue_conf in queues {
+            if !indexes.insert(queue_conf.queue) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The ethtool coalesce queue {} is defined more than \
+                        once in interface {}",
+                        queue_conf.queue, apply_iface.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        let mut channels = match self
+            .current
+            .as_ref()
+            .and_then(|i| i.base_iface().ethtool.as_ref())
+            .and_then(|e| e.channels.as_ref())
+        {
+            Some(c) => c.clone(),
+            None => return Ok(()),
+        };
+        if let Some(des_channels) = apply_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.channels.as_ref())
+        {
+            for (count, des_count) in [
+                (&mut channels.rx, des_channels.rx),
+                (&mut channels.tx, des_channels.tx),
+                (&mut channels.combined, des_channels.combined),
+            ] {
+                if des_count.is_some() {
+                    *count = des_count;
+                }
+            }
+        }
+        let queue_count = channels.queue_count();
+        if let Some(queue_conf) = queues.iter().find(|q| q.queue >= queue_count)
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The ethtool coalesce queue {} of interface {} does not \
+                    exist, the device has {queue_count} queues",
+                    queue_conf.queue, apply_iface.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
         }
+        Ok(())
     }
 }
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+// The Geneve VNI is 24 bits long
+const GENEVE_ID_MAX: u32 = 0xFFFFFF;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel Geneve(Generic Network Virtualization Encapsulation) tunnel
+/// interface. The example yaml output of [crate::NetworkState] with a Geneve
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: geneve1
+///   type: geneve
+///   state: up
+///   mtu: 1450
+///   geneve:
+///     id: 1001
+///     remote: 192.0.2.2
+///     destination-port: 6081
+///     ttl: 64
+///     tos: 0
+/// ```
+pub struct GeneveInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geneve: Option<GeneveConfig>,
+}
+
+impl Default for GeneveInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Geneve,
+                ..Default::default()
+            },
+            geneve: None,
+        }
+    }
+}
+
+impl GeneveInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.geneve.as_ref() {
+            if is_desired && conf.id > GENEVE_ID_MAX {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The id {} of Geneve interface {} exceeds the \
+                        maximum value {GENEVE_ID_MAX}",
+                        conf.id, self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GeneveConfig {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// Geneve Virtual Network Identifier(VNI).
+    pub id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote IPv4 or IPv6 address of the tunnel endpoint.
+    pub remote: Option<std::net::IpAddr>,
+    #[serde(
+        rename = "destination-port",
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// UDP destination port, kernel default is 6081.
+    /// Deserialize and serialize from/to `destination-port`.
+    pub dst_port: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Time to live of tunneled packets. Set to 0 to use the route default.
+    pub ttl: Option<u8>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Type of service of tunneled packets.
+    pub tos: Option<u8>,
+}
+
+impl GeneveConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel GRE tunnel interface carrying layer 3 traffic over IPv4.
+/// Nispor does not provide GRE configuration yet, hence the `gre` section is
+/// only queried from NetworkManager backend. The example yaml output of
+/// [crate::NetworkState] with a GRE interface would be:
+/// ```yml
+/// interfaces:
+/// - name: gre1
+///   type: gre
+///   state: up
+///   gre:
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     ikey: 100
+///     okey: 100
+///     ttl: 64
+/// ```
+pub struct GreInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gre: Option<GreConfig>,
+}
+
+impl Default for GreInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gre,
+                ..Default::default()
+            },
+            gre: None,
+        }
+    }
+}
+
+impl GreInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.gre.as_mut() {
+            conf.sanitize(self.base.name.as_str(), is_desired)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel GRETAP tunnel interface carrying ethernet frames over IPv4.
+/// The `gretap` section shares the same properties with
+/// [GreInterface]. The example yaml output of [crate::NetworkState] with a
+/// GRETAP interface would be:
+/// ```yml
+/// interfaces:
+/// - name: gretap1
+///   type: gretap
+///   state: up
+///   gretap:
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+/// ```
+pub struct GreTapInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gretap: Option<GreConfig>,
+}
+
+impl Default for GreTapInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::GreTap,
+                ..Default::default()
+            },
+            gretap: None,
+        }
+    }
+}
+
+impl GreTapInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.gretap.as_mut() {
+            conf.sanitize(self.base.name.as_str(), is_desired)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GreConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local IPv4 address of the tunnel endpoint.
+    pub local: Option<std::net::IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote IPv4 address of the tunnel endpoint.
+    pub remote: Option<std::net::IpAddr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Shortcut for setting `ikey` and `okey` to the same value. Only used
+    /// when applying, the query result will always show `ikey` and `okey`.
+    pub key: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key of incoming packets.
+    pub ikey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key of outgoing packets.
+    pub okey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Time to live of tunneled packets. Set to 0 to inherit from the inner
+    /// packet.
+    pub ttl: Option<u8>,
+}
+
+impl GreConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sanitize(
+        &mut self,
+        iface_name: &str,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(key) = self.key {
+            for (name, v) in [("ikey", self.ikey), ("okey", self.okey)] {
+                if is_desired && v.is_some() && v != Some(key) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The key {key} of GRE interface {iface_name} \
+                            conflicts with {name} {}",
+                            v.unwrap_or_default()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            self.ikey = Some(key);
+            self.okey = Some(key);
+            self.key = None;
+        }
+        if is_desired {
+            for (name, addr) in [("local", self.local), ("remote", self.remote)]
+            {
+                if let Some(std::net::IpAddr::V6(addr)) = addr {
+                    let e = NmstateError::new(
+                        ErrorKind::NotSupportedError,
+                        format!(
+                            "The {name} address {addr} of GRE interface \
+                            {iface_name} is not IPv4, IPv6 GRE tunnel is \
+                            not supported yet"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
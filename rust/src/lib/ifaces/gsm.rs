@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceState, InterfaceType, NetworkState,
+    NmstateError,
+};
+
+const PIN_MIN_LENGTH: usize = 4;
+const PIN_MAX_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// GSM/UMTS/LTE mobile broadband(WWAN) modem connection. The modem is managed
+/// by ModemManager and the connection is established by NetworkManager,
+/// hence GSM is only supported by NetworkManager backend.
+/// The interface name is the modem control port(e.g. `cdc-wdm0`) as shown in
+/// NetworkManager device list.
+/// The `pin` and `password` are hidden by
+/// [NetworkState::PASSWORD_HID_BY_NMSTATE] unless querying with secrets
+/// included.
+/// Both `gsm` and `wwan` are accepted as interface type when deserializing.
+/// The example yaml output of [crate::NetworkState] with a GSM interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: cdc-wdm0
+///   type: gsm
+///   state: up
+///   gsm:
+///     apn: internet.example.net
+///     pin: <_password_hid_by_nmstate>
+///     roaming: false
+///     bearer:
+///       connected: true
+///       apn: internet.example.net
+///       operator-code: "310260"
+/// ```
+pub struct GsmInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gsm: Option<GsmConfig>,
+}
+
+impl Default for GsmInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gsm,
+                ..Default::default()
+            },
+            gsm: None,
+        }
+    }
+}
+
+impl GsmInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.gsm.as_mut() {
+            // Query only
+            conf.bearer = None;
+            if is_desired && self.base.state == InterfaceState::Up {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(conf) = self.gsm.as_mut() {
+            if conf.pin.is_some() {
+                conf.pin =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+            if conf.password.is_some() {
+                conf.password =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GsmConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Access Point Name(APN) of the mobile network. When undefined,
+    /// NetworkManager will use the APN provided by the modem or network.
+    pub apn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// PIN code of the SIM card in 4 to 8 digits.
+    pub pin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Username used for authentication with the mobile network.
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Password used for authentication with the mobile network.
+    pub password: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to allow connecting to roaming networks. When set to false,
+    /// only home network will be used.
+    pub roaming: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// State of the active data bearer. Query only, ignored when applying.
+    pub bearer: Option<GsmBearer>,
+}
+
+impl GsmConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if let Some(pin) = self
+            .pin
+            .as_deref()
+            .filter(|p| *p != NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            if !(PIN_MIN_LENGTH..=PIN_MAX_LENGTH).contains(&pin.len())
+                || !pin.chars().all(|c| c.is_ascii_digit())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The PIN of GSM interface {iface_name} should be \
+                        {PIN_MIN_LENGTH} to {PIN_MAX_LENGTH} digits"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if self.apn.as_deref().map(|a| a.contains(char::is_whitespace))
+            == Some(true)
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The APN of GSM interface {iface_name} should not \
+                    contain whitespace"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// State of the active data bearer of the modem.
+pub struct GsmBearer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Whether the data bearer is connected.
+    pub connected: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// APN the modem is connected to.
+    pub apn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// MCC and MNC of the mobile network operator.
+    /// Serialize and deserialize to/from `operator-code`.
+    pub operator_code: Option<String>,
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel GTP-U(GPRS Tunnelling Protocol User Plane) interface used by
+/// mobile network user plane functions.
+/// The PDP contexts, including the remote peers and the tunnel endpoint
+/// identifiers(TEIDs), are managed by the control plane via GTP generic
+/// netlink, nmstate only creates the GTP interface with its UDP sockets.
+/// NetworkManager does not support GTP interface, hence it is created via
+/// netlink and not persisted by NetworkManager backend.
+/// The example yaml output of [crate::NetworkState] with a GTP interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: gtp0
+///   type: gtp
+///   state: up
+///   gtp:
+///     role: ggsn
+///     local: 192.0.2.1
+///     external-teid: true
+/// ```
+pub struct GtpInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gtp: Option<GtpConfig>,
+}
+
+impl Default for GtpInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gtp,
+                ..Default::default()
+            },
+            gtp: None,
+        }
+    }
+}
+
+impl GtpInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired
+            && self.gtp.as_ref().and_then(|c| c.external_teid) == Some(false)
+        {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Managing TEIDs of GTP interface {} is not supported, \
+                    please set `external-teid: true` and manage the PDP \
+                    contexts via the control plane",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    // Kernel cannot change GTP properties after creation
+    pub(crate) fn is_link_changed(&self, current: &Self) -> bool {
+        let des_conf = self.gtp.clone().unwrap_or_default();
+        let cur_conf = current.gtp.clone().unwrap_or_default();
+        des_conf.role.unwrap_or_default() != cur_conf.role.unwrap_or_default()
+            || (des_conf.local.is_some() && des_conf.local != cur_conf.local)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GtpConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The role of GTP interface, default to [GtpRole::Ggsn].
+    pub role: Option<GtpRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local IPv4 or IPv6 address which the GTP-U UDP sockets bind to.
+    /// Requires Linux kernel 6.10 or newer.
+    pub local: Option<std::net::IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Whether the TEIDs and PDP contexts are managed externally by the
+    /// control plane. Only `true` is supported.
+    pub external_teid: Option<bool>,
+}
+
+impl GtpConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum GtpRole {
+    /// Gateway GPRS support node, also used for PGW-U and UPF.
+    /// Deserialize and serialize from/to `ggsn`.
+    Ggsn,
+    /// Serving GPRS support node, also used for SGW-U and gNodeB.
+    /// Deserialize and serialize from/to `sgsn`.
+    Sgsn,
+}
+
+impl Default for GtpRole {
+    fn default() -> Self {
+        Self::Ggsn
+    }
+}
+
+impl From<GtpRole> for u32 {
+    fn from(v: GtpRole) -> u32 {
+        match v {
+            GtpRole::Ggsn => 0,
+            GtpRole::Sgsn => 1,
+        }
+    }
+}
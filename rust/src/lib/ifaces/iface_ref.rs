@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, MergedInterfaces, NmstateError};
+
+// Kernel does not allow `:` in interface name, hence these prefixes never
+// conflict with real interface names.
+const IFACE_REF_MAC_PREFIX: &str = "mac:";
+const IFACE_REF_LABEL_PREFIX: &str = "label:";
+
+impl MergedInterfaces {
+    // Resolve interface reference used by routes and DNS:
+    //  * `mac:<MAC>`: The current kernel interface holding specified MAC
+    //    address. Permanent MAC address is preferred, so VLAN or bond
+    //    sharing the MAC address of its parent or port will not cause
+    //    ambiguity.
+    //  * `label:<key>=<value>`: The kernel interface with specified label
+    //    after merging desired state, so label and reference could be
+    //    defined in the same desired state.
+    // Return None if specified string is not an interface reference.
+    // Raise error if no interface or more than one interfaces matches.
+    pub(crate) fn resolve_iface_ref(
+        &self,
+        iface_ref: &str,
+    ) -> Result<Option<String>, NmstateError> {
+        let mut matches: Vec<&str> = if let Some(mac) =
+            iface_ref.strip_prefix(IFACE_REF_MAC_PREFIX)
+        {
+            let mac = mac.to_uppercase();
+            let is_mac_match = |mac_addr: Option<&String>| {
+                mac_addr
+                    .map(|m| m.to_uppercase() == mac)
+                    .unwrap_or_default()
+            };
+            let mut matches: Vec<&str> = self
+                .current_kernel_ifaces()
+                .filter(|i| {
+                    is_mac_match(i.base_iface().permanent_mac_address.as_ref())
+                })
+                .map(|i| i.name())
+                .collect();
+            if matches.is_empty() {
+                matches = self
+                    .current_kernel_ifaces()
+                    .filter(|i| {
+                        is_mac_match(i.base_iface().mac_address.as_ref())
+                    })
+                    .map(|i| i.name())
+                    .collect();
+            }
+            matches
+        } else if let Some(label) =
+            iface_ref.strip_prefix(IFACE_REF_LABEL_PREFIX)
+        {
+            let (key, value) = match label.split_once('=') {
+                Some((k, v)) if !k.is_empty() => (k, v),
+                _ => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid interface reference {iface_ref}, the \
+                            format should be 'label:<key>=<value>'"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            };
+            self.kernel_ifaces
+                .values()
+                .filter(|i| !i.merged.is_absent())
+                .map(|i| &i.merged)
+                .filter(|i| {
+                    i.base_iface()
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.labels.as_ref())
+                        .and_then(|l| l.get(key))
+                        .map(|v| v == value)
+                        .unwrap_or_default()
+                })
+                .map(|i| i.name())
+                .collect()
+        } else {
+            return Ok(None);
+        };
+
+        matches.sort_unstable();
+        match matches.as_slice() {
+            [iface_name] => {
+                log::info!(
+                    "Interface reference {} resolved to interface {}",
+                    iface_ref,
+                    iface_name
+                );
+                Ok(Some(iface_name.to_string()))
+            }
+            [] => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("No interface found for reference {iface_ref}"),
+                );
+                log::error!("{}", e);
+                Err(e)
+            }
+            _ => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface reference {iface_ref} is ambiguous, \
+                        matched interfaces: {}",
+                        matches.join(", ")
+                    ),
+                );
+                log::error!("{}", e);
+                Err(e)
+            }
+        }
+    }
+
+    fn current_kernel_ifaces(&self) -> impl Iterator<Item = &Interface> {
+        self.kernel_ifaces
+            .values()
+            .filter_map(|i| i.current.as_ref())
+    }
+}
@@ -2,7 +2,19 @@
 
 use serde::{Deserialize, Serialize, Serializer};
 
-use crate::{BaseInterface, InterfaceType};
+use crate::{
+    BaseInterface, ErrorKind, Interface, InterfaceType, MergedInterfaces,
+    NmstateError,
+};
+
+// The P-Key 0xffff is the default partition of base interface
+const IB_DEFAULT_PKEY: u16 = 0xffff;
+// The lower 15 bits of P-Key should not be 0, the highest bit is the
+// membership type
+const IB_PKEY_MEMBERSHIP_BIT: u16 = 0x8000;
+// The 4 bytes IPoIB header is included in the 4096 bytes IB link MTU
+const IB_DATAGRAM_MAX_MTU: u64 = 4092;
+const IB_CONNECTED_MAX_MTU: u64 = 65520;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -40,6 +52,70 @@ impl InfiniBandInterface {
     pub(crate) fn parent(&self) -> Option<&str> {
         self.ib.as_ref().and_then(|cfg| cfg.base_iface.as_deref())
     }
+
+    /// Whether this is a P-Key partition sub-interface created on top of
+    /// the base InfiniBand interface.
+    pub(crate) fn is_pkey_iface(&self) -> bool {
+        self.ib.as_ref().map(|cfg| {
+            cfg.pkey.is_some()
+                && cfg.pkey != Some(IB_DEFAULT_PKEY)
+                && !cfg.base_iface.as_deref().unwrap_or_default().is_empty()
+        }) == Some(true)
+    }
+
+    // Return None if InfiniBand mode is unknown
+    pub(crate) fn max_mtu(&self) -> Option<u64> {
+        self.ib.as_ref().map(|cfg| match cfg.mode {
+            InfiniBandMode::Datagram => IB_DATAGRAM_MAX_MTU,
+            InfiniBandMode::Connected => IB_CONNECTED_MAX_MTU,
+        })
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if !is_desired {
+            return Ok(());
+        }
+        if let Some(ib_conf) = self.ib.as_ref() {
+            let has_base_iface =
+                !ib_conf.base_iface.as_deref().unwrap_or_default().is_empty();
+            let pkey = ib_conf.pkey.filter(|p| *p != IB_DEFAULT_PKEY);
+            let e = match pkey {
+                Some(p) if p & !IB_PKEY_MEMBERSHIP_BIT == 0 => {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The pkey {p:#x} of InfiniBand interface {} is \
+                            invalid, 0x0000 and 0x8000 are reserved",
+                            self.base.name
+                        ),
+                    )
+                }
+                Some(p) if !has_base_iface => NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The base-iface is required for InfiniBand \
+                        interface {} with pkey {p:#x}",
+                        self.base.name
+                    ),
+                ),
+                None if has_base_iface => NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The pkey is required for InfiniBand interface {} \
+                        with base-iface defined",
+                        self.base.name
+                    ),
+                ),
+                _ => return Ok(()),
+            };
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
@@ -81,6 +157,8 @@ pub struct InfiniBandConfig {
     /// For pkey sub-interface only. Empty for base interface.
     pub base_iface: Option<String>,
     #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
         serialize_with = "show_as_hex",
         deserialize_with = "crate::deserializer::option_u16_or_string"
     )]
@@ -97,6 +175,90 @@ impl InfiniBandConfig {
     }
 }
 
+impl MergedInterfaces {
+    // NetworkManager fails with cryptic message when activating IPoIB
+    // interface with MTU exceeding the limit of its mode or VLAN on top of
+    // IPoIB, hence we validate them before apply.
+    pub(crate) fn validate_infiniband_mtu_and_vlan(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for iface in self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_desired() && i.merged.is_up())
+            .map(|i| &i.merged)
+        {
+            if let Interface::Vlan(vlan_iface) = iface {
+                if let Some(parent) = vlan_iface.parent() {
+                    if self.get_ib_max_mtu(parent).is_some() {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "VLAN interface {} cannot be created on top \
+                                of InfiniBand interface {parent}, please use \
+                                InfiniBand pkey sub-interface instead",
+                                iface.name()
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+                continue;
+            }
+            if let (Some(mtu), Some(max_mtu)) =
+                (iface.base_iface().mtu, self.get_ib_max_mtu(iface.name()))
+            {
+                if mtu > max_mtu {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        if iface.iface_type() == InterfaceType::InfiniBand {
+                            format!(
+                                "The MTU {mtu} of InfiniBand interface {} \
+                                exceeds the maximum {max_mtu} of its mode",
+                                iface.name()
+                            )
+                        } else {
+                            format!(
+                                "The MTU {mtu} of {} interface {} exceeds \
+                                the maximum {max_mtu} of its InfiniBand ports",
+                                iface.iface_type(),
+                                iface.name()
+                            )
+                        },
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Return the maximum MTU of InfiniBand interface or the minimum of
+    // maximum MTU of InfiniBand ports of bond interface.
+    // Return None if not InfiniBand or unknown.
+    fn get_ib_max_mtu(&self, iface_name: &str) -> Option<u64> {
+        match self.kernel_ifaces.get(iface_name).map(|i| &i.merged) {
+            Some(Interface::InfiniBand(ib_iface)) => ib_iface.max_mtu(),
+            Some(Interface::Bond(bond_iface)) => bond_iface
+                .ports()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|port| {
+                    match self.kernel_ifaces.get(port).map(|i| &i.merged) {
+                        Some(Interface::InfiniBand(ib_iface)) => {
+                            ib_iface.max_mtu()
+                        }
+                        _ => None,
+                    }
+                })
+                .min(),
+            _ => None,
+        }
+    }
+}
+
 fn show_as_hex<S>(v: &Option<u16>, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -12,31 +12,31 @@ use crate::{
     MergedInterface, NmstateError,
 };
 
-// The max loop count for Interfaces.set_ifaces_up_priority()
-// This allows interface with 4 nested levels in any order.
-// To support more nested level, user could place top controller at the
-// beginning of desire state
-const INTERFACES_SET_PRIORITY_MAX_RETRY: u32 = 4;
-
 const COPY_MAC_ALLOWED_IFACE_TYPES: [InterfaceType; 3] = [
     InterfaceType::Bond,
     InterfaceType::LinuxBridge,
     InterfaceType::OvsInterface,
 ];
 
+const MTU_INHERIT_IFACE_TYPES: [InterfaceType; 4] = [
+    InterfaceType::Vlan,
+    InterfaceType::MacVlan,
+    InterfaceType::MacVtap,
+    InterfaceType::IpVlan,
+];
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[non_exhaustive]
 /// Represent a list of [Interface] with special [serde::Deserializer] and
 /// [serde::Serializer].
 /// When applying complex nested interface(e.g. bridge over bond over vlan of
-/// eth1), the supported maximum nest level is 4 like previous example.
-/// For 5+ nested level, you need to place controller interface before its
-/// ports.
+/// eth1), the activation order is resolved from the controller and parent of
+/// each interface regardless of nest level. Dependency loop is rejected
+/// before applying.
 pub struct Interfaces {
     pub(crate) kernel_ifaces: HashMap<String, Interface>,
     pub(crate) user_ifaces: HashMap<(String, InterfaceType), Interface>,
-    // The insert_order is allowing user to provided ordered interface
-    // to support 5+ nested dependency.
+    // The insert_order is preserving user provided interface order.
     pub(crate) insert_order: Vec<(String, InterfaceType)>,
 }
 
@@ -88,7 +88,11 @@ impl Interfaces {
         for iface in self.user_ifaces.values() {
             ifaces.push(iface);
         }
-        ifaces.sort_unstable_by_key(|iface| iface.name());
+        // OVS internal interface might share the same name with OVS bridge,
+        // so also sort by interface type for stable output.
+        ifaces.sort_unstable_by(|a, b| {
+            (a.name(), a.iface_type()).cmp(&(b.name(), b.iface_type()))
+        });
         // Use sort_by_key() instead of unstable one, do we can alphabet
         // activation order which is required to simulate the OS boot-up.
         ifaces.sort_by_key(|iface| iface.base_iface().up_priority);
@@ -160,6 +164,24 @@ impl Interfaces {
     pub(crate) fn hide_secrets(&mut self) {
         for iface in self.kernel_ifaces.values_mut() {
             iface.base_iface_mut().hide_secrets();
+            if let Interface::WireGuard(iface) = iface {
+                iface.hide_secrets();
+            }
+            if let Interface::MacSec(iface) = iface {
+                iface.hide_secrets();
+            }
+            if let Interface::Pppoe(iface) = iface {
+                iface.hide_secrets();
+            }
+            if let Interface::Wifi(iface) = iface {
+                iface.hide_secrets();
+            }
+            if let Interface::Gsm(iface) = iface {
+                iface.hide_secrets();
+            }
+            if let Interface::Ethernet(iface) = iface {
+                iface.hide_secrets();
+            }
         }
     }
 
@@ -176,6 +198,7 @@ impl Interfaces {
     // Not allowing changing veth peer away from ignored peer unless previous
     // peer changed from ignore to managed
     // Not allowing creating veth without peer config
+    // Not allowing veth or netkit peer desired as up when other end is absent
     pub(crate) fn pre_ignore_check(
         &self,
         current: &Self,
@@ -183,6 +206,8 @@ impl Interfaces {
     ) -> Result<(), NmstateError> {
         self.validate_change_veth_ignored_peer(current, ignored_ifaces)?;
         self.validate_new_veth_without_peer(current)?;
+        self.validate_veth_pair_absent(current)?;
+        self.validate_netkit_pair_absent(current)?;
         Ok(())
     }
 
@@ -492,6 +517,7 @@ impl MergedInterfaces {
             desired.resolve_unknown_ifaces(&current)?;
         }
 
+        desired.include_veth_peer_ifaces()?;
         desired.auto_managed_controller_ports(&current);
 
         let ignored_ifaces = get_ignored_ifaces(&desired, &current);
@@ -601,11 +627,14 @@ impl MergedInterfaces {
         self.validate_controller_and_port_list_confliction()?;
         self.handle_changed_ports()?;
         self.resolve_port_iface_controller_type()?;
-        self._set_up_priority()?;
+        self.apply_mtu_propagate();
+        self.set_ifaces_up_priority()?;
         self.check_overbook_ports()?;
         self.check_infiniband_as_ports()?;
+        self.validate_infiniband_mtu_and_vlan()?;
         self.mark_orphan_interface_as_absent()?;
         self.process_veth_peer_changes()?;
+        self.process_netkit_peer_changes()?;
         for iface in self
             .kernel_ifaces
             .values_mut()
@@ -617,26 +646,6 @@ impl MergedInterfaces {
         Ok(())
     }
 
-    fn _set_up_priority(&mut self) -> Result<(), NmstateError> {
-        for _ in 0..INTERFACES_SET_PRIORITY_MAX_RETRY {
-            if self.set_ifaces_up_priority() {
-                return Ok(());
-            }
-        }
-        log::error!(
-            "Failed to set up priority: please order the interfaces in desire \
-            state to place controller before its ports"
-        );
-        Err(NmstateError::new(
-            ErrorKind::InvalidArgument,
-            "Failed to set up priority: nmstate only support nested interface \
-            up to 4 levels. To support more nest level, \
-            please order the interfaces in desire \
-            state to place controller before its ports"
-                .to_string(),
-        ))
-    }
-
     fn apply_copy_mac_from(&mut self) -> Result<(), NmstateError> {
         let mut pending_changes: HashMap<String, String> = HashMap::new();
         for (iface_name, merged_iface) in self.kernel_ifaces.iter() {
@@ -709,6 +718,65 @@ impl MergedInterfaces {
         Ok(())
     }
 
+    // Ports and VLAN alike interfaces not having MTU defined in desired state
+    // inherit MTU from interface with `mtu-propagate: true`.
+    fn apply_mtu_propagate(&mut self) {
+        let mut pending_changes: HashMap<String, u64> = HashMap::new();
+        for merged_iface in self
+            .kernel_ifaces
+            .values()
+            .chain(self.user_ifaces.values())
+            .filter(|i| i.is_desired() && i.merged.is_up())
+        {
+            let base_iface = merged_iface.merged.base_iface();
+            if base_iface.mtu_propagate != Some(true) {
+                continue;
+            }
+            let mtu = if let Some(m) = base_iface.mtu {
+                m
+            } else {
+                continue;
+            };
+            let mut child_names: Vec<&str> =
+                merged_iface.merged.ports().unwrap_or_default();
+            child_names.extend(
+                self.kernel_ifaces
+                    .values()
+                    .filter(|i| {
+                        MTU_INHERIT_IFACE_TYPES.contains(&i.merged.iface_type())
+                            && i.merged.parent()
+                                == Some(base_iface.name.as_str())
+                    })
+                    .map(|i| i.merged.name()),
+            );
+            for child_name in child_names {
+                if let Some(child_iface) = self.kernel_ifaces.get(child_name) {
+                    if !child_iface.merged.is_up()
+                        || child_iface
+                            .desired
+                            .as_ref()
+                            .and_then(|i| i.base_iface().mtu)
+                            .is_some()
+                        || (!child_iface.is_changed()
+                            && child_iface.merged.base_iface().mtu == Some(mtu))
+                    {
+                        continue;
+                    }
+                    log::info!(
+                        "Propagating MTU {mtu} of interface {} to {child_name}",
+                        base_iface.name
+                    );
+                    pending_changes.insert(child_name.to_string(), mtu);
+                }
+            }
+        }
+        for (iface_name, mtu) in pending_changes.drain() {
+            if let Some(iface) = self.kernel_ifaces.get_mut(&iface_name) {
+                iface.set_inherited_mtu(mtu);
+            }
+        }
+    }
+
     // Unlike orphan check in `apply_ctrller_change()`, this function is for
     // orphan interface without controller.
     fn mark_orphan_interface_as_absent(&mut self) -> Result<(), NmstateError> {
@@ -303,37 +303,111 @@ impl MergedInterfaces {
         Ok(())
     }
 
-    // Return True if we have all up_priority fixed.
-    pub(crate) fn set_ifaces_up_priority(&mut self) -> bool {
-        // Return true when all interface has correct priority.
-        let mut ret = true;
-        let mut pending_changes: HashMap<String, u32> = HashMap::new();
-        // Use the push order to allow user providing help on dependency order
-
+    // Resolve the up priority of interfaces to activate: an interface should
+    // be activated after its controller and its parent, hence its priority is
+    // the maximum priority of its dependencies plus one. Dependency loop is
+    // rejected with the offending chain.
+    pub(crate) fn set_ifaces_up_priority(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        let mut resolved: HashMap<(String, InterfaceType), u32> =
+            HashMap::new();
+        let mut chain: Vec<(String, InterfaceType)> = Vec::new();
+        // Use the push order to generate consistent error message
         for (iface_name, iface_type) in &self.insert_order {
-            let iface = match self.get_iface(iface_name, iface_type.clone()) {
-                Some(i) => {
-                    if let Some(i) = i.for_apply.as_ref() {
-                        i
-                    } else {
-                        continue;
-                    }
+            if let Some(merged_iface) =
+                self.get_iface(iface_name, iface_type.clone())
+            {
+                if merged_iface.for_apply.as_ref().map(|i| i.is_up())
+                    == Some(true)
+                {
+                    self.resolve_up_priority(
+                        merged_iface,
+                        &mut resolved,
+                        &mut chain,
+                    )?;
+                }
+            }
+        }
+
+        log::debug!("Resolved up priority {:?}", resolved);
+        for iface in self
+            .kernel_ifaces
+            .values_mut()
+            .chain(self.user_ifaces.values_mut())
+        {
+            let key =
+                (iface.merged.name().to_string(), iface.merged.iface_type());
+            if let Some(apply_iface) =
+                iface.for_apply.as_mut().filter(|i| i.is_up())
+            {
+                if let Some(priority) = resolved.get(&key) {
+                    apply_iface.base_iface_mut().up_priority = *priority;
                 }
-                None => continue,
-            };
-            if !iface.is_up() {
-                continue;
             }
+        }
+        Ok(())
+    }
+
+    fn resolve_up_priority(
+        &self,
+        merged_iface: &MergedInterface,
+        resolved: &mut HashMap<(String, InterfaceType), u32>,
+        chain: &mut Vec<(String, InterfaceType)>,
+    ) -> Result<u32, NmstateError> {
+        let key = (
+            merged_iface.merged.name().to_string(),
+            merged_iface.merged.iface_type(),
+        );
+        if let Some(priority) = resolved.get(&key) {
+            return Ok(*priority);
+        }
+        if let Some(pos) = chain.iter().position(|k| k == &key) {
+            let mut names: Vec<&str> =
+                chain[pos..].iter().map(|(n, _)| n.as_str()).collect();
+            names.push(key.0.as_str());
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Interface dependency loop found: {}, please check the \
+                    controller and parent of these interfaces",
+                    names.join(" -> ")
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        let iface = match merged_iface.for_apply.as_ref() {
+            Some(i) => i,
+            None => return Ok(0),
+        };
 
-            if iface.base_iface().is_up_priority_valid() {
+        chain.push(key.clone());
+        let mut priority = 0;
+        for dep_iface in self.get_up_dependencies(iface) {
+            if (dep_iface.merged.name(), dep_iface.merged.iface_type())
+                == (key.0.as_str(), key.1.clone())
+            {
                 continue;
             }
+            priority = std::cmp::max(
+                priority,
+                self.resolve_up_priority(dep_iface, resolved, chain)? + 1,
+            );
+        }
+        chain.pop();
 
-            if let Some(ref ctrl_name) = iface.base_iface().controller {
-                if ctrl_name.is_empty() {
-                    continue;
-                }
-                let ctrl_iface = self
+        resolved.insert(key, priority);
+        Ok(priority)
+    }
+
+    // Interfaces required to be activated before specified interface:
+    // its controller and its parent, only those in the apply list.
+    fn get_up_dependencies(&self, iface: &Interface) -> Vec<&MergedInterface> {
+        let mut ret = Vec::new();
+        if let Some(ctrl_name) = iface.base_iface().controller.as_deref() {
+            if !ctrl_name.is_empty() {
+                if let Some(ctrl_iface) = self
                     .get_iface(
                         ctrl_name,
                         iface
@@ -342,86 +416,21 @@ impl MergedInterfaces {
                             .clone()
                             .unwrap_or_default(),
                     )
-                    .and_then(|i| i.for_apply.as_ref());
-                if let Some(ctrl_iface) = ctrl_iface {
-                    if let Some(ctrl_pri) = pending_changes.remove(ctrl_name) {
-                        pending_changes.insert(ctrl_name.to_string(), ctrl_pri);
-                        pending_changes
-                            .insert(iface_name.to_string(), ctrl_pri + 1);
-                    } else if ctrl_iface.base_iface().is_up_priority_valid() {
-                        pending_changes.insert(
-                            iface_name.to_string(),
-                            ctrl_iface.base_iface().up_priority + 1,
-                        );
-                    } else {
-                        // Its controller does not have valid up priority yet.
-                        log::debug!(
-                            "Controller {ctrl_name} of {iface_name} is has no \
-                            up priority"
-                        );
-                        ret = false;
-                    }
-                } else {
-                    // Interface has no controller defined in desire
-                    continue;
-                }
-            } else {
-                continue;
-            }
-        }
-
-        // If not remaining unknown up_priority, we set up the parent/child
-        // up_priority
-        if ret {
-            for (iface_name, iface_type) in &self.insert_order {
-                let iface = match self.get_iface(iface_name, iface_type.clone())
+                    .filter(|i| i.for_apply.is_some())
                 {
-                    Some(i) => {
-                        if let Some(i) = i.for_apply.as_ref() {
-                            i
-                        } else {
-                            continue;
-                        }
-                    }
-                    None => continue,
-                };
-                if !iface.is_up() {
-                    continue;
-                }
-                if let Some(parent) = iface.parent() {
-                    let parent_priority = pending_changes.get(parent).cloned();
-                    if let Some(parent_priority) = parent_priority {
-                        pending_changes.insert(
-                            iface_name.to_string(),
-                            parent_priority + 1,
-                        );
-                    } else if let Some(parent_iface) = self
-                        .kernel_ifaces
-                        .get(parent)
-                        .and_then(|i| i.for_apply.as_ref())
-                    {
-                        if parent_iface.base_iface().is_up_priority_valid() {
-                            pending_changes.insert(
-                                iface_name.to_string(),
-                                parent_iface.base_iface().up_priority + 1,
-                            );
-                        }
-                    }
+                    ret.push(ctrl_iface);
                 }
             }
         }
-
-        log::debug!("Pending kernel up priority changes {:?}", pending_changes);
-        for (iface_name, priority) in pending_changes.iter() {
-            if let Some(iface) = self
+        if let Some(parent) = iface.parent() {
+            if let Some(parent_iface) = self
                 .kernel_ifaces
-                .get_mut(iface_name)
-                .and_then(|i| i.for_apply.as_mut())
+                .get(parent)
+                .filter(|i| i.for_apply.is_some())
             {
-                iface.base_iface_mut().up_priority = *priority;
+                ret.push(parent_iface);
             }
         }
-
         ret
     }
 
@@ -446,6 +455,8 @@ impl MergedInterfaces {
 
     // Infiniband over IP can only be port of active_backup bond as it is a
     // layer 3 interface like tun.
+    // Kernel only allows InfiniBand interfaces as ports of active-backup
+    // bond, and bond cannot mix InfiniBand ports with other link types.
     pub(crate) fn check_infiniband_as_ports(&self) -> Result<(), NmstateError> {
         let ib_iface_names: HashSet<&str> = self
             .kernel_ifaces
@@ -463,25 +474,62 @@ impl MergedInterfaces {
             .map(|i| &i.merged)
         {
             if let Some(ports) = iface.ports() {
-                let ports = HashSet::from_iter(ports.iter().cloned());
-                if !ib_iface_names.is_disjoint(&ports) {
-                    if let Interface::Bond(iface) = iface {
-                        if iface.mode() == Some(BondMode::ActiveBackup) {
-                            continue;
-                        }
+                let ports: HashSet<&str> = HashSet::from_iter(ports);
+                let mut ib_ports: Vec<&str> =
+                    ib_iface_names.intersection(&ports).copied().collect();
+                if ib_ports.is_empty() {
+                    continue;
+                }
+                ib_ports.sort_unstable();
+                let e = if let Interface::Bond(bond_iface) = iface {
+                    if bond_iface.mode() != Some(BondMode::ActiveBackup) {
+                        NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "InfiniBand interface {} cannot use as \
+                                port of bond {} in mode {}. Only \
+                                active-backup bond allowed.",
+                                ib_ports.join(", "),
+                                iface.name(),
+                                bond_iface
+                                    .mode()
+                                    .map(|m| m.to_string())
+                                    .unwrap_or_default()
+                            ),
+                        )
+                    } else if ib_ports.len() != ports.len() {
+                        let mut other_ports: Vec<&str> = ports
+                            .difference(&ib_iface_names)
+                            .copied()
+                            .collect();
+                        other_ports.sort_unstable();
+                        NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Bond {} cannot mix InfiniBand ports {} \
+                                with non-InfiniBand ports {}",
+                                iface.name(),
+                                ib_ports.join(", "),
+                                other_ports.join(", ")
+                            ),
+                        )
+                    } else {
+                        continue;
                     }
-                    let e = NmstateError::new(
+                } else {
+                    NmstateError::new(
                         ErrorKind::InvalidArgument,
                         format!(
-                            "InfiniBand interface {:?} cannot use as \
-                            port of {}. Only active-backup bond allowed.",
-                            ib_iface_names.intersection(&ports),
+                            "InfiniBand interface {} cannot use as \
+                            port of {} {}. Only active-backup bond allowed.",
+                            ib_ports.join(", "),
+                            iface.iface_type(),
                             iface.name()
                         ),
-                    );
-                    log::error!("{}", e);
-                    return Err(e);
-                }
+                    )
+                };
+                log::error!("{}", e);
+                return Err(e);
             }
         }
         Ok(())
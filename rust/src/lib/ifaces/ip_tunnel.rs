@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPIP tunnel interface carrying IPv4 over IPv4.
+/// Nispor does not provide IPIP configuration yet, hence the `ipip` section is
+/// only queried from NetworkManager backend. The example yaml output of
+/// [crate::NetworkState] with an IPIP interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ipip1
+///   type: ipip
+///   state: up
+///   ipip:
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     ttl: 64
+///     pmtudisc: true
+/// ```
+pub struct IpIpInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipip: Option<IpTunnelConfig>,
+}
+
+impl Default for IpIpInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::IpIp,
+                ..Default::default()
+            },
+            ipip: None,
+        }
+    }
+}
+
+impl IpIpInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.ipip.as_mut() {
+            if is_desired && conf.sixrd_prefix.is_some() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The 6rd-prefix is only valid for SIT interface, \
+                        but defined in IPIP interface {}",
+                        self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            conf.sanitize(self.base.name.as_str(), is_desired)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel SIT tunnel interface carrying IPv6 over IPv4, commonly used
+/// for IPv6 transition mechanisms like 6in4 and 6rd. The `sit` section shares
+/// the same properties with [IpIpInterface] along with the `6rd-prefix`.
+/// The example yaml output of [crate::NetworkState] with a SIT interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: sit1
+///   type: sit
+///   state: up
+///   sit:
+///     local: 192.0.2.1
+///     ttl: 64
+///     6rd-prefix: 2001:db8::/32
+/// ```
+pub struct SitInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sit: Option<IpTunnelConfig>,
+}
+
+impl Default for SitInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Sit,
+                ..Default::default()
+            },
+            sit: None,
+        }
+    }
+}
+
+impl SitInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.sit.as_mut() {
+            conf.sanitize(self.base.name.as_str(), is_desired)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpTunnelConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local IPv4 address of the tunnel endpoint.
+    pub local: Option<std::net::Ipv4Addr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote IPv4 address of the tunnel endpoint.
+    pub remote: Option<std::net::Ipv4Addr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Time to live of tunneled packets. Set to 0 to inherit from the inner
+    /// packet.
+    pub ttl: Option<u8>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to enable Path MTU Discovery on the tunnel. Disabling it
+    /// requires `ttl` to be 0.
+    pub pmtudisc: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "6rd-prefix")]
+    /// IPv6 prefix of IPv6 Rapid Deployment(6rd), only valid for SIT
+    /// interface. This property is applied via netlink and not stored in
+    /// NetworkManager profile.
+    /// Deserialize and serialize from/to `6rd-prefix`.
+    pub sixrd_prefix: Option<String>,
+}
+
+impl IpTunnelConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sanitize(
+        &mut self,
+        iface_name: &str,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired
+            && self.pmtudisc == Some(false)
+            && self.ttl.unwrap_or_default() != 0
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The ttl {} of tunnel interface {iface_name} should be \
+                    0 when pmtudisc is disabled",
+                    self.ttl.unwrap_or_default()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(prefix) = self.sixrd_prefix.as_mut() {
+            match ipnet::Ipv6Net::from_str(prefix) {
+                Ok(p) => {
+                    *prefix = p.trunc().to_string();
+                }
+                Err(e) => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid 6rd-prefix {prefix} of tunnel interface \
+                            {iface_name}: {e}"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceState, InterfaceType, NmstateError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel static(unmanaged) L2TPv3 ethernet pseudowire. Each interface
+/// is a L2TPv3 session, the L2TPv3 tunnel carrying the session is created
+/// on demand and shared by all sessions using the same `tunnel-id`.
+/// There is no control connection, the tunnel and session identifiers
+/// should match the peer's configuration.
+/// NetworkManager does not support L2TPv3 pseudowire, hence it is created via
+/// generic netlink and not persisted by NetworkManager backend.
+/// Kernel cannot change the tunnel or session of existing pseudowire, nmstate
+/// will recreate the interface when they changed.
+/// The example yaml output of [crate::NetworkState] with a L2TPv3 interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: l2tpeth0
+///   type: l2tpv3
+///   state: up
+///   l2tpv3:
+///     tunnel-id: 1000
+///     peer-tunnel-id: 2000
+///     session-id: 10
+///     peer-session-id: 20
+///     encap: udp
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     udp-source-port: 5000
+///     udp-destination-port: 5000
+/// ```
+pub struct L2tpv3Interface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l2tpv3: Option<L2tpv3Config>,
+}
+
+impl Default for L2tpv3Interface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::L2tpv3,
+                ..Default::default()
+            },
+            l2tpv3: None,
+        }
+    }
+}
+
+impl L2tpv3Interface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired && self.base.state == InterfaceState::Up {
+            if let Some(conf) = self.l2tpv3.as_ref() {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct L2tpv3Config {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Local tunnel identifier, should not be 0.
+    /// Serialize and deserialize to/from `tunnel-id`.
+    pub tunnel_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Tunnel identifier used by the peer, should not be 0.
+    /// Serialize and deserialize to/from `peer-tunnel-id`.
+    pub peer_tunnel_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Local session identifier, should not be 0.
+    /// Serialize and deserialize to/from `session-id`.
+    pub session_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Session identifier used by the peer, should not be 0.
+    /// Serialize and deserialize to/from `peer-session-id`.
+    pub peer_session_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Encapsulation of the tunnel. Default to [L2tpv3Encap::Udp] when
+    /// creating the tunnel.
+    pub encap: Option<L2tpv3Encap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local address of the tunnel endpoint.
+    pub local: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote address of the tunnel endpoint.
+    pub remote: Option<IpAddr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Local UDP port of the tunnel, only valid for [L2tpv3Encap::Udp].
+    /// Serialize and deserialize to/from `udp-source-port`.
+    pub udp_source_port: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Remote UDP port of the tunnel, only valid for [L2tpv3Encap::Udp].
+    /// Serialize and deserialize to/from `udp-destination-port`.
+    pub udp_destination_port: Option<u16>,
+}
+
+impl L2tpv3Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn encap(&self) -> L2tpv3Encap {
+        self.encap.unwrap_or_default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        for (prop, value) in [
+            ("tunnel-id", self.tunnel_id),
+            ("peer-tunnel-id", self.peer_tunnel_id),
+            ("session-id", self.session_id),
+            ("peer-session-id", self.peer_session_id),
+        ] {
+            if value.unwrap_or_default() == 0 {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The {prop} of L2TPv3 interface {iface_name} should \
+                        be defined and not be 0"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        match (self.local, self.remote) {
+            (Some(local), Some(remote)) => {
+                if local.is_ipv6() != remote.is_ipv6() {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The local address {local} and remote address \
+                            {remote} of L2TPv3 interface {iface_name} should \
+                            be in the same address family"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            _ => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Both local and remote address of L2TPv3 interface \
+                        {iface_name} should be defined"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        let has_udp_port = self.udp_source_port.is_some()
+            || self.udp_destination_port.is_some();
+        match self.encap() {
+            L2tpv3Encap::Ip if has_udp_port => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The UDP ports of L2TPv3 interface {iface_name} are \
+                        only valid for udp encapsulation"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            L2tpv3Encap::Udp
+                if self.udp_source_port.unwrap_or_default() == 0
+                    || self.udp_destination_port.unwrap_or_default() == 0 =>
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The udp-source-port and udp-destination-port of \
+                        L2TPv3 interface {iface_name} should be defined and \
+                        not be 0 for udp encapsulation"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum L2tpv3Encap {
+    /// Encapsulate in UDP.
+    /// Deserialize and serialize from/to `udp`.
+    Udp,
+    /// Encapsulate directly in IP using protocol number 115.
+    /// Deserialize and serialize from/to `ip`.
+    Ip,
+}
+
+impl Default for L2tpv3Encap {
+    fn default() -> Self {
+        Self::Udp
+    }
+}
+
+impl std::fmt::Display for L2tpv3Encap {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Udp => "udp",
+                Self::Ip => "ip",
+            }
+        )
+    }
+}
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Link status and statistics of interface reported by kernel.
+/// Only available in running state, ignored during apply.
+pub struct LinkStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Whether kernel has detected carrier(lower layer up).
+    pub carrier: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Count of carrier transitions since interface created.
+    /// Serialize and deserialize to/from `carrier-changes`.
+    pub carrier_changes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Count of carrier up events since interface created.
+    /// Serialize and deserialize to/from `carrier-up-count`.
+    pub carrier_up_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Count of carrier down events since interface created.
+    /// Serialize and deserialize to/from `carrier-down-count`.
+    pub carrier_down_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Speed in Mbps actually negotiated with link partner.
+    /// Serialize and deserialize to/from `negotiated-speed`.
+    pub negotiated_speed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Speed in Mbps configured on this interface: the highest advertised
+    /// link mode when auto negotiation is enabled, or the forced speed
+    /// otherwise.
+    /// Serialize and deserialize to/from `configured-speed`.
+    pub configured_speed: Option<u32>,
+}
+
+impl LinkStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
@@ -117,6 +117,13 @@ impl LinuxBridgeInterface {
         self.flatten_port_vlan_ranges();
         self.sort_port_vlans();
         self.remove_runtime_only_timers();
+        if let Some(fdb_entries) =
+            self.bridge.as_mut().and_then(|b| b.fdb.as_mut())
+        {
+            for fdb_entry in fdb_entries.iter_mut() {
+                fdb_entry.sanitize(is_desired)?;
+            }
+        }
         if let Some(port_confs) = self
             .bridge
             .as_ref()
@@ -157,7 +164,7 @@ impl LinuxBridgeInterface {
         }
     }
 
-    fn sort_port_vlans(&mut self) {
+    pub(crate) fn sort_port_vlans(&mut self) {
         if let Some(port_confs) = self
             .bridge
             .as_mut()
@@ -172,7 +179,29 @@ impl LinuxBridgeInterface {
         }
     }
 
-    fn sort_ports(&mut self) {
+    // With `exact: false`, current ports not mentioned in desired port list
+    // are preserved.
+    pub(crate) fn include_current_ports(&mut self, current: &Self) {
+        let cur_port_confs =
+            match current.bridge.as_ref().and_then(|b| b.port.as_ref()) {
+                Some(p) => p,
+                None => return,
+            };
+        if let Some(port_confs) = self
+            .bridge
+            .as_mut()
+            .filter(|b| b.exact == Some(false))
+            .and_then(|b| b.port.as_mut())
+        {
+            for cur_port_conf in cur_port_confs {
+                if !port_confs.iter().any(|p| p.name == cur_port_conf.name) {
+                    port_confs.push(cur_port_conf.clone());
+                }
+            }
+        }
+    }
+
+    pub(crate) fn sort_ports(&mut self) {
         if let Some(ref mut br_conf) = self.bridge {
             if let Some(ref mut port_confs) = &mut br_conf.port {
                 port_confs.sort_unstable_by_key(|p| p.name.clone())
@@ -345,8 +374,24 @@ pub struct LinuxBridgeConfig {
     pub options: Option<LinuxBridgeOptions>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "ports")]
     /// Linux bridge ports. When applying, desired port list will __override__
-    /// current port list.
+    /// current port list unless `exact` is set to false.
     pub port: Option<Vec<LinuxBridgePortConfig>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// When set to false, current ports not mentioned in desired port list
+    /// will be kept attached to this bridge. When undefined or set to true,
+    /// desired port list overrides current port list.
+    pub exact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Static forwarding database entries. When applying, entries are added
+    /// to current FDB, use `state: absent` to remove an entry.
+    /// Only included in queried state when
+    /// [crate::NetworkState::set_include_fdb()] is set, learned entries are
+    /// included with `learned: true`.
+    pub fdb: Option<Vec<LinuxBridgeFdbEntry>>,
 }
 
 impl LinuxBridgeConfig {
@@ -355,6 +400,115 @@ impl LinuxBridgeConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Linux bridge forwarding database(FDB) entry.
+pub struct LinuxBridgeFdbEntry {
+    /// MAC address in the format: upper case hex string separated by `:` on
+    /// every two characters. Case insensitive when applying.
+    /// Serialize and deserialize to/from `mac-address`.
+    pub mac_address: String,
+    /// The bridge port this MAC address is forwarded to. For VXLAN remote
+    /// entry, this should be the VXLAN interface attached to this bridge.
+    pub port: String,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// VLAN ID of this entry.
+    pub vlan: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IP address of remote VXLAN tunnel endpoint. Only valid when `port` is
+    /// VXLAN interface.
+    pub remote: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Whether this entry is learned dynamically by kernel.
+    /// Ignored during apply.
+    pub learned: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Only valid for applying, set to `absent` to remove this entry.
+    pub state: Option<LinuxBridgeFdbEntryState>,
+}
+
+impl LinuxBridgeFdbEntry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn is_absent(&self) -> bool {
+        self.state == Some(LinuxBridgeFdbEntryState::Absent)
+    }
+
+    pub(crate) fn is_match(&self, other: &Self) -> bool {
+        self.mac_address == other.mac_address
+            && self.port == other.port
+            && self.vlan == other.vlan
+            && self.remote == other.remote
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        self.mac_address.make_ascii_uppercase();
+        if is_desired {
+            self.learned = None;
+            if self.mac_bytes().is_none() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid MAC address {} in FDB entry of port {}",
+                        self.mac_address, self.port
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let Some(remote) = self.remote.as_deref() {
+                match std::net::IpAddr::from_str(remote) {
+                    Ok(ip) => self.remote = Some(ip.to_string()),
+                    Err(_) => {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Invalid remote IP address {remote} in FDB \
+                                entry {} of port {}",
+                                self.mac_address, self.port
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Convert MAC address like `00:23:45:67:89:1A` to bytes.
+    pub(crate) fn mac_bytes(&self) -> Option<Vec<u8>> {
+        let bytes = self
+            .mac_address
+            .split(':')
+            .map(|b| u8::from_str_radix(b, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        if bytes.len() == 6 {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum LinuxBridgeFdbEntryState {
+    Absent,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
@@ -86,7 +86,7 @@ pub struct MacVtapConfig {
         deserialize_with = "crate::deserializer::option_bool_or_string"
     )]
     /// Serialize to `promiscuous`.
-    /// Deserialize from `promiscuous` or `accept-all-mac`.
+    /// Deserialize from `promiscuous` or deprecated `accept-all-mac`.
     pub accept_all_mac: Option<bool>,
 }
 
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, NetworkState, NmstateError,
+};
+
+const MKA_CAK_LENGTHS: [usize; 2] = [32, 64];
+const MKA_CKN_MAX_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel MACsec(IEEE 802.1AE) interface. The MACsec Key Agreement
+/// (MKA) using pre-shared key (CAK and CKN) is done by `wpa_supplicant`
+/// spawned by NetworkManager, hence the `mka-cak` and `mka-ckn` are only
+/// supported by NetworkManager backend and are not verified against kernel.
+/// The `mka-cak` is hidden by [NetworkState::PASSWORD_HID_BY_NMSTATE] unless
+/// querying with secrets included. The example yaml output of
+/// [crate::NetworkState] with a MACsec interface would be:
+/// ```yml
+/// interfaces:
+/// - name: macsec0
+///   type: macsec
+///   state: up
+///   macsec:
+///     base-iface: eth1
+///     encrypt: true
+///     port: 1
+///     validation: strict
+///     send-sci: true
+///     mka-cak: <_password_hid_by_nmstate>
+///     mka-ckn: 0123456789abcdef0123456789abcdef
+/// ```
+pub struct MacSecInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub macsec: Option<MacSecConfig>,
+}
+
+impl Default for MacSecInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::MacSec,
+                ..Default::default()
+            },
+            macsec: None,
+        }
+    }
+}
+
+impl MacSecInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.macsec.as_ref().and_then(|cfg| {
+            if cfg.base_iface.is_empty() {
+                None
+            } else {
+                Some(cfg.base_iface.as_str())
+            }
+        })
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.macsec.as_mut() {
+            if let Some(ckn) = conf.mka_ckn.as_mut() {
+                ckn.make_ascii_lowercase();
+            }
+            if is_desired {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(conf) = self.macsec.as_mut() {
+            if conf.mka_cak.is_some() {
+                conf.mka_cak =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct MacSecConfig {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    /// Parent interface carrying the encrypted traffic.
+    /// Deserialize and serialize from/to `base-iface`.
+    pub base_iface: String,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to encrypt the traffic. When set to false, only integrity is
+    /// protected.
+    pub encrypt: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Port number of Secure Channel Identifier(SCI), between 1 and 65534.
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Validation mode of incoming frames.
+    pub validation: Option<MacSecValidate>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to include Secure Channel Identifier(SCI) in every packet.
+    /// Deserialize and serialize from/to `send-sci`.
+    pub send_sci: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Pre-shared Connectivity Association Key(CAK) for MKA in 32 or 64
+    /// hexadecimal characters.
+    /// Deserialize and serialize from/to `mka-cak`.
+    pub mka_cak: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Pre-shared Connectivity Association Key Name(CKN) for MKA in even
+    /// number of hexadecimal characters up to 64.
+    /// Deserialize and serialize from/to `mka-ckn`.
+    pub mka_ckn: Option<String>,
+}
+
+impl MacSecConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if self.port == Some(0) || self.port == Some(u16::MAX) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid MACsec port {} of interface {iface_name}, \
+                    should be in the range of 1 to 65534",
+                    self.port.unwrap_or_default()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.mka_cak.is_some() != self.mka_ckn.is_some() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The mka-cak and mka-ckn of MACsec interface \
+                    {iface_name} should be defined together"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(cak) = self.mka_cak.as_deref() {
+            if cak != NetworkState::PASSWORD_HID_BY_NMSTATE
+                && (!MKA_CAK_LENGTHS.contains(&cak.len()) || !is_hex(cak))
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid mka-cak of MACsec interface {iface_name}, \
+                        should be 32 or 64 hexadecimal characters"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if let Some(ckn) = self.mka_ckn.as_deref() {
+            if ckn.is_empty()
+                || ckn.len() > MKA_CKN_MAX_LENGTH
+                || ckn.len() % 2 != 0
+                || !is_hex(ckn)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid mka-ckn {ckn} of MACsec interface \
+                        {iface_name}, should be even number of hexadecimal \
+                        characters up to {MKA_CKN_MAX_LENGTH}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum MacSecValidate {
+    /// Accept all incoming frames without validation.
+    /// Deserialize and serialize from/to `disabled`.
+    Disabled,
+    /// Validate incoming frames but accept invalid ones.
+    /// Deserialize and serialize from/to `check`.
+    Check,
+    /// Drop incoming frames failed the validation.
+    /// Deserialize and serialize from/to `strict`.
+    Strict,
+}
+
+impl Default for MacSecValidate {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl From<MacSecValidate> for i32 {
+    fn from(v: MacSecValidate) -> i32 {
+        match v {
+            MacSecValidate::Disabled => 0,
+            MacSecValidate::Check => 1,
+            MacSecValidate::Strict => 2,
+        }
+    }
+}
@@ -1,57 +1,109 @@
 mod base;
+mod batadv;
 mod bond;
 mod bridge_vlan;
+mod can;
 mod dummy;
 mod ethernet;
 mod ethtool;
+mod geneve;
+mod gre;
+mod gsm;
+mod gtp;
+mod iface_ref;
 pub(crate) mod inter_ifaces;
+mod ipvlan;
+mod l2tpv3;
+mod link_stats;
 mod loopback;
+mod netkit;
+mod pci_hints;
+mod pppoe;
+mod tun;
+mod vcan;
 mod vrf;
+mod vti;
 mod vxlan;
+mod wifi;
+mod wireguard;
+mod xfrm;
 // The pub(crate) is only for unit test
 mod infiniband;
 pub(crate) mod inter_ifaces_controller;
+mod ip_tunnel;
 mod linux_bridge;
 mod mac_vlan;
 mod mac_vtap;
+mod macsec;
 mod ovs;
 mod sriov;
 mod vlan;
 
 pub use base::*;
+pub use batadv::{
+    BatadvConfig, BatadvGwMode, BatadvInterface, BatadvRoutingAlgorithm,
+};
 pub use bond::{
     BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
     BondConfig, BondFailOverMac, BondInterface, BondLacpRate, BondMode,
-    BondOptions, BondPrimaryReselect, BondXmitHashPolicy,
+    BondOptions, BondPortConfig, BondPrimaryReselect, BondXmitHashPolicy,
 };
 pub use bridge_vlan::{
     BridgePortTunkTag, BridgePortVlanConfig, BridgePortVlanMode,
     BridgePortVlanRange,
 };
+pub use can::{CanConfig, CanInterface};
 pub use dummy::DummyInterface;
 pub use ethernet::{
-    EthernetConfig, EthernetDuplex, EthernetInterface, VethConfig,
+    EthernetConfig, EthernetDuplex, EthernetInterface, EthernetUsbRole,
+    EthernetWakeOnLanConfig, VethConfig,
 };
+#[cfg(feature = "query_apply")]
+pub(crate) use ethtool::{flow_rule_ip_to_string, parse_flow_rule_ip};
 pub use ethtool::{
-    EthtoolCoalesceConfig, EthtoolConfig, EthtoolFeatureConfig,
-    EthtoolPauseConfig, EthtoolRingConfig,
+    EthtoolChannelsConfig, EthtoolCoalesceConfig, EthtoolConfig,
+    EthtoolEeeConfig, EthtoolFeatureConfig, EthtoolFlowAction, EthtoolFlowRule,
+    EthtoolFlowType, EthtoolPauseConfig, EthtoolQueueCoalesceConfig,
+    EthtoolRingConfig,
 };
+pub use geneve::{GeneveConfig, GeneveInterface};
+pub use gre::{GreConfig, GreInterface, GreTapInterface};
+pub use gsm::{GsmBearer, GsmConfig, GsmInterface};
+pub use gtp::{GtpConfig, GtpInterface, GtpRole};
 pub use infiniband::{InfiniBandConfig, InfiniBandInterface, InfiniBandMode};
 pub(crate) use inter_ifaces::MergedInterfaces;
 pub use inter_ifaces::*;
+pub use ip_tunnel::{IpIpInterface, IpTunnelConfig, SitInterface};
+pub use ipvlan::{IpVlanConfig, IpVlanInterface, IpVlanMode};
+pub use l2tpv3::{L2tpv3Config, L2tpv3Encap, L2tpv3Interface};
+pub use link_stats::LinkStats;
 pub use linux_bridge::{
-    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
-    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
+    LinuxBridgeConfig, LinuxBridgeFdbEntry, LinuxBridgeFdbEntryState,
+    LinuxBridgeInterface, LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
+    LinuxBridgePortConfig, LinuxBridgeStpOptions,
 };
 pub use loopback::LoopbackInterface;
 pub use mac_vlan::{MacVlanConfig, MacVlanInterface, MacVlanMode};
 pub use mac_vtap::{MacVtapConfig, MacVtapInterface, MacVtapMode};
+pub use macsec::{MacSecConfig, MacSecInterface, MacSecValidate};
+pub use netkit::{NetkitConfig, NetkitInterface, NetkitMode, NetkitPolicy};
 pub use ovs::{
     OvsBridgeBondConfig, OvsBridgeBondMode, OvsBridgeBondPortConfig,
     OvsBridgeConfig, OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
     OvsDpdkConfig, OvsInterface, OvsPatchConfig,
 };
-pub use sriov::{SrIovConfig, SrIovVfConfig};
+pub use pci_hints::PciHints;
+pub use pppoe::{PppoeConfig, PppoeInterface};
+pub use sriov::{SrIovConfig, SrIovEswitchMode, SrIovVfConfig};
+pub use tun::{TunConfig, TunInterface, TunMode};
+pub use vcan::VcanInterface;
 pub use vlan::{VlanConfig, VlanInterface, VlanProtocol};
 pub use vrf::{VrfConfig, VrfInterface};
+pub use vti::{Vti6Interface, VtiConfig, VtiInterface};
 pub use vxlan::{VxlanConfig, VxlanInterface};
+pub use wifi::{
+    WifiBand, WifiConfig, WifiInterface, WifiKeyMgmt, WifiMode, WifiSecretFlag,
+    WifiSecurityConfig,
+};
+pub use wireguard::{WireGuardConfig, WireGuardInterface, WireGuardPeerConfig};
+pub use xfrm::{XfrmConfig, XfrmInterface};
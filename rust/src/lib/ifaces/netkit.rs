@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, Interface, InterfaceType, Interfaces,
+    MergedInterfaces, NmstateError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel netkit device pair. Similar to veth pair, but the traffic is
+/// processed by BPF programs attached to the primary device instead of
+/// going through the backlog queue.
+/// Like veth pair, marking either end as absent will remove the whole pair.
+/// Kernel does not support changing the peer, mode or policy of existing
+/// netkit pair, hence nmstate will delete and recreate the pair when they
+/// changed.
+/// NetworkManager does not support netkit device, hence it is created via
+/// netlink and not persisted by NetworkManager backend.
+/// The example yaml output of [crate::NetworkState] with a netkit pair
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: nk0
+///   type: netkit
+///   state: up
+///   netkit:
+///     peer: nk0.ep
+///     mode: l3
+///     policy: pass
+///     peer-policy: drop
+/// - name: nk0.ep
+///   type: netkit
+///   state: up
+///   netkit:
+///     peer: nk0
+/// ```
+pub struct NetkitInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub netkit: Option<NetkitConfig>,
+}
+
+impl Default for NetkitInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Netkit,
+                ..Default::default()
+            },
+            netkit: None,
+        }
+    }
+}
+
+impl NetkitInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.netkit.as_ref() {
+                if conf.peer.is_empty() || conf.peer == self.base.name {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The peer of netkit interface {} should be \
+                            defined and not be itself",
+                            self.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn peer(&self) -> Option<&str> {
+        self.netkit.as_ref().map(|c| c.peer.as_str())
+    }
+
+    // Whether kernel need to recreate the netkit pair for desired config
+    pub(crate) fn is_link_changed(&self, current: &Self) -> bool {
+        if let (Some(des), Some(cur)) =
+            (self.netkit.as_ref(), current.netkit.as_ref())
+        {
+            des.peer != cur.peer
+                || (des.mode.is_some() && des.mode != cur.mode)
+                || (des.policy.is_some() && des.policy != cur.policy)
+                || (des.peer_policy.is_some()
+                    && des.peer_policy != cur.peer_policy)
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct NetkitConfig {
+    /// The name of netkit peer.
+    pub peer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Operating mode of the netkit pair. Default to [NetkitMode::L3] when
+    /// creating.
+    pub mode: Option<NetkitMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Default action of this end when no BPF program attached. Default to
+    /// [NetkitPolicy::Pass] when creating.
+    pub policy: Option<NetkitPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Default action of the peer when no BPF program attached. Default to
+    /// [NetkitPolicy::Pass] when creating.
+    /// Serialize and deserialize to/from `peer-policy`.
+    pub peer_policy: Option<NetkitPolicy>,
+}
+
+impl NetkitConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum NetkitMode {
+    /// Layer 2 mode, the netkit devices have MAC address.
+    /// Deserialize and serialize from/to `l2`.
+    L2,
+    /// Layer 3 mode, the netkit devices have no MAC address.
+    /// Deserialize and serialize from/to `l3`.
+    L3,
+}
+
+impl Default for NetkitMode {
+    fn default() -> Self {
+        Self::L3
+    }
+}
+
+impl From<NetkitMode> for u32 {
+    fn from(v: NetkitMode) -> u32 {
+        match v {
+            NetkitMode::L2 => 0,
+            NetkitMode::L3 => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum NetkitPolicy {
+    /// Forward packets.
+    /// Deserialize and serialize from/to `pass`.
+    Pass,
+    /// Drop packets.
+    /// Deserialize and serialize from/to `drop`.
+    Drop,
+}
+
+impl Default for NetkitPolicy {
+    fn default() -> Self {
+        Self::Pass
+    }
+}
+
+impl From<NetkitPolicy> for u32 {
+    fn from(v: NetkitPolicy) -> u32 {
+        match v {
+            NetkitPolicy::Pass => 0,
+            NetkitPolicy::Drop => 2,
+        }
+    }
+}
+
+impl MergedInterfaces {
+    // Raise error if new netkit interface has no peer defined.
+    // Mark old netkit peer as absent when netkit changed its peer.
+    // Mark netkit peer as absent also when netkit is marked as absent.
+    pub(crate) fn process_netkit_peer_changes(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        let netkit_peers: Vec<&str> = self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.merged.is_up())
+            .filter_map(|i| {
+                if let Interface::Netkit(iface) = &i.merged {
+                    iface.peer()
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for iface in self.kernel_ifaces.values().filter(|i| {
+            i.is_desired() && i.current.is_none() && i.merged.is_up()
+        }) {
+            if let Some(Interface::Netkit(des_iface)) = &iface.desired {
+                if des_iface.netkit.is_none()
+                    && !self.gen_conf_mode
+                    && !netkit_peers.contains(&des_iface.base.name.as_str())
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Netkit interface {} does not exist, peer should \
+                            be defined for creating",
+                            des_iface.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+
+        let mut pending_deletions: Vec<String> = Vec::new();
+        for iface in self.kernel_ifaces.values().filter(|i| i.is_desired()) {
+            let cur_peer = match iface.current.as_ref() {
+                Some(Interface::Netkit(cur_iface)) => match cur_iface.peer() {
+                    Some(p) => p,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if iface.merged.is_absent() {
+                pending_deletions.push(cur_peer.to_string());
+            } else if let Some(Interface::Netkit(des_iface)) =
+                iface.desired.as_ref()
+            {
+                if des_iface.peer().map(|p| p != cur_peer).unwrap_or_default() {
+                    pending_deletions.push(cur_peer.to_string());
+                }
+            }
+        }
+        for del_peer in pending_deletions {
+            if let Some(iface) = self.kernel_ifaces.get_mut(&del_peer) {
+                iface.mark_as_absent();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Interfaces {
+    // Removing either end of netkit pair means removing the whole pair, hence
+    // not allowing netkit peer desired as up when other end is marked as
+    // absent.
+    pub(crate) fn validate_netkit_pair_absent(
+        &self,
+        current: &Self,
+    ) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values().filter(|i| {
+            i.is_absent() && i.iface_type() == InterfaceType::Netkit
+        }) {
+            let peer = match current.kernel_ifaces.get(iface.name()) {
+                Some(Interface::Netkit(cur_iface)) => match cur_iface.peer() {
+                    Some(p) => p,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if let Some(peer_iface) =
+                self.kernel_ifaces.get(peer).filter(|i| i.is_up())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Netkit interface {} is marked as absent, its peer {} \
+                        will be removed also, but it is desired as up",
+                        iface.name(),
+                        peer_iface.name(),
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -96,7 +96,29 @@ impl OvsBridgeInterface {
         ret
     }
 
-    fn sort_ports(&mut self) {
+    // With `exact: false`, current ports not mentioned in desired port list
+    // are preserved.
+    pub(crate) fn include_current_ports(&mut self, current: &Self) {
+        let cur_port_confs =
+            match current.bridge.as_ref().and_then(|b| b.ports.as_ref()) {
+                Some(p) => p,
+                None => return,
+            };
+        if let Some(port_confs) = self
+            .bridge
+            .as_mut()
+            .filter(|b| b.exact == Some(false))
+            .and_then(|b| b.ports.as_mut())
+        {
+            for cur_port_conf in cur_port_confs {
+                if !port_confs.iter().any(|p| p.name == cur_port_conf.name) {
+                    port_confs.push(cur_port_conf.clone());
+                }
+            }
+        }
+    }
+
+    pub(crate) fn sort_ports(&mut self) {
         if let Some(ref mut br_conf) = self.bridge {
             if let Some(ref mut port_confs) = &mut br_conf.ports {
                 port_confs.sort_unstable_by_key(|p| p.name.clone());
@@ -252,8 +274,19 @@ pub struct OvsBridgeConfig {
         rename = "port",
         alias = "ports"
     )]
-    /// Serialize to 'port'. Deserialize from `port` or `ports`.
+    /// Serialize to 'port'. Deserialize from `port` or deprecated `ports`.
+    /// When applying, desired port list will override current port list
+    /// unless `exact` is set to false.
     pub ports: Option<Vec<OvsBridgePortConfig>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// When set to false, current ports not mentioned in desired port list
+    /// will be kept attached to this bridge. When undefined or set to true,
+    /// desired port list overrides current port list.
+    pub exact: Option<bool>,
 }
 
 impl OvsBridgeConfig {
@@ -493,7 +526,7 @@ pub struct OvsBridgeBondConfig {
         rename = "port",
         alias = "ports"
     )]
-    /// Serialize to 'port'. Deserialize from `port` or `ports`.
+    /// Serialize to 'port'. Deserialize from `port` or deprecated `ports`.
     pub ports: Option<Vec<OvsBridgeBondPortConfig>>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// NUMA and IRQ placement hints of the PCI device backing this interface,
+/// for co-locating workloads with the network interface.
+/// Only available in running state for PCI network devices, ignored during
+/// apply.
+pub struct PciHints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// PCI address of the device, for example `0000:3b:00.0`.
+    /// Serialize and deserialize to/from `pci-address`.
+    pub pci_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// NUMA node the PCI device is attached to. Not shown when the platform
+    /// does not report NUMA affinity.
+    /// Serialize and deserialize to/from `numa-node`.
+    pub numa_node: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// CPUs local to the PCI device in the kernel CPU list format, for
+    /// example `0-15,32-47`.
+    /// Serialize and deserialize to/from `local-cpus`.
+    pub local_cpus: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Count of interrupts(MSI/MSI-X vectors or the legacy INTx) allocated
+    /// to the PCI device.
+    /// Serialize and deserialize to/from `irq-count`.
+    pub irq_count: Option<u32>,
+}
+
+impl PciHints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceState, InterfaceType, NetworkState,
+    NmstateError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Point-to-Point Protocol over Ethernet(PPPoE) interface. The PPP session
+/// is established by `pppd` spawned by NetworkManager over the parent
+/// ethernet interface, hence PPPoE is only supported by NetworkManager
+/// backend and the PPP interface only exists after the session established.
+/// The `password` is hidden by [NetworkState::PASSWORD_HID_BY_NMSTATE] unless
+/// querying with secrets included. The example yaml output of
+/// [crate::NetworkState] with a PPPoE interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ppp0
+///   type: pppoe
+///   state: up
+///   pppoe:
+///     parent: eth1
+///     username: user@isp
+///     password: <_password_hid_by_nmstate>
+///     service: isp-service
+/// ```
+pub struct PppoeInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pppoe: Option<PppoeConfig>,
+}
+
+impl Default for PppoeInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Pppoe,
+                ..Default::default()
+            },
+            pppoe: None,
+        }
+    }
+}
+
+impl PppoeInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.pppoe.as_ref().and_then(|cfg| {
+            if cfg.parent.is_empty() {
+                None
+            } else {
+                Some(cfg.parent.as_str())
+            }
+        })
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired && self.base.state == InterfaceState::Up {
+            if let Some(conf) = self.pppoe.as_ref() {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(conf) = self.pppoe.as_mut() {
+            if conf.password.is_some() {
+                conf.password =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct PppoeConfig {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    /// Ethernet interface used to carry the PPPoE session.
+    pub parent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Username used for PPP authentication.
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Password used for PPP authentication.
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Name of the PPPoE service. When undefined, the first access
+    /// concentrator offering any service will be used.
+    pub service: Option<String>,
+}
+
+impl PppoeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if self.parent == iface_name {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The parent of PPPoE interface {iface_name} should not \
+                    be itself"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.username.as_deref() == Some("") {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The username of PPPoE interface {iface_name} should not \
+                    be empty"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 };
 
 const SRIOV_VF_NAMING_PREFIX: &str = "sriov:";
+const SRIOV_VF_REP_NAMING_PREFIX: &str = "sriov-rep:";
 const SRIOV_VF_NAMING_SEPERATOR: char = ':';
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -36,6 +37,8 @@ const SRIOV_VF_NAMING_SEPERATOR: char = ':';
 ///         max-tx-rate: 0
 ///         vlan-id: 0
 ///         qos: 0
+///         pci-address: 0000:3b:02.0
+///         driver: iavf
 ///       - id: 1
 ///         mac-address: 00:11:22:33:00:ef
 ///         spoof-check: true
@@ -45,13 +48,44 @@ const SRIOV_VF_NAMING_SEPERATOR: char = ':';
 ///         vlan-id: 0
 ///         qos: 0
 /// ```
+///
+/// The VF could be referred as `sriov:<pf_name>:<vf_id>` in interface name or
+/// port name of controller. The eswitch mode of PF could be changed by:
+/// ```yml
+/// interfaces:
+/// - name: ens1f1
+///   type: ethernet
+///   state: up
+///   ethernet:
+///     sr-iov:
+///       eswitch-mode: switchdev
+/// ```
+///
+/// When PF is in `switchdev` eswitch mode, the VF representor could be
+/// referred as `sriov-rep:<pf_name>:<vf_id>`, for example, attaching VF
+/// representor to OVS bridge with MTU changed:
+/// ```yml
+/// interfaces:
+/// - name: sriov-rep:ens1f1:0
+///   type: ethernet
+///   state: up
+///   mtu: 9000
+/// - name: br0
+///   type: ovs-bridge
+///   state: up
+///   bridge:
+///     port:
+///     - name: sriov-rep:ens1f1:0
+/// ```
 pub struct SrIovConfig {
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
-    /// The number of VFs enabled on PF.
+    /// The number of VFs enabled on PF. When decreasing, the
+    /// NetworkManager backend deactivates the connections on the VFs to be
+    /// removed before changing it.
     /// Deserialize and serialize from/to `total-vfs`.
     pub total_vfs: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,6 +94,39 @@ pub struct SrIovConfig {
     ///   to defaults.
     /// * If not empty, missing [SrIovVfConfig] will use current configuration.
     pub vfs: Option<Vec<SrIovVfConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Eswitch mode of PF. Changing it will unbind all VFs from their
+    /// drivers, change the mode via devlink and probe the VFs again, hence
+    /// VFs and VF representors are recreated. Not persisted by
+    /// NetworkManager.
+    /// Deserialize and serialize from/to `eswitch-mode`.
+    pub eswitch_mode: Option<SrIovEswitchMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Eswitch mode of SR-IOV PF.
+pub enum SrIovEswitchMode {
+    /// Deserialize and serialize from/to `legacy`.
+    Legacy,
+    /// VF representors are created for each VF, required by OVS hardware
+    /// offload.
+    /// Deserialize and serialize from/to `switchdev`.
+    Switchdev,
+}
+
+impl std::fmt::Display for SrIovEswitchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Legacy => "legacy",
+                Self::Switchdev => "switchdev",
+            }
+        )
+    }
 }
 
 impl SrIovConfig {
@@ -69,15 +136,23 @@ impl SrIovConfig {
 
     // * Convert VF MAC address to upper case
     // * Sort by VF ID
-    pub(crate) fn sanitize(&mut self) {
+    // * Remove query only VF properties
+    // * Validate VF TX rate limit
+    pub(crate) fn sanitize(
+        &mut self,
+        iface_name: &str,
+    ) -> Result<(), NmstateError> {
         if let Some(vfs) = self.vfs.as_mut() {
             for vf in vfs.iter_mut() {
                 if let Some(address) = vf.mac_address.as_mut() {
                     address.make_ascii_uppercase()
                 }
+                vf.remove_query_only_props();
+                vf.validate_tx_rate(iface_name)?;
             }
             vfs.sort_unstable_by(|a, b| a.id.cmp(&b.id));
         }
+        Ok(())
     }
 
     // * Auto fill unmentioned VF ID
@@ -150,13 +225,16 @@ pub struct SrIovVfConfig {
         default,
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
-    /// Deserialize and serialize from/to `min_tx_rate`.
+    /// Minimum TX rate of VF in Mbps, 0 means unlimited.
+    /// Deserialize and serialize from/to `min-tx-rate`.
     pub min_tx_rate: Option<u32>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
+    /// Maximum TX rate of VF in Mbps, 0 means unlimited. Should not be
+    /// smaller than `min-tx-rate` unless unlimited.
     /// Deserialize and serialize from/to `max-tx-rate`.
     pub max_tx_rate: Option<u32>,
     #[serde(
@@ -172,12 +250,54 @@ pub struct SrIovVfConfig {
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
     pub qos: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. Interface name of VF representor when PF is in
+    /// `switchdev` eswitch mode. Ignored when applying.
+    pub representor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. PCI address of VF, for example `0000:3b:02.0`.
+    /// Ignored when applying.
+    /// Deserialize and serialize from/to `pci-address`.
+    pub pci_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. Kernel driver bound to VF, for example `iavf` or
+    /// `vfio-pci`. Ignored when applying.
+    pub driver: Option<String>,
 }
 
 impl SrIovVfConfig {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub(crate) fn remove_query_only_props(&mut self) {
+        self.representor = None;
+        self.pci_address = None;
+        self.driver = None;
+    }
+
+    // Kernel refuses `min-tx-rate` larger than `max-tx-rate` unless
+    // `max-tx-rate` is 0 which means unlimited.
+    fn validate_tx_rate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if let (Some(min_rate), Some(max_rate)) =
+            (self.min_tx_rate, self.max_tx_rate)
+        {
+            if max_rate != 0 && min_rate > max_rate {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "SR-IOV VF {} of interface {iface_name} has \
+                        min-tx-rate {min_rate} larger than max-tx-rate \
+                        {max_rate}",
+                        self.id
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Interfaces {
@@ -196,10 +316,11 @@ impl Interfaces {
     ) -> Result<(), NmstateError> {
         let mut changed_iface_names: Vec<String> = Vec::new();
         for iface in self.kernel_ifaces.values_mut() {
-            if let Some((pf_name, vf_id)) = parse_sriov_vf_naming(iface.name())?
+            if let Some((pf_name, vf_id, is_rep)) =
+                parse_sriov_vf_naming(iface.name())?
             {
                 if let Some(vf_iface_name) =
-                    get_sriov_vf_iface_name(current, pf_name, vf_id)
+                    get_sriov_vf_iface_name(current, pf_name, vf_id, is_rep)
                 {
                     changed_iface_names.push(iface.name().to_string());
                     log::info!(
@@ -212,7 +333,8 @@ impl Interfaces {
                     let e = NmstateError::new(
                         ErrorKind::InvalidArgument,
                         format!(
-                            "Failed to find SR-IOV VF interface name for {}",
+                            "Failed to find SR-IOV VF {}interface name for {}",
+                            if is_rep { "representor " } else { "" },
                             iface.name()
                         ),
                     );
@@ -261,9 +383,11 @@ impl Interfaces {
                 None => continue,
             };
             for port in ports {
-                if let Some((pf_name, vf_id)) = parse_sriov_vf_naming(port)? {
+                if let Some((pf_name, vf_id, is_rep)) =
+                    parse_sriov_vf_naming(port)?
+                {
                     if let Some(vf_iface_name) =
-                        get_sriov_vf_iface_name(current, pf_name, vf_id)
+                        get_sriov_vf_iface_name(current, pf_name, vf_id, is_rep)
                     {
                         log::info!(
                             "SR-IOV VF {} resolved to interface name {}",
@@ -280,8 +404,9 @@ impl Interfaces {
                         return Err(NmstateError::new(
                             ErrorKind::InvalidArgument,
                             format!(
-                                "Failed to find SR-IOV VF interface \
+                                "Failed to find SR-IOV VF {}interface \
                                 name for {}",
+                                if is_rep { "representor " } else { "" },
                                 iface.name()
                             ),
                         ));
@@ -298,40 +423,44 @@ impl Interfaces {
     }
 }
 
+// Return (pf_name, vf_id, is_representor)
 fn parse_sriov_vf_naming(
     iface_name: &str,
-) -> Result<Option<(&str, u32)>, NmstateError> {
-    if iface_name.starts_with(SRIOV_VF_NAMING_PREFIX) {
-        let names: Vec<&str> =
-            iface_name.split(SRIOV_VF_NAMING_SEPERATOR).collect();
-        if names.len() == 3 {
-            match names[2].parse::<u32>() {
-                Ok(vf_id) => Ok(Some((names[1], vf_id))),
-                Err(e) => {
-                    let e = NmstateError::new(
-                        ErrorKind::InvalidArgument,
-                        format!(
-                            "Invalid SR-IOV VF ID in {iface_name}, correct format \
-                            is 'sriov:<pf_name>:<vf_id>', error: {e}"
-                        ),
-                    );
-                    log::error!("{}", e);
-                    Err(e)
-                }
+) -> Result<Option<(&str, u32, bool)>, NmstateError> {
+    let (prefix, is_rep) = if iface_name.starts_with(SRIOV_VF_NAMING_PREFIX) {
+        (SRIOV_VF_NAMING_PREFIX, false)
+    } else if iface_name.starts_with(SRIOV_VF_REP_NAMING_PREFIX) {
+        (SRIOV_VF_REP_NAMING_PREFIX, true)
+    } else {
+        return Ok(None);
+    };
+    let names: Vec<&str> =
+        iface_name.split(SRIOV_VF_NAMING_SEPERATOR).collect();
+    if names.len() == 3 {
+        match names[2].parse::<u32>() {
+            Ok(vf_id) => Ok(Some((names[1], vf_id, is_rep))),
+            Err(e) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid SR-IOV VF ID in {iface_name}, correct format \
+                        is '{prefix}<pf_name>:<vf_id>', error: {e}"
+                    ),
+                );
+                log::error!("{}", e);
+                Err(e)
             }
-        } else {
-            let e = NmstateError::new(
-                ErrorKind::InvalidArgument,
-                format!(
-                    "Invalid SR-IOV VF name {iface_name}, correct format is \
-                    'sriov:<pf_name>:<vf_id>'",
-                ),
-            );
-            log::error!("{}", e);
-            Err(e)
         }
     } else {
-        Ok(None)
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Invalid SR-IOV VF name {iface_name}, correct format is \
+                '{prefix}<pf_name>:<vf_id>'",
+            ),
+        );
+        log::error!("{}", e);
+        Err(e)
     }
 }
 
@@ -339,6 +468,7 @@ fn get_sriov_vf_iface_name(
     current: &Interfaces,
     pf_name: &str,
     vf_id: u32,
+    is_rep: bool,
 ) -> Option<String> {
     if let Some(Interface::Ethernet(pf_iface)) =
         current.get_iface(pf_name, InterfaceType::Ethernet)
@@ -351,7 +481,9 @@ fn get_sriov_vf_iface_name(
         {
             for vf in vfs {
                 if vf.id == vf_id {
-                    if !vf.iface_name.is_empty() {
+                    if is_rep {
+                        return vf.representor.clone();
+                    } else if !vf.iface_name.is_empty() {
                         return Some(vf.iface_name.clone());
                     }
                     break;
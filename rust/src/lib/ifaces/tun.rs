@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, InterfaceType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel persistent TUN/TAP interface.
+/// The example yaml output of [crate::NetworkState] with a TAP interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: tap0
+///   type: tun
+///   state: up
+///   tun:
+///     mode: tap
+///     owner: 1000
+///     group: 1000
+///     multi-queue: true
+///     pi: false
+/// ```
+pub struct TunInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tun: Option<TunConfig>,
+}
+
+impl Default for TunInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Tun,
+                ..Default::default()
+            },
+            tun: None,
+        }
+    }
+}
+
+impl TunInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TunConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Default to [TunMode::Tun].
+    pub mode: Option<TunMode>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// The user ID allowed to attach to the interface. When not defined,
+    /// any user is allowed.
+    pub owner: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// The group ID allowed to attach to the interface. When not defined,
+    /// any group is allowed.
+    pub group: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether the interface supports multiple queues.
+    /// Deserialize and serialize from/to `multi-queue`.
+    pub multi_queue: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to prepend packet information header to packets.
+    pub pi: Option<bool>,
+}
+
+impl TunConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum TunMode {
+    /// Layer 3 TUN device.
+    /// Deserialize and serialize from/to `tun`.
+    Tun,
+    /// Layer 2 TAP device.
+    /// Deserialize and serialize from/to `tap`.
+    Tap,
+}
+
+impl Default for TunMode {
+    fn default() -> Self {
+        Self::Tun
+    }
+}
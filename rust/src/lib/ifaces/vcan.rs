@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, InterfaceType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Virtual Controller Area Network(vcan) interface, normally used for
+/// testing CAN applications without hardware. Only contain information of
+/// [BaseInterface].
+/// NetworkManager does not support vcan interface, hence it is created via
+/// netlink and not persisted by NetworkManager backend.
+/// Example yaml output of `[crate::NetworkState]` with vcan interface:
+/// ```yml
+/// interfaces:
+/// - name: vcan0
+///   type: vcan
+///   state: up
+/// ```
+pub struct VcanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+}
+
+impl Default for VcanInterface {
+    fn default() -> Self {
+        let mut base = BaseInterface::new();
+        base.iface_type = InterfaceType::Vcan;
+        Self { base }
+    }
+}
+
+impl VcanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
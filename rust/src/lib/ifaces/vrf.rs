@@ -131,7 +131,7 @@ pub struct VrfConfig {
     #[serde(alias = "ports")]
     /// Port list.
     /// Deserialize and serialize from/to `port`.
-    /// Also deserialize from `ports`.
+    /// Also deserialize from deprecated `ports`.
     pub port: Option<Vec<String>>,
     #[serde(
         rename = "route-table-id",
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel Virtual Tunnel Interface(VTI) over IPv4, used as routable
+/// endpoint of policy-based IPsec. The `ikey` and `okey` are the marks
+/// used to match the XFRM policies of the IPsec tunnel.
+/// Nispor does not provide VTI configuration yet, hence the `vti` section is
+/// only queried from NetworkManager backend. The example yaml output of
+/// [crate::NetworkState] with a VTI interface would be:
+/// ```yml
+/// interfaces:
+/// - name: vti1
+///   type: vti
+///   state: up
+///   vti:
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     ikey: 100
+///     okey: 100
+/// ```
+pub struct VtiInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vti: Option<VtiConfig>,
+}
+
+impl Default for VtiInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Vti,
+                ..Default::default()
+            },
+            vti: None,
+        }
+    }
+}
+
+impl VtiInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.vti.as_ref() {
+                conf.validate(self.base.name.as_str(), false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel Virtual Tunnel Interface(VTI) over IPv6. The `vti6` section
+/// shares the same properties with [VtiInterface] while the `local` and
+/// `remote` should be IPv6 addresses. The example yaml output of
+/// [crate::NetworkState] with a VTI6 interface would be:
+/// ```yml
+/// interfaces:
+/// - name: vti6-1
+///   type: vti6
+///   state: up
+///   vti6:
+///     local: 2001:db8::1
+///     remote: 2001:db8::2
+///     ikey: 100
+///     okey: 100
+/// ```
+pub struct Vti6Interface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vti6: Option<VtiConfig>,
+}
+
+impl Default for Vti6Interface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Vti6,
+                ..Default::default()
+            },
+            vti6: None,
+        }
+    }
+}
+
+impl Vti6Interface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.vti6.as_ref() {
+                conf.validate(self.base.name.as_str(), true)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct VtiConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local address of the tunnel endpoint.
+    pub local: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote address of the tunnel endpoint.
+    pub remote: Option<IpAddr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key(mark) of incoming packets.
+    pub ikey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key(mark) of outgoing packets.
+    pub okey: Option<u32>,
+}
+
+impl VtiConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(
+        &self,
+        iface_name: &str,
+        is_ipv6: bool,
+    ) -> Result<(), NmstateError> {
+        for (name, addr) in [("local", self.local), ("remote", self.remote)] {
+            if let Some(addr) = addr {
+                if addr.is_ipv6() != is_ipv6 {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The {name} address {addr} of {} interface \
+                            {iface_name} should be {}",
+                            if is_ipv6 { "VTI6" } else { "VTI" },
+                            if is_ipv6 { "IPv6" } else { "IPv4" },
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
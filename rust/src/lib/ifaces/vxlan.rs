@@ -22,6 +22,9 @@ use crate::{BaseInterface, InterfaceType};
 ///     id: 102
 ///     remote: 239.1.1.1
 ///     destination-port: 1235
+///     flood-remotes:
+///     - 192.0.2.1
+///     - 192.0.2.2
 /// ```
 pub struct VxlanInterface {
     #[serde(flatten)]
@@ -56,6 +59,15 @@ impl VxlanInterface {
             }
         })
     }
+
+    pub(crate) fn sanitize(&mut self) {
+        if let Some(remotes) =
+            self.vxlan.as_mut().and_then(|c| c.flood_remotes.as_mut())
+        {
+            remotes.sort_unstable();
+            remotes.dedup();
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -79,4 +91,13 @@ pub struct VxlanConfig {
     )]
     /// Deserialize and serialize from/to `destination-port`.
     pub dst_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote VTEP addresses for head-end replication of broadcast, unknown
+    /// unicast and multicast traffic, also known as the all-zero MAC
+    /// address FDB entries. When applying, desired list will override
+    /// current list. The `remote` property is not included.
+    /// These entries are applied via netlink and not stored in
+    /// NetworkManager profile.
+    /// Deserialize and serialize from/to `flood-remotes`.
+    pub flood_remotes: Option<Vec<std::net::IpAddr>>,
 }
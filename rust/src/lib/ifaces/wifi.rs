@@ -0,0 +1,454 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceState, InterfaceType, NetworkState,
+    NmstateError,
+};
+
+const SSID_MAX_LENGTH: usize = 32;
+const PSK_MIN_LENGTH: usize = 8;
+const PSK_MAX_LENGTH: usize = 63;
+const PSK_HEX_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Wi-Fi interface in station(client) or access point(hotspot) mode. The
+/// association and authentication are done by `wpa_supplicant` spawned by
+/// NetworkManager, hence Wi-Fi is only supported by NetworkManager backend.
+/// The pre-shared key is not saved into NetworkManager profile, it is owned
+/// by secret agent by default, see [WifiSecurityConfig::psk_flags]. It is
+/// hidden by [NetworkState::PASSWORD_HID_BY_NMSTATE] unless querying with
+/// secrets included. For WPA-EAP, the credentials are defined in the
+/// `802.1x` section of the interface.
+/// The example yaml output of [crate::NetworkState] with a Wi-Fi interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: wlan0
+///   type: wifi
+///   state: up
+///   wifi:
+///     ssid: edge-net
+///     band: 5ghz
+///     hidden: false
+///     security:
+///       key-mgmt: wpa-psk
+///       psk: <_password_hid_by_nmstate>
+/// ```
+/// In access point mode, the IPv4 is shared with clients: NetworkManager
+/// serves DHCP on the static IPv4 address(`10.42.0.1/24` if undefined) and
+/// NAT the traffic to default route:
+/// ```yml
+/// interfaces:
+/// - name: wlan0
+///   type: wifi
+///   state: up
+///   ipv4:
+///     enabled: true
+///   wifi:
+///     mode: ap
+///     ssid: edge-mgmt
+///     band: 2.4ghz
+///     channel: 6
+///     security:
+///       key-mgmt: wpa-psk
+///       psk: <_password_hid_by_nmstate>
+/// ```
+pub struct WifiInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wifi: Option<WifiConfig>,
+}
+
+impl Default for WifiInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Wifi,
+                ..Default::default()
+            },
+            wifi: None,
+        }
+    }
+}
+
+impl WifiInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired && self.base.state == InterfaceState::Up {
+            if let Some(conf) = self.wifi.as_ref() {
+                conf.validate(&self.base)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(sec_conf) =
+            self.wifi.as_mut().and_then(|c| c.security.as_mut())
+        {
+            if sec_conf.psk.is_some() {
+                sec_conf.psk =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WifiConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Operation mode. Default to [WifiMode::Station].
+    pub mode: Option<WifiMode>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    /// Service Set Identifier(SSID) of the network to connect to or to
+    /// create in access point mode, up to 32 bytes.
+    pub ssid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Restrict the connection to specified frequency band. When undefined,
+    /// any band supported by the access point and the device will be used.
+    pub band: Option<WifiBand>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Wireless channel to use, only valid with `band` defined.
+    pub channel: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether the network does not broadcast its SSID, which requires
+    /// active probing.
+    pub hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Security configuration. When undefined, connect to open network.
+    pub security: Option<WifiSecurityConfig>,
+}
+
+impl WifiConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, base_iface: &BaseInterface) -> Result<(), NmstateError> {
+        if self.ssid.len() > SSID_MAX_LENGTH {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The SSID {} of Wi-Fi interface {} exceeds the maximum \
+                    length {SSID_MAX_LENGTH} bytes",
+                    self.ssid, base_iface.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.channel.is_some() && self.band.is_none() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The channel of Wi-Fi interface {} requires band to be \
+                    defined",
+                    base_iface.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(sec_conf) = self.security.as_ref() {
+            sec_conf.validate(base_iface)?;
+        }
+        if self.is_ap() {
+            self.validate_ap(base_iface)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_ap(&self) -> bool {
+        self.mode == Some(WifiMode::Ap)
+    }
+
+    fn validate_ap(
+        &self,
+        base_iface: &BaseInterface,
+    ) -> Result<(), NmstateError> {
+        let msg = if self.ssid.is_empty() {
+            Some("requires ssid to be defined".to_string())
+        } else if let Some(key_mgmt) =
+            self.security.as_ref().map(|s| s.key_mgmt).filter(|k| {
+                matches!(k, WifiKeyMgmt::WpaEap | WifiKeyMgmt::WpaEapSuiteB192)
+            })
+        {
+            Some(format!("does not support {key_mgmt} key management"))
+        } else if base_iface.ipv4.as_ref().and_then(|i| i.dhcp) == Some(true) {
+            Some(
+                "cannot use DHCP client for IPv4, the IPv4 is shared with \
+                clients instead"
+                    .to_string(),
+            )
+        } else {
+            None
+        };
+        if let Some(msg) = msg {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Wi-Fi interface {} in access point mode {msg}",
+                    base_iface.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum WifiMode {
+    /// Connect to access point as client.
+    /// Deserialize and serialize from/to `station`.
+    Station,
+    /// Act as access point(hotspot) for other stations.
+    /// Deserialize and serialize from/to `ap`.
+    Ap,
+}
+
+impl Default for WifiMode {
+    fn default() -> Self {
+        Self::Station
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum WifiBand {
+    /// 2.4 GHz band.
+    /// Deserialize and serialize from/to `2.4ghz`.
+    #[serde(rename = "2.4ghz")]
+    Band2G4,
+    /// 5 GHz band.
+    /// Deserialize and serialize from/to `5ghz`.
+    #[serde(rename = "5ghz")]
+    Band5G,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WifiSecurityConfig {
+    /// Key management method.
+    /// Deserialize and serialize from/to `key-mgmt`.
+    pub key_mgmt: WifiKeyMgmt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Pre-shared key for [WifiKeyMgmt::WpaPsk] in 8 to 63 ASCII characters
+    /// or 64 hexadecimal characters, or password for [WifiKeyMgmt::Sae].
+    pub psk: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// How NetworkManager handles the [WifiSecurityConfig::psk]. Default to
+    /// [WifiSecretFlag::AgentOwned].
+    pub psk_flags: Option<WifiSecretFlag>,
+}
+
+impl WifiSecurityConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, base_iface: &BaseInterface) -> Result<(), NmstateError> {
+        let psk = self
+            .psk
+            .as_deref()
+            .filter(|p| *p != NetworkState::PASSWORD_HID_BY_NMSTATE);
+        match self.key_mgmt {
+            WifiKeyMgmt::WpaPsk => {
+                if let Some(psk) = psk {
+                    if !is_valid_psk(psk) {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "The psk of Wi-Fi interface {} should be \
+                                {PSK_MIN_LENGTH} to {PSK_MAX_LENGTH} ASCII \
+                                characters or {PSK_HEX_LENGTH} hexadecimal \
+                                characters",
+                                base_iface.name
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+            // SAE password has no length limitation of WPA pre-shared key
+            WifiKeyMgmt::Sae => {
+                if psk == Some("") {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The psk of Wi-Fi interface {} should not be \
+                            empty for sae key management",
+                            base_iface.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            WifiKeyMgmt::WpaEap | WifiKeyMgmt::WpaEapSuiteB192 => {
+                if self.psk.is_some() {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The psk of Wi-Fi interface {} is not allowed \
+                            for {} key management, please define the \
+                            credentials in `802.1x` section",
+                            base_iface.name, self.key_mgmt
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Secret flag of Wi-Fi pre-shared key, the secret is never stored into
+/// NetworkManager profile.
+pub enum WifiSecretFlag {
+    /// Secret is saved by user's secret agent, for example the keyring of
+    /// desktop session, and provided to NetworkManager when activating.
+    /// Deserialize and serialize from/to `agent-owned`.
+    AgentOwned,
+    /// Secret is not saved at all, NetworkManager asks secret agent for it
+    /// on every activation.
+    /// Deserialize and serialize from/to `not-saved`.
+    NotSaved,
+}
+
+impl Default for WifiSecretFlag {
+    fn default() -> Self {
+        Self::AgentOwned
+    }
+}
+
+fn is_valid_psk(psk: &str) -> bool {
+    if psk.len() == PSK_HEX_LENGTH {
+        psk.chars().all(|c| c.is_ascii_hexdigit())
+    } else {
+        psk.is_ascii() && (PSK_MIN_LENGTH..=PSK_MAX_LENGTH).contains(&psk.len())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", try_from = "String")]
+#[non_exhaustive]
+/// Key management of Wi-Fi network.
+/// The IEEE 802.11r fast BSS transition(FT) is negotiated automatically by
+/// wpa_supplicant when supported by both the access point and the driver.
+/// NetworkManager provides no option to control it, hence `ft-psk`, `ft-sae`
+/// and `ft-eap` are rejected instead of silently applied without FT.
+pub enum WifiKeyMgmt {
+    /// WPA2 and WPA3 personal, allowing mixed WPA2/WPA3 network.
+    /// Deserialize and serialize from/to `wpa-psk`.
+    WpaPsk,
+    /// WPA3 personal only using Simultaneous Authentication of Equals.
+    /// Protected management frames is required.
+    /// Deserialize and serialize from/to `sae`.
+    Sae,
+    /// WPA2 and WPA3 enterprise using IEEE 802.1X authentication.
+    /// Deserialize and serialize from/to `wpa-eap`.
+    WpaEap,
+    /// WPA3 enterprise only using 192-bit security suite.
+    /// Protected management frames is required.
+    /// Deserialize and serialize from/to `wpa-eap-suite-b-192`.
+    #[serde(rename = "wpa-eap-suite-b-192")]
+    WpaEapSuiteB192,
+}
+
+impl Default for WifiKeyMgmt {
+    fn default() -> Self {
+        Self::WpaPsk
+    }
+}
+
+impl std::fmt::Display for WifiKeyMgmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::WpaPsk => "wpa-psk",
+                Self::Sae => "sae",
+                Self::WpaEap => "wpa-eap",
+                Self::WpaEapSuiteB192 => "wpa-eap-suite-b-192",
+            }
+        )
+    }
+}
+
+impl TryFrom<String> for WifiKeyMgmt {
+    type Error = NmstateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "wpa-psk" => Ok(Self::WpaPsk),
+            "sae" => Ok(Self::Sae),
+            "wpa-eap" => Ok(Self::WpaEap),
+            "wpa-eap-suite-b-192" => Ok(Self::WpaEapSuiteB192),
+            "ft-psk" => Err(ft_key_mgmt_not_supported(&value, Self::WpaPsk)),
+            "ft-sae" => Err(ft_key_mgmt_not_supported(&value, Self::Sae)),
+            "ft-eap" => Err(ft_key_mgmt_not_supported(&value, Self::WpaEap)),
+            _ => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid Wi-Fi key management {value}, expecting \
+                    wpa-psk, sae, wpa-eap or wpa-eap-suite-b-192"
+                ),
+            )),
+        }
+    }
+}
+
+fn ft_key_mgmt_not_supported(
+    value: &str,
+    key_mgmt: WifiKeyMgmt,
+) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::InvalidArgument,
+        format!(
+            "Wi-Fi key management {value} is not supported, the fast BSS \
+            transition(802.11r) cannot be controlled and is negotiated \
+            automatically, please use {key_mgmt} instead"
+        ),
+    )
+}
+
+impl WifiKeyMgmt {
+    /// Whether WPA2 is disallowed.
+    pub(crate) fn is_wpa3_only(&self) -> bool {
+        matches!(self, Self::Sae | Self::WpaEapSuiteB192)
+    }
+}
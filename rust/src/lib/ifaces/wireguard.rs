@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ip::sanitize_ip_network, BaseInterface, ErrorKind, InterfaceType,
+    NetworkState, NmstateError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// WireGuard tunnel interface. Only supported by NetworkManager backend.
+/// The private key and preshared keys are hidden by
+/// [NetworkState::PASSWORD_HID_BY_NMSTATE] unless querying with secrets
+/// included. The example yaml output of [crate::NetworkState] with a
+/// WireGuard interface would be:
+/// ```yml
+/// interfaces:
+/// - name: wg0
+///   type: wireguard
+///   state: up
+///   mtu: 1420
+///   ipv4:
+///     enabled: true
+///     address:
+///     - ip: 10.0.0.1
+///       prefix-length: 24
+///   wireguard:
+///     private-key: <_password_hid_by_nmstate>
+///     listen-port: 51820
+///     peers:
+///     - public-key: xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=
+///       endpoint: 192.0.2.1:51820
+///       allowed-ips:
+///       - 10.0.0.2/32
+///       persistent-keepalive: 25
+/// ```
+pub struct WireGuardInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wireguard: Option<WireGuardConfig>,
+}
+
+impl Default for WireGuardInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::WireGuard,
+                ..Default::default()
+            },
+            wireguard: None,
+        }
+    }
+}
+
+impl WireGuardInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(peers) =
+            self.wireguard.as_mut().and_then(|c| c.peers.as_mut())
+        {
+            for peer in peers.iter_mut() {
+                peer.sanitize(is_desired)?;
+            }
+            if is_desired {
+                for (i, peer) in peers.iter().enumerate() {
+                    if peers[..i]
+                        .iter()
+                        .any(|p| p.public_key == peer.public_key)
+                    {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "WireGuard peer {} is defined more than once \
+                                in interface {}",
+                                peer.public_key, self.base.name
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(conf) = self.wireguard.as_mut() {
+            conf.hide_secrets();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WireGuardConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Base64 encoded private key of this interface.
+    /// Deserialize and serialize from/to `private-key`.
+    pub private_key: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// UDP port to listen on. Random port will be chosen when not defined.
+    /// Deserialize and serialize from/to `listen-port`.
+    pub listen_port: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Firewall mark of outgoing packets, 0 means disabled.
+    pub fwmark: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When applying, desired list will override current list.
+    pub peers: Option<Vec<WireGuardPeerConfig>>,
+}
+
+impl WireGuardConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn hide_secrets(&mut self) {
+        if self.private_key.is_some() {
+            self.private_key =
+                Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+        }
+        for peer in self.peers.as_deref_mut().unwrap_or_default() {
+            if peer.preshared_key.is_some() {
+                peer.preshared_key =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WireGuardPeerConfig {
+    /// Base64 encoded public key of the peer.
+    /// Deserialize and serialize from/to `public-key`.
+    pub public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote endpoint in the format of `<ip>:<port>` or `<host>:<port>`.
+    /// IPv6 address should be enclosed in brackets, e.g. `[2001:db8::1]:51820`.
+    pub endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// IP networks allowed to be received from and routed to this peer.
+    /// Deserialize and serialize from/to `allowed-ips`.
+    pub allowed_ips: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Base64 encoded preshared key for additional symmetric encryption.
+    /// Deserialize and serialize from/to `preshared-key`.
+    pub preshared_key: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Interval in seconds of sending keepalive packet, 0 means disabled.
+    /// Deserialize and serialize from/to `persistent-keepalive`.
+    pub persistent_keepalive: Option<u32>,
+}
+
+impl WireGuardPeerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sanitize(&mut self, is_desired: bool) -> Result<(), NmstateError> {
+        if is_desired && self.public_key.is_empty() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "WireGuard peer public-key is mandatory".to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(allowed_ips) = self.allowed_ips.as_mut() {
+            for allowed_ip in allowed_ips.iter_mut() {
+                match sanitize_ip_network(allowed_ip) {
+                    Ok(ip_net) => *allowed_ip = ip_net,
+                    Err(e) => {
+                        if is_desired {
+                            let e = NmstateError::new(
+                                ErrorKind::InvalidArgument,
+                                format!(
+                                    "Invalid allowed-ips '{allowed_ip}' of \
+                                    WireGuard peer {}: {e}",
+                                    self.public_key
+                                ),
+                            );
+                            log::error!("{}", e);
+                            return Err(e);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel XFRM interface used by route-based IPsec VPN. The IPsec
+/// security policies and states with the same `if-id` are applied to traffic
+/// routed through this interface.
+/// NetworkManager does not support XFRM interface, hence it is created via
+/// netlink and not persisted by NetworkManager backend.
+/// The example yaml output of [crate::NetworkState] with a XFRM interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: ipsec1
+///   type: xfrm
+///   state: up
+///   xfrm:
+///     if-id: 100
+///     parent: eth1
+/// ```
+pub struct XfrmInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xfrm: Option<XfrmConfig>,
+}
+
+impl Default for XfrmInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Xfrm,
+                ..Default::default()
+            },
+            xfrm: None,
+        }
+    }
+}
+
+impl XfrmInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.xfrm.as_ref().and_then(|cfg| cfg.parent.as_deref())
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired && self.xfrm.as_ref().map(|c| c.if_id) == Some(0) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The if-id of XFRM interface {} should not be 0 which is \
+                    reserved by kernel",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct XfrmConfig {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// The XFRM interface ID matching the `if_id` of IPsec security policies
+    /// and states. Deserialize and serialize from/to `if-id`.
+    pub if_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The underlying interface used for IPsec offloading.
+    pub parent: Option<String>,
+}
+
+impl XfrmConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -8,7 +8,7 @@ use serde::{self, Deserialize, Deserializer, Serialize};
 
 use crate::{
     BaseInterface, DnsClientState, ErrorKind, MergedInterface,
-    MptcpAddressFlag, NmstateError, RouteRuleEntry,
+    MptcpAddressFlag, NmstateError, RouteEntry, RouteRuleEntry,
 };
 
 const AF_INET: u8 = 2;
@@ -75,6 +75,13 @@ struct InterfaceIp {
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
     pub auto_table_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "route-table",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub route_table: Option<u32>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         rename = "auto-route-metric",
@@ -92,6 +99,14 @@ struct InterfaceIp {
     pub allow_extra_address: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub exact: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dhcp-lease")]
+    pub dhcp_lease: Option<DhcpLease>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -147,6 +162,13 @@ pub struct InterfaceIpv4 {
     /// If not defined, the main(254) will be used.
     /// Serialize and deserialize to/from `auto-table-id`.
     pub auto_table_id: Option<u32>,
+    /// The route table ID used to hold all routes not explicitly
+    /// specifying a route table, including routes retrieved from DHCP
+    /// server and the prefix routes of IP addresses.
+    /// Setting to 0 means main(254) route table.
+    /// Only supported by NetworkManager backend.
+    /// Serialize and deserialize to/from `route-table`.
+    pub route_table: Option<u32>,
     /// By default(true), nmstate verification process allows extra IP address
     /// found as long as desired IP address matched.
     /// When set to false, the verification process of nmstate do exact equal
@@ -158,9 +180,27 @@ pub struct InterfaceIpv4 {
     /// Only available for DHCPv4 enabled interface.
     /// Deserialize from `auto-route-metric`
     pub auto_route_metric: Option<u32>,
+    /// By default, applying desired IP addresses only removes the addresses
+    /// added by nmstate, addresses added by other tools (e.g. VIP managed by
+    /// keepalived) are preserved. When set to true, the desired addresses
+    /// are the complete list, all other addresses will be removed and
+    /// verification will fail if any extra address found. When `routes` section
+    /// is defined in desired state, the desired IPv4 routes of this interface
+    /// are also treated as the complete list.
+    pub exact: Option<bool>,
+    /// DHCP lease information received from DHCP server. Query only,
+    /// ignored when applying.
+    /// Serialize to `dhcp-lease`.
+    pub dhcp_lease: Option<DhcpLease>,
 
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
+    // Addresses and routes stored in backend configuration, `None` means
+    // the backend does not track them.
+    pub(crate) owned_addresses: Option<Vec<InterfaceIpAddr>>,
+    pub(crate) owned_routes: Option<Vec<RouteEntry>>,
+    // Addresses preserved by merging as they were not added by nmstate.
+    pub(crate) external_addresses: Vec<InterfaceIpAddr>,
 }
 
 impl Default for InterfaceIpv4 {
@@ -177,8 +217,14 @@ impl Default for InterfaceIpv4 {
             auto_gateway: None,
             auto_routes: None,
             auto_table_id: None,
+            route_table: None,
             allow_extra_address: default_allow_extra_address(),
             auto_route_metric: None,
+            exact: None,
+            owned_addresses: None,
+            owned_routes: None,
+            external_addresses: Vec::new(),
+            dhcp_lease: None,
         }
     }
 }
@@ -240,6 +286,17 @@ impl InterfaceIpv4 {
         {
             self.addresses = current.addresses.clone();
         }
+        if self.enabled && !self.is_auto() && !current.is_auto() {
+            self.external_addresses = get_external_addresses(
+                desired.addresses.as_deref(),
+                desired.exact,
+                current.addresses.as_deref(),
+                current.owned_addresses.as_deref(),
+            );
+            if let Some(addrs) = self.addresses.as_mut() {
+                addrs.extend_from_slice(self.external_addresses.as_slice());
+            }
+        }
     }
 
     // * Remove link-local address
@@ -289,6 +346,7 @@ impl InterfaceIpv4 {
         if !self.enabled {
             self.dhcp = None;
             self.addresses = None;
+            self.route_table = None;
         }
 
         if self.dhcp != Some(true) {
@@ -298,11 +356,16 @@ impl InterfaceIpv4 {
             self.auto_table_id = None;
             self.auto_route_metric = None;
         }
+        if is_desired {
+            validate_route_table(self.auto_table_id, self.route_table, "IPv4")?;
+        }
         if let Some(addrs) = self.addresses.as_mut() {
             for addr in addrs.iter_mut() {
                 addr.mptcp_flags = None;
             }
         }
+        // Query only
+        self.dhcp_lease = None;
         Ok(())
     }
 }
@@ -353,8 +416,11 @@ impl From<InterfaceIp> for InterfaceIpv4 {
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
             auto_table_id: ip.auto_table_id,
+            route_table: ip.route_table,
             allow_extra_address: ip.allow_extra_address,
             auto_route_metric: ip.auto_route_metric,
+            exact: ip.exact,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -376,8 +442,11 @@ impl From<InterfaceIpv4> for InterfaceIp {
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
             auto_table_id: ip.auto_table_id,
+            route_table: ip.route_table,
             allow_extra_address: ip.allow_extra_address,
             auto_route_metric: ip.auto_route_metric,
+            exact: ip.exact,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -445,6 +514,13 @@ pub struct InterfaceIpv6 {
     /// If not defined, the main(254) will be used.
     /// Serialize and deserialize to/from `auto-table-id`.
     pub auto_table_id: Option<u32>,
+    /// The route table ID used to hold all routes not explicitly
+    /// specifying a route table, including routes retrieved from autoconf
+    /// or DHCPv6 and the prefix routes of IP addresses.
+    /// Setting to 0 means main(254) route table.
+    /// Only supported by NetworkManager backend.
+    /// Serialize and deserialize to/from `route-table`.
+    pub route_table: Option<u32>,
     /// By default(true), nmstate verification process allows extra IP address
     /// found as long as desired IP address matched.
     /// When set to false, the verification process of nmstate do exact equal
@@ -459,9 +535,27 @@ pub struct InterfaceIpv6 {
     /// IETF draft(expired) Tokenised IPv6 Identifiers. Should be only
     /// containing the tailing 64 bites for IPv6 address.
     pub token: Option<String>,
+    /// By default, applying desired IP addresses only removes the addresses
+    /// added by nmstate, addresses added by other tools (e.g. VIP managed by
+    /// keepalived) are preserved. When set to true, the desired addresses
+    /// are the complete list, all other addresses will be removed and
+    /// verification will fail if any extra address found. When `routes` section
+    /// is defined in desired state, the desired IPv6 routes of this interface
+    /// are also treated as the complete list.
+    pub exact: Option<bool>,
+    /// DHCP lease information received from DHCP server. Query only,
+    /// ignored when applying.
+    /// Serialize to `dhcp-lease`.
+    pub dhcp_lease: Option<DhcpLease>,
 
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
+    // Addresses and routes stored in backend configuration, `None` means
+    // the backend does not track them.
+    pub(crate) owned_addresses: Option<Vec<InterfaceIpAddr>>,
+    pub(crate) owned_routes: Option<Vec<RouteEntry>>,
+    // Addresses preserved by merging as they were not added by nmstate.
+    pub(crate) external_addresses: Vec<InterfaceIpAddr>,
 }
 
 impl Default for InterfaceIpv6 {
@@ -480,9 +574,15 @@ impl Default for InterfaceIpv6 {
             auto_gateway: None,
             auto_routes: None,
             auto_table_id: None,
+            route_table: None,
             allow_extra_address: default_allow_extra_address(),
             auto_route_metric: None,
             token: None,
+            exact: None,
+            owned_addresses: None,
+            owned_routes: None,
+            external_addresses: Vec::new(),
+            dhcp_lease: None,
         }
     }
 }
@@ -559,6 +659,7 @@ impl InterfaceIpv6 {
             self.dhcp = None;
             self.autoconf = None;
             self.addresses = None;
+            self.route_table = None;
         }
 
         if !self.is_auto() {
@@ -568,11 +669,16 @@ impl InterfaceIpv6 {
             self.auto_table_id = None;
             self.auto_route_metric = None;
         }
+        if is_desired {
+            validate_route_table(self.auto_table_id, self.route_table, "IPv6")?;
+        }
         if let Some(addrs) = self.addresses.as_mut() {
             for addr in addrs.iter_mut() {
                 addr.mptcp_flags = None;
             }
         }
+        // Query only
+        self.dhcp_lease = None;
         if let Some(token) = self.token.as_mut() {
             if is_desired
                 && self.autoconf == Some(false)
@@ -615,6 +721,17 @@ impl InterfaceIpv6 {
         {
             self.addresses = current.addresses.clone();
         }
+        if self.enabled && !self.is_auto() && !current.is_auto() {
+            self.external_addresses = get_external_addresses(
+                desired.addresses.as_deref(),
+                desired.exact,
+                current.addresses.as_deref(),
+                current.owned_addresses.as_deref(),
+            );
+            if let Some(addrs) = self.addresses.as_mut() {
+                addrs.extend_from_slice(self.external_addresses.as_slice());
+            }
+        }
     }
 
     pub(crate) fn merge_ip(&mut self, current: &Self) {
@@ -683,10 +800,13 @@ impl From<InterfaceIp> for InterfaceIpv6 {
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
             auto_table_id: ip.auto_table_id,
+            route_table: ip.route_table,
             addr_gen_mode: ip.addr_gen_mode,
             allow_extra_address: ip.allow_extra_address,
             auto_route_metric: ip.auto_route_metric,
             token: ip.token,
+            exact: ip.exact,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -709,10 +829,13 @@ impl From<InterfaceIpv6> for InterfaceIp {
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
             auto_table_id: ip.auto_table_id,
+            route_table: ip.route_table,
             addr_gen_mode: ip.addr_gen_mode,
             allow_extra_address: ip.allow_extra_address,
             auto_route_metric: ip.auto_route_metric,
             token: ip.token,
+            exact: ip.exact,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -802,6 +925,37 @@ impl std::convert::From<&InterfaceIpAddr> for String {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(rename_all = "kebab-case")]
+/// DHCP lease information received by the host, query only.
+/// Example YAML output of DHCPv4 lease:
+/// ```yaml
+/// dhcp-lease:
+///   server-id: 192.0.2.1
+///   lease-time: 3600
+///   expiry: 1760000000
+/// ```
+pub struct DhcpLease {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// DHCP server identifier, IPv4 address of DHCPv4 server or DUID of
+    /// DHCPv6 server.
+    /// Serialize and deserialize to/from `server-id`.
+    pub server_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Lease time in seconds.
+    /// Serialize and deserialize to/from `lease-time`.
+    pub lease_time: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Lease expiry time in seconds since UNIX epoch.
+    pub expiry: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Hex string of relay agent information(DHCPv4 option 82) when
+    /// present.
+    /// Serialize and deserialize to/from `relay-agent-info`.
+    pub relay_agent_info: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(from = "String", into = "String")]
@@ -967,6 +1121,68 @@ impl std::fmt::Display for WaitIp {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// How verification treats the IPv6 link local addresses generated by kernel,
+/// set by [crate::NetworkState::set_ipv6_link_local_verify()].
+/// The IPv6 link local addresses are never compared with desired IP
+/// addresses regardless of this setting.
+pub enum Ipv6LinkLocalVerify {
+    /// Kernel only considers interface as IPv6 enabled when it holds any
+    /// IPv6 address.
+    /// Deserialize and serialize from/to `auto`.
+    Auto,
+    /// Interface with IPv6 enabled in kernel but still waiting for the
+    /// IPv6 link local address is considered as IPv6 enabled, which prevent
+    /// spurious verification retries with drivers delaying the link local
+    /// address generation.
+    /// Deserialize and serialize from/to `skip`.
+    Skip,
+    /// Verification fails until every desired interface with IPv6 enabled
+    /// holds an IPv6 link local address.
+    /// Deserialize and serialize from/to `require`.
+    Require,
+}
+
+impl Default for Ipv6LinkLocalVerify {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl std::fmt::Display for Ipv6LinkLocalVerify {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Auto => "auto",
+                Self::Skip => "skip",
+                Self::Require => "require",
+            }
+        )
+    }
+}
+
+impl FromStr for Ipv6LinkLocalVerify {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, NmstateError> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "skip" => Ok(Self::Skip),
+            "require" => Ok(Self::Require),
+            _ => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid IPv6 link local verify mode {s}, expecting \
+                    auto, skip or require"
+                ),
+            )),
+        }
+    }
+}
+
 fn validate_wait_ip(base_iface: &BaseInterface) -> Result<(), NmstateError> {
     if let Some(wait_ip) = base_iface.wait_ip.as_ref() {
         if (wait_ip == &WaitIp::Ipv4 || wait_ip == &WaitIp::Ipv4AndIpv6)
@@ -1010,6 +1226,32 @@ fn validate_wait_ip(base_iface: &BaseInterface) -> Result<(), NmstateError> {
     Ok(())
 }
 
+// Both `auto-route-table-id` and `route-table` are stored into the same
+// `route-table` property of NetworkManager.
+fn validate_route_table(
+    auto_table_id: Option<u32>,
+    route_table: Option<u32>,
+    family: &str,
+) -> Result<(), NmstateError> {
+    if let (Some(auto_table_id), Some(route_table)) =
+        (auto_table_id, route_table)
+    {
+        if auto_table_id != route_table {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The {family} auto-route-table-id {auto_table_id} \
+                    conflicts with route-table {route_table}, please \
+                    define only one of them or set them to the same value"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
 fn get_ip_prop_list(
     map: &serde_json::Map<String, serde_json::Value>,
 ) -> Vec<&'static str> {
@@ -1045,9 +1287,15 @@ fn get_ip_prop_list(
     if map.contains_key("auto-route-table-id") {
         ret.push("auto_table_id")
     }
+    if map.contains_key("route-table") {
+        ret.push("route_table")
+    }
     if map.contains_key("addr-gen-mode") {
         ret.push("addr_gen_mode")
     }
+    if map.contains_key("exact") {
+        ret.push("exact")
+    }
     ret
 }
 
@@ -1082,6 +1330,49 @@ fn is_none_or_empty_mptcp_flags(v: &Option<Vec<MptcpAddressFlag>>) -> bool {
     }
 }
 
+// When backend tracks the addresses it configured, the addresses only found in
+// current are added by other tools and should be preserved unless desired
+// addresses are marked as exact.
+fn get_external_addresses(
+    desired_addrs: Option<&[InterfaceIpAddr]>,
+    exact: Option<bool>,
+    current_addrs: Option<&[InterfaceIpAddr]>,
+    owned_addrs: Option<&[InterfaceIpAddr]>,
+) -> Vec<InterfaceIpAddr> {
+    let (desired_addrs, current_addrs, owned_addrs) =
+        match (desired_addrs, current_addrs, owned_addrs) {
+            (Some(d), Some(c), Some(o)) if exact != Some(true) => (d, c, o),
+            _ => return Vec::new(),
+        };
+    let is_in = |addrs: &[InterfaceIpAddr], addr: &InterfaceIpAddr| {
+        addrs
+            .iter()
+            .any(|a| a.ip == addr.ip && a.prefix_length == addr.prefix_length)
+    };
+    let mut ret = Vec::new();
+    for addr in current_addrs {
+        if let IpAddr::V6(ip) = addr.ip {
+            if is_ipv6_unicast_link_local(&ip) {
+                continue;
+            }
+        }
+        if !is_in(owned_addrs, addr)
+            && !is_in(desired_addrs, addr)
+            && !is_in(ret.as_slice(), addr)
+        {
+            log::info!(
+                "Preserving IP address {}/{} which is not added by nmstate",
+                addr.ip,
+                addr.prefix_length
+            );
+            let mut addr = addr.clone();
+            addr.mptcp_flags = None;
+            ret.push(addr);
+        }
+    }
+    ret
+}
+
 // Allow extra IP by default
 fn default_allow_extra_address() -> bool {
     true
@@ -83,11 +83,19 @@
 //! }
 //! ```
 
+mod canonicalize;
+mod capabilities;
+mod connectivity;
+mod deprecation;
 mod deserializer;
 mod dns;
+mod drift;
+#[cfg(feature = "gen_conf")]
+mod early_boot;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+mod guardrails;
 mod hostname;
 mod ieee8021x;
 mod iface;
@@ -99,9 +107,13 @@ mod net_state;
 #[cfg(feature = "query_apply")]
 mod nispor;
 mod nm;
+mod nm_conf;
+mod ovn;
 mod ovs;
 #[cfg(feature = "query_apply")]
 mod ovsdb;
+mod pci;
+mod plan;
 #[cfg(feature = "query_apply")]
 mod policy;
 #[cfg(feature = "query_apply")]
@@ -111,10 +123,18 @@ mod route_rule;
 mod serializer;
 mod state;
 mod unit_tests;
+mod validation_profile;
 
+#[cfg(not(feature = "query_apply"))]
+pub use crate::capabilities::capabilities;
+pub use crate::capabilities::NmstateCapabilities;
+pub use crate::connectivity::{ConnectivityState, InterfaceConnectivity};
+pub use crate::deprecation::DeprecationWarning;
 pub(crate) use crate::dns::MergedDnsState;
 pub use crate::dns::{DnsClientState, DnsState};
+pub use crate::drift::{InterfaceDrift, NetworkDrift};
 pub use crate::error::{ErrorKind, NmstateError};
+pub use crate::guardrails::{Guardrails, InterfaceGuardrail};
 pub use crate::hostname::HostNameState;
 pub(crate) use crate::hostname::MergedHostNameState;
 pub use crate::ieee8021x::Ieee8021XConfig;
@@ -124,27 +144,43 @@ pub use crate::iface::{
 };
 pub(crate) use crate::ifaces::MergedInterfaces;
 pub use crate::ifaces::{
-    BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
-    BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
-    BondMode, BondOptions, BondPrimaryReselect, BondXmitHashPolicy,
-    BridgePortTunkTag, BridgePortVlanConfig, BridgePortVlanMode,
-    BridgePortVlanRange, DummyInterface, EthernetConfig, EthernetDuplex,
-    EthernetInterface, EthtoolCoalesceConfig, EthtoolConfig,
-    EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig,
-    InfiniBandConfig, InfiniBandInterface, InfiniBandMode, Interfaces,
-    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
-    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
-    LoopbackInterface, MacVlanConfig, MacVlanInterface, MacVlanMode,
-    MacVtapConfig, MacVtapInterface, MacVtapMode, OvsBridgeBondConfig,
+    BaseInterface, BatadvConfig, BatadvGwMode, BatadvInterface,
+    BatadvRoutingAlgorithm, BondAdSelect, BondAllPortsActive,
+    BondArpAllTargets, BondArpValidate, BondConfig, BondFailOverMac,
+    BondInterface, BondLacpRate, BondMode, BondOptions, BondPortConfig,
+    BondPrimaryReselect, BondXmitHashPolicy, BridgePortTunkTag,
+    BridgePortVlanConfig, BridgePortVlanMode, BridgePortVlanRange, CanConfig,
+    CanInterface, DummyInterface, EthernetConfig, EthernetDuplex,
+    EthernetInterface, EthernetUsbRole, EthernetWakeOnLanConfig,
+    EthtoolChannelsConfig, EthtoolCoalesceConfig, EthtoolConfig,
+    EthtoolEeeConfig, EthtoolFeatureConfig, EthtoolFlowAction, EthtoolFlowRule,
+    EthtoolFlowType, EthtoolPauseConfig, EthtoolQueueCoalesceConfig,
+    EthtoolRingConfig, GeneveConfig, GeneveInterface, GreConfig, GreInterface,
+    GreTapInterface, GsmBearer, GsmConfig, GsmInterface, GtpConfig,
+    GtpInterface, GtpRole, InfiniBandConfig, InfiniBandInterface,
+    InfiniBandMode, InterfaceMetadata, Interfaces, IpIpInterface,
+    IpTunnelConfig, IpVlanConfig, IpVlanInterface, IpVlanMode, L2tpv3Config,
+    L2tpv3Encap, L2tpv3Interface, LinkStats, LinuxBridgeConfig,
+    LinuxBridgeFdbEntry, LinuxBridgeFdbEntryState, LinuxBridgeInterface,
+    LinuxBridgeMulticastRouterType, LinuxBridgeOptions, LinuxBridgePortConfig,
+    LinuxBridgeStpOptions, LoopbackInterface, MacSecConfig, MacSecInterface,
+    MacSecValidate, MacVlanConfig, MacVlanInterface, MacVlanMode,
+    MacVtapConfig, MacVtapInterface, MacVtapMode, MultiConnect, NetkitConfig,
+    NetkitInterface, NetkitMode, NetkitPolicy, OvsBridgeBondConfig,
     OvsBridgeBondMode, OvsBridgeBondPortConfig, OvsBridgeConfig,
     OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig, OvsDpdkConfig,
-    OvsInterface, OvsPatchConfig, SrIovConfig, SrIovVfConfig, VethConfig,
-    VlanConfig, VlanInterface, VlanProtocol, VrfConfig, VrfInterface,
-    VxlanConfig, VxlanInterface,
+    OvsInterface, OvsPatchConfig, PciHints, PppoeConfig, PppoeInterface,
+    SitInterface, SrIovConfig, SrIovEswitchMode, SrIovVfConfig, TunConfig,
+    TunInterface, TunMode, VcanInterface, VethConfig, VlanConfig,
+    VlanInterface, VlanProtocol, VrfConfig, VrfInterface, Vti6Interface,
+    VtiConfig, VtiInterface, VxlanConfig, VxlanInterface, WifiBand, WifiConfig,
+    WifiInterface, WifiKeyMgmt, WifiMode, WifiSecretFlag, WifiSecurityConfig,
+    WireGuardConfig, WireGuardInterface, WireGuardPeerConfig, XfrmConfig,
+    XfrmInterface,
 };
 pub use crate::ip::{
-    AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr, InterfaceIpv4,
-    InterfaceIpv6, Ipv6AddrGenMode, WaitIp,
+    AddressFamily, DhcpLease, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr,
+    InterfaceIpv4, InterfaceIpv6, Ipv6AddrGenMode, Ipv6LinkLocalVerify, WaitIp,
 };
 pub use crate::lldp::{
     LldpAddressFamily, LldpChassisId, LldpChassisIdType, LldpConfig,
@@ -154,17 +190,32 @@ pub use crate::lldp::{
     LldpSystemName, LldpVlan, LldpVlans,
 };
 pub use crate::mptcp::{MptcpAddressFlag, MptcpConfig};
-pub(crate) use crate::net_state::MergedNetworkState;
-pub use crate::net_state::NetworkState;
+pub use crate::net_state::{
+    MergedNetworkState, NetworkState, NetworkStateSection,
+};
+pub(crate) use crate::nm_conf::MergedNetworkManagerConfig;
+pub use crate::nm_conf::NetworkManagerConfig;
+pub use crate::ovn::{
+    OvnBridgeMapping, OvnBridgeMappingState, OvnConfiguration,
+};
 pub(crate) use crate::ovs::MergedOvsDbGlobalConfig;
 pub use crate::ovs::{OvsDbGlobalConfig, OvsDbIfaceConfig};
+pub(crate) use crate::pci::MergedPciDevices;
+pub use crate::pci::PciDeviceConfig;
+pub use crate::plan::{ChangeRisk, InterfacePlan, NetworkPlan};
 #[cfg(feature = "query_apply")]
 pub use crate::policy::{
     NetworkCaptureRules, NetworkPolicy, NetworkStateTemplate,
 };
+#[cfg(feature = "query_apply")]
+pub use crate::query_apply::{capabilities, NetworkBackend};
+#[cfg(feature = "mock")]
+pub use crate::query_apply::{MockBackend, MockPhase};
 pub(crate) use crate::route::MergedRoutes;
-pub use crate::route::{RouteEntry, RouteState, Routes};
+pub use crate::route::{RouteEntry, RouteProtocol, RouteState, Routes};
 pub(crate) use crate::route_rule::MergedRouteRules;
 pub use crate::route_rule::{
-    RouteRuleAction, RouteRuleEntry, RouteRuleState, RouteRules,
+    RouteRuleAction, RouteRuleEntry, RouteRulePriorityRange, RouteRuleState,
+    RouteRules,
 };
+pub use crate::validation_profile::{ProfileViolation, ValidationProfile};
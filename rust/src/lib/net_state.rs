@@ -6,10 +6,13 @@ use std::collections::HashMap;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
-    DnsState, ErrorKind, HostNameState, Interface, Interfaces, MergedDnsState,
-    MergedHostNameState, MergedInterfaces, MergedOvsDbGlobalConfig,
-    MergedRouteRules, MergedRoutes, NmstateError, OvsDbGlobalConfig,
-    RouteRules, Routes,
+    deprecation::rename_deprecated_iface_props, ovn::ovn_to_ovsdb,
+    ConnectivityState, DeprecationWarning, DnsState, ErrorKind, Guardrails,
+    HostNameState, Interface, Interfaces, Ipv6LinkLocalVerify, MergedDnsState,
+    MergedHostNameState, MergedInterfaces, MergedNetworkManagerConfig,
+    MergedOvsDbGlobalConfig, MergedPciDevices, MergedRouteRules, MergedRoutes,
+    NetworkManagerConfig, NmstateError, OvnConfiguration, OvsDbGlobalConfig,
+    PciDeviceConfig, RouteEntry, RouteRules, Routes,
 };
 
 #[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
@@ -99,6 +102,21 @@ pub struct NetworkState {
     )]
     /// The global configurations of OpenvSwitach daemon
     pub ovsdb: OvsDbGlobalConfig,
+    #[serde(default, skip_serializing_if = "OvnConfiguration::is_none")]
+    /// OVN chassis configurations stored in OpenvSwitch database
+    pub ovn: OvnConfiguration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Global internet connectivity state checked by NetworkManager.
+    /// Ignored during apply.
+    pub connectivity: Option<ConnectivityState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Global configuration of NetworkManager daemon, deserialize and
+    /// serialize from/to `networkmanager`.
+    pub networkmanager: Option<NetworkManagerConfig>,
+    #[serde(rename = "pci-devices", skip_serializing_if = "Option::is_none")]
+    /// Kernel driver binding of PCI devices, deserialize and serialize
+    /// from/to `pci-devices`.
+    pub pci_devices: Option<Vec<PciDeviceConfig>>,
     #[serde(skip)]
     // Contain a list of struct member name which is defined explicitly in
     // desire state instead of generated.
@@ -112,6 +130,8 @@ pub struct NetworkState {
     #[serde(skip)]
     pub(crate) no_commit: bool,
     #[serde(skip)]
+    pub(crate) no_checkpoint: bool,
+    #[serde(skip)]
     pub(crate) timeout: Option<u32>,
     #[serde(skip)]
     pub(crate) include_secrets: bool,
@@ -121,6 +141,43 @@ pub struct NetworkState {
     pub(crate) running_config_only: bool,
     #[serde(skip)]
     pub(crate) memory_only: bool,
+    #[serde(skip)]
+    pub(crate) preserve_kernel_order: bool,
+    #[serde(skip)]
+    pub(crate) minimal: bool,
+    #[serde(skip)]
+    pub(crate) saved_config_only: bool,
+    #[serde(skip)]
+    pub(crate) gen_early_boot: bool,
+    #[serde(skip)]
+    pub(crate) include_fdb: bool,
+    #[serde(skip)]
+    pub(crate) netns: Option<String>,
+    #[serde(skip)]
+    pub(crate) migrate_team: bool,
+    #[serde(skip)]
+    pub(crate) stage_only: bool,
+    #[serde(skip)]
+    pub(crate) ipv6_link_local_verify: Ipv6LinkLocalVerify,
+    #[serde(skip)]
+    pub(crate) deprecations: Vec<DeprecationWarning>,
+    #[serde(skip)]
+    pub(crate) guardrails: Option<Guardrails>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// Section of [NetworkState] which could be retrieved individually by
+/// [NetworkState::retrieve_section()].
+pub enum NetworkStateSection {
+    /// The [NetworkState::routes] section.
+    Routes,
+    /// The [NetworkState::dns] section.
+    Dns,
+    /// The [NetworkState::hostname] section.
+    HostName,
+    /// The [NetworkState::ovsdb] section.
+    OvsDb,
 }
 
 impl<'de> Deserialize<'de> for NetworkState {
@@ -138,8 +195,10 @@ impl<'de> Deserialize<'de> for NetworkState {
                 )));
             }
         };
-        if let Some(ifaces_value) = v.remove("interfaces") {
+        if let Some(mut ifaces_value) = v.remove("interfaces") {
             net_state.prop_list.push("interfaces");
+            net_state.deprecations =
+                rename_deprecated_iface_props(&mut ifaces_value);
             net_state.interfaces = Interfaces::deserialize(ifaces_value)
                 .map_err(serde::de::Error::custom)?;
         }
@@ -163,6 +222,32 @@ impl<'de> Deserialize<'de> for NetworkState {
             net_state.ovsdb = OvsDbGlobalConfig::deserialize(ovsdb_value)
                 .map_err(serde::de::Error::custom)?;
         }
+        if let Some(ovn_value) = v.remove("ovn") {
+            net_state.prop_list.push("ovn");
+            net_state.ovn = OvnConfiguration::deserialize(ovn_value)
+                .map_err(serde::de::Error::custom)?;
+        }
+        if let Some(connectivity_value) = v.remove("connectivity") {
+            net_state.prop_list.push("connectivity");
+            net_state.connectivity = Some(
+                ConnectivityState::deserialize(connectivity_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
+        if let Some(nm_conf_value) = v.remove("networkmanager") {
+            net_state.prop_list.push("networkmanager");
+            net_state.networkmanager = Some(
+                NetworkManagerConfig::deserialize(nm_conf_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
+        if let Some(pci_value) = v.remove("pci-devices") {
+            net_state.prop_list.push("pci_devices");
+            net_state.pci_devices = Some(
+                Vec::<PciDeviceConfig>::deserialize(pci_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
         if let Some(hostname_value) = v.remove("hostname") {
             net_state.prop_list.push("hostname");
             net_state.hostname = Some(
@@ -189,6 +274,9 @@ impl NetworkState {
             && self.routes.is_empty()
             && self.interfaces.is_empty()
             && self.ovsdb.is_none()
+            && self.ovn.is_none()
+            && self.networkmanager.is_none()
+            && self.pci_devices.is_none()
     }
 
     pub(crate) const PASSWORD_HID_BY_NMSTATE: &'static str =
@@ -224,6 +312,17 @@ impl NetworkState {
         self
     }
 
+    /// Only available when [NetworkState::set_kernel_only()] set to false.
+    /// When set to false, apply without NetworkManager checkpoint which is
+    /// missing in some minimal NetworkManager builds. There is no rollback
+    /// on failure in this mode, the network might be left half configured.
+    /// Cannot be used with [NetworkState::set_commit()] set to false.
+    /// Default to true.
+    pub fn set_checkpoint(&mut self, value: bool) -> &mut Self {
+        self.no_checkpoint = !value;
+        self
+    }
+
     /// Only available when [NetworkState::set_commit()] set to false.
     /// The time to wait before rolling back the network state to the state
     /// before [NetworkState::apply()` invoked.
@@ -255,6 +354,17 @@ impl NetworkState {
         self
     }
 
+    /// Query the configuration persisted by backend(e.g. NetworkManager
+    /// connection profiles stored on disk) which will be activated after
+    /// reboot, instead of the live network state. Comparing it with
+    /// [NetworkState::set_running_config_only()] could detect configuration
+    /// saved but not activated yet.
+    /// Not available for `kernel only` mode.
+    pub fn set_saved_config_only(&mut self, value: bool) -> &mut Self {
+        self.saved_config_only = value;
+        self
+    }
+
     /// When set to true, the network state be applied and only stored in memory
     /// which will be purged after system reboot.
     pub fn set_memory_only(&mut self, value: bool) -> &mut Self {
@@ -262,6 +372,107 @@ impl NetworkState {
         self
     }
 
+    /// By default(false), the routes retrieved by [NetworkState::retrieve()]
+    /// are sorted by route table, destination and metric, route rules are
+    /// sorted by priority, so the serialized output is stable for diff.
+    /// When set to true, the order provided by kernel is preserved.
+    /// Interfaces are always serialized in the order of name, DNS servers
+    /// are always in the order of configuration.
+    pub fn set_preserve_kernel_order(&mut self, value: bool) -> &mut Self {
+        self.preserve_kernel_order = value;
+        self
+    }
+
+    /// When set to true, [NetworkState::retrieve()] omits status only
+    /// properties and properties holding the default value of backend, and
+    /// converts the state into the form of [NetworkState::canonicalize()].
+    /// The output is concise enough for checking into git.
+    /// Default is false.
+    pub fn set_minimal(&mut self, value: bool) -> &mut Self {
+        self.minimal = value;
+        self
+    }
+
+    /// When set to true, [NetworkState::gen_conf()] also generates a shell
+    /// script using `ip` command and a systemd unit invoking it, so the
+    /// kernel network configuration is realized before NetworkManager
+    /// starts. Useful when root filesystem is network-backed.
+    /// Only ethernet, bond, linux bridge, VLAN and dummy interfaces with
+    /// static IP and routes are included.
+    /// Default is false.
+    pub fn set_gen_early_boot(&mut self, value: bool) -> &mut Self {
+        self.gen_early_boot = value;
+        self
+    }
+
+    /// When set to true, [NetworkState::retrieve()] includes the forwarding
+    /// database(FDB) entries of linux bridges, both static and learned.
+    /// Default is false.
+    pub fn set_include_fdb(&mut self, value: bool) -> &mut Self {
+        self.include_fdb = value;
+        self
+    }
+
+    /// Set the network namespace for [NetworkState::retrieve()], identified
+    /// by name under `/run/netns` or by PID of process inside it, for example
+    /// a container. As NetworkManager only manages the network namespace of
+    /// host, only the kernel state is retrieved. Read only,
+    /// [NetworkState::apply()] refuses state with network namespace set.
+    /// Default is None which means the network namespace of current process.
+    pub fn set_netns(&mut self, value: Option<&str>) -> &mut Self {
+        self.netns = value.map(|v| v.to_string());
+        self
+    }
+
+    /// When set to true, [NetworkState::apply()] deletes the NetworkManager
+    /// team profiles of interfaces which are bond in desired state within
+    /// the same checkpoint. Set by [NetworkState::team_to_bond()].
+    /// Not available for `kernel only` or memory only mode.
+    /// Default is false.
+    pub fn set_migrate_team(&mut self, value: bool) -> &mut Self {
+        self.migrate_team = value;
+        self
+    }
+
+    /// When set to true, [NetworkState::apply()] only saves the
+    /// NetworkManager profiles without activating them, interfaces marked
+    /// as absent or down are untouched. Applying the same state again without
+    /// this option activates the staged profiles.
+    /// Verification is skipped as nothing is activated.
+    /// Not available for `kernel only` or memory only mode.
+    /// Default is false.
+    pub fn set_stage_only(&mut self, value: bool) -> &mut Self {
+        self.stage_only = value;
+        self
+    }
+
+    /// How [NetworkState::apply()] verifies the IPv6 link local addresses
+    /// generated by kernel. Only affects the kernel IPv6 state, the IPv6
+    /// link local addresses are never compared with desired IP addresses.
+    /// Default is [Ipv6LinkLocalVerify::Auto].
+    pub fn set_ipv6_link_local_verify(
+        &mut self,
+        value: Ipv6LinkLocalVerify,
+    ) -> &mut Self {
+        self.ipv6_link_local_verify = value;
+        self
+    }
+
+    /// Constraints enforced against this state when applying or generating
+    /// configurations. When not set, [NetworkState::apply()] loads them from
+    /// `/etc/nmstate/guardrails.yml` if exists.
+    /// Default is None.
+    pub fn set_guardrails(&mut self, value: Guardrails) -> &mut Self {
+        self.guardrails = Some(value);
+        self
+    }
+
+    /// Deprecated properties found when deserializing this state. The
+    /// deprecated properties are still honored as their replacement.
+    pub fn deprecation_warnings(&self) -> &[DeprecationWarning] {
+        self.deprecations.as_slice()
+    }
+
     /// Create empty [NetworkState]
     pub fn new() -> Self {
         Default::default()
@@ -291,6 +502,30 @@ impl NetworkState {
         }
     }
 
+    /// Parse and validate untrusted network state in YAML or JSON format.
+    /// No I/O is performed against the host: the state is validated as
+    /// desired state against empty current state, the same way
+    /// [NetworkState::gen_conf()] does, hence the result only depends on the
+    /// input bytes. Suitable for fuzzing harness or admission webhook.
+    pub fn parse_strict(content: &[u8]) -> Result<Self, NmstateError> {
+        let net_state: Self = match serde_yaml::from_slice(content) {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid network state: {e}"),
+                ));
+            }
+        };
+        MergedNetworkState::new(
+            net_state.clone(),
+            NetworkState::new(),
+            true,
+            false,
+        )?;
+        Ok(net_state)
+    }
+
     /// Append [Interface] into [NetworkState]
     pub fn append_interface_data(&mut self, iface: Interface) {
         self.interfaces.push(iface);
@@ -318,6 +553,18 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "query_apply"))]
+    pub fn apply_and_get_checkpoint(
+        &self,
+    ) -> Result<Option<String>, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::apply_and_get_checkpoint() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
     #[cfg(not(feature = "gen_conf"))]
     pub fn gen_conf(
         &self,
@@ -350,14 +597,22 @@ impl NetworkState {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub(crate) struct MergedNetworkState {
+#[non_exhaustive]
+/// Desired state merged with current state, provided to
+/// [crate::NetworkBackend] for applying.
+pub struct MergedNetworkState {
     pub(crate) hostname: MergedHostNameState,
     pub(crate) dns: MergedDnsState,
     pub(crate) interfaces: MergedInterfaces,
     pub(crate) ovsdb: MergedOvsDbGlobalConfig,
+    pub(crate) networkmanager: MergedNetworkManagerConfig,
+    pub(crate) pci_devices: MergedPciDevices,
     pub(crate) routes: MergedRoutes,
     pub(crate) rules: MergedRouteRules,
     pub(crate) memory_only: bool,
+    pub(crate) migrate_team: bool,
+    pub(crate) stage_only: bool,
+    pub(crate) ipv6_link_local_verify: Ipv6LinkLocalVerify,
     pub(crate) prop_list: Vec<&'static str>,
 }
 
@@ -374,30 +629,94 @@ impl MergedNetworkState {
             gen_conf_mode,
             memory_only,
         )?;
+        if let Some(guardrails) = desired.guardrails.as_ref() {
+            guardrails.check(&interfaces)?;
+        }
         let ignored_ifaces = interfaces.ignored_ifaces.as_slice();
 
+        let mut desired_routes = desired.routes;
+        desired_routes.resolve_iface_refs(&interfaces)?;
         let mut routes =
-            MergedRoutes::new(desired.routes, current.routes, &interfaces)?;
+            MergedRoutes::new(desired_routes, current.routes, &interfaces)?;
         routes.remove_routes_to_ignored_ifaces(ignored_ifaces);
 
+        let mut desired_dns = desired.dns;
+        desired_dns.resolve_iface_refs(&interfaces)?;
+
         let mut rules = MergedRouteRules::new(desired.rules, current.rules)?;
         rules.remove_rules_to_ignored_ifaces(ignored_ifaces);
 
         let hostname =
             MergedHostNameState::new(desired.hostname, current.hostname);
+        let networkmanager = MergedNetworkManagerConfig::new(
+            desired.networkmanager,
+            current.networkmanager,
+            memory_only,
+        )?;
+        let pci_devices =
+            MergedPciDevices::new(desired.pci_devices, current.pci_devices)?;
+        let migrate_team = desired.migrate_team;
+        let stage_only = desired.stage_only;
+        let ipv6_link_local_verify = desired.ipv6_link_local_verify;
+
+        let mut desired_ovsdb = desired.ovsdb;
+        let mut prop_list = desired.prop_list;
+        if prop_list.contains(&"ovn") {
+            ovn_to_ovsdb(
+                &desired.ovn,
+                &mut desired_ovsdb,
+                &current.ovsdb,
+                &interfaces,
+            )?;
+            if !prop_list.contains(&"ovsdb") {
+                prop_list.push("ovsdb");
+            }
+        }
 
         let ret = Self {
             interfaces,
             routes,
             rules,
-            dns: MergedDnsState::new(desired.dns, current.dns)?,
-            ovsdb: MergedOvsDbGlobalConfig::new(desired.ovsdb, current.ovsdb),
+            dns: MergedDnsState::new(desired_dns, current.dns)?,
+            ovsdb: MergedOvsDbGlobalConfig::new(desired_ovsdb, current.ovsdb),
             hostname,
+            networkmanager,
+            pci_devices,
             memory_only,
-            prop_list: desired.prop_list,
+            migrate_team,
+            stage_only,
+            ipv6_link_local_verify,
+            prop_list,
         };
         ret.validate_ipv6_link_local_address_dns_srv()?;
 
         Ok(ret)
     }
+
+    /// Interfaces changed by desired state with current properties merged.
+    pub fn interfaces_for_apply(&self) -> Interfaces {
+        let mut ifaces = Interfaces::new();
+        for merged_iface in self.interfaces.iter() {
+            if let Some(iface) = merged_iface.for_apply.as_ref() {
+                ifaces.push(iface.clone());
+            }
+        }
+        ifaces
+    }
+
+    /// Routes of interfaces whose routes are changed by desired state.
+    pub fn routes_for_apply(&self) -> Routes {
+        let mut rts: Vec<RouteEntry> = self
+            .routes
+            .route_changed_ifaces
+            .iter()
+            .filter_map(|iface_name| self.routes.indexed.get(iface_name))
+            .flatten()
+            .cloned()
+            .collect();
+        rts.sort_unstable();
+        let mut routes = Routes::new();
+        routes.config = Some(rts);
+        routes
+    }
 }
@@ -2,12 +2,20 @@
 
 use crate::{
     nispor::{
-        ip::{nmstate_ipv4_to_np, nmstate_ipv6_to_np},
+        ip::{
+            nmstate_ip_addrs_to_np, nmstate_ipv4_to_np, nmstate_ipv6_to_np,
+            remove_extra_np_ip_addrs,
+        },
+        netlink::run_netlink_requests,
+        route::{
+            gen_route_add_request, is_np_route_table_supported,
+            nmstate_route_to_np,
+        },
         veth::nms_veth_conf_to_np,
         vlan::nms_vlan_conf_to_np,
     },
     ErrorKind, Interface, InterfaceType, MergedInterface, MergedInterfaces,
-    MergedNetworkState, NmstateError,
+    MergedNetworkState, NmstateError, RouteEntry,
 };
 
 pub(crate) fn nispor_apply(
@@ -37,7 +45,11 @@ pub(crate) fn nispor_apply(
         i.merged.iface_type() != InterfaceType::Unknown && !i.merged.is_absent()
     }) {
         if let Some(iface) = merged_iface.for_apply.as_ref() {
-            np_ifaces.push(nmstate_iface_to_np(iface)?);
+            let mut np_iface = nmstate_iface_to_np(iface)?;
+            if let Some(cur_iface) = merged_iface.current.as_ref() {
+                remove_extra_ip_addrs(&mut np_iface, iface, cur_iface);
+            }
+            np_ifaces.push(np_iface);
         }
     }
 
@@ -54,6 +66,137 @@ pub(crate) fn nispor_apply(
     }
 }
 
+// Backend might remove IP addresses and routes not stored in its
+// configuration when activating interface, restore those preserved by merging.
+// The addresses are missing between the activation and this restore, hence
+// NetworkManager backend reapplies active profiles with only IP addresses
+// changed instead of activating them.
+pub(crate) fn nispor_restore_external_ip(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed() && i.merged.is_up())
+    {
+        let base_iface = merged_iface.merged.base_iface();
+        let ipv4_addrs = base_iface
+            .ipv4
+            .as_ref()
+            .map(|i| i.external_addresses.as_slice())
+            .unwrap_or_default();
+        let ipv6_addrs = base_iface
+            .ipv6
+            .as_ref()
+            .map(|i| i.external_addresses.as_slice())
+            .unwrap_or_default();
+        if ipv4_addrs.is_empty() && ipv6_addrs.is_empty() {
+            continue;
+        }
+        let mut np_iface = nispor::IfaceConf::default();
+        np_iface.name = base_iface.name.to_string();
+        np_iface.state = nispor::IfaceState::Up;
+        if !ipv4_addrs.is_empty() {
+            np_iface.ipv4 = Some(nmstate_ip_addrs_to_np(ipv4_addrs));
+        }
+        if !ipv6_addrs.is_empty() {
+            np_iface.ipv6 = Some(nmstate_ip_addrs_to_np(ipv6_addrs));
+        }
+        np_ifaces.push(np_iface);
+    }
+    let (np_rts, big_table_rts): (Vec<&RouteEntry>, Vec<&RouteEntry>) =
+        merged_state
+            .routes
+            .external
+            .iter()
+            .partition(|rt| is_np_route_table_supported(rt));
+    let np_routes: Vec<nispor::RouteConf> =
+        np_rts.into_iter().filter_map(nmstate_route_to_np).collect();
+    if !np_ifaces.is_empty() || !np_routes.is_empty() {
+        let mut net_conf = nispor::NetConf::default();
+        net_conf.ifaces = Some(np_ifaces);
+        net_conf.routes = Some(np_routes);
+
+        if let Err(e) = net_conf.apply() {
+            return Err(NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!(
+                    "Failed to restore IP addresses and routes not added by \
+                    nmstate: {}, {}",
+                    e.kind, e.msg
+                ),
+            ));
+        }
+    }
+    restore_big_table_routes(big_table_rts.as_slice())
+}
+
+fn restore_big_table_routes(rts: &[&RouteEntry]) -> Result<(), NmstateError> {
+    if rts.is_empty() {
+        return Ok(());
+    }
+    let mut filter = nispor::NetStateFilter::default();
+    filter.route = None;
+    let np_state = nispor::NetState::retrieve_with_filter(&filter)
+        .map_err(super::error::np_error_to_nmstate)?;
+
+    let mut reqs = Vec::new();
+    for rt in rts {
+        let index = match rt
+            .next_hop_iface
+            .as_deref()
+            .and_then(|i| np_state.ifaces.get(i))
+        {
+            Some(i) => i.index,
+            None => {
+                log::warn!(
+                    "Failed to find next hop interface of route {rt}, \
+                    not restoring it"
+                );
+                continue;
+            }
+        };
+        reqs.push(gen_route_add_request(rt, index)?);
+    }
+    run_netlink_requests(reqs, true, "restore routes not added by nmstate")?;
+    Ok(())
+}
+
+// Nispor only adds IP addresses, with `exact: true` desired, the current IP
+// addresses not desired should be removed explicitly.
+fn remove_extra_ip_addrs(
+    np_iface: &mut nispor::IfaceConf,
+    apply_iface: &Interface,
+    cur_iface: &Interface,
+) {
+    let apply_iface = apply_iface.base_iface();
+    let cur_iface = cur_iface.base_iface();
+    if let (Some(np_ip_conf), Some(des_ipv4), Some(cur_ipv4)) = (
+        np_iface.ipv4.as_mut(),
+        apply_iface.ipv4.as_ref().filter(|i| i.exact == Some(true)),
+        cur_iface.ipv4.as_ref(),
+    ) {
+        remove_extra_np_ip_addrs(
+            np_ip_conf,
+            des_ipv4.addresses.as_deref().unwrap_or_default(),
+            cur_ipv4.addresses.as_deref().unwrap_or_default(),
+        );
+    }
+    if let (Some(np_ip_conf), Some(des_ipv6), Some(cur_ipv6)) = (
+        np_iface.ipv6.as_mut(),
+        apply_iface.ipv6.as_ref().filter(|i| i.exact == Some(true)),
+        cur_iface.ipv6.as_ref(),
+    ) {
+        remove_extra_np_ip_addrs(
+            np_ip_conf,
+            des_ipv6.addresses.as_deref().unwrap_or_default(),
+            cur_ipv6.addresses.as_deref().unwrap_or_default(),
+        );
+    }
+}
+
 fn nmstate_iface_type_to_np(
     nms_iface_type: &InterfaceType,
 ) -> nispor::IfaceType {
@@ -117,10 +260,14 @@ fn delete_ifaces(merged_ifaces: &MergedInterfaces) -> Result<(), NmstateError> {
         .values()
         .filter(|i| i.merged.is_absent())
     {
-        // Deleting one end of veth peer is enough
+        // Deleting one end of veth or netkit peer is enough
         if deleted_veths.contains(&iface.merged.name()) {
             continue;
         }
+        // The L2TPv3 pseudowire is deleted by `nispor_apply_l2tpv3()`
+        if iface.merged.iface_type() == InterfaceType::L2tpv3 {
+            continue;
+        }
 
         if let Some(Interface::Ethernet(eth_iface)) = &iface.current {
             if let Some(peer_name) = eth_iface
@@ -132,6 +279,12 @@ fn delete_ifaces(merged_ifaces: &MergedInterfaces) -> Result<(), NmstateError> {
                 deleted_veths.push(peer_name);
             }
         }
+        if let Some(Interface::Netkit(nk_iface)) = &iface.current {
+            if let Some(peer_name) = nk_iface.peer() {
+                deleted_veths.push(nk_iface.base.name.as_str());
+                deleted_veths.push(peer_name);
+            }
+        }
         if let Some(apply_iface) = iface.for_apply.as_ref() {
             np_ifaces.push(nmstate_iface_to_np(apply_iface)?);
         }
@@ -1,16 +1,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    nispor::ethernet::np_usb_role,
     nispor::ethtool::np_ethtool_to_nmstate,
-    nispor::ip::{np_ipv4_to_nmstate, np_ipv6_to_nmstate},
+    nispor::ip::{
+        np_ipv4_to_nmstate, np_ipv6_to_nmstate, np_multicast_groups_to_nmstate,
+    },
+    nispor::l2tp::np_is_l2tp_eth,
+    nispor::link_stats::np_link_stats_to_nmstate,
+    nispor::link_tuning::np_link_tuning_to_nmstate,
     nispor::mptcp::get_iface_mptcp_conf,
+    nispor::pci::np_pci_hints_to_nmstate,
+    nispor::wifi::np_is_wifi,
     BaseInterface, InterfaceState, InterfaceType,
 };
 
-fn np_iface_type_to_nmstate(
-    np_iface_type: &nispor::IfaceType,
-) -> InterfaceType {
+fn np_iface_type_to_nmstate(np_iface: &nispor::Iface) -> InterfaceType {
+    // USB networking interface might not be ethernet link layer from
+    // kernel point of view, use driver to detect them.
+    if np_usb_role(np_iface).is_some() {
+        return InterfaceType::Ethernet;
+    }
+    let np_iface_type = &np_iface.iface_type;
     match np_iface_type {
+        nispor::IfaceType::Ethernet if np_is_wifi(np_iface) => {
+            InterfaceType::Wifi
+        }
+        nispor::IfaceType::Ethernet if np_is_l2tp_eth(np_iface) => {
+            InterfaceType::L2tpv3
+        }
         nispor::IfaceType::Bond => InterfaceType::Bond,
         nispor::IfaceType::Bridge => InterfaceType::LinuxBridge,
         nispor::IfaceType::Dummy => InterfaceType::Dummy,
@@ -24,16 +42,43 @@ fn np_iface_type_to_nmstate(
         nispor::IfaceType::Vrf => InterfaceType::Vrf,
         nispor::IfaceType::Vxlan => InterfaceType::Vxlan,
         nispor::IfaceType::Ipoib => InterfaceType::InfiniBand,
+        nispor::IfaceType::MacSec => InterfaceType::MacSec,
+        nispor::IfaceType::Tun => InterfaceType::Tun,
+        nispor::IfaceType::Other(s) if s == "wireguard" => {
+            InterfaceType::WireGuard
+        }
+        nispor::IfaceType::Other(s) if s == "gretun" || s == "gre" => {
+            InterfaceType::Gre
+        }
+        nispor::IfaceType::Other(s) if s == "gretap" => InterfaceType::GreTap,
+        nispor::IfaceType::Other(s) if s == "iptun" || s == "ipip" => {
+            InterfaceType::IpIp
+        }
+        nispor::IfaceType::Other(s) if s == "sittun" || s == "sit" => {
+            InterfaceType::Sit
+        }
+        nispor::IfaceType::Other(s) if s == "vti" => InterfaceType::Vti,
+        nispor::IfaceType::Other(s) if s == "vti6" => InterfaceType::Vti6,
+        nispor::IfaceType::Other(s) if s == "geneve" => InterfaceType::Geneve,
+        nispor::IfaceType::Other(s) if s == "gtp" => InterfaceType::Gtp,
+        nispor::IfaceType::Other(s) if s == "xfrm" => InterfaceType::Xfrm,
+        nispor::IfaceType::Other(s) if s == "ipvlan" => InterfaceType::IpVlan,
+        nispor::IfaceType::Other(s) if s == "can" => InterfaceType::Can,
+        nispor::IfaceType::Other(s) if s == "vcan" => InterfaceType::Vcan,
+        nispor::IfaceType::Other(s) if s == "netkit" => InterfaceType::Netkit,
+        nispor::IfaceType::Other(s) if s == "batadv" => InterfaceType::Batadv,
+        // The PPP interface is created by pppd and only PPPoE is supported
+        nispor::IfaceType::Other(s) if s == "ppp" => InterfaceType::Pppoe,
         _ => InterfaceType::Other(format!("{np_iface_type:?}")),
     }
 }
 
-impl From<(&nispor::IfaceState, &[nispor::IfaceFlags])> for InterfaceState {
-    fn from(tuple: (&nispor::IfaceState, &[nispor::IfaceFlags])) -> Self {
+impl From<(&nispor::IfaceState, &[nispor::IfaceFlag])> for InterfaceState {
+    fn from(tuple: (&nispor::IfaceState, &[nispor::IfaceFlag])) -> Self {
         let (state, flags) = tuple;
         if *state == nispor::IfaceState::Up
-            || flags.contains(&nispor::IfaceFlags::Up)
-            || flags.contains(&nispor::IfaceFlags::Running)
+            || flags.contains(&nispor::IfaceFlag::Up)
+            || flags.contains(&nispor::IfaceFlag::Running)
         {
             InterfaceState::Up
         } else if *state == nispor::IfaceState::Down {
@@ -51,7 +96,7 @@ pub(crate) fn np_iface_to_base_iface(
     let mut base_iface = BaseInterface {
         name: np_iface.name.to_string(),
         state: (&np_iface.state, np_iface.flags.as_slice()).into(),
-        iface_type: np_iface_type_to_nmstate(&np_iface.iface_type),
+        iface_type: np_iface_type_to_nmstate(np_iface),
         ipv4: np_ipv4_to_nmstate(np_iface, running_config_only),
         ipv6: np_ipv6_to_nmstate(np_iface, running_config_only),
         mac_address: Some(np_iface.mac_address.to_uppercase()),
@@ -77,7 +122,8 @@ pub(crate) fn np_iface_to_base_iface(
         },
         max_mtu: if !running_config_only {
             if let Some(mtu) = np_iface.max_mtu {
-                if mtu >= 0 {
+                // Kernel use 0 for no maximum MTU limitation
+                if mtu > 0 {
                     Some(mtu as u64)
                 } else {
                     None
@@ -88,15 +134,26 @@ pub(crate) fn np_iface_to_base_iface(
         } else {
             None
         },
+        link_stats: if !running_config_only {
+            np_link_stats_to_nmstate(np_iface)
+        } else {
+            None
+        },
+        pci_hints: if !running_config_only {
+            np_pci_hints_to_nmstate(np_iface)
+        } else {
+            None
+        },
         accept_all_mac_addresses: if np_iface
             .flags
-            .contains(&nispor::IfaceFlags::Promisc)
+            .contains(&nispor::IfaceFlag::Promisc)
         {
             Some(true)
         } else {
             Some(false)
         },
         ethtool: np_ethtool_to_nmstate(np_iface),
+        multicast_groups: np_multicast_groups_to_nmstate(np_iface),
         prop_list: vec![
             "name",
             "state",
@@ -109,6 +166,12 @@ pub(crate) fn np_iface_to_base_iface(
             "mtu",
             "accept_all_mac_addresses",
             "ethtool",
+            "link_stats",
+            "pci_hints",
+            "multicast_groups",
+            "txqueuelen",
+            "gro_flush_timeout",
+            "napi_defer_hard_irqs",
         ],
         ..Default::default()
     };
@@ -122,6 +185,7 @@ pub(crate) fn np_iface_to_base_iface(
     }
 
     base_iface.mptcp = get_iface_mptcp_conf(&base_iface);
+    np_link_tuning_to_nmstate(np_iface, &mut base_iface);
 
     base_iface
 }
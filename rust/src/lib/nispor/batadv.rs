@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use rtnetlink::{
+    packet_core::{DefaultNla, NetlinkMessage, NLM_F_ACK},
+    packet_route::{
+        link::{InfoData, InfoKind, LinkAttribute, LinkInfo, LinkMessage},
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{
+    emit_nlas, gen_link_del_request, gen_link_new_request,
+    gen_link_set_request, get_links, run_genl_requests, run_netlink_requests,
+    GenlRawMessage, NlLink,
+};
+use crate::{
+    BatadvConfig, BatadvGwMode, BatadvInterface, BatadvRoutingAlgorithm,
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedNetworkState,
+    NmstateError,
+};
+
+const BATADV_ACTION: &str = "query or change batman-adv interface";
+
+const BATADV_KIND: &str = "batadv";
+
+const IFLA_BATADV_ALGO_NAME: u16 = 1;
+
+const BATADV_GENL_NAME: &str = "batadv";
+const BATADV_GENL_VERSION: u8 = 1;
+
+const BATADV_CMD_GET_MESH: u8 = 1;
+const BATADV_CMD_SET_MESH: u8 = 15;
+
+const BATADV_ATTR_ALGO_NAME: u16 = 2;
+const BATADV_ATTR_MESH_IFINDEX: u16 = 3;
+const BATADV_ATTR_GW_MODE: u16 = 51;
+
+const BATADV_GW_MODE_CLIENT: u8 = 1;
+const BATADV_GW_MODE_SERVER: u8 = 2;
+
+struct BatadvChange<'a> {
+    name: &'a str,
+    cur_hard_ifaces: Vec<&'a str>,
+    des_hard_ifaces: Option<Vec<&'a str>>,
+    gw_mode: Option<BatadvGwMode>,
+}
+
+// Nispor does not support batman-adv interface yet. The mesh settings are
+// queried via batman-adv generic netlink.
+pub(crate) fn append_batadv_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Batadv)
+    {
+        return Ok(());
+    }
+    let links = get_links_by_name()?;
+    for (name, link) in
+        links.iter().filter(|(_, l)| l.is_other_kind(BATADV_KIND))
+    {
+        if let Some(Interface::Batadv(batadv_iface)) =
+            ifaces.kernel_ifaces.get_mut(name)
+        {
+            let mut conf = get_mesh_conf(link.index)?;
+            let mut hard_ifaces: Vec<String> = links
+                .iter()
+                .filter(|(_, l)| l.controller == Some(link.index))
+                .map(|(n, _)| n.to_string())
+                .collect();
+            hard_ifaces.sort_unstable();
+            conf.hard_ifaces = Some(hard_ifaces);
+            batadv_iface.batadv = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support batman-adv interface, hence both backends
+// create the interface, attach the hard interfaces and set the gateway mode
+// via netlink before activating the profiles.
+// The removal of batman-adv interface is handled by backend as other virtual
+// interfaces.
+pub(crate) fn nispor_apply_batadv(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    let mut changed_ifaces: Vec<BatadvChange> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::Batadv(batadv_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            let mut change = BatadvChange {
+                name: batadv_iface.base.name.as_str(),
+                cur_hard_ifaces: Vec::new(),
+                des_hard_ifaces: batadv_iface.hard_ifaces(),
+                gw_mode: batadv_iface.batadv.as_ref().and_then(|c| c.gw_mode),
+            };
+            if let Some(Interface::Batadv(cur_iface)) =
+                merged_iface.current.as_ref()
+            {
+                let cur_hard_ifaces =
+                    cur_iface.hard_ifaces().unwrap_or_default();
+                if batadv_iface.is_link_changed(cur_iface) {
+                    log::info!(
+                        "Recreating batman-adv interface {} as kernel does \
+                        not support changing its routing algorithm",
+                        change.name
+                    );
+                    reqs.push(gen_link_del_request(change.name));
+                    reqs.push(gen_batadv_new_request(batadv_iface));
+                    // Kernel detached the hard interfaces on deletion,
+                    // restore the current settings if not desired.
+                    if change.des_hard_ifaces.is_none() {
+                        change.des_hard_ifaces = Some(cur_hard_ifaces);
+                    }
+                    if change.gw_mode.is_none() {
+                        change.gw_mode =
+                            cur_iface.batadv.as_ref().and_then(|c| c.gw_mode);
+                    }
+                } else {
+                    change.cur_hard_ifaces = cur_hard_ifaces;
+                }
+            } else {
+                log::info!("Creating batman-adv interface {}", change.name);
+                reqs.push(gen_batadv_new_request(batadv_iface));
+            }
+            changed_ifaces.push(change);
+        }
+    }
+    if changed_ifaces.is_empty() {
+        return Ok(());
+    }
+    if !reqs.is_empty() {
+        run_netlink_requests(reqs, true, BATADV_ACTION)?;
+    }
+
+    // The ifindex of new batman-adv interface is only known after creation
+    let links = get_links_by_name()?;
+    let get_index = |iface_name: &str| {
+        if let Some(link) = links.get(iface_name) {
+            Ok(link.index)
+        } else {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Interface {iface_name} does not exist"),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    };
+    let mut reqs = Vec::new();
+    let mut genl_reqs = Vec::new();
+    for change in changed_ifaces {
+        let index = get_index(change.name)?;
+        if let Some(des_hard_ifaces) = change.des_hard_ifaces.as_ref() {
+            for hard_iface in change
+                .cur_hard_ifaces
+                .iter()
+                .filter(|i| !des_hard_ifaces.contains(i))
+            {
+                log::info!(
+                    "Detaching hard interface {hard_iface} from batman-adv \
+                    interface {}",
+                    change.name
+                );
+                reqs.push(gen_set_controller_request(
+                    get_index(hard_iface)?,
+                    0,
+                ));
+            }
+            for hard_iface in des_hard_ifaces
+                .iter()
+                .filter(|i| !change.cur_hard_ifaces.contains(i))
+            {
+                log::info!(
+                    "Attaching hard interface {hard_iface} to batman-adv \
+                    interface {}",
+                    change.name
+                );
+                reqs.push(gen_set_controller_request(
+                    get_index(hard_iface)?,
+                    index,
+                ));
+            }
+        }
+        if let Some(gw_mode) = change.gw_mode {
+            genl_reqs.push(
+                GenlRawMessage::new(
+                    BATADV_CMD_SET_MESH,
+                    BATADV_GENL_VERSION,
+                    vec![
+                        DefaultNla::new(
+                            BATADV_ATTR_MESH_IFINDEX,
+                            index.to_ne_bytes().to_vec(),
+                        ),
+                        DefaultNla::new(
+                            BATADV_ATTR_GW_MODE,
+                            vec![u8::from(gw_mode)],
+                        ),
+                    ],
+                )
+                .gen_request(NLM_F_ACK),
+            );
+        }
+    }
+    if !reqs.is_empty() {
+        run_netlink_requests(reqs, true, BATADV_ACTION)?;
+    }
+    if !genl_reqs.is_empty() {
+        run_genl_requests(BATADV_GENL_NAME, genl_reqs, true, BATADV_ACTION)?;
+    }
+    Ok(())
+}
+
+// Return HashMap with interface name as key
+fn get_links_by_name() -> Result<HashMap<String, NlLink>, NmstateError> {
+    Ok(get_links(BATADV_ACTION)?
+        .into_iter()
+        .map(|l| (l.name.clone(), l))
+        .collect())
+}
+
+fn get_mesh_conf(index: u32) -> Result<BatadvConfig, NmstateError> {
+    let req = GenlRawMessage::new(
+        BATADV_CMD_GET_MESH,
+        BATADV_GENL_VERSION,
+        vec![DefaultNla::new(
+            BATADV_ATTR_MESH_IFINDEX,
+            index.to_ne_bytes().to_vec(),
+        )],
+    )
+    .gen_request(NLM_F_ACK);
+    let mut conf = BatadvConfig::new();
+    for msg in
+        run_genl_requests(BATADV_GENL_NAME, vec![req], false, BATADV_ACTION)?
+    {
+        conf.routing_algorithm = msg
+            .get_nla_string(BATADV_ATTR_ALGO_NAME)
+            .and_then(|n| np_algo_to_nmstate(&n));
+        conf.gw_mode = msg
+            .get_nla_value(BATADV_ATTR_GW_MODE)
+            .and_then(|v| v.first().copied())
+            .map(np_gw_mode_to_nmstate);
+    }
+    Ok(conf)
+}
+
+fn np_algo_to_nmstate(name: &str) -> Option<BatadvRoutingAlgorithm> {
+    [
+        BatadvRoutingAlgorithm::BatmanIv,
+        BatadvRoutingAlgorithm::BatmanV,
+    ]
+    .iter()
+    .copied()
+    .find(|a| a.to_string() == name)
+}
+
+fn np_gw_mode_to_nmstate(value: u8) -> BatadvGwMode {
+    match value {
+        BATADV_GW_MODE_CLIENT => BatadvGwMode::Client,
+        BATADV_GW_MODE_SERVER => BatadvGwMode::Server,
+        _ => BatadvGwMode::Off,
+    }
+}
+
+// The netlink-packet-route crate does not support IFLA_INFO_DATA of
+// batman-adv interface, hence we emit the netlink attribute by ourselves.
+fn gen_batadv_info_data(algo: BatadvRoutingAlgorithm) -> Vec<u8> {
+    emit_nlas(&[DefaultNla::new(
+        IFLA_BATADV_ALGO_NAME,
+        format!("{algo}\0").into_bytes(),
+    )])
+}
+
+fn gen_batadv_new_request(
+    batadv_iface: &BatadvInterface,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut link_info =
+        vec![LinkInfo::Kind(InfoKind::Other(BATADV_KIND.to_string()))];
+    if let Some(algo) = batadv_iface
+        .batadv
+        .as_ref()
+        .and_then(|c| c.routing_algorithm)
+    {
+        link_info
+            .push(LinkInfo::Data(InfoData::Other(gen_batadv_info_data(algo))));
+    }
+    gen_link_new_request(&batadv_iface.base.name, Vec::new(), link_info, true)
+}
+
+// Setting controller ifindex to 0 detaches the interface
+fn gen_set_controller_request(
+    index: u32,
+    controller_index: u32,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = index;
+    nl_msg
+        .attributes
+        .push(LinkAttribute::Controller(controller_index));
+    gen_link_set_request(nl_msg)
+}
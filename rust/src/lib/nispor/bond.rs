@@ -3,7 +3,8 @@ use log::warn;
 use crate::{
     BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
     BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
-    BondMode, BondOptions, BondPrimaryReselect, BondXmitHashPolicy,
+    BondMode, BondOptions, BondPortConfig, BondPrimaryReselect,
+    BondXmitHashPolicy,
 };
 
 pub(crate) fn np_bond_to_nmstate(
@@ -42,6 +43,26 @@ pub(crate) fn np_bond_to_nmstate(
     bond_iface
 }
 
+pub(crate) fn append_bond_port_config(
+    bond_iface: &mut BondInterface,
+    port_np_ifaces: Vec<&nispor::Iface>,
+) {
+    let mut port_confs: Vec<BondPortConfig> = Vec::new();
+    for port_np_iface in port_np_ifaces {
+        let mut port_conf = BondPortConfig::new();
+        port_conf.name = port_np_iface.name.to_string();
+        if let Some(np_port_info) = &port_np_iface.bond_subordinate {
+            port_conf.priority = Some(np_port_info.prio);
+            port_conf.queue_id = Some(np_port_info.queue_id);
+        }
+        port_confs.push(port_conf);
+    }
+
+    if let Some(bond_conf) = bond_iface.bond.as_mut() {
+        bond_conf.ports_config = Some(port_confs);
+    }
+}
+
 fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
     let mut options = BondOptions::default();
     if let Some(ref np_bond) = &np_iface.bond {
@@ -87,6 +108,7 @@ fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
             });
         options.arp_interval = np_bond.arp_interval;
         options.arp_ip_target = np_bond.arp_ip_target.clone();
+        options.arp_missed_max = np_bond.arp_missed_max;
         options.arp_validate =
             np_bond.arp_validate.as_ref().and_then(|r| match r {
                 nispor::BondArpValidate::None => Some(BondArpValidate::None),
@@ -134,6 +156,13 @@ fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
         options.lp_interval = np_bond.lp_interval;
         options.miimon = np_bond.miimon;
         options.min_links = np_bond.min_links;
+        options.ns_ip6_target = np_bond.ns_ip6_target.as_ref().map(|addrs| {
+            addrs
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        });
         options.num_grat_arp = np_bond.num_grat_arp;
         options.num_unsol_na = np_bond.num_unsol_na;
         options.packets_per_slave = np_bond.packets_per_subordinate;
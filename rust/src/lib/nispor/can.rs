@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use rtnetlink::{
+    packet_core::{DefaultNla, NetlinkMessage, Nla},
+    packet_route::{
+        link::{
+            InfoData, InfoKind, LinkAttribute, LinkFlags, LinkInfo, LinkMessage,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{
+    emit_nlas, gen_link_down_request, gen_link_new_request,
+    gen_link_set_request, get_links, get_nla_bytes, parse_nlas,
+    run_netlink_requests,
+};
+use crate::{
+    CanConfig, ErrorKind, Interface, InterfaceType, Interfaces,
+    MergedNetworkState, NmstateError,
+};
+
+const CAN_ACTION: &str = "query or change CAN interface";
+
+const CAN_KIND: &str = "can";
+const VCAN_KIND: &str = "vcan";
+
+const IFLA_CAN_BITTIMING: u16 = 1;
+const IFLA_CAN_CTRLMODE: u16 = 5;
+const IFLA_CAN_RESTART_MS: u16 = 6;
+const IFLA_CAN_DATA_BITTIMING: u16 = 9;
+
+const CAN_CTRLMODE_FD: u32 = 0x20;
+
+// The `struct can_bittiming` holds 8 u32: bitrate, sample_point, tq,
+// prop_seg, phase_seg1, phase_seg2, sjw and brp. Kernel calculates the
+// others when only bitrate and sample_point are defined.
+const CAN_BITTIMING_U32_COUNT: usize = 8;
+
+// Nispor does not support CAN interface yet.
+pub(crate) fn append_can_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Can)
+    {
+        return Ok(());
+    }
+    for (name, (_, data)) in get_can_links()? {
+        if let Some(Interface::Can(can_iface)) =
+            ifaces.kernel_ifaces.get_mut(&name)
+        {
+            can_iface.can = Some(parse_can_nlas(&data));
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support CAN and vcan interfaces, hence both
+// backends create vcan interfaces and change the bit timing of CAN interfaces
+// via netlink before activating the profiles.
+// The removal of vcan interface is handled by backend as other virtual
+// interfaces.
+pub(crate) fn nispor_apply_can(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut can_links: Option<HashMap<String, (u32, Vec<u8>)>> = None;
+    let mut reqs = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        match merged_iface.for_apply.as_ref().filter(|i| i.is_up()) {
+            Some(Interface::Vcan(vcan_iface)) => {
+                if merged_iface.current.is_none() {
+                    log::info!(
+                        "Creating vcan interface {}",
+                        vcan_iface.base.name
+                    );
+                }
+                reqs.push(gen_vcan_new_request(&vcan_iface.base.name));
+            }
+            Some(Interface::Can(can_iface)) => {
+                if can_links.is_none() {
+                    can_links = Some(get_can_links()?);
+                }
+                let index = match can_links
+                    .as_ref()
+                    .and_then(|l| l.get(can_iface.base.name.as_str()))
+                {
+                    Some((index, _)) => *index,
+                    None => {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "CAN interface {} does not exist",
+                                can_iface.base.name
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                };
+                let cur_conf = if let Some(Interface::Can(cur_iface)) =
+                    merged_iface.current.as_ref()
+                {
+                    cur_iface.can.as_ref()
+                } else {
+                    None
+                };
+                let des_conf = can_iface
+                    .can
+                    .as_ref()
+                    .filter(|c| is_can_conf_changed(c, cur_conf));
+                if des_conf.is_some() {
+                    // Kernel refuses to change bit timing of CAN interface
+                    // in up state.
+                    log::info!(
+                        "Changing CAN configuration of interface {}",
+                        can_iface.base.name
+                    );
+                    reqs.push(gen_link_down_request(index));
+                }
+                reqs.push(gen_can_change_request(index, des_conf, cur_conf));
+            }
+            _ => (),
+        }
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_netlink_requests(reqs, true, CAN_ACTION)?;
+    Ok(())
+}
+
+// Return HashMap with interface name as key, and tuple of interface index
+// and raw IFLA_INFO_DATA as value.
+fn get_can_links() -> Result<HashMap<String, (u32, Vec<u8>)>, NmstateError> {
+    Ok(get_links(CAN_ACTION)?
+        .into_iter()
+        .filter(|link| link.is_other_kind(CAN_KIND))
+        .map(|link| {
+            let data = match link.data {
+                Some(InfoData::Other(d)) => d,
+                _ => Vec::new(),
+            };
+            (link.name, (link.index, data))
+        })
+        .collect())
+}
+
+fn is_can_conf_changed(des: &CanConfig, cur: Option<&CanConfig>) -> bool {
+    let cur = cur.cloned().unwrap_or_default();
+    (des.bitrate.is_some() && des.bitrate != cur.bitrate)
+        || (des.sample_point.is_some() && des.sample_point != cur.sample_point)
+        || (des.restart_ms.is_some() && des.restart_ms != cur.restart_ms)
+        || (des.fd.is_some() && des.fd != cur.fd)
+        || (des.data_bitrate.is_some() && des.data_bitrate != cur.data_bitrate)
+        || (des.data_sample_point.is_some()
+            && des.data_sample_point != cur.data_sample_point)
+}
+
+fn parse_can_nlas(data: &[u8]) -> CanConfig {
+    let mut conf = CanConfig::new();
+    for nla in parse_nlas(data) {
+        let value = get_nla_bytes(&nla);
+        let value = value.as_slice();
+        match nla.kind() {
+            IFLA_CAN_BITTIMING => {
+                conf.bitrate = read_u32(value, 0).filter(|v| *v > 0);
+                conf.sample_point = read_u32(value, 1).filter(|v| *v > 0);
+            }
+            IFLA_CAN_DATA_BITTIMING => {
+                conf.data_bitrate = read_u32(value, 0).filter(|v| *v > 0);
+                conf.data_sample_point = read_u32(value, 1).filter(|v| *v > 0);
+            }
+            // The `struct can_ctrlmode` holds u32 mask and u32 flags
+            IFLA_CAN_CTRLMODE => {
+                conf.fd = read_u32(value, 1).map(|f| f & CAN_CTRLMODE_FD > 0);
+            }
+            IFLA_CAN_RESTART_MS => {
+                conf.restart_ms = read_u32(value, 0);
+            }
+            _ => (),
+        }
+    }
+    if conf.fd != Some(true) {
+        conf.data_bitrate = None;
+        conf.data_sample_point = None;
+    }
+    conf
+}
+
+fn read_u32(value: &[u8], index: usize) -> Option<u32> {
+    value
+        .get(index * 4..(index + 1) * 4)
+        .and_then(|v| <[u8; 4]>::try_from(v).ok())
+        .map(u32::from_ne_bytes)
+}
+
+fn gen_bittiming(bitrate: u32, sample_point: Option<u32>) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(CAN_BITTIMING_U32_COUNT * 4);
+    ret.extend_from_slice(&bitrate.to_ne_bytes());
+    ret.extend_from_slice(&sample_point.unwrap_or_default().to_ne_bytes());
+    ret.resize(CAN_BITTIMING_U32_COUNT * 4, 0);
+    ret
+}
+
+// The netlink-packet-route crate does not support IFLA_INFO_DATA of CAN
+// interface, hence we emit the netlink attributes by ourselves.
+fn gen_can_info_data(des: &CanConfig, cur: Option<&CanConfig>) -> Vec<u8> {
+    let mut nlas = Vec::new();
+    if des.bitrate.is_some() || des.sample_point.is_some() {
+        if let Some(bitrate) =
+            des.bitrate.or_else(|| cur.and_then(|c| c.bitrate))
+        {
+            nlas.push(DefaultNla::new(
+                IFLA_CAN_BITTIMING,
+                gen_bittiming(bitrate, des.sample_point),
+            ));
+        }
+    }
+    if des.data_bitrate.is_some() || des.data_sample_point.is_some() {
+        if let Some(bitrate) = des
+            .data_bitrate
+            .or_else(|| cur.and_then(|c| c.data_bitrate))
+        {
+            nlas.push(DefaultNla::new(
+                IFLA_CAN_DATA_BITTIMING,
+                gen_bittiming(bitrate, des.data_sample_point),
+            ));
+        }
+    }
+    if let Some(fd) = des.fd {
+        let mut value = CAN_CTRLMODE_FD.to_ne_bytes().to_vec();
+        value.extend_from_slice(
+            &(if fd { CAN_CTRLMODE_FD } else { 0 }).to_ne_bytes(),
+        );
+        nlas.push(DefaultNla::new(IFLA_CAN_CTRLMODE, value));
+    }
+    if let Some(restart_ms) = des.restart_ms {
+        nlas.push(DefaultNla::new(
+            IFLA_CAN_RESTART_MS,
+            restart_ms.to_ne_bytes().to_vec(),
+        ));
+    }
+    emit_nlas(&nlas)
+}
+
+fn gen_can_change_request(
+    index: u32,
+    des: Option<&CanConfig>,
+    cur: Option<&CanConfig>,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = index;
+    nl_msg.header.flags = LinkFlags::Up;
+    nl_msg.header.change_mask = LinkFlags::Up;
+    if let Some(des) = des {
+        nl_msg.attributes.push(LinkAttribute::LinkInfo(vec![
+            LinkInfo::Kind(InfoKind::Other(CAN_KIND.to_string())),
+            LinkInfo::Data(InfoData::Other(gen_can_info_data(des, cur))),
+        ]));
+    }
+    gen_link_set_request(nl_msg)
+}
+
+// Without NLM_F_EXCL, kernel will bring existing vcan interface up.
+fn gen_vcan_new_request(
+    iface_name: &str,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    gen_link_new_request(
+        iface_name,
+        Vec::new(),
+        vec![LinkInfo::Kind(InfoKind::Other(VCAN_KIND.to_string()))],
+        false,
+    )
+}
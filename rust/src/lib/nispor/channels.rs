@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::packet_core::{DefaultNla, NLM_F_ACK, NLM_F_DUMP};
+
+use super::{
+    ethtool::{
+        gen_ethtool_header, get_ethtool_dev_name, ETHTOOL_GENL_NAME,
+        ETHTOOL_GENL_VERSION,
+    },
+    netlink::{run_genl_requests, GenlRawMessage},
+};
+use crate::{
+    EthtoolChannelsConfig, EthtoolConfig, Interfaces, MergedNetworkState,
+    NmstateError,
+};
+
+const CHANNELS_ACTION: &str = "query or change ethtool channels";
+
+const ETHTOOL_MSG_CHANNELS_GET: u8 = 17;
+const ETHTOOL_MSG_CHANNELS_SET: u8 = 18;
+
+const ETHTOOL_A_CHANNELS_HEADER: u16 = 1;
+const ETHTOOL_A_CHANNELS_RX_MAX: u16 = 2;
+const ETHTOOL_A_CHANNELS_TX_MAX: u16 = 3;
+const ETHTOOL_A_CHANNELS_OTHER_MAX: u16 = 4;
+const ETHTOOL_A_CHANNELS_COMBINED_MAX: u16 = 5;
+const ETHTOOL_A_CHANNELS_RX_COUNT: u16 = 6;
+const ETHTOOL_A_CHANNELS_TX_COUNT: u16 = 7;
+const ETHTOOL_A_CHANNELS_OTHER_COUNT: u16 = 8;
+const ETHTOOL_A_CHANNELS_COMBINED_COUNT: u16 = 9;
+
+// Nispor does not support ethtool channels yet.
+pub(crate) fn append_channels_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.base_iface().ethtool.is_some())
+    {
+        return Ok(());
+    }
+    let req = GenlRawMessage::new(
+        ETHTOOL_MSG_CHANNELS_GET,
+        ETHTOOL_GENL_VERSION,
+        vec![gen_ethtool_header(ETHTOOL_A_CHANNELS_HEADER, None, false)],
+    )
+    .gen_request(NLM_F_DUMP);
+    // Interfaces without channels support are not included in dump reply
+    for msg in
+        run_genl_requests(ETHTOOL_GENL_NAME, vec![req], false, CHANNELS_ACTION)?
+    {
+        let iface_name =
+            match get_ethtool_dev_name(&msg, ETHTOOL_A_CHANNELS_HEADER) {
+                Some(n) => n,
+                None => continue,
+            };
+        if let Some(iface) = ifaces.kernel_ifaces.get_mut(&iface_name) {
+            let mut conf = EthtoolChannelsConfig::new();
+            conf.rx = msg.get_nla_u32(ETHTOOL_A_CHANNELS_RX_COUNT);
+            conf.rx_max = msg.get_nla_u32(ETHTOOL_A_CHANNELS_RX_MAX);
+            conf.tx = msg.get_nla_u32(ETHTOOL_A_CHANNELS_TX_COUNT);
+            conf.tx_max = msg.get_nla_u32(ETHTOOL_A_CHANNELS_TX_MAX);
+            conf.other = msg.get_nla_u32(ETHTOOL_A_CHANNELS_OTHER_COUNT);
+            conf.other_max = msg.get_nla_u32(ETHTOOL_A_CHANNELS_OTHER_MAX);
+            conf.combined = msg.get_nla_u32(ETHTOOL_A_CHANNELS_COMBINED_COUNT);
+            conf.combined_max =
+                msg.get_nla_u32(ETHTOOL_A_CHANNELS_COMBINED_MAX);
+            iface
+                .base_iface_mut()
+                .ethtool
+                .get_or_insert_with(EthtoolConfig::new)
+                .channels = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager backend applies channels via `ethtool` setting, this is only
+// used by kernel backend. The maximum counts have been validated by
+// `MergedInterface::validate_ethtool_channels()`.
+pub(crate) fn nispor_apply_channels(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i.base_iface(),
+            _ => continue,
+        };
+        let channels_conf = match apply_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.channels.as_ref())
+        {
+            Some(c) => c,
+            None => continue,
+        };
+        let mut nlas = Vec::new();
+        for (kind, count) in [
+            (ETHTOOL_A_CHANNELS_RX_COUNT, channels_conf.rx),
+            (ETHTOOL_A_CHANNELS_TX_COUNT, channels_conf.tx),
+            (ETHTOOL_A_CHANNELS_OTHER_COUNT, channels_conf.other),
+            (ETHTOOL_A_CHANNELS_COMBINED_COUNT, channels_conf.combined),
+        ] {
+            if let Some(count) = count {
+                nlas.push(DefaultNla::new(kind, count.to_ne_bytes().to_vec()));
+            }
+        }
+        if nlas.is_empty() {
+            continue;
+        }
+        log::info!(
+            "Changing ethtool channels of interface {}",
+            apply_iface.name
+        );
+        nlas.insert(
+            0,
+            gen_ethtool_header(
+                ETHTOOL_A_CHANNELS_HEADER,
+                Some(apply_iface.name.as_str()),
+                false,
+            ),
+        );
+        reqs.push(
+            GenlRawMessage::new(
+                ETHTOOL_MSG_CHANNELS_SET,
+                ETHTOOL_GENL_VERSION,
+                nlas,
+            )
+            .gen_request(NLM_F_ACK),
+        );
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_genl_requests(ETHTOOL_GENL_NAME, reqs, false, CHANNELS_ACTION)?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nix::libc;
+
+use super::ethtool::ethtool_ioctl;
+use crate::{
+    ErrorKind, EthtoolCoalesceConfig, EthtoolQueueCoalesceConfig, Interfaces,
+    MergedNetworkState, NmstateError,
+};
+
+const ETHTOOL_GCOALESCE: u32 = 0x0e;
+const ETHTOOL_SCOALESCE: u32 = 0x0f;
+const ETHTOOL_PERQUEUE: u32 = 0x4b;
+
+const MAX_NUM_QUEUE: u32 = 4096;
+// The `queue_mask` of `struct ethtool_per_queue_op` is bitmap of u32
+const QUEUE_MASK_LEN: usize = MAX_NUM_QUEUE as usize / 32;
+// The size of `cmd`, `sub_command` and `queue_mask`
+const PER_QUEUE_OP_HEADER_LEN: usize = 2 + QUEUE_MASK_LEN;
+
+// The `struct ethtool_coalesce` holds 23 u32 members
+const ETHTOOL_COALESCE_LEN: usize = 23;
+const COALESCE_RX_USECS: usize = 1;
+const COALESCE_RX_FRAMES: usize = 2;
+const COALESCE_TX_USECS: usize = 5;
+const COALESCE_TX_FRAMES: usize = 6;
+
+type EthtoolCoalesce = [u32; ETHTOOL_COALESCE_LEN];
+
+// Nispor, NetworkManager and ethtool generic netlink do not support per-queue
+// coalescing yet, hence we use the legacy SIOCETHTOOL ioctl.
+// To keep the output short, only queues with settings different from the
+// device-wide ones are included.
+pub(crate) fn append_coalesce_queues_conf(ifaces: &mut Interfaces) {
+    for iface in ifaces.kernel_ifaces.values_mut() {
+        let iface_name = iface.name().to_string();
+        let ethtool_conf = match iface.base_iface_mut().ethtool.as_mut() {
+            Some(e) => e,
+            None => continue,
+        };
+        let queue_count = match ethtool_conf.channels.as_ref() {
+            Some(c) => c.queue_count().min(MAX_NUM_QUEUE),
+            None => continue,
+        };
+        let coalesce_conf = match ethtool_conf.coalesce.as_mut() {
+            Some(c) => c,
+            None => continue,
+        };
+        let queues: Vec<u32> = (0..queue_count).collect();
+        let mut coalesces = vec![[0u32; ETHTOOL_COALESCE_LEN]; queues.len()];
+        if let Err(e) = per_queue_coalesce_ioctl(
+            iface_name.as_str(),
+            ETHTOOL_GCOALESCE,
+            queues.as_slice(),
+            coalesces.as_mut_slice(),
+        ) {
+            log::debug!(
+                "Per-queue coalescing not supported by interface \
+                {iface_name}: {e}"
+            );
+            continue;
+        }
+        let queue_confs: Vec<EthtoolQueueCoalesceConfig> = queues
+            .iter()
+            .zip(coalesces.iter())
+            .map(|(queue, coalesce)| parse_coalesce(*queue, coalesce))
+            .filter(|q| !is_same_as_device_wide(q, coalesce_conf))
+            .collect();
+        if !queue_confs.is_empty() {
+            coalesce_conf.queues = Some(queue_confs);
+        }
+    }
+}
+
+// Neither nispor nor NetworkManager support per-queue coalescing, hence both
+// backends apply them after interfaces activated.
+pub(crate) fn nispor_apply_coalesce_queues(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i.base_iface(),
+            _ => continue,
+        };
+        let mut queue_confs: Vec<&EthtoolQueueCoalesceConfig> =
+            match apply_iface
+                .ethtool
+                .as_ref()
+                .and_then(|e| e.coalesce.as_ref())
+                .and_then(|c| c.queues.as_ref())
+            {
+                Some(q) if !q.is_empty() => q.iter().collect(),
+                _ => continue,
+            };
+        // Kernel iterates the queue mask in ascending order
+        queue_confs.sort_unstable_by_key(|q| q.queue);
+        let iface_name = apply_iface.name.as_str();
+        let queues: Vec<u32> = queue_confs.iter().map(|q| q.queue).collect();
+
+        // The whole `struct ethtool_coalesce` is required when setting,
+        // hence we retrieve it first.
+        let mut coalesces = vec![[0u32; ETHTOOL_COALESCE_LEN]; queues.len()];
+        per_queue_coalesce_ioctl(
+            iface_name,
+            ETHTOOL_GCOALESCE,
+            queues.as_slice(),
+            coalesces.as_mut_slice(),
+        )
+        .map_err(|e| coalesce_queue_error(iface_name, e))?;
+        let mut changed = false;
+        for (queue_conf, coalesce) in
+            queue_confs.iter().zip(coalesces.iter_mut())
+        {
+            for (index, value) in [
+                (COALESCE_RX_USECS, queue_conf.rx_usecs),
+                (COALESCE_RX_FRAMES, queue_conf.rx_frames),
+                (COALESCE_TX_USECS, queue_conf.tx_usecs),
+                (COALESCE_TX_FRAMES, queue_conf.tx_frames),
+            ] {
+                if let Some(value) = value.filter(|v| *v != coalesce[index]) {
+                    coalesce[index] = value;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            continue;
+        }
+        log::info!(
+            "Changing per-queue ethtool coalesce of interface {iface_name}"
+        );
+        per_queue_coalesce_ioctl(
+            iface_name,
+            ETHTOOL_SCOALESCE,
+            queues.as_slice(),
+            coalesces.as_mut_slice(),
+        )
+        .map_err(|e| coalesce_queue_error(iface_name, e))?;
+    }
+    Ok(())
+}
+
+fn coalesce_queue_error(
+    iface_name: &str,
+    error: std::io::Error,
+) -> NmstateError {
+    let e = NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!(
+            "Failed to query or change per-queue ethtool coalesce of \
+            interface {iface_name}: {error}"
+        ),
+    );
+    log::error!("{}", e);
+    e
+}
+
+// The `queues` should be sorted and unique, the `coalesces` holds the
+// `struct ethtool_coalesce` for each queue in the same order.
+fn per_queue_coalesce_ioctl(
+    iface_name: &str,
+    sub_command: u32,
+    queues: &[u32],
+    coalesces: &mut [EthtoolCoalesce],
+) -> Result<(), std::io::Error> {
+    let mut buffer = vec![
+        0u32;
+        PER_QUEUE_OP_HEADER_LEN
+            + queues.len() * ETHTOOL_COALESCE_LEN
+    ];
+    buffer[0] = ETHTOOL_PERQUEUE;
+    buffer[1] = sub_command;
+    for queue in queues {
+        buffer[2 + (queue / 32) as usize] |= 1 << (queue % 32);
+    }
+    for (coalesce, chunk) in coalesces.iter().zip(
+        buffer[PER_QUEUE_OP_HEADER_LEN..]
+            .chunks_exact_mut(ETHTOOL_COALESCE_LEN),
+    ) {
+        chunk.copy_from_slice(coalesce);
+        chunk[0] = sub_command;
+    }
+    ethtool_ioctl(iface_name, buffer.as_mut_ptr() as *mut libc::c_void)?;
+    for (coalesce, chunk) in coalesces.iter_mut().zip(
+        buffer[PER_QUEUE_OP_HEADER_LEN..].chunks_exact(ETHTOOL_COALESCE_LEN),
+    ) {
+        coalesce.copy_from_slice(chunk);
+    }
+    Ok(())
+}
+
+fn parse_coalesce(
+    queue: u32,
+    coalesce: &EthtoolCoalesce,
+) -> EthtoolQueueCoalesceConfig {
+    let mut queue_conf = EthtoolQueueCoalesceConfig::new(queue);
+    queue_conf.rx_usecs = Some(coalesce[COALESCE_RX_USECS]);
+    queue_conf.rx_frames = Some(coalesce[COALESCE_RX_FRAMES]);
+    queue_conf.tx_usecs = Some(coalesce[COALESCE_TX_USECS]);
+    queue_conf.tx_frames = Some(coalesce[COALESCE_TX_FRAMES]);
+    queue_conf
+}
+
+fn is_same_as_device_wide(
+    queue_conf: &EthtoolQueueCoalesceConfig,
+    coalesce_conf: &EthtoolCoalesceConfig,
+) -> bool {
+    queue_conf.rx_usecs == coalesce_conf.rx_usecs.or(Some(0))
+        && queue_conf.rx_frames == coalesce_conf.rx_frames.or(Some(0))
+        && queue_conf.tx_usecs == coalesce_conf.tx_usecs.or(Some(0))
+        && queue_conf.tx_frames == coalesce_conf.tx_frames.or(Some(0))
+}
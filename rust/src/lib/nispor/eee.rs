@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::packet_core::{DefaultNla, Nla, NLM_F_ACK, NLM_F_DUMP};
+
+use super::{
+    ethtool::{
+        gen_ethtool_header, get_ethtool_dev_name, ETHTOOL_GENL_NAME,
+        ETHTOOL_GENL_VERSION,
+    },
+    netlink::{
+        gen_nested_nla, get_nla_bytes, parse_nested_nla, run_genl_requests,
+        GenlRawMessage,
+    },
+};
+use crate::{
+    EthtoolConfig, EthtoolEeeConfig, Interfaces, MergedNetworkState,
+    NmstateError,
+};
+
+const EEE_ACTION: &str = "query or change ethtool EEE";
+
+const ETHTOOL_MSG_EEE_GET: u8 = 23;
+const ETHTOOL_MSG_EEE_SET: u8 = 24;
+
+const ETHTOOL_A_EEE_HEADER: u16 = 1;
+const ETHTOOL_A_EEE_MODES_OURS: u16 = 2;
+const ETHTOOL_A_EEE_ENABLED: u16 = 5;
+
+const ETHTOOL_A_BITSET_NOMASK: u16 = 1;
+const ETHTOOL_A_BITSET_BITS: u16 = 3;
+const ETHTOOL_A_BITSET_BITS_BIT: u16 = 1;
+const ETHTOOL_A_BITSET_BIT_NAME: u16 = 2;
+const ETHTOOL_A_BITSET_BIT_VALUE: u16 = 3;
+
+// Nispor does not support EEE yet. Link modes are queried and changed by name
+// using non-compact bitset, so we do not need to maintain the link mode index
+// table.
+pub(crate) fn append_eee_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.base_iface().ethtool.is_some())
+    {
+        return Ok(());
+    }
+    let req = GenlRawMessage::new(
+        ETHTOOL_MSG_EEE_GET,
+        ETHTOOL_GENL_VERSION,
+        vec![gen_ethtool_header(ETHTOOL_A_EEE_HEADER, None, false)],
+    )
+    .gen_request(NLM_F_DUMP);
+    // Interfaces without EEE support are not included in dump reply
+    for msg in
+        run_genl_requests(ETHTOOL_GENL_NAME, vec![req], false, EEE_ACTION)?
+    {
+        let iface_name = match get_ethtool_dev_name(&msg, ETHTOOL_A_EEE_HEADER)
+        {
+            Some(n) => n,
+            None => continue,
+        };
+        if let Some(iface) = ifaces.kernel_ifaces.get_mut(&iface_name) {
+            let mut eee_conf = EthtoolEeeConfig::new();
+            eee_conf.enabled = msg
+                .get_nla_value(ETHTOOL_A_EEE_ENABLED)
+                .and_then(|v| v.first().copied())
+                .map(|v| v > 0);
+            let mut advertise = parse_bitset_names(
+                msg.get_nla_nested(ETHTOOL_A_EEE_MODES_OURS).as_slice(),
+            );
+            advertise.sort_unstable();
+            eee_conf.advertise = Some(advertise);
+            iface
+                .base_iface_mut()
+                .ethtool
+                .get_or_insert_with(EthtoolConfig::new)
+                .eee = Some(eee_conf);
+        }
+    }
+    Ok(())
+}
+
+// Neither nispor nor NetworkManager support changing EEE, hence both
+// backends apply it after interfaces activated.
+pub(crate) fn nispor_apply_eee(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i.base_iface(),
+            _ => continue,
+        };
+        let eee_conf =
+            match apply_iface.ethtool.as_ref().and_then(|e| e.eee.as_ref()) {
+                Some(c) => c,
+                None => continue,
+            };
+        let cur_eee_conf = merged_iface
+            .current
+            .as_ref()
+            .and_then(|i| i.base_iface().ethtool.as_ref())
+            .and_then(|e| e.eee.as_ref());
+        if is_eee_conf_applied(eee_conf, cur_eee_conf) {
+            continue;
+        }
+        log::info!("Changing ethtool EEE of interface {}", apply_iface.name);
+        reqs.push(
+            gen_eee_set_msg(apply_iface.name.as_str(), eee_conf)
+                .gen_request(NLM_F_ACK),
+        );
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_genl_requests(ETHTOOL_GENL_NAME, reqs, false, EEE_ACTION)?;
+    Ok(())
+}
+
+fn is_eee_conf_applied(
+    des: &EthtoolEeeConfig,
+    cur: Option<&EthtoolEeeConfig>,
+) -> bool {
+    let cur = match cur {
+        Some(c) => c,
+        None => return false,
+    };
+    if des.enabled.is_some() && des.enabled != cur.enabled {
+        return false;
+    }
+    if let Some(des_advertise) = des.advertise.as_ref() {
+        let mut des_advertise = des_advertise.clone();
+        des_advertise.sort_unstable();
+        des_advertise.dedup();
+        if Some(&des_advertise) != cur.advertise.as_ref() {
+            return false;
+        }
+    }
+    true
+}
+
+// The non-compact bitset reply contains all the supported bits, only the
+// ones with ETHTOOL_A_BITSET_BIT_VALUE flag are enabled.
+fn parse_bitset_names(bitset_nlas: &[DefaultNla]) -> Vec<String> {
+    let mut ret = Vec::new();
+    for bits_nla in bitset_nlas
+        .iter()
+        .filter(|n| n.kind() == ETHTOOL_A_BITSET_BITS)
+    {
+        for bit_nla in parse_nested_nla(bits_nla)
+            .iter()
+            .filter(|n| n.kind() == ETHTOOL_A_BITSET_BITS_BIT)
+        {
+            let bit_nlas = parse_nested_nla(bit_nla);
+            if !bit_nlas
+                .iter()
+                .any(|n| n.kind() == ETHTOOL_A_BITSET_BIT_VALUE)
+            {
+                continue;
+            }
+            if let Some(name_nla) = bit_nlas
+                .iter()
+                .find(|n| n.kind() == ETHTOOL_A_BITSET_BIT_NAME)
+            {
+                ret.push(
+                    String::from_utf8_lossy(
+                        get_nla_bytes(name_nla)
+                            .split(|c| *c == 0)
+                            .next()
+                            .unwrap_or(&[]),
+                    )
+                    .to_string(),
+                );
+            }
+        }
+    }
+    ret
+}
+
+fn gen_eee_set_msg(
+    iface_name: &str,
+    eee_conf: &EthtoolEeeConfig,
+) -> GenlRawMessage {
+    let mut nlas = vec![gen_ethtool_header(
+        ETHTOOL_A_EEE_HEADER,
+        Some(iface_name),
+        false,
+    )];
+    if let Some(enabled) = eee_conf.enabled {
+        nlas.push(DefaultNla::new(ETHTOOL_A_EEE_ENABLED, vec![enabled as u8]));
+    }
+    // With ETHTOOL_A_BITSET_NOMASK, kernel disables all link modes not
+    // listed.
+    if let Some(advertise) = eee_conf.advertise.as_ref() {
+        let bits: Vec<DefaultNla> = advertise
+            .iter()
+            .map(|name| {
+                gen_nested_nla(
+                    ETHTOOL_A_BITSET_BITS_BIT,
+                    &[
+                        DefaultNla::new(
+                            ETHTOOL_A_BITSET_BIT_NAME,
+                            format!("{name}\0").into_bytes(),
+                        ),
+                        DefaultNla::new(ETHTOOL_A_BITSET_BIT_VALUE, Vec::new()),
+                    ],
+                )
+            })
+            .collect();
+        nlas.push(gen_nested_nla(
+            ETHTOOL_A_EEE_MODES_OURS,
+            &[
+                DefaultNla::new(ETHTOOL_A_BITSET_NOMASK, Vec::new()),
+                gen_nested_nla(ETHTOOL_A_BITSET_BITS, bits.as_slice()),
+            ],
+        ));
+    }
+    GenlRawMessage::new(ETHTOOL_MSG_EEE_SET, ETHTOOL_GENL_VERSION, nlas)
+}
@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::packet_core::{DefaultNla, NLM_F_ACK};
+
+use super::{
+    netlink::{run_genl_requests, GenlRawMessage},
+    pci::{probe_pci_driver, unbind_pci_driver},
+};
+use crate::{
+    ErrorKind, Interface, Interfaces, MergedNetworkState, NmstateError,
+    SrIovEswitchMode,
+};
+
+const ESWITCH_ACTION: &str = "query or change SR-IOV eswitch mode";
+
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+const SYSFS_VF_PREFIX: &str = "virtfn";
+
+const DEVLINK_GENL_NAME: &str = "devlink";
+const DEVLINK_GENL_VERSION: u8 = 1;
+
+const DEVLINK_CMD_ESWITCH_GET: u8 = 29;
+const DEVLINK_CMD_ESWITCH_SET: u8 = 30;
+
+const DEVLINK_ATTR_BUS_NAME: u16 = 1;
+const DEVLINK_ATTR_DEV_NAME: u16 = 2;
+const DEVLINK_ATTR_ESWITCH_MODE: u16 = 25;
+
+const DEVLINK_ESWITCH_MODE_LEGACY: u16 = 0;
+const DEVLINK_ESWITCH_MODE_SWITCHDEV: u16 = 1;
+
+const PCI_BUS_NAME: &str = "pci";
+
+// Nispor does not support devlink yet. Kernel does not support dumping
+// eswitch mode, hence we query each SR-IOV PF individually.
+pub(crate) fn append_eswitch_mode(ifaces: &mut Interfaces) {
+    for iface in ifaces.kernel_ifaces.values_mut() {
+        let eth_iface = match iface {
+            Interface::Ethernet(i) => i,
+            _ => continue,
+        };
+        let pf_name = eth_iface.base.name.as_str();
+        let sriov_conf =
+            match eth_iface.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut()) {
+                Some(s) => s,
+                None => continue,
+            };
+        let pci_address = match get_pci_address(pf_name) {
+            Some(p) => p,
+            None => continue,
+        };
+        let req = GenlRawMessage::new(
+            DEVLINK_CMD_ESWITCH_GET,
+            DEVLINK_GENL_VERSION,
+            gen_devlink_dev_nlas(pci_address.as_str()),
+        )
+        .gen_request(NLM_F_ACK);
+        match run_genl_requests(
+            DEVLINK_GENL_NAME,
+            vec![req],
+            false,
+            ESWITCH_ACTION,
+        ) {
+            Ok(msgs) => {
+                sriov_conf.eswitch_mode = msgs
+                    .iter()
+                    .find_map(|m| m.get_nla_u16(DEVLINK_ATTR_ESWITCH_MODE))
+                    .and_then(|m| match m {
+                        DEVLINK_ESWITCH_MODE_LEGACY => {
+                            Some(SrIovEswitchMode::Legacy)
+                        }
+                        DEVLINK_ESWITCH_MODE_SWITCHDEV => {
+                            Some(SrIovEswitchMode::Switchdev)
+                        }
+                        _ => None,
+                    });
+            }
+            Err(e) => {
+                log::debug!(
+                    "Eswitch mode not supported by interface {pf_name}: {e}"
+                );
+            }
+        }
+    }
+}
+
+// Kernel refuses to change eswitch mode when VFs are in use, hence we unbind
+// all VFs from their drivers, change the mode and probe the VFs again. The
+// VFs and VF representors are recreated, backend configures them afterwards.
+// This should be done before activating PF and VFs. NetworkManager does not
+// support eswitch mode, hence both backends use this.
+pub(crate) fn nispor_apply_eswitch_mode(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let eth_iface =
+            match merged_iface.for_apply.as_ref().filter(|i| i.is_up()) {
+                Some(Interface::Ethernet(i)) => i,
+                _ => continue,
+            };
+        let des_mode = match eth_iface
+            .ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+            .and_then(|s| s.eswitch_mode)
+        {
+            Some(m) => m,
+            None => continue,
+        };
+        let cur_mode = match merged_iface.current.as_ref() {
+            Some(Interface::Ethernet(i)) => i
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.sr_iov.as_ref())
+                .and_then(|s| s.eswitch_mode),
+            _ => None,
+        };
+        if cur_mode == Some(des_mode) {
+            continue;
+        }
+        let pf_name = eth_iface.base.name.as_str();
+        let pci_address = match get_pci_address(pf_name) {
+            Some(p) => p,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Cannot change eswitch mode of interface {pf_name} \
+                        which is not a PCI device"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        let vf_pci_addresses = get_vf_pci_addresses(pf_name);
+        log::info!(
+            "Changing eswitch mode of SR-IOV PF {pf_name} to {des_mode}"
+        );
+        for vf_pci_address in &vf_pci_addresses {
+            unbind_pci_driver(vf_pci_address)?;
+        }
+        let mut nlas = gen_devlink_dev_nlas(pci_address.as_str());
+        nlas.push(DefaultNla::new(
+            DEVLINK_ATTR_ESWITCH_MODE,
+            match des_mode {
+                SrIovEswitchMode::Legacy => DEVLINK_ESWITCH_MODE_LEGACY,
+                SrIovEswitchMode::Switchdev => DEVLINK_ESWITCH_MODE_SWITCHDEV,
+            }
+            .to_ne_bytes()
+            .to_vec(),
+        ));
+        let result = run_genl_requests(
+            DEVLINK_GENL_NAME,
+            vec![GenlRawMessage::new(
+                DEVLINK_CMD_ESWITCH_SET,
+                DEVLINK_GENL_VERSION,
+                nlas,
+            )
+            .gen_request(NLM_F_ACK)],
+            false,
+            ESWITCH_ACTION,
+        );
+        // Probe VFs regardless of the result to not leave them unbound
+        for vf_pci_address in &vf_pci_addresses {
+            probe_pci_driver(vf_pci_address)?;
+        }
+        result?;
+    }
+    Ok(())
+}
+
+fn gen_devlink_dev_nlas(pci_address: &str) -> Vec<DefaultNla> {
+    vec![
+        DefaultNla::new(
+            DEVLINK_ATTR_BUS_NAME,
+            format!("{PCI_BUS_NAME}\0").into_bytes(),
+        ),
+        DefaultNla::new(
+            DEVLINK_ATTR_DEV_NAME,
+            format!("{pci_address}\0").into_bytes(),
+        ),
+    ]
+}
+
+fn get_pci_address(iface_name: &str) -> Option<String> {
+    std::fs::canonicalize(format!("{SYSFS_NET_PATH}/{iface_name}/device"))
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+}
+
+fn get_vf_pci_addresses(pf_name: &str) -> Vec<String> {
+    let dev_path = format!("{SYSFS_NET_PATH}/{pf_name}/device");
+    let entries = match std::fs::read_dir(&dev_path) {
+        Ok(e) => e,
+        Err(e) => {
+            log::debug!("Failed to read {dev_path}: {e}");
+            return Vec::new();
+        }
+    };
+    let mut ret: Vec<String> = entries
+        .flatten()
+        .filter(|e| {
+            e.file_name().to_string_lossy().starts_with(SYSFS_VF_PREFIX)
+        })
+        .filter_map(|e| {
+            std::fs::read_link(e.path()).ok().and_then(|p| {
+                p.file_name().map(|n| n.to_string_lossy().to_string())
+            })
+        })
+        .collect();
+    ret.sort_unstable();
+    ret
+}
@@ -1,8 +1,39 @@
+use std::collections::HashMap;
+
 use crate::{
     BaseInterface, EthernetConfig, EthernetDuplex, EthernetInterface,
-    SrIovConfig, SrIovVfConfig,
+    EthernetUsbRole, Interface, Interfaces, SrIovConfig, SrIovVfConfig,
 };
 
+// Nispor does not provide IFLA_PHYS_SWITCH_ID and IFLA_PHYS_PORT_NAME yet.
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
+const USB_HOST_ETH_DRIVERS: [&str; 5] = [
+    "cdc_ether",
+    "cdc_ncm",
+    "cdc_eem",
+    "cdc_subset",
+    "rndis_host",
+];
+const USB_GADGET_ETH_DRIVERS: [&str; 4] =
+    ["g_ether", "g_multi", "g_cdc", "g_ncm"];
+// Configfs gadget driver is named as `configfs-gadget` or
+// `configfs-gadget.<gadget_name>` depending on kernel version.
+const USB_CONFIGFS_GADGET_DRIVER_PREFIX: &str = "configfs-gadget";
+
+pub(crate) fn np_usb_role(np_iface: &nispor::Iface) -> Option<EthernetUsbRole> {
+    let driver = np_iface.driver.as_deref()?;
+    if USB_HOST_ETH_DRIVERS.contains(&driver) {
+        Some(EthernetUsbRole::Host)
+    } else if USB_GADGET_ETH_DRIVERS.contains(&driver)
+        || driver.starts_with(USB_CONFIGFS_GADGET_DRIVER_PREFIX)
+    {
+        Some(EthernetUsbRole::Gadget)
+    } else {
+        None
+    }
+}
+
 pub(crate) fn np_ethernet_to_nmstate(
     np_iface: &nispor::Iface,
     base_iface: BaseInterface,
@@ -15,8 +46,9 @@ pub(crate) fn np_ethernet_to_nmstate(
 
 fn gen_eth_conf(np_iface: &nispor::Iface) -> EthernetConfig {
     let mut eth_conf = EthernetConfig::new();
+    eth_conf.usb_role = np_usb_role(np_iface);
     if let Some(sriov_info) = &np_iface.sriov {
-        eth_conf.sr_iov = Some(gen_sriov_conf(sriov_info));
+        eth_conf.sr_iov = Some(gen_sriov_conf(&np_iface.name, sriov_info));
     }
     if let Some(ethtool_info) = &np_iface.ethtool {
         if let Some(link_mode_info) = &ethtool_info.link_mode {
@@ -39,7 +71,10 @@ fn gen_eth_conf(np_iface: &nispor::Iface) -> EthernetConfig {
     eth_conf
 }
 
-fn gen_sriov_conf(sriov_info: &nispor::SriovInfo) -> SrIovConfig {
+fn gen_sriov_conf(
+    pf_name: &str,
+    sriov_info: &nispor::SriovInfo,
+) -> SrIovConfig {
     let mut ret = SrIovConfig::new();
     let mut vfs: Vec<SrIovVfConfig> = Vec::new();
     for vf_info in &sriov_info.vfs {
@@ -54,9 +89,108 @@ fn gen_sriov_conf(sriov_info: &nispor::SriovInfo) -> SrIovConfig {
         vf.max_tx_rate = Some(vf_info.max_tx_rate);
         vf.vlan_id = Some(vf_info.vlan_id);
         vf.qos = Some(vf_info.qos);
+        let vf_dev = format!("device/virtfn{}", vf_info.id);
+        vf.pci_address = read_sysfs_link_name(pf_name, &vf_dev);
+        vf.driver = read_sysfs_link_name(pf_name, &format!("{vf_dev}/driver"));
         vfs.push(vf);
     }
     ret.total_vfs = Some(vfs.len() as u32);
     ret.vfs = Some(vfs);
     ret
 }
+
+// When PF is in switchdev eswitch mode, the PF(uplink representor) and VF
+// representors share the same `phys_switch_id`, the `phys_port_name` of
+// uplink is `p<pf_index>` while VF representor is `pf<pf_index>vf<vf_id>`.
+pub(crate) fn append_sriov_vf_representors(ifaces: &mut Interfaces) {
+    // HashMap<(switch_id, pf_index, vf_id), representor_name>
+    let mut reps: HashMap<(String, u32, u32), String> = HashMap::new();
+    for iface in ifaces.kernel_ifaces.values() {
+        if let Interface::Ethernet(eth_iface) = iface {
+            if let (Some(switch_id), Some((pf_index, Some(vf_id)))) = (
+                read_sysfs_str(&eth_iface.base.name, "phys_switch_id"),
+                read_sysfs_str(&eth_iface.base.name, "phys_port_name")
+                    .as_deref()
+                    .and_then(parse_phys_port_name),
+            ) {
+                reps.insert(
+                    (switch_id, pf_index, vf_id),
+                    eth_iface.base.name.clone(),
+                );
+            }
+        }
+    }
+    if reps.is_empty() {
+        return;
+    }
+
+    for iface in ifaces.kernel_ifaces.values_mut() {
+        if let Interface::Ethernet(eth_iface) = iface {
+            let vfs = match eth_iface
+                .ethernet
+                .as_mut()
+                .and_then(|e| e.sr_iov.as_mut())
+                .and_then(|s| s.vfs.as_mut())
+            {
+                Some(v) if !v.is_empty() => v,
+                _ => continue,
+            };
+            if let (Some(switch_id), Some((pf_index, None))) = (
+                read_sysfs_str(&eth_iface.base.name, "phys_switch_id"),
+                read_sysfs_str(&eth_iface.base.name, "phys_port_name")
+                    .as_deref()
+                    .and_then(parse_phys_port_name),
+            ) {
+                for vf in vfs.iter_mut() {
+                    vf.representor = reps
+                        .get(&(switch_id.clone(), pf_index, vf.id))
+                        .cloned();
+                }
+            }
+        }
+    }
+}
+
+// Return (pf_index, None) for uplink `p0` and (pf_index, Some(vf_id)) for
+// VF representor `pf0vf1`.
+fn parse_phys_port_name(name: &str) -> Option<(u32, Option<u32>)> {
+    if let Some(rest) = name.strip_prefix("pf") {
+        let (pf_index, vf_id) = rest.split_once("vf")?;
+        Some((pf_index.parse().ok()?, Some(vf_id.parse().ok()?)))
+    } else {
+        Some((name.strip_prefix('p')?.parse().ok()?, None))
+    }
+}
+
+fn read_sysfs_str(iface_name: &str, prop: &str) -> Option<String> {
+    let path = format!("{SYSFS_NET_PATH}/{iface_name}/{prop}");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            let content = content.trim();
+            if content.is_empty() {
+                None
+            } else {
+                Some(content.to_string())
+            }
+        }
+        Err(e) => {
+            log::debug!("Failed to read {path}: {e}");
+            None
+        }
+    }
+}
+
+// Return the file name of symbolic link target
+fn read_sysfs_link_name(iface_name: &str, prop: &str) -> Option<String> {
+    let path = format!("{SYSFS_NET_PATH}/{iface_name}/{prop}");
+    match std::fs::read_link(&path) {
+        Ok(target) => target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_string()),
+        Err(e) => {
+            log::debug!("Failed to read link {path}: {e}");
+            None
+        }
+    }
+}
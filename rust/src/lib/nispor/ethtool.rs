@@ -1,7 +1,18 @@
+use nix::libc;
+use rtnetlink::packet_core::{DefaultNla, Nla};
+
+use super::netlink::{gen_nested_nla, get_nla_bytes, GenlRawMessage};
 use crate::{
     EthtoolCoalesceConfig, EthtoolConfig, EthtoolPauseConfig, EthtoolRingConfig,
 };
 
+pub(crate) const ETHTOOL_GENL_NAME: &str = "ethtool";
+pub(crate) const ETHTOOL_GENL_VERSION: u8 = 1;
+
+const ETHTOOL_A_HEADER_DEV_NAME: u16 = 2;
+const ETHTOOL_A_HEADER_FLAGS: u16 = 3;
+const ETHTOOL_FLAG_COMPACT_BITSETS: u32 = 1;
+
 pub(crate) fn np_ethtool_to_nmstate(
     np_iface: &nispor::Iface,
 ) -> Option<EthtoolConfig> {
@@ -61,3 +72,91 @@ fn gen_ethtool_config(ethtool_info: &nispor::EthtoolInfo) -> EthtoolConfig {
     }
     ret
 }
+
+// Helpers for the ethtool generic netlink messages not exposed by nispor.
+// The `iface_name` is optional as dump request does not need it.
+pub(crate) fn gen_ethtool_header(
+    header_kind: u16,
+    iface_name: Option<&str>,
+    compact_bitsets: bool,
+) -> DefaultNla {
+    let mut nlas = Vec::new();
+    if compact_bitsets {
+        nlas.push(DefaultNla::new(
+            ETHTOOL_A_HEADER_FLAGS,
+            ETHTOOL_FLAG_COMPACT_BITSETS.to_ne_bytes().to_vec(),
+        ));
+    }
+    if let Some(iface_name) = iface_name {
+        nlas.push(DefaultNla::new(
+            ETHTOOL_A_HEADER_DEV_NAME,
+            format!("{iface_name}\0").into_bytes(),
+        ));
+    }
+    gen_nested_nla(header_kind, nlas.as_slice())
+}
+
+pub(crate) fn get_ethtool_dev_name(
+    msg: &GenlRawMessage,
+    header_kind: u16,
+) -> Option<String> {
+    msg.get_nla_nested(header_kind)
+        .iter()
+        .find(|nla| nla.kind() == ETHTOOL_A_HEADER_DEV_NAME)
+        .map(|nla| {
+            String::from_utf8_lossy(
+                get_nla_bytes(nla).split(|c| *c == 0).next().unwrap_or(&[]),
+            )
+            .to_string()
+        })
+}
+
+#[repr(C)]
+struct IfReq {
+    ifr_name: [u8; libc::IFNAMSIZ],
+    ifr_data: *mut libc::c_void,
+    // Make sure we are not smaller than kernel `struct ifreq`
+    _padding: [u8; 16],
+}
+
+// Some ethtool features are only available via the legacy SIOCETHTOOL ioctl.
+// The `data` should point to the ethtool command structure with enough space
+// for kernel reply.
+pub(crate) fn ethtool_ioctl(
+    iface_name: &str,
+    data: *mut libc::c_void,
+) -> Result<(), std::io::Error> {
+    let mut ifr = IfReq {
+        ifr_name: [0; libc::IFNAMSIZ],
+        ifr_data: data,
+        _padding: [0; 16],
+    };
+    for (dst, src) in ifr
+        .ifr_name
+        .iter_mut()
+        .zip(iface_name.as_bytes().iter().take(libc::IFNAMSIZ - 1))
+    {
+        *dst = *src;
+    }
+    // Safe: socket() has no memory side effect
+    let fd = unsafe {
+        libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0)
+    };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Safe: the `ifr_data` points to buffer holding the ethtool command
+    // with enough space for kernel reply.
+    let rc = unsafe {
+        libc::ioctl(fd, libc::SIOCETHTOOL as _, &mut ifr as *mut IfReq)
+    };
+    let ret = if rc < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    };
+    unsafe {
+        libc::close(fd);
+    }
+    ret
+}
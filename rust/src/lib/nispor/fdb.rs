@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use rtnetlink::{
+    packet_core::{
+        NetlinkMessage, NLM_F_ACK, NLM_F_APPEND, NLM_F_CREATE, NLM_F_DUMP,
+        NLM_F_REPLACE, NLM_F_REQUEST,
+    },
+    packet_route::{
+        neighbour::{
+            NeighbourAddress, NeighbourAttribute, NeighbourFlags,
+            NeighbourMessage, NeighbourState,
+        },
+        AddressFamily, RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::run_netlink_requests;
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, LinuxBridgeConfig,
+    LinuxBridgeFdbEntry, MergedNetworkState, NmstateError,
+};
+
+const FDB_ACTION: &str = "query or change FDB";
+
+// Nispor does not support bridge FDB yet. The flood remotes of VXLAN
+// interfaces are always included, the FDB of linux bridges is only included
+// when `include_bridge_fdb` is true.
+pub(crate) fn append_fdb(
+    ifaces: &mut Interfaces,
+    np_ifaces: &HashMap<String, nispor::Iface>,
+    include_bridge_fdb: bool,
+) -> Result<(), NmstateError> {
+    let has_vxlan = ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Vxlan);
+    if !has_vxlan && !include_bridge_fdb {
+        return Ok(());
+    }
+    let index_to_np_iface: HashMap<u32, &nispor::Iface> =
+        np_ifaces.values().map(|i| (i.index, i)).collect();
+
+    let mut nl_msg = NeighbourMessage::default();
+    nl_msg.header.family = AddressFamily::Bridge;
+    let mut req =
+        NetlinkMessage::from(RouteNetlinkMessage::GetNeighbour(nl_msg));
+    req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+    let nl_msgs: Vec<NeighbourMessage> =
+        run_netlink_requests(vec![req], false, FDB_ACTION)?
+            .into_iter()
+            .filter_map(|m| match m {
+                RouteNetlinkMessage::NewNeighbour(n) => Some(n),
+                _ => None,
+            })
+            .collect();
+
+    if has_vxlan {
+        append_vxlan_flood_remotes(ifaces, &nl_msgs, np_ifaces);
+    }
+    if include_bridge_fdb {
+        append_bridge_fdb(ifaces, &nl_msgs, &index_to_np_iface);
+    }
+    Ok(())
+}
+
+fn append_vxlan_flood_remotes(
+    ifaces: &mut Interfaces,
+    nl_msgs: &[NeighbourMessage],
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) {
+    let mut flood_remotes: HashMap<u32, Vec<IpAddr>> = HashMap::new();
+    for nl_msg in nl_msgs
+        .iter()
+        .filter(|n| n.header.flags.contains(NeighbourFlags::Own))
+    {
+        let mut is_all_zero_mac = false;
+        let mut remote = None;
+        for nla in nl_msg.attributes.as_slice() {
+            match nla {
+                NeighbourAttribute::LinkLocalAddress(mac) => {
+                    is_all_zero_mac = mac.iter().all(|b| *b == 0);
+                }
+                NeighbourAttribute::Destination(NeighbourAddress::Inet(ip)) => {
+                    remote = Some(IpAddr::V4(*ip));
+                }
+                NeighbourAttribute::Destination(NeighbourAddress::Inet6(
+                    ip,
+                )) => {
+                    remote = Some(IpAddr::V6(*ip));
+                }
+                _ => (),
+            }
+        }
+        if let Some(remote) = remote.filter(|_| is_all_zero_mac) {
+            flood_remotes
+                .entry(nl_msg.header.ifindex)
+                .or_default()
+                .push(remote);
+        }
+    }
+
+    for iface in ifaces.kernel_ifaces.values_mut() {
+        if let Interface::Vxlan(vxlan_iface) = iface {
+            let index = match np_ifaces.get(&vxlan_iface.base.name) {
+                Some(i) => i.index,
+                None => continue,
+            };
+            if let Some(vxlan_conf) = vxlan_iface.vxlan.as_mut() {
+                let mut remotes =
+                    flood_remotes.remove(&index).unwrap_or_default();
+                // Kernel creates all-zero entry for the default remote
+                remotes.retain(|r| Some(*r) != vxlan_conf.remote);
+                remotes.sort_unstable();
+                vxlan_conf.flood_remotes = Some(remotes);
+            }
+        }
+    }
+}
+
+fn append_bridge_fdb(
+    ifaces: &mut Interfaces,
+    nl_msgs: &[NeighbourMessage],
+    index_to_np_iface: &HashMap<u32, &nispor::Iface>,
+) {
+    let mut bridge_fdbs: HashMap<String, Vec<LinuxBridgeFdbEntry>> =
+        HashMap::new();
+    for nl_msg in nl_msgs {
+        if let Some((br_name, entry)) =
+            np_fdb_to_nmstate(nl_msg, index_to_np_iface)
+        {
+            bridge_fdbs.entry(br_name).or_default().push(entry);
+        }
+    }
+
+    for iface in ifaces.kernel_ifaces.values_mut() {
+        if let Interface::LinuxBridge(br_iface) = iface {
+            let mut entries =
+                bridge_fdbs.remove(&br_iface.base.name).unwrap_or_default();
+            entries.sort_unstable_by(|a, b| {
+                (a.port.as_str(), a.vlan, a.mac_address.as_str(), &a.remote)
+                    .cmp(&(
+                        b.port.as_str(),
+                        b.vlan,
+                        b.mac_address.as_str(),
+                        &b.remote,
+                    ))
+            });
+            br_iface
+                .bridge
+                .get_or_insert_with(LinuxBridgeConfig::new)
+                .fdb = Some(entries);
+        }
+    }
+}
+
+fn np_fdb_to_nmstate(
+    nl_msg: &NeighbourMessage,
+    index_to_np_iface: &HashMap<u32, &nispor::Iface>,
+) -> Option<(String, LinuxBridgeFdbEntry)> {
+    let port_np_iface = index_to_np_iface.get(&nl_msg.header.ifindex)?;
+    let is_vxlan = port_np_iface.iface_type == nispor::IfaceType::Vxlan;
+    // The permanent entries of bridge ports are their own MAC addresses
+    if !is_vxlan && nl_msg.header.state == NeighbourState::Permanent {
+        return None;
+    }
+
+    let mut entry = LinuxBridgeFdbEntry::new();
+    entry.port = port_np_iface.name.to_string();
+    let mut br_index = None;
+    for nla in nl_msg.attributes.as_slice() {
+        match nla {
+            NeighbourAttribute::LinkLocalAddress(mac) => {
+                entry.mac_address = mac
+                    .iter()
+                    .map(|b| format!("{b:02X}"))
+                    .collect::<Vec<String>>()
+                    .join(":");
+            }
+            NeighbourAttribute::Vlan(vlan) => entry.vlan = Some(*vlan),
+            NeighbourAttribute::Destination(NeighbourAddress::Inet(ip)) => {
+                entry.remote = Some(ip.to_string());
+            }
+            NeighbourAttribute::Destination(NeighbourAddress::Inet6(ip)) => {
+                entry.remote = Some(ip.to_string());
+            }
+            NeighbourAttribute::Controller(index) => br_index = Some(*index),
+            _ => (),
+        }
+    }
+    entry.learned = Some(
+        nl_msg.header.flags.contains(NeighbourFlags::ExtLearned)
+            || !matches!(
+                nl_msg.header.state,
+                NeighbourState::Permanent | NeighbourState::Noarp
+            ),
+    );
+
+    let br_name = match br_index {
+        Some(i) => index_to_np_iface.get(&i)?.name.to_string(),
+        // VXLAN remote entry is stored in VXLAN interface itself
+        None if is_vxlan && entry.remote.is_some() => {
+            port_np_iface.controller.as_ref()?.to_string()
+        }
+        None => return None,
+    };
+    Some((br_name, entry))
+}
+
+// Program the static FDB entries of desired linux bridges and flood remotes
+// of desired VXLAN interfaces. Should be invoked after bridge ports attached.
+pub(crate) fn nispor_apply_fdb(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut fdb_entries: Vec<(&str, &LinuxBridgeFdbEntry)> = Vec::new();
+    // Tuple of VXLAN interface name, remote and whether to remove
+    let mut flood_remotes: Vec<(&str, IpAddr, bool)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        match merged_iface.for_apply.as_ref().filter(|i| i.is_up()) {
+            Some(Interface::LinuxBridge(br_iface)) => {
+                for entry in br_iface
+                    .bridge
+                    .as_ref()
+                    .and_then(|b| b.fdb.as_deref())
+                    .unwrap_or_default()
+                {
+                    fdb_entries.push((br_iface.base.name.as_str(), entry));
+                }
+            }
+            Some(Interface::Vxlan(vxlan_iface)) => {
+                let des_remotes = match vxlan_iface
+                    .vxlan
+                    .as_ref()
+                    .and_then(|v| v.flood_remotes.as_deref())
+                {
+                    Some(r) => r,
+                    None => continue,
+                };
+                let cur_remotes = match merged_iface.current.as_ref() {
+                    Some(Interface::Vxlan(cur_iface)) => cur_iface
+                        .vxlan
+                        .as_ref()
+                        .and_then(|v| v.flood_remotes.as_deref())
+                        .unwrap_or_default(),
+                    _ => &[],
+                };
+                let name = vxlan_iface.base.name.as_str();
+                for remote in des_remotes {
+                    flood_remotes.push((name, *remote, false));
+                }
+                for remote in
+                    cur_remotes.iter().filter(|r| !des_remotes.contains(r))
+                {
+                    flood_remotes.push((name, *remote, true));
+                }
+            }
+            _ => (),
+        }
+    }
+    if fdb_entries.is_empty() && flood_remotes.is_empty() {
+        return Ok(());
+    }
+
+    let mut filter = nispor::NetStateFilter::default();
+    filter.route = None;
+    let np_state = nispor::NetState::retrieve_with_filter(&filter)
+        .map_err(super::error::np_error_to_nmstate)?;
+
+    let mut reqs = Vec::new();
+    for (br_name, entry) in fdb_entries {
+        reqs.push(gen_fdb_request(br_name, entry, &np_state.ifaces)?);
+    }
+    for (iface_name, remote, is_absent) in flood_remotes {
+        let index = match np_state.ifaces.get(iface_name) {
+            Some(i) => i.index,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to find VXLAN interface {iface_name} for \
+                        setting flood remote {remote}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!(
+            "{} flood remote {remote} of VXLAN interface {iface_name}",
+            if is_absent { "Removing" } else { "Adding" },
+        );
+        reqs.push(gen_neighbour_request(
+            index,
+            vec![0u8; 6],
+            None,
+            Some(remote),
+            is_absent,
+        ));
+    }
+    run_netlink_requests(reqs, true, FDB_ACTION)?;
+    Ok(())
+}
+
+fn gen_fdb_request(
+    br_name: &str,
+    entry: &LinuxBridgeFdbEntry,
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) -> Result<NetlinkMessage<RouteNetlinkMessage>, NmstateError> {
+    let port_np_iface = match np_ifaces
+        .get(&entry.port)
+        .filter(|i| i.controller.as_deref() == Some(br_name))
+    {
+        Some(i) => i,
+        None => {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Port {} of FDB entry {} is not attached to linux \
+                    bridge {br_name}",
+                    entry.port, entry.mac_address
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    };
+    let is_vxlan = port_np_iface.iface_type == nispor::IfaceType::Vxlan;
+    if entry.remote.is_some() && !is_vxlan {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "The remote of FDB entry {} in linux bridge {br_name} is \
+                only valid for VXLAN interface, but port {} is not",
+                entry.mac_address, entry.port
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+
+    log::info!(
+        "{} FDB entry {} port {} of linux bridge {br_name}",
+        if entry.is_absent() {
+            "Removing"
+        } else {
+            "Adding"
+        },
+        entry.mac_address,
+        entry.port
+    );
+    Ok(gen_neighbour_request(
+        port_np_iface.index,
+        entry.mac_bytes().unwrap_or_default(),
+        entry.vlan,
+        entry
+            .remote
+            .as_deref()
+            .and_then(|r| IpAddr::from_str(r).ok()),
+        entry.is_absent(),
+    ))
+}
+
+// Entry with remote is stored in VXLAN interface itself, otherwise stored in
+// its bridge.
+fn gen_neighbour_request(
+    ifindex: u32,
+    mac: Vec<u8>,
+    vlan: Option<u16>,
+    remote: Option<IpAddr>,
+    is_absent: bool,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = NeighbourMessage::default();
+    nl_msg.header.family = AddressFamily::Bridge;
+    nl_msg.header.ifindex = ifindex;
+    if remote.is_some() {
+        nl_msg.header.flags = NeighbourFlags::Own;
+        nl_msg.header.state = NeighbourState::Permanent;
+    } else {
+        nl_msg.header.flags = NeighbourFlags::Controller;
+        nl_msg.header.state = NeighbourState::Noarp;
+    }
+    nl_msg
+        .attributes
+        .push(NeighbourAttribute::LinkLocalAddress(mac));
+    if let Some(vlan) = vlan {
+        nl_msg.attributes.push(NeighbourAttribute::Vlan(vlan));
+    }
+    if let Some(remote) = remote {
+        nl_msg
+            .attributes
+            .push(NeighbourAttribute::Destination(match remote {
+                IpAddr::V4(ip) => NeighbourAddress::Inet(ip),
+                IpAddr::V6(ip) => NeighbourAddress::Inet6(ip),
+            }));
+    }
+
+    if is_absent {
+        let mut req =
+            NetlinkMessage::from(RouteNetlinkMessage::DelNeighbour(nl_msg));
+        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req
+    } else {
+        let mut req =
+            NetlinkMessage::from(RouteNetlinkMessage::NewNeighbour(nl_msg));
+        // VXLAN could hold multiple remotes for the same MAC address
+        req.header.flags = NLM_F_REQUEST
+            | NLM_F_ACK
+            | NLM_F_CREATE
+            | if remote.is_some() {
+                NLM_F_APPEND
+            } else {
+                NLM_F_REPLACE
+            };
+        req
+    }
+}
@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+use nix::libc;
+
+use super::ethtool::ethtool_ioctl;
+use crate::{
+    ifaces::{flow_rule_ip_to_string, parse_flow_rule_ip},
+    ErrorKind, EthtoolConfig, EthtoolFlowAction, EthtoolFlowRule,
+    EthtoolFlowType, Interfaces, MergedNetworkState, NmstateError,
+};
+
+const ETHTOOL_GRXCLSRLCNT: u32 = 0x2e;
+const ETHTOOL_GRXCLSRULE: u32 = 0x2f;
+const ETHTOOL_GRXCLSRLALL: u32 = 0x30;
+const ETHTOOL_SRXCLSRLDEL: u32 = 0x31;
+const ETHTOOL_SRXCLSRLINS: u32 = 0x32;
+
+const TCP_V4_FLOW: u32 = 0x01;
+const UDP_V4_FLOW: u32 = 0x02;
+const SCTP_V4_FLOW: u32 = 0x03;
+const TCP_V6_FLOW: u32 = 0x05;
+const UDP_V6_FLOW: u32 = 0x06;
+const SCTP_V6_FLOW: u32 = 0x07;
+const FLOW_EXT: u32 = 0x8000_0000;
+const FLOW_MAC_EXT: u32 = 0x4000_0000;
+const FLOW_RSS: u32 = 0x2000_0000;
+
+const RX_CLS_FLOW_DISC: u64 = u64::MAX;
+const ETHTOOL_RX_FLOW_SPEC_RING: u64 = 0xffff_ffff;
+
+const ETHTOOL_FLOW_UNION_SIZE: usize = 52;
+
+// Mirror of kernel structure, not all members are used by nmstate
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct EthtoolFlowExt {
+    padding: [u8; 2],
+    h_dest: [u8; 6],
+    vlan_etype: u16,
+    vlan_tci: u16,
+    data: [u32; 2],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct EthtoolRxFlowSpec {
+    flow_type: u32,
+    // Both `ethtool_tcpip4_spec` and `ethtool_tcpip6_spec` are stored in
+    // network byte order.
+    h_u: [u8; ETHTOOL_FLOW_UNION_SIZE],
+    h_ext: EthtoolFlowExt,
+    m_u: [u8; ETHTOOL_FLOW_UNION_SIZE],
+    m_ext: EthtoolFlowExt,
+    ring_cookie: u64,
+    location: u32,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct EthtoolRxnfc {
+    cmd: u32,
+    flow_type: u32,
+    data: u64,
+    fs: EthtoolRxFlowSpec,
+    rule_cnt: u32,
+    // Followed by `rule_locs` array for ETHTOOL_GRXCLSRLALL
+}
+
+// The `rule_locs` is placed right after `rule_cnt` without padding.
+const RULE_LOCS_OFFSET: usize = std::mem::size_of::<u32>() * 2
+    + std::mem::size_of::<u64>()
+    + std::mem::size_of::<EthtoolRxFlowSpec>()
+    + std::mem::size_of::<u32>();
+
+impl EthtoolRxnfc {
+    fn new(cmd: u32) -> Self {
+        // Safe: all members are plain integers
+        let mut nfc: Self = unsafe { std::mem::zeroed() };
+        nfc.cmd = cmd;
+        nfc
+    }
+}
+
+// Nispor, NetworkManager and ethtool generic netlink do not support ntuple
+// flow steering rules yet, hence we use the legacy SIOCETHTOOL ioctl.
+// Interfaces not supporting flow rules will have `flow_rules: None`.
+pub(crate) fn append_flow_rules_conf(ifaces: &mut Interfaces) {
+    for iface in ifaces
+        .kernel_ifaces
+        .values_mut()
+        .filter(|i| i.base_iface().ethtool.is_some())
+    {
+        let iface_name = iface.name().to_string();
+        match get_flow_rules(iface_name.as_str()) {
+            Ok(mut rules) => {
+                rules.sort_unstable_by_key(|r| r.id);
+                iface
+                    .base_iface_mut()
+                    .ethtool
+                    .get_or_insert_with(EthtoolConfig::new)
+                    .flow_rules = Some(rules);
+            }
+            Err(e) => {
+                log::debug!(
+                    "Ethtool flow rules not supported by interface \
+                    {iface_name}: {e}"
+                );
+            }
+        }
+    }
+}
+
+// Neither nispor nor NetworkManager support ethtool flow rules, hence both
+// backends apply them after interfaces activated. The `flow-rules` is the
+// full list of rules, rules not mentioned are removed.
+pub(crate) fn nispor_apply_flow_rules(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i.base_iface(),
+            _ => continue,
+        };
+        let rules = match apply_iface
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.flow_rules.as_ref())
+        {
+            Some(r) => r,
+            None => continue,
+        };
+        let cur_rules = merged_iface
+            .current
+            .as_ref()
+            .and_then(|i| i.base_iface().ethtool.as_ref())
+            .and_then(|e| e.flow_rules.as_deref())
+            .unwrap_or_default();
+        let iface_name = apply_iface.name.as_str();
+        for cur_rule in cur_rules.iter().filter(|r| !rules.contains(r)) {
+            log::info!(
+                "Removing ethtool flow rule {} from interface {iface_name}",
+                cur_rule.id
+            );
+            let mut nfc = EthtoolRxnfc::new(ETHTOOL_SRXCLSRLDEL);
+            nfc.fs.location = cur_rule.id;
+            ethtool_ioctl(iface_name, &mut nfc as *mut _ as *mut libc::c_void)
+                .map_err(|e| flow_rule_error(iface_name, cur_rule.id, e))?;
+        }
+        for rule in rules.iter().filter(|r| !cur_rules.contains(r)) {
+            log::info!(
+                "Adding ethtool flow rule {} to interface {iface_name}",
+                rule.id
+            );
+            let mut nfc = EthtoolRxnfc::new(ETHTOOL_SRXCLSRLINS);
+            nfc.fs = gen_flow_spec(rule);
+            ethtool_ioctl(iface_name, &mut nfc as *mut _ as *mut libc::c_void)
+                .map_err(|e| flow_rule_error(iface_name, rule.id, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn flow_rule_error(
+    iface_name: &str,
+    id: u32,
+    error: std::io::Error,
+) -> NmstateError {
+    let e = NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!(
+            "Failed to change ethtool flow rule {id} of interface \
+            {iface_name}: {error}"
+        ),
+    );
+    log::error!("{}", e);
+    e
+}
+
+fn get_flow_rules(
+    iface_name: &str,
+) -> Result<Vec<EthtoolFlowRule>, std::io::Error> {
+    let mut nfc = EthtoolRxnfc::new(ETHTOOL_GRXCLSRLCNT);
+    ethtool_ioctl(iface_name, &mut nfc as *mut _ as *mut libc::c_void)?;
+    let count = nfc.rule_cnt as usize;
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Use u64 buffer to meet the alignment of `struct ethtool_rxnfc`
+    let buffer_size = std::mem::size_of::<EthtoolRxnfc>()
+        + count * std::mem::size_of::<u32>();
+    let mut buffer = vec![0u64; (buffer_size + 7) / 8];
+    let nfc_ptr = buffer.as_mut_ptr() as *mut EthtoolRxnfc;
+    // Safe: the buffer is zeroed and big enough for `EthtoolRxnfc`
+    unsafe {
+        (*nfc_ptr).cmd = ETHTOOL_GRXCLSRLALL;
+        (*nfc_ptr).rule_cnt = count as u32;
+    }
+    ethtool_ioctl(iface_name, nfc_ptr as *mut libc::c_void)?;
+    // Safe: kernel never return more rules than `rule_cnt` we requested
+    let locations: Vec<u32> = unsafe {
+        let count = ((*nfc_ptr).rule_cnt as usize).min(count);
+        std::slice::from_raw_parts(
+            (buffer.as_ptr() as *const u8).add(RULE_LOCS_OFFSET) as *const u32,
+            count,
+        )
+        .to_vec()
+    };
+
+    let mut rules = Vec::new();
+    for location in locations {
+        let mut nfc = EthtoolRxnfc::new(ETHTOOL_GRXCLSRULE);
+        nfc.fs.location = location;
+        ethtool_ioctl(iface_name, &mut nfc as *mut _ as *mut libc::c_void)?;
+        match parse_flow_spec(&nfc.fs) {
+            Some(rule) => rules.push(rule),
+            None => log::debug!(
+                "Ignoring unsupported ethtool flow rule {location} of \
+                interface {iface_name}"
+            ),
+        }
+    }
+    Ok(rules)
+}
+
+// Return (ip_length, port_offset) of `ethtool_tcpip4_spec` or
+// `ethtool_tcpip6_spec`.
+fn flow_spec_layout(flow_type: EthtoolFlowType) -> (usize, usize) {
+    if flow_type.is_ipv6() {
+        (16, 32)
+    } else {
+        (4, 8)
+    }
+}
+
+fn parse_flow_spec(fs: &EthtoolRxFlowSpec) -> Option<EthtoolFlowRule> {
+    let flow_type = match fs.flow_type & !(FLOW_EXT | FLOW_MAC_EXT | FLOW_RSS) {
+        TCP_V4_FLOW => EthtoolFlowType::Tcp4,
+        UDP_V4_FLOW => EthtoolFlowType::Udp4,
+        SCTP_V4_FLOW => EthtoolFlowType::Sctp4,
+        TCP_V6_FLOW => EthtoolFlowType::Tcp6,
+        UDP_V6_FLOW => EthtoolFlowType::Udp6,
+        SCTP_V6_FLOW => EthtoolFlowType::Sctp6,
+        _ => return None,
+    };
+    let mut rule = if fs.ring_cookie == RX_CLS_FLOW_DISC {
+        EthtoolFlowRule::new(fs.location, flow_type, EthtoolFlowAction::Drop)
+    } else {
+        let mut rule = EthtoolFlowRule::new(
+            fs.location,
+            flow_type,
+            EthtoolFlowAction::Queue,
+        );
+        rule.queue = Some((fs.ring_cookie & ETHTOOL_RX_FLOW_SPEC_RING) as u32);
+        rule
+    };
+    let (ip_len, port_offset) = flow_spec_layout(flow_type);
+    rule.src_ip = parse_ip(&fs.h_u[..ip_len], &fs.m_u[..ip_len]);
+    rule.dst_ip =
+        parse_ip(&fs.h_u[ip_len..ip_len * 2], &fs.m_u[ip_len..ip_len * 2]);
+    rule.src_port = parse_port(
+        &fs.h_u[port_offset..port_offset + 2],
+        &fs.m_u[port_offset..port_offset + 2],
+    );
+    rule.dst_port = parse_port(
+        &fs.h_u[port_offset + 2..port_offset + 4],
+        &fs.m_u[port_offset + 2..port_offset + 4],
+    );
+    Some(rule)
+}
+
+// Bits set in mask means match, empty mask means wildcard.
+fn parse_ip(value: &[u8], mask: &[u8]) -> Option<String> {
+    let prefix: u32 = mask.iter().map(|b| b.count_ones()).sum();
+    if prefix == 0 {
+        return None;
+    }
+    let mut octets = [0u8; 16];
+    octets[..value.len()].copy_from_slice(value);
+    let addr = if value.len() == 16 {
+        IpAddr::from(octets)
+    } else {
+        IpAddr::from([octets[0], octets[1], octets[2], octets[3]])
+    };
+    Some(flow_rule_ip_to_string(addr, prefix as u8))
+}
+
+fn parse_port(value: &[u8], mask: &[u8]) -> Option<u16> {
+    if mask.iter().all(|b| *b == 0) {
+        None
+    } else {
+        Some(u16::from_be_bytes([value[0], value[1]]))
+    }
+}
+
+fn ip_octets(addr: IpAddr) -> Vec<u8> {
+    match addr {
+        IpAddr::V4(a) => a.octets().to_vec(),
+        IpAddr::V6(a) => a.octets().to_vec(),
+    }
+}
+
+// The IP addresses have been validated by `EthtoolFlowRule::sanitize()`.
+fn gen_flow_spec(rule: &EthtoolFlowRule) -> EthtoolRxFlowSpec {
+    let mut fs = EthtoolRxnfc::new(0).fs;
+    fs.flow_type = match rule.flow_type {
+        EthtoolFlowType::Tcp4 => TCP_V4_FLOW,
+        EthtoolFlowType::Udp4 => UDP_V4_FLOW,
+        EthtoolFlowType::Sctp4 => SCTP_V4_FLOW,
+        EthtoolFlowType::Tcp6 => TCP_V6_FLOW,
+        EthtoolFlowType::Udp6 => UDP_V6_FLOW,
+        EthtoolFlowType::Sctp6 => SCTP_V6_FLOW,
+    };
+    fs.location = rule.id;
+    fs.ring_cookie = match rule.action {
+        EthtoolFlowAction::Drop => RX_CLS_FLOW_DISC,
+        EthtoolFlowAction::Queue => rule.queue.unwrap_or_default().into(),
+    };
+    let (ip_len, port_offset) = flow_spec_layout(rule.flow_type);
+    for (ip, offset) in [
+        (rule.src_ip.as_deref(), 0),
+        (rule.dst_ip.as_deref(), ip_len),
+    ] {
+        if let Some((addr, prefix)) = ip.and_then(parse_flow_rule_ip) {
+            let mask = match IpNet::new(addr, prefix) {
+                Ok(n) => n.netmask(),
+                Err(_) => continue,
+            };
+            fs.h_u[offset..offset + ip_len]
+                .copy_from_slice(ip_octets(addr).as_slice());
+            fs.m_u[offset..offset + ip_len]
+                .copy_from_slice(ip_octets(mask).as_slice());
+        }
+    }
+    for (port, offset) in [
+        (rule.src_port, port_offset),
+        (rule.dst_port, port_offset + 2),
+    ] {
+        if let Some(port) = port {
+            fs.h_u[offset..offset + 2].copy_from_slice(&port.to_be_bytes());
+            fs.m_u[offset..offset + 2].copy_from_slice(&[0xff, 0xff]);
+        }
+    }
+    fs
+}
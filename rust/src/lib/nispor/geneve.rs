@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use rtnetlink::packet_route::link::{InfoData, InfoGeneve};
+
+use super::netlink::get_links;
+use crate::{GeneveConfig, Interface, InterfaceType, Interfaces, NmstateError};
+
+// Nispor does not provide Geneve configuration yet.
+pub(crate) fn append_geneve_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Geneve)
+    {
+        return Ok(());
+    }
+    for link in get_links("query Geneve interfaces")? {
+        if let (Some(InfoData::Geneve(nlas)), Some(Interface::Geneve(iface))) =
+            (link.data.as_ref(), ifaces.kernel_ifaces.get_mut(&link.name))
+        {
+            iface.geneve = Some(np_geneve_nlas_to_nmstate(nlas));
+        }
+    }
+    Ok(())
+}
+
+fn np_geneve_nlas_to_nmstate(nlas: &[InfoGeneve]) -> GeneveConfig {
+    let mut conf = GeneveConfig::new();
+    for nla in nlas {
+        match nla {
+            InfoGeneve::Id(v) => conf.id = *v,
+            InfoGeneve::Remote(v) => conf.remote = Some(IpAddr::V4(*v)),
+            InfoGeneve::Remote6(v) => conf.remote = Some(IpAddr::V6(*v)),
+            InfoGeneve::Port(v) => conf.dst_port = Some(*v),
+            InfoGeneve::Ttl(v) => conf.ttl = Some(*v),
+            InfoGeneve::Tos(v) => conf.tos = Some(*v),
+            _ => (),
+        }
+    }
+    conf
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use rtnetlink::{
+    packet_core::{DefaultNla, NetlinkMessage, Nla},
+    packet_route::{
+        link::{InfoData, InfoGtp, InfoKind, LinkInfo},
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{
+    gen_link_del_request, gen_link_new_request, get_links, run_netlink_requests,
+};
+use crate::{
+    GtpConfig, GtpInterface, GtpRole, Interface, InterfaceType, Interfaces,
+    MergedNetworkState, NmstateError,
+};
+
+const GTP_ACTION: &str = "query or change GTP interface";
+
+const IFLA_GTP_ROLE: u16 = 4;
+const IFLA_GTP_CREATE_SOCKETS: u16 = 5;
+const IFLA_GTP_LOCAL: u16 = 7;
+const IFLA_GTP_LOCAL6: u16 = 8;
+
+const GTP_ROLE_SGSN: u32 = 1;
+
+// Nispor does not support GTP interface yet.
+pub(crate) fn append_gtp_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Gtp)
+    {
+        return Ok(());
+    }
+    for link in get_links(GTP_ACTION)? {
+        if let (Some(InfoData::Gtp(nlas)), Some(Interface::Gtp(gtp_iface))) =
+            (link.data.as_ref(), ifaces.kernel_ifaces.get_mut(&link.name))
+        {
+            gtp_iface.gtp = Some(parse_gtp_nlas(nlas));
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support GTP interface, hence both backends create
+// the GTP interface via netlink before activating the profiles.
+// The removal of GTP interface is handled by backend as other virtual
+// interfaces.
+pub(crate) fn nispor_apply_gtp(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::Gtp(gtp_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            match merged_iface.current.as_ref() {
+                Some(Interface::Gtp(cur_iface)) => {
+                    if !gtp_iface.is_link_changed(cur_iface) {
+                        continue;
+                    }
+                    log::info!(
+                        "Recreating GTP interface {} as kernel does not \
+                        support changing its role or local address",
+                        gtp_iface.base.name
+                    );
+                    reqs.push(gen_link_del_request(&gtp_iface.base.name));
+                }
+                _ => {
+                    log::info!(
+                        "Creating GTP interface {}",
+                        gtp_iface.base.name
+                    );
+                }
+            }
+            reqs.push(gen_gtp_new_request(gtp_iface));
+        }
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_netlink_requests(reqs, true, GTP_ACTION)?;
+    Ok(())
+}
+
+fn parse_gtp_nlas(nlas: &[InfoGtp]) -> GtpConfig {
+    let mut conf = GtpConfig::new();
+    // The PDP contexts are never managed by nmstate
+    conf.external_teid = Some(true);
+    conf.role = Some(GtpRole::Ggsn);
+    for nla in nlas {
+        let mut value = vec![0u8; nla.value_len()];
+        nla.emit_value(&mut value);
+        match nla.kind() {
+            IFLA_GTP_ROLE
+                if <[u8; 4]>::try_from(value.as_slice())
+                    .ok()
+                    .map(u32::from_ne_bytes)
+                    == Some(GTP_ROLE_SGSN) =>
+            {
+                conf.role = Some(GtpRole::Sgsn);
+            }
+            IFLA_GTP_LOCAL => {
+                if let Ok(v) = <[u8; 4]>::try_from(value.as_slice()) {
+                    conf.local = Some(IpAddr::V4(Ipv4Addr::from(v)));
+                }
+            }
+            IFLA_GTP_LOCAL6 => {
+                if let Ok(v) = <[u8; 16]>::try_from(value.as_slice()) {
+                    conf.local = Some(IpAddr::V6(Ipv6Addr::from(v)));
+                }
+            }
+            _ => (),
+        }
+    }
+    conf
+}
+
+fn gen_gtp_new_request(
+    gtp_iface: &GtpInterface,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let conf = gtp_iface.gtp.clone().unwrap_or_default();
+    // Let kernel create the GTP-U UDP sockets instead of passing file
+    // descriptors
+    let mut nlas = vec![
+        InfoGtp::Other(DefaultNla::new(IFLA_GTP_CREATE_SOCKETS, vec![1u8])),
+        InfoGtp::Other(DefaultNla::new(
+            IFLA_GTP_ROLE,
+            u32::from(conf.role.unwrap_or_default())
+                .to_ne_bytes()
+                .to_vec(),
+        )),
+    ];
+    match conf.local {
+        Some(IpAddr::V4(addr)) => nlas.push(InfoGtp::Other(DefaultNla::new(
+            IFLA_GTP_LOCAL,
+            addr.octets().to_vec(),
+        ))),
+        Some(IpAddr::V6(addr)) => nlas.push(InfoGtp::Other(DefaultNla::new(
+            IFLA_GTP_LOCAL6,
+            addr.octets().to_vec(),
+        ))),
+        None => (),
+    }
+
+    gen_link_new_request(
+        &gtp_iface.base.name,
+        Vec::new(),
+        vec![
+            LinkInfo::Kind(InfoKind::Gtp),
+            LinkInfo::Data(InfoData::Gtp(nlas)),
+        ],
+        true,
+    )
+}
@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
 use std::str::FromStr;
 
 use crate::{
-    nispor::mptcp::get_mptcp_flags, InterfaceIpAddr, InterfaceIpv4,
-    InterfaceIpv6,
+    ip::is_ipv6_unicast_link_local, nispor::mptcp::get_mptcp_flags,
+    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6,
 };
 
 pub(crate) fn np_ipv4_to_nmstate(
@@ -21,7 +22,11 @@ pub(crate) fn np_ipv4_to_nmstate(
         }
         ip.enabled = true;
         let mut addresses = Vec::new();
-        for np_addr in &np_ip.addresses {
+        for np_addr in np_ip
+            .addresses
+            .iter()
+            .filter(|a| !is_multicast_addr(a.address.as_str()))
+        {
             if np_addr.valid_lft != "forever" {
                 ip.dhcp = Some(true);
                 ip.prop_list.push("dhcp");
@@ -79,7 +84,11 @@ pub(crate) fn np_ipv6_to_nmstate(
         }
 
         let mut addresses = Vec::new();
-        for np_addr in &np_ip.addresses {
+        for np_addr in np_ip
+            .addresses
+            .iter()
+            .filter(|a| !is_multicast_addr(a.address.as_str()))
+        {
             if np_addr.valid_lft != "forever" {
                 ip.autoconf = Some(true);
                 ip.prop_list.push("autoconf");
@@ -118,36 +127,100 @@ pub(crate) fn np_ipv6_to_nmstate(
     }
 }
 
+// The multicast addresses are static joined multicast groups, which are
+// stored in `BaseInterface.multicast_groups` instead of IP addresses.
+fn is_multicast_addr(address: &str) -> bool {
+    IpAddr::from_str(address)
+        .map(|i| i.is_multicast())
+        .unwrap_or_default()
+}
+
+pub(crate) fn np_multicast_groups_to_nmstate(
+    np_iface: &nispor::Iface,
+) -> Option<Vec<IpAddr>> {
+    let mut groups: Vec<IpAddr> = np_iface
+        .ipv4
+        .as_ref()
+        .map(|i| i.addresses.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|a| a.address.as_str())
+        .chain(
+            np_iface
+                .ipv6
+                .as_ref()
+                .map(|i| i.addresses.as_slice())
+                .unwrap_or_default()
+                .iter()
+                .map(|a| a.address.as_str()),
+        )
+        .filter_map(|a| IpAddr::from_str(a).ok())
+        .filter(|i| i.is_multicast())
+        .collect();
+    if groups.is_empty() {
+        None
+    } else {
+        groups.sort_unstable();
+        Some(groups)
+    }
+}
+
 pub(crate) fn nmstate_ipv4_to_np(
     nms_ipv4: Option<&InterfaceIpv4>,
 ) -> nispor::IpConf {
-    let mut np_ip_conf = nispor::IpConf::default();
-    if let Some(nms_ipv4) = nms_ipv4 {
-        for nms_addr in nms_ipv4.addresses.as_deref().unwrap_or_default() {
-            np_ip_conf.addresses.push({
-                let mut ip_conf = nispor::IpAddrConf::default();
-                ip_conf.address = nms_addr.ip.to_string();
-                ip_conf.prefix_len = nms_addr.prefix_length;
-                ip_conf
-            });
-        }
-    }
-    np_ip_conf
+    nmstate_ip_addrs_to_np(
+        nms_ipv4
+            .and_then(|i| i.addresses.as_deref())
+            .unwrap_or_default(),
+    )
 }
 
 pub(crate) fn nmstate_ipv6_to_np(
     nms_ipv6: Option<&InterfaceIpv6>,
+) -> nispor::IpConf {
+    nmstate_ip_addrs_to_np(
+        nms_ipv6
+            .and_then(|i| i.addresses.as_deref())
+            .unwrap_or_default(),
+    )
+}
+
+pub(crate) fn nmstate_ip_addrs_to_np(
+    nms_addrs: &[InterfaceIpAddr],
 ) -> nispor::IpConf {
     let mut np_ip_conf = nispor::IpConf::default();
-    if let Some(nms_ipv6) = nms_ipv6 {
-        for nms_addr in nms_ipv6.addresses.as_deref().unwrap_or_default() {
-            np_ip_conf.addresses.push({
-                let mut ip_conf = nispor::IpAddrConf::default();
-                ip_conf.address = nms_addr.ip.to_string();
-                ip_conf.prefix_len = nms_addr.prefix_length;
-                ip_conf
-            });
-        }
+    for nms_addr in nms_addrs {
+        np_ip_conf.addresses.push({
+            let mut ip_conf = nispor::IpAddrConf::default();
+            ip_conf.address = nms_addr.ip.to_string();
+            ip_conf.prefix_len = nms_addr.prefix_length;
+            ip_conf
+        });
     }
     np_ip_conf
 }
+
+// Remove current IP addresses not found in desired, IPv6 link-local
+// addresses are managed by kernel, hence untouched.
+pub(crate) fn remove_extra_np_ip_addrs(
+    np_ip_conf: &mut nispor::IpConf,
+    des_addrs: &[InterfaceIpAddr],
+    cur_addrs: &[InterfaceIpAddr],
+) {
+    for cur_addr in cur_addrs {
+        if let IpAddr::V6(ip) = cur_addr.ip {
+            if is_ipv6_unicast_link_local(&ip) {
+                continue;
+            }
+        }
+        if !des_addrs.iter().any(|a| {
+            a.ip == cur_addr.ip && a.prefix_length == cur_addr.prefix_length
+        }) {
+            let mut ip_conf = nispor::IpAddrConf::default();
+            ip_conf.remove = true;
+            ip_conf.address = cur_addr.ip.to_string();
+            ip_conf.prefix_len = cur_addr.prefix_length;
+            np_ip_conf.addresses.push(ip_conf);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use rtnetlink::{
+    packet_core::{DefaultNla, NetlinkMessage, Nla},
+    packet_route::{
+        link::{
+            InfoData, InfoKind, InfoSitTun, LinkAttribute, LinkInfo,
+            LinkMessage,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{gen_link_set_request, get_links, run_netlink_requests};
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, IpTunnelConfig,
+    MergedNetworkState, NmstateError,
+};
+
+const SIT_6RD_ACTION: &str = "query or change SIT 6rd prefix";
+
+const IFLA_IPTUN_6RD_PREFIX: u16 = 13;
+const IFLA_IPTUN_6RD_PREFIXLEN: u16 = 15;
+
+// Nispor does not support 6rd prefix of SIT interface yet.
+pub(crate) fn append_sit_6rd_prefix(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Sit)
+    {
+        return Ok(());
+    }
+    for (name, (_, nlas)) in get_sit_nlas()? {
+        if let Some(Interface::Sit(sit_iface)) =
+            ifaces.kernel_ifaces.get_mut(&name)
+        {
+            if let Some(prefix) = parse_6rd_prefix(&nlas) {
+                sit_iface
+                    .sit
+                    .get_or_insert_with(IpTunnelConfig::new)
+                    .sixrd_prefix = Some(prefix);
+            }
+        }
+    }
+    Ok(())
+}
+
+// The kernel resets all the tunnel parameters which are not included in the
+// RTM_NEWLINK request, hence we resend the existing parameters along with the
+// new 6rd prefix. Should be invoked after SIT interfaces activated.
+pub(crate) fn nispor_apply_sit_6rd_prefix(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut changes: Vec<(&str, &str)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::Sit(sit_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            let des_prefix = match sit_iface
+                .sit
+                .as_ref()
+                .and_then(|s| s.sixrd_prefix.as_deref())
+            {
+                Some(p) => p,
+                None => continue,
+            };
+            let cur_prefix = match merged_iface.current.as_ref() {
+                Some(Interface::Sit(cur_iface)) => cur_iface
+                    .sit
+                    .as_ref()
+                    .and_then(|s| s.sixrd_prefix.as_deref()),
+                _ => None,
+            };
+            if cur_prefix != Some(des_prefix) {
+                changes.push((sit_iface.base.name.as_str(), des_prefix));
+            }
+        }
+    }
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut sit_nlas = get_sit_nlas()?;
+    let mut reqs = Vec::new();
+    for (iface_name, prefix) in changes {
+        let (index, nlas) = match sit_nlas.remove(iface_name) {
+            Some(n) => n,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to find SIT interface {iface_name} for \
+                        setting 6rd prefix {prefix}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!("Setting 6rd prefix {prefix} of SIT interface {iface_name}");
+        reqs.push(gen_sit_6rd_request(index, nlas, prefix)?);
+    }
+    run_netlink_requests(reqs, true, SIT_6RD_ACTION)?;
+    Ok(())
+}
+
+// Return interface name to tuple of interface index and SIT netlink
+// attributes.
+fn get_sit_nlas(
+) -> Result<HashMap<String, (u32, Vec<InfoSitTun>)>, NmstateError> {
+    Ok(get_links(SIT_6RD_ACTION)?
+        .into_iter()
+        .filter_map(|link| match link.data {
+            Some(InfoData::SitTun(nlas)) => {
+                Some((link.name, (link.index, nlas)))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+fn parse_6rd_prefix(nlas: &[InfoSitTun]) -> Option<String> {
+    let mut addr = None;
+    let mut prefix_len = None;
+    for nla in nlas {
+        let mut value = vec![0u8; nla.value_len()];
+        nla.emit_value(&mut value);
+        match nla.kind() {
+            IFLA_IPTUN_6RD_PREFIX => {
+                addr = <[u8; 16]>::try_from(value.as_slice())
+                    .ok()
+                    .map(Ipv6Addr::from);
+            }
+            IFLA_IPTUN_6RD_PREFIXLEN => {
+                prefix_len = <[u8; 2]>::try_from(value.as_slice())
+                    .ok()
+                    .map(u16::from_ne_bytes);
+            }
+            _ => (),
+        }
+    }
+    match (addr, prefix_len) {
+        (Some(addr), Some(prefix_len)) if prefix_len != 0 => {
+            Some(format!("{addr}/{prefix_len}"))
+        }
+        _ => None,
+    }
+}
+
+fn gen_sit_6rd_request(
+    ifindex: u32,
+    cur_nlas: Vec<InfoSitTun>,
+    prefix: &str,
+) -> Result<NetlinkMessage<RouteNetlinkMessage>, NmstateError> {
+    let prefix = match ipnet::Ipv6Net::from_str(prefix) {
+        Ok(p) => p,
+        Err(e) => {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid 6rd prefix {prefix}: {e}"),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    };
+    let mut nlas: Vec<InfoSitTun> = cur_nlas
+        .into_iter()
+        .filter(|n| {
+            n.kind() != IFLA_IPTUN_6RD_PREFIX
+                && n.kind() != IFLA_IPTUN_6RD_PREFIXLEN
+        })
+        .collect();
+    nlas.push(InfoSitTun::Other(DefaultNla::new(
+        IFLA_IPTUN_6RD_PREFIX,
+        prefix.addr().octets().to_vec(),
+    )));
+    nlas.push(InfoSitTun::Other(DefaultNla::new(
+        IFLA_IPTUN_6RD_PREFIXLEN,
+        u16::from(prefix.prefix_len()).to_ne_bytes().to_vec(),
+    )));
+
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = ifindex;
+    nl_msg.attributes.push(LinkAttribute::LinkInfo(vec![
+        LinkInfo::Kind(InfoKind::SitTun),
+        LinkInfo::Data(InfoData::SitTun(nlas)),
+    ]));
+    Ok(gen_link_set_request(nl_msg))
+}
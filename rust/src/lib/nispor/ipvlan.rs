@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use rtnetlink::{
+    packet_core::NetlinkMessage,
+    packet_route::{
+        link::{
+            InfoData, InfoIpVlan, InfoKind, IpVlanFlags,
+            IpVlanMode as NlIpVlanMode, LinkAttribute, LinkInfo,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{
+    gen_link_del_request, gen_link_new_request, get_links, run_netlink_requests,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, IpVlanConfig,
+    IpVlanInterface, IpVlanMode, MergedNetworkState, NmstateError,
+};
+
+const IPVLAN_ACTION: &str = "query or change IP VLAN interface";
+
+// Nispor does not support IP VLAN interface yet.
+pub(crate) fn append_ipvlan_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::IpVlan)
+    {
+        return Ok(());
+    }
+    let links = get_links(IPVLAN_ACTION)?;
+    let index_to_name: HashMap<u32, &str> =
+        links.iter().map(|l| (l.index, l.name.as_str())).collect();
+    for link in links.iter() {
+        if let (
+            Some(InfoData::IpVlan(nlas)),
+            Some(Interface::IpVlan(ipvlan_iface)),
+        ) = (link.data.as_ref(), ifaces.kernel_ifaces.get_mut(&link.name))
+        {
+            let mut conf = IpVlanConfig::default();
+            if let Some(parent) = link.link.and_then(|i| index_to_name.get(&i))
+            {
+                conf.base_iface = parent.to_string();
+            }
+            // Kernel always reports both mode and flags
+            for nla in nlas {
+                match nla {
+                    InfoIpVlan::Mode(m) => conf.mode = np_mode_to_nmstate(m),
+                    InfoIpVlan::Flags(f) => {
+                        conf.private = Some(f.contains(IpVlanFlags::Private));
+                        conf.vepa = Some(f.contains(IpVlanFlags::Vepa));
+                    }
+                    _ => (),
+                }
+            }
+            ipvlan_iface.ipvlan = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// Only used by kernel mode, NetworkManager backend is using the `ipvlan`
+// setting instead.
+// Kernel cannot change the mode of existing IP VLAN interface, hence we
+// delete the interface first when mode changed.
+pub(crate) fn nispor_apply_ipvlan(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut ipvlan_ifaces: Vec<(&IpVlanInterface, bool)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::IpVlan(apply_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            let mode_changed = match (
+                apply_iface.ipvlan.as_ref(),
+                merged_iface.current.as_ref(),
+            ) {
+                (Some(conf), Some(Interface::IpVlan(cur_iface))) => {
+                    cur_iface.ipvlan.as_ref().map(|c| c.mode) != Some(conf.mode)
+                }
+                _ => false,
+            };
+            ipvlan_ifaces.push((apply_iface, mode_changed));
+        }
+    }
+    if ipvlan_ifaces.is_empty() {
+        return Ok(());
+    }
+    let name_to_index: HashMap<String, u32> = get_links(IPVLAN_ACTION)?
+        .into_iter()
+        .map(|l| (l.name, l.index))
+        .collect();
+
+    let mut reqs = Vec::new();
+    for (ipvlan_iface, mode_changed) in ipvlan_ifaces {
+        let conf = match ipvlan_iface.ipvlan.as_ref() {
+            Some(c) => c,
+            None => continue,
+        };
+        let parent_index = match name_to_index.get(&conf.base_iface) {
+            Some(i) => *i,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The base interface {} of IP VLAN interface {} \
+                        does not exist",
+                        conf.base_iface, ipvlan_iface.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        if mode_changed {
+            log::info!(
+                "Deleting IP VLAN interface {} for changing mode",
+                ipvlan_iface.base.name
+            );
+            reqs.push(gen_link_del_request(ipvlan_iface.base.name.as_str()));
+        }
+        log::info!(
+            "Creating or modifying IP VLAN interface {}",
+            ipvlan_iface.base.name
+        );
+        reqs.push(gen_ipvlan_new_request(ipvlan_iface, conf, parent_index));
+    }
+    run_netlink_requests(reqs, true, IPVLAN_ACTION)?;
+    Ok(())
+}
+
+fn np_mode_to_nmstate(mode: &NlIpVlanMode) -> IpVlanMode {
+    match mode {
+        NlIpVlanMode::L2 => IpVlanMode::L2,
+        NlIpVlanMode::L3 => IpVlanMode::L3,
+        NlIpVlanMode::L3S => IpVlanMode::L3s,
+        _ => {
+            log::warn!("Unknown supported IP VLAN mode {:?}", mode);
+            IpVlanMode::Unknown
+        }
+    }
+}
+
+fn nmstate_mode_to_np(mode: IpVlanMode) -> NlIpVlanMode {
+    match mode {
+        IpVlanMode::L2 => NlIpVlanMode::L2,
+        IpVlanMode::L3s => NlIpVlanMode::L3S,
+        // Kernel default mode is L3
+        _ => NlIpVlanMode::L3,
+    }
+}
+
+// Without NLM_F_EXCL, kernel will modify the flags of existing IP VLAN
+// interface.
+fn gen_ipvlan_new_request(
+    ipvlan_iface: &IpVlanInterface,
+    conf: &IpVlanConfig,
+    parent_index: u32,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut flags = IpVlanFlags::empty();
+    if conf.private == Some(true) {
+        flags |= IpVlanFlags::Private;
+    }
+    if conf.vepa == Some(true) {
+        flags |= IpVlanFlags::Vepa;
+    }
+
+    gen_link_new_request(
+        &ipvlan_iface.base.name,
+        vec![LinkAttribute::Link(parent_index)],
+        vec![
+            LinkInfo::Kind(InfoKind::IpVlan),
+            LinkInfo::Data(InfoData::IpVlan(vec![
+                InfoIpVlan::Mode(nmstate_mode_to_np(conf.mode)),
+                InfoIpVlan::Flags(flags),
+            ])),
+        ],
+        false,
+    )
+}
@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use rtnetlink::{
+    packet_core::{DefaultNla, NetlinkMessage, NLM_F_ACK, NLM_F_DUMP},
+    packet_route::RouteNetlinkMessage,
+};
+
+use super::netlink::{
+    gen_link_up_request, run_genl_requests, run_netlink_requests,
+    GenlRawMessage,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, L2tpv3Config, L2tpv3Encap,
+    MergedNetworkState, NmstateError,
+};
+
+const L2TP_ACTION: &str = "query or change L2TPv3 pseudowire";
+
+const L2TP_GENL_NAME: &str = "l2tp";
+const L2TP_GENL_VERSION: u8 = 1;
+
+const L2TP_CMD_TUNNEL_CREATE: u8 = 1;
+const L2TP_CMD_TUNNEL_DELETE: u8 = 2;
+const L2TP_CMD_TUNNEL_GET: u8 = 4;
+const L2TP_CMD_SESSION_CREATE: u8 = 5;
+const L2TP_CMD_SESSION_DELETE: u8 = 6;
+const L2TP_CMD_SESSION_GET: u8 = 8;
+
+const L2TP_ATTR_PW_TYPE: u16 = 1;
+const L2TP_ATTR_ENCAP_TYPE: u16 = 2;
+const L2TP_ATTR_PROTO_VERSION: u16 = 7;
+const L2TP_ATTR_IFNAME: u16 = 8;
+const L2TP_ATTR_CONN_ID: u16 = 9;
+const L2TP_ATTR_PEER_CONN_ID: u16 = 10;
+const L2TP_ATTR_SESSION_ID: u16 = 11;
+const L2TP_ATTR_PEER_SESSION_ID: u16 = 12;
+const L2TP_ATTR_IP_SADDR: u16 = 24;
+const L2TP_ATTR_IP_DADDR: u16 = 25;
+const L2TP_ATTR_UDP_SPORT: u16 = 26;
+const L2TP_ATTR_UDP_DPORT: u16 = 27;
+const L2TP_ATTR_IP6_SADDR: u16 = 31;
+const L2TP_ATTR_IP6_DADDR: u16 = 32;
+
+const L2TP_PWTYPE_ETH: u16 = 5;
+const L2TP_ENCAPTYPE_UDP: u16 = 0;
+const L2TP_ENCAPTYPE_IP: u16 = 1;
+const L2TP_PROTO_VERSION_3: u8 = 3;
+
+// Nispor reports L2TP ethernet pseudowire as ethernet interface, the
+// `DEVTYPE` in sysfs uevent is used to distinguish them.
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
+pub(crate) fn np_is_l2tp_eth(np_iface: &nispor::Iface) -> bool {
+    std::fs::read_to_string(format!(
+        "{SYSFS_NET_PATH}/{}/uevent",
+        np_iface.name
+    ))
+    .map(|c| c.lines().any(|l| l == "DEVTYPE=l2tpeth"))
+    .unwrap_or_default()
+}
+
+#[derive(Debug)]
+struct L2tpSession {
+    ifname: String,
+    tunnel_id: u32,
+    session_id: u32,
+    peer_session_id: Option<u32>,
+}
+
+// Nispor does not support L2TP yet.
+pub(crate) fn append_l2tpv3_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::L2tpv3)
+    {
+        return Ok(());
+    }
+    let tunnels = get_l2tp_tunnels()?;
+    for session in get_l2tp_sessions()? {
+        if let Some(Interface::L2tpv3(iface)) =
+            ifaces.kernel_ifaces.get_mut(&session.ifname)
+        {
+            let mut conf =
+                tunnels.get(&session.tunnel_id).cloned().unwrap_or_default();
+            conf.tunnel_id = Some(session.tunnel_id);
+            conf.session_id = Some(session.session_id);
+            conf.peer_session_id = session.peer_session_id;
+            iface.l2tpv3 = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support L2TPv3 pseudowire, hence both backends
+// create and remove the L2TP tunnels and sessions via generic netlink before
+// activating the profiles.
+// Kernel cannot modify the identifiers or addresses of existing tunnel or
+// session, hence we recreate them when changed. The tunnel is removed once
+// no session is using it.
+pub(crate) fn nispor_apply_l2tpv3(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut del_sessions: Vec<(&str, &L2tpv3Config)> = Vec::new();
+    let mut new_sessions: Vec<(&str, &L2tpv3Config)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let cur_conf = match merged_iface.current.as_ref() {
+            Some(Interface::L2tpv3(i)) => i.l2tpv3.as_ref(),
+            _ => None,
+        };
+        let des_conf =
+            match merged_iface.for_apply.as_ref().filter(|i| i.is_up()) {
+                Some(Interface::L2tpv3(i)) => i.l2tpv3.as_ref(),
+                _ => None,
+            };
+        if des_conf.is_some()
+            && des_conf.map(normalize) == cur_conf.map(normalize)
+        {
+            continue;
+        }
+        let iface_name = merged_iface.merged.name();
+        if let Some(cur_conf) = cur_conf {
+            del_sessions.push((iface_name, cur_conf));
+        }
+        if let Some(des_conf) = des_conf {
+            new_sessions.push((iface_name, des_conf));
+        }
+    }
+    if del_sessions.is_empty() && new_sessions.is_empty() {
+        return Ok(());
+    }
+
+    let mut cur_tunnels = get_l2tp_tunnels()?;
+    let mut tunnel_sessions: HashMap<u32, HashSet<u32>> = HashMap::new();
+    for session in get_l2tp_sessions()? {
+        tunnel_sessions
+            .entry(session.tunnel_id)
+            .or_default()
+            .insert(session.session_id);
+    }
+
+    let mut reqs = Vec::new();
+    for (iface_name, conf) in del_sessions {
+        let (tunnel_id, session_id) = match (conf.tunnel_id, conf.session_id) {
+            (Some(t), Some(s)) => (t, s),
+            _ => continue,
+        };
+        log::info!("Deleting L2TPv3 session of interface {iface_name}");
+        reqs.push(gen_session_del_request(tunnel_id, session_id));
+        if let Some(sessions) = tunnel_sessions.get_mut(&tunnel_id) {
+            sessions.remove(&session_id);
+        }
+    }
+    for (tunnel_id, sessions) in tunnel_sessions.iter() {
+        if sessions.is_empty() {
+            log::info!("Deleting unused L2TPv3 tunnel {tunnel_id}");
+            reqs.push(gen_tunnel_del_request(*tunnel_id));
+            cur_tunnels.remove(tunnel_id);
+        }
+    }
+
+    let mut new_tunnels: HashMap<u32, (&str, &L2tpv3Config)> = HashMap::new();
+    for (iface_name, conf) in new_sessions.as_slice() {
+        let tunnel_id = conf.tunnel_id.unwrap_or_default();
+        if let Some((other_name, other_conf)) = new_tunnels.get(&tunnel_id) {
+            if !is_same_tunnel(conf, other_conf) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "L2TPv3 interfaces {other_name} and {iface_name} are \
+                        using the same tunnel-id {tunnel_id} with different \
+                        tunnel properties"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            continue;
+        }
+        new_tunnels.insert(tunnel_id, (iface_name, conf));
+        match cur_tunnels.get(&tunnel_id) {
+            Some(cur_conf) if is_same_tunnel(conf, cur_conf) => (),
+            Some(_) => {
+                if tunnel_sessions
+                    .get(&tunnel_id)
+                    .map(|s| !s.is_empty())
+                    .unwrap_or_default()
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Cannot change the L2TPv3 tunnel {tunnel_id} of \
+                            interface {iface_name} as it is still used by \
+                            other sessions"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                log::info!(
+                    "Recreating L2TPv3 tunnel {tunnel_id} for interface \
+                    {iface_name}"
+                );
+                reqs.push(gen_tunnel_del_request(tunnel_id));
+                reqs.push(gen_tunnel_new_request(conf));
+            }
+            None => {
+                log::info!(
+                    "Creating L2TPv3 tunnel {tunnel_id} for interface \
+                    {iface_name}"
+                );
+                reqs.push(gen_tunnel_new_request(conf));
+            }
+        }
+    }
+    for (iface_name, conf) in new_sessions.as_slice() {
+        log::info!("Creating L2TPv3 session of interface {iface_name}");
+        reqs.push(gen_session_new_request(iface_name, conf));
+    }
+    run_genl_requests(L2TP_GENL_NAME, reqs, true, L2TP_ACTION)?;
+
+    // The kernel creates the pseudowire interface in down state
+    let link_reqs: Vec<NetlinkMessage<RouteNetlinkMessage>> = new_sessions
+        .iter()
+        .map(|(iface_name, _)| gen_link_up_request(iface_name))
+        .collect();
+    if !link_reqs.is_empty() {
+        run_netlink_requests(link_reqs, false, L2TP_ACTION)?;
+    }
+    Ok(())
+}
+
+fn normalize(conf: &L2tpv3Config) -> L2tpv3Config {
+    let mut conf = conf.clone();
+    conf.encap = Some(conf.encap());
+    conf
+}
+
+fn is_same_tunnel(conf: &L2tpv3Config, other: &L2tpv3Config) -> bool {
+    conf.peer_tunnel_id == other.peer_tunnel_id
+        && conf.encap() == other.encap()
+        && conf.local == other.local
+        && conf.remote == other.remote
+        && conf.udp_source_port == other.udp_source_port
+        && conf.udp_destination_port == other.udp_destination_port
+}
+
+fn get_l2tp_tunnels() -> Result<HashMap<u32, L2tpv3Config>, NmstateError> {
+    let req =
+        GenlRawMessage::new(L2TP_CMD_TUNNEL_GET, L2TP_GENL_VERSION, Vec::new())
+            .gen_request(NLM_F_DUMP);
+    let mut ret = HashMap::new();
+    for msg in run_genl_requests(L2TP_GENL_NAME, vec![req], false, L2TP_ACTION)?
+    {
+        let tunnel_id = match msg.get_nla_u32(L2TP_ATTR_CONN_ID) {
+            Some(i) => i,
+            None => continue,
+        };
+        let mut conf = L2tpv3Config::new();
+        conf.tunnel_id = Some(tunnel_id);
+        conf.peer_tunnel_id = msg.get_nla_u32(L2TP_ATTR_PEER_CONN_ID);
+        conf.encap = match msg.get_nla_u16(L2TP_ATTR_ENCAP_TYPE) {
+            Some(L2TP_ENCAPTYPE_IP) => Some(L2tpv3Encap::Ip),
+            _ => Some(L2tpv3Encap::Udp),
+        };
+        conf.local = get_nla_ip(&msg, L2TP_ATTR_IP_SADDR, L2TP_ATTR_IP6_SADDR);
+        conf.remote = get_nla_ip(&msg, L2TP_ATTR_IP_DADDR, L2TP_ATTR_IP6_DADDR);
+        if conf.encap == Some(L2tpv3Encap::Udp) {
+            conf.udp_source_port = msg.get_nla_u16(L2TP_ATTR_UDP_SPORT);
+            conf.udp_destination_port = msg.get_nla_u16(L2TP_ATTR_UDP_DPORT);
+        }
+        ret.insert(tunnel_id, conf);
+    }
+    Ok(ret)
+}
+
+fn get_l2tp_sessions() -> Result<Vec<L2tpSession>, NmstateError> {
+    let req = GenlRawMessage::new(
+        L2TP_CMD_SESSION_GET,
+        L2TP_GENL_VERSION,
+        Vec::new(),
+    )
+    .gen_request(NLM_F_DUMP);
+    let mut ret = Vec::new();
+    for msg in run_genl_requests(L2TP_GENL_NAME, vec![req], false, L2TP_ACTION)?
+    {
+        if let (Some(ifname), Some(tunnel_id), Some(session_id)) = (
+            msg.get_nla_string(L2TP_ATTR_IFNAME),
+            msg.get_nla_u32(L2TP_ATTR_CONN_ID),
+            msg.get_nla_u32(L2TP_ATTR_SESSION_ID),
+        ) {
+            ret.push(L2tpSession {
+                ifname,
+                tunnel_id,
+                session_id,
+                peer_session_id: msg.get_nla_u32(L2TP_ATTR_PEER_SESSION_ID),
+            });
+        }
+    }
+    Ok(ret)
+}
+
+fn get_nla_ip(
+    msg: &GenlRawMessage,
+    ipv4_kind: u16,
+    ipv6_kind: u16,
+) -> Option<IpAddr> {
+    if let Some(v) = msg
+        .get_nla_value(ipv4_kind)
+        .and_then(|v| <[u8; 4]>::try_from(v.as_slice()).ok())
+    {
+        Some(IpAddr::V4(Ipv4Addr::from(v)))
+    } else {
+        msg.get_nla_value(ipv6_kind)
+            .and_then(|v| <[u8; 16]>::try_from(v.as_slice()).ok())
+            .map(|v| IpAddr::V6(Ipv6Addr::from(v)))
+    }
+}
+
+fn gen_tunnel_new_request(
+    conf: &L2tpv3Config,
+) -> NetlinkMessage<GenlRawMessage> {
+    let mut nlas = vec![
+        DefaultNla::new(
+            L2TP_ATTR_CONN_ID,
+            conf.tunnel_id.unwrap_or_default().to_ne_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            L2TP_ATTR_PEER_CONN_ID,
+            conf.peer_tunnel_id
+                .unwrap_or_default()
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+        DefaultNla::new(L2TP_ATTR_PROTO_VERSION, vec![L2TP_PROTO_VERSION_3]),
+    ];
+    match conf.encap() {
+        L2tpv3Encap::Udp => {
+            nlas.push(DefaultNla::new(
+                L2TP_ATTR_ENCAP_TYPE,
+                L2TP_ENCAPTYPE_UDP.to_ne_bytes().to_vec(),
+            ));
+            nlas.push(DefaultNla::new(
+                L2TP_ATTR_UDP_SPORT,
+                conf.udp_source_port
+                    .unwrap_or_default()
+                    .to_ne_bytes()
+                    .to_vec(),
+            ));
+            nlas.push(DefaultNla::new(
+                L2TP_ATTR_UDP_DPORT,
+                conf.udp_destination_port
+                    .unwrap_or_default()
+                    .to_ne_bytes()
+                    .to_vec(),
+            ));
+        }
+        L2tpv3Encap::Ip => {
+            nlas.push(DefaultNla::new(
+                L2TP_ATTR_ENCAP_TYPE,
+                L2TP_ENCAPTYPE_IP.to_ne_bytes().to_vec(),
+            ));
+        }
+    }
+    for (addr, ipv4_kind, ipv6_kind) in [
+        (conf.local, L2TP_ATTR_IP_SADDR, L2TP_ATTR_IP6_SADDR),
+        (conf.remote, L2TP_ATTR_IP_DADDR, L2TP_ATTR_IP6_DADDR),
+    ] {
+        match addr {
+            Some(IpAddr::V4(a)) => {
+                nlas.push(DefaultNla::new(ipv4_kind, a.octets().to_vec()))
+            }
+            Some(IpAddr::V6(a)) => {
+                nlas.push(DefaultNla::new(ipv6_kind, a.octets().to_vec()))
+            }
+            None => (),
+        }
+    }
+    GenlRawMessage::new(L2TP_CMD_TUNNEL_CREATE, L2TP_GENL_VERSION, nlas)
+        .gen_request(NLM_F_ACK)
+}
+
+fn gen_tunnel_del_request(tunnel_id: u32) -> NetlinkMessage<GenlRawMessage> {
+    GenlRawMessage::new(
+        L2TP_CMD_TUNNEL_DELETE,
+        L2TP_GENL_VERSION,
+        vec![DefaultNla::new(
+            L2TP_ATTR_CONN_ID,
+            tunnel_id.to_ne_bytes().to_vec(),
+        )],
+    )
+    .gen_request(NLM_F_ACK)
+}
+
+fn gen_session_new_request(
+    iface_name: &str,
+    conf: &L2tpv3Config,
+) -> NetlinkMessage<GenlRawMessage> {
+    let nlas = vec![
+        DefaultNla::new(
+            L2TP_ATTR_CONN_ID,
+            conf.tunnel_id.unwrap_or_default().to_ne_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            L2TP_ATTR_SESSION_ID,
+            conf.session_id.unwrap_or_default().to_ne_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            L2TP_ATTR_PEER_SESSION_ID,
+            conf.peer_session_id
+                .unwrap_or_default()
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+        DefaultNla::new(
+            L2TP_ATTR_PW_TYPE,
+            L2TP_PWTYPE_ETH.to_ne_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            L2TP_ATTR_IFNAME,
+            format!("{iface_name}\0").into_bytes(),
+        ),
+    ];
+    GenlRawMessage::new(L2TP_CMD_SESSION_CREATE, L2TP_GENL_VERSION, nlas)
+        .gen_request(NLM_F_ACK)
+}
+
+fn gen_session_del_request(
+    tunnel_id: u32,
+    session_id: u32,
+) -> NetlinkMessage<GenlRawMessage> {
+    GenlRawMessage::new(
+        L2TP_CMD_SESSION_DELETE,
+        L2TP_GENL_VERSION,
+        vec![
+            DefaultNla::new(
+                L2TP_ATTR_CONN_ID,
+                tunnel_id.to_ne_bytes().to_vec(),
+            ),
+            DefaultNla::new(
+                L2TP_ATTR_SESSION_ID,
+                session_id.to_ne_bytes().to_vec(),
+            ),
+        ],
+    )
+    .gen_request(NLM_F_ACK)
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::LinkStats;
+
+// The carrier counters are identical to IFLA_CARRIER_CHANGES,
+// IFLA_CARRIER_UP_COUNT and IFLA_CARRIER_DOWN_COUNT of netlink which are not
+// provided by nispor yet.
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
+pub(crate) fn np_link_stats_to_nmstate(
+    np_iface: &nispor::Iface,
+) -> Option<LinkStats> {
+    let mut ret = LinkStats::new();
+    ret.carrier = Some(np_iface.flags.contains(&nispor::IfaceFlag::LowerUp));
+    ret.carrier_changes = read_sysfs_u32(&np_iface.name, "carrier_changes");
+    ret.carrier_up_count = read_sysfs_u32(&np_iface.name, "carrier_up_count");
+    ret.carrier_down_count =
+        read_sysfs_u32(&np_iface.name, "carrier_down_count");
+
+    if let Some(link_mode) =
+        np_iface.ethtool.as_ref().and_then(|e| e.link_mode.as_ref())
+    {
+        if link_mode.speed > 0 {
+            ret.negotiated_speed = Some(link_mode.speed);
+        }
+        ret.configured_speed = if link_mode.auto_negotiate {
+            link_mode
+                .ours
+                .iter()
+                .filter_map(|m| link_mode_to_speed(m.as_str()))
+                .max()
+        } else {
+            ret.negotiated_speed
+        };
+    }
+    if ret.is_empty() {
+        None
+    } else {
+        Some(ret)
+    }
+}
+
+// The ethtool link mode is in the format of `1000baseT/Full`.
+fn link_mode_to_speed(link_mode: &str) -> Option<u32> {
+    link_mode
+        .split_once("base")
+        .and_then(|(speed, _)| speed.parse::<u32>().ok())
+}
+
+fn read_sysfs_u32(iface_name: &str, prop: &str) -> Option<u32> {
+    let path = format!("{SYSFS_NET_PATH}/{iface_name}/{prop}");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => content.trim().parse::<u32>().ok(),
+        Err(e) => {
+            log::debug!("Failed to read {path}: {e}");
+            None
+        }
+    }
+}
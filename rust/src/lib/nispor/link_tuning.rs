@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use rtnetlink::packet_route::link::{LinkAttribute, LinkMessage};
+
+use super::netlink::{gen_link_set_request, run_netlink_requests};
+use crate::{BaseInterface, ErrorKind, MergedNetworkState, NmstateError};
+
+// Nispor does not provide TX queue length and NAPI tuning knobs yet, hence
+// we query them from sysfs.
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
+pub(crate) fn np_link_tuning_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: &mut BaseInterface,
+) {
+    base_iface.txqueuelen = read_sysfs(&np_iface.name, "tx_queue_len");
+    base_iface.gro_flush_timeout =
+        read_sysfs(&np_iface.name, "gro_flush_timeout");
+    base_iface.napi_defer_hard_irqs =
+        read_sysfs(&np_iface.name, "napi_defer_hard_irqs");
+}
+
+// Neither nispor nor NetworkManager support changing these knobs, hence both
+// backends apply them after interfaces activated: `txqueuelen` via rtnetlink,
+// `gro-flush-timeout` and `napi-defer-hard-irqs` via sysfs.
+pub(crate) fn nispor_apply_link_tuning(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    // Tuple of interface name and desired TX queue length
+    let mut txqueuelen_changes: Vec<(&str, u32)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i.base_iface(),
+            _ => continue,
+        };
+        let cur_iface = merged_iface.current.as_ref().map(|i| i.base_iface());
+
+        if let Some(des) = apply_iface.txqueuelen {
+            if cur_iface.and_then(|i| i.txqueuelen) != Some(des) {
+                txqueuelen_changes.push((apply_iface.name.as_str(), des));
+            }
+        }
+        if let Some(des) = apply_iface.gro_flush_timeout {
+            if cur_iface.and_then(|i| i.gro_flush_timeout) != Some(des) {
+                write_sysfs(&apply_iface.name, "gro_flush_timeout", des)?;
+            }
+        }
+        if let Some(des) = apply_iface.napi_defer_hard_irqs {
+            if cur_iface.and_then(|i| i.napi_defer_hard_irqs) != Some(des) {
+                write_sysfs(&apply_iface.name, "napi_defer_hard_irqs", des)?;
+            }
+        }
+    }
+    if txqueuelen_changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut filter = nispor::NetStateFilter::default();
+    filter.route = None;
+    let np_state = nispor::NetState::retrieve_with_filter(&filter)
+        .map_err(super::error::np_error_to_nmstate)?;
+
+    let mut reqs = Vec::new();
+    for (iface_name, txqueuelen) in txqueuelen_changes {
+        let index = match np_state.ifaces.get(iface_name) {
+            Some(i) => i.index,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to find interface {iface_name} for \
+                        changing txqueuelen"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!(
+            "Setting txqueuelen of interface {iface_name} to {txqueuelen}"
+        );
+        let mut nl_msg = LinkMessage::default();
+        nl_msg.header.index = index;
+        nl_msg
+            .attributes
+            .push(LinkAttribute::TxQueueLen(txqueuelen));
+        reqs.push(gen_link_set_request(nl_msg));
+    }
+    run_netlink_requests(reqs, true, "change txqueuelen")?;
+    Ok(())
+}
+
+fn read_sysfs<T: FromStr>(iface_name: &str, prop: &str) -> Option<T> {
+    let path = format!("{SYSFS_NET_PATH}/{iface_name}/{prop}");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => content.trim().parse::<T>().ok(),
+        Err(e) => {
+            log::debug!("Failed to read {path}: {e}");
+            None
+        }
+    }
+}
+
+fn write_sysfs<T: std::fmt::Display>(
+    iface_name: &str,
+    prop: &str,
+    value: T,
+) -> Result<(), NmstateError> {
+    let path = format!("{SYSFS_NET_PATH}/{iface_name}/{prop}");
+    log::info!("Setting {prop} of interface {iface_name} to {value}");
+    if let Err(e) = std::fs::write(&path, value.to_string()) {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to write '{value}' to {path}: {e}"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, MacSecConfig, MacSecInterface, MacSecValidate};
+
+pub(crate) fn np_macsec_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> MacSecInterface {
+    let macsec_conf = np_iface.macsec.as_ref().map(|np_macsec_info| {
+        let mut conf = MacSecConfig::new();
+        conf.base_iface = np_macsec_info.base_iface.clone().unwrap_or_default();
+        conf.encrypt = Some(np_macsec_info.encrypt);
+        conf.port = Some(np_macsec_info.port);
+        conf.send_sci = Some(np_macsec_info.send_sci);
+        conf.validation = match np_macsec_info.validate {
+            nispor::MacSecValidate::Disabled => Some(MacSecValidate::Disabled),
+            nispor::MacSecValidate::Check => Some(MacSecValidate::Check),
+            nispor::MacSecValidate::Strict => Some(MacSecValidate::Strict),
+            _ => {
+                log::warn!(
+                    "Unknown supported MACsec validation mode {:?}",
+                    np_macsec_info.validate
+                );
+                None
+            }
+        };
+        conf
+    });
+
+    MacSecInterface {
+        base: base_iface,
+        macsec: macsec_conf,
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use rtnetlink::{
+    packet_core::{NetlinkMessage, NLM_F_ACK, NLM_F_CREATE, NLM_F_REQUEST},
+    packet_route::{
+        address::{AddressAttribute, AddressFlags, AddressMessage},
+        AddressFamily, RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::run_netlink_requests;
+use crate::{ErrorKind, MergedNetworkState, NmstateError};
+
+// Nispor does not support joining multicast group yet. Kernel join the
+// multicast group on behalf of us when the multicast address is added with
+// IFA_F_MCAUTOJOIN flag, and leave the group when that address is removed.
+pub(crate) fn nispor_apply_mcast_groups(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    // Tuple of interface name, multicast group and whether to leave
+    let mut changes: Vec<(&str, IpAddr, bool)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i,
+            _ => continue,
+        };
+        let des_groups =
+            match apply_iface.base_iface().multicast_groups.as_ref() {
+                Some(g) => g,
+                None => continue,
+            };
+        let cur_groups = merged_iface
+            .current
+            .as_ref()
+            .and_then(|i| i.base_iface().multicast_groups.as_deref())
+            .unwrap_or_default();
+        let name = apply_iface.name();
+        for group in des_groups {
+            changes.push((name, *group, false));
+        }
+        for group in cur_groups.iter().filter(|g| !des_groups.contains(g)) {
+            changes.push((name, *group, true));
+        }
+    }
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut filter = nispor::NetStateFilter::default();
+    filter.route = None;
+    let np_state = nispor::NetState::retrieve_with_filter(&filter)
+        .map_err(super::error::np_error_to_nmstate)?;
+
+    let mut reqs = Vec::new();
+    for (iface_name, group, is_leave) in changes {
+        let index = match np_state.ifaces.get(iface_name) {
+            Some(i) => i.index,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to find interface {iface_name} for \
+                        joining multicast group {group}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!(
+            "{} multicast group {group} on interface {iface_name}",
+            if is_leave { "Leaving" } else { "Joining" },
+        );
+        reqs.push(gen_mcast_group_request(index, group, is_leave));
+    }
+    run_netlink_requests(reqs, true, "join or leave multicast group")?;
+    Ok(())
+}
+
+fn gen_mcast_group_request(
+    ifindex: u32,
+    group: IpAddr,
+    is_leave: bool,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = AddressMessage::default();
+    nl_msg.header.index = ifindex;
+    match group {
+        IpAddr::V4(_) => {
+            nl_msg.header.family = AddressFamily::Inet;
+            nl_msg.header.prefix_len = 32;
+        }
+        IpAddr::V6(_) => {
+            nl_msg.header.family = AddressFamily::Inet6;
+            nl_msg.header.prefix_len = 128;
+        }
+    }
+    nl_msg.attributes.push(AddressAttribute::Local(group));
+    nl_msg.attributes.push(AddressAttribute::Address(group));
+    nl_msg
+        .attributes
+        .push(AddressAttribute::Flags(AddressFlags::Mcautojoin));
+
+    if is_leave {
+        let mut req =
+            NetlinkMessage::from(RouteNetlinkMessage::DelAddress(nl_msg));
+        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req
+    } else {
+        let mut req =
+            NetlinkMessage::from(RouteNetlinkMessage::NewAddress(nl_msg));
+        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE;
+        req
+    }
+}
@@ -1,24 +1,74 @@
 mod apply;
 mod base_iface;
+mod batadv;
 mod bond;
+mod can;
+mod channels;
+mod coalesce_queue;
+mod eee;
 mod error;
+mod eswitch;
 mod ethernet;
 mod ethtool;
+mod fdb;
+mod flow_rule;
+mod geneve;
+mod gtp;
 mod hostname;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
+mod l2tp;
+mod link_stats;
+mod link_tuning;
 mod linux_bridge;
 mod linux_bridge_port_vlan;
 mod mac_vlan;
+mod macsec;
+mod mcast;
 mod mptcp;
+mod netkit;
+mod netlink;
+mod netns;
+mod pci;
+mod promisc;
 mod route;
 mod route_rule;
 mod show;
+mod tun;
+#[cfg(test)]
+mod unit_tests;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wifi;
+mod wol;
+mod xfrm;
 
-pub(crate) use apply::nispor_apply;
-pub(crate) use hostname::set_running_hostname;
+pub(crate) use apply::{nispor_apply, nispor_restore_external_ip};
+pub(crate) use batadv::nispor_apply_batadv;
+pub(crate) use can::nispor_apply_can;
+pub(crate) use channels::nispor_apply_channels;
+pub(crate) use coalesce_queue::nispor_apply_coalesce_queues;
+pub(crate) use eee::nispor_apply_eee;
+pub(crate) use eswitch::nispor_apply_eswitch_mode;
+pub(crate) use fdb::nispor_apply_fdb;
+pub(crate) use flow_rule::nispor_apply_flow_rules;
+pub(crate) use gtp::nispor_apply_gtp;
+pub(crate) use hostname::{get_hostname_state, set_running_hostname};
+pub(crate) use ip_tunnel::nispor_apply_sit_6rd_prefix;
+pub(crate) use ipvlan::nispor_apply_ipvlan;
+pub(crate) use l2tp::nispor_apply_l2tpv3;
+pub(crate) use link_tuning::nispor_apply_link_tuning;
+pub(crate) use mcast::nispor_apply_mcast_groups;
+pub(crate) use netkit::nispor_apply_netkit;
+pub(crate) use netlink::nispor_delete_ifaces;
+pub(crate) use netns::enter_netns;
+pub(crate) use pci::{nispor_apply_pci_devices, nispor_restore_pci_devices};
+pub(crate) use promisc::nispor_apply_accept_all_mac_addresses;
+pub(crate) use route::get_routes;
 pub(crate) use show::nispor_retrieve;
+pub(crate) use wol::nispor_apply_wol;
+pub(crate) use xfrm::nispor_apply_xfrm;
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use rtnetlink::{
+    packet_core::{DefaultNla, Emitable, NetlinkMessage, Nla},
+    packet_route::{
+        link::{
+            InfoData, InfoKind, LinkAttribute, LinkFlags, LinkInfo, LinkMessage,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{
+    emit_nlas, gen_link_del_request, gen_link_new_request, get_links,
+    get_nla_bytes, parse_nlas, run_netlink_requests,
+};
+use crate::{
+    Interface, InterfaceType, Interfaces, MergedNetworkState, NetkitConfig,
+    NetkitInterface, NetkitMode, NetkitPolicy, NmstateError,
+};
+
+const NETKIT_ACTION: &str = "query or change netkit interface";
+// The netlink-packet-route crate does not know netkit, hence we use the raw
+// `IFLA_INFO_KIND` string and `IFLA_INFO_DATA` attributes.
+const NETKIT_KIND: &str = "netkit";
+
+const IFLA_NETKIT_PEER_INFO: u16 = 1;
+const IFLA_NETKIT_POLICY: u16 = 3;
+const IFLA_NETKIT_PEER_POLICY: u16 = 4;
+const IFLA_NETKIT_MODE: u16 = 5;
+
+const NETKIT_DROP: u32 = 2;
+const NETKIT_L2: u32 = 0;
+
+// Nispor does not support netkit interface yet.
+pub(crate) fn append_netkit_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Netkit)
+    {
+        return Ok(());
+    }
+    let links = get_links(NETKIT_ACTION)?;
+    let index_to_name: HashMap<u32, &str> =
+        links.iter().map(|l| (l.index, l.name.as_str())).collect();
+    for link in links.iter() {
+        // When peer is moved to other network namespace, its ifindex is
+        // meaningless in current network namespace, hence `link.link` is
+        // None and we do not include the `netkit` section.
+        if let (
+            Some(InfoData::Other(data)),
+            Some(peer),
+            Some(Interface::Netkit(nk_iface)),
+        ) = (
+            link.data
+                .as_ref()
+                .filter(|_| link.is_other_kind(NETKIT_KIND)),
+            link.link.and_then(|i| index_to_name.get(&i)),
+            ifaces.kernel_ifaces.get_mut(&link.name),
+        ) {
+            let mut conf = parse_netkit_nlas(&parse_nlas(data));
+            conf.peer = peer.to_string();
+            nk_iface.netkit = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support netkit interface, hence both backends
+// create the netkit pair via netlink before activating the profiles.
+// Kernel cannot change the peer or mode of existing netkit pair, hence we
+// delete the pair first when they changed.
+// The removal of netkit interface is handled by backend as other virtual
+// interfaces.
+pub(crate) fn nispor_apply_netkit(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    // Both ends of new netkit pair might be desired, only create it once.
+    let mut created: HashSet<&str> = HashSet::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::Netkit(nk_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            let peer = match nk_iface.peer() {
+                Some(p) => p,
+                None => continue,
+            };
+            if created.contains(nk_iface.base.name.as_str()) {
+                continue;
+            }
+            match merged_iface.current.as_ref() {
+                Some(Interface::Netkit(cur_iface)) => {
+                    if !nk_iface.is_link_changed(cur_iface) {
+                        continue;
+                    }
+                    log::info!(
+                        "Recreating netkit interface {} as kernel does not \
+                        support changing its peer, mode or policy",
+                        nk_iface.base.name
+                    );
+                    reqs.push(gen_link_del_request(&nk_iface.base.name));
+                }
+                _ => {
+                    log::info!(
+                        "Creating netkit interface {} with peer {}",
+                        nk_iface.base.name,
+                        peer
+                    );
+                }
+            }
+            created.insert(nk_iface.base.name.as_str());
+            created.insert(peer);
+            reqs.push(gen_netkit_new_request(nk_iface));
+        }
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_netlink_requests(reqs, true, NETKIT_ACTION)?;
+    Ok(())
+}
+
+fn parse_netkit_nlas(nlas: &[DefaultNla]) -> NetkitConfig {
+    let mut conf = NetkitConfig::new();
+    for nla in nlas {
+        let value = get_nla_bytes(nla);
+        let value = match <[u8; 4]>::try_from(value.as_slice()) {
+            Ok(v) => u32::from_ne_bytes(v),
+            Err(_) => continue,
+        };
+        match nla.kind() {
+            IFLA_NETKIT_MODE => {
+                conf.mode = Some(if value == NETKIT_L2 {
+                    NetkitMode::L2
+                } else {
+                    NetkitMode::L3
+                });
+            }
+            IFLA_NETKIT_POLICY => {
+                conf.policy = Some(np_policy_to_nmstate(value));
+            }
+            IFLA_NETKIT_PEER_POLICY => {
+                conf.peer_policy = Some(np_policy_to_nmstate(value));
+            }
+            _ => (),
+        }
+    }
+    conf
+}
+
+fn np_policy_to_nmstate(value: u32) -> NetkitPolicy {
+    if value == NETKIT_DROP {
+        NetkitPolicy::Drop
+    } else {
+        NetkitPolicy::Pass
+    }
+}
+
+fn gen_netkit_new_request(
+    nk_iface: &NetkitInterface,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let conf = nk_iface.netkit.clone().unwrap_or_default();
+
+    // The peer is described by a nested ifinfomsg with its own attributes
+    let mut peer_msg = LinkMessage::default();
+    peer_msg.header.flags = LinkFlags::Up;
+    peer_msg.header.change_mask = LinkFlags::Up;
+    peer_msg
+        .attributes
+        .push(LinkAttribute::IfName(conf.peer.clone()));
+    let mut peer_info = vec![0u8; peer_msg.buffer_len()];
+    peer_msg.emit(&mut peer_info);
+
+    let nlas = vec![
+        DefaultNla::new(IFLA_NETKIT_PEER_INFO, peer_info),
+        DefaultNla::new(
+            IFLA_NETKIT_MODE,
+            u32::from(conf.mode.unwrap_or_default())
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+        DefaultNla::new(
+            IFLA_NETKIT_POLICY,
+            u32::from(conf.policy.unwrap_or_default())
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+        DefaultNla::new(
+            IFLA_NETKIT_PEER_POLICY,
+            u32::from(conf.peer_policy.unwrap_or_default())
+                .to_ne_bytes()
+                .to_vec(),
+        ),
+    ];
+
+    gen_link_new_request(
+        &nk_iface.base.name,
+        Vec::new(),
+        vec![
+            LinkInfo::Kind(InfoKind::Other(NETKIT_KIND.to_string())),
+            LinkInfo::Data(InfoData::Other(emit_nlas(&nlas))),
+        ],
+        true,
+    )
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use futures::stream::StreamExt;
+use rtnetlink::{
+    packet_core::{
+        DecodeError, DefaultNla, Emitable, NetlinkDeserializable,
+        NetlinkHeader, NetlinkMessage, NetlinkPayload, NetlinkSerializable,
+        Nla, NlasIterator, Parseable, NLM_F_ACK, NLM_F_REQUEST,
+    },
+    sys::{protocols::NETLINK_GENERIC, SocketAddr},
+};
+
+use super::{get_nla_bytes, parse_nlas, EEXIST, ENOENT};
+use crate::{ErrorKind, NmstateError};
+
+const GENL_HDR_LEN: usize = 4;
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+// Generic netlink message with raw attributes. The `family_id` is resolved by
+// `run_genl_requests()` from family name, hence could be left as 0 when
+// generating requests.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct GenlRawMessage {
+    pub(crate) family_id: u16,
+    pub(crate) cmd: u8,
+    pub(crate) version: u8,
+    pub(crate) nlas: Vec<DefaultNla>,
+}
+
+impl GenlRawMessage {
+    pub(crate) fn new(cmd: u8, version: u8, nlas: Vec<DefaultNla>) -> Self {
+        Self {
+            family_id: 0,
+            cmd,
+            version,
+            nlas,
+        }
+    }
+
+    pub(crate) fn gen_request(
+        self,
+        flags: u16,
+    ) -> NetlinkMessage<GenlRawMessage> {
+        let mut req = NetlinkMessage::new(
+            NetlinkHeader::default(),
+            NetlinkPayload::InnerMessage(self),
+        );
+        req.header.flags = NLM_F_REQUEST | flags;
+        req.finalize();
+        req
+    }
+
+    pub(crate) fn get_nla_value(&self, kind: u16) -> Option<Vec<u8>> {
+        self.nlas
+            .iter()
+            .find(|nla| nla.kind() == kind)
+            .map(get_nla_bytes)
+    }
+
+    pub(crate) fn get_nla_u16(&self, kind: u16) -> Option<u16> {
+        self.get_nla_value(kind)
+            .and_then(|v| <[u8; 2]>::try_from(v.as_slice()).ok())
+            .map(u16::from_ne_bytes)
+    }
+
+    pub(crate) fn get_nla_u32(&self, kind: u16) -> Option<u32> {
+        self.get_nla_value(kind)
+            .and_then(|v| <[u8; 4]>::try_from(v.as_slice()).ok())
+            .map(u32::from_ne_bytes)
+    }
+
+    pub(crate) fn get_nla_nested(&self, kind: u16) -> Vec<DefaultNla> {
+        self.get_nla_value(kind)
+            .map(|v| parse_nlas(v.as_slice()))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_nla_string(&self, kind: u16) -> Option<String> {
+        self.get_nla_value(kind).map(|v| {
+            String::from_utf8_lossy(v.split(|c| *c == 0).next().unwrap_or(&[]))
+                .to_string()
+        })
+    }
+}
+
+impl NetlinkSerializable for GenlRawMessage {
+    fn message_type(&self) -> u16 {
+        self.family_id
+    }
+
+    fn buffer_len(&self) -> usize {
+        GENL_HDR_LEN + self.nlas.as_slice().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        buffer[0] = self.cmd;
+        buffer[1] = self.version;
+        buffer[2] = 0;
+        buffer[3] = 0;
+        self.nlas.as_slice().emit(&mut buffer[GENL_HDR_LEN..]);
+    }
+}
+
+impl NetlinkDeserializable for GenlRawMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        if payload.len() < GENL_HDR_LEN {
+            return Err(DecodeError::from(
+                "Generic netlink message is shorter than its header",
+            ));
+        }
+        let mut nlas = Vec::new();
+        for nla_buf in NlasIterator::new(&payload[GENL_HDR_LEN..]) {
+            nlas.push(DefaultNla::parse(&nla_buf?)?);
+        }
+        Ok(Self {
+            family_id: header.message_type,
+            cmd: payload[0],
+            version: payload[1],
+            nlas,
+        })
+    }
+}
+
+// Neither nispor nor rtnetlink support generic netlink, hence we send raw
+// generic netlink messages to the `family` resolved via the generic netlink
+// controller. The `action` is used in error message.
+// When `is_change` is true, the ENOENT and EEXIST errors are ignored as
+// removing non-exist entry or appending existing entry is considered as
+// success.
+pub(crate) fn run_genl_requests(
+    family: &str,
+    reqs: Vec<NetlinkMessage<GenlRawMessage>>,
+    is_change: bool,
+    action: &str,
+) -> Result<Vec<GenlRawMessage>, NmstateError> {
+    let nl_error = |e: &dyn std::fmt::Display| {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to {action}: {e}"),
+        );
+        log::error!("{}", e);
+        e
+    };
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .map_err(|e| nl_error(&e))?;
+    rt.block_on(async {
+        let (connection, handle, _) =
+            rtnetlink::proto::new_connection::<GenlRawMessage>(NETLINK_GENERIC)
+                .map_err(|e| nl_error(&e))?;
+        tokio::spawn(connection);
+
+        let mut family_req = GenlRawMessage::new(
+            CTRL_CMD_GETFAMILY,
+            1,
+            vec![DefaultNla::new(
+                CTRL_ATTR_FAMILY_NAME,
+                format!("{family}\0").into_bytes(),
+            )],
+        );
+        family_req.family_id = GENL_ID_CTRL;
+        let mut family_id = None;
+        let mut response = handle
+            .request(family_req.gen_request(NLM_F_ACK), SocketAddr::new(0, 0))
+            .map_err(|e| nl_error(&e))?;
+        while let Some(nl_msg) = response.next().await {
+            match nl_msg.payload {
+                NetlinkPayload::InnerMessage(m) => {
+                    family_id = m.get_nla_u16(CTRL_ATTR_FAMILY_ID);
+                }
+                NetlinkPayload::Error(e) if e.code.is_some() => {
+                    return Err(nl_error(&format!(
+                        "generic netlink family {family} not found, \
+                        kernel module not loaded? {e}"
+                    )));
+                }
+                _ => (),
+            }
+        }
+        let family_id = match family_id {
+            Some(i) => i,
+            None => {
+                return Err(nl_error(&format!(
+                    "generic netlink family {family} not found"
+                )));
+            }
+        };
+
+        let mut ret = Vec::new();
+        for mut req in reqs {
+            req.header.message_type = family_id;
+            if let NetlinkPayload::InnerMessage(m) = &mut req.payload {
+                m.family_id = family_id;
+            }
+            let mut response = handle
+                .request(req, SocketAddr::new(0, 0))
+                .map_err(|e| nl_error(&e))?;
+            while let Some(nl_msg) = response.next().await {
+                match nl_msg.payload {
+                    NetlinkPayload::InnerMessage(m) => ret.push(m),
+                    NetlinkPayload::Error(e)
+                        if is_change
+                            && (e.raw_code() == -ENOENT
+                                || e.raw_code() == -EEXIST) => {}
+                    NetlinkPayload::Error(e) if e.code.is_some() => {
+                        return Err(nl_error(&e));
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(ret)
+    })
+}
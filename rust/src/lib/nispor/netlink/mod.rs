@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not cover every kernel feature nmstate supports, the plugin
+// modules query and change those features via rtnetlink or raw generic
+// netlink using the helpers of this module. The netlink-packet-core and
+// netlink-packet-route crates are pinned in Cargo.toml because several
+// modules emit and parse raw netlink attributes on top of their structures.
+
+mod genl;
+
+use futures::stream::StreamExt;
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, Emitable, NetlinkMessage, NetlinkPayload, Nla,
+        NlasIterator, Parseable, NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP,
+        NLM_F_EXCL, NLM_F_REQUEST,
+    },
+    packet_route::{
+        link::{
+            InfoData, InfoKind, LinkAttribute, LinkFlags, LinkInfo, LinkMessage,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use crate::{ErrorKind, NmstateError};
+
+pub(crate) use self::genl::{run_genl_requests, GenlRawMessage};
+
+pub(crate) const ENOENT: i32 = 2;
+pub(crate) const EEXIST: i32 = 17;
+
+const NLA_F_NESTED: u16 = 0x8000;
+
+// Interface information from the RTM_NEWLINK reply of link dump.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct NlLink {
+    pub(crate) index: u32,
+    pub(crate) name: String,
+    pub(crate) controller: Option<u32>,
+    // The IFLA_LINK is ignored when the link is in other network namespace
+    // as the index cannot be resolved to interface name of current one.
+    pub(crate) link: Option<u32>,
+    pub(crate) kind: Option<InfoKind>,
+    pub(crate) data: Option<InfoData>,
+}
+
+impl NlLink {
+    // Kinds unknown to the netlink-packet-route crate are stored as
+    // `InfoKind::Other`.
+    pub(crate) fn is_other_kind(&self, kind: &str) -> bool {
+        matches!(&self.kind, Some(InfoKind::Other(k)) if k == kind)
+    }
+}
+
+// Send the requests via rtnetlink. The `action` is used in error message.
+// When `is_change` is true, the ENOENT and EEXIST errors are ignored as
+// removing non-exist entry or appending existing entry is considered as
+// success.
+pub(crate) fn run_netlink_requests(
+    reqs: Vec<NetlinkMessage<RouteNetlinkMessage>>,
+    is_change: bool,
+    action: &str,
+) -> Result<Vec<RouteNetlinkMessage>, NmstateError> {
+    let nl_error = |e: &dyn std::fmt::Display| {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to {action}: {e}"),
+        );
+        log::error!("{}", e);
+        e
+    };
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .map_err(|e| nl_error(&e))?;
+    rt.block_on(async {
+        let (connection, mut handle, _) =
+            rtnetlink::new_connection().map_err(|e| nl_error(&e))?;
+        tokio::spawn(connection);
+        let mut ret = Vec::new();
+        for req in reqs {
+            let mut response = handle.request(req).map_err(|e| nl_error(&e))?;
+            while let Some(nl_msg) = response.next().await {
+                match nl_msg.payload {
+                    NetlinkPayload::InnerMessage(m) => ret.push(m),
+                    NetlinkPayload::Error(e)
+                        if is_change
+                            && (e.raw_code() == -ENOENT
+                                || e.raw_code() == -EEXIST) => {}
+                    NetlinkPayload::Error(e) if e.code.is_some() => {
+                        return Err(nl_error(&e));
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(ret)
+    })
+}
+
+pub(crate) fn gen_link_del_request(
+    iface_name: &str,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg
+        .attributes
+        .push(LinkAttribute::IfName(iface_name.to_string()));
+    let mut req = NetlinkMessage::from(RouteNetlinkMessage::DelLink(nl_msg));
+    req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+    req
+}
+
+// Used by backends which cannot delete the interfaces nmstate created via
+// netlink. Removing non-exist interface is considered as success.
+pub(crate) fn nispor_delete_ifaces(
+    iface_names: &[&str],
+) -> Result<(), NmstateError> {
+    if iface_names.is_empty() {
+        return Ok(());
+    }
+    log::info!("Deleting interfaces {}", iface_names.join(", "));
+    run_netlink_requests(
+        iface_names
+            .iter()
+            .map(|name| gen_link_del_request(name))
+            .collect(),
+        true,
+        "delete interface",
+    )?;
+    Ok(())
+}
+
+pub(crate) fn parse_link_msg(link_msg: LinkMessage) -> Option<NlLink> {
+    let mut link = NlLink {
+        index: link_msg.header.index,
+        ..Default::default()
+    };
+    let mut name = None;
+    let mut link_in_other_ns = false;
+    for attr in link_msg.attributes {
+        match attr {
+            LinkAttribute::IfName(n) => name = Some(n),
+            LinkAttribute::Controller(i) => link.controller = Some(i),
+            LinkAttribute::Link(i) => link.link = Some(i),
+            LinkAttribute::LinkNetNsId(_) => link_in_other_ns = true,
+            LinkAttribute::LinkInfo(infos) => {
+                for info in infos {
+                    match info {
+                        LinkInfo::Kind(k) => link.kind = Some(k),
+                        LinkInfo::Data(d) => link.data = Some(d),
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if link_in_other_ns {
+        link.link = None;
+    }
+    link.name = name?;
+    Some(link)
+}
+
+pub(crate) fn get_links(action: &str) -> Result<Vec<NlLink>, NmstateError> {
+    let mut req = NetlinkMessage::from(RouteNetlinkMessage::GetLink(
+        LinkMessage::default(),
+    ));
+    req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+
+    Ok(run_netlink_requests(vec![req], false, action)?
+        .into_iter()
+        .filter_map(|nl_msg| match nl_msg {
+            RouteNetlinkMessage::NewLink(l) => parse_link_msg(l),
+            _ => None,
+        })
+        .collect())
+}
+
+// Create interface in up state. When `exclusive` is false, kernel applies
+// the request to existing interface instead of failing with EEXIST.
+pub(crate) fn gen_link_new_request(
+    iface_name: &str,
+    attributes: Vec<LinkAttribute>,
+    link_info: Vec<LinkInfo>,
+    exclusive: bool,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.flags = LinkFlags::Up;
+    nl_msg.header.change_mask = LinkFlags::Up;
+    nl_msg
+        .attributes
+        .push(LinkAttribute::IfName(iface_name.to_string()));
+    nl_msg.attributes.extend(attributes);
+    nl_msg.attributes.push(LinkAttribute::LinkInfo(link_info));
+    let mut req = NetlinkMessage::from(RouteNetlinkMessage::NewLink(nl_msg));
+    req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE;
+    if exclusive {
+        req.header.flags |= NLM_F_EXCL;
+    }
+    req
+}
+
+// Change existing interface identified by the index or `IFLA_IFNAME` of
+// `nl_msg`.
+pub(crate) fn gen_link_set_request(
+    nl_msg: LinkMessage,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut req = NetlinkMessage::from(RouteNetlinkMessage::NewLink(nl_msg));
+    req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+    req
+}
+
+pub(crate) fn gen_link_up_request(
+    iface_name: &str,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.flags = LinkFlags::Up;
+    nl_msg.header.change_mask = LinkFlags::Up;
+    nl_msg
+        .attributes
+        .push(LinkAttribute::IfName(iface_name.to_string()));
+    gen_link_set_request(nl_msg)
+}
+
+pub(crate) fn gen_link_down_request(
+    index: u32,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nl_msg = LinkMessage::default();
+    nl_msg.header.index = index;
+    nl_msg.header.flags = LinkFlags::empty();
+    nl_msg.header.change_mask = LinkFlags::Up;
+    gen_link_set_request(nl_msg)
+}
+
+pub(crate) fn get_nla_bytes(nla: &DefaultNla) -> Vec<u8> {
+    let mut value = vec![0u8; nla.value_len()];
+    nla.emit_value(&mut value);
+    value
+}
+
+pub(crate) fn parse_nested_nla(nla: &DefaultNla) -> Vec<DefaultNla> {
+    parse_nlas(get_nla_bytes(nla).as_slice())
+}
+
+// Parse the raw netlink attributes, invalid ones are ignored.
+pub(crate) fn parse_nlas(value: &[u8]) -> Vec<DefaultNla> {
+    let mut ret = Vec::new();
+    for nla_buf in NlasIterator::new(value) {
+        if let Ok(nla) = nla_buf.and_then(|b| DefaultNla::parse(&b)) {
+            ret.push(nla);
+        }
+    }
+    ret
+}
+
+// Emit the netlink attributes with padding, used for the IFLA_INFO_DATA of
+// interface kinds unknown to the netlink-packet-route crate.
+pub(crate) fn emit_nlas(nlas: &[DefaultNla]) -> Vec<u8> {
+    let mut value = vec![0u8; nlas.buffer_len()];
+    nlas.emit(&mut value);
+    value
+}
+
+pub(crate) fn gen_nested_nla(kind: u16, nlas: &[DefaultNla]) -> DefaultNla {
+    DefaultNla::new(kind | NLA_F_NESTED, emit_nlas(nlas))
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::unix::io::AsRawFd;
+
+use nix::{
+    errno::Errno,
+    sched::{setns, CloneFlags},
+};
+
+use crate::{ErrorKind, NmstateError};
+
+const NETNS_RUN_DIR: &str = "/run/netns";
+
+// The network namespace could be specified by name under `/run/netns`
+// (created by `ip netns add`) or by PID of process inside it, for example
+// container.
+fn get_netns_path(netns: &str) -> Result<String, NmstateError> {
+    if netns.is_empty() || netns.contains('/') || netns == "." || netns == ".."
+    {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid network namespace name or PID: '{netns}'"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(if netns.chars().all(|c| c.is_ascii_digit()) {
+        format!("/proc/{netns}/ns/net")
+    } else {
+        format!("{NETNS_RUN_DIR}/{netns}")
+    })
+}
+
+// Network namespace is per-thread, caller should invoke this in a dedicated
+// thread to leave the network namespace of other threads untouched.
+pub(crate) fn enter_netns(netns: &str) -> Result<(), NmstateError> {
+    let netns_path = get_netns_path(netns)?;
+    let fd = match std::fs::File::open(&netns_path) {
+        Ok(f) => f,
+        Err(e) => {
+            let e = NmstateError::new(
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    ErrorKind::PermissionError
+                } else {
+                    ErrorKind::InvalidArgument
+                },
+                format!(
+                    "Failed to open network namespace {netns} \
+                    at {netns_path}: {e}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    };
+    if let Err(errno) = setns(fd.as_raw_fd(), CloneFlags::CLONE_NEWNET) {
+        let e = NmstateError::new(
+            if errno == Errno::EPERM {
+                ErrorKind::PermissionError
+            } else {
+                ErrorKind::PluginFailure
+            },
+            format!("Failed to enter network namespace {netns}: {errno}"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    log::debug!("Entered network namespace {netns}");
+    Ok(())
+}
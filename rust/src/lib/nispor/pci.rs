@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use crate::{
+    ErrorKind, MergedNetworkState, NmstateError, PciDeviceConfig, PciHints,
+};
+
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+const SYSFS_PCI_DEVICES_PATH: &str = "/sys/bus/pci/devices";
+const SYSFS_PCI_DRIVERS_PROBE_PATH: &str = "/sys/bus/pci/drivers_probe";
+// Compatible with the persistent storage of `driverctl`
+const DRIVERCTL_CONF_DIR: &str = "/etc/driverctl.d";
+const DRIVER_OVERRIDE_NULL: &str = "(null)";
+
+// Only include PCI devices with driver override
+pub(crate) fn get_pci_devices() -> Option<Vec<PciDeviceConfig>> {
+    let entries = match std::fs::read_dir(SYSFS_PCI_DEVICES_PATH) {
+        Ok(e) => e,
+        Err(e) => {
+            log::debug!("Failed to read {SYSFS_PCI_DEVICES_PATH}: {e}");
+            return None;
+        }
+    };
+    let mut ret = Vec::new();
+    for entry in entries.flatten() {
+        let pci_address = entry.file_name().to_string_lossy().to_string();
+        let dev_path = entry.path();
+        let driver_override = match read_driver_override(&dev_path) {
+            Some(d) => d,
+            None => continue,
+        };
+        let mut dev = PciDeviceConfig::new();
+        dev.pci_address = pci_address;
+        dev.driver_override = Some(driver_override);
+        dev.driver =
+            std::fs::read_link(dev_path.join("driver"))
+                .ok()
+                .and_then(|p| {
+                    p.file_name().map(|n| n.to_string_lossy().to_string())
+                });
+        ret.push(dev);
+    }
+    if ret.is_empty() {
+        None
+    } else {
+        ret.sort_unstable_by(|a, b| a.pci_address.cmp(&b.pci_address));
+        Some(ret)
+    }
+}
+
+// Nispor does not provide NUMA and IRQ information of the PCI device, hence
+// we read them from sysfs of the PCI device backing the network interface.
+pub(crate) fn np_pci_hints_to_nmstate(
+    np_iface: &nispor::Iface,
+) -> Option<PciHints> {
+    let dev_path = std::path::Path::new(SYSFS_NET_PATH)
+        .join(&np_iface.name)
+        .join("device");
+    let subsystem = std::fs::read_link(dev_path.join("subsystem")).ok()?;
+    if subsystem.file_name().map(|n| n != "pci").unwrap_or(true) {
+        return None;
+    }
+    let mut ret = PciHints::new();
+    ret.pci_address = std::fs::canonicalize(&dev_path)
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+    // Kernel use -1 for no NUMA affinity
+    ret.numa_node = std::fs::read_to_string(dev_path.join("numa_node"))
+        .ok()
+        .and_then(|c| c.trim().parse::<i32>().ok())
+        .and_then(|n| u32::try_from(n).ok());
+    ret.local_cpus = std::fs::read_to_string(dev_path.join("local_cpulist"))
+        .ok()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    ret.irq_count = match std::fs::read_dir(dev_path.join("msi_irqs")) {
+        Ok(entries) => Some(entries.count() as u32),
+        // Legacy INTx interrupt
+        Err(_) => std::fs::read_to_string(dev_path.join("irq"))
+            .ok()
+            .and_then(|c| c.trim().parse::<u32>().ok())
+            .map(|irq| if irq > 0 { 1 } else { 0 }),
+    };
+    if ret.is_empty() {
+        None
+    } else {
+        Some(ret)
+    }
+}
+
+fn read_driver_override(dev_path: &std::path::Path) -> Option<String> {
+    let content =
+        std::fs::read_to_string(dev_path.join("driver_override")).ok()?;
+    let content = content.trim();
+    if content.is_empty() || content == DRIVER_OVERRIDE_NULL {
+        None
+    } else {
+        Some(content.to_string())
+    }
+}
+
+// Return the previous driver override of changed PCI devices for rollback
+pub(crate) fn nispor_apply_pci_devices(
+    merged_state: &MergedNetworkState,
+    persist: bool,
+) -> Result<Vec<PciDeviceConfig>, NmstateError> {
+    let mut old_devs = Vec::new();
+    for dev in merged_state.pci_devices.for_apply() {
+        let des_driver = dev.driver_override.as_deref().unwrap_or_default();
+        let cur_dev = merged_state
+            .pci_devices
+            .get_current(dev.pci_address.as_str());
+        let cur_driver = cur_dev
+            .and_then(|c| c.driver_override.as_deref())
+            .unwrap_or_default();
+        let mut old_dev = PciDeviceConfig::new();
+        old_dev.pci_address = dev.pci_address.clone();
+        old_dev.driver_override = Some(cur_driver.to_string());
+        old_devs.push(old_dev);
+
+        if des_driver != cur_driver
+            || (!des_driver.is_empty()
+                && cur_dev.and_then(|c| c.driver.as_deref())
+                    != Some(des_driver))
+        {
+            bind_pci_driver(dev.pci_address.as_str(), des_driver)?;
+        }
+        if persist {
+            persist_pci_driver(dev.pci_address.as_str(), des_driver)?;
+        }
+    }
+    Ok(old_devs)
+}
+
+pub(crate) fn nispor_restore_pci_devices(
+    old_devs: &[PciDeviceConfig],
+    persist: bool,
+) -> Result<(), NmstateError> {
+    for dev in old_devs {
+        let driver = dev.driver_override.as_deref().unwrap_or_default();
+        bind_pci_driver(dev.pci_address.as_str(), driver)?;
+        if persist {
+            persist_pci_driver(dev.pci_address.as_str(), driver)?;
+        }
+    }
+    Ok(())
+}
+
+// Empty driver means return to default kernel driver
+fn bind_pci_driver(
+    pci_address: &str,
+    driver: &str,
+) -> Result<(), NmstateError> {
+    let dev_path = format!("{SYSFS_PCI_DEVICES_PATH}/{pci_address}");
+    if !std::path::Path::new(&dev_path).exists() {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("PCI device {pci_address} does not exist"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    if !driver.is_empty() {
+        // Driver like vfio-pci is not loaded automatically
+        if let Err(e) = std::process::Command::new("modprobe")
+            .args(["-q", driver])
+            .status()
+        {
+            log::warn!("Failed to load kernel module {driver}: {e}");
+        }
+    }
+    write_sysfs(
+        format!("{dev_path}/driver_override").as_str(),
+        if driver.is_empty() { "\n" } else { driver },
+    )?;
+    unbind_pci_driver(pci_address)?;
+    probe_pci_driver(pci_address)?;
+    log::info!(
+        "PCI device {pci_address} bound to {}",
+        if driver.is_empty() {
+            "default kernel driver"
+        } else {
+            driver
+        }
+    );
+    Ok(())
+}
+
+pub(crate) fn unbind_pci_driver(pci_address: &str) -> Result<(), NmstateError> {
+    let dev_path = format!("{SYSFS_PCI_DEVICES_PATH}/{pci_address}");
+    if std::path::Path::new(&format!("{dev_path}/driver")).exists() {
+        let unbind_path = format!("{dev_path}/driver/unbind");
+        write_sysfs(unbind_path.as_str(), pci_address)?;
+    }
+    Ok(())
+}
+
+// Bind PCI device to the driver matching its driver override or ID table
+pub(crate) fn probe_pci_driver(pci_address: &str) -> Result<(), NmstateError> {
+    write_sysfs(SYSFS_PCI_DRIVERS_PROBE_PATH, pci_address)
+}
+
+fn persist_pci_driver(
+    pci_address: &str,
+    driver: &str,
+) -> Result<(), NmstateError> {
+    let path = format!("{DRIVERCTL_CONF_DIR}/pci-{pci_address}");
+    let result = if driver.is_empty() {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            r => r,
+        }
+    } else {
+        std::fs::create_dir_all(DRIVERCTL_CONF_DIR)
+            .and_then(|_| std::fs::write(&path, format!("{driver}\n")))
+    };
+    if let Err(e) = result {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to update {path}: {e}"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn write_sysfs(path: &str, content: &str) -> Result<(), NmstateError> {
+    if let Err(e) = std::fs::write(path, content) {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to write '{}' to {path}: {e}", content.trim()),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::packet_route::link::{LinkFlags, LinkMessage};
+
+use super::netlink::{gen_link_set_request, run_netlink_requests};
+use crate::{ErrorKind, MergedNetworkState, NmstateError};
+
+// Nispor does not support changing promiscuous mode yet. Only used by
+// kernel mode, NetworkManager backend is
+// using `802-3-ethernet.accept-all-mac-addresses` instead.
+pub(crate) fn nispor_apply_accept_all_mac_addresses(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    // Tuple of interface name and whether to enable promiscuous mode
+    let mut changes: Vec<(&str, bool)> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        let apply_iface = match merged_iface.for_apply.as_ref() {
+            Some(i) if i.is_up() => i,
+            _ => continue,
+        };
+        let des_value = match apply_iface.base_iface().accept_all_mac_addresses
+        {
+            Some(v) => v,
+            None => continue,
+        };
+        let cur_value = merged_iface
+            .current
+            .as_ref()
+            .and_then(|i| i.base_iface().accept_all_mac_addresses);
+        if cur_value != Some(des_value) {
+            changes.push((apply_iface.name(), des_value));
+        }
+    }
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut filter = nispor::NetStateFilter::default();
+    filter.route = None;
+    let np_state = nispor::NetState::retrieve_with_filter(&filter)
+        .map_err(super::error::np_error_to_nmstate)?;
+
+    let mut reqs = Vec::new();
+    for (iface_name, enabled) in changes {
+        let index = match np_state.ifaces.get(iface_name) {
+            Some(i) => i.index,
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to find interface {iface_name} for \
+                        changing accept-all-mac-addresses"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!(
+            "{} accept-all-mac-addresses(promiscuous) mode on interface \
+            {iface_name}",
+            if enabled { "Enabling" } else { "Disabling" },
+        );
+        let mut nl_msg = LinkMessage::default();
+        nl_msg.header.index = index;
+        nl_msg.header.change_mask = LinkFlags::Promisc;
+        if enabled {
+            nl_msg.header.flags = LinkFlags::Promisc;
+        }
+        reqs.push(gen_link_set_request(nl_msg));
+    }
+    run_netlink_requests(reqs, true, "change promiscuous mode")?;
+    Ok(())
+}
@@ -1,11 +1,24 @@
+use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::str::FromStr;
+
 use log::warn;
+use rtnetlink::{
+    packet_core::{
+        NetlinkMessage, NLM_F_ACK, NLM_F_CREATE, NLM_F_REPLACE, NLM_F_REQUEST,
+    },
+    packet_route::{
+        route::RouteProtocol as NlRouteProtocol, RouteNetlinkMessage,
+    },
+    RouteMessageBuilder,
+};
 
-use crate::{RouteEntry, Routes};
+use crate::{ErrorKind, NmstateError, RouteEntry, RouteProtocol, Routes};
 
 const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 2] =
     [nispor::RouteScope::Universe, nispor::RouteScope::Link];
 
-const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 7] = [
+const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 13] = [
     nispor::RouteProtocol::Boot,
     nispor::RouteProtocol::Static,
     nispor::RouteProtocol::Ra,
@@ -13,6 +26,12 @@ const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 7] = [
     nispor::RouteProtocol::Mrouted,
     nispor::RouteProtocol::KeepAlived,
     nispor::RouteProtocol::Babel,
+    nispor::RouteProtocol::Zebra,
+    nispor::RouteProtocol::Bird,
+    nispor::RouteProtocol::Bgp,
+    nispor::RouteProtocol::Ospf,
+    nispor::RouteProtocol::Isis,
+    nispor::RouteProtocol::Rip,
 ];
 
 const SUPPORTED_STATIC_ROUTE_PROTOCOL: [nispor::RouteProtocol; 2] =
@@ -137,10 +156,34 @@ fn np_route_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     route_entry.next_hop_addr = next_hop_addr;
     route_entry.metric = np_route.metric.map(i64::from);
     route_entry.table_id = Some(np_route.table);
+    route_entry.protocol =
+        Some(np_route_protocol_to_nmstate(&np_route.protocol));
 
     route_entry
 }
 
+fn np_route_protocol_to_nmstate(
+    np_protocol: &nispor::RouteProtocol,
+) -> RouteProtocol {
+    match np_protocol {
+        nispor::RouteProtocol::Kernel => RouteProtocol::Kernel,
+        nispor::RouteProtocol::Boot => RouteProtocol::Boot,
+        nispor::RouteProtocol::Static => RouteProtocol::Static,
+        nispor::RouteProtocol::Dhcp => RouteProtocol::Dhcp,
+        nispor::RouteProtocol::Ra => RouteProtocol::Ra,
+        nispor::RouteProtocol::Mrouted => RouteProtocol::Mrouted,
+        nispor::RouteProtocol::KeepAlived => RouteProtocol::Keepalived,
+        nispor::RouteProtocol::Babel => RouteProtocol::Babel,
+        nispor::RouteProtocol::Zebra => RouteProtocol::Zebra,
+        nispor::RouteProtocol::Bird => RouteProtocol::Bird,
+        nispor::RouteProtocol::Bgp => RouteProtocol::Bgp,
+        nispor::RouteProtocol::Ospf => RouteProtocol::Ospf,
+        nispor::RouteProtocol::Isis => RouteProtocol::Isis,
+        nispor::RouteProtocol::Rip => RouteProtocol::Rip,
+        _ => RouteProtocol::Unknown,
+    }
+}
+
 fn is_multipath(np_route: &nispor::Route) -> bool {
     np_route
         .multipath
@@ -165,3 +208,77 @@ fn flat_multipath_route(np_route: &nispor::Route) -> Vec<RouteEntry> {
     }
     ret
 }
+
+// Only static routes stored in `Routes.config` is supported. The route table
+// ID bigger than 255 is not supported by nispor, please use
+// `gen_route_add_request()` for it.
+pub(crate) fn nmstate_route_to_np(
+    route: &RouteEntry,
+) -> Option<nispor::RouteConf> {
+    let mut np_route = nispor::RouteConf::default();
+    np_route.dst = route.destination.as_deref()?.to_string();
+    np_route.oif = route.next_hop_iface.clone();
+    np_route.via = route.next_hop_addr.clone();
+    np_route.metric = route.metric.and_then(|m| u32::try_from(m).ok());
+    if let Some(table_id) = route.table_id {
+        np_route.table = Some(u8::try_from(table_id).ok()?);
+    }
+    np_route.protocol = Some(match route.protocol {
+        Some(RouteProtocol::Boot) => nispor::RouteProtocol::Boot,
+        _ => nispor::RouteProtocol::Static,
+    });
+    Some(np_route)
+}
+
+pub(crate) fn is_np_route_table_supported(route: &RouteEntry) -> bool {
+    route
+        .table_id
+        .map(|t| u8::try_from(t).is_ok())
+        .unwrap_or(true)
+}
+
+// Nispor only supports 8 bits route table ID, hence routes in bigger route
+// table are added via rtnetlink using the 32 bits RTA_TABLE attribute.
+pub(crate) fn gen_route_add_request(
+    route: &RouteEntry,
+    oif_index: u32,
+) -> Result<NetlinkMessage<RouteNetlinkMessage>, NmstateError> {
+    let invalid_route = |msg: String| {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid route {route}: {msg}"),
+        );
+        log::error!("{}", e);
+        e
+    };
+    let dst: ipnet::IpNet = route
+        .destination
+        .as_deref()
+        .ok_or_else(|| invalid_route("no destination".to_string()))?
+        .parse()?;
+    let mut builder = RouteMessageBuilder::<IpAddr>::new()
+        .destination_prefix(dst.addr(), dst.prefix_len())
+        .map_err(|e| invalid_route(e.to_string()))?
+        .output_interface(oif_index)
+        .protocol(match route.protocol {
+            Some(RouteProtocol::Boot) => NlRouteProtocol::Boot,
+            _ => NlRouteProtocol::Static,
+        });
+    if let Some(via) = route.next_hop_addr.as_deref() {
+        let via =
+            IpAddr::from_str(via).map_err(|e| invalid_route(e.to_string()))?;
+        builder = builder
+            .gateway(via)
+            .map_err(|e| invalid_route(e.to_string()))?;
+    }
+    if let Some(metric) = route.metric.and_then(|m| u32::try_from(m).ok()) {
+        builder = builder.priority(metric);
+    }
+    if let Some(table_id) = route.table_id {
+        builder = builder.table_id(table_id);
+    }
+    let mut req =
+        NetlinkMessage::from(RouteNetlinkMessage::NewRoute(builder.build()));
+    req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE;
+    Ok(req)
+}
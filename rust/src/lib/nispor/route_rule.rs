@@ -10,7 +10,7 @@ use crate::{AddressFamily, RouteRuleAction, RouteRuleEntry, RouteRules};
 const SUPPORTED_STATIC_ROUTE_PROTOCOL: [nispor::RouteProtocol; 3] = [
     nispor::RouteProtocol::Boot,
     nispor::RouteProtocol::Static,
-    nispor::RouteProtocol::UnSpec,
+    nispor::RouteProtocol::Unspec,
 ];
 
 const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 8] = [
@@ -21,7 +21,7 @@ const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 8] = [
     nispor::RouteProtocol::Mrouted,
     nispor::RouteProtocol::KeepAlived,
     nispor::RouteProtocol::Babel,
-    nispor::RouteProtocol::UnSpec,
+    nispor::RouteProtocol::Unspec,
 ];
 
 pub(crate) fn get_route_rules(
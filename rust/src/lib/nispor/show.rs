@@ -5,30 +5,62 @@ use std::collections::HashMap;
 use crate::{
     nispor::{
         base_iface::np_iface_to_base_iface,
-        bond::np_bond_to_nmstate,
+        batadv::append_batadv_conf,
+        bond::{append_bond_port_config, np_bond_to_nmstate},
+        can::append_can_conf,
+        channels::append_channels_conf,
+        coalesce_queue::append_coalesce_queues_conf,
+        eee::append_eee_conf,
         error::np_error_to_nmstate,
-        ethernet::np_ethernet_to_nmstate,
+        eswitch::append_eswitch_mode,
+        ethernet::{append_sriov_vf_representors, np_ethernet_to_nmstate},
+        fdb::append_fdb,
+        flow_rule::append_flow_rules_conf,
+        geneve::append_geneve_conf,
+        gtp::append_gtp_conf,
         hostname::get_hostname_state,
         infiniband::np_ib_to_nmstate,
+        ip_tunnel::append_sit_6rd_prefix,
+        ipvlan::append_ipvlan_conf,
+        l2tp::append_l2tpv3_conf,
         linux_bridge::{append_bridge_port_config, np_bridge_to_nmstate},
         mac_vlan::{np_mac_vlan_to_nmstate, np_mac_vtap_to_nmstate},
+        macsec::np_macsec_to_nmstate,
+        netkit::append_netkit_conf,
+        pci::get_pci_devices,
         route::get_routes,
         route_rule::get_route_rules,
+        tun::np_tun_to_nmstate,
         veth::np_veth_to_nmstate,
         vlan::np_vlan_to_nmstate,
         vrf::np_vrf_to_nmstate,
         vxlan::np_vxlan_to_nmstate,
+        wol::append_wol_conf,
+        xfrm::append_xfrm_conf,
     },
-    DummyInterface, Interface, InterfaceType, Interfaces, LoopbackInterface,
-    NetworkState, NmstateError, OvsInterface, UnknownInterface,
+    BatadvInterface, CanInterface, DummyInterface, GeneveInterface,
+    GreInterface, GreTapInterface, GtpInterface, Interface, InterfaceType,
+    Interfaces, IpIpInterface, IpVlanInterface, L2tpv3Interface,
+    LoopbackInterface, NetkitInterface, NetworkState, NmstateError,
+    OvsInterface, PppoeInterface, SitInterface, UnknownInterface,
+    VcanInterface, Vti6Interface, VtiInterface, WifiInterface,
+    WireGuardInterface, XfrmInterface,
 };
 
 pub(crate) fn nispor_retrieve(
     running_config_only: bool,
+    include_fdb: bool,
 ) -> Result<NetworkState, NmstateError> {
     let mut net_state = NetworkState {
         hostname: get_hostname_state(),
-        prop_list: vec!["interfaces", "routes", "rules", "hostname"],
+        pci_devices: get_pci_devices(),
+        prop_list: vec![
+            "interfaces",
+            "routes",
+            "rules",
+            "hostname",
+            "pci_devices",
+        ],
         ..Default::default()
     };
     let mut filter = nispor::NetStateFilter::default();
@@ -62,7 +94,15 @@ pub(crate) fn nispor_retrieve(
                 Interface::LinuxBridge(br_iface)
             }
             InterfaceType::Bond => {
-                Interface::Bond(np_bond_to_nmstate(np_iface, base_iface))
+                let mut bond_iface = np_bond_to_nmstate(np_iface, base_iface);
+                let mut port_np_ifaces = Vec::new();
+                for port_name in bond_iface.ports().unwrap_or_default() {
+                    if let Some(p) = np_state.ifaces.get(port_name) {
+                        port_np_ifaces.push(p)
+                    }
+                }
+                append_bond_port_config(&mut bond_iface, port_np_ifaces);
+                Interface::Bond(bond_iface)
             }
             InterfaceType::Ethernet => Interface::Ethernet(
                 np_ethernet_to_nmstate(np_iface, base_iface),
@@ -76,6 +116,9 @@ pub(crate) fn nispor_retrieve(
             InterfaceType::Vxlan => {
                 Interface::Vxlan(np_vxlan_to_nmstate(np_iface, base_iface))
             }
+            InterfaceType::Tun => {
+                Interface::Tun(np_tun_to_nmstate(np_iface, base_iface))
+            }
             InterfaceType::Dummy => Interface::Dummy({
                 let mut iface = DummyInterface::new();
                 iface.base = base_iface;
@@ -110,6 +153,114 @@ pub(crate) fn nispor_retrieve(
             InterfaceType::Loopback => {
                 Interface::Loopback(LoopbackInterface { base: base_iface })
             }
+            InterfaceType::MacSec => {
+                Interface::MacSec(np_macsec_to_nmstate(np_iface, base_iface))
+            }
+            // Nispor does not provide WireGuard configuration yet
+            InterfaceType::WireGuard => Interface::WireGuard({
+                let mut iface = WireGuardInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // Nispor does not provide GRE configuration yet
+            InterfaceType::Gre => Interface::Gre({
+                let mut iface = GreInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::GreTap => Interface::GreTap({
+                let mut iface = GreTapInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // Nispor does not provide IPIP and SIT configuration yet, the
+            // 6rd prefix of SIT is appended by `append_sit_6rd_prefix()`
+            InterfaceType::IpIp => Interface::IpIp({
+                let mut iface = IpIpInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Sit => Interface::Sit({
+                let mut iface = SitInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // Nispor does not provide VTI configuration yet
+            InterfaceType::Vti => Interface::Vti({
+                let mut iface = VtiInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Vti6 => Interface::Vti6({
+                let mut iface = Vti6Interface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `geneve` section is appended by `append_geneve_conf()`
+            InterfaceType::Geneve => Interface::Geneve({
+                let mut iface = GeneveInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `gtp` section is appended by `append_gtp_conf()`
+            InterfaceType::Gtp => Interface::Gtp({
+                let mut iface = GtpInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `xfrm` section is appended by `append_xfrm_conf()`
+            InterfaceType::Xfrm => Interface::Xfrm({
+                let mut iface = XfrmInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `ipvlan` section is appended by `append_ipvlan_conf()`
+            InterfaceType::IpVlan => Interface::IpVlan({
+                let mut iface = IpVlanInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `l2tpv3` section is appended by `append_l2tpv3_conf()`
+            InterfaceType::L2tpv3 => Interface::L2tpv3({
+                let mut iface = L2tpv3Interface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `netkit` section is appended by `append_netkit_conf()`
+            InterfaceType::Netkit => Interface::Netkit({
+                let mut iface = NetkitInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `batadv` section is appended by `append_batadv_conf()`
+            InterfaceType::Batadv => Interface::Batadv({
+                let mut iface = BatadvInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `can` section is appended by `append_can_conf()`
+            InterfaceType::Can => Interface::Can({
+                let mut iface = CanInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Vcan => Interface::Vcan({
+                let mut iface = VcanInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `wifi` section is only stored in NetworkManager profile
+            InterfaceType::Wifi => Interface::Wifi({
+                let mut iface = WifiInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            // The `pppoe` section is only stored in NetworkManager profile
+            InterfaceType::Pppoe => Interface::Pppoe({
+                let mut iface = PppoeInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
             _ => {
                 log::info!(
                     "Got unsupported interface {} type {:?}",
@@ -126,6 +277,23 @@ pub(crate) fn nispor_retrieve(
         net_state.append_interface_data(iface);
     }
     set_controller_type(&mut net_state.interfaces);
+    append_sriov_vf_representors(&mut net_state.interfaces);
+    append_eswitch_mode(&mut net_state.interfaces);
+    append_fdb(&mut net_state.interfaces, &np_state.ifaces, include_fdb)?;
+    append_sit_6rd_prefix(&mut net_state.interfaces)?;
+    append_geneve_conf(&mut net_state.interfaces)?;
+    append_gtp_conf(&mut net_state.interfaces)?;
+    append_xfrm_conf(&mut net_state.interfaces)?;
+    append_ipvlan_conf(&mut net_state.interfaces)?;
+    append_l2tpv3_conf(&mut net_state.interfaces)?;
+    append_netkit_conf(&mut net_state.interfaces)?;
+    append_batadv_conf(&mut net_state.interfaces)?;
+    append_can_conf(&mut net_state.interfaces)?;
+    append_wol_conf(&mut net_state.interfaces)?;
+    append_eee_conf(&mut net_state.interfaces)?;
+    append_channels_conf(&mut net_state.interfaces)?;
+    append_coalesce_queues_conf(&mut net_state.interfaces);
+    append_flow_rules_conf(&mut net_state.interfaces);
     net_state.routes = get_routes(running_config_only);
     net_state.rules = get_route_rules(&np_state.rules, running_config_only);
 
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, TunConfig, TunInterface, TunMode};
+
+pub(crate) fn np_tun_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> TunInterface {
+    let tun_conf = np_iface.tun.as_ref().map(|np_tun_info| {
+        let mut conf = TunConfig::new();
+        conf.mode = match np_tun_info.mode {
+            nispor::TunMode::Tun => Some(TunMode::Tun),
+            nispor::TunMode::Tap => Some(TunMode::Tap),
+            _ => None,
+        };
+        conf.owner = np_tun_info.owner;
+        conf.group = np_tun_info.group;
+        conf.multi_queue = Some(np_tun_info.multi_queue);
+        conf.pi = Some(np_tun_info.pi);
+        conf
+    });
+
+    TunInterface {
+        base: base_iface,
+        tun: tun_conf,
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nispor::bond::{append_bond_port_config, np_bond_to_nmstate};
+use crate::{BaseInterface, BondConfig, BondInterface};
+
+fn gen_np_bond_port(name: &str, prio: i32, queue_id: u16) -> nispor::Iface {
+    let mut port_info = nispor::BondSubordinateInfo::default();
+    port_info.prio = prio;
+    port_info.queue_id = queue_id;
+    let mut np_iface = nispor::Iface::default();
+    np_iface.name = name.to_string();
+    np_iface.bond_subordinate = Some(port_info);
+    np_iface
+}
+
+#[test]
+fn test_np_bond_port_priority_and_queue_id() {
+    let mut bond_iface = BondInterface::new();
+    bond_iface.bond = Some(BondConfig::new());
+    let port1 = gen_np_bond_port("eth1", 10, 1);
+    let port2 = gen_np_bond_port("eth2", -5, 0);
+
+    append_bond_port_config(&mut bond_iface, vec![&port1, &port2]);
+
+    let port_confs = bond_iface
+        .bond
+        .as_ref()
+        .and_then(|b| b.ports_config.as_ref())
+        .unwrap();
+    assert_eq!(port_confs.len(), 2);
+    assert_eq!(port_confs[0].name, "eth1");
+    assert_eq!(port_confs[0].priority, Some(10));
+    assert_eq!(port_confs[0].queue_id, Some(1));
+    assert_eq!(port_confs[1].name, "eth2");
+    assert_eq!(port_confs[1].priority, Some(-5));
+    assert_eq!(port_confs[1].queue_id, Some(0));
+}
+
+#[test]
+fn test_np_bond_ns_ip6_target() {
+    let mut np_bond = nispor::BondInfo::default();
+    np_bond.ns_ip6_target = Some(vec![
+        "2001:db8::1".parse().unwrap(),
+        "2001:db8::2".parse().unwrap(),
+    ]);
+    let mut np_iface = nispor::Iface::default();
+    np_iface.name = "bond99".to_string();
+    np_iface.bond = Some(np_bond);
+
+    let bond_iface = np_bond_to_nmstate(&np_iface, BaseInterface::new());
+
+    assert_eq!(
+        bond_iface
+            .bond
+            .as_ref()
+            .and_then(|b| b.options.as_ref())
+            .and_then(|o| o.ns_ip6_target.as_deref()),
+        Some("2001:db8::1,2001:db8::2")
+    );
+}
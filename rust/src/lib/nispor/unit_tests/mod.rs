@@ -0,0 +1,3 @@
+mod bond;
+mod netlink;
+mod route;
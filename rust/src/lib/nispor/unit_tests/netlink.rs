@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::{
+    packet_core::{
+        DefaultNla, NetlinkPayload, Nla, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL,
+        NLM_F_REQUEST,
+    },
+    packet_route::{
+        link::{
+            InfoData, InfoKind, LinkAttribute, LinkFlags, LinkInfo, LinkMessage,
+        },
+        RouteNetlinkMessage,
+    },
+};
+
+use crate::nispor::netlink::{
+    emit_nlas, gen_link_down_request, gen_link_new_request, gen_nested_nla,
+    get_nla_bytes, parse_link_msg, parse_nested_nla, parse_nlas,
+};
+
+fn new_link_msg(attributes: Vec<LinkAttribute>) -> LinkMessage {
+    let mut link_msg = LinkMessage::default();
+    link_msg.header.index = 3;
+    link_msg.attributes = attributes;
+    link_msg
+}
+
+#[test]
+fn test_parse_link_msg() {
+    let link = parse_link_msg(new_link_msg(vec![
+        LinkAttribute::IfName("nk0".to_string()),
+        LinkAttribute::Link(4),
+        LinkAttribute::Controller(2),
+        LinkAttribute::LinkInfo(vec![
+            LinkInfo::Kind(InfoKind::Other("netkit".to_string())),
+            LinkInfo::Data(InfoData::Other(vec![1, 2, 3, 4])),
+        ]),
+    ]))
+    .unwrap();
+
+    assert_eq!(link.index, 3);
+    assert_eq!(link.name, "nk0");
+    assert_eq!(link.link, Some(4));
+    assert_eq!(link.controller, Some(2));
+    assert!(link.is_other_kind("netkit"));
+    assert!(!link.is_other_kind("can"));
+    assert_eq!(link.data, Some(InfoData::Other(vec![1, 2, 3, 4])));
+}
+
+#[test]
+fn test_parse_link_msg_link_in_other_netns() {
+    let link = parse_link_msg(new_link_msg(vec![
+        LinkAttribute::Link(4),
+        LinkAttribute::LinkNetNsId(1),
+        LinkAttribute::IfName("nk0".to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(link.link, None);
+}
+
+#[test]
+fn test_parse_link_msg_without_name() {
+    assert!(
+        parse_link_msg(new_link_msg(vec![LinkAttribute::Link(4)])).is_none()
+    );
+}
+
+#[test]
+fn test_gen_link_new_request() {
+    let link_info = vec![LinkInfo::Kind(InfoKind::Other("vcan".to_string()))];
+    let req = gen_link_new_request(
+        "vcan0",
+        vec![LinkAttribute::Link(2)],
+        link_info.clone(),
+        false,
+    );
+    assert_eq!(req.header.flags, NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE);
+    let nl_msg = match req.payload {
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewLink(m)) => m,
+        _ => panic!("Expecting NewLink message"),
+    };
+    assert_eq!(nl_msg.header.flags, LinkFlags::Up);
+    assert_eq!(nl_msg.header.change_mask, LinkFlags::Up);
+    assert_eq!(
+        nl_msg.attributes,
+        vec![
+            LinkAttribute::IfName("vcan0".to_string()),
+            LinkAttribute::Link(2),
+            LinkAttribute::LinkInfo(link_info.clone()),
+        ]
+    );
+
+    let req = gen_link_new_request("vcan0", Vec::new(), link_info, true);
+    assert_eq!(
+        req.header.flags,
+        NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL
+    );
+}
+
+#[test]
+fn test_gen_link_down_request() {
+    let req = gen_link_down_request(5);
+    assert_eq!(req.header.flags, NLM_F_REQUEST | NLM_F_ACK);
+    let nl_msg = match req.payload {
+        NetlinkPayload::InnerMessage(RouteNetlinkMessage::NewLink(m)) => m,
+        _ => panic!("Expecting NewLink message"),
+    };
+    assert_eq!(nl_msg.header.index, 5);
+    assert!(nl_msg.header.flags.is_empty());
+    assert_eq!(nl_msg.header.change_mask, LinkFlags::Up);
+}
+
+#[test]
+fn test_emit_and_parse_nlas() {
+    let nlas = vec![
+        DefaultNla::new(1, b"batman_v\0".to_vec()),
+        DefaultNla::new(6, 100u32.to_ne_bytes().to_vec()),
+    ];
+    let data = emit_nlas(&nlas);
+    // The 9 bytes string attribute is padded to 4 bytes alignment
+    assert_eq!(data.len(), 16 + 8);
+
+    let parsed = parse_nlas(&data);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].kind(), 1);
+    assert_eq!(get_nla_bytes(&parsed[0]), b"batman_v\0".to_vec());
+    assert_eq!(parsed[1].kind(), 6);
+    assert_eq!(get_nla_bytes(&parsed[1]), 100u32.to_ne_bytes().to_vec());
+}
+
+#[test]
+fn test_parse_nlas_ignore_truncated() {
+    let mut data = emit_nlas(&[DefaultNla::new(1, vec![1, 2, 3, 4])]);
+    // Attribute header claims 12 bytes but only 1 byte of value follows
+    data.extend_from_slice(&12u16.to_ne_bytes());
+    data.extend_from_slice(&2u16.to_ne_bytes());
+    data.push(1);
+
+    let parsed = parse_nlas(&data);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(get_nla_bytes(&parsed[0]), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_gen_and_parse_nested_nla() {
+    let nlas = vec![DefaultNla::new(2, b"eth1\0".to_vec())];
+    let nested = gen_nested_nla(1, &nlas);
+
+    let parsed = parse_nested_nla(&nested);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].kind(), 2);
+    assert_eq!(get_nla_bytes(&parsed[0]), b"eth1\0".to_vec());
+}
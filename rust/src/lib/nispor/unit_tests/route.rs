@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use rtnetlink::packet_route::{route::RouteAttribute, RouteNetlinkMessage};
+
+use crate::nispor::route::{
+    gen_route_add_request, is_np_route_table_supported, nmstate_route_to_np,
+};
+use crate::RouteEntry;
+
+#[test]
+fn test_route_big_table_id_to_netlink() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        next-hop-address: 192.0.2.254
+        table-id: 1000
+        metric: 150
+        "#,
+    )
+    .unwrap();
+
+    assert!(!is_np_route_table_supported(&route));
+    assert!(nmstate_route_to_np(&route).is_none());
+
+    let req = gen_route_add_request(&route, 5).unwrap();
+    let nl_msg = match req.payload {
+        rtnetlink::packet_core::NetlinkPayload::InnerMessage(
+            RouteNetlinkMessage::NewRoute(m),
+        ) => m,
+        _ => panic!("Expecting NewRoute message"),
+    };
+    assert_eq!(nl_msg.header.destination_prefix_length, 24);
+    assert!(nl_msg.attributes.contains(&RouteAttribute::Table(1000)));
+    assert!(nl_msg.attributes.contains(&RouteAttribute::Oif(5)));
+    assert!(nl_msg.attributes.contains(&RouteAttribute::Priority(150)));
+}
+
+#[test]
+fn test_route_small_table_id_to_nispor() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        table-id: 100
+        "#,
+    )
+    .unwrap();
+
+    assert!(is_np_route_table_supported(&route));
+    assert_eq!(nmstate_route_to_np(&route).unwrap().table, Some(100));
+}
@@ -12,6 +12,7 @@ pub(crate) fn np_veth_to_nmstate(
         } else {
             Some(VethConfig {
                 peer: np_veth_info.peer.clone(),
+                ..Default::default()
             })
         }
     });
@@ -15,6 +15,7 @@ pub(crate) fn np_vxlan_to_nmstate(
         local: std::net::IpAddr::from_str(np_vxlan_info.local.as_str()).ok(),
         remote: std::net::IpAddr::from_str(np_vxlan_info.remote.as_str()).ok(),
         dst_port: Some(np_vxlan_info.dst_port),
+        flood_remotes: None,
     });
 
     VxlanInterface {
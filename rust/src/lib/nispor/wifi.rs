@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not distinguish Wi-Fi interface from ethernet, the cfg80211
+// based wireless interface has `phy80211` link in sysfs.
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
+pub(crate) fn np_is_wifi(np_iface: &nispor::Iface) -> bool {
+    std::path::Path::new(&format!(
+        "{SYSFS_NET_PATH}/{}/phy80211",
+        np_iface.name
+    ))
+    .exists()
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use rtnetlink::packet_core::{DefaultNla, Nla, NLM_F_ACK, NLM_F_DUMP};
+
+use super::{
+    ethtool::{
+        gen_ethtool_header, get_ethtool_dev_name, ETHTOOL_GENL_NAME,
+        ETHTOOL_GENL_VERSION,
+    },
+    netlink::{
+        gen_nested_nla, get_nla_bytes, run_genl_requests, GenlRawMessage,
+    },
+};
+use crate::{
+    EthernetConfig, EthernetWakeOnLanConfig, Interface, InterfaceType,
+    Interfaces, MergedNetworkState, NetworkState, NmstateError,
+};
+
+const WOL_ACTION: &str = "query or change wake-on-lan";
+
+const ETHTOOL_MSG_WOL_GET: u8 = 9;
+const ETHTOOL_MSG_WOL_SET: u8 = 10;
+
+const ETHTOOL_A_WOL_HEADER: u16 = 1;
+const ETHTOOL_A_WOL_MODES: u16 = 2;
+const ETHTOOL_A_WOL_SOPASS: u16 = 3;
+
+const ETHTOOL_A_BITSET_SIZE: u16 = 2;
+const ETHTOOL_A_BITSET_VALUE: u16 = 4;
+const ETHTOOL_A_BITSET_MASK: u16 = 5;
+
+const WOL_MODE_COUNT: u32 = 8;
+const WAKE_UCAST: u32 = 1 << 1;
+const WAKE_BCAST: u32 = 1 << 3;
+const WAKE_MAGIC: u32 = 1 << 5;
+const WAKE_MAGICSECURE: u32 = 1 << 6;
+// The wake up methods managed by nmstate, others are untouched
+const WAKE_NMSTATE_MASK: u32 =
+    WAKE_UCAST | WAKE_BCAST | WAKE_MAGIC | WAKE_MAGICSECURE;
+
+// Nispor does not support wake-on-lan yet.
+pub(crate) fn append_wol_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Ethernet)
+    {
+        return Ok(());
+    }
+    let req = GenlRawMessage::new(
+        ETHTOOL_MSG_WOL_GET,
+        ETHTOOL_GENL_VERSION,
+        vec![gen_ethtool_header(ETHTOOL_A_WOL_HEADER, None, true)],
+    )
+    .gen_request(NLM_F_DUMP);
+    for msg in
+        run_genl_requests(ETHTOOL_GENL_NAME, vec![req], false, WOL_ACTION)?
+    {
+        let iface_name = match get_ethtool_dev_name(&msg, ETHTOOL_A_WOL_HEADER)
+        {
+            Some(n) => n,
+            None => continue,
+        };
+        if let Some(Interface::Ethernet(eth_iface)) =
+            ifaces.kernel_ifaces.get_mut(&iface_name)
+        {
+            if let Some(wol_conf) = parse_wol_reply(&msg) {
+                eth_iface
+                    .ethernet
+                    .get_or_insert_with(EthernetConfig::new)
+                    .wake_on_lan = Some(wol_conf);
+            }
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager backend applies wake-on-lan via `802-3-ethernet` setting,
+// this is only used by kernel backend.
+pub(crate) fn nispor_apply_wol(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut reqs = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        if let Some(Interface::Ethernet(eth_iface)) =
+            merged_iface.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            if let Some(wol_conf) = eth_iface
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.wake_on_lan.as_ref())
+            {
+                log::info!(
+                    "Changing wake-on-lan of interface {}",
+                    eth_iface.base.name
+                );
+                reqs.push(
+                    gen_wol_set_msg(eth_iface.base.name.as_str(), wol_conf)
+                        .gen_request(NLM_F_ACK),
+                );
+            }
+        }
+    }
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    run_genl_requests(ETHTOOL_GENL_NAME, reqs, false, WOL_ACTION)?;
+    Ok(())
+}
+
+// Return None if interface does not support any wake up method managed by
+// nmstate.
+fn parse_wol_reply(msg: &GenlRawMessage) -> Option<EthernetWakeOnLanConfig> {
+    let mut modes = 0u32;
+    let mut supported = 0u32;
+    for nla in msg.get_nla_nested(ETHTOOL_A_WOL_MODES) {
+        let value = match get_nla_bytes(&nla).get(..4).map(<[u8; 4]>::try_from)
+        {
+            Some(Ok(v)) => u32::from_ne_bytes(v),
+            _ => continue,
+        };
+        match nla.kind() {
+            ETHTOOL_A_BITSET_VALUE => modes = value,
+            ETHTOOL_A_BITSET_MASK => supported = value,
+            _ => (),
+        }
+    }
+    if supported & WAKE_NMSTATE_MASK == 0 {
+        return None;
+    }
+    let mut conf = EthernetWakeOnLanConfig::new();
+    conf.magic = Some(modes & (WAKE_MAGIC | WAKE_MAGICSECURE) > 0);
+    conf.unicast = Some(modes & WAKE_UCAST > 0);
+    conf.broadcast = Some(modes & WAKE_BCAST > 0);
+    if modes & WAKE_MAGICSECURE > 0 {
+        conf.password = msg.get_nla_value(ETHTOOL_A_WOL_SOPASS).map(|v| {
+            v.iter()
+                .map(|b| format!("{b:02X}"))
+                .collect::<Vec<String>>()
+                .join(":")
+        });
+    }
+    Some(conf)
+}
+
+fn gen_wol_set_msg(
+    iface_name: &str,
+    wol_conf: &EthernetWakeOnLanConfig,
+) -> GenlRawMessage {
+    let password = wol_conf
+        .password
+        .as_deref()
+        .filter(|p| *p != NetworkState::PASSWORD_HID_BY_NMSTATE);
+    let mut modes = 0u32;
+    if wol_conf.magic == Some(true) {
+        modes |= if password.is_some() {
+            WAKE_MAGICSECURE
+        } else {
+            WAKE_MAGIC
+        };
+    }
+    if wol_conf.unicast == Some(true) {
+        modes |= WAKE_UCAST;
+    }
+    if wol_conf.broadcast == Some(true) {
+        modes |= WAKE_BCAST;
+    }
+    let mut nlas = vec![
+        gen_ethtool_header(ETHTOOL_A_WOL_HEADER, Some(iface_name), true),
+        gen_nested_nla(
+            ETHTOOL_A_WOL_MODES,
+            &[
+                DefaultNla::new(
+                    ETHTOOL_A_BITSET_SIZE,
+                    WOL_MODE_COUNT.to_ne_bytes().to_vec(),
+                ),
+                DefaultNla::new(
+                    ETHTOOL_A_BITSET_VALUE,
+                    modes.to_ne_bytes().to_vec(),
+                ),
+                DefaultNla::new(
+                    ETHTOOL_A_BITSET_MASK,
+                    WAKE_NMSTATE_MASK.to_ne_bytes().to_vec(),
+                ),
+            ],
+        ),
+    ];
+    // The password is validated as MAC address format by sanitize()
+    if let Some(password) = password {
+        nlas.push(DefaultNla::new(
+            ETHTOOL_A_WOL_SOPASS,
+            password
+                .split(':')
+                .filter_map(|s| u8::from_str_radix(s, 16).ok())
+                .collect(),
+        ));
+    }
+    GenlRawMessage::new(ETHTOOL_MSG_WOL_SET, ETHTOOL_GENL_VERSION, nlas)
+}
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use rtnetlink::{
+    packet_core::NetlinkMessage,
+    packet_route::{
+        link::{InfoData, InfoKind, InfoXfrm, LinkInfo},
+        RouteNetlinkMessage,
+    },
+};
+
+use super::netlink::{gen_link_new_request, get_links, run_netlink_requests};
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedNetworkState,
+    NmstateError, XfrmConfig, XfrmInterface,
+};
+
+const XFRM_ACTION: &str = "query or change XFRM interface";
+
+// Nispor does not support XFRM interface yet.
+pub(crate) fn append_xfrm_conf(
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    if !ifaces
+        .kernel_ifaces
+        .values()
+        .any(|i| i.iface_type() == InterfaceType::Xfrm)
+    {
+        return Ok(());
+    }
+    let links = get_links(XFRM_ACTION)?;
+    let index_to_name: HashMap<u32, &str> =
+        links.iter().map(|l| (l.index, l.name.as_str())).collect();
+    for link in links.iter() {
+        if let (Some(InfoData::Xfrm(nlas)), Some(Interface::Xfrm(xfrm_iface))) =
+            (link.data.as_ref(), ifaces.kernel_ifaces.get_mut(&link.name))
+        {
+            let mut conf = XfrmConfig::new();
+            for nla in nlas {
+                match nla {
+                    InfoXfrm::IfId(d) => conf.if_id = *d,
+                    InfoXfrm::Link(d) if *d != 0 => {
+                        conf.parent =
+                            index_to_name.get(d).map(|n| n.to_string())
+                    }
+                    _ => (),
+                }
+            }
+            xfrm_iface.xfrm = Some(conf);
+        }
+    }
+    Ok(())
+}
+
+// NetworkManager does not support XFRM interface, hence both backends create
+// or modify the XFRM interface via netlink before activating the profiles.
+pub(crate) fn nispor_apply_xfrm(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let xfrm_ifaces: Vec<&XfrmInterface> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+        .filter_map(|i| {
+            if let Some(Interface::Xfrm(iface)) =
+                i.for_apply.as_ref().filter(|i| i.is_up())
+            {
+                Some(iface)
+            } else {
+                None
+            }
+        })
+        .collect();
+    if xfrm_ifaces.is_empty() {
+        return Ok(());
+    }
+    let name_to_index: HashMap<String, u32> = get_links(XFRM_ACTION)?
+        .into_iter()
+        .map(|l| (l.name, l.index))
+        .collect();
+
+    let mut reqs = Vec::new();
+    for xfrm_iface in xfrm_ifaces {
+        let parent_index = match xfrm_iface.parent() {
+            Some(parent) => match name_to_index.get(parent) {
+                Some(i) => Some(*i),
+                None => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The parent {} of XFRM interface {} does not \
+                            exist",
+                            parent, xfrm_iface.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            },
+            None => None,
+        };
+        log::info!(
+            "Creating or modifying XFRM interface {}",
+            xfrm_iface.base.name
+        );
+        reqs.push(gen_xfrm_new_request(xfrm_iface, parent_index));
+    }
+    run_netlink_requests(reqs, true, XFRM_ACTION)?;
+    Ok(())
+}
+
+// Without NLM_F_EXCL, kernel will modify the `if_id` and `link` of existing
+// XFRM interface.
+fn gen_xfrm_new_request(
+    xfrm_iface: &XfrmInterface,
+    parent_index: Option<u32>,
+) -> NetlinkMessage<RouteNetlinkMessage> {
+    let mut nlas = Vec::new();
+    if let Some(conf) = xfrm_iface.xfrm.as_ref() {
+        nlas.push(InfoXfrm::IfId(conf.if_id));
+    }
+    if let Some(parent_index) = parent_index {
+        nlas.push(InfoXfrm::Link(parent_index));
+    }
+
+    gen_link_new_request(
+        &xfrm_iface.base.name,
+        Vec::new(),
+        vec![
+            LinkInfo::Kind(InfoKind::Xfrm),
+            LinkInfo::Data(InfoData::Xfrm(nlas)),
+        ],
+        false,
+    )
+}
@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    checkpoint::{
+        nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_rollback,
+        nm_checkpoint_timeout_extend,
+    },
+    nm_conf::{nm_conf_apply, nm_conf_restore, NmConfBackup},
+    query_apply::{nm_apply, nm_plan},
+    saved::nm_retrieve_saved,
+    show::{nm_retrieve, nm_retrieve_dns},
+};
+use crate::{
+    nispor::{
+        nispor_apply_batadv, nispor_apply_can, nispor_apply_coalesce_queues,
+        nispor_apply_eee, nispor_apply_eswitch_mode, nispor_apply_fdb,
+        nispor_apply_flow_rules, nispor_apply_gtp, nispor_apply_l2tpv3,
+        nispor_apply_link_tuning, nispor_apply_mcast_groups,
+        nispor_apply_netkit, nispor_apply_pci_devices,
+        nispor_apply_sit_6rd_prefix, nispor_apply_xfrm, nispor_delete_ifaces,
+        nispor_restore_external_ip, nispor_restore_pci_devices,
+    },
+    ovsdb::{ovsdb_apply, ovsdb_is_running},
+    query_apply::{apply_running_hostname, NetworkBackend},
+    DnsState, InterfaceType, MergedNetworkState, NetworkPlan, NetworkState,
+    NmstateError, PciDeviceConfig,
+};
+
+const RETRY_NM_COUNT: usize = 2;
+const RETRY_NM_INTERVAL_MILLISECONDS: u64 = 2000;
+
+// Interfaces created via netlink instead of NetworkManager, hence not removed
+// by checkpoint rollback.
+const NETLINK_CREATED_IFACE_TYPES: [InterfaceType; 6] = [
+    InterfaceType::Gtp,
+    InterfaceType::Xfrm,
+    InterfaceType::L2tpv3,
+    InterfaceType::Netkit,
+    InterfaceType::Batadv,
+    InterfaceType::Vcan,
+];
+
+/// Apply network state via NetworkManager under the protection of
+/// NetworkManager checkpoint unless `no_checkpoint` is set.
+/// Besides the checkpoint, rollback restores the NetworkManager global
+/// configuration, the PCI driver binding and removes the GTP, XFRM, L2TPv3,
+/// netkit, batman-adv and vcan interfaces created via netlink. Other netlink
+/// changes are not rolled back: CAN bit timing, eSwitch mode and the
+/// properties of existing interfaces of above types.
+#[derive(Debug, Default)]
+pub(crate) struct NmBackend {
+    no_checkpoint: bool,
+    checkpoint: Option<String>,
+    // Previous content of nmstate owned NetworkManager configuration files,
+    // which are not covered by checkpoint.
+    nm_conf_backup: Option<NmConfBackup>,
+    // Previous driver binding of changed PCI devices and whether persisted.
+    pci_backup: Option<(Vec<PciDeviceConfig>, bool)>,
+    // Interfaces created via netlink, removed on rollback.
+    netlink_created_ifaces: Vec<String>,
+}
+
+impl NmBackend {
+    pub(crate) fn new(no_checkpoint: bool) -> Self {
+        Self {
+            no_checkpoint,
+            ..Default::default()
+        }
+    }
+
+    // Extend the timeout of checkpoint if created. Nothing to do when
+    // applying without checkpoint.
+    fn checkpoint_timeout_extend(
+        &self,
+        timeout: u32,
+    ) -> Result<(), NmstateError> {
+        if let Some(checkpoint) = self.checkpoint.as_deref() {
+            nm_checkpoint_timeout_extend(checkpoint, timeout)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl NetworkBackend for NmBackend {
+    fn name(&self) -> &'static str {
+        "NetworkManager"
+    }
+
+    fn query(
+        &mut self,
+        running_config_only: bool,
+        unprivileged: bool,
+    ) -> Result<NetworkState, NmstateError> {
+        nm_retrieve(running_config_only, unprivileged)
+    }
+
+    fn query_dns(
+        &mut self,
+        running_config_only: bool,
+    ) -> Result<DnsState, NmstateError> {
+        nm_retrieve_dns(running_config_only)
+    }
+
+    fn query_saved(&mut self) -> Result<NetworkState, NmstateError> {
+        nm_retrieve_saved()
+    }
+
+    fn prepare(&mut self, timeout: u32) -> Result<(), NmstateError> {
+        if self.no_checkpoint {
+            log::warn!(
+                "Applying without NetworkManager checkpoint, the changes \
+                will NOT be rolled back on failure"
+            );
+            return Ok(());
+        }
+        let checkpoint = match nm_checkpoint_create(timeout) {
+            Ok(c) => c,
+            Err(e) => {
+                if e.kind().can_retry() {
+                    log::info!("Retrying on: {}", e);
+                    std::thread::sleep(std::time::Duration::from_millis(
+                        RETRY_NM_INTERVAL_MILLISECONDS,
+                    ));
+                    nm_checkpoint_create(timeout)?
+                } else {
+                    return Err(e);
+                }
+            }
+        };
+        log::info!("Created checkpoint {}", &checkpoint);
+        self.checkpoint = Some(checkpoint);
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        timeout: u32,
+    ) -> Result<(), NmstateError> {
+        self.checkpoint_timeout_extend(timeout)?;
+        if let Some(nm_conf) = merged_state.networkmanager.for_apply() {
+            if merged_state.stage_only {
+                log::info!(
+                    "NM: Global configuration will be changed when \
+                    activating staged profiles"
+                );
+            } else {
+                let backup = nm_conf_apply(&nm_conf)?;
+                // Retry of apply should not override the original backup
+                if self.nm_conf_backup.is_none() {
+                    self.nm_conf_backup = Some(backup);
+                }
+            }
+        }
+        // The PCI driver should be bound before activating profiles
+        if !merged_state.stage_only {
+            let persist = !merged_state.memory_only;
+            let old_devs = nispor_apply_pci_devices(merged_state, persist)?;
+            if self.pci_backup.is_none() && !old_devs.is_empty() {
+                self.pci_backup = Some((old_devs, persist));
+            }
+            // VFs and VF representors are recreated by eswitch mode change
+            nispor_apply_eswitch_mode(merged_state)?;
+            // NetworkManager cannot create GTP, XFRM, vcan, L2TPv3, netkit
+            // and batman-adv interfaces or configure CAN interfaces
+            for iface_name in get_netlink_new_ifaces(merged_state) {
+                if !self.netlink_created_ifaces.contains(&iface_name) {
+                    self.netlink_created_ifaces.push(iface_name);
+                }
+            }
+            nispor_apply_gtp(merged_state)?;
+            nispor_apply_xfrm(merged_state)?;
+            nispor_apply_l2tpv3(merged_state)?;
+            nispor_apply_netkit(merged_state)?;
+            nispor_apply_batadv(merged_state)?;
+            nispor_apply_can(merged_state)?;
+        }
+        nm_apply(merged_state, self.checkpoint.as_deref(), timeout)?;
+        // Runtime only changes are done when activating staged profiles
+        if merged_state.stage_only {
+            return Ok(());
+        }
+        nispor_restore_external_ip(merged_state)?;
+        nispor_apply_fdb(merged_state)?;
+        nispor_apply_mcast_groups(merged_state)?;
+        nispor_apply_link_tuning(merged_state)?;
+        nispor_apply_eee(merged_state)?;
+        nispor_apply_coalesce_queues(merged_state)?;
+        nispor_apply_flow_rules(merged_state)?;
+        nispor_apply_sit_6rd_prefix(merged_state)?;
+        if merged_state.is_global_ovsdb_changed() && ovsdb_is_running() {
+            ovsdb_apply(merged_state)?;
+        }
+        apply_running_hostname(merged_state)
+    }
+
+    fn verify(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        current: &NetworkState,
+        timeout: u32,
+    ) -> Result<(), NmstateError> {
+        self.checkpoint_timeout_extend(timeout)?;
+        merged_state.verify(current)
+    }
+
+    fn plan(
+        &mut self,
+        merged_state: &MergedNetworkState,
+    ) -> Result<NetworkPlan, NmstateError> {
+        nm_plan(merged_state)
+    }
+
+    fn commit(&mut self) -> Result<(), NmstateError> {
+        self.nm_conf_backup = None;
+        self.pci_backup = None;
+        self.netlink_created_ifaces.clear();
+        if let Some(checkpoint) = self.checkpoint.take() {
+            nm_checkpoint_destroy(&checkpoint)?;
+            log::info!("Destroyed checkpoint {}", checkpoint);
+        }
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), NmstateError> {
+        if let Some(checkpoint) = self.checkpoint.take() {
+            nm_checkpoint_rollback(&checkpoint)?;
+            log::info!("Rollbacked to checkpoint {}", checkpoint);
+        } else if self.no_checkpoint {
+            log::warn!(
+                "No checkpoint to rollback, the NetworkManager profiles \
+                and devices are left in the failed state"
+            );
+        }
+        let created_ifaces = std::mem::take(&mut self.netlink_created_ifaces);
+        if !created_ifaces.is_empty() {
+            nispor_delete_ifaces(
+                created_ifaces
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<&str>>()
+                    .as_slice(),
+            )?;
+            log::info!(
+                "Removed interfaces created via netlink: {}",
+                created_ifaces.join(", ")
+            );
+        }
+        if let Some(backup) = self.nm_conf_backup.take() {
+            nm_conf_restore(backup)?;
+            log::info!("Restored NetworkManager global configuration");
+        }
+        if let Some((old_devs, persist)) = self.pci_backup.take() {
+            nispor_restore_pci_devices(&old_devs, persist)?;
+            log::info!("Restored PCI device driver binding");
+        }
+        Ok(())
+    }
+
+    fn checkpoint_path(&self) -> Option<String> {
+        self.checkpoint.clone()
+    }
+
+    // NM might have unknown race problem found by verify stage,
+    // we try to apply the state again if so.
+    fn apply_retry_count(&self) -> usize {
+        RETRY_NM_COUNT
+    }
+}
+
+// The new interfaces which will be created via netlink.
+pub(crate) fn get_netlink_new_ifaces(
+    merged_state: &MergedNetworkState,
+) -> Vec<String> {
+    let mut ret: Vec<String> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.current.is_none())
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| {
+            i.is_up() && NETLINK_CREATED_IFACE_TYPES.contains(&i.iface_type())
+        })
+        .map(|i| i.name().to_string())
+        .collect();
+    ret.sort_unstable();
+    ret
+}
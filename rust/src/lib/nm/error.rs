@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::io::{Read, Seek, SeekFrom};
+
 use crate::nm::nm_dbus::{
     ErrorKind as NmErrorKind, NmConnectionError, NmError, NmManagerError,
     NmSettingError,
@@ -7,17 +9,28 @@ use crate::nm::nm_dbus::{
 
 use crate::{ErrorKind, NmstateError};
 
+const SELINUX_ENFORCE_PATH: &str = "/sys/fs/selinux/enforce";
+const AUDIT_LOG_PATH: &str = "/var/log/audit/audit.log";
+// The AVC denial we are looking for just happened, no need to read the
+// whole audit log which could be hundreds of megabytes.
+const AUDIT_LOG_TAIL_SIZE: u64 = 256 * 1024;
+
 pub(crate) fn nm_error_to_nmstate(nm_error: NmError) -> NmstateError {
     match nm_error.kind {
+        NmErrorKind::CheckpointNotSupported => NmstateError::new(
+            ErrorKind::NotSupportedError,
+            format!(
+                "{}, please disable checkpoint(e.g. `nmstatectl apply \
+                --no-checkpoint`) to apply without rollback protection",
+                nm_error.msg
+            ),
+        ),
         NmErrorKind::Manager(NmManagerError::MissingPlugin) => {
             NmstateError::new(ErrorKind::DependencyError, nm_error.to_string())
         }
         NmErrorKind::Manager(NmManagerError::PermissionDenied) => {
             if nm_error.msg.starts_with("Not authorized") {
-                NmstateError::new(
-                    ErrorKind::PermissionError,
-                    nm_error.to_string(),
-                )
+                nm_permission_error(&nm_error)
             } else {
                 NmstateError::new(
                     ErrorKind::Bug,
@@ -25,9 +38,8 @@ pub(crate) fn nm_error_to_nmstate(nm_error: NmError) -> NmstateError {
                 )
             }
         }
-        NmErrorKind::Setting(NmSettingError::PermissionDenied) => {
-            NmstateError::new(ErrorKind::PermissionError, nm_error.to_string())
-        }
+        NmErrorKind::Setting(NmSettingError::PermissionDenied)
+        | NmErrorKind::PermissionDenied => nm_permission_error(&nm_error),
         NmErrorKind::Connection(NmConnectionError::InvalidSetting) => {
             NmstateError::new(
                 ErrorKind::DependencyError,
@@ -46,3 +58,98 @@ pub(crate) fn nm_error_to_nmstate(nm_error: NmError) -> NmstateError {
         ),
     }
 }
+
+fn nm_permission_error(nm_error: &NmError) -> NmstateError {
+    let selinux_enforcing = is_selinux_enforcing();
+    let avc_denial = if selinux_enforcing {
+        get_last_dbus_avc_denial()
+    } else {
+        None
+    };
+    let hints = gen_permission_hints(
+        nm_error.msg.as_str(),
+        nix::unistd::geteuid().is_root(),
+        selinux_enforcing,
+        avc_denial.as_deref(),
+    );
+    let e = NmstateError::new(
+        ErrorKind::PermissionError,
+        format!("{nm_error}. {}", hints.join(" ")),
+    );
+    log::error!("{}", e);
+    e
+}
+
+// Generate remediation guidance for permission failure of NetworkManager
+// D-Bus calls.
+pub(crate) fn gen_permission_hints(
+    msg: &str,
+    is_root: bool,
+    selinux_enforcing: bool,
+    avc_denial: Option<&str>,
+) -> Vec<String> {
+    let mut hints = Vec::new();
+    if !is_root {
+        hints.push(
+            "Nmstate is not running as root, please run as root or grant \
+            permission for org.freedesktop.NetworkManager.* actions to \
+            current user via polkit rule."
+                .to_string(),
+        );
+    } else if msg.contains("Not authorized") || msg.contains("polkit") {
+        hints.push(
+            "Please check the polkit policy of \
+            org.freedesktop.NetworkManager.* actions."
+                .to_string(),
+        );
+    }
+    if let Some(avc_denial) = avc_denial {
+        hints.push(format!("SELinux denial found in audit log: {avc_denial}"));
+    } else if selinux_enforcing {
+        hints.push(
+            "SELinux is in enforcing mode, please check AVC denials via \
+            `ausearch -m avc -ts recent`."
+                .to_string(),
+        );
+    }
+    hints
+}
+
+fn is_selinux_enforcing() -> bool {
+    std::fs::read_to_string(SELINUX_ENFORCE_PATH)
+        .map(|c| c.trim() == "1")
+        .unwrap_or_default()
+}
+
+// The audit log is only readable by root, we just silently return None when
+// failed to read it.
+fn get_last_dbus_avc_denial() -> Option<String> {
+    let content = read_file_tail(AUDIT_LOG_PATH, AUDIT_LOG_TAIL_SIZE)?;
+    find_last_dbus_avc_denial(&content).map(|l| l.to_string())
+}
+
+// Read at most `max_size` bytes from the end of file, the partial line at the
+// beginning of the tail is discarded.
+pub(crate) fn read_file_tail(file_path: &str, max_size: u64) -> Option<String> {
+    let mut fd = std::fs::File::open(file_path).ok()?;
+    let offset = fd.metadata().ok()?.len().saturating_sub(max_size);
+    fd.seek(SeekFrom::Start(offset)).ok()?;
+    let mut content = Vec::new();
+    fd.read_to_end(&mut content).ok()?;
+    let content = String::from_utf8_lossy(&content);
+    if offset > 0 {
+        content
+            .split_once('\n')
+            .map(|(_, remain)| remain.to_string())
+    } else {
+        Some(content.to_string())
+    }
+}
+
+pub(crate) fn find_last_dbus_avc_denial(content: &str) -> Option<&str> {
+    content.lines().rev().find(|line| {
+        line.contains("avc:")
+            && line.contains("denied")
+            && (line.contains("dbus") || line.contains("NetworkManager"))
+    })
+}
@@ -23,6 +23,20 @@ pub(crate) fn nm_gen_conf(
         );
     }
 
+    if merged_state.networkmanager.desired.is_some() {
+        log::warn!(
+            "Cannot store NetworkManager global configuration to keyfile, \
+            please edit /etc/NetworkManager/conf.d manually"
+        );
+    }
+
+    if !merged_state.pci_devices.desired.is_empty() {
+        log::warn!(
+            "Cannot store PCI device driver binding to keyfile, \
+            please use driverctl manually"
+        );
+    }
+
     let mut merged_state = merged_state.clone();
     store_route_config(&mut merged_state)?;
     store_route_rule_config(&mut merged_state)?;
@@ -3,6 +3,8 @@
 #[cfg(feature = "query_apply")]
 mod active_connection;
 #[cfg(feature = "query_apply")]
+mod backend;
+#[cfg(feature = "query_apply")]
 mod checkpoint;
 #[cfg(feature = "query_apply")]
 mod device;
@@ -11,27 +13,35 @@ pub(crate) mod dns;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+#[cfg(feature = "query_apply")]
+mod nm_conf;
 mod nm_dbus;
 mod profile;
 #[cfg(feature = "query_apply")]
 mod query_apply;
 pub(crate) mod route;
 pub(crate) mod route_rule;
+#[cfg(feature = "query_apply")]
+mod saved;
 mod settings;
 #[cfg(feature = "query_apply")]
 mod show;
+#[cfg(feature = "query_apply")]
+mod team;
 #[cfg(test)]
 mod unit_tests;
 mod version;
 
 #[cfg(feature = "query_apply")]
-pub(crate) use checkpoint::{
-    nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_rollback,
-    nm_checkpoint_timeout_extend,
-};
+pub(crate) use backend::NmBackend;
+#[cfg(feature = "query_apply")]
+pub(crate) use checkpoint::{nm_checkpoint_destroy, nm_checkpoint_rollback};
 #[cfg(feature = "gen_conf")]
 pub(crate) use gen_conf::nm_gen_conf;
 #[cfg(feature = "query_apply")]
-pub(crate) use query_apply::nm_apply;
+pub(crate) use team::nm_retrieve_team_as_bond;
 #[cfg(feature = "query_apply")]
-pub(crate) use show::nm_retrieve;
+pub(crate) use version::{
+    nm_version, NmPlugins, NM_VERSION_GLOBAL_DNS, NM_VERSION_MPTCP,
+    NM_VERSION_VETH,
+};
@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{error::nm_error_to_nmstate, nm_dbus::NmApi};
+use crate::{ErrorKind, NetworkManagerConfig, NmstateError};
+
+const NM_CONF_FILE: &str = "/etc/NetworkManager/conf.d/99-nmstate.conf";
+const NM_CONF_HEADER: &str = "# Generated by nmstate, do not edit";
+// Options only used by nmstate when generating NetworkManager connections,
+// hence not stored in NetworkManager configuration file.
+const NMSTATE_NM_CONF_FILE: &str = "/etc/nmstate/networkmanager.yml";
+
+const NM_CONF_SECTION_MAIN: &str = "main";
+const NM_CONF_SECTION_CONNECTIVITY: &str = "connectivity";
+
+// Previous content of configuration files for rollback, None means file
+// does not exist.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct NmConfBackup {
+    nm_conf: Option<String>,
+    nmstate_nm_conf: Option<String>,
+}
+
+// Return None if none of nmstate owned configuration files exists
+pub(crate) fn nm_conf_retrieve(
+) -> Result<Option<NetworkManagerConfig>, NmstateError> {
+    let nm_conf = read_conf_file(NM_CONF_FILE)?;
+    let nmstate_nm_conf = read_conf_file(NMSTATE_NM_CONF_FILE)?;
+    if nm_conf.is_none() && nmstate_nm_conf.is_none() {
+        return Ok(None);
+    }
+    let mut ret = nm_conf.as_deref().map(nm_conf_from_str).unwrap_or_default();
+    if let Some(content) = nmstate_nm_conf.as_deref() {
+        ret.profile_naming = nmstate_nm_conf_from_str(content)?.profile_naming;
+    }
+    Ok(Some(ret))
+}
+
+// Store the configuration, NetworkManager is only reloaded when its
+// configuration file changed. Return the previous content of configuration
+// files for rollback.
+pub(crate) fn nm_conf_apply(
+    conf: &NetworkManagerConfig,
+) -> Result<NmConfBackup, NmstateError> {
+    let backup = NmConfBackup {
+        nm_conf: read_conf_file(NM_CONF_FILE)?,
+        nmstate_nm_conf: read_conf_file(NMSTATE_NM_CONF_FILE)?,
+    };
+    let nm_conf = nm_conf_to_string(conf);
+    if nm_conf != backup.nm_conf {
+        write_conf_file(NM_CONF_FILE, nm_conf)?;
+        nm_reload()?;
+    }
+    let nmstate_nm_conf = nmstate_nm_conf_to_string(conf);
+    if nmstate_nm_conf != backup.nmstate_nm_conf {
+        write_conf_file(NMSTATE_NM_CONF_FILE, nmstate_nm_conf)?;
+    }
+    Ok(backup)
+}
+
+pub(crate) fn nm_conf_restore(
+    backup: NmConfBackup,
+) -> Result<(), NmstateError> {
+    if read_conf_file(NM_CONF_FILE)? != backup.nm_conf {
+        write_conf_file(NM_CONF_FILE, backup.nm_conf)?;
+        nm_reload()?;
+    }
+    if read_conf_file(NMSTATE_NM_CONF_FILE)? != backup.nmstate_nm_conf {
+        write_conf_file(NMSTATE_NM_CONF_FILE, backup.nmstate_nm_conf)?;
+    }
+    Ok(())
+}
+
+// Return None if no NetworkManager option defined
+pub(crate) fn nm_conf_to_string(conf: &NetworkManagerConfig) -> Option<String> {
+    let mut main_lines = Vec::new();
+    if let Some(dns) = conf.dns.as_ref() {
+        main_lines.push(format!("dns={dns}"));
+    }
+    if let Some(specs) = conf.no_auto_default.as_ref() {
+        main_lines.push(format!("no-auto-default={}", specs.join(",")));
+    }
+    let mut connectivity_lines = Vec::new();
+    if let Some(uri) = conf.connectivity_uri.as_ref() {
+        connectivity_lines.push(format!("uri={uri}"));
+    }
+    if main_lines.is_empty() && connectivity_lines.is_empty() {
+        return None;
+    }
+
+    let mut ret = format!("{NM_CONF_HEADER}\n");
+    for (section, lines) in [
+        (NM_CONF_SECTION_MAIN, main_lines),
+        (NM_CONF_SECTION_CONNECTIVITY, connectivity_lines),
+    ] {
+        if !lines.is_empty() {
+            ret += &format!("\n[{section}]\n{}\n", lines.join("\n"));
+        }
+    }
+    Some(ret)
+}
+
+// Return None if no nmstate only option defined
+pub(crate) fn nmstate_nm_conf_to_string(
+    conf: &NetworkManagerConfig,
+) -> Option<String> {
+    let conf = NetworkManagerConfig {
+        profile_naming: Some(conf.profile_naming.clone()?),
+        ..Default::default()
+    };
+    serde_yaml::to_string(&conf)
+        .ok()
+        .map(|c| format!("{NM_CONF_HEADER}\n{c}"))
+}
+
+pub(crate) fn nmstate_nm_conf_from_str(
+    content: &str,
+) -> Result<NetworkManagerConfig, NmstateError> {
+    serde_yaml::from_str(content).map_err(|e| {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid content of {NMSTATE_NM_CONF_FILE}: {e}"),
+        );
+        log::error!("{}", e);
+        e
+    })
+}
+
+pub(crate) fn nm_conf_from_str(content: &str) -> NetworkManagerConfig {
+    let mut ret = NetworkManagerConfig::default();
+    let mut section = "";
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(s) =
+            line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+        {
+            section = s.trim();
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => continue,
+        };
+        match (section, key) {
+            (NM_CONF_SECTION_MAIN, "dns") => {
+                ret.dns = Some(value.to_string());
+            }
+            (NM_CONF_SECTION_MAIN, "no-auto-default") => {
+                ret.no_auto_default = Some(
+                    value
+                        .split([',', ';'])
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                        .collect(),
+                );
+            }
+            (NM_CONF_SECTION_CONNECTIVITY, "uri") => {
+                ret.connectivity_uri = Some(value.to_string());
+            }
+            _ => {
+                log::debug!(
+                    "Ignoring unknown option {key} of section [{section}] \
+                    in {NM_CONF_FILE}"
+                );
+            }
+        }
+    }
+    ret
+}
+
+fn read_conf_file(file_path: &str) -> Result<Option<String>, NmstateError> {
+    match std::fs::read_to_string(file_path) {
+        Ok(c) => Ok(Some(c)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => {
+            let e = NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!("Failed to read {file_path}: {e}"),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+// Remove the file when content is None
+fn write_conf_file(
+    file_path: &str,
+    content: Option<String>,
+) -> Result<(), NmstateError> {
+    let result = match content {
+        Some(c) => std::path::Path::new(file_path)
+            .parent()
+            .map(std::fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| std::fs::write(file_path, c)),
+        None => match std::fs::remove_file(file_path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            r => r,
+        },
+    };
+    if let Err(e) = result {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to update {file_path}: {e}"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    log::info!("Updated nmstate owned configuration {file_path}");
+    Ok(())
+}
+
+fn nm_reload() -> Result<(), NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    nm_api.reload().map_err(nm_error_to_nmstate)
+}
@@ -41,3 +41,41 @@ impl ToDbusValue for NmSettingBond {
         Ok(ret)
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingBondPort {
+    pub priority: Option<i32>,
+    pub queue_id: Option<u32>,
+    _other: HashMap<String, zvariant::OwnedValue>,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingBondPort {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            priority: _from_map!(v, "prio", i32::try_from)?,
+            queue_id: _from_map!(v, "queue-id", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingBondPort {
+    fn to_value(&self) -> Result<HashMap<&str, Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.priority {
+            ret.insert("prio", Value::new(v));
+        }
+        if let Some(v) = self.queue_id {
+            ret.insert("queue-id", Value::new(v));
+        }
+        ret.extend(
+            self._other
+                .iter()
+                .map(|(key, value)| (key.as_str(), Value::from(value.clone()))),
+        );
+        Ok(ret)
+    }
+}
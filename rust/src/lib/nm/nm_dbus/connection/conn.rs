@@ -9,26 +9,36 @@ use serde::Deserialize;
 use zvariant::{Signature, Type};
 
 use super::super::{
-    connection::bond::NmSettingBond,
+    connection::bond::{NmSettingBond, NmSettingBondPort},
     connection::bridge::{NmSettingBridge, NmSettingBridgePort},
     connection::ethtool::NmSettingEthtool,
+    connection::geneve::NmSettingGeneve,
+    connection::gsm::NmSettingGsm,
     connection::ieee8021x::NmSetting8021X,
     connection::infiniband::NmSettingInfiniBand,
     connection::ip::NmSettingIp,
+    connection::ip_tunnel::NmSettingIpTunnel,
+    connection::ipvlan::NmSettingIpVlan,
     connection::loopback::NmSettingLoopback,
     connection::mac_vlan::NmSettingMacVlan,
+    connection::macsec::NmSettingMacSec,
     connection::ovs::{
         NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
         NmSettingOvsIface, NmSettingOvsOtherConfig, NmSettingOvsPatch,
         NmSettingOvsPort,
     },
+    connection::pppoe::{NmSettingPpp, NmSettingPppoe},
     connection::sriov::NmSettingSriov,
+    connection::team::{NmSettingTeam, NmSettingTeamPort},
+    connection::tun::NmSettingTun,
     connection::user::NmSettingUser,
     connection::veth::NmSettingVeth,
     connection::vlan::NmSettingVlan,
     connection::vrf::NmSettingVrf,
     connection::vxlan::NmSettingVxlan,
+    connection::wifi::{NmSettingWireless, NmSettingWirelessSecurity},
     connection::wired::NmSettingWired,
+    connection::wireguard::NmSettingWireGuard,
     convert::ToDbusValue,
     NmError,
 };
@@ -79,6 +89,7 @@ fn from_u32_to_vec_nm_conn_flags(i: u32) -> Vec<NmSettingsConnectionFlag> {
 pub struct NmConnection {
     pub connection: Option<NmSettingConnection>,
     pub bond: Option<NmSettingBond>,
+    pub bond_port: Option<NmSettingBondPort>,
     pub bridge: Option<NmSettingBridge>,
     pub bridge_port: Option<NmSettingBridgePort>,
     pub ipv4: Option<NmSettingIp>,
@@ -93,7 +104,9 @@ pub struct NmConnection {
     pub wired: Option<NmSettingWired>,
     pub vlan: Option<NmSettingVlan>,
     pub vxlan: Option<NmSettingVxlan>,
+    pub geneve: Option<NmSettingGeneve>,
     pub mac_vlan: Option<NmSettingMacVlan>,
+    pub ipvlan: Option<NmSettingIpVlan>,
     pub sriov: Option<NmSettingSriov>,
     pub vrf: Option<NmSettingVrf>,
     pub veth: Option<NmSettingVeth>,
@@ -102,6 +115,17 @@ pub struct NmConnection {
     pub ethtool: Option<NmSettingEthtool>,
     pub infiniband: Option<NmSettingInfiniBand>,
     pub loopback: Option<NmSettingLoopback>,
+    pub team: Option<NmSettingTeam>,
+    pub team_port: Option<NmSettingTeamPort>,
+    pub wireguard: Option<NmSettingWireGuard>,
+    pub macsec: Option<NmSettingMacSec>,
+    pub ip_tunnel: Option<NmSettingIpTunnel>,
+    pub tun: Option<NmSettingTun>,
+    pub pppoe: Option<NmSettingPppoe>,
+    pub ppp: Option<NmSettingPpp>,
+    pub wireless: Option<NmSettingWireless>,
+    pub wireless_security: Option<NmSettingWirelessSecurity>,
+    pub gsm: Option<NmSettingGsm>,
     #[serde(skip)]
     pub obj_path: String,
     #[serde(skip)]
@@ -131,6 +155,7 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
             ipv4: _from_map!(v, "ipv4", NmSettingIp::try_from)?,
             ipv6: _from_map!(v, "ipv6", NmSettingIp::try_from)?,
             bond: _from_map!(v, "bond", NmSettingBond::try_from)?,
+            bond_port: _from_map!(v, "bond-port", NmSettingBondPort::try_from)?,
             bridge: _from_map!(v, "bridge", NmSettingBridge::try_from)?,
             bridge_port: _from_map!(
                 v,
@@ -163,8 +188,10 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
             wired: _from_map!(v, "802-3-ethernet", NmSettingWired::try_from)?,
             vlan: _from_map!(v, "vlan", NmSettingVlan::try_from)?,
             vxlan: _from_map!(v, "vxlan", NmSettingVxlan::try_from)?,
+            geneve: _from_map!(v, "geneve", NmSettingGeneve::try_from)?,
             sriov: _from_map!(v, "sriov", NmSettingSriov::try_from)?,
             mac_vlan: _from_map!(v, "macvlan", NmSettingMacVlan::try_from)?,
+            ipvlan: _from_map!(v, "ipvlan", NmSettingIpVlan::try_from)?,
             vrf: _from_map!(v, "vrf", NmSettingVrf::try_from)?,
             veth: _from_map!(v, "veth", NmSettingVeth::try_from)?,
             ieee8021x: _from_map!(v, "802-1x", NmSetting8021X::try_from)?,
@@ -176,6 +203,29 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
                 NmSettingInfiniBand::try_from
             )?,
             loopback: _from_map!(v, "loopback", NmSettingLoopback::try_from)?,
+            team: _from_map!(v, "team", NmSettingTeam::try_from)?,
+            team_port: _from_map!(v, "team-port", NmSettingTeamPort::try_from)?,
+            wireguard: _from_map!(
+                v,
+                "wireguard",
+                NmSettingWireGuard::try_from
+            )?,
+            macsec: _from_map!(v, "macsec", NmSettingMacSec::try_from)?,
+            ip_tunnel: _from_map!(v, "ip-tunnel", NmSettingIpTunnel::try_from)?,
+            tun: _from_map!(v, "tun", NmSettingTun::try_from)?,
+            pppoe: _from_map!(v, "pppoe", NmSettingPppoe::try_from)?,
+            ppp: _from_map!(v, "ppp", NmSettingPpp::try_from)?,
+            wireless: _from_map!(
+                v,
+                "802-11-wireless",
+                NmSettingWireless::try_from
+            )?,
+            wireless_security: _from_map!(
+                v,
+                "802-11-wireless-security",
+                NmSettingWirelessSecurity::try_from
+            )?,
+            gsm: _from_map!(v, "gsm", NmSettingGsm::try_from)?,
             _other: v,
             ..Default::default()
         })
@@ -212,6 +262,9 @@ impl NmConnection {
         if let Some(bond_set) = &self.bond {
             ret.insert("bond", bond_set.to_value()?);
         }
+        if let Some(bond_port_set) = &self.bond_port {
+            ret.insert("bond-port", bond_port_set.to_value()?);
+        }
         if let Some(br_set) = &self.bridge {
             ret.insert("bridge", br_set.to_value()?);
         }
@@ -254,12 +307,18 @@ impl NmConnection {
         if let Some(vxlan) = &self.vxlan {
             ret.insert("vxlan", vxlan.to_value()?);
         }
+        if let Some(geneve) = &self.geneve {
+            ret.insert("geneve", geneve.to_value()?);
+        }
         if let Some(sriov) = &self.sriov {
             ret.insert("sriov", sriov.to_value()?);
         }
         if let Some(mac_vlan) = &self.mac_vlan {
             ret.insert("macvlan", mac_vlan.to_value()?);
         }
+        if let Some(ipvlan) = &self.ipvlan {
+            ret.insert("ipvlan", ipvlan.to_value()?);
+        }
         if let Some(vrf) = &self.vrf {
             ret.insert("vrf", vrf.to_value()?);
         }
@@ -281,6 +340,39 @@ impl NmConnection {
         if let Some(v) = &self.loopback {
             ret.insert("loopback", v.to_value()?);
         }
+        if let Some(v) = &self.team {
+            ret.insert("team", v.to_value()?);
+        }
+        if let Some(v) = &self.team_port {
+            ret.insert("team-port", v.to_value()?);
+        }
+        if let Some(v) = &self.wireguard {
+            ret.insert("wireguard", v.to_value()?);
+        }
+        if let Some(v) = &self.macsec {
+            ret.insert("macsec", v.to_value()?);
+        }
+        if let Some(v) = &self.ip_tunnel {
+            ret.insert("ip-tunnel", v.to_value()?);
+        }
+        if let Some(v) = &self.tun {
+            ret.insert("tun", v.to_value()?);
+        }
+        if let Some(v) = &self.pppoe {
+            ret.insert("pppoe", v.to_value()?);
+        }
+        if let Some(v) = &self.ppp {
+            ret.insert("ppp", v.to_value()?);
+        }
+        if let Some(v) = &self.wireless {
+            ret.insert("802-11-wireless", v.to_value()?);
+        }
+        if let Some(v) = &self.wireless_security {
+            ret.insert("802-11-wireless-security", v.to_value()?);
+        }
+        if let Some(v) = &self.gsm {
+            ret.insert("gsm", v.to_value()?);
+        }
         for (key, setting_value) in &self._other {
             let mut other_setting_value: HashMap<&str, zvariant::Value> =
                 HashMap::new();
@@ -308,6 +400,15 @@ impl NmConnection {
         if let Some(setting) = self.mac_vlan.as_mut() {
             setting.parent = Some(parent.to_string());
         }
+        if let Some(setting) = self.ipvlan.as_mut() {
+            setting.parent = Some(parent.to_string());
+        }
+        if let Some(setting) = self.macsec.as_mut() {
+            setting.parent = Some(parent.to_string());
+        }
+        if let Some(setting) = self.pppoe.as_mut() {
+            setting.parent = Some(parent.to_string());
+        }
     }
 
     pub fn uuid(&self) -> Option<&str> {
@@ -334,6 +435,8 @@ pub struct NmSettingConnection {
     pub autoconnect_ports: Option<bool>,
     pub lldp: Option<bool>,
     pub mptcp_flags: Option<u32>,
+    pub multi_connect: Option<i32>,
+    pub permissions: Option<Vec<String>>,
     _other: HashMap<String, zvariant::OwnedValue>,
 }
 
@@ -354,6 +457,8 @@ impl TryFrom<DbusDictionary> for NmSettingConnection {
             ),
             lldp: _from_map!(v, "lldp", i32::try_from)?.map(|i| i == 1),
             mptcp_flags: _from_map!(v, "mptcp-flags", u32::try_from)?,
+            multi_connect: _from_map!(v, "multi-connect", i32::try_from)?,
+            permissions: _from_map!(v, "permissions", <Vec<String>>::try_from)?,
             _other: v,
         })
     }
@@ -386,6 +491,12 @@ impl ToDbusValue for NmSettingConnection {
         if let Some(v) = &self.mptcp_flags {
             ret.insert("mptcp-flags", zvariant::Value::new(v));
         }
+        if let Some(v) = &self.multi_connect {
+            ret.insert("multi-connect", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.permissions {
+            ret.insert("permissions", zvariant::Value::new(v));
+        }
 
         ret.insert(
             "autoconnect",
@@ -447,6 +558,54 @@ pub(crate) fn nm_con_get_from_obj_path(
             }
         }
     }
+    if let Some(wg_conf) = nm_conn.wireguard.as_mut() {
+        if let Ok(nm_secrets) = proxy.call::<&str, NmConnectionDbusOwnedValue>(
+            "GetSecrets",
+            &"wireguard",
+        ) {
+            if let Some(nm_secret) = nm_secrets.get("wireguard") {
+                wg_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
+    if let Some(macsec_conf) = nm_conn.macsec.as_mut() {
+        if let Ok(nm_secrets) = proxy
+            .call::<&str, NmConnectionDbusOwnedValue>("GetSecrets", &"macsec")
+        {
+            if let Some(nm_secret) = nm_secrets.get("macsec") {
+                macsec_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
+    if let Some(pppoe_conf) = nm_conn.pppoe.as_mut() {
+        if let Ok(nm_secrets) = proxy
+            .call::<&str, NmConnectionDbusOwnedValue>("GetSecrets", &"pppoe")
+        {
+            if let Some(nm_secret) = nm_secrets.get("pppoe") {
+                pppoe_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
+    if let Some(wifi_sec_conf) = nm_conn.wireless_security.as_mut() {
+        if let Ok(nm_secrets) = proxy.call::<&str, NmConnectionDbusOwnedValue>(
+            "GetSecrets",
+            &"802-11-wireless-security",
+        ) {
+            if let Some(nm_secret) = nm_secrets.get("802-11-wireless-security")
+            {
+                wifi_sec_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
+    if let Some(gsm_conf) = nm_conn.gsm.as_mut() {
+        if let Ok(nm_secrets) =
+            proxy.call::<&str, NmConnectionDbusOwnedValue>("GetSecrets", &"gsm")
+        {
+            if let Some(nm_secret) = nm_secrets.get("gsm") {
+                gsm_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
     if let Ok(flags) = proxy.get_property::<u32>("Flags") {
         nm_conn.flags = from_u32_to_vec_nm_conn_flags(flags);
     }
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingGeneve {
+    pub id: Option<u32>,
+    pub remote: Option<String>,
+    pub dst_port: Option<u32>,
+    pub ttl: Option<u32>,
+    pub tos: Option<u32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingGeneve {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: _from_map!(v, "id", u32::try_from)?,
+            remote: _from_map!(v, "remote", String::try_from)?,
+            dst_port: _from_map!(v, "destination-port", u32::try_from)?,
+            ttl: _from_map!(v, "ttl", u32::try_from)?,
+            tos: _from_map!(v, "tos", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingGeneve {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.id {
+            ret.insert("id", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.remote {
+            ret.insert("remote", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.dst_port {
+            ret.insert("destination-port", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.ttl {
+            ret.insert("ttl", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.tos {
+            ret.insert("tos", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingGsm {
+    pub apn: Option<String>,
+    pub auto_config: Option<bool>,
+    pub home_only: Option<bool>,
+    pub mtu: Option<u32>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub password_flags: Option<u32>,
+    pub pin: Option<String>,
+    pub pin_flags: Option<u32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingGsm {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            apn: _from_map!(v, "apn", String::try_from)?,
+            auto_config: _from_map!(v, "auto-config", bool::try_from)?,
+            home_only: _from_map!(v, "home-only", bool::try_from)?,
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            username: _from_map!(v, "username", String::try_from)?,
+            // Secrets are only available via GetSecrets
+            password: None,
+            password_flags: _from_map!(v, "password-flags", u32::try_from)?,
+            pin: None,
+            pin_flags: _from_map!(v, "pin-flags", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingGsm {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.apn {
+            ret.insert("apn", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.auto_config {
+            ret.insert("auto-config", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.home_only {
+            ret.insert("home-only", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.mtu {
+            ret.insert("mtu", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.username {
+            ret.insert("username", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.password {
+            ret.insert("password", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.password_flags {
+            ret.insert("password-flags", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.pin {
+            ret.insert("pin", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.pin_flags {
+            ret.insert("pin-flags", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+impl NmSettingGsm {
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
+        for (key, dst) in
+            [("password", &mut self.password), ("pin", &mut self.pin)]
+        {
+            if let Some(v) = secrets.get(key) {
+                match String::try_from(v.clone()) {
+                    Ok(s) => {
+                        *dst = Some(s);
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Failed to convert GSM {key}: {:?} {:?}",
+                            v,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ use super::super::{
 };
 
 const GLIB_FILE_PATH_PREFIX: &str = "file://";
+const PKCS11_URI_PREFIX: &str = "pkcs11:";
 
 #[derive(Debug, Clone, PartialEq, Default, Deserialize)]
 #[serde(try_from = "DbusDictionary")]
@@ -89,6 +90,15 @@ impl NmSetting8021X {
         format!("{GLIB_FILE_PATH_PREFIX}{file_path}\0").into_bytes()
     }
 
+    // NetworkManager stores PKCS#11 URI as is with NULL appended.
+    pub fn cert_to_glib_bytes(value: &str) -> Vec<u8> {
+        if value.starts_with(PKCS11_URI_PREFIX) {
+            format!("{value}\0").into_bytes()
+        } else {
+            Self::file_path_to_glib_bytes(value)
+        }
+    }
+
     pub fn glib_bytes_to_file_path(value: &[u8]) -> Result<String, NmError> {
         let mut file_path = match String::from_utf8(value.to_vec()) {
             Ok(f) => f.trim_end_matches(char::from(0)).to_string(),
@@ -107,6 +117,8 @@ impl NmSetting8021X {
         if file_path.starts_with(GLIB_FILE_PATH_PREFIX) {
             file_path.drain(..GLIB_FILE_PATH_PREFIX.len());
             Ok(file_path)
+        } else if file_path.starts_with(PKCS11_URI_PREFIX) {
+            Ok(file_path)
         } else {
             let e = NmError::new(
                 ErrorKind::InvalidArgument,
@@ -21,23 +21,33 @@ mod bridge;
 mod conn;
 mod dns;
 mod ethtool;
+mod geneve;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
 mod loopback;
 mod mac_vlan;
+mod macsec;
 mod ovs;
+mod pppoe;
 mod route;
 mod route_rule;
 mod sriov;
+mod team;
+mod tun;
 mod user;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wifi;
 mod wired;
+mod wireguard;
 
-pub use self::bond::NmSettingBond;
+pub use self::bond::{NmSettingBond, NmSettingBondPort};
 pub use self::bridge::{
     NmSettingBridge, NmSettingBridgePort, NmSettingBridgeVlanRange,
 };
@@ -45,25 +55,34 @@ pub use self::conn::{
     NmConnection, NmRange, NmSettingConnection, NmSettingsConnectionFlag,
 };
 pub use self::ethtool::NmSettingEthtool;
+pub use self::geneve::NmSettingGeneve;
+pub use self::gsm::NmSettingGsm;
 pub use self::ieee8021x::NmSetting8021X;
 pub use self::infiniband::NmSettingInfiniBand;
 pub use self::ip::{NmSettingIp, NmSettingIpMethod};
+pub use self::ip_tunnel::NmSettingIpTunnel;
+pub use self::ipvlan::NmSettingIpVlan;
 pub use self::loopback::NmSettingLoopback;
 pub use self::mac_vlan::NmSettingMacVlan;
+pub use self::macsec::NmSettingMacSec;
 pub use self::ovs::{
     NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
     NmSettingOvsIface, NmSettingOvsOtherConfig, NmSettingOvsPatch,
     NmSettingOvsPort,
 };
+pub use self::pppoe::{NmSettingPpp, NmSettingPppoe};
 pub use self::route::NmIpRoute;
 pub use self::route_rule::{NmIpRouteRule, NmIpRouteRuleAction};
 pub use self::sriov::{NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan};
+pub use self::tun::NmSettingTun;
 pub use self::user::NmSettingUser;
 pub use self::veth::NmSettingVeth;
 pub use self::vlan::{NmSettingVlan, NmVlanProtocol};
 pub use self::vrf::NmSettingVrf;
 pub use self::vxlan::NmSettingVxlan;
+pub use self::wifi::{NmSettingWireless, NmSettingWirelessSecurity};
 pub use self::wired::NmSettingWired;
+pub use self::wireguard::{NmSettingWireGuard, NmWireGuardPeer};
 
 pub(crate) use self::conn::DbusDictionary;
 #[cfg(feature = "query_apply")]
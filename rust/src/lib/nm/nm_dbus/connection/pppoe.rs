@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingPppoe {
+    pub parent: Option<String>,
+    pub service: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub password_flags: Option<u32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingPppoe {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            parent: _from_map!(v, "parent", String::try_from)?,
+            service: _from_map!(v, "service", String::try_from)?,
+            username: _from_map!(v, "username", String::try_from)?,
+            // Secret is only available via GetSecrets
+            password: None,
+            password_flags: _from_map!(v, "password-flags", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingPppoe {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.parent {
+            ret.insert("parent", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.service {
+            ret.insert("service", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.username {
+            ret.insert("username", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.password {
+            ret.insert("password", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.password_flags {
+            ret.insert("password-flags", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+impl NmSettingPppoe {
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
+        if let Some(v) = secrets.get("password") {
+            match String::try_from(v.clone()) {
+                Ok(s) => {
+                    self.password = Some(s);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to convert PPPoE password: {:?} {:?}",
+                        v,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingPpp {
+    pub mtu: Option<u32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingPpp {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingPpp {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.mtu {
+            ret.insert("mtu", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
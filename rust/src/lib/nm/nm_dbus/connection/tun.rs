@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingTun {
+    pub mode: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub multi_queue: Option<bool>,
+    pub pi: Option<bool>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingTun {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mode: _from_map!(v, "mode", u32::try_from)?,
+            owner: _from_map!(v, "owner", String::try_from)?,
+            group: _from_map!(v, "group", String::try_from)?,
+            multi_queue: _from_map!(v, "multi-queue", bool::try_from)?,
+            pi: _from_map!(v, "pi", bool::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingTun {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.mode {
+            ret.insert("mode", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.owner {
+            ret.insert("owner", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.group {
+            ret.insert("group", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.multi_queue {
+            ret.insert("multi-queue", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.pi {
+            ret.insert("pi", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
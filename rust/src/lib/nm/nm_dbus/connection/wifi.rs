@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{
+    connection::DbusDictionary,
+    convert::mac_str_to_u8_array,
+    convert::{own_value_to_bytes_array, u8_array_to_mac_string},
+    NmError, ToDbusValue,
+};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingWireless {
+    pub ssid: Option<Vec<u8>>,
+    pub mode: Option<String>,
+    pub band: Option<String>,
+    pub channel: Option<u32>,
+    pub hidden: Option<bool>,
+    pub mtu: Option<u32>,
+    pub cloned_mac_address: Option<String>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingWireless {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            ssid: _from_map!(v, "ssid", <Vec<u8>>::try_from)?,
+            mode: _from_map!(v, "mode", String::try_from)?,
+            band: _from_map!(v, "band", String::try_from)?,
+            channel: _from_map!(v, "channel", u32::try_from)?,
+            hidden: _from_map!(v, "hidden", bool::try_from)?,
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            cloned_mac_address: _from_map!(
+                v,
+                "cloned-mac-address",
+                own_value_to_bytes_array
+            )?
+            .map(u8_array_to_mac_string),
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingWireless {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.ssid {
+            ret.insert("ssid", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.mode {
+            ret.insert("mode", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.band {
+            ret.insert("band", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.channel {
+            ret.insert("channel", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.hidden {
+            ret.insert("hidden", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.mtu {
+            ret.insert("mtu", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.cloned_mac_address {
+            ret.insert(
+                "cloned-mac-address",
+                zvariant::Value::new(mac_str_to_u8_array(v)),
+            );
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingWirelessSecurity {
+    pub key_mgmt: Option<String>,
+    pub psk: Option<String>,
+    pub psk_flags: Option<u32>,
+    pub pmf: Option<i32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingWirelessSecurity {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key_mgmt: _from_map!(v, "key-mgmt", String::try_from)?,
+            // Secret is only available via GetSecrets
+            psk: None,
+            psk_flags: _from_map!(v, "psk-flags", u32::try_from)?,
+            pmf: _from_map!(v, "pmf", i32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingWirelessSecurity {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.key_mgmt {
+            ret.insert("key-mgmt", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.psk {
+            ret.insert("psk", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.psk_flags {
+            ret.insert("psk-flags", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.pmf {
+            ret.insert("pmf", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+impl NmSettingWirelessSecurity {
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
+        if let Some(v) = secrets.get("psk") {
+            match String::try_from(v.clone()) {
+                Ok(s) => {
+                    self.psk = Some(s);
+                }
+                Err(e) => {
+                    log::warn!("Failed to convert Wi-Fi psk: {:?} {:?}", v, e);
+                }
+            }
+        }
+    }
+}
@@ -22,6 +22,8 @@ pub struct NmSettingWired {
     pub speed: Option<u32>,
     pub duplex: Option<String>,
     pub auto_negotiate: Option<bool>,
+    pub wake_on_lan: Option<u32>,
+    pub wake_on_lan_password: Option<String>,
     _other: HashMap<String, zvariant::OwnedValue>,
 }
 
@@ -44,6 +46,12 @@ impl TryFrom<DbusDictionary> for NmSettingWired {
             speed: _from_map!(v, "speed", u32::try_from)?,
             duplex: _from_map!(v, "duplex", String::try_from)?,
             auto_negotiate: _from_map!(v, "auto-negotiate", bool::try_from)?,
+            wake_on_lan: _from_map!(v, "wake-on-lan", u32::try_from)?,
+            wake_on_lan_password: _from_map!(
+                v,
+                "wake-on-lan-password",
+                String::try_from
+            )?,
             _other: v,
         })
     }
@@ -73,6 +81,12 @@ impl ToDbusValue for NmSettingWired {
         if let Some(v) = &self.duplex {
             ret.insert("duplex", zvariant::Value::new(v));
         }
+        if let Some(v) = &self.wake_on_lan {
+            ret.insert("wake-on-lan", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.wake_on_lan_password {
+            ret.insert("wake-on-lan-password", zvariant::Value::new(v));
+        }
         ret.extend(self._other.iter().map(|(key, value)| {
             (key.as_str(), zvariant::Value::from(value.clone()))
         }));
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use log::warn;
+
+use super::{
+    dbus::{NM_DBUS_INTERFACE_DEV, NM_DBUS_INTERFACE_ROOT},
+    ErrorKind, NmError,
+};
+
+const NM_CONNECTIVITY_UNKNOWN: u32 = 0;
+const NM_CONNECTIVITY_NONE: u32 = 1;
+const NM_CONNECTIVITY_PORTAL: u32 = 2;
+const NM_CONNECTIVITY_LIMITED: u32 = 3;
+const NM_CONNECTIVITY_FULL: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NmConnectivityState {
+    Unknown,
+    None,
+    Portal,
+    Limited,
+    Full,
+}
+
+impl Default for NmConnectivityState {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+impl From<u32> for NmConnectivityState {
+    fn from(i: u32) -> Self {
+        match i {
+            NM_CONNECTIVITY_UNKNOWN => Self::Unknown,
+            NM_CONNECTIVITY_NONE => Self::None,
+            NM_CONNECTIVITY_PORTAL => Self::Portal,
+            NM_CONNECTIVITY_LIMITED => Self::Limited,
+            NM_CONNECTIVITY_FULL => Self::Full,
+            _ => {
+                warn!("Unknown connectivity state {}", i);
+                Self::Unknown
+            }
+        }
+    }
+}
+
+// Return connectivity state of IPv4 and IPv6
+pub(crate) fn nm_dev_get_connectivity(
+    dbus_conn: &zbus::Connection,
+    obj_path: &str,
+) -> Result<(NmConnectivityState, NmConnectivityState), NmError> {
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        obj_path,
+        NM_DBUS_INTERFACE_DEV,
+    )?;
+    let mut ret = Vec::new();
+    for prop_name in ["Ip4Connectivity", "Ip6Connectivity"] {
+        match proxy.get_property::<u32>(prop_name) {
+            Ok(i) => ret.push(NmConnectivityState::from(i)),
+            Err(e) => {
+                return Err(NmError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to retrieve {prop_name} of device \
+                        {obj_path}: {e}"
+                    ),
+                ));
+            }
+        }
+    }
+    Ok((ret[0], ret[1]))
+}
@@ -18,6 +18,9 @@ const NM_CHECKPOINT_CREATE_FLAG_DISCONNECT_NEW_DEVICES: u32 = 0x04;
 
 const OBJ_PATH_NULL_STR: &str = "/";
 
+const DBUS_UNKNOWN_METHOD_ERROR: &str =
+    "org.freedesktop.DBus.Error.UnknownMethod";
+
 pub(crate) const NM_DBUS_INTERFACE_ROOT: &str =
     "org.freedesktop.NetworkManager";
 pub(crate) const NM_DBUS_INTERFACE_SETTING: &str =
@@ -37,6 +40,9 @@ const NM_SETTINGS_UPDATE2_FLAGS_TO_DISK: u32 = 1;
 const NM_SETTINGS_UPDATE2_FLAGS_IN_MEMORY: u32 = 2;
 const NM_SETTINGS_UPDATE2_FLAGS_BLOCK_AUTOCONNECT: u32 = 32;
 
+// Reload everything, equivalent to SIGHUP
+const NM_MANAGER_RELOAD_FLAG_ALL: u32 = 0;
+
 pub(crate) struct NmDbus<'a> {
     pub(crate) connection: zbus::Connection,
     proxy: NetworkManagerProxy<'a>,
@@ -85,6 +91,16 @@ impl<'a> NmDbus<'a> {
                             please wait its timeout or destroy it"
                                 .to_string(),
                         )
+                    } else if error_type == DBUS_UNKNOWN_METHOD_ERROR
+                        || error_type.ends_with(".NotSupported")
+                    {
+                        NmError::new(
+                            ErrorKind::CheckpointNotSupported,
+                            format!(
+                                "NetworkManager does not support checkpoint: \
+                                {e}"
+                            ),
+                        )
                     } else {
                         e.into()
                     }
@@ -358,6 +374,14 @@ impl<'a> NmDbus<'a> {
         Ok(self.setting_proxy.save_hostname(hostname)?)
     }
 
+    pub(crate) fn connectivity(&self) -> Result<u32, NmError> {
+        Ok(self.proxy.connectivity()?)
+    }
+
+    pub(crate) fn reload(&self) -> Result<(), NmError> {
+        Ok(self.proxy.reload(NM_MANAGER_RELOAD_FLAG_ALL)?)
+    }
+
     pub(crate) fn global_dns_configuration(
         &self,
     ) -> Result<HashMap<String, zvariant::OwnedValue>, NmError> {
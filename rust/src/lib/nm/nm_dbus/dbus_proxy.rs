@@ -18,6 +18,9 @@ trait NetworkManager {
         &self,
     ) -> zbus::Result<Vec<zvariant::OwnedObjectPath>>;
 
+    #[dbus_proxy(property)]
+    fn connectivity(&self) -> zbus::Result<u32>;
+
     #[dbus_proxy(property)]
     fn checkpoints(&self) -> zbus::Result<Vec<zvariant::OwnedObjectPath>>;
 
@@ -75,6 +78,9 @@ trait NetworkManager {
         checkpoint: &zvariant::ObjectPath,
         add_timeout: u32,
     ) -> zbus::Result<()>;
+
+    /// Reload method
+    fn reload(&self, flags: u32) -> zbus::Result<()>;
 }
 
 #[dbus_proxy(
@@ -1,14 +1,18 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use log::warn;
 
 use super::{
     connection::DbusDictionary,
-    dbus::{NM_DBUS_INTERFACE_DEV, NM_DBUS_INTERFACE_ROOT},
+    dbus::{obj_path_to_string, NM_DBUS_INTERFACE_DEV, NM_DBUS_INTERFACE_ROOT},
     lldp::NmLldpNeighbor,
     ErrorKind, NmError,
 };
 
+const NM_DBUS_INTERFACE_DEV_MODEM: &str =
+    "org.freedesktop.NetworkManager.Device.Modem";
+
 const NM_DEVICE_TYPE_UNKNOWN: u32 = 0;
 const NM_DEVICE_TYPE_ETHERNET: u32 = 1;
 const NM_DEVICE_TYPE_WIFI: u32 = 2;
@@ -42,6 +46,7 @@ const NM_DEVICE_TYPE_WIREGUARD: u32 = 29;
 const NM_DEVICE_TYPE_WIFI_P2P: u32 = 30;
 const NM_DEVICE_TYPE_VRF: u32 = 31;
 const NM_DEVICE_TYPE_LOOPBACK: u32 = 32;
+const NM_DEVICE_TYPE_IPVLAN: u32 = 34;
 
 const NM_DEVICE_STATE_UNKNOWN: u32 = 0;
 const NM_DEVICE_STATE_UNMANAGED: u32 = 10;
@@ -456,6 +461,7 @@ fn nm_dev_iface_type_get(
             NM_DEVICE_TYPE_WIFI_P2P => "wifi-p2p".to_string(),
             NM_DEVICE_TYPE_VRF => "vrf".to_string(),
             NM_DEVICE_TYPE_LOOPBACK => "loopback".to_string(),
+            NM_DEVICE_TYPE_IPVLAN => "ipvlan".to_string(),
             _ => format!("unknown({i})"),
         }),
         Err(e) => Err(NmError::new(
@@ -593,3 +599,106 @@ pub(crate) fn nm_dev_get_llpd(
         )),
     }
 }
+
+// Return empty HashMap if DHCP is not running on this device
+pub(crate) fn nm_dev_get_dhcp_options(
+    dbus_conn: &zbus::Connection,
+    obj_path: &str,
+    is_ipv6: bool,
+) -> Result<HashMap<String, String>, NmError> {
+    let (prop_name, dbus_iface) = if is_ipv6 {
+        (
+            "Dhcp6Config",
+            format!("{NM_DBUS_INTERFACE_ROOT}.DHCP6Config"),
+        )
+    } else {
+        (
+            "Dhcp4Config",
+            format!("{NM_DBUS_INTERFACE_ROOT}.DHCP4Config"),
+        )
+    };
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        obj_path,
+        NM_DBUS_INTERFACE_DEV,
+    )?;
+    let dhcp_obj_path =
+        match proxy.get_property::<zvariant::OwnedObjectPath>(prop_name) {
+            Ok(p) => obj_path_to_string(p),
+            Err(e) => {
+                return Err(NmError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to retrieve {prop_name} of device \
+                        {obj_path}: {e}"
+                    ),
+                ));
+            }
+        };
+    let mut ret = HashMap::new();
+    if dhcp_obj_path.is_empty() || dhcp_obj_path == "/" {
+        return Ok(ret);
+    }
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        dhcp_obj_path.as_str(),
+        dbus_iface.as_str(),
+    )?;
+    match proxy.get_property::<DbusDictionary>("Options") {
+        Ok(options) => {
+            for (key, value) in options {
+                match String::try_from(value) {
+                    Ok(v) => {
+                        ret.insert(key, v);
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Ignoring DHCP option {key} of device \
+                            {obj_path}: {e}"
+                        );
+                    }
+                }
+            }
+            Ok(ret)
+        }
+        Err(e) => Err(NmError::new(
+            ErrorKind::Bug,
+            format!(
+                "Failed to retrieve DHCP options of device {obj_path}: {e}"
+            ),
+        )),
+    }
+}
+
+// Return the APN and operator code(MCC and MNC) of the active data bearer
+pub(crate) fn nm_dev_get_modem_bearer(
+    dbus_conn: &zbus::Connection,
+    obj_path: &str,
+) -> Result<(String, String), NmError> {
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        obj_path,
+        NM_DBUS_INTERFACE_DEV_MODEM,
+    )?;
+    let mut ret = Vec::new();
+    for prop_name in ["Apn", "OperatorCode"] {
+        match proxy.get_property::<String>(prop_name) {
+            Ok(v) => ret.push(v),
+            Err(e) => {
+                return Err(NmError::new(
+                    ErrorKind::Bug,
+                    format!(
+                        "Failed to retrieve {prop_name} of modem device \
+                        {obj_path}: {e}"
+                    ),
+                ));
+            }
+        }
+    }
+    let operator_code = ret.pop().unwrap_or_default();
+    let apn = ret.pop().unwrap_or_default();
+    Ok((apn, operator_code))
+}
@@ -6,12 +6,14 @@
 pub enum ErrorKind {
     DbusConnectionError,
     CheckpointConflict,
+    CheckpointNotSupported,
     InvalidArgument,
     NotFound,
     IncompatibleReapply,
     Bug,
     Timeout,
     LoopbackIfaceNotSupported,
+    PermissionDenied,
     Device(NmDeviceError),
     Manager(NmManagerError),
     Setting(NmSettingError),
@@ -183,6 +185,15 @@ impl From<&str> for NmManagerError {
 #[cfg(feature = "query_apply")]
 const NM_DBUS_ERR_PREFIX: &str = "org.freedesktop.NetworkManager.";
 
+// D-Bus errors raised by dbus-daemon(e.g. D-Bus policy or SELinux denial) or
+// polkit instead of NetworkManager.
+#[cfg(feature = "query_apply")]
+const DBUS_PERMISSION_ERRORS: [&str; 3] = [
+    "org.freedesktop.DBus.Error.AccessDenied",
+    "org.freedesktop.DBus.Error.AuthFailed",
+    "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired",
+];
+
 #[cfg(feature = "query_apply")]
 fn parse_nm_dbus_error(nm_err_kind: &str, nm_err_msg: &str) -> NmError {
     if nm_err_kind.len() > NM_DBUS_ERR_PREFIX.len() {
@@ -278,6 +289,24 @@ impl From<zbus::Error> for NmError {
                     },
                 );
             }
+            if DBUS_PERMISSION_ERRORS.contains(&dbus_err_kind.as_str()) {
+                return Self::new(
+                    ErrorKind::PermissionDenied,
+                    format!(
+                        "{}: {}",
+                        dbus_err_kind,
+                        dbus_err_msg.as_deref().unwrap_or_default()
+                    ),
+                );
+            }
+        }
+        if let zbus::Error::Io(io_err) = &e {
+            if io_err.kind() == std::io::ErrorKind::PermissionDenied {
+                return Self::new(
+                    ErrorKind::PermissionDenied,
+                    format!("Failed to connect to system D-Bus: {io_err}"),
+                );
+            }
         }
 
         log::warn!("Unknown DBUS error {:?}", e);
@@ -292,9 +321,17 @@ impl From<zbus::Error> for NmError {
 #[cfg(feature = "query_apply")]
 impl From<zbus::fdo::Error> for NmError {
     fn from(e: zbus::fdo::Error) -> Self {
-        Self {
-            kind: ErrorKind::Bug,
-            msg: format!("zbus fdo error {e}"),
+        match e {
+            zbus::fdo::Error::AccessDenied(_)
+            | zbus::fdo::Error::AuthFailed(_)
+            | zbus::fdo::Error::InteractiveAuthorizationRequired(_) => Self {
+                kind: ErrorKind::PermissionDenied,
+                msg: format!("zbus fdo error {e}"),
+            },
+            _ => Self {
+                kind: ErrorKind::Bug,
+                msg: format!("zbus fdo error {e}"),
+            },
         }
     }
 }
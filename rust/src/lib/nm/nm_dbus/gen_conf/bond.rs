@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 use zvariant::Value;
 
-use super::super::{NmError, NmSettingBond, ToKeyfile};
+use super::super::{
+    NmError, NmSettingBond, NmSettingBondPort, ToDbusValue, ToKeyfile,
+};
 
 impl ToKeyfile for NmSettingBond {
     fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
@@ -13,3 +15,13 @@ impl ToKeyfile for NmSettingBond {
         Ok(ret)
     }
 }
+
+impl ToKeyfile for NmSettingBondPort {
+    fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        for (k, v) in self.to_value()?.drain() {
+            ret.insert(k.to_string(), v);
+        }
+        Ok(ret)
+    }
+}
@@ -11,6 +11,20 @@ impl ToKeyfile for NmSettingConnection {}
 
 impl NmConnection {
     pub fn to_keyfile(&self) -> Result<String, NmError> {
+        let wg_peers = self
+            .wireguard
+            .as_ref()
+            .and_then(|w| w.peers.as_deref())
+            .unwrap_or_default();
+        let wg_peer_section_names: Vec<String> = wg_peers
+            .iter()
+            .map(|p| {
+                format!(
+                    "wireguard-peer.{}",
+                    p.public_key.as_deref().unwrap_or_default()
+                )
+            })
+            .collect();
         let mut sections: Vec<(&str, HashMap<String, zvariant::Value>)> =
             Vec::new();
         if let Some(con_set) = &self.connection {
@@ -19,6 +33,9 @@ impl NmConnection {
         if let Some(bond_set) = &self.bond {
             sections.push(("bond", bond_set.to_keyfile()?));
         }
+        if let Some(bond_port_set) = &self.bond_port {
+            sections.push(("bond-port", bond_port_set.to_keyfile()?));
+        }
         if let Some(br_set) = &self.bridge {
             sections.push(("bridge", br_set.to_keyfile()?));
         }
@@ -55,12 +72,18 @@ impl NmConnection {
         if let Some(vxlan) = &self.vxlan {
             sections.push(("vxlan", vxlan.to_keyfile()?));
         }
+        if let Some(geneve) = &self.geneve {
+            sections.push(("geneve", geneve.to_keyfile()?));
+        }
         if let Some(sriov) = &self.sriov {
             sections.push(("sriov", sriov.to_keyfile()?));
         }
         if let Some(mac_vlan) = &self.mac_vlan {
             sections.push(("macvlan", mac_vlan.to_keyfile()?));
         }
+        if let Some(ipvlan) = &self.ipvlan {
+            sections.push(("ipvlan", ipvlan.to_keyfile()?));
+        }
         if let Some(vrf) = &self.vrf {
             sections.push(("vrf", vrf.to_keyfile()?));
         }
@@ -85,6 +108,36 @@ impl NmConnection {
         if let Some(ovs_other_cfgs) = &self.ovs_other_config {
             sections.push(("ovs-other-config", ovs_other_cfgs.to_keyfile()?));
         }
+        if let Some(macsec) = &self.macsec {
+            sections.push(("macsec", macsec.to_keyfile()?));
+        }
+        if let Some(ip_tunnel) = &self.ip_tunnel {
+            sections.push(("ip-tunnel", ip_tunnel.to_keyfile()?));
+        }
+        if let Some(tun) = &self.tun {
+            sections.push(("tun", tun.to_keyfile()?));
+        }
+        if let Some(pppoe) = &self.pppoe {
+            sections.push(("pppoe", pppoe.to_keyfile()?));
+        }
+        if let Some(ppp) = &self.ppp {
+            sections.push(("ppp", ppp.to_keyfile()?));
+        }
+        if let Some(wireless) = &self.wireless {
+            sections.push(("wifi", wireless.to_keyfile()?));
+        }
+        if let Some(wireless_security) = &self.wireless_security {
+            sections.push(("wifi-security", wireless_security.to_keyfile()?));
+        }
+        if let Some(gsm) = &self.gsm {
+            sections.push(("gsm", gsm.to_keyfile()?));
+        }
+        if let Some(wireguard) = &self.wireguard {
+            sections.push(("wireguard", wireguard.to_keyfile()?));
+            for (name, peer) in wg_peer_section_names.iter().zip(wg_peers) {
+                sections.push((name.as_str(), peer.to_keyfile()));
+            }
+        }
 
         keyfile_sections_to_string(&sections)
     }
@@ -4,20 +4,29 @@ mod bond;
 mod bridge;
 mod conn;
 mod ethtool;
+mod geneve;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
 mod keyfile;
 mod mac_vlan;
+mod macsec;
 mod ovs;
+mod pppoe;
 mod route;
 mod route_rule;
 mod sriov;
+mod tun;
 mod user;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wifi;
 mod wired;
+mod wireguard;
 
 pub(crate) use keyfile::ToKeyfile;
@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::{NmSettingPpp, NmSettingPppoe, ToKeyfile};
+
+impl ToKeyfile for NmSettingPppoe {}
+
+impl ToKeyfile for NmSettingPpp {}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use zvariant::Value;
+
+use super::super::{
+    NmError, NmSettingWireless, NmSettingWirelessSecurity, ToDbusValue,
+    ToKeyfile,
+};
+
+impl ToKeyfile for NmSettingWireless {
+    fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        for (k, v) in self.to_value()?.drain() {
+            if k != "cloned-mac-address" && k != "ssid" {
+                ret.insert(k.to_string(), v);
+            }
+        }
+        if let Some(v) = &self.cloned_mac_address {
+            ret.insert("cloned-mac-address".to_string(), Value::new(v));
+        }
+        // NetworkManager keyfile stores printable SSID as plain string
+        if let Some(v) = &self.ssid {
+            ret.insert(
+                "ssid".to_string(),
+                match std::str::from_utf8(v) {
+                    Ok(s) => Value::new(s.to_string()),
+                    Err(_) => Value::new(v.clone()),
+                },
+            );
+        }
+        Ok(ret)
+    }
+}
+
+// Like NetworkManager, secret with non-zero flags is not stored in keyfile.
+impl ToKeyfile for NmSettingWirelessSecurity {
+    fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        for (k, v) in self.to_value()?.drain() {
+            if k != "psk" || self.psk_flags.unwrap_or_default() == 0 {
+                ret.insert(k.to_string(), v);
+            }
+        }
+        Ok(ret)
+    }
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::super::{
+    NmError, NmSettingWireGuard, NmWireGuardPeer, ToDbusValue, ToKeyfile,
+};
+
+impl ToKeyfile for NmSettingWireGuard {
+    fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        // Peers are stored in their own `[wireguard-peer.<public_key>]`
+        // sections.
+        for (k, v) in self.to_value()?.drain() {
+            if k != "peers" {
+                ret.insert(k.to_string(), v);
+            }
+        }
+        Ok(ret)
+    }
+}
+
+impl NmWireGuardPeer {
+    pub(crate) fn to_keyfile(&self) -> HashMap<String, zvariant::Value> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.endpoint {
+            ret.insert("endpoint".to_string(), zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.allowed_ips {
+            ret.insert("allowed-ips".to_string(), zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.preshared_key {
+            ret.insert("preshared-key".to_string(), zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.persistent_keepalive {
+            ret.insert(
+                "persistent-keepalive".to_string(),
+                zvariant::Value::new(v),
+            );
+        }
+        ret
+    }
+}
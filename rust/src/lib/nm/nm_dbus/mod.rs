@@ -2,6 +2,8 @@
 
 mod active_connection;
 mod connection;
+#[cfg(feature = "query_apply")]
+mod connectivity;
 mod convert;
 #[cfg(feature = "query_apply")]
 mod dbus;
@@ -25,16 +27,22 @@ pub use self::active_connection::NmActiveConnection;
 pub use self::active_connection::NM_ACTIVATION_STATE_FLAG_EXTERNAL;
 pub use self::connection::{
     NmConnection, NmIpRoute, NmIpRouteRule, NmIpRouteRuleAction, NmRange,
-    NmSetting8021X, NmSettingBond, NmSettingBridge, NmSettingBridgePort,
-    NmSettingBridgeVlanRange, NmSettingConnection, NmSettingEthtool,
-    NmSettingInfiniBand, NmSettingIp, NmSettingIpMethod, NmSettingLoopback,
-    NmSettingMacVlan, NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
-    NmSettingOvsIface, NmSettingOvsOtherConfig, NmSettingOvsPatch,
-    NmSettingOvsPort, NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan,
-    NmSettingUser, NmSettingVeth, NmSettingVlan, NmSettingVrf, NmSettingVxlan,
-    NmSettingWired, NmSettingsConnectionFlag, NmVlanProtocol,
+    NmSetting8021X, NmSettingBond, NmSettingBondPort, NmSettingBridge,
+    NmSettingBridgePort, NmSettingBridgeVlanRange, NmSettingConnection,
+    NmSettingEthtool, NmSettingGeneve, NmSettingGsm, NmSettingInfiniBand,
+    NmSettingIp, NmSettingIpMethod, NmSettingIpTunnel, NmSettingIpVlan,
+    NmSettingLoopback, NmSettingMacSec, NmSettingMacVlan, NmSettingOvsBridge,
+    NmSettingOvsDpdk, NmSettingOvsExtIds, NmSettingOvsIface,
+    NmSettingOvsOtherConfig, NmSettingOvsPatch, NmSettingOvsPort, NmSettingPpp,
+    NmSettingPppoe, NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan,
+    NmSettingTun, NmSettingUser, NmSettingVeth, NmSettingVlan, NmSettingVrf,
+    NmSettingVxlan, NmSettingWireGuard, NmSettingWired, NmSettingWireless,
+    NmSettingWirelessSecurity, NmSettingsConnectionFlag, NmVlanProtocol,
+    NmWireGuardPeer,
 };
 #[cfg(feature = "query_apply")]
+pub use self::connectivity::NmConnectivityState;
+#[cfg(feature = "query_apply")]
 pub use self::device::{NmDevice, NmDeviceState, NmDeviceStateReason};
 #[cfg(feature = "query_apply")]
 pub use self::dns::{NmDnsEntry, NmGlobalDnsConfig};
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::time::{Duration, Instant};
 
@@ -10,10 +11,12 @@ use super::{
         get_nm_ac_by_obj_path, nm_ac_obj_path_uuid_get, NmActiveConnection,
     },
     connection::{nm_con_get_from_obj_path, NmConnection},
+    connectivity::{nm_dev_get_connectivity, NmConnectivityState},
     dbus::NmDbus,
     device::{
-        nm_dev_delete, nm_dev_from_obj_path, nm_dev_get_llpd, NmDevice,
-        NmDeviceState, NmDeviceStateReason,
+        nm_dev_delete, nm_dev_from_obj_path, nm_dev_get_dhcp_options,
+        nm_dev_get_llpd, nm_dev_get_modem_bearer, NmDevice, NmDeviceState,
+        NmDeviceStateReason,
     },
     dns::{NmDnsEntry, NmGlobalDnsConfig},
     error::{ErrorKind, NmError},
@@ -287,6 +290,31 @@ impl<'a> NmApi<'a> {
         nm_dev_get_llpd(&self.dbus.connection, nm_dev_obj_path)
     }
 
+    pub fn device_connectivity_get(
+        &mut self,
+        nm_dev_obj_path: &str,
+    ) -> Result<(NmConnectivityState, NmConnectivityState), NmError> {
+        self.extend_timeout_if_required()?;
+        nm_dev_get_connectivity(&self.dbus.connection, nm_dev_obj_path)
+    }
+
+    pub fn device_modem_bearer_get(
+        &mut self,
+        nm_dev_obj_path: &str,
+    ) -> Result<(String, String), NmError> {
+        self.extend_timeout_if_required()?;
+        nm_dev_get_modem_bearer(&self.dbus.connection, nm_dev_obj_path)
+    }
+
+    pub fn device_dhcp_options_get(
+        &mut self,
+        nm_dev_obj_path: &str,
+        is_ipv6: bool,
+    ) -> Result<HashMap<String, String>, NmError> {
+        self.extend_timeout_if_required()?;
+        nm_dev_get_dhcp_options(&self.dbus.connection, nm_dev_obj_path, is_ipv6)
+    }
+
     // If any device is with NewActivation or IpConfig state,
     // we wait its activation.
     pub fn wait_checkpoint_rollback(
@@ -352,6 +380,16 @@ impl<'a> NmApi<'a> {
         }
     }
 
+    pub fn connectivity_get(&self) -> Result<NmConnectivityState, NmError> {
+        Ok(NmConnectivityState::from(self.dbus.connectivity()?))
+    }
+
+    /// Reload configuration files of NetworkManager daemon
+    pub fn reload(&mut self) -> Result<(), NmError> {
+        self.extend_timeout_if_required()?;
+        self.dbus.reload()
+    }
+
     pub fn extend_timeout_if_required(&mut self) -> Result<(), NmError> {
         if let (Some(cp_refresh_time), Some(checkpoint)) =
             (self.cp_refresh_time.as_ref(), self.checkpoint.as_ref())
@@ -4,6 +4,7 @@ use super::nm_dbus::{NmActiveConnection, NmConnection};
 use super::settings::{
     get_exist_profile, iface_to_nm_connections, remove_nm_mptcp_set,
     use_uuid_for_controller_reference, use_uuid_for_parent_reference,
+    NM_UNSUPPORTED_IFACE_TYPES,
 };
 
 use crate::{InterfaceType, MergedInterface, MergedNetworkState, NmstateError};
@@ -60,8 +61,14 @@ pub(crate) fn perpare_nm_conns(
         .cloned()
         .collect();
 
+    // NetworkManager does not support GTP, XFRM, CAN, vcan, L2TPv3, netkit
+    // and batman-adv interfaces, they are handled by `nispor_apply_gtp()`,
+    // `nispor_apply_xfrm()`, `nispor_apply_can()`, `nispor_apply_l2tpv3()`,
+    // `nispor_apply_netkit()` and `nispor_apply_batadv()` instead.
     for merged_iface in ifaces.iter().filter(|i| {
-        i.merged.iface_type() != InterfaceType::Unknown && !i.merged.is_absent()
+        i.merged.iface_type() != InterfaceType::Unknown
+            && !NM_UNSUPPORTED_IFACE_TYPES.contains(&i.merged.iface_type())
+            && !i.merged.is_absent()
     }) {
         let iface = if let Some(i) = merged_iface.for_apply.as_ref() {
             i
@@ -6,37 +6,58 @@ use super::super::{
     device::create_index_for_nm_devs,
     dns::{cur_dns_ifaces_still_valid_for_dns, store_dns_config_to_iface},
     error::nm_error_to_nmstate,
-    nm_dbus::{NmApi, NmConnection},
+    nm_dbus::{NmActiveConnection, NmApi, NmConnection},
     profile::{perpare_nm_conns, PerparedNmConnections},
     query_apply::{
         activate_nm_profiles, create_index_for_nm_conns_by_name_type,
         deactivate_nm_profiles, delete_exist_profiles, delete_orphan_ovs_ports,
         dns::{purge_global_dns_config, store_dns_config_via_global_api},
-        is_mptcp_flags_changed, is_mptcp_supported, is_route_removed,
+        drain_nm_ifaces, drain_sriov_vfs, is_dns_only_changed,
+        is_geneve_changed, is_ip_addr_only_changed, is_ip_tunnel_mode_matched,
+        is_ipvlan_changed, is_mptcp_flags_changed, is_mptcp_supported,
+        is_route_only_changed, is_route_removed, is_tun_changed,
         is_veth_peer_changed, is_vlan_changed, is_vrf_table_id_changed,
-        is_vxlan_changed, save_nm_profiles,
+        is_vxlan_changed, run_802_1x_enrollment, save_nm_profiles,
     },
     route::store_route_config,
     route_rule::store_route_rule_config,
-    settings::{iface_type_to_nm, NM_SETTING_OVS_PORT_SETTING_NAME},
+    settings::{
+        iface_type_to_nm, NM_SETTING_OVS_PORT_SETTING_NAME,
+        NM_UNSUPPORTED_IFACE_TYPES,
+    },
+    team::delete_team_profiles,
 };
 
-use crate::{InterfaceType, MergedNetworkState, NmstateError};
+use crate::{
+    nispor::nispor_delete_ifaces, ChangeRisk, InterfaceType,
+    MergedNetworkState, NetworkPlan, NmstateError,
+};
 
 // There is plan to simply the `add_net_state`, `chg_net_state`, `del_net_state`
 // `cur_net_state`, `des_net_state` into single struct. Suppress the clippy
 // warning for now
 pub(crate) fn nm_apply(
     merged_state: &MergedNetworkState,
-    checkpoint: &str,
+    checkpoint: Option<&str>,
     timeout: u32,
 ) -> Result<(), NmstateError> {
     let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
-    nm_api.set_checkpoint(checkpoint, timeout);
-    nm_api.set_checkpoint_auto_refresh(true);
+    // Without checkpoint, there is nothing to refresh
+    if let Some(checkpoint) = checkpoint {
+        nm_api.set_checkpoint(checkpoint, timeout);
+        nm_api.set_checkpoint_auto_refresh(true);
+    }
 
-    if !merged_state.memory_only {
+    if !merged_state.stage_only {
+        drain_nm_ifaces(&mut nm_api, merged_state)?;
+        drain_sriov_vfs(&mut nm_api, merged_state)?;
+    }
+
+    if !merged_state.memory_only && !merged_state.stage_only {
         delete_ifaces(&mut nm_api, merged_state)?;
+        if merged_state.migrate_team {
+            delete_team_profiles(&mut nm_api, merged_state)?;
+        }
     }
 
     if let Some(hostname) = merged_state
@@ -50,6 +71,10 @@ pub(crate) fn nm_apply(
                 "NM: Cannot change configure hostname in memory only mode, \
                 ignoring"
             );
+        } else if merged_state.stage_only {
+            log::info!(
+                "NM: Hostname will be changed when activating staged profiles"
+            );
         } else {
             nm_api.hostname_set(hostname).map_err(nm_error_to_nmstate)?;
         }
@@ -69,27 +94,37 @@ pub(crate) fn nm_apply(
 
     store_route_rule_config(&mut merged_state)?;
 
-    if merged_state.dns.is_changed()
-        || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces)
+    if !merged_state.stage_only
+        && (merged_state.dns.is_changed()
+            || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces))
     {
         purge_global_dns_config(&mut nm_api)?;
     }
 
     if let Err(e) = store_dns_config_to_iface(&mut merged_state) {
-        log::warn!(
-            "Cannot store DNS to NetworkManager interface connection: {e}"
-        );
-        log::warn!(
-            "Storing DNS to NetworkManager via global dns API, \
+        if merged_state.stage_only {
+            log::warn!(
+                "Cannot store DNS to NetworkManager interface connection: \
+                {e}, DNS will be changed when activating staged profiles"
+            );
+        } else {
+            log::warn!(
+                "Cannot store DNS to NetworkManager interface connection: {e}"
+            );
+            log::warn!(
+                "Storing DNS to NetworkManager via global dns API, \
             this will cause _all__ interface level DNS settings been ignored"
-        );
-        store_dns_config_via_global_api(
-            &mut nm_api,
-            merged_state.dns.servers.as_slice(),
-            merged_state.dns.searches.as_slice(),
-        )?;
+            );
+            store_dns_config_via_global_api(
+                &mut nm_api,
+                merged_state.dns.servers.as_slice(),
+                merged_state.dns.searches.as_slice(),
+            )?;
+        }
     }
 
+    run_802_1x_enrollment(&merged_state)?;
+
     let PerparedNmConnections {
         to_store: nm_conns_to_store,
         to_activate: nm_conns_to_activate,
@@ -102,18 +137,17 @@ pub(crate) fn nm_apply(
         false,
     )?;
 
-    let nm_ac_uuids: Vec<&str> =
-        nm_acs.iter().map(|nm_ac| &nm_ac.uuid as &str).collect();
-    let activated_nm_conns: Vec<&NmConnection> = exist_nm_conns
-        .iter()
-        .filter(|c| {
-            if let Some(uuid) = c.uuid() {
-                nm_ac_uuids.contains(&uuid)
-            } else {
-                false
-            }
-        })
-        .collect();
+    if merged_state.stage_only {
+        save_nm_profiles(&mut nm_api, nm_conns_to_store.as_slice(), false)?;
+        log::info!(
+            "NM: Staged {} profiles without activation",
+            nm_conns_to_store.len()
+        );
+        return Ok(());
+    }
+
+    let activated_nm_conns =
+        get_activated_nm_conns(exist_nm_conns.as_slice(), nm_acs.as_slice());
     let nm_conns_to_deactivate_first = gen_nm_conn_need_to_deactivate_first(
         nm_conns_to_activate.as_slice(),
         activated_nm_conns.as_slice(),
@@ -142,10 +176,16 @@ pub(crate) fn nm_apply(
         )?;
     }
 
+    let reapply_only_uuids = gen_reapply_only_uuids(
+        nm_conns_to_activate.as_slice(),
+        activated_nm_conns.as_slice(),
+    );
+
     activate_nm_profiles(
         &mut nm_api,
         nm_conns_to_activate.as_slice(),
         &nm_acs,
+        reapply_only_uuids.as_slice(),
     )?;
 
     deactivate_nm_profiles(&mut nm_api, nm_conns_to_deactivate.as_slice())?;
@@ -153,6 +193,119 @@ pub(crate) fn nm_apply(
     Ok(())
 }
 
+// Generate the same NetworkManager profiles as `nm_apply()` without saving or
+// activating them, then classify the disruption risk of each changed
+// interface by the same predicates used when activating.
+pub(crate) fn nm_plan(
+    merged_state: &MergedNetworkState,
+) -> Result<NetworkPlan, NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mptcp_supported = is_mptcp_supported(&nm_api);
+    let exist_nm_conns =
+        nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let nm_acs = nm_api
+        .active_connections_get()
+        .map_err(nm_error_to_nmstate)?;
+
+    let mut merged_state = merged_state.clone();
+    store_route_config(&mut merged_state)?;
+    store_route_rule_config(&mut merged_state)?;
+    // DNS stored via global API does not change the activation of profiles
+    if let Err(e) = store_dns_config_to_iface(&mut merged_state) {
+        log::debug!(
+            "Cannot store DNS to NetworkManager interface connection: {e}"
+        );
+    }
+
+    let PerparedNmConnections {
+        to_activate: nm_conns_to_activate,
+        ..
+    } = perpare_nm_conns(
+        &merged_state,
+        exist_nm_conns.as_slice(),
+        nm_acs.as_slice(),
+        mptcp_supported,
+        false,
+    )?;
+    let activated_nm_conns =
+        get_activated_nm_conns(exist_nm_conns.as_slice(), nm_acs.as_slice());
+
+    Ok(gen_nm_plan(
+        &merged_state,
+        nm_conns_to_activate.as_slice(),
+        activated_nm_conns.as_slice(),
+    ))
+}
+
+// * Absent or down interface is `Delete`.
+// * Profile deactivated first is `Reactivate`.
+// * Profile with only routes, IP addresses or DNS changed is `Reapply`.
+// * Other activations are `Flap` as reapply might fallback to activation.
+// * Interfaces without profile to activate use backend neutral risk.
+pub(crate) fn gen_nm_plan(
+    merged_state: &MergedNetworkState,
+    nm_conns_to_activate: &[NmConnection],
+    activated_nm_conns: &[&NmConnection],
+) -> NetworkPlan {
+    let nm_conns_to_deactivate_first = gen_nm_conn_need_to_deactivate_first(
+        nm_conns_to_activate,
+        activated_nm_conns,
+    );
+    let reapply_only_uuids =
+        gen_reapply_only_uuids(nm_conns_to_activate, activated_nm_conns);
+
+    let mut plan = NetworkPlan::new(merged_state);
+    for iface_plan in plan
+        .interfaces
+        .iter_mut()
+        .filter(|p| p.risk != ChangeRisk::Delete)
+    {
+        let nm_iface_type = iface_type_to_nm(&iface_plan.iface_type).ok();
+        let uuid = match nm_conns_to_activate
+            .iter()
+            .find(|c| {
+                c.iface_name() == Some(iface_plan.name.as_str())
+                    && (nm_iface_type.is_none()
+                        || c.iface_type() == nm_iface_type.as_deref())
+            })
+            .and_then(|c| c.uuid())
+        {
+            Some(u) => u,
+            None => continue,
+        };
+        iface_plan.risk = if nm_conns_to_deactivate_first
+            .iter()
+            .any(|c| c.uuid() == Some(uuid))
+        {
+            ChangeRisk::Reactivate
+        } else if reapply_only_uuids.contains(&uuid) {
+            ChangeRisk::Reapply
+        } else {
+            ChangeRisk::Flap
+        };
+    }
+    plan.sort();
+    plan
+}
+
+fn get_activated_nm_conns<'a>(
+    exist_nm_conns: &'a [NmConnection],
+    nm_acs: &[NmActiveConnection],
+) -> Vec<&'a NmConnection> {
+    let nm_ac_uuids: Vec<&str> =
+        nm_acs.iter().map(|nm_ac| &nm_ac.uuid as &str).collect();
+    exist_nm_conns
+        .iter()
+        .filter(|c| {
+            if let Some(uuid) = c.uuid() {
+                nm_ac_uuids.contains(&uuid)
+            } else {
+                false
+            }
+        })
+        .collect()
+}
+
 fn delete_ifaces(
     nm_api: &mut NmApi,
     merged_state: &MergedNetworkState,
@@ -189,6 +342,10 @@ fn delete_ifaces(
         // Delete all existing connections for this interface
         if let Some(nm_conns) = nm_conns_to_delete {
             for nm_conn in nm_conns {
+                // GRE and GRETAP share the same NM `ip-tunnel` type
+                if !is_ip_tunnel_mode_matched(nm_conn, &iface.iface_type()) {
+                    continue;
+                }
                 if let Some(uuid) = nm_conn.uuid() {
                     log::info!(
                         "Deleting NM connection for absent interface \
@@ -235,6 +392,7 @@ fn delete_remain_virtual_interface_as_desired(
 ) -> Result<(), NmstateError> {
     let nm_devs = nm_api.devices_get().map_err(nm_error_to_nmstate)?;
     let nm_devs_indexed = create_index_for_nm_devs(&nm_devs);
+    let mut netlink_ifaces_to_delete: Vec<&str> = Vec::new();
     // Interfaces created by non-NM tools will not be deleted by connection
     // deletion, remove manually.
     for iface in merged_state
@@ -246,6 +404,15 @@ fn delete_remain_virtual_interface_as_desired(
         })
         .map(|i| &i.merged)
     {
+        // NetworkManager cannot delete `generic` devices. The L2TPv3
+        // pseudowire is deleted by `nispor_apply_l2tpv3()` already.
+        if NM_UNSUPPORTED_IFACE_TYPES.contains(&iface.iface_type()) {
+            if iface.is_virtual() && iface.iface_type() != InterfaceType::L2tpv3
+            {
+                netlink_ifaces_to_delete.push(iface.name());
+            }
+            continue;
+        }
         if iface.is_virtual() {
             if let Some(nm_dev) = nm_devs_indexed.get(&(
                 iface.name().to_string(),
@@ -265,7 +432,7 @@ fn delete_remain_virtual_interface_as_desired(
             }
         }
     }
-    Ok(())
+    nispor_delete_ifaces(&netlink_ifaces_to_delete)
 }
 
 // If any connection still referring to deleted UUID, we should delete it also
@@ -306,6 +473,7 @@ fn delete_orphan_ports(
 // * NM cannot change VRF table ID, so we deactivate first
 // * VLAN config changed.
 // * Veth peer changed.
+// * IP VLAN mode changed.
 // * NM cannot reapply changes to MPTCP flags.
 fn gen_nm_conn_need_to_deactivate_first(
     nm_conns_to_activate: &[NmConnection],
@@ -327,7 +495,10 @@ fn gen_nm_conn_need_to_deactivate_first(
                     || is_vrf_table_id_changed(nm_conn, activated_nm_con)
                     || is_vlan_changed(nm_conn, activated_nm_con)
                     || is_vxlan_changed(nm_conn, activated_nm_con)
+                    || is_geneve_changed(nm_conn, activated_nm_con)
+                    || is_tun_changed(nm_conn, activated_nm_con)
                     || is_veth_peer_changed(nm_conn, activated_nm_con)
+                    || is_ipvlan_changed(nm_conn, activated_nm_con)
                     || is_mptcp_flags_changed(nm_conn, activated_nm_con)
                 {
                     ret.push((*activated_nm_con).clone());
@@ -337,3 +508,46 @@ fn gen_nm_conn_need_to_deactivate_first(
     }
     ret
 }
+
+// Activated profiles with only routes, routing rules, static IP addresses or
+// DNS changed will be reapplied without fallback to reactivation, so IP
+// addresses not added by nmstate are kept. The route removal case is
+// excluded as it is deactivated first by
+// `gen_nm_conn_need_to_deactivate_first()`.
+fn gen_reapply_only_uuids<'a>(
+    nm_conns_to_activate: &'a [NmConnection],
+    activated_nm_conns: &[&NmConnection],
+) -> Vec<&'a str> {
+    let mut ret: Vec<&str> = Vec::new();
+    for nm_conn in nm_conns_to_activate {
+        if let Some(uuid) = nm_conn.uuid() {
+            if let Some(activated_nm_con) =
+                activated_nm_conns.iter().find(|c| c.uuid() == Some(uuid))
+            {
+                let changed =
+                    if is_route_only_changed(nm_conn, activated_nm_con)
+                        && !is_route_removed(nm_conn, activated_nm_con)
+                    {
+                        "routes or routing rules"
+                    } else if is_ip_addr_only_changed(nm_conn, activated_nm_con)
+                    {
+                        "IP addresses"
+                    } else if is_dns_only_changed(nm_conn, activated_nm_con) {
+                        "DNS"
+                    } else {
+                        continue;
+                    };
+                log::info!(
+                    "Only {} changed for connection {}: {}/{}, will \
+                    reapply without reactivation",
+                    changed,
+                    uuid,
+                    nm_conn.iface_name().unwrap_or(""),
+                    nm_conn.iface_type().unwrap_or("")
+                );
+                ret.push(uuid);
+            }
+        }
+    }
+    ret
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::DhcpLease;
+
+const NM_DHCP4_OPT_SERVER_ID: &str = "dhcp_server_identifier";
+const NM_DHCP4_OPT_LEASE_TIME: &str = "dhcp_lease_time";
+const NM_DHCP4_OPT_EXPIRY: &str = "expiry";
+// NetworkManager internal DHCP client uses `private_<code>` for option not
+// parsed by itself.
+const NM_DHCP4_OPT_RELAY_AGENT_INFO: [&str; 2] =
+    ["relay_agent_information", "private_82"];
+const NM_DHCP6_OPT_SERVER_ID: &str = "dhcp6_server_id";
+const NM_DHCP6_OPT_MAX_LIFE: &str = "max_life";
+
+pub(crate) fn nm_dhcp_opts_to_nmstate(
+    opts: &HashMap<String, String>,
+    is_ipv6: bool,
+) -> Option<DhcpLease> {
+    if opts.is_empty() {
+        return None;
+    }
+    let lease = if is_ipv6 {
+        DhcpLease {
+            server_id: opts.get(NM_DHCP6_OPT_SERVER_ID).cloned(),
+            lease_time: parse_opt(opts, NM_DHCP6_OPT_MAX_LIFE),
+            ..Default::default()
+        }
+    } else {
+        DhcpLease {
+            server_id: opts.get(NM_DHCP4_OPT_SERVER_ID).cloned(),
+            lease_time: parse_opt(opts, NM_DHCP4_OPT_LEASE_TIME),
+            expiry: parse_opt(opts, NM_DHCP4_OPT_EXPIRY),
+            relay_agent_info: NM_DHCP4_OPT_RELAY_AGENT_INFO
+                .iter()
+                .find_map(|k| opts.get(*k))
+                .cloned(),
+        }
+    };
+    if lease == DhcpLease::default() {
+        None
+    } else {
+        Some(lease)
+    }
+}
+
+fn parse_opt<T: std::str::FromStr>(
+    opts: &HashMap<String, String>,
+    key: &str,
+) -> Option<T> {
+    let value = opts.get(key)?;
+    match value.parse::<T>() {
+        Ok(v) => Some(v),
+        Err(_) => {
+            log::warn!("Ignoring invalid DHCP option {key}: {value}");
+            None
+        }
+    }
+}
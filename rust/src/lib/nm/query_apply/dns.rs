@@ -4,7 +4,9 @@ use std::str::FromStr;
 
 use super::super::{
     error::nm_error_to_nmstate,
-    nm_dbus::{NmApi, NmDnsEntry, NmGlobalDnsConfig, NmSettingIp},
+    nm_dbus::{
+        NmApi, NmConnection, NmDnsEntry, NmGlobalDnsConfig, NmSettingIp,
+    },
 };
 
 use crate::{
@@ -170,3 +172,46 @@ pub(crate) fn nm_global_dns_to_nmstate(
         config: Some(config),
     }
 }
+
+// Whether the only difference between new and current NmConnection is the DNS
+// servers, searches or priority stored in profile. NM can apply such changes
+// via `Device.Reapply` without reactivating the connection, hence pure DNS
+// rotation will not cause connectivity blip.
+pub(crate) fn is_dns_only_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    get_dns_settings(new_nm_conn) != get_dns_settings(cur_nm_conn)
+        && strip_dns(new_nm_conn) == strip_dns(cur_nm_conn)
+}
+
+type NmDnsSettings<'a> =
+    (Option<&'a [String]>, Option<&'a [String]>, Option<i32>);
+
+fn get_dns_settings(nm_conn: &NmConnection) -> Vec<NmDnsSettings> {
+    nm_conn
+        .ipv4
+        .iter()
+        .chain(nm_conn.ipv6.iter())
+        .map(|ip| {
+            (
+                ip.dns.as_deref().filter(|d| !d.is_empty()),
+                ip.dns_search.as_deref().filter(|d| !d.is_empty()),
+                ip.dns_priority,
+            )
+        })
+        .collect()
+}
+
+// The D-Bus object path and flags are not part of profile settings.
+fn strip_dns(nm_conn: &NmConnection) -> NmConnection {
+    let mut nm_conn = nm_conn.clone();
+    nm_conn.obj_path = String::new();
+    nm_conn.flags = Vec::new();
+    for nm_ip_set in nm_conn.ipv4.iter_mut().chain(nm_conn.ipv6.iter_mut()) {
+        nm_ip_set.dns = None;
+        nm_ip_set.dns_search = None;
+        nm_ip_set.dns_priority = None;
+    }
+    nm_conn
+}
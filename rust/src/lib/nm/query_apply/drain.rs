@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+use super::super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{NmApi, NmConnection, NM_ACTIVATION_STATE_FLAG_EXTERNAL},
+};
+use crate::{MergedNetworkState, NmstateError};
+
+// Routes of draining interface will only be used when no other path is
+// available.
+const DRAIN_ROUTE_METRIC: u32 = u32::MAX;
+const DRAIN_WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+// For interfaces changing from up to down or absent with `drain-delay`
+// defined, raise the metric of routes on them via reapply so that traffic
+// could move to other paths, then wait for the longest delay before any
+// deactivation or deletion.
+pub(crate) fn drain_nm_ifaces(
+    nm_api: &mut NmApi,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let drain_ifaces: Vec<(&str, u32)> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.current.as_ref().map(|c| c.is_up()) == Some(true))
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_down() || i.is_absent())
+        .filter_map(|i| {
+            i.base_iface()
+                .drain_delay
+                .filter(|d| *d > 0)
+                .map(|d| (i.name(), d))
+        })
+        .collect();
+    if drain_ifaces.is_empty() {
+        return Ok(());
+    }
+
+    let applied_nm_conns = nm_api
+        .applied_connections_get()
+        .map_err(nm_error_to_nmstate)?;
+    let mut wait_secs = 0;
+    for (iface_name, drain_delay) in drain_ifaces {
+        let nm_conn = if let Some(c) = applied_nm_conns
+            .iter()
+            .find(|c| c.iface_name() == Some(iface_name))
+        {
+            c
+        } else {
+            log::debug!(
+                "No applied connection found for interface {iface_name}, \
+                skipping drain"
+            );
+            continue;
+        };
+        log::info!(
+            "Draining interface {iface_name} by raising route metric to \
+            {DRAIN_ROUTE_METRIC} for {drain_delay} seconds"
+        );
+        if let Err(e) =
+            nm_api.connection_reapply(&gen_nm_conn_for_drain(nm_conn))
+        {
+            log::warn!("Failed to drain interface {iface_name}: {e}");
+            continue;
+        }
+        wait_secs = std::cmp::max(wait_secs, drain_delay);
+    }
+
+    if wait_secs > 0 {
+        log::info!("Waiting {wait_secs} seconds for interfaces to drain");
+        let deadline = Instant::now() + Duration::from_secs(wait_secs.into());
+        while Instant::now() < deadline {
+            // Prevent checkpoint from timeout during the wait
+            nm_api
+                .extend_timeout_if_required()
+                .map_err(nm_error_to_nmstate)?;
+            std::thread::sleep(std::cmp::min(
+                DRAIN_WAIT_INTERVAL,
+                deadline.saturating_duration_since(Instant::now()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// When `total-vfs` of SR-IOV PF is decreasing, deactivate the profiles on the
+// VFs to be removed before changing `sriov_numvfs`, so that kernel does not
+// remove the VF interfaces from under active connections. VFs used outside of
+// NetworkManager are only warned as we cannot detach them gracefully.
+pub(crate) fn drain_sriov_vfs(
+    nm_api: &mut NmApi,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut vf_iface_names: Vec<&str> = Vec::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        for vf in merged_iface.get_sriov_vfs_to_remove() {
+            if vf.iface_name.is_empty() {
+                log::warn!(
+                    "SR-IOV VF {} of PF {} has no network interface{}, it \
+                    might be used by userspace application or virtual \
+                    machine, removing it by decreasing total-vfs",
+                    vf.id,
+                    merged_iface.merged.name(),
+                    vf.driver
+                        .as_deref()
+                        .map(|d| format!(" but bound to driver {d}"))
+                        .unwrap_or_default()
+                );
+            } else {
+                vf_iface_names.push(vf.iface_name.as_str());
+            }
+        }
+    }
+    if vf_iface_names.is_empty() {
+        return Ok(());
+    }
+
+    let nm_acs = nm_api
+        .active_connections_get()
+        .map_err(nm_error_to_nmstate)?;
+    for iface_name in vf_iface_names {
+        for nm_ac in nm_acs.iter().filter(|a| a.iface_name == iface_name) {
+            if (nm_ac.state_flags & NM_ACTIVATION_STATE_FLAG_EXTERNAL) > 0 {
+                log::warn!(
+                    "SR-IOV VF interface {iface_name} is used externally, \
+                    removing it by decreasing total-vfs"
+                );
+            } else {
+                log::info!(
+                    "Deactivating connection {} of SR-IOV VF interface \
+                    {iface_name} to be removed by decreasing total-vfs",
+                    nm_ac.uuid
+                );
+                nm_api
+                    .connection_deactivate(nm_ac.uuid.as_str())
+                    .map_err(nm_error_to_nmstate)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn gen_nm_conn_for_drain(nm_conn: &NmConnection) -> NmConnection {
+    let mut nm_conn = nm_conn.clone();
+    for nm_ip_set in nm_conn.ipv4.iter_mut().chain(nm_conn.ipv6.iter_mut()) {
+        nm_ip_set.route_metric = Some(DRAIN_ROUTE_METRIC.into());
+        for nm_route in nm_ip_set.routes.iter_mut() {
+            nm_route.metric = Some(DRAIN_ROUTE_METRIC);
+        }
+    }
+    nm_conn
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+// Kernel does not support changing the VNI of existing Geneve interface
+pub(crate) fn is_geneve_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    if let (Some(new_geneve_conf), Some(cur_geneve_conf)) =
+        (new_nm_conn.geneve.as_ref(), cur_nm_conn.geneve.as_ref())
+    {
+        new_geneve_conf.id != cur_geneve_conf.id
+    } else {
+        false
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingGsm;
+
+use crate::GsmConfig;
+
+pub(crate) fn nm_gsm_to_nmstate(nm_setting: &NmSettingGsm) -> GsmConfig {
+    let mut conf = GsmConfig::new();
+    conf.apn = nm_setting.apn.clone();
+    conf.pin = nm_setting.pin.clone();
+    conf.username = nm_setting.username.clone();
+    conf.password = nm_setting.password.clone();
+    conf.roaming = nm_setting.home_only.map(|v| !v);
+    conf
+}
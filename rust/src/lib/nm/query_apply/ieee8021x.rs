@@ -2,7 +2,7 @@
 
 use super::super::nm_dbus::NmSetting8021X;
 
-use crate::Ieee8021XConfig;
+use crate::{Ieee8021XConfig, MergedNetworkState, NmstateError};
 
 pub(crate) fn nm_802_1x_to_nmstate(
     nm_setting: &NmSetting8021X,
@@ -20,9 +20,30 @@ pub(crate) fn nm_802_1x_to_nmstate(
             .and_then(vec_u8_to_file_path),
         ca_cert: nm_setting.ca_cert.as_deref().and_then(vec_u8_to_file_path),
         private_key_password: nm_setting.private_key_password.clone(),
+        enrollment_command: None,
     }
 }
 
+// Enrollment commands should be done before profile creation, so the
+// certificate and key files referred exist when activating.
+pub(crate) fn run_802_1x_enrollment(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let mut ifaces: Vec<_> = merged_state
+        .interfaces
+        .iter()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up())
+        .collect();
+    ifaces.sort_unstable_by_key(|i| i.name());
+    for iface in ifaces {
+        if let Some(conf) = iface.base_iface().ieee8021x.as_ref() {
+            conf.run_enrollment_command(iface.name())?;
+        }
+    }
+    Ok(())
+}
+
 fn vec_u8_to_file_path(raw: &[u8]) -> Option<String> {
     match NmSetting8021X::glib_bytes_to_file_path(raw) {
         Ok(s) => Some(s),
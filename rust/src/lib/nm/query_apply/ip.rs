@@ -1,16 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::convert::TryFrom;
 use std::ops::BitXor;
 
 use super::super::nm_dbus::{
-    NmIpRouteRuleAction, NmSettingIp, NmSettingIpMethod,
+    NmConnection, NmIpRouteRuleAction, NmSettingIp, NmSettingIpMethod,
 };
 
 use super::dns::nm_dns_to_nmstate;
 
 use crate::{
-    AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpv4, InterfaceIpv6,
-    Ipv6AddrGenMode, RouteRuleAction, RouteRuleEntry, WaitIp,
+    AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr, InterfaceIpv4,
+    InterfaceIpv6, Ipv6AddrGenMode, RouteEntry, RouteRuleAction,
+    RouteRuleEntry, WaitIp,
 };
 
 const ADDR_GEN_MODE_EUI64: i32 = 0;
@@ -42,6 +44,7 @@ pub(crate) fn nm_ip_setting_to_nmstate4(
             auto_routes,
             auto_gateway,
             auto_table_id,
+            route_table: nm_route_table_to_nmstate(enabled, nm_ip_setting),
             prop_list: vec![
                 "enabled",
                 "dhcp",
@@ -51,6 +54,7 @@ pub(crate) fn nm_ip_setting_to_nmstate4(
                 "auto_routes",
                 "auto_gateway",
                 "auto_table_id",
+                "route_table",
                 "auto_route_metric",
                 "rules",
             ],
@@ -89,6 +93,7 @@ pub(crate) fn nm_ip_setting_to_nmstate6(
             auto_routes,
             auto_gateway,
             auto_table_id,
+            route_table: nm_route_table_to_nmstate(enabled, nm_ip_setting),
             prop_list: vec![
                 "enabled",
                 "dhcp",
@@ -99,6 +104,7 @@ pub(crate) fn nm_ip_setting_to_nmstate6(
                 "auto_routes",
                 "auto_gateway",
                 "auto_table_id",
+                "route_table",
                 "dhcp_duid",
                 "addr_gen_mode",
                 "auto_route_metric",
@@ -148,6 +154,18 @@ fn parse_dhcp_opts(
     }
 }
 
+// Only show non-default route table to keep the output clean
+fn nm_route_table_to_nmstate(
+    enabled: bool,
+    nm_setting: &NmSettingIp,
+) -> Option<u32> {
+    if enabled {
+        nm_setting.route_table.filter(|t| *t != 0)
+    } else {
+        None
+    }
+}
+
 fn flip_bool(v: bool) -> bool {
     v.bitxor(true)
 }
@@ -279,3 +297,76 @@ fn nm_rules_to_nmstate(
     }
     Some(ret)
 }
+
+pub(crate) fn nm_ip_addrs_to_nmstate(
+    nm_ip_setting: &NmSettingIp,
+) -> Vec<InterfaceIpAddr> {
+    let mut addrs = Vec::new();
+    for nm_addr in nm_ip_setting.addresses.as_slice() {
+        match InterfaceIpAddr::try_from(nm_addr.as_str()) {
+            Ok(addr) => addrs.push(addr),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring invalid IP address {} in NetworkManager \
+                    profile: {}",
+                    nm_addr,
+                    e
+                );
+            }
+        }
+    }
+    addrs
+}
+
+pub(crate) fn nm_routes_to_nmstate(
+    iface_name: &str,
+    nm_ip_setting: &NmSettingIp,
+) -> Vec<RouteEntry> {
+    let mut routes = Vec::new();
+    for nm_route in nm_ip_setting.routes.as_slice() {
+        if let (Some(dest), Some(prefix)) =
+            (nm_route.dest.as_deref(), nm_route.prefix)
+        {
+            let mut route = RouteEntry::new();
+            route.destination = Some(format!("{dest}/{prefix}"));
+            route.next_hop_iface = Some(iface_name.to_string());
+            route.next_hop_addr = nm_route.next_hop.clone();
+            route.metric = nm_route.metric.map(i64::from);
+            route.table_id = nm_route.table;
+            route.weight = nm_route.weight.and_then(|w| u16::try_from(w).ok());
+            routes.push(route);
+        }
+    }
+    routes
+}
+
+// Whether the only difference between new and current NmConnection is the
+// static IP addresses. NM can apply such changes via `Device.Reapply` which
+// does not flush the addresses added by other tools, while activation does.
+pub(crate) fn is_ip_addr_only_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    get_ip_addrs(new_nm_conn) != get_ip_addrs(cur_nm_conn)
+        && strip_ip_addrs(new_nm_conn) == strip_ip_addrs(cur_nm_conn)
+}
+
+fn get_ip_addrs(nm_conn: &NmConnection) -> Vec<&[String]> {
+    nm_conn
+        .ipv4
+        .iter()
+        .chain(nm_conn.ipv6.iter())
+        .map(|ip| ip.addresses.as_slice())
+        .collect()
+}
+
+// The D-Bus object path and flags are not part of profile settings.
+fn strip_ip_addrs(nm_conn: &NmConnection) -> NmConnection {
+    let mut nm_conn = nm_conn.clone();
+    nm_conn.obj_path = String::new();
+    nm_conn.flags = Vec::new();
+    for nm_ip_set in nm_conn.ipv4.iter_mut().chain(nm_conn.ipv6.iter_mut()) {
+        nm_ip_set.addresses.clear();
+    }
+    nm_conn
+}
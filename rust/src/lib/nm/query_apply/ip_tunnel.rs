@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use super::super::{
+    nm_dbus::{NmConnection, NmSettingIpTunnel},
+    settings::{
+        NM_IP_TUNNEL_MODE_GRE, NM_IP_TUNNEL_MODE_GRETAP,
+        NM_IP_TUNNEL_MODE_IPIP, NM_IP_TUNNEL_MODE_SIT, NM_IP_TUNNEL_MODE_VTI,
+        NM_IP_TUNNEL_MODE_VTI6, NM_SETTING_IP_TUNNEL_SETTING_NAME,
+    },
+};
+
+use crate::{GreConfig, InterfaceType, IpTunnelConfig, VtiConfig};
+
+// NetworkManager uses the same `ip-tunnel` setting for all kinds of IP tunnel,
+// the interface type is determined by the tunnel mode.
+pub(crate) fn nm_ip_tunnel_iface_type(nm_conn: &NmConnection) -> InterfaceType {
+    match nm_conn.ip_tunnel.as_ref().and_then(|t| t.mode) {
+        Some(NM_IP_TUNNEL_MODE_GRE) => InterfaceType::Gre,
+        Some(NM_IP_TUNNEL_MODE_GRETAP) => InterfaceType::GreTap,
+        Some(NM_IP_TUNNEL_MODE_IPIP) => InterfaceType::IpIp,
+        Some(NM_IP_TUNNEL_MODE_SIT) => InterfaceType::Sit,
+        Some(NM_IP_TUNNEL_MODE_VTI) => InterfaceType::Vti,
+        Some(NM_IP_TUNNEL_MODE_VTI6) => InterfaceType::Vti6,
+        _ => {
+            InterfaceType::Other(NM_SETTING_IP_TUNNEL_SETTING_NAME.to_string())
+        }
+    }
+}
+
+// Prevent deleting IP tunnel profile of other tunnel mode when tunnel
+// interface of the same name is marked as absent, e.g. GRETAP profile should
+// not be deleted for absent GRE interface.
+pub(crate) fn is_ip_tunnel_mode_matched(
+    nm_conn: &NmConnection,
+    iface_type: &InterfaceType,
+) -> bool {
+    match iface_type {
+        InterfaceType::Gre
+        | InterfaceType::GreTap
+        | InterfaceType::IpIp
+        | InterfaceType::Sit
+        | InterfaceType::Vti
+        | InterfaceType::Vti6 => {
+            nm_conn.ip_tunnel.is_none()
+                || &nm_ip_tunnel_iface_type(nm_conn) == iface_type
+        }
+        _ => true,
+    }
+}
+
+pub(crate) fn nm_gre_to_nmstate(nm_setting: &NmSettingIpTunnel) -> GreConfig {
+    let mut conf = GreConfig::new();
+    conf.local = nm_setting
+        .local
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    conf.remote = nm_setting
+        .remote
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    conf.ikey = nm_setting
+        .input_key
+        .as_deref()
+        .and_then(|k| u32::from_str(k).ok());
+    conf.okey = nm_setting
+        .output_key
+        .as_deref()
+        .and_then(|k| u32::from_str(k).ok());
+    conf.ttl = nm_setting.ttl.and_then(|t| u8::try_from(t).ok());
+    conf
+}
+
+pub(crate) fn nm_ip_tunnel_to_nmstate(
+    nm_setting: &NmSettingIpTunnel,
+) -> IpTunnelConfig {
+    let mut conf = IpTunnelConfig::new();
+    conf.local = nm_setting
+        .local
+        .as_deref()
+        .and_then(|a| std::net::Ipv4Addr::from_str(a).ok());
+    conf.remote = nm_setting
+        .remote
+        .as_deref()
+        .and_then(|a| std::net::Ipv4Addr::from_str(a).ok());
+    conf.ttl = nm_setting.ttl.and_then(|t| u8::try_from(t).ok());
+    conf.pmtudisc = nm_setting.path_mtu_discovery;
+    conf
+}
+
+pub(crate) fn nm_vti_to_nmstate(nm_setting: &NmSettingIpTunnel) -> VtiConfig {
+    let mut conf = VtiConfig::new();
+    conf.local = nm_setting
+        .local
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    conf.remote = nm_setting
+        .remote
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    conf.ikey = nm_setting
+        .input_key
+        .as_deref()
+        .and_then(|k| u32::from_str(k).ok());
+    conf.okey = nm_setting
+        .output_key
+        .as_deref()
+        .and_then(|k| u32::from_str(k).ok());
+    conf
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+// Kernel does not support changing the mode of existing IP VLAN interface
+pub(crate) fn is_ipvlan_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    if let (Some(new_ipvlan_conf), Some(cur_ipvlan_conf)) =
+        (new_nm_conn.ipvlan.as_ref(), cur_nm_conn.ipvlan.as_ref())
+    {
+        new_ipvlan_conf.mode != cur_ipvlan_conf.mode
+    } else {
+        false
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingMacSec;
+
+use crate::MacSecConfig;
+
+// Other MACsec properties are queried from kernel via nispor, hence only
+// include the MKA keys which kernel has no knowledge of.
+pub(crate) fn nm_macsec_to_nmstate(
+    nm_setting: &NmSettingMacSec,
+) -> MacSecConfig {
+    let mut conf = MacSecConfig::new();
+    conf.mka_cak = nm_setting.mka_cak.clone();
+    conf.mka_ckn = nm_setting.mka_ckn.clone();
+    conf
+}
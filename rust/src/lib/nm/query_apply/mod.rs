@@ -1,36 +1,67 @@
 // SPDX-License-Identifier: Apache-2.0
 
-mod apply;
+pub(crate) mod apply;
+mod dhcp;
 pub(crate) mod dns;
+pub(crate) mod drain;
+mod geneve;
+mod gsm;
 mod ieee8021x;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
 mod lldp;
+mod macsec;
 mod mptcp;
+mod multi_connect;
 mod ovs;
-mod profile;
+mod permissions;
+mod pppoe;
+pub(crate) mod profile;
 mod route;
+mod tun;
 mod user;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wifi;
+mod wireguard;
 
-pub(crate) use self::apply::nm_apply;
-pub(crate) use self::dns::retrieve_dns_info;
-pub(crate) use self::ieee8021x::nm_802_1x_to_nmstate;
+pub(crate) use self::apply::{nm_apply, nm_plan};
+pub(crate) use self::dhcp::nm_dhcp_opts_to_nmstate;
+pub(crate) use self::dns::{is_dns_only_changed, retrieve_dns_info};
+pub(crate) use self::drain::{drain_nm_ifaces, drain_sriov_vfs};
+pub(crate) use self::geneve::is_geneve_changed;
+pub(crate) use self::gsm::nm_gsm_to_nmstate;
+pub(crate) use self::ieee8021x::{nm_802_1x_to_nmstate, run_802_1x_enrollment};
 pub(crate) use self::ip::{
-    nm_ip_setting_to_nmstate4, nm_ip_setting_to_nmstate6, query_nmstate_wait_ip,
+    is_ip_addr_only_changed, nm_ip_addrs_to_nmstate, nm_ip_setting_to_nmstate4,
+    nm_ip_setting_to_nmstate6, nm_routes_to_nmstate, query_nmstate_wait_ip,
 };
+pub(crate) use self::ip_tunnel::{
+    is_ip_tunnel_mode_matched, nm_gre_to_nmstate, nm_ip_tunnel_iface_type,
+    nm_ip_tunnel_to_nmstate, nm_vti_to_nmstate,
+};
+pub(crate) use self::ipvlan::is_ipvlan_changed;
 pub(crate) use self::lldp::{get_lldp, is_lldp_enabled};
+pub(crate) use self::macsec::nm_macsec_to_nmstate;
 pub(crate) use self::mptcp::{is_mptcp_flags_changed, is_mptcp_supported};
+pub(crate) use self::multi_connect::get_multi_connect;
 pub(crate) use self::ovs::delete_orphan_ovs_ports;
+pub(crate) use self::permissions::get_permissions;
+pub(crate) use self::pppoe::nm_pppoe_to_nmstate;
 pub(crate) use self::profile::{
     activate_nm_profiles, create_index_for_nm_conns_by_name_type,
-    deactivate_nm_profiles, delete_exist_profiles, save_nm_profiles,
+    deactivate_nm_profiles, delete_exist_profiles, delete_profiles,
+    save_nm_profiles,
 };
-pub(crate) use self::route::is_route_removed;
-pub(crate) use self::user::get_description;
+pub(crate) use self::route::{is_route_only_changed, is_route_removed};
+pub(crate) use self::tun::is_tun_changed;
+pub(crate) use self::user::{get_description, get_metadata};
 pub(crate) use self::veth::is_veth_peer_changed;
 pub(crate) use self::vlan::is_vlan_changed;
 pub(crate) use self::vrf::is_vrf_table_id_changed;
 pub(crate) use self::vxlan::is_vxlan_changed;
+pub(crate) use self::wifi::nm_wifi_to_nmstate;
+pub(crate) use self::wireguard::nm_wireguard_to_nmstate;
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::nm_dbus::{NmApi, NmConnection};
+use super::super::{
+    nm_dbus::{NmApi, NmConnection},
+    version::{NmVersion, NM_VERSION_MPTCP},
+};
 
 pub(crate) fn is_mptcp_flags_changed(
     nm_conn: &NmConnection,
@@ -19,16 +22,7 @@ pub(crate) fn is_mptcp_flags_changed(
 }
 
 pub(crate) fn is_mptcp_supported(nm_api: &NmApi) -> bool {
-    let version_str = nm_api.version().unwrap_or_default();
-    let versions: Vec<&str> = version_str.split('.').collect();
-    if versions.len() < 2 {
-        return false;
-    }
-    if let (Ok(major), Ok(minor)) =
-        (versions[0].parse::<i32>(), versions[1].parse::<i32>())
-    {
-        major >= 1 && minor >= 40
-    } else {
-        false
-    }
+    NmVersion::parse(&nm_api.version().unwrap_or_default())
+        .map(|v| v.is_newer_or_equal(NM_VERSION_MPTCP))
+        .unwrap_or_default()
 }
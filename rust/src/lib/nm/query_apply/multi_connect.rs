@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+use super::super::settings::{
+    NM_MULTI_CONNECT_DEFAULT, NM_MULTI_CONNECT_MANUAL_MULTIPLE,
+    NM_MULTI_CONNECT_MULTIPLE, NM_MULTI_CONNECT_SINGLE,
+};
+
+use crate::MultiConnect;
+
+// NetworkManager does not include default value in connection settings,
+// hence `None` is returned for [MultiConnect::Default].
+pub(crate) fn get_multi_connect(
+    nm_conn: &NmConnection,
+) -> Option<MultiConnect> {
+    match nm_conn.connection.as_ref().and_then(|s| s.multi_connect) {
+        Some(NM_MULTI_CONNECT_SINGLE) => Some(MultiConnect::Single),
+        Some(NM_MULTI_CONNECT_MANUAL_MULTIPLE) => {
+            Some(MultiConnect::ManualMultiple)
+        }
+        Some(NM_MULTI_CONNECT_MULTIPLE) => Some(MultiConnect::Multiple),
+        Some(NM_MULTI_CONNECT_DEFAULT) | None => None,
+        Some(v) => {
+            log::warn!("Unknown NetworkManager multi-connect value {}", v);
+            None
+        }
+    }
+}
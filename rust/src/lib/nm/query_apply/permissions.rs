@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+use super::super::settings::NM_PERMISSION_USER_PREFIX;
+
+// Empty permissions means all users are allowed, `None` is returned for
+// that.
+pub(crate) fn get_permissions(nm_conn: &NmConnection) -> Option<Vec<String>> {
+    let mut users = Vec::new();
+    for permission in nm_conn
+        .connection
+        .as_ref()
+        .and_then(|s| s.permissions.as_deref())
+        .unwrap_or_default()
+    {
+        match permission
+            .strip_prefix(NM_PERMISSION_USER_PREFIX)
+            .and_then(|p| p.split(':').next())
+        {
+            Some(user) if !user.is_empty() => users.push(user.to_string()),
+            _ => {
+                log::warn!(
+                    "Unknown NetworkManager connection permission {}",
+                    permission
+                );
+            }
+        }
+    }
+    if users.is_empty() {
+        None
+    } else {
+        users.sort_unstable();
+        Some(users)
+    }
+}
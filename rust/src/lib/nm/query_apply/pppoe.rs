@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingPppoe;
+
+use crate::PppoeConfig;
+
+// Kernel has no knowledge of PPPoE session configuration, hence all
+// properties are queried from NetworkManager profile.
+pub(crate) fn nm_pppoe_to_nmstate(nm_setting: &NmSettingPppoe) -> PppoeConfig {
+    let mut conf = PppoeConfig::new();
+    conf.parent = nm_setting.parent.clone().unwrap_or_default();
+    conf.username = nm_setting.username.clone();
+    conf.password = nm_setting.password.clone();
+    conf.service = nm_setting.service.clone();
+    conf
+}
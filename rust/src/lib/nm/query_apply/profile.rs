@@ -123,6 +123,7 @@ pub(crate) fn activate_nm_profiles(
     nm_api: &mut NmApi,
     nm_conns: &[NmConnection],
     nm_acs: &[NmActiveConnection],
+    reapply_only_uuids: &[&str],
 ) -> Result<(), NmstateError> {
     let mut nm_conns = nm_conns.to_vec();
     let nm_ac_uuids: Vec<&str> =
@@ -134,6 +135,7 @@ pub(crate) fn activate_nm_profiles(
                 nm_api,
                 nm_conns.as_slice(),
                 nm_ac_uuids.as_slice(),
+                reapply_only_uuids,
             )?;
             if remain_nm_conns.is_empty() {
                 break;
@@ -166,6 +168,7 @@ fn _activate_nm_profiles(
     nm_api: &mut NmApi,
     nm_conns: &[NmConnection],
     nm_ac_uuids: &[&str],
+    reapply_only_uuids: &[&str],
 ) -> Result<Vec<(NmConnection, NmstateError)>, NmstateError> {
     let mut new_controllers: Vec<&str> = Vec::new();
     let mut failed_nm_conns: Vec<(NmConnection, NmstateError)> = Vec::new();
@@ -181,7 +184,11 @@ fn _activate_nm_profiles(
                 nm_conn.iface_type().unwrap_or("")
             );
             if nm_ac_uuids.contains(&uuid) {
-                if let Err(e) = reapply_or_activate(nm_api, nm_conn) {
+                if let Err(e) = reapply_or_activate(
+                    nm_api,
+                    nm_conn,
+                    reapply_only_uuids.contains(&uuid),
+                ) {
                     if e.kind().can_retry() {
                         failed_nm_conns.push((nm_conn.clone(), e));
                     } else {
@@ -203,10 +210,20 @@ fn _activate_nm_profiles(
             }
         }
     }
-    for nm_conn in nm_conns.iter().filter(|c| {
-        c.iface_type().map(|t| NM_SETTING_CONTROLLERS.contains(&t))
-            != Some(true)
-    }) {
+    let other_nm_conns: Vec<&NmConnection> = nm_conns
+        .iter()
+        .filter(|c| {
+            c.iface_type().map(|t| NM_SETTING_CONTROLLERS.contains(&t))
+                != Some(true)
+        })
+        .collect();
+    for activation in coalesce_device_activations(
+        &other_nm_conns,
+        nm_ac_uuids,
+        reapply_only_uuids,
+        new_controllers.as_slice(),
+    ) {
+        let nm_conn = activation.nm_conn;
         if let Some(uuid) = nm_conn.uuid() {
             if nm_ac_uuids.contains(&uuid) {
                 log::info!(
@@ -215,7 +232,11 @@ fn _activate_nm_profiles(
                     nm_conn.iface_name().unwrap_or(""),
                     nm_conn.iface_type().unwrap_or("")
                 );
-                if let Err(e) = reapply_or_activate(nm_api, nm_conn) {
+                if let Err(e) = reapply_or_activate(
+                    nm_api,
+                    nm_conn,
+                    activation.reapply_only,
+                ) {
                     if e.kind().can_retry() {
                         failed_nm_conns.push((nm_conn.clone(), e));
                     } else {
@@ -223,24 +244,6 @@ fn _activate_nm_profiles(
                     }
                 }
             } else {
-                if let Some(ctrller) = nm_conn.controller() {
-                    if nm_conn.iface_type() != Some("ovs-interface") {
-                        // OVS port does not do auto port activation.
-                        if new_controllers.contains(&ctrller)
-                            && nm_conn.controller_type() != Some("ovs-port")
-                        {
-                            log::info!(
-                                "Skip connection activation as its \
-                                controller already activated its ports: \
-                                {}: {}/{}",
-                                uuid,
-                                nm_conn.iface_name().unwrap_or(""),
-                                nm_conn.iface_type().unwrap_or("")
-                            );
-                            continue;
-                        }
-                    }
-                }
                 log::info!(
                     "Activating connection {}: {}/{}",
                     uuid,
@@ -263,6 +266,97 @@ fn _activate_nm_profiles(
     Ok(failed_nm_conns)
 }
 
+// Single activation or reapply of kernel device holding all the profile
+// changes pending on it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NmDeviceActivation<'a> {
+    // The last changed profile of the device, NetworkManager can only have
+    // one active profile on each device.
+    pub(crate) nm_conn: &'a NmConnection,
+    // UUIDs of all the profiles changed for this device in this apply.
+    pub(crate) pending_uuids: Vec<&'a str>,
+    // Only reapply when all the pending changes can be reapplied.
+    pub(crate) reapply_only: bool,
+}
+
+// Activate each kernel device at most once in single apply, otherwise NIC
+// might flap multiple times, for example once for controller attaching and
+// once for ethtool changes:
+//  * Profile changes are grouped by kernel device, the device is activated
+//    or reapplied once with the last changed profile at the position of its
+//    first pending change.
+//  * Inactive ports of newly activated controller are activated by their
+//    controller with all pending settings stored in profile, hence no need to
+//    activate them again. Active ones are still reapplied.
+pub(crate) fn coalesce_device_activations<'a>(
+    nm_conns: &[&'a NmConnection],
+    nm_ac_uuids: &[&str],
+    reapply_only_uuids: &[&str],
+    new_controllers: &[&str],
+) -> Vec<NmDeviceActivation<'a>> {
+    let mut activations: Vec<NmDeviceActivation> = Vec::new();
+    let mut device_indexes: HashMap<&str, usize> = HashMap::new();
+    for nm_conn in nm_conns {
+        let uuid = match nm_conn.uuid() {
+            Some(u) => u,
+            None => continue,
+        };
+        // Profile without interface name is not bound to any device yet
+        let device = nm_conn.iface_name().unwrap_or(uuid);
+        match device_indexes.entry(device) {
+            Entry::Occupied(o) => {
+                let activation = &mut activations[*o.get()];
+                activation.nm_conn = nm_conn;
+                if !activation.pending_uuids.contains(&uuid) {
+                    activation.pending_uuids.push(uuid);
+                }
+            }
+            Entry::Vacant(v) => {
+                v.insert(activations.len());
+                activations.push(NmDeviceActivation {
+                    nm_conn,
+                    pending_uuids: vec![uuid],
+                    reapply_only: false,
+                });
+            }
+        }
+    }
+
+    let mut ret: Vec<NmDeviceActivation> = Vec::new();
+    for mut activation in activations {
+        let nm_conn = activation.nm_conn;
+        let uuid = nm_conn.uuid().unwrap_or("");
+        let iface_name = nm_conn.iface_name().unwrap_or("");
+        let iface_type = nm_conn.iface_type().unwrap_or("");
+        if activation.pending_uuids.len() > 1 {
+            log::info!(
+                "Coalescing changes of connections {} into single \
+                activation of {uuid}: {iface_name}/{iface_type}",
+                activation.pending_uuids.join(", ")
+            );
+        }
+        // OVS port does not do auto port activation.
+        if !nm_ac_uuids.contains(&uuid)
+            && nm_conn.controller().map(|c| new_controllers.contains(&c))
+                == Some(true)
+            && iface_type != "ovs-interface"
+            && nm_conn.controller_type() != Some("ovs-port")
+        {
+            log::info!(
+                "Skip connection activation as its controller already \
+                activated its ports: {uuid}: {iface_name}/{iface_type}"
+            );
+            continue;
+        }
+        activation.reapply_only = activation
+            .pending_uuids
+            .iter()
+            .all(|u| reapply_only_uuids.contains(u));
+        ret.push(activation);
+    }
+    ret
+}
+
 pub(crate) fn deactivate_nm_profiles(
     nm_api: &mut NmApi,
     nm_conns: &[NmConnection],
@@ -338,10 +432,19 @@ pub(crate) fn delete_profiles(
     Ok(())
 }
 
+// When only routes, routing rules or DNS changed, we do not fallback to
+// activation on reapply failure to avoid carrier flap of pure routing or DNS
+// update.
 fn reapply_or_activate(
     nm_api: &mut NmApi,
     nm_conn: &NmConnection,
+    reapply_only: bool,
 ) -> Result<(), NmstateError> {
+    if reapply_only {
+        return nm_api
+            .connection_reapply(nm_conn)
+            .map_err(nm_error_to_nmstate);
+    }
     if let Err(e) = nm_api.connection_reapply(nm_conn) {
         if let Some(uuid) = nm_conn.uuid() {
             log::debug!(
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::nm_dbus::{NmConnection, NmIpRoute};
+use super::super::nm_dbus::{NmConnection, NmIpRoute, NmIpRouteRule};
 
 pub(crate) fn is_route_removed(
     new_nm_conn: &NmConnection,
@@ -42,3 +42,37 @@ fn is_nm_ip_route_removed(
     }
     false
 }
+
+// Whether the only difference between new and current NmConnection is the
+// routes or routing rules. NM can apply such changes via `Device.Reapply`
+// without reactivating the connection, hence no carrier flap.
+pub(crate) fn is_route_only_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    get_routes_and_rules(new_nm_conn) != get_routes_and_rules(cur_nm_conn)
+        && strip_routes(new_nm_conn) == strip_routes(cur_nm_conn)
+}
+
+fn get_routes_and_rules(
+    nm_conn: &NmConnection,
+) -> Vec<(&[NmIpRoute], &[NmIpRouteRule])> {
+    nm_conn
+        .ipv4
+        .iter()
+        .chain(nm_conn.ipv6.iter())
+        .map(|ip| (ip.routes.as_slice(), ip.route_rules.as_slice()))
+        .collect()
+}
+
+// The D-Bus object path and flags are not part of profile settings.
+fn strip_routes(nm_conn: &NmConnection) -> NmConnection {
+    let mut nm_conn = nm_conn.clone();
+    nm_conn.obj_path = String::new();
+    nm_conn.flags = Vec::new();
+    for nm_ip_set in nm_conn.ipv4.iter_mut().chain(nm_conn.ipv6.iter_mut()) {
+        nm_ip_set.routes.clear();
+        nm_ip_set.route_rules.clear();
+    }
+    nm_conn
+}
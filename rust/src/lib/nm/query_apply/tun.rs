@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+// Kernel does not support changing the mode and flags of existing TUN/TAP
+// interface
+pub(crate) fn is_tun_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    if let (Some(new_tun_conf), Some(cur_tun_conf)) =
+        (new_nm_conn.tun.as_ref(), cur_nm_conn.tun.as_ref())
+    {
+        new_tun_conf.mode != cur_tun_conf.mode
+            || new_tun_conf.multi_queue != cur_tun_conf.multi_queue
+            || new_tun_conf.pi != cur_tun_conf.pi
+    } else {
+        false
+    }
+}
@@ -1,8 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use super::super::nm_dbus::NmConnection;
 
-use super::super::settings::NMSTATE_DESCRIPTION;
+use super::super::settings::{NMSTATE_DESCRIPTION, NMSTATE_LABEL_PREFIX};
+use crate::InterfaceMetadata;
 
 pub(crate) fn get_description(nm_conn: &NmConnection) -> Option<String> {
     Some(
@@ -15,3 +18,28 @@ pub(crate) fn get_description(nm_conn: &NmConnection) -> Option<String> {
             .unwrap_or_default(),
     )
 }
+
+pub(crate) fn get_metadata(
+    nm_conn: &NmConnection,
+) -> Option<InterfaceMetadata> {
+    let labels: HashMap<String, String> = nm_conn
+        .user
+        .as_ref()
+        .and_then(|nm_setting| nm_setting.data.as_ref())
+        .map(|data| {
+            data.iter()
+                .filter_map(|(k, v)| {
+                    k.strip_prefix(NMSTATE_LABEL_PREFIX)
+                        .map(|k| (k.to_string(), v.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if labels.is_empty() {
+        None
+    } else {
+        let mut metadata = InterfaceMetadata::new();
+        metadata.labels = Some(labels);
+        Some(metadata)
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmSettingWireless, NmSettingWirelessSecurity};
+
+use super::super::settings::{
+    NM_SETTING_SECRET_FLAG_AGENT_OWNED, NM_SETTING_SECRET_FLAG_NOT_SAVED,
+};
+
+use crate::{
+    WifiBand, WifiConfig, WifiKeyMgmt, WifiMode, WifiSecretFlag,
+    WifiSecurityConfig,
+};
+
+const NM_WIFI_KEY_MGMT_WPA_PSK: &str = "wpa-psk";
+const NM_WIFI_KEY_MGMT_SAE: &str = "sae";
+const NM_WIFI_KEY_MGMT_WPA_EAP: &str = "wpa-eap";
+const NM_WIFI_KEY_MGMT_WPA_EAP_SUITE_B_192: &str = "wpa-eap-suite-b-192";
+
+// Kernel has no knowledge of the network to associate with, hence all
+// properties are queried from NetworkManager profile.
+pub(crate) fn nm_wifi_to_nmstate(
+    nm_wifi_set: &NmSettingWireless,
+    nm_sec_set: Option<&NmSettingWirelessSecurity>,
+) -> WifiConfig {
+    let mut conf = WifiConfig::new();
+    conf.mode = match nm_wifi_set.mode.as_deref() {
+        Some("ap") => Some(WifiMode::Ap),
+        _ => Some(WifiMode::Station),
+    };
+    if let Some(ssid) = nm_wifi_set.ssid.as_deref() {
+        conf.ssid = String::from_utf8_lossy(ssid).to_string();
+    }
+    conf.band = match nm_wifi_set.band.as_deref() {
+        Some("bg") => Some(WifiBand::Band2G4),
+        Some("a") => Some(WifiBand::Band5G),
+        _ => None,
+    };
+    // Channel 0 means automatic channel selection in NetworkManager
+    conf.channel = nm_wifi_set.channel.filter(|c| *c != 0);
+    conf.hidden = Some(nm_wifi_set.hidden.unwrap_or_default());
+    conf.security = nm_sec_set.and_then(|nm_sec_set| {
+        let key_mgmt = match nm_sec_set.key_mgmt.as_deref() {
+            Some(NM_WIFI_KEY_MGMT_WPA_PSK) => WifiKeyMgmt::WpaPsk,
+            Some(NM_WIFI_KEY_MGMT_SAE) => WifiKeyMgmt::Sae,
+            Some(NM_WIFI_KEY_MGMT_WPA_EAP) => WifiKeyMgmt::WpaEap,
+            Some(NM_WIFI_KEY_MGMT_WPA_EAP_SUITE_B_192) => {
+                WifiKeyMgmt::WpaEapSuiteB192
+            }
+            v => {
+                log::debug!("Unsupported Wi-Fi key management {v:?}");
+                return None;
+            }
+        };
+        let mut sec_conf = WifiSecurityConfig::new();
+        sec_conf.key_mgmt = key_mgmt;
+        sec_conf.psk = nm_sec_set.psk.clone();
+        sec_conf.psk_flags = match nm_sec_set.psk_flags {
+            Some(NM_SETTING_SECRET_FLAG_AGENT_OWNED) => {
+                Some(WifiSecretFlag::AgentOwned)
+            }
+            Some(NM_SETTING_SECRET_FLAG_NOT_SAVED) => {
+                Some(WifiSecretFlag::NotSaved)
+            }
+            _ => None,
+        };
+        Some(sec_conf)
+    });
+    conf
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingWireGuard;
+
+use crate::{WireGuardConfig, WireGuardPeerConfig};
+
+pub(crate) fn nm_wireguard_to_nmstate(
+    nm_setting: &NmSettingWireGuard,
+) -> WireGuardConfig {
+    let mut conf = WireGuardConfig::new();
+    conf.private_key = nm_setting.private_key.clone();
+    conf.listen_port = nm_setting.listen_port.map(|p| p as u16);
+    conf.fwmark = nm_setting.fwmark;
+    conf.peers = Some(
+        nm_setting
+            .peers
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|nm_peer| {
+                let mut peer = WireGuardPeerConfig::new();
+                peer.public_key =
+                    nm_peer.public_key.clone().unwrap_or_default();
+                peer.endpoint = nm_peer.endpoint.clone();
+                peer.allowed_ips = nm_peer.allowed_ips.clone();
+                peer.preshared_key = nm_peer.preshared_key.clone();
+                peer.persistent_keepalive = nm_peer.persistent_keepalive;
+                peer
+            })
+            .collect(),
+    );
+    conf
+}
@@ -337,7 +337,7 @@ fn iface_has_route_for_table_id(
             }
         }
     }
-    // For interface with `auto_table_id`
+    // For interface with `auto_table_id` or `route_table`
     if let Some(iface) = merged_state.interfaces.kernel_ifaces.get(iface_name) {
         if is_ipv6 {
             let iface_table_id = iface
@@ -345,7 +345,7 @@ fn iface_has_route_for_table_id(
                 .base_iface()
                 .ipv6
                 .as_ref()
-                .and_then(|i| i.auto_table_id)
+                .and_then(|i| i.auto_table_id.or(i.route_table))
                 .unwrap_or(DEFAULT_TABLE_ID);
             if iface_table_id == table_id
                 || (iface_table_id == RouteEntry::USE_DEFAULT_ROUTE_TABLE
@@ -359,7 +359,7 @@ fn iface_has_route_for_table_id(
                 .base_iface()
                 .ipv4
                 .as_ref()
-                .and_then(|i| i.auto_table_id)
+                .and_then(|i| i.auto_table_id.or(i.route_table))
                 .unwrap_or(DEFAULT_TABLE_ID);
             if iface_table_id == table_id
                 || (iface_table_id == RouteEntry::USE_DEFAULT_ROUTE_TABLE
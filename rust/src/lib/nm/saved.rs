@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{NmApi, NmConnection},
+    query_apply::{
+        is_lldp_enabled, nm_ip_addrs_to_nmstate, nm_routes_to_nmstate,
+    },
+    settings::NM_SETTING_OVS_PORT_SETTING_NAME,
+    show::{iface_get, nm_dns_retrieve, nm_iface_type_to_nmstate},
+};
+use crate::{
+    BondConfig, BondMode, Interface, InterfaceState, LinuxBridgeConfig,
+    LinuxBridgePortConfig, NetworkState, NmstateError, OvsBridgeConfig,
+    OvsBridgePortConfig, RouteEntry, VlanConfig,
+};
+
+// Retrieve the configuration stored in NetworkManager connection profiles
+// regardless of whether they are activated or not.
+pub(crate) fn nm_retrieve_saved() -> Result<NetworkState, NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let nm_saved_conns =
+        nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let mut net_state = nm_saved_conns_to_nmstate(nm_saved_conns.as_slice());
+    net_state.dns = nm_dns_retrieve(&mut nm_api, &net_state.interfaces)?;
+    net_state.dns.running = None;
+    Ok(net_state)
+}
+
+pub(crate) fn nm_saved_conns_to_nmstate(
+    nm_conns: &[NmConnection],
+) -> NetworkState {
+    let mut net_state = NetworkState::new();
+    net_state.prop_list = vec!["interfaces", "routes", "rules", "dns"];
+
+    let mut uuid_to_name: HashMap<&str, &str> = HashMap::new();
+    let mut ovs_port_to_ctrl: HashMap<&str, &str> = HashMap::new();
+    for nm_conn in nm_conns {
+        if let (Some(uuid), Some(iface_name)) =
+            (nm_conn.uuid(), nm_conn.iface_name())
+        {
+            uuid_to_name.insert(uuid, iface_name);
+        }
+        if nm_conn.iface_type() == Some(NM_SETTING_OVS_PORT_SETTING_NAME) {
+            if let (Some(iface_name), Some(ctrl)) =
+                (nm_conn.iface_name(), nm_conn.controller())
+            {
+                ovs_port_to_ctrl.insert(iface_name, ctrl);
+            }
+        }
+    }
+    // The controller or VLAN parent could be referred by UUID
+    let resolve_name = |name_or_uuid: &str| -> String {
+        uuid_to_name
+            .get(name_or_uuid)
+            .copied()
+            .unwrap_or(name_or_uuid)
+            .to_string()
+    };
+
+    // The profile with autoconnect disabled is only used for
+    // `state: down` interface, prefer the one with autoconnect enabled when
+    // multiple profiles exist for the same interface.
+    let mut nm_conns: Vec<&NmConnection> = nm_conns.iter().collect();
+    nm_conns.sort_by_key(|nm_conn| !is_autoconnect(nm_conn));
+
+    let mut routes: Vec<RouteEntry> = Vec::new();
+    let mut rules = Vec::new();
+    let mut ctrl_ports: HashMap<String, Vec<String>> = HashMap::new();
+    for nm_conn in nm_conns {
+        let iface_type = match nm_conn.iface_type() {
+            Some(t) => nm_iface_type_to_nmstate(
+                t,
+                nm_conn.mac_vlan.as_ref().and_then(|m| m.tap) == Some(true),
+            ),
+            None => continue,
+        };
+        let lldp_neighbors = if is_lldp_enabled(nm_conn) {
+            Some(Vec::new())
+        } else {
+            None
+        };
+        let mut iface =
+            match iface_get(iface_type, nm_conn, Some(nm_conn), lldp_neighbors)
+            {
+                Some(i) => i,
+                None => continue,
+            };
+        if net_state
+            .interfaces
+            .get_iface(iface.name(), iface.iface_type())
+            .is_some()
+        {
+            log::debug!(
+                "Ignoring extra NetworkManager profile {} for interface \
+                {}/{}",
+                nm_conn.uuid().unwrap_or_default(),
+                iface.name(),
+                iface.iface_type()
+            );
+            continue;
+        }
+        if !is_autoconnect(nm_conn) {
+            iface.base_iface_mut().state = InterfaceState::Down;
+        }
+        let iface_name = iface.name().to_string();
+
+        let base_iface = iface.base_iface_mut();
+        if let (Some(ipv4), Some(nm_ip_set)) =
+            (base_iface.ipv4.as_mut(), nm_conn.ipv4.as_ref())
+        {
+            if ipv4.enabled {
+                ipv4.prop_list.push("addresses");
+                ipv4.addresses = Some(nm_ip_addrs_to_nmstate(nm_ip_set));
+            }
+            rules.extend(ipv4.rules.take().unwrap_or_default());
+            routes.extend(nm_routes_to_nmstate(&iface_name, nm_ip_set));
+        }
+        if let (Some(ipv6), Some(nm_ip_set)) =
+            (base_iface.ipv6.as_mut(), nm_conn.ipv6.as_ref())
+        {
+            if ipv6.enabled {
+                ipv6.prop_list.push("addresses");
+                ipv6.addresses = Some(nm_ip_addrs_to_nmstate(nm_ip_set));
+            }
+            rules.extend(ipv6.rules.take().unwrap_or_default());
+            routes.extend(nm_routes_to_nmstate(&iface_name, nm_ip_set));
+        }
+        if let Some(ctrl) = base_iface.controller.as_deref() {
+            let mut ctrl = resolve_name(ctrl);
+            if let Some(ovs_br) = ovs_port_to_ctrl.get(ctrl.as_str()) {
+                ctrl = resolve_name(ovs_br);
+            }
+            ctrl_ports
+                .entry(ctrl.clone())
+                .or_default()
+                .push(iface_name.clone());
+            base_iface.controller = Some(ctrl);
+        }
+
+        match &mut iface {
+            Interface::Bond(bond_iface) => {
+                if let Some(mode) = nm_conn
+                    .bond
+                    .as_ref()
+                    .and_then(|nm_bond| nm_bond.options.get("mode"))
+                {
+                    bond_iface.bond.get_or_insert_with(BondConfig::new).mode =
+                        serde_json::from_value::<BondMode>(
+                            serde_json::Value::String(mode.to_string()),
+                        )
+                        .ok();
+                }
+            }
+            Interface::Vlan(vlan_iface) => {
+                if let Some(nm_vlan) = nm_conn.vlan.as_ref() {
+                    if let (Some(parent), Some(id)) =
+                        (nm_vlan.parent.as_deref(), nm_vlan.id)
+                    {
+                        vlan_iface.vlan = Some(VlanConfig {
+                            base_iface: resolve_name(parent),
+                            id: u16::try_from(id).unwrap_or_default(),
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+        net_state.interfaces.push(iface);
+    }
+
+    for iface in net_state
+        .interfaces
+        .kernel_ifaces
+        .values_mut()
+        .chain(net_state.interfaces.user_ifaces.values_mut())
+    {
+        if let Some(mut ports) = ctrl_ports.remove(iface.name()) {
+            ports.sort_unstable();
+            set_ports(iface, ports);
+        }
+    }
+
+    net_state.routes.config = Some(routes);
+    net_state.rules.config = Some(rules);
+    net_state
+}
+
+// NetworkManager activates profile automatically if not defined.
+fn is_autoconnect(nm_conn: &NmConnection) -> bool {
+    nm_conn.connection.as_ref().and_then(|c| c.autoconnect) != Some(false)
+}
+
+fn set_ports(iface: &mut Interface, ports: Vec<String>) {
+    match iface {
+        Interface::Bond(iface) => {
+            iface.bond.get_or_insert_with(BondConfig::new).port = Some(ports);
+        }
+        Interface::LinuxBridge(iface) => {
+            iface.bridge.get_or_insert_with(LinuxBridgeConfig::new).port = Some(
+                ports
+                    .into_iter()
+                    .map(|name| LinuxBridgePortConfig {
+                        name,
+                        ..Default::default()
+                    })
+                    .collect(),
+            );
+        }
+        Interface::OvsBridge(iface) => {
+            iface.bridge.get_or_insert_with(OvsBridgeConfig::new).ports = Some(
+                ports
+                    .into_iter()
+                    .map(|name| OvsBridgePortConfig {
+                        name,
+                        ..Default::default()
+                    })
+                    .collect(),
+            );
+        }
+        _ => {
+            log::debug!(
+                "Ignoring ports {:?} of non-controller interface {}/{}",
+                ports,
+                iface.name(),
+                iface.iface_type()
+            );
+        }
+    }
+}
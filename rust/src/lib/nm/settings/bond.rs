@@ -48,6 +48,30 @@ pub(crate) fn gen_nm_bond_setting(
     nm_conn.bond = Some(nm_bond_setting);
 }
 
+pub(crate) fn gen_nm_bond_port_setting(
+    bond_iface: &BondInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let port_conf = if let Some(p) = nm_conn
+        .iface_name()
+        .and_then(|iface_name| bond_iface.get_port_conf(iface_name))
+    {
+        p
+    } else {
+        return;
+    };
+    let mut nm_set = nm_conn.bond_port.as_ref().cloned().unwrap_or_default();
+
+    if let Some(v) = port_conf.priority {
+        nm_set.priority = Some(v);
+    }
+    if let Some(v) = port_conf.queue_id {
+        nm_set.queue_id = Some(v.into());
+    }
+
+    nm_conn.bond_port = Some(nm_set);
+}
+
 fn apply_bond_mode(nm_bond_set: &mut NmSettingBond, bond_conf: &BondConfig) {
     if let Some(mode) = bond_conf.mode {
         if Some(&mode.to_string()) != nm_bond_set.options.get("mode") {
@@ -98,6 +122,9 @@ fn apply_bond_options(
             nm_bond_set
                 .options
                 .insert("arp_ip_target".to_string(), String::new());
+            nm_bond_set
+                .options
+                .insert("ns_ip6_target".to_string(), String::new());
         }
         nm_bond_set
             .options
@@ -108,6 +135,11 @@ fn apply_bond_options(
             .options
             .insert("arp_ip_target".to_string(), v.clone());
     }
+    if let Some(v) = bond_opts.arp_missed_max.as_ref() {
+        nm_bond_set
+            .options
+            .insert("arp_missed_max".to_string(), v.to_string());
+    }
     if let Some(v) = bond_opts.arp_validate.as_ref() {
         nm_bond_set
             .options
@@ -143,6 +175,11 @@ fn apply_bond_options(
             .options
             .insert("min_links".to_string(), v.to_string());
     }
+    if let Some(v) = bond_opts.ns_ip6_target.as_ref() {
+        nm_bond_set
+            .options
+            .insert("ns_ip6_target".to_string(), v.clone());
+    }
     if let Some(v) = bond_opts.num_grat_arp.as_ref() {
         nm_bond_set
             .options
@@ -1,30 +1,43 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::nm_dbus::{
-    NmConnection, NmSettingConnection, NmSettingMacVlan, NmSettingVeth,
-    NmSettingVrf, NmSettingVxlan, NmSettingsConnectionFlag,
+    NmConnection, NmSettingConnection, NmSettingGeneve, NmSettingIpVlan,
+    NmSettingMacVlan, NmSettingTun, NmSettingVeth, NmSettingVrf,
+    NmSettingVxlan, NmSettingsConnectionFlag,
 };
 use super::{
-    bond::gen_nm_bond_setting,
+    bond::{gen_nm_bond_port_setting, gen_nm_bond_setting},
     bridge::{gen_nm_br_port_setting, gen_nm_br_setting},
     ethtool::gen_ethtool_setting,
+    gen_nm_user_setting,
+    gsm::gen_nm_gsm_setting,
     ieee8021x::gen_nm_802_1x_setting,
     infiniband::gen_nm_ib_setting,
     ip::gen_nm_ip_setting,
+    ip_tunnel::gen_nm_ip_tunnel_setting,
     loopback::gen_nm_loopback_setting,
+    macsec::gen_nm_macsec_setting,
     mptcp::apply_mptcp_conf,
+    multi_connect::apply_multi_connect,
     ovs::{
         create_ovs_port_nm_conn, gen_nm_iface_ovs_db_setting,
         gen_nm_ovs_br_setting, gen_nm_ovs_iface_setting, get_ovs_port_name,
     },
+    permissions::apply_permissions,
+    pppoe::gen_nm_pppoe_setting,
     sriov::gen_nm_sriov_setting,
-    user::gen_nm_user_setting,
     veth::create_veth_peer_profile_if_not_found,
     vlan::gen_nm_vlan_setting,
+    wifi::gen_nm_wifi_setting,
     wired::gen_nm_wired_setting,
+    wireguard::gen_nm_wireguard_setting,
 };
 
 use crate::{
+    nm_conf::{
+        NM_PROFILE_NAMING_IFACE_NAME, NM_PROFILE_NAMING_NAME,
+        NM_PROFILE_NAMING_NMSTATE_PREFIX, NM_PROFILE_NAMING_TYPE,
+    },
     ErrorKind, Interface, InterfaceType, MergedInterface, MergedNetworkState,
     NmstateError, OvsBridgePortConfig,
 };
@@ -38,11 +51,34 @@ pub(crate) const NM_SETTING_VETH_SETTING_NAME: &str = "veth";
 pub(crate) const NM_SETTING_BOND_SETTING_NAME: &str = "bond";
 pub(crate) const NM_SETTING_DUMMY_SETTING_NAME: &str = "dummy";
 pub(crate) const NM_SETTING_MACVLAN_SETTING_NAME: &str = "macvlan";
+pub(crate) const NM_SETTING_IPVLAN_SETTING_NAME: &str = "ipvlan";
 pub(crate) const NM_SETTING_VRF_SETTING_NAME: &str = "vrf";
 pub(crate) const NM_SETTING_VLAN_SETTING_NAME: &str = "vlan";
 pub(crate) const NM_SETTING_VXLAN_SETTING_NAME: &str = "vxlan";
 pub(crate) const NM_SETTING_INFINIBAND_SETTING_NAME: &str = "infiniband";
 pub(crate) const NM_SETTING_LOOPBACK_SETTING_NAME: &str = "loopback";
+pub(crate) const NM_SETTING_TEAM_SETTING_NAME: &str = "team";
+pub(crate) const NM_SETTING_WIREGUARD_SETTING_NAME: &str = "wireguard";
+pub(crate) const NM_SETTING_MACSEC_SETTING_NAME: &str = "macsec";
+pub(crate) const NM_SETTING_IP_TUNNEL_SETTING_NAME: &str = "ip-tunnel";
+pub(crate) const NM_SETTING_GENEVE_SETTING_NAME: &str = "geneve";
+pub(crate) const NM_SETTING_GENERIC_SETTING_NAME: &str = "generic";
+pub(crate) const NM_SETTING_TUN_SETTING_NAME: &str = "tun";
+pub(crate) const NM_SETTING_PPPOE_SETTING_NAME: &str = "pppoe";
+pub(crate) const NM_SETTING_GSM_SETTING_NAME: &str = "gsm";
+pub(crate) const NM_SETTING_WIRELESS_SETTING_NAME: &str = "802-11-wireless";
+
+// NetworkManager cannot create these interfaces, nmstate creates them via
+// netlink and NetworkManager only sees them as `generic` devices.
+pub(crate) const NM_UNSUPPORTED_IFACE_TYPES: [InterfaceType; 7] = [
+    InterfaceType::Gtp,
+    InterfaceType::Xfrm,
+    InterfaceType::Can,
+    InterfaceType::Vcan,
+    InterfaceType::L2tpv3,
+    InterfaceType::Netkit,
+    InterfaceType::Batadv,
+];
 
 pub(crate) const NM_SETTING_USER_SPACES: [&str; 2] = [
     NM_SETTING_OVS_BRIDGE_SETTING_NAME,
@@ -65,12 +101,24 @@ pub(crate) fn iface_to_nm_connections(
     };
 
     let base_iface = iface.base_iface();
-    let exist_nm_conn = get_exist_profile(
-        exist_nm_conns,
-        &base_iface.name,
-        &base_iface.iface_type,
-        nm_ac_uuids,
-    );
+    let exist_nm_conn = match base_iface.profile_uuid.as_deref() {
+        Some(uuid) => get_exist_profile_by_uuid(
+            exist_nm_conns,
+            uuid,
+            &base_iface.name,
+            &base_iface.iface_type,
+            nm_ac_uuids,
+        )?,
+        None => None,
+    }
+    .or_else(|| {
+        get_exist_profile(
+            exist_nm_conns,
+            &base_iface.name,
+            &base_iface.iface_type,
+            nm_ac_uuids,
+        )
+    });
     if iface.is_up_exist_config() {
         if let Some(nm_conn) = exist_nm_conn {
             if !iface.is_userspace()
@@ -112,18 +160,30 @@ pub(crate) fn iface_to_nm_connections(
     // This enable us to generate the same output for `nm_gen_conf()`
     // when the desire state is the same.
     let stable_uuid = gen_conf_mode;
+    let profile_naming = merged_state.networkmanager.profile_naming();
 
-    gen_nm_conn_setting(iface, &mut nm_conn, stable_uuid)?;
+    gen_nm_conn_setting(iface, &mut nm_conn, stable_uuid, profile_naming)?;
     gen_nm_ip_setting(
         iface,
         iface.base_iface().routes.as_deref(),
         &mut nm_conn,
     )?;
-    // InfiniBand over IP and loopback can not have layer 2 configuration.
+    // InfiniBand over IP, loopback, WireGuard, GRE, IPIP, SIT, VTI, VTI6, PPPoE
+    // and GSM can not have layer 2 configuration. The layer 2 configuration of Wi-Fi is stored
+    // in wireless setting.
     if iface.iface_type() != InterfaceType::InfiniBand
         && iface.iface_type() != InterfaceType::Loopback
+        && iface.iface_type() != InterfaceType::WireGuard
+        && iface.iface_type() != InterfaceType::Gre
+        && iface.iface_type() != InterfaceType::IpIp
+        && iface.iface_type() != InterfaceType::Sit
+        && iface.iface_type() != InterfaceType::Vti
+        && iface.iface_type() != InterfaceType::Vti6
+        && iface.iface_type() != InterfaceType::Pppoe
+        && iface.iface_type() != InterfaceType::Wifi
+        && iface.iface_type() != InterfaceType::Gsm
     {
-        gen_nm_wired_setting(iface, &mut nm_conn);
+        gen_nm_wired_setting(iface, &mut nm_conn, gen_conf_mode);
     }
     gen_nm_iface_ovs_db_setting(iface, &mut nm_conn);
     gen_nm_802_1x_setting(iface, &mut nm_conn);
@@ -146,6 +206,7 @@ pub(crate) fn iface_to_nm_connections(
                     ovs_port_conf,
                     exist_nm_ovs_port_conn,
                     stable_uuid,
+                    profile_naming,
                 )?)
             }
         }
@@ -166,6 +227,39 @@ pub(crate) fn iface_to_nm_connections(
                 nm_conn.vxlan = Some(NmSettingVxlan::from(conf))
             }
         }
+        Interface::Geneve(geneve_iface) => {
+            if let Some(conf) = geneve_iface.geneve.as_ref() {
+                nm_conn.geneve = Some(NmSettingGeneve::from(conf))
+            }
+        }
+        Interface::Tun(tun_iface) => {
+            if let Some(conf) = tun_iface.tun.as_ref() {
+                nm_conn.tun = Some(NmSettingTun::from(conf))
+            }
+        }
+        Interface::WireGuard(wg_iface) => {
+            gen_nm_wireguard_setting(wg_iface, &mut nm_conn);
+        }
+        Interface::MacSec(macsec_iface) => {
+            gen_nm_macsec_setting(macsec_iface, &mut nm_conn);
+        }
+        Interface::Pppoe(pppoe_iface) => {
+            gen_nm_pppoe_setting(pppoe_iface, &mut nm_conn);
+        }
+        Interface::Wifi(wifi_iface) => {
+            gen_nm_wifi_setting(wifi_iface, &mut nm_conn);
+        }
+        Interface::Gsm(gsm_iface) => {
+            gen_nm_gsm_setting(gsm_iface, &mut nm_conn);
+        }
+        Interface::Gre(_)
+        | Interface::GreTap(_)
+        | Interface::IpIp(_)
+        | Interface::Sit(_)
+        | Interface::Vti(_)
+        | Interface::Vti6(_) => {
+            gen_nm_ip_tunnel_setting(iface, &mut nm_conn);
+        }
         Interface::Ethernet(eth_iface) => {
             if let Some(veth_conf) = eth_iface.veth.as_ref() {
                 nm_conn.veth = Some(NmSettingVeth::from(veth_conf));
@@ -192,6 +286,7 @@ pub(crate) fn iface_to_nm_connections(
                         eth_iface.base.name.as_str(),
                         exist_nm_conns,
                         stable_uuid,
+                        profile_naming,
                     )?);
                 }
             }
@@ -207,6 +302,11 @@ pub(crate) fn iface_to_nm_connections(
                 nm_conn.mac_vlan = Some(NmSettingMacVlan::from(conf));
             }
         }
+        Interface::IpVlan(iface) => {
+            if let Some(conf) = iface.ipvlan.as_ref() {
+                nm_conn.ipvlan = Some(NmSettingIpVlan::from(conf));
+            }
+        }
         Interface::Vrf(iface) => {
             if let Some(vrf_conf) = iface.vrf.as_ref() {
                 nm_conn.vrf = Some(NmSettingVrf::from(vrf_conf));
@@ -225,10 +325,20 @@ pub(crate) fn iface_to_nm_connections(
         nm_conn.bridge_port = None;
     }
 
+    if nm_conn.controller_type() != Some(NM_SETTING_BOND_SETTING_NAME) {
+        nm_conn.bond_port = None;
+    }
+
     if nm_conn.controller_type() != Some(NM_SETTING_OVS_PORT_SETTING_NAME) {
         nm_conn.ovs_iface = None;
     }
 
+    // Nmstate does not support team, the team port setting should be removed
+    // when port is moved to other controller, e.g. migrating team to bond.
+    if nm_conn.controller_type() != Some(NM_SETTING_TEAM_SETTING_NAME) {
+        nm_conn.team_port = None;
+    }
+
     if let (Some(ctrl), Some(ctrl_type)) = (
         base_iface.controller.as_ref(),
         base_iface.controller_type.as_ref(),
@@ -240,6 +350,16 @@ pub(crate) fn iface_to_nm_connections(
                 Interface::LinuxBridge(br_iface) => {
                     gen_nm_br_port_setting(br_iface, &mut nm_conn);
                 }
+                // Only use desired ports configuration, as old
+                // NetworkManager does not support bond port priority which
+                // is always reported by kernel in current.
+                Interface::Bond(_) => {
+                    if let Some(Interface::Bond(bond_iface)) =
+                        ctrl_iface.desired.as_ref()
+                    {
+                        gen_nm_bond_port_setting(bond_iface, &mut nm_conn);
+                    }
+                }
                 Interface::OvsBridge(ovs_br_iface) => {
                     // When user attaching change controller property
                     // on OVS system or internal interface, we should
@@ -276,6 +396,7 @@ pub(crate) fn iface_to_nm_connections(
                             },
                             exist_nm_ovs_port_conn,
                             stable_uuid,
+                            profile_naming,
                         )?);
                     }
                 }
@@ -317,6 +438,7 @@ pub(crate) fn iface_type_to_nm(
         InterfaceType::MacVtap => {
             Ok(NM_SETTING_MACVLAN_SETTING_NAME.to_string())
         }
+        InterfaceType::IpVlan => Ok(NM_SETTING_IPVLAN_SETTING_NAME.to_string()),
         InterfaceType::Vrf => Ok(NM_SETTING_VRF_SETTING_NAME.to_string()),
         InterfaceType::Veth => Ok(NM_SETTING_VETH_SETTING_NAME.to_string()),
         InterfaceType::InfiniBand => {
@@ -325,6 +447,32 @@ pub(crate) fn iface_type_to_nm(
         InterfaceType::Loopback => {
             Ok(NM_SETTING_LOOPBACK_SETTING_NAME.to_string())
         }
+        InterfaceType::WireGuard => {
+            Ok(NM_SETTING_WIREGUARD_SETTING_NAME.to_string())
+        }
+        InterfaceType::MacSec => Ok(NM_SETTING_MACSEC_SETTING_NAME.to_string()),
+        InterfaceType::Gre
+        | InterfaceType::GreTap
+        | InterfaceType::IpIp
+        | InterfaceType::Sit
+        | InterfaceType::Vti
+        | InterfaceType::Vti6 => {
+            Ok(NM_SETTING_IP_TUNNEL_SETTING_NAME.to_string())
+        }
+        InterfaceType::Geneve => Ok(NM_SETTING_GENEVE_SETTING_NAME.to_string()),
+        InterfaceType::Tun => Ok(NM_SETTING_TUN_SETTING_NAME.to_string()),
+        InterfaceType::Pppoe => Ok(NM_SETTING_PPPOE_SETTING_NAME.to_string()),
+        InterfaceType::Wifi => Ok(NM_SETTING_WIRELESS_SETTING_NAME.to_string()),
+        InterfaceType::Gsm => Ok(NM_SETTING_GSM_SETTING_NAME.to_string()),
+        InterfaceType::Gtp
+        | InterfaceType::Xfrm
+        | InterfaceType::Can
+        | InterfaceType::Vcan
+        | InterfaceType::L2tpv3
+        | InterfaceType::Netkit
+        | InterfaceType::Batadv => {
+            Ok(NM_SETTING_GENERIC_SETTING_NAME.to_string())
+        }
         InterfaceType::Other(s) => Ok(s.to_string()),
         _ => Err(NmstateError::new(
             ErrorKind::NotImplementedError,
@@ -337,27 +485,13 @@ pub(crate) fn gen_nm_conn_setting(
     iface: &Interface,
     nm_conn: &mut NmConnection,
     stable_uuid: bool,
+    profile_naming: Option<&str>,
 ) -> Result<(), NmstateError> {
     let mut nm_conn_set = if let Some(cur_nm_conn_set) = &nm_conn.connection {
         cur_nm_conn_set.clone()
     } else {
         let mut new_nm_conn_set = NmSettingConnection::default();
-        let conn_name = match iface.iface_type() {
-            InterfaceType::OvsBridge => {
-                format!("{}-br", iface.name())
-            }
-            InterfaceType::Other(ref other_type)
-                if other_type == "ovs-port" =>
-            {
-                format!("{}-port", iface.name())
-            }
-            InterfaceType::OvsInterface => {
-                format!("{}-if", iface.name())
-            }
-            _ => iface.name().to_string(),
-        };
-
-        new_nm_conn_set.id = Some(conn_name);
+        new_nm_conn_set.id = Some(gen_nm_conn_name(iface, profile_naming));
         new_nm_conn_set.uuid = Some(if stable_uuid {
             uuid_from_name_and_type(iface.name(), &iface.iface_type())
         } else {
@@ -375,6 +509,15 @@ pub(crate) fn gen_nm_conn_setting(
         new_nm_conn_set
     };
 
+    if let Some(uuid) = iface.base_iface().profile_uuid.as_deref() {
+        if nm_conn_set.uuid.as_deref() != Some(uuid) {
+            // NetworkManager does not allow changing UUID of existing
+            // profile, hence create new profile with desired UUID, the old
+            // one will be deleted by `delete_exist_profiles()`.
+            nm_conn_set.uuid = Some(uuid.to_string());
+            nm_conn.obj_path = String::new();
+        }
+    }
     nm_conn_set.iface_name = Some(iface.name().to_string());
     nm_conn_set.autoconnect = Some(true);
     nm_conn_set.autoconnect_ports = if iface.is_controller() {
@@ -413,6 +556,12 @@ pub(crate) fn gen_nm_conn_setting(
     if let Some(mptcp_conf) = iface.base_iface().mptcp.as_ref() {
         apply_mptcp_conf(&mut nm_conn_set, mptcp_conf)?;
     }
+    if let Some(multi_connect) = iface.base_iface().multi_connect {
+        apply_multi_connect(&mut nm_conn_set, multi_connect);
+    }
+    if let Some(users) = iface.base_iface().permissions.as_deref() {
+        apply_permissions(&mut nm_conn_set, users);
+    }
 
     nm_conn.connection = Some(nm_conn_set);
 
@@ -463,6 +612,69 @@ pub(crate) fn get_exist_profile<'a>(
     found_nm_conns.pop()
 }
 
+// The profile pinned by UUID should belong to the same interface
+fn get_exist_profile_by_uuid<'a>(
+    exist_nm_conns: &'a [NmConnection],
+    uuid: &str,
+    iface_name: &str,
+    iface_type: &InterfaceType,
+    nm_ac_uuids: &[&str],
+) -> Result<Option<&'a NmConnection>, NmstateError> {
+    let exist_nm_conn =
+        match exist_nm_conns.iter().find(|c| c.uuid() == Some(uuid)) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+    if get_exist_profile(
+        std::slice::from_ref(exist_nm_conn),
+        iface_name,
+        iface_type,
+        nm_ac_uuids,
+    )
+    .is_none()
+    {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Profile UUID {uuid} of interface {iface_name} \
+                {iface_type} is used by profile of interface {} {}",
+                exist_nm_conn.iface_name().unwrap_or_default(),
+                exist_nm_conn.iface_type().unwrap_or_default(),
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(Some(exist_nm_conn))
+}
+
+// The profile naming has been validated by
+// `MergedNetworkManagerConfig::new()`.
+pub(crate) fn gen_nm_conn_name(
+    iface: &Interface,
+    profile_naming: Option<&str>,
+) -> String {
+    let name = match iface.iface_type() {
+        InterfaceType::OvsBridge => {
+            format!("{}-br", iface.name())
+        }
+        InterfaceType::Other(ref other_type) if other_type == "ovs-port" => {
+            format!("{}-port", iface.name())
+        }
+        InterfaceType::OvsInterface => {
+            format!("{}-if", iface.name())
+        }
+        _ => iface.name().to_string(),
+    };
+    match profile_naming {
+        None | Some(NM_PROFILE_NAMING_IFACE_NAME) => name,
+        Some(NM_PROFILE_NAMING_NMSTATE_PREFIX) => format!("nmstate-{name}"),
+        Some(template) => template
+            .replace(NM_PROFILE_NAMING_TYPE, &iface.iface_type().to_string())
+            .replace(NM_PROFILE_NAMING_NAME, &name),
+    }
+}
+
 fn persisten_iface_cur_conf(
     cur_iface: &Interface,
     merged_state: &MergedNetworkState,
@@ -2,8 +2,9 @@ use std::collections::HashMap;
 
 use crate::nm::nm_dbus::{NmConnection, NmSettingEthtool};
 use crate::{
-    ErrorKind, EthtoolCoalesceConfig, EthtoolFeatureConfig, EthtoolPauseConfig,
-    EthtoolRingConfig, Interface, NmstateError,
+    ErrorKind, EthtoolChannelsConfig, EthtoolCoalesceConfig,
+    EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig, Interface,
+    NmstateError,
 };
 
 const KERNEL_ETHTOOL_FEATURE_2_NM: [(&str, &str); 10] = [
@@ -39,6 +40,9 @@ pub(crate) fn gen_ethtool_setting(
         if let Some(ring_conf) = ethtool_iface.ring.as_ref() {
             apply_ring_options(&mut nm_ethtool_set, ring_conf);
         }
+        if let Some(channels_conf) = ethtool_iface.channels.as_ref() {
+            apply_channels_options(&mut nm_ethtool_set, channels_conf);
+        }
         nm_conn.ethtool = Some(nm_ethtool_set);
     }
     Ok(())
@@ -114,3 +118,13 @@ fn apply_ring_options(
     nm_ethtool_set.ring_rx_mini = ring_conf.rx_mini;
     nm_ethtool_set.ring_tx = ring_conf.tx;
 }
+
+fn apply_channels_options(
+    nm_ethtool_set: &mut NmSettingEthtool,
+    channels_conf: &EthtoolChannelsConfig,
+) {
+    nm_ethtool_set.channels_rx = channels_conf.rx;
+    nm_ethtool_set.channels_tx = channels_conf.tx;
+    nm_ethtool_set.channels_other = channels_conf.other;
+    nm_ethtool_set.channels_combined = channels_conf.combined;
+}
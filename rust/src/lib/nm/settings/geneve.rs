@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingGeneve;
+
+use crate::GeneveConfig;
+
+impl From<&GeneveConfig> for NmSettingGeneve {
+    fn from(config: &GeneveConfig) -> Self {
+        let mut setting = NmSettingGeneve::default();
+        setting.id = Some(config.id);
+        if let Some(v) = config.remote.as_ref() {
+            setting.remote = Some(v.to_string());
+        }
+        if let Some(v) = config.dst_port {
+            setting.dst_port = Some(v.into());
+        }
+        if let Some(v) = config.ttl {
+            setting.ttl = Some(v.into());
+        }
+        if let Some(v) = config.tos {
+            setting.tos = Some(v.into());
+        }
+        setting
+    }
+}
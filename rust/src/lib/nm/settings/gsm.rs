@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+use crate::{GsmInterface, NetworkState};
+
+// The PIN and password are owned by system and stored in NetworkManager
+// profile
+const NM_SETTING_SECRET_FLAG_NONE: u32 = 0;
+
+pub(crate) fn gen_nm_gsm_setting(
+    iface: &GsmInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_gsm_set = nm_conn.gsm.as_ref().cloned().unwrap_or_default();
+    if let Some(conf) = iface.gsm.as_ref() {
+        if let Some(v) = conf.apn.as_ref() {
+            nm_gsm_set.apn = Some(v.to_string());
+        }
+        if let Some(v) = conf.username.as_ref() {
+            nm_gsm_set.username = Some(v.to_string());
+        }
+        if let Some(roaming) = conf.roaming {
+            nm_gsm_set.home_only = Some(!roaming);
+        }
+        // Keep the current secrets if desired ones are hidden by nmstate
+        if conf.pin.is_some()
+            && conf.pin.as_deref()
+                != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            nm_gsm_set.pin = conf.pin.clone();
+            nm_gsm_set.pin_flags = Some(NM_SETTING_SECRET_FLAG_NONE);
+        }
+        if conf.password.is_some()
+            && conf.password.as_deref()
+                != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            nm_gsm_set.password = conf.password.clone();
+            nm_gsm_set.password_flags = Some(NM_SETTING_SECRET_FLAG_NONE);
+        }
+    }
+    if let Some(mtu) = iface.base.mtu {
+        nm_gsm_set.mtu = Some(mtu as u32);
+    }
+    nm_conn.gsm = Some(nm_gsm_set);
+}
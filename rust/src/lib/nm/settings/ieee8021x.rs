@@ -15,15 +15,15 @@ pub(crate) fn gen_nm_802_1x_setting(
         nm_setting.private_key = conf
             .private_key
             .as_deref()
-            .map(NmSetting8021X::file_path_to_glib_bytes);
+            .map(NmSetting8021X::cert_to_glib_bytes);
         nm_setting.client_cert = conf
             .client_cert
             .as_deref()
-            .map(NmSetting8021X::file_path_to_glib_bytes);
+            .map(NmSetting8021X::cert_to_glib_bytes);
         nm_setting.ca_cert = conf
             .ca_cert
             .as_deref()
-            .map(NmSetting8021X::file_path_to_glib_bytes);
+            .map(NmSetting8021X::cert_to_glib_bytes);
         if conf.private_key_password.as_deref()
             == Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
         {
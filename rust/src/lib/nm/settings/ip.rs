@@ -9,8 +9,8 @@ use super::{
 use crate::nm::nm_dbus::{NmConnection, NmSettingIp, NmSettingIpMethod};
 use crate::{
     BaseInterface, Dhcpv4ClientId, Dhcpv6Duid, ErrorKind, Interface,
-    InterfaceIpv4, InterfaceIpv6, Ipv6AddrGenMode, NmstateError, RouteEntry,
-    WaitIp,
+    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, Ipv6AddrGenMode,
+    NmstateError, RouteEntry, WaitIp,
 };
 
 const ADDR_GEN_MODE_EUI64: i32 = 0;
@@ -33,16 +33,15 @@ fn gen_nm_ipv4_setting(
         Some(i) => i,
     };
 
-    let mut addresses: Vec<String> = Vec::new();
+    // Addresses not added by nmstate should not be stored into profile
+    let addresses = gen_nm_ip_addrs(
+        iface_ip.addresses.as_deref().unwrap_or_default(),
+        iface_ip.external_addresses.as_slice(),
+    );
     let method = if iface_ip.enabled {
         if iface_ip.dhcp == Some(true) {
             NmSettingIpMethod::Auto
-        } else if !iface_ip.addresses.as_deref().unwrap_or_default().is_empty()
-        {
-            for ip_addr in iface_ip.addresses.as_deref().unwrap_or_default() {
-                addresses
-                    .push(format!("{}/{}", ip_addr.ip, ip_addr.prefix_length));
-            }
+        } else if !addresses.is_empty() {
             NmSettingIpMethod::Manual
         } else {
             NmSettingIpMethod::Disabled
@@ -51,8 +50,12 @@ fn gen_nm_ipv4_setting(
         NmSettingIpMethod::Disabled
     };
     let mut nm_setting = nm_conn.ipv4.as_ref().cloned().unwrap_or_default();
+    nm_setting.addresses = if method == NmSettingIpMethod::Manual {
+        addresses
+    } else {
+        Vec::new()
+    };
     nm_setting.method = Some(method);
-    nm_setting.addresses = addresses;
     if iface_ip.is_auto() {
         nm_setting.dhcp_timeout = Some(i32::MAX);
         nm_setting.route_metric = iface_ip.auto_route_metric.map(|i| i.into());
@@ -74,6 +77,9 @@ fn gen_nm_ipv4_setting(
         // enabled.
         nm_setting.routes = Vec::new();
     }
+    if let Some(v) = iface_ip.route_table {
+        nm_setting.route_table = Some(v);
+    }
     nm_setting.gateway = None;
     if iface_ip.enabled {
         if let Some(routes) = routes {
@@ -109,7 +115,11 @@ fn gen_nm_ipv6_setting(
         }
         Some(i) => i,
     };
-    let mut addresses: Vec<String> = Vec::new();
+    // Addresses not added by nmstate should not be stored into profile
+    let addresses = gen_nm_ip_addrs(
+        iface_ip.addresses.as_deref().unwrap_or_default(),
+        iface_ip.external_addresses.as_slice(),
+    );
     let method = if iface_ip.enabled {
         match (
             iface_ip.dhcp.unwrap_or_default(),
@@ -124,16 +134,7 @@ fn gen_nm_ipv6_setting(
                 ))
             }
             (false, false) => {
-                if !iface_ip.addresses.as_deref().unwrap_or_default().is_empty()
-                {
-                    for ip_addr in
-                        iface_ip.addresses.as_deref().unwrap_or_default()
-                    {
-                        addresses.push(format!(
-                            "{}/{}",
-                            ip_addr.ip, ip_addr.prefix_length
-                        ));
-                    }
+                if !addresses.is_empty() {
                     NmSettingIpMethod::Manual
                 } else {
                     NmSettingIpMethod::LinkLocal
@@ -144,8 +145,12 @@ fn gen_nm_ipv6_setting(
         NmSettingIpMethod::Disabled
     };
     let mut nm_setting = nm_conn.ipv6.as_ref().cloned().unwrap_or_default();
+    nm_setting.addresses = if method == NmSettingIpMethod::Manual {
+        addresses
+    } else {
+        Vec::new()
+    };
     nm_setting.method = Some(method);
-    nm_setting.addresses = addresses;
     nm_setting.addr_gen_mode =
         Some(nmstate_addr_gen_mode_to_nm(iface_ip.addr_gen_mode.as_ref()));
     if iface_ip.is_auto() {
@@ -180,6 +185,9 @@ fn gen_nm_ipv6_setting(
     } else {
         nm_setting.token = None;
     }
+    if let Some(v) = iface_ip.route_table {
+        nm_setting.route_table = Some(v);
+    }
     nm_setting.gateway = None;
     if iface_ip.enabled {
         if let Some(routes) = routes {
@@ -199,6 +207,19 @@ fn gen_nm_ipv6_setting(
     Ok(())
 }
 
+// The static IP method is chosen after removing the addresses not added by
+// nmstate, NetworkManager refuses `manual` method without any address.
+fn gen_nm_ip_addrs(
+    addrs: &[InterfaceIpAddr],
+    external_addrs: &[InterfaceIpAddr],
+) -> Vec<String> {
+    addrs
+        .iter()
+        .filter(|a| !external_addrs.contains(a))
+        .map(|a| format!("{}/{}", a.ip, a.prefix_length))
+        .collect()
+}
+
 pub(crate) fn gen_nm_ip_setting(
     iface: &Interface,
     routes: Option<&[RouteEntry]>,
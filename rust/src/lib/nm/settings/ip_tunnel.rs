@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmSettingIpTunnel};
+
+use crate::{GreConfig, Interface, IpTunnelConfig, VtiConfig};
+
+pub(crate) const NM_IP_TUNNEL_MODE_IPIP: u32 = 1;
+pub(crate) const NM_IP_TUNNEL_MODE_GRE: u32 = 2;
+pub(crate) const NM_IP_TUNNEL_MODE_SIT: u32 = 3;
+pub(crate) const NM_IP_TUNNEL_MODE_VTI: u32 = 5;
+pub(crate) const NM_IP_TUNNEL_MODE_VTI6: u32 = 9;
+pub(crate) const NM_IP_TUNNEL_MODE_GRETAP: u32 = 10;
+
+pub(crate) fn gen_nm_ip_tunnel_setting(
+    iface: &Interface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_tun_set =
+        nm_conn.ip_tunnel.as_ref().cloned().unwrap_or_default();
+    match iface {
+        Interface::Gre(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_GRE);
+            if let Some(conf) = iface.gre.as_ref() {
+                apply_gre_conf(conf, &mut nm_tun_set);
+            }
+        }
+        Interface::GreTap(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_GRETAP);
+            if let Some(conf) = iface.gretap.as_ref() {
+                apply_gre_conf(conf, &mut nm_tun_set);
+            }
+        }
+        Interface::IpIp(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_IPIP);
+            if let Some(conf) = iface.ipip.as_ref() {
+                apply_ip_tunnel_conf(conf, &mut nm_tun_set);
+            }
+        }
+        Interface::Sit(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_SIT);
+            if let Some(conf) = iface.sit.as_ref() {
+                apply_ip_tunnel_conf(conf, &mut nm_tun_set);
+            }
+        }
+        Interface::Vti(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_VTI);
+            if let Some(conf) = iface.vti.as_ref() {
+                apply_vti_conf(conf, &mut nm_tun_set);
+            }
+        }
+        Interface::Vti6(iface) => {
+            nm_tun_set.mode = Some(NM_IP_TUNNEL_MODE_VTI6);
+            if let Some(conf) = iface.vti6.as_ref() {
+                apply_vti_conf(conf, &mut nm_tun_set);
+            }
+        }
+        _ => return,
+    }
+    nm_conn.ip_tunnel = Some(nm_tun_set);
+}
+
+fn apply_gre_conf(conf: &GreConfig, nm_tun_set: &mut NmSettingIpTunnel) {
+    if let Some(v) = conf.local {
+        nm_tun_set.local = Some(v.to_string());
+    }
+    if let Some(v) = conf.remote {
+        nm_tun_set.remote = Some(v.to_string());
+    }
+    if let Some(v) = conf.ikey {
+        nm_tun_set.input_key = Some(v.to_string());
+    }
+    if let Some(v) = conf.okey {
+        nm_tun_set.output_key = Some(v.to_string());
+    }
+    if let Some(v) = conf.ttl {
+        nm_tun_set.ttl = Some(v.into());
+    }
+}
+
+// NetworkManager has no property for 6rd prefix, it is applied via netlink
+// after activation.
+fn apply_ip_tunnel_conf(
+    conf: &IpTunnelConfig,
+    nm_tun_set: &mut NmSettingIpTunnel,
+) {
+    if let Some(v) = conf.local {
+        nm_tun_set.local = Some(v.to_string());
+    }
+    if let Some(v) = conf.remote {
+        nm_tun_set.remote = Some(v.to_string());
+    }
+    if let Some(v) = conf.ttl {
+        nm_tun_set.ttl = Some(v.into());
+    }
+    if let Some(v) = conf.pmtudisc {
+        nm_tun_set.path_mtu_discovery = Some(v);
+    }
+}
+
+fn apply_vti_conf(conf: &VtiConfig, nm_tun_set: &mut NmSettingIpTunnel) {
+    if let Some(v) = conf.local {
+        nm_tun_set.local = Some(v.to_string());
+    }
+    if let Some(v) = conf.remote {
+        nm_tun_set.remote = Some(v.to_string());
+    }
+    if let Some(v) = conf.ikey {
+        nm_tun_set.input_key = Some(v.to_string());
+    }
+    if let Some(v) = conf.okey {
+        nm_tun_set.output_key = Some(v.to_string());
+    }
+}
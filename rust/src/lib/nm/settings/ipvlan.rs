@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmSettingIpVlan;
+
+use crate::IpVlanConfig;
+
+impl From<&IpVlanConfig> for NmSettingIpVlan {
+    fn from(config: &IpVlanConfig) -> Self {
+        let mut settings = NmSettingIpVlan::default();
+        settings.mode = Some(config.mode.into());
+        settings.parent = Some(config.base_iface.clone());
+        if let Some(v) = config.private {
+            settings.private = Some(v);
+        }
+        if let Some(v) = config.vepa {
+            settings.vepa = Some(v);
+        }
+        settings
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+use crate::{MacSecInterface, NetworkState};
+
+const NM_MACSEC_MODE_PSK: i32 = 0;
+
+pub(crate) fn gen_nm_macsec_setting(
+    iface: &MacSecInterface,
+    nm_conn: &mut NmConnection,
+) {
+    if let Some(conf) = iface.macsec.as_ref() {
+        let mut nm_macsec_set =
+            nm_conn.macsec.as_ref().cloned().unwrap_or_default();
+        if !conf.base_iface.is_empty() {
+            nm_macsec_set.parent = Some(conf.base_iface.clone());
+        }
+        if let Some(v) = conf.encrypt {
+            nm_macsec_set.encrypt = Some(v);
+        }
+        if let Some(v) = conf.port {
+            nm_macsec_set.port = Some(v.into());
+        }
+        if let Some(v) = conf.validation {
+            nm_macsec_set.validation = Some(v.into());
+        }
+        if let Some(v) = conf.send_sci {
+            nm_macsec_set.send_sci = Some(v);
+        }
+        if conf.mka_ckn.is_some() {
+            nm_macsec_set.mode = Some(NM_MACSEC_MODE_PSK);
+            nm_macsec_set.mka_ckn = conf.mka_ckn.clone();
+        }
+        // Keep the current secret if desired one is hidden by nmstate
+        if conf.mka_cak.is_some()
+            && conf.mka_cak.as_deref()
+                != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            nm_macsec_set.mka_cak = conf.mka_cak.clone();
+        }
+        nm_conn.macsec = Some(nm_macsec_set);
+    }
+}
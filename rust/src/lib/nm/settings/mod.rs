@@ -5,35 +5,53 @@ mod bridge;
 mod connection;
 mod dns;
 mod ethtool;
+mod geneve;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod inter_connections;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
 mod loopback;
 mod mac_vlan;
+mod macsec;
 mod mptcp;
+mod multi_connect;
 mod ovs;
+mod permissions;
+mod pppoe;
 mod route;
 mod route_rule;
 mod sriov;
+mod tun;
 mod user;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod wifi;
 mod wired;
+mod wireguard;
 
-pub(crate) use self::connection::{get_exist_profile, iface_to_nm_connections};
+pub(crate) use self::connection::{
+    get_exist_profile, iface_to_nm_connections, NM_UNSUPPORTED_IFACE_TYPES,
+};
 #[cfg(feature = "query_apply")]
 pub(crate) use self::connection::{
     iface_type_to_nm, NM_SETTING_BOND_SETTING_NAME,
     NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
-    NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
-    NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
-    NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_OVS_PORT_SETTING_NAME,
+    NM_SETTING_GENEVE_SETTING_NAME, NM_SETTING_GSM_SETTING_NAME,
+    NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_IPVLAN_SETTING_NAME,
+    NM_SETTING_IP_TUNNEL_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
+    NM_SETTING_MACSEC_SETTING_NAME, NM_SETTING_MACVLAN_SETTING_NAME,
+    NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
+    NM_SETTING_OVS_PORT_SETTING_NAME, NM_SETTING_PPPOE_SETTING_NAME,
+    NM_SETTING_TEAM_SETTING_NAME, NM_SETTING_TUN_SETTING_NAME,
     NM_SETTING_VETH_SETTING_NAME, NM_SETTING_VLAN_SETTING_NAME,
     NM_SETTING_VRF_SETTING_NAME, NM_SETTING_VXLAN_SETTING_NAME,
-    NM_SETTING_WIRED_SETTING_NAME,
+    NM_SETTING_WIRED_SETTING_NAME, NM_SETTING_WIREGUARD_SETTING_NAME,
+    NM_SETTING_WIRELESS_SETTING_NAME,
 };
 pub(crate) use self::inter_connections::{
     use_uuid_for_controller_reference, use_uuid_for_parent_reference,
@@ -42,6 +60,23 @@ pub(crate) use self::inter_connections::{
 #[cfg(feature = "query_apply")]
 pub(crate) use self::bond::get_bond_balance_slb;
 #[cfg(feature = "query_apply")]
-pub(crate) use self::user::NMSTATE_DESCRIPTION;
+pub(crate) use self::ip_tunnel::{
+    NM_IP_TUNNEL_MODE_GRE, NM_IP_TUNNEL_MODE_GRETAP, NM_IP_TUNNEL_MODE_IPIP,
+    NM_IP_TUNNEL_MODE_SIT, NM_IP_TUNNEL_MODE_VTI, NM_IP_TUNNEL_MODE_VTI6,
+};
+pub(crate) use self::user::gen_nm_user_setting;
+#[cfg(feature = "query_apply")]
+pub(crate) use self::user::{NMSTATE_DESCRIPTION, NMSTATE_LABEL_PREFIX};
 
 pub(crate) use self::mptcp::remove_nm_mptcp_set;
+#[cfg(feature = "query_apply")]
+pub(crate) use self::multi_connect::{
+    NM_MULTI_CONNECT_DEFAULT, NM_MULTI_CONNECT_MANUAL_MULTIPLE,
+    NM_MULTI_CONNECT_MULTIPLE, NM_MULTI_CONNECT_SINGLE,
+};
+#[cfg(feature = "query_apply")]
+pub(crate) use self::permissions::NM_PERMISSION_USER_PREFIX;
+#[cfg(feature = "query_apply")]
+pub(crate) use self::wifi::{
+    NM_SETTING_SECRET_FLAG_AGENT_OWNED, NM_SETTING_SECRET_FLAG_NOT_SAVED,
+};
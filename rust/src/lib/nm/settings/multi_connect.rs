@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingConnection;
+
+use crate::MultiConnect;
+
+pub(crate) const NM_MULTI_CONNECT_DEFAULT: i32 = 0;
+pub(crate) const NM_MULTI_CONNECT_SINGLE: i32 = 1;
+pub(crate) const NM_MULTI_CONNECT_MANUAL_MULTIPLE: i32 = 2;
+pub(crate) const NM_MULTI_CONNECT_MULTIPLE: i32 = 3;
+
+pub(crate) fn apply_multi_connect(
+    nm_conn_set: &mut NmSettingConnection,
+    multi_connect: MultiConnect,
+) {
+    nm_conn_set.multi_connect = Some(match multi_connect {
+        MultiConnect::Default => NM_MULTI_CONNECT_DEFAULT,
+        MultiConnect::Single => NM_MULTI_CONNECT_SINGLE,
+        MultiConnect::ManualMultiple => NM_MULTI_CONNECT_MANUAL_MULTIPLE,
+        MultiConnect::Multiple => NM_MULTI_CONNECT_MULTIPLE,
+    });
+}
@@ -20,6 +20,7 @@ pub(crate) fn create_ovs_port_nm_conn(
     port_conf: &OvsBridgePortConfig,
     exist_nm_conn: Option<&NmConnection>,
     stable_uuid: bool,
+    profile_naming: Option<&str>,
 ) -> Result<NmConnection, NmstateError> {
     let mut nm_conn = exist_nm_conn.cloned().unwrap_or_default();
     let mut base_iface = BaseInterface::new();
@@ -29,7 +30,12 @@ pub(crate) fn create_ovs_port_nm_conn(
     base_iface.controller_type = Some(InterfaceType::OvsBridge);
     let mut iface = UnknownInterface::new();
     iface.base = base_iface;
-    gen_nm_conn_setting(&Interface::Unknown(iface), &mut nm_conn, stable_uuid)?;
+    gen_nm_conn_setting(
+        &Interface::Unknown(iface),
+        &mut nm_conn,
+        stable_uuid,
+        profile_naming,
+    )?;
 
     let mut nm_ovs_port_set =
         nm_conn.ovs_port.as_ref().cloned().unwrap_or_default();
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingConnection;
+
+// NetworkManager only supports user permission in the format of
+// `user:<name>:`, the trailing part is reserved.
+pub(crate) const NM_PERMISSION_USER_PREFIX: &str = "user:";
+
+pub(crate) fn apply_permissions(
+    nm_conn_set: &mut NmSettingConnection,
+    users: &[String],
+) {
+    nm_conn_set.permissions = Some(
+        users
+            .iter()
+            .map(|user| format!("{NM_PERMISSION_USER_PREFIX}{user}:"))
+            .collect(),
+    );
+}
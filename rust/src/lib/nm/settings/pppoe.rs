@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+use crate::{NetworkState, PppoeInterface};
+
+// The password is owned by system and stored in NetworkManager profile
+const NM_SETTING_SECRET_FLAG_NONE: u32 = 0;
+
+pub(crate) fn gen_nm_pppoe_setting(
+    iface: &PppoeInterface,
+    nm_conn: &mut NmConnection,
+) {
+    if let Some(conf) = iface.pppoe.as_ref() {
+        let mut nm_pppoe_set =
+            nm_conn.pppoe.as_ref().cloned().unwrap_or_default();
+        if !conf.parent.is_empty() {
+            nm_pppoe_set.parent = Some(conf.parent.clone());
+        }
+        if let Some(v) = conf.username.as_ref() {
+            nm_pppoe_set.username = Some(v.to_string());
+        }
+        if let Some(v) = conf.service.as_ref() {
+            nm_pppoe_set.service = Some(v.to_string());
+        }
+        // Keep the current secret if desired one is hidden by nmstate
+        if conf.password.is_some()
+            && conf.password.as_deref()
+                != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            nm_pppoe_set.password = conf.password.clone();
+            nm_pppoe_set.password_flags = Some(NM_SETTING_SECRET_FLAG_NONE);
+        }
+        nm_conn.pppoe = Some(nm_pppoe_set);
+    }
+
+    // NetworkManager requires the `ppp` setting for PPPoE connection, the MTU
+    // of PPPoE interface is negotiated by pppd according to it.
+    let mut nm_ppp_set = nm_conn.ppp.as_ref().cloned().unwrap_or_default();
+    if let Some(mtu) = iface.base.mtu {
+        nm_ppp_set.mtu = Some(mtu as u32);
+    }
+    nm_conn.ppp = Some(nm_ppp_set);
+}
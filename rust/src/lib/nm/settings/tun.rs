@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingTun;
+
+use crate::{TunConfig, TunMode};
+
+const NM_TUN_MODE_TUN: u32 = 1;
+const NM_TUN_MODE_TAP: u32 = 2;
+
+impl From<&TunConfig> for NmSettingTun {
+    fn from(config: &TunConfig) -> Self {
+        let mut setting = NmSettingTun::default();
+        setting.mode = Some(match config.mode.unwrap_or_default() {
+            TunMode::Tun => NM_TUN_MODE_TUN,
+            TunMode::Tap => NM_TUN_MODE_TAP,
+        });
+        setting.owner = config.owner.map(|v| v.to_string());
+        setting.group = config.group.map(|v| v.to_string());
+        setting.multi_queue = config.multi_queue;
+        setting.pi = config.pi;
+        setting
+    }
+}
@@ -1,25 +1,43 @@
 use std::collections::HashMap;
 
-use crate::nm::nm_dbus::{NmConnection, NmSettingUser};
+use crate::nm::nm_dbus::NmConnection;
 
 use crate::Interface;
 
 pub(crate) const NMSTATE_DESCRIPTION: &str = "nmstate.interface.description";
+pub(crate) const NMSTATE_LABEL_PREFIX: &str = "nmstate.interface.label.";
 
 pub(crate) fn gen_nm_user_setting(
     iface: &Interface,
     nm_conn: &mut NmConnection,
 ) {
-    if let Some(description) = iface.base_iface().description.as_ref() {
-        let mut data: HashMap<String, String> = HashMap::new();
+    let base_iface = iface.base_iface();
+    let labels = base_iface
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.labels.as_ref());
+    if base_iface.description.is_none() && labels.is_none() {
+        return;
+    }
+    // Preserve the user data not owned by the properties being changed
+    let mut nm_setting = nm_conn.user.clone().unwrap_or_default();
+    let mut data: HashMap<String, String> =
+        nm_setting.data.take().unwrap_or_default();
+    if let Some(description) = base_iface.description.as_ref() {
+        data.remove(NMSTATE_DESCRIPTION);
         if !description.is_empty() {
             data.insert(
                 NMSTATE_DESCRIPTION.to_string(),
                 description.to_string(),
             );
         }
-        let mut nm_setting = NmSettingUser::default();
-        nm_setting.data = Some(data);
-        nm_conn.user = Some(nm_setting);
     }
+    if let Some(labels) = labels {
+        data.retain(|k, _| !k.starts_with(NMSTATE_LABEL_PREFIX));
+        for (k, v) in labels.iter() {
+            data.insert(format!("{NMSTATE_LABEL_PREFIX}{k}"), v.to_string());
+        }
+    }
+    nm_setting.data = Some(data);
+    nm_conn.user = Some(nm_setting);
 }
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmSettingIpMethod};
+
+use crate::{
+    NetworkState, WifiBand, WifiInterface, WifiKeyMgmt, WifiMode,
+    WifiSecretFlag,
+};
+
+const NM_WIFI_MODE_INFRASTRUCTURE: &str = "infrastructure";
+const NM_WIFI_MODE_AP: &str = "ap";
+const NM_WIFI_BAND_2G4: &str = "bg";
+const NM_WIFI_BAND_5G: &str = "a";
+
+// NetworkManager does not store secrets with these flags into profile.
+pub(crate) const NM_SETTING_SECRET_FLAG_AGENT_OWNED: u32 = 1;
+pub(crate) const NM_SETTING_SECRET_FLAG_NOT_SAVED: u32 = 2;
+
+// Protected management frames(IEEE 802.11w) is mandatory for WPA3 only
+// network. For other key management, we use global default of
+// NetworkManager which allows WPA2 and WPA3 transition mode.
+const NM_WIFI_PMF_REQUIRED: i32 = 3;
+
+pub(crate) fn gen_nm_wifi_setting(
+    iface: &WifiInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_wifi_set =
+        nm_conn.wireless.as_ref().cloned().unwrap_or_default();
+    nm_wifi_set.mode = Some(
+        match iface.wifi.as_ref().and_then(|c| c.mode).unwrap_or_default() {
+            WifiMode::Station => NM_WIFI_MODE_INFRASTRUCTURE,
+            WifiMode::Ap => NM_WIFI_MODE_AP,
+        }
+        .to_string(),
+    );
+    if let Some(mac) = iface.base.mac_address.as_ref() {
+        nm_wifi_set.cloned_mac_address = Some(mac.to_string());
+    }
+    if let Some(mtu) = iface.base.mtu {
+        nm_wifi_set.mtu = Some(mtu as u32);
+    }
+    if let Some(conf) = iface.wifi.as_ref() {
+        if !conf.ssid.is_empty() {
+            nm_wifi_set.ssid = Some(conf.ssid.as_bytes().to_vec());
+        }
+        if let Some(band) = conf.band {
+            nm_wifi_set.band = Some(
+                match band {
+                    WifiBand::Band2G4 => NM_WIFI_BAND_2G4,
+                    WifiBand::Band5G => NM_WIFI_BAND_5G,
+                }
+                .to_string(),
+            );
+        }
+        nm_wifi_set.channel = conf.channel;
+        if let Some(v) = conf.hidden {
+            nm_wifi_set.hidden = Some(v);
+        }
+        if let Some(sec_conf) = conf.security.as_ref() {
+            let mut nm_sec_set = nm_conn
+                .wireless_security
+                .as_ref()
+                .cloned()
+                .unwrap_or_default();
+            nm_sec_set.key_mgmt = Some(sec_conf.key_mgmt.to_string());
+            nm_sec_set.pmf = if sec_conf.key_mgmt.is_wpa3_only() {
+                Some(NM_WIFI_PMF_REQUIRED)
+            } else {
+                None
+            };
+            // Keep the current secret if desired one is hidden by nmstate
+            if sec_conf.psk.is_some()
+                && sec_conf.psk.as_deref()
+                    != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+            {
+                nm_sec_set.psk = sec_conf.psk.clone();
+            }
+            if matches!(
+                sec_conf.key_mgmt,
+                WifiKeyMgmt::WpaPsk | WifiKeyMgmt::Sae
+            ) {
+                nm_sec_set.psk_flags =
+                    Some(match sec_conf.psk_flags.unwrap_or_default() {
+                        WifiSecretFlag::AgentOwned => {
+                            NM_SETTING_SECRET_FLAG_AGENT_OWNED
+                        }
+                        WifiSecretFlag::NotSaved => {
+                            NM_SETTING_SECRET_FLAG_NOT_SAVED
+                        }
+                    });
+            } else {
+                nm_sec_set.psk = None;
+                nm_sec_set.psk_flags = None;
+            }
+            nm_conn.wireless_security = Some(nm_sec_set);
+        }
+        // The access point serves DHCP and NAT to its clients via the
+        // `shared` IPv4 method, static addresses are still honored as the
+        // gateway address of the shared network.
+        if conf.is_ap() {
+            if let Some(nm_ip_set) = nm_conn.ipv4.as_mut() {
+                if iface.base.ipv4.as_ref().map(|i| i.enabled) == Some(true) {
+                    nm_ip_set.method = Some(NmSettingIpMethod::Shared);
+                }
+            }
+        }
+    }
+    nm_conn.wireless = Some(nm_wifi_set);
+}
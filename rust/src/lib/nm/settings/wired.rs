@@ -1,12 +1,18 @@
 use crate::nm::nm_dbus::NmConnection;
 
 use crate::{
-    nm::version::nm_supports_accept_all_mac_addresses_mode, Interface,
+    nm::version::nm_supports_accept_all_mac_addresses_mode,
+    EthernetWakeOnLanConfig, Interface, NetworkState,
 };
 
+const NM_WOL_UNICAST: u32 = 0x4;
+const NM_WOL_BROADCAST: u32 = 0x10;
+const NM_WOL_MAGIC: u32 = 0x40;
+
 pub(crate) fn gen_nm_wired_setting(
     iface: &Interface,
     nm_conn: &mut NmConnection,
+    gen_conf_mode: bool,
 ) {
     let mut nm_wired_set = nm_conn.wired.as_ref().cloned().unwrap_or_default();
 
@@ -44,15 +50,37 @@ pub(crate) fn gen_nm_wired_setting(
                 }
                 None => (),
             }
+            if let Some(wol_conf) = eth_conf.wake_on_lan.as_ref() {
+                flag_need_wired = true;
+                nm_wired_set.wake_on_lan = Some(gen_nm_wol_flags(wol_conf));
+                // Keep existing password if hidden
+                if wol_conf.password.as_deref()
+                    != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+                {
+                    nm_wired_set
+                        .wake_on_lan_password
+                        .clone_from(&wol_conf.password);
+                }
+            }
         }
     }
 
     if let Some(accept_all_mac_addresses) = &base_iface.accept_all_mac_addresses
     {
-        if nm_supports_accept_all_mac_addresses_mode().unwrap_or_default() {
+        // The NetworkManager version of the targeted host is unknown in
+        // gen_conf mode
+        if gen_conf_mode
+            || nm_supports_accept_all_mac_addresses_mode().unwrap_or_default()
+        {
             nm_wired_set.accept_all_mac_addresses =
                 Some(i32::from(*accept_all_mac_addresses));
             flag_need_wired = true;
+        } else {
+            log::warn!(
+                "The accept-all-mac-addresses of interface {} is ignored as \
+                NetworkManager 1.32 or newer is required",
+                base_iface.name
+            );
         }
     }
 
@@ -60,3 +88,18 @@ pub(crate) fn gen_nm_wired_setting(
         nm_conn.wired = Some(nm_wired_set);
     }
 }
+
+// NetworkManager treat 0 as disabling all wake up methods
+fn gen_nm_wol_flags(wol_conf: &EthernetWakeOnLanConfig) -> u32 {
+    let mut ret = 0;
+    if wol_conf.magic == Some(true) {
+        ret |= NM_WOL_MAGIC;
+    }
+    if wol_conf.unicast == Some(true) {
+        ret |= NM_WOL_UNICAST;
+    }
+    if wol_conf.broadcast == Some(true) {
+        ret |= NM_WOL_BROADCAST;
+    }
+    ret
+}
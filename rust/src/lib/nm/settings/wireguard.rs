@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmWireGuardPeer};
+
+use crate::{NetworkState, WireGuardInterface};
+
+pub(crate) fn gen_nm_wireguard_setting(
+    iface: &WireGuardInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_wg_set = nm_conn.wireguard.as_ref().cloned().unwrap_or_default();
+    if let Some(conf) = iface.wireguard.as_ref() {
+        // Keep the current secret if desired one is hidden by nmstate
+        if conf.private_key.as_deref()
+            != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        {
+            nm_wg_set.private_key = conf.private_key.clone();
+        }
+        if let Some(v) = conf.listen_port {
+            nm_wg_set.listen_port = Some(u32::from(v));
+        }
+        if let Some(v) = conf.fwmark {
+            nm_wg_set.fwmark = Some(v);
+        }
+        if let Some(peers) = conf.peers.as_ref() {
+            let cur_peers = nm_wg_set.peers.take().unwrap_or_default();
+            let mut nm_peers = Vec::new();
+            for peer in peers {
+                let mut nm_peer = NmWireGuardPeer::default();
+                nm_peer.public_key = Some(peer.public_key.clone());
+                nm_peer.endpoint = peer.endpoint.clone();
+                nm_peer.allowed_ips = peer.allowed_ips.clone();
+                nm_peer.persistent_keepalive = peer.persistent_keepalive;
+                nm_peer.preshared_key = if peer.preshared_key.as_deref()
+                    == Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+                {
+                    cur_peers
+                        .iter()
+                        .find(|p| p.public_key == nm_peer.public_key)
+                        .and_then(|p| p.preshared_key.clone())
+                } else {
+                    peer.preshared_key.clone()
+                };
+                nm_peers.push(nm_peer);
+            }
+            nm_wg_set.peers = Some(nm_peers);
+        }
+    }
+    // WireGuard is layer 3 interface, its MTU is stored in wireguard
+    // setting instead of wired setting.
+    if let Some(mtu) = iface.base.mtu {
+        nm_wg_set.mtu = Some(mtu as u32);
+    }
+    nm_conn.wireguard = Some(nm_wg_set)
+}
@@ -3,40 +3,58 @@
 use std::collections::HashMap;
 
 use crate::nm::nm_dbus::{
-    NmActiveConnection, NmApi, NmConnection, NmDevice, NmDeviceState,
-    NmLldpNeighbor, NM_ACTIVATION_STATE_FLAG_EXTERNAL,
+    NmActiveConnection, NmApi, NmConnection, NmConnectivityState, NmDevice,
+    NmDeviceState, NmLldpNeighbor, NM_ACTIVATION_STATE_FLAG_EXTERNAL,
 };
 
 use super::{
     active_connection::create_index_for_nm_acs_by_name_type,
     error::nm_error_to_nmstate,
+    nm_conf::nm_conf_retrieve,
     query_apply::{
         create_index_for_nm_conns_by_name_type, dns::nm_global_dns_to_nmstate,
-        get_description, get_lldp, is_lldp_enabled, is_mptcp_supported,
-        nm_802_1x_to_nmstate, nm_ip_setting_to_nmstate4,
-        nm_ip_setting_to_nmstate6, query_nmstate_wait_ip, retrieve_dns_info,
+        get_description, get_lldp, get_metadata, get_multi_connect,
+        get_permissions, is_lldp_enabled, is_mptcp_supported,
+        nm_802_1x_to_nmstate, nm_dhcp_opts_to_nmstate, nm_gre_to_nmstate,
+        nm_gsm_to_nmstate, nm_ip_addrs_to_nmstate, nm_ip_setting_to_nmstate4,
+        nm_ip_setting_to_nmstate6, nm_ip_tunnel_iface_type,
+        nm_ip_tunnel_to_nmstate, nm_macsec_to_nmstate, nm_pppoe_to_nmstate,
+        nm_routes_to_nmstate, nm_vti_to_nmstate, nm_wifi_to_nmstate,
+        nm_wireguard_to_nmstate, query_nmstate_wait_ip, retrieve_dns_info,
     },
     settings::{
         get_bond_balance_slb, NM_SETTING_BOND_SETTING_NAME,
         NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
-        NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
-        NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
-        NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
-        NM_SETTING_VLAN_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
-        NM_SETTING_VXLAN_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
+        NM_SETTING_GENEVE_SETTING_NAME, NM_SETTING_GSM_SETTING_NAME,
+        NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_IPVLAN_SETTING_NAME,
+        NM_SETTING_IP_TUNNEL_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
+        NM_SETTING_MACSEC_SETTING_NAME, NM_SETTING_MACVLAN_SETTING_NAME,
+        NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
+        NM_SETTING_PPPOE_SETTING_NAME, NM_SETTING_TUN_SETTING_NAME,
+        NM_SETTING_VETH_SETTING_NAME, NM_SETTING_VLAN_SETTING_NAME,
+        NM_SETTING_VRF_SETTING_NAME, NM_SETTING_VXLAN_SETTING_NAME,
+        NM_SETTING_WIRED_SETTING_NAME, NM_SETTING_WIREGUARD_SETTING_NAME,
+        NM_SETTING_WIRELESS_SETTING_NAME,
     },
 };
 use crate::{
-    BaseInterface, BondConfig, BondInterface, BondOptions, DummyInterface,
-    EthernetInterface, InfiniBandInterface, Interface, InterfaceState,
-    InterfaceType, Interfaces, LinuxBridgeInterface, LoopbackInterface,
-    MacVlanInterface, MacVtapInterface, NetworkState, NmstateError,
-    OvsBridgeInterface, OvsInterface, UnknownInterface, VlanInterface,
-    VrfInterface, VxlanInterface,
+    BaseInterface, BondConfig, BondInterface, BondOptions, ConnectivityState,
+    DnsState, DummyInterface, EthernetInterface, GeneveInterface, GreInterface,
+    GreTapInterface, GsmBearer, GsmConfig, GsmInterface, InfiniBandInterface,
+    Interface, InterfaceConnectivity, InterfaceState, InterfaceType,
+    Interfaces, IpIpInterface, IpVlanInterface, LinuxBridgeInterface,
+    LoopbackInterface, MacSecInterface, MacVlanInterface, MacVtapInterface,
+    NetworkState, NmstateError, OvsBridgeInterface, OvsInterface,
+    PppoeInterface, SitInterface, TunInterface, UnknownInterface,
+    VlanInterface, VrfInterface, Vti6Interface, VtiInterface, VxlanInterface,
+    WifiInterface, WireGuardInterface,
 };
 
+// When `unprivileged` is true, information requiring root permission will be
+// omitted with warning.
 pub(crate) fn nm_retrieve(
     running_config_only: bool,
+    unprivileged: bool,
 ) -> Result<NetworkState, NmstateError> {
     let mut net_state = NetworkState::new();
     net_state.prop_list = vec!["interfaces", "dns"];
@@ -46,8 +64,18 @@ pub(crate) fn nm_retrieve(
         .map_err(nm_error_to_nmstate)?;
     let nm_devs = nm_api.devices_get().map_err(nm_error_to_nmstate)?;
 
-    let nm_saved_conns =
-        nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let nm_saved_conns = match nm_api.connections_get() {
+        Ok(c) => c,
+        Err(e) if unprivileged => {
+            log::warn!(
+                "Omitting saved NetworkManager connections for non-root \
+                user: {}",
+                e
+            );
+            Vec::new()
+        }
+        Err(e) => return Err(nm_error_to_nmstate(e)),
+    };
     let nm_acs = nm_api
         .active_connections_get()
         .map_err(nm_error_to_nmstate)?;
@@ -130,18 +158,30 @@ pub(crate) fn nm_retrieve(
                     if running_config_only {
                         Some(Vec::new())
                     } else {
-                        Some(
-                            nm_api
-                                .device_lldp_neighbor_get(&nm_dev.obj_path)
-                                .map_err(nm_error_to_nmstate)?,
-                        )
+                        match nm_api.device_lldp_neighbor_get(&nm_dev.obj_path)
+                        {
+                            Ok(n) => Some(n),
+                            Err(e) if unprivileged => {
+                                log::warn!(
+                                    "Omitting LLDP neighbors of {} for \
+                                    non-root user: {}",
+                                    nm_dev.name,
+                                    e
+                                );
+                                Some(Vec::new())
+                            }
+                            Err(e) => return Err(nm_error_to_nmstate(e)),
+                        }
                     }
                 } else {
                     None
                 };
-                if let Some(mut iface) =
-                    iface_get(nm_dev, nm_conn, nm_saved_conn, lldp_neighbors)
-                {
+                if let Some(mut iface) = iface_get(
+                    nm_dev_iface_type_to_nmstate(nm_dev),
+                    nm_conn,
+                    nm_saved_conn,
+                    lldp_neighbors,
+                ) {
                     // Suppress mptcp only when MPTCP is not supported by
                     // NetworkManager, so user will not get failure when they
                     // apply the returned state.
@@ -149,6 +189,16 @@ pub(crate) fn nm_retrieve(
                         iface.base_iface_mut().prop_list.push("mptcp");
                         iface.base_iface_mut().mptcp = None;
                     }
+                    if !running_config_only {
+                        set_dhcp_lease(
+                            &mut nm_api,
+                            nm_dev,
+                            &mut iface,
+                            unprivileged,
+                        )?;
+                        set_connectivity(&mut nm_api, nm_dev, &mut iface);
+                        set_gsm_bearer(&mut nm_api, nm_dev, &mut iface);
+                    }
 
                     log::debug!(
                         "Found NM interface {}/{}",
@@ -174,31 +224,198 @@ pub(crate) fn nm_retrieve(
             iface.base_iface_mut().state = InterfaceState::Ignore;
         }
     }
-    if let Ok(nm_global_dns_conf) = nm_api
+    net_state.dns = nm_dns_retrieve(&mut nm_api, &net_state.interfaces)?;
+    if running_config_only {
+        net_state.dns.running = None;
+    }
+
+    set_ovs_iface_controller_info(&mut net_state.interfaces);
+
+    if !running_config_only {
+        match nm_api.connectivity_get() {
+            Ok(c) => {
+                net_state.prop_list.push("connectivity");
+                net_state.connectivity = Some(nm_connectivity_to_nmstate(c));
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to retrieve NetworkManager connectivity: {e}"
+                );
+            }
+        }
+    }
+
+    if let Some(nm_conf) = nm_conf_retrieve()? {
+        net_state.prop_list.push("networkmanager");
+        net_state.networkmanager = Some(nm_conf);
+    }
+
+    Ok(net_state)
+}
+
+// Only query the DNS information without enumerating the NetworkManager
+// devices. The DNS configuration of interfaces are taken from the applied
+// connections directly.
+pub(crate) fn nm_retrieve_dns(
+    running_config_only: bool,
+) -> Result<DnsState, NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let nm_conns = nm_api
+        .applied_connections_get()
+        .map_err(nm_error_to_nmstate)?;
+    let mut ifaces = Interfaces::new();
+    for nm_conn in nm_conns.iter() {
+        let iface_name = match nm_conn.iface_name() {
+            Some(n) => n,
+            None => continue,
+        };
+        let mut iface = UnknownInterface::new();
+        iface.base.name = iface_name.to_string();
+        iface.base.ipv4 = nm_conn.ipv4.as_ref().map(nm_ip_setting_to_nmstate4);
+        iface.base.ipv6 = nm_conn
+            .ipv6
+            .as_ref()
+            .map(|nm_ip_set| nm_ip_setting_to_nmstate6(iface_name, nm_ip_set));
+        ifaces.push(Interface::Unknown(iface));
+    }
+    let mut dns = nm_dns_retrieve(&mut nm_api, &ifaces)?;
+    if running_config_only {
+        dns.running = None;
+    }
+    Ok(dns)
+}
+
+// Store the lease information of DHCPv4, DHCPv6 or IPv6 autoconf into IP
+// configuration of interface.
+fn set_dhcp_lease(
+    nm_api: &mut NmApi,
+    nm_dev: &NmDevice,
+    iface: &mut Interface,
+    unprivileged: bool,
+) -> Result<(), NmstateError> {
+    let mut get_lease = |is_ipv6: bool| -> Result<_, NmstateError> {
+        match nm_api.device_dhcp_options_get(&nm_dev.obj_path, is_ipv6) {
+            Ok(opts) => Ok(nm_dhcp_opts_to_nmstate(&opts, is_ipv6)),
+            Err(e) if unprivileged => {
+                log::warn!(
+                    "Omitting DHCP lease of {} for non-root user: {}",
+                    nm_dev.name,
+                    e
+                );
+                Ok(None)
+            }
+            Err(e) => Err(nm_error_to_nmstate(e)),
+        }
+    };
+    let base_iface = iface.base_iface_mut();
+    if let Some(ipv4) = base_iface.ipv4.as_mut().filter(|i| i.is_auto()) {
+        ipv4.dhcp_lease = get_lease(false)?;
+        ipv4.prop_list.push("dhcp_lease");
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_mut().filter(|i| i.is_auto()) {
+        ipv6.dhcp_lease = get_lease(true)?;
+        ipv6.prop_list.push("dhcp_lease");
+    }
+    Ok(())
+}
+
+// The global DNS configuration of NetworkManager takes precedence over DNS
+// configuration of profiles.
+// Connectivity is only for query, hence failure is not fatal
+fn set_connectivity(
+    nm_api: &mut NmApi,
+    nm_dev: &NmDevice,
+    iface: &mut Interface,
+) {
+    match nm_api.device_connectivity_get(&nm_dev.obj_path) {
+        Ok((ipv4, ipv6)) => {
+            let base_iface = iface.base_iface_mut();
+            base_iface.prop_list.push("connectivity");
+            base_iface.connectivity = Some(InterfaceConnectivity {
+                ipv4: Some(nm_connectivity_to_nmstate(ipv4)),
+                ipv6: Some(nm_connectivity_to_nmstate(ipv6)),
+            });
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to retrieve connectivity of {}: {}",
+                nm_dev.name,
+                e
+            );
+        }
+    }
+}
+
+fn nm_connectivity_to_nmstate(
+    nm_connectivity: NmConnectivityState,
+) -> ConnectivityState {
+    match nm_connectivity {
+        NmConnectivityState::None => ConnectivityState::None,
+        NmConnectivityState::Portal => ConnectivityState::Portal,
+        NmConnectivityState::Limited => ConnectivityState::Limited,
+        NmConnectivityState::Full => ConnectivityState::Full,
+        _ => ConnectivityState::Unknown,
+    }
+}
+
+pub(crate) fn nm_dns_retrieve(
+    nm_api: &mut NmApi,
+    ifaces: &Interfaces,
+) -> Result<DnsState, NmstateError> {
+    let mut dns = if let Ok(nm_global_dns_conf) = nm_api
         .get_global_dns_configuration()
         .map_err(nm_error_to_nmstate)
     {
         if nm_global_dns_conf.is_empty() {
-            net_state.dns =
-                retrieve_dns_info(&mut nm_api, &net_state.interfaces)?;
+            retrieve_dns_info(nm_api, ifaces)?
         } else {
-            net_state.dns = nm_global_dns_to_nmstate(&nm_global_dns_conf);
+            nm_global_dns_to_nmstate(&nm_global_dns_conf)
         }
     } else {
-        net_state.dns = retrieve_dns_info(&mut nm_api, &net_state.interfaces)?;
-    }
-    net_state.dns.sanitize().ok();
-    if running_config_only {
-        net_state.dns.running = None;
-    }
-
-    set_ovs_iface_controller_info(&mut net_state.interfaces);
+        retrieve_dns_info(nm_api, ifaces)?
+    };
+    dns.sanitize().ok();
+    Ok(dns)
+}
 
-    Ok(net_state)
+// Bearer state is only for query, hence failure is not fatal
+fn set_gsm_bearer(
+    nm_api: &mut NmApi,
+    nm_dev: &NmDevice,
+    iface: &mut Interface,
+) {
+    let gsm_conf = if let Interface::Gsm(gsm_iface) = iface {
+        gsm_iface.gsm.get_or_insert_with(GsmConfig::new)
+    } else {
+        return;
+    };
+    match nm_api.device_modem_bearer_get(&nm_dev.obj_path) {
+        Ok((apn, operator_code)) => {
+            gsm_conf.bearer = Some(GsmBearer {
+                connected: Some(nm_dev.state == NmDeviceState::Activated),
+                apn: Some(apn).filter(|a| !a.is_empty()),
+                operator_code: Some(operator_code).filter(|o| !o.is_empty()),
+            });
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to retrieve bearer state of GSM modem {}: {}",
+                nm_dev.name,
+                e
+            );
+        }
+    }
 }
 
 fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
-    match nm_dev.iface_type.as_str() {
+    nm_iface_type_to_nmstate(nm_dev.iface_type.as_str(), nm_dev.is_mac_vtap)
+}
+
+pub(crate) fn nm_iface_type_to_nmstate(
+    nm_iface_type: &str,
+    is_mac_vtap: bool,
+) -> InterfaceType {
+    match nm_iface_type {
         NM_SETTING_WIRED_SETTING_NAME => InterfaceType::Ethernet,
         NM_SETTING_VETH_SETTING_NAME => InterfaceType::Ethernet,
         NM_SETTING_BOND_SETTING_NAME => InterfaceType::Bond,
@@ -210,30 +427,55 @@ fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
         NM_SETTING_VLAN_SETTING_NAME => InterfaceType::Vlan,
         NM_SETTING_VXLAN_SETTING_NAME => InterfaceType::Vxlan,
         NM_SETTING_MACVLAN_SETTING_NAME => {
-            if nm_dev.is_mac_vtap {
+            if is_mac_vtap {
                 InterfaceType::MacVtap
             } else {
                 InterfaceType::MacVlan
             }
         }
+        NM_SETTING_IPVLAN_SETTING_NAME => InterfaceType::IpVlan,
         NM_SETTING_LOOPBACK_SETTING_NAME => InterfaceType::Loopback,
         NM_SETTING_INFINIBAND_SETTING_NAME => InterfaceType::InfiniBand,
-        _ => InterfaceType::Other(nm_dev.iface_type.to_string()),
+        NM_SETTING_WIREGUARD_SETTING_NAME => InterfaceType::WireGuard,
+        NM_SETTING_MACSEC_SETTING_NAME => InterfaceType::MacSec,
+        NM_SETTING_GENEVE_SETTING_NAME => InterfaceType::Geneve,
+        NM_SETTING_TUN_SETTING_NAME => InterfaceType::Tun,
+        // NetworkManager create PPP device for PPPoE connection
+        NM_SETTING_PPPOE_SETTING_NAME | "ppp" => InterfaceType::Pppoe,
+        NM_SETTING_WIRELESS_SETTING_NAME => InterfaceType::Wifi,
+        // NetworkManager create modem device for GSM connection
+        NM_SETTING_GSM_SETTING_NAME | "modem" => InterfaceType::Gsm,
+        _ => InterfaceType::Other(nm_iface_type.to_string()),
     }
 }
 
 fn nm_conn_to_base_iface(
-    nm_dev: &NmDevice,
+    iface_type: InterfaceType,
     nm_conn: &NmConnection,
     nm_saved_conn: Option<&NmConnection>,
     lldp_neighbors: Option<Vec<NmLldpNeighbor>>,
 ) -> Option<BaseInterface> {
     if let Some(iface_name) = nm_conn.iface_name() {
-        let ipv4 = nm_conn.ipv4.as_ref().map(nm_ip_setting_to_nmstate4);
-        let ipv6 = nm_conn
-            .ipv6
-            .as_ref()
-            .map(|nm_ip_set| nm_ip_setting_to_nmstate6(iface_name, nm_ip_set));
+        // The addresses and routes stored in profile are owned by nmstate,
+        // others are added by other tools.
+        let ipv4 = nm_conn.ipv4.as_ref().map(|nm_ip_set| {
+            let mut ipv4 = nm_ip_setting_to_nmstate4(nm_ip_set);
+            ipv4.prop_list.push("owned_addresses");
+            ipv4.prop_list.push("owned_routes");
+            ipv4.owned_addresses = Some(nm_ip_addrs_to_nmstate(nm_ip_set));
+            ipv4.owned_routes =
+                Some(nm_routes_to_nmstate(iface_name, nm_ip_set));
+            ipv4
+        });
+        let ipv6 = nm_conn.ipv6.as_ref().map(|nm_ip_set| {
+            let mut ipv6 = nm_ip_setting_to_nmstate6(iface_name, nm_ip_set);
+            ipv6.prop_list.push("owned_addresses");
+            ipv6.prop_list.push("owned_routes");
+            ipv6.owned_addresses = Some(nm_ip_addrs_to_nmstate(nm_ip_set));
+            ipv6.owned_routes =
+                Some(nm_routes_to_nmstate(iface_name, nm_ip_set));
+            ipv6
+        });
 
         let mut base_iface = BaseInterface::new();
         base_iface.name = iface_name.to_string();
@@ -246,9 +488,13 @@ fn nm_conn_to_base_iface(
             "description",
             "lldp",
             "wait_ip",
+            "multi_connect",
+            "permissions",
+            "metadata",
+            "profile_uuid",
         ];
         base_iface.state = InterfaceState::Up;
-        base_iface.iface_type = nm_dev_iface_type_to_nmstate(nm_dev);
+        base_iface.iface_type = iface_type;
         if base_iface.iface_type.is_userspace() {
             // Only override iface type for user space. For other interface,
             // we trust nispor to set the correct interface type.
@@ -260,6 +506,10 @@ fn nm_conn_to_base_iface(
             query_nmstate_wait_ip(nm_conn.ipv4.as_ref(), nm_conn.ipv6.as_ref());
         base_iface.controller = nm_conn.controller().map(|c| c.to_string());
         base_iface.description = get_description(nm_conn);
+        base_iface.multi_connect = get_multi_connect(nm_conn);
+        base_iface.permissions = get_permissions(nm_conn);
+        base_iface.metadata = get_metadata(nm_conn);
+        base_iface.profile_uuid = nm_conn.uuid().map(|u| u.to_string());
         base_iface.lldp =
             Some(lldp_neighbors.map(get_lldp).unwrap_or_default());
         if let Some(nm_saved_conn) = nm_saved_conn {
@@ -274,15 +524,24 @@ fn nm_conn_to_base_iface(
 
 // Applied connection does not hold OVS config, we need the NmConnection
 // used by `NmActiveConnection` also.
-fn iface_get(
-    nm_dev: &NmDevice,
+pub(crate) fn iface_get(
+    iface_type: InterfaceType,
     nm_conn: &NmConnection,
     nm_saved_conn: Option<&NmConnection>,
     lldp_neighbors: Option<Vec<NmLldpNeighbor>>,
 ) -> Option<Interface> {
-    if let Some(base_iface) =
-        nm_conn_to_base_iface(nm_dev, nm_conn, nm_saved_conn, lldp_neighbors)
-    {
+    let iface_type = match iface_type {
+        InterfaceType::Other(s) if s == NM_SETTING_IP_TUNNEL_SETTING_NAME => {
+            nm_ip_tunnel_iface_type(nm_conn)
+        }
+        t => t,
+    };
+    if let Some(base_iface) = nm_conn_to_base_iface(
+        iface_type,
+        nm_conn,
+        nm_saved_conn,
+        lldp_neighbors,
+    ) {
         let iface = match &base_iface.iface_type {
             InterfaceType::LinuxBridge => Interface::LinuxBridge({
                 let mut iface = LinuxBridgeInterface::new();
@@ -337,6 +596,11 @@ fn iface_get(
                 iface.base = base_iface;
                 iface
             }),
+            InterfaceType::IpVlan => Interface::IpVlan({
+                let mut iface = IpVlanInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
             InterfaceType::Vrf => Interface::Vrf({
                 let mut iface = VrfInterface::new();
                 iface.base = base_iface;
@@ -352,6 +616,108 @@ fn iface_get(
                 iface.base = base_iface;
                 iface
             }),
+            InterfaceType::WireGuard => Interface::WireGuard({
+                let mut iface = WireGuardInterface::new();
+                iface.base = base_iface;
+                // WireGuard secrets are only available in saved connection
+                iface.wireguard = nm_saved_conn
+                    .and_then(|c| c.wireguard.as_ref())
+                    .or(nm_conn.wireguard.as_ref())
+                    .map(nm_wireguard_to_nmstate);
+                iface
+            }),
+            InterfaceType::MacSec => Interface::MacSec({
+                let mut iface = MacSecInterface::new();
+                iface.base = base_iface;
+                // MACsec secrets are only available in saved connection
+                iface.macsec = nm_saved_conn
+                    .and_then(|c| c.macsec.as_ref())
+                    .or(nm_conn.macsec.as_ref())
+                    .map(nm_macsec_to_nmstate);
+                iface
+            }),
+            InterfaceType::Pppoe => Interface::Pppoe({
+                let mut iface = PppoeInterface::new();
+                iface.base = base_iface;
+                // PPPoE password is only available in saved connection
+                iface.pppoe = nm_saved_conn
+                    .and_then(|c| c.pppoe.as_ref())
+                    .or(nm_conn.pppoe.as_ref())
+                    .map(nm_pppoe_to_nmstate);
+                iface
+            }),
+            InterfaceType::Wifi => Interface::Wifi({
+                let mut iface = WifiInterface::new();
+                iface.base = base_iface;
+                // Wi-Fi secrets are only available in saved connection
+                let nm_sec_set = nm_saved_conn
+                    .and_then(|c| c.wireless_security.as_ref())
+                    .or(nm_conn.wireless_security.as_ref());
+                iface.wifi = nm_conn
+                    .wireless
+                    .as_ref()
+                    .map(|s| nm_wifi_to_nmstate(s, nm_sec_set));
+                iface
+            }),
+            InterfaceType::Gsm => Interface::Gsm({
+                let mut iface = GsmInterface::new();
+                iface.base = base_iface;
+                // GSM secrets are only available in saved connection
+                iface.gsm = nm_saved_conn
+                    .and_then(|c| c.gsm.as_ref())
+                    .or(nm_conn.gsm.as_ref())
+                    .map(nm_gsm_to_nmstate);
+                iface
+            }),
+            InterfaceType::Gre => Interface::Gre({
+                let mut iface = GreInterface::new();
+                iface.base = base_iface;
+                iface.gre = nm_conn.ip_tunnel.as_ref().map(nm_gre_to_nmstate);
+                iface
+            }),
+            InterfaceType::GreTap => Interface::GreTap({
+                let mut iface = GreTapInterface::new();
+                iface.base = base_iface;
+                iface.gretap =
+                    nm_conn.ip_tunnel.as_ref().map(nm_gre_to_nmstate);
+                iface
+            }),
+            InterfaceType::IpIp => Interface::IpIp({
+                let mut iface = IpIpInterface::new();
+                iface.base = base_iface;
+                iface.ipip =
+                    nm_conn.ip_tunnel.as_ref().map(nm_ip_tunnel_to_nmstate);
+                iface
+            }),
+            InterfaceType::Sit => Interface::Sit({
+                let mut iface = SitInterface::new();
+                iface.base = base_iface;
+                iface.sit =
+                    nm_conn.ip_tunnel.as_ref().map(nm_ip_tunnel_to_nmstate);
+                iface
+            }),
+            InterfaceType::Vti => Interface::Vti({
+                let mut iface = VtiInterface::new();
+                iface.base = base_iface;
+                iface.vti = nm_conn.ip_tunnel.as_ref().map(nm_vti_to_nmstate);
+                iface
+            }),
+            InterfaceType::Vti6 => Interface::Vti6({
+                let mut iface = Vti6Interface::new();
+                iface.base = base_iface;
+                iface.vti6 = nm_conn.ip_tunnel.as_ref().map(nm_vti_to_nmstate);
+                iface
+            }),
+            InterfaceType::Geneve => Interface::Geneve({
+                let mut iface = GeneveInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Tun => Interface::Tun({
+                let mut iface = TunInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
             _ => {
                 log::debug!("Skip unsupported interface {:?}", base_iface);
                 return None;
@@ -372,11 +738,11 @@ fn get_first_nm_conn<'a>(
     name: &'a str,
     nm_iface_type: &'a str,
 ) -> Option<&'a NmConnection> {
-    // Treating veth as ethernet
-    let nm_iface_type = if nm_iface_type == NM_SETTING_VETH_SETTING_NAME {
-        NM_SETTING_WIRED_SETTING_NAME
-    } else {
-        nm_iface_type
+    // Treating veth as ethernet and modem device as gsm connection
+    let nm_iface_type = match nm_iface_type {
+        NM_SETTING_VETH_SETTING_NAME => NM_SETTING_WIRED_SETTING_NAME,
+        "modem" => NM_SETTING_GSM_SETTING_NAME,
+        t => t,
     };
     if let Some(nm_conns) = nm_conns_name_type_index.get(&(name, nm_iface_type))
     {
@@ -403,6 +769,7 @@ fn get_nm_ac<'a>(
             name,
             match nm_iface_type {
                 NM_SETTING_VETH_SETTING_NAME => NM_SETTING_WIRED_SETTING_NAME,
+                "modem" => NM_SETTING_GSM_SETTING_NAME,
                 t => t,
             },
         ))
@@ -502,6 +869,11 @@ fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
             iface.base = base_iface;
             iface
         }),
+        InterfaceType::IpVlan => Interface::IpVlan({
+            let mut iface = IpVlanInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
         InterfaceType::Vrf => Interface::Vrf({
             let mut iface = VrfInterface::new();
             iface.base = base_iface;
@@ -518,6 +890,31 @@ fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
                 ..Default::default()
             }
         }),
+        InterfaceType::WireGuard => Interface::WireGuard({
+            let mut iface = WireGuardInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::MacSec => Interface::MacSec({
+            let mut iface = MacSecInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Pppoe => Interface::Pppoe({
+            let mut iface = PppoeInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Wifi => Interface::Wifi({
+            let mut iface = WifiInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Gsm => Interface::Gsm({
+            let mut iface = GsmInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
         iface_type
             if iface_type == &InterfaceType::Other("ovs-port".to_string()) =>
         {
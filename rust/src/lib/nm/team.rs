@@ -0,0 +1,445 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{NmApi, NmConnection},
+    query_apply::delete_profiles,
+    saved::nm_saved_conns_to_nmstate,
+    settings::{NM_SETTING_BOND_SETTING_NAME, NM_SETTING_TEAM_SETTING_NAME},
+};
+use crate::{
+    BondArpValidate, BondConfig, BondFailOverMac, BondLacpRate, BondMode,
+    BondOptions, BondPortConfig, BondPrimaryReselect, BondXmitHashPolicy,
+    ErrorKind, Interface, MergedNetworkState, NetworkState, NmstateError,
+};
+
+// Bond requires `updelay` and `downdelay` to be multiple of `miimon`
+const DEFAULT_MIIMON: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct TeamdConf {
+    runner: TeamdRunner,
+    link_watch: Option<TeamdLinkWatches>,
+    ports: HashMap<String, TeamdPortConf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct TeamdRunner {
+    name: Option<String>,
+    hwaddr_policy: Option<String>,
+    tx_hash: Option<Vec<String>>,
+    tx_balancer: Option<serde_json::Value>,
+    active: Option<bool>,
+    fast_rate: Option<bool>,
+    min_ports: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+enum TeamdLinkWatches {
+    Single(TeamdLinkWatch),
+    Multiple(Vec<TeamdLinkWatch>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct TeamdLinkWatch {
+    name: String,
+    delay_up: Option<u32>,
+    delay_down: Option<u32>,
+    interval: Option<u32>,
+    target_host: Option<String>,
+    validate_active: Option<bool>,
+    validate_inactive: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct TeamdPortConf {
+    prio: Option<i32>,
+    sticky: Option<bool>,
+    queue_id: Option<u32>,
+    lacp_prio: Option<u32>,
+    lacp_key: Option<u32>,
+}
+
+// Generate bond configuration equivalent to the team profiles stored in
+// NetworkManager.
+pub(crate) fn nm_retrieve_team_as_bond() -> Result<NetworkState, NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let nm_conns = nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    nm_team_conns_to_bond(nm_conns.as_slice())
+}
+
+pub(crate) fn nm_team_conns_to_bond(
+    nm_conns: &[NmConnection],
+) -> Result<NetworkState, NmstateError> {
+    let mut net_state = NetworkState::new();
+    let mut routes = Vec::new();
+    let mut rules = Vec::new();
+
+    for nm_conn in nm_conns
+        .iter()
+        .filter(|c| c.iface_type() == Some(NM_SETTING_TEAM_SETTING_NAME))
+    {
+        let team_name = match nm_conn.iface_name() {
+            Some(n) => n,
+            None => continue,
+        };
+        if net_state.interfaces.kernel_ifaces.contains_key(team_name) {
+            log::debug!(
+                "Ignoring extra NetworkManager team profile {} for \
+                interface {team_name}",
+                nm_conn.uuid().unwrap_or_default()
+            );
+            continue;
+        }
+        let team_conf = parse_teamd_conf(
+            team_name,
+            nm_conn.team.as_ref().and_then(|t| t.config.as_deref()),
+        )?;
+        let ports = get_team_ports(nm_conn, team_name, &team_conf, nm_conns)?;
+
+        // Reuse the conversion of saved profile for IP, route and rule
+        let mut bond_nm_conn = nm_conn.clone();
+        if let Some(nm_conn_set) = bond_nm_conn.connection.as_mut() {
+            nm_conn_set.iface_type =
+                Some(NM_SETTING_BOND_SETTING_NAME.to_string());
+        }
+        bond_nm_conn.team = None;
+        let mut bond_state = nm_saved_conns_to_nmstate(&[bond_nm_conn]);
+        let mut bond_iface =
+            match bond_state.interfaces.kernel_ifaces.remove(team_name) {
+                Some(Interface::Bond(i)) => i,
+                _ => continue,
+            };
+        bond_iface.bond =
+            Some(teamd_conf_to_bond_conf(team_name, &team_conf, &ports)?);
+        net_state.interfaces.push(Interface::Bond(bond_iface));
+        routes.extend(bond_state.routes.config.take().unwrap_or_default());
+        rules.extend(bond_state.rules.config.take().unwrap_or_default());
+    }
+    if !routes.is_empty() {
+        net_state.routes.config = Some(routes);
+    }
+    if !rules.is_empty() {
+        net_state.rules.config = Some(rules);
+    }
+    Ok(net_state)
+}
+
+fn parse_teamd_conf(
+    team_name: &str,
+    config: Option<&str>,
+) -> Result<TeamdConf, NmstateError> {
+    match config.filter(|c| !c.is_empty()) {
+        Some(config) => serde_json::from_str(config).map_err(|e| {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid teamd config of team {team_name}: {e}"),
+            );
+            log::error!("{}", e);
+            e
+        }),
+        None => Ok(TeamdConf::default()),
+    }
+}
+
+// The port config stored in team-port setting takes precedence over the
+// `ports` section of teamd config.
+fn get_team_ports(
+    team_nm_conn: &NmConnection,
+    team_name: &str,
+    team_conf: &TeamdConf,
+    nm_conns: &[NmConnection],
+) -> Result<Vec<(String, TeamdPortConf)>, NmstateError> {
+    let mut ret: Vec<(String, TeamdPortConf)> = Vec::new();
+    for nm_conn in nm_conns.iter().filter(|c| {
+        c.controller_type() == Some(NM_SETTING_TEAM_SETTING_NAME)
+            && (c.controller() == Some(team_name)
+                || (c.controller().is_some()
+                    && c.controller() == team_nm_conn.uuid()))
+    }) {
+        let port_name = match nm_conn.iface_name() {
+            Some(n) => n,
+            None => continue,
+        };
+        if ret.iter().any(|(n, _)| n == port_name) {
+            continue;
+        }
+        let port_conf = match nm_conn
+            .team_port
+            .as_ref()
+            .and_then(|p| p.config.as_deref())
+            .filter(|c| !c.is_empty())
+        {
+            Some(config) => serde_json::from_str(config).map_err(|e| {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid teamd port config of port {port_name} in \
+                        team {team_name}: {e}"
+                    ),
+                );
+                log::error!("{}", e);
+                e
+            })?,
+            None => team_conf.ports.get(port_name).cloned().unwrap_or_default(),
+        };
+        ret.push((port_name.to_string(), port_conf));
+    }
+    ret.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    Ok(ret)
+}
+
+fn teamd_conf_to_bond_conf(
+    team_name: &str,
+    team_conf: &TeamdConf,
+    ports: &[(String, TeamdPortConf)],
+) -> Result<BondConfig, NmstateError> {
+    let runner = &team_conf.runner;
+    let mut opts = BondOptions::new();
+    let mode = match runner.name.as_deref().unwrap_or("roundrobin") {
+        "broadcast" => BondMode::Broadcast,
+        "roundrobin" => BondMode::RoundRobin,
+        "activebackup" => {
+            opts.fail_over_mac = match runner.hwaddr_policy.as_deref() {
+                None | Some("same_all") => None,
+                Some("by_active") => Some(BondFailOverMac::Active),
+                Some("only_active") => Some(BondFailOverMac::Follow),
+                Some(p) => {
+                    log::warn!(
+                        "Team {team_name}: unknown hwaddr_policy {p}, \
+                        ignoring"
+                    );
+                    None
+                }
+            };
+            set_primary_port(team_name, &mut opts, ports);
+            BondMode::ActiveBackup
+        }
+        "loadbalance" => {
+            opts.xmit_hash_policy =
+                Some(tx_hash_to_xmit_hash_policy(runner.tx_hash.as_deref()));
+            if runner.tx_balancer.is_some() {
+                BondMode::TLB
+            } else {
+                BondMode::XOR
+            }
+        }
+        "lacp" => {
+            if runner.active == Some(false) {
+                log::warn!(
+                    "Team {team_name}: passive LACP is not supported by \
+                    bond, using active LACP"
+                );
+            }
+            opts.lacp_rate = Some(if runner.fast_rate == Some(true) {
+                BondLacpRate::Fast
+            } else {
+                BondLacpRate::Slow
+            });
+            opts.min_links = runner.min_ports;
+            opts.xmit_hash_policy =
+                Some(tx_hash_to_xmit_hash_policy(runner.tx_hash.as_deref()));
+            BondMode::LACP
+        }
+        name => {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Team {team_name}: runner {name} has no equivalent \
+                    bond mode"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    };
+    set_link_watch(team_name, &mut opts, team_conf.link_watch.as_ref());
+
+    let mut port_confs: Vec<BondPortConfig> = Vec::new();
+    for (port_name, port_conf) in ports {
+        let mut bond_port_conf = BondPortConfig::new();
+        bond_port_conf.name = port_name.to_string();
+        if let Some(queue_id) = port_conf.queue_id {
+            match u16::try_from(queue_id) {
+                Ok(q) => bond_port_conf.queue_id = Some(q),
+                Err(_) => log::warn!(
+                    "Team {team_name}: queue_id {queue_id} of port \
+                    {port_name} is out of bond range, ignoring"
+                ),
+            }
+        }
+        port_confs.push(bond_port_conf);
+        if port_conf.lacp_prio.is_some() || port_conf.lacp_key.is_some() {
+            log::warn!(
+                "Team {team_name}: lacp_prio and lacp_key of port \
+                {port_name} are not supported by bond, ignoring"
+            );
+        }
+    }
+
+    Ok(BondConfig {
+        mode: Some(mode),
+        options: Some(opts),
+        port: Some(ports.iter().map(|(n, _)| n.to_string()).collect()),
+        ports_config: if port_confs.iter().any(|p| p.queue_id.is_some()) {
+            Some(port_confs)
+        } else {
+            None
+        },
+    })
+}
+
+// Teamd hashes on all of `eth`, `ipv4` and `ipv6` by default.
+fn tx_hash_to_xmit_hash_policy(
+    tx_hash: Option<&[String]>,
+) -> BondXmitHashPolicy {
+    let tx_hash = match tx_hash {
+        Some(t) => t,
+        None => return BondXmitHashPolicy::Layer23,
+    };
+    let has =
+        |names: &[&str]| tx_hash.iter().any(|t| names.contains(&t.as_str()));
+    if has(&["l4", "tcp", "udp", "sctp"]) {
+        BondXmitHashPolicy::Layer34
+    } else if has(&["l3", "ipv4", "ipv6", "ip"]) {
+        BondXmitHashPolicy::Layer23
+    } else {
+        BondXmitHashPolicy::Layer2
+    }
+}
+
+// Sticky port will not lose its active state to better port, the closest
+// equivalent in bond is primary port with `primary_reselect: failure`.
+// Without sticky port, the port with the highest priority is preferred.
+fn set_primary_port(
+    team_name: &str,
+    opts: &mut BondOptions,
+    ports: &[(String, TeamdPortConf)],
+) {
+    let sticky_ports: Vec<&str> = ports
+        .iter()
+        .filter(|(_, p)| p.sticky == Some(true))
+        .map(|(n, _)| n.as_str())
+        .collect();
+    if let Some(sticky_port) = sticky_ports.first() {
+        if sticky_ports.len() > 1 {
+            log::warn!(
+                "Team {team_name}: bond only support single primary port, \
+                using {sticky_port} out of sticky ports {sticky_ports:?}"
+            );
+        }
+        opts.primary = Some(sticky_port.to_string());
+        opts.primary_reselect = Some(BondPrimaryReselect::Failure);
+        return;
+    }
+    let max_prio = ports.iter().filter_map(|(_, p)| p.prio).max();
+    if let Some(max_prio) = max_prio {
+        let top_ports: Vec<&str> = ports
+            .iter()
+            .filter(|(_, p)| p.prio.unwrap_or_default() == max_prio)
+            .map(|(n, _)| n.as_str())
+            .collect();
+        if top_ports.len() == 1 {
+            opts.primary = Some(top_ports[0].to_string());
+        }
+    }
+}
+
+fn set_link_watch(
+    team_name: &str,
+    opts: &mut BondOptions,
+    link_watches: Option<&TeamdLinkWatches>,
+) {
+    let link_watch = match link_watches {
+        Some(TeamdLinkWatches::Single(w)) => Some(w),
+        Some(TeamdLinkWatches::Multiple(ws)) => {
+            if ws.len() > 1 {
+                log::warn!(
+                    "Team {team_name}: bond only support single link \
+                    watcher, using the first one"
+                );
+            }
+            ws.first()
+        }
+        None => None,
+    };
+    match link_watch {
+        Some(w) if w.name == "arp_ping" => {
+            opts.arp_interval = w.interval;
+            opts.arp_ip_target = w.target_host.clone();
+            opts.arp_validate = match (
+                w.validate_active == Some(true),
+                w.validate_inactive == Some(true),
+            ) {
+                (true, true) => Some(BondArpValidate::All),
+                (true, false) => Some(BondArpValidate::Active),
+                (false, true) => Some(BondArpValidate::Backup),
+                (false, false) => None,
+            };
+        }
+        Some(w) if w.name == "ethtool" => {
+            opts.miimon = Some(DEFAULT_MIIMON);
+            opts.updelay = w.delay_up.map(round_up_to_miimon);
+            opts.downdelay = w.delay_down.map(round_up_to_miimon);
+        }
+        Some(w) => {
+            log::warn!(
+                "Team {team_name}: link watcher {} is not supported by \
+                bond, using MII monitoring",
+                w.name
+            );
+            opts.miimon = Some(DEFAULT_MIIMON);
+        }
+        // Teamd use ethtool link watcher by default
+        None => {
+            opts.miimon = Some(DEFAULT_MIIMON);
+        }
+    }
+}
+
+fn round_up_to_miimon(delay: u32) -> u32 {
+    (delay + DEFAULT_MIIMON - 1) / DEFAULT_MIIMON * DEFAULT_MIIMON
+}
+
+// Delete the team profiles of interfaces which are changed to bond in
+// desired state, NetworkManager will remove the team device afterwards.
+pub(crate) fn delete_team_profiles(
+    nm_api: &mut NmApi,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let nm_conns = nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let mut uuids_to_delete: Vec<&str> = Vec::new();
+    for nm_conn in nm_conns
+        .iter()
+        .filter(|c| c.iface_type() == Some(NM_SETTING_TEAM_SETTING_NAME))
+    {
+        if let (Some(uuid), Some(iface_name)) =
+            (nm_conn.uuid(), nm_conn.iface_name())
+        {
+            if let Some(Interface::Bond(_)) = merged_state
+                .interfaces
+                .kernel_ifaces
+                .get(iface_name)
+                .and_then(|i| i.for_apply.as_ref())
+            {
+                log::info!(
+                    "Deleting team connection UUID {uuid}, id {:?} for \
+                    migrating interface {iface_name} to bond",
+                    nm_conn.id()
+                );
+                uuids_to_delete.push(uuid);
+            }
+        }
+    }
+    delete_profiles(nm_api, &uuids_to_delete)
+}
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingConnection, NmSettingVlan};
+use crate::nm::query_apply::{
+    apply::gen_nm_plan,
+    profile::{coalesce_device_activations, NmDeviceActivation},
+};
+use crate::{ChangeRisk, MergedNetworkState, NetworkState};
+
+fn gen_nm_conn(
+    uuid: &str,
+    iface_name: &str,
+    iface_type: &str,
+    controller: Option<&str>,
+) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_name = Some(iface_name.to_string());
+    nm_conn_set.iface_type = Some(iface_type.to_string());
+    nm_conn_set.controller = controller.map(|c| c.to_string());
+    nm_conn_set.controller_type = controller.map(|_| "bond".to_string());
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn
+}
+
+fn activated_uuids<'a>(activations: &[NmDeviceActivation<'a>]) -> Vec<&'a str> {
+    activations
+        .iter()
+        .map(|a| a.nm_conn.uuid().unwrap_or(""))
+        .collect()
+}
+
+#[test]
+fn test_skip_ports_of_new_controller() {
+    let eth1 = gen_nm_conn("uuid-eth1", "eth1", "802-3-ethernet", Some("b0"));
+    let eth2 = gen_nm_conn("uuid-eth2", "eth2", "802-3-ethernet", Some("b1"));
+    let eth3 = gen_nm_conn("uuid-eth3", "eth3", "802-3-ethernet", None);
+
+    let activations =
+        coalesce_device_activations(&[&eth1, &eth2, &eth3], &[], &[], &["b0"]);
+
+    assert_eq!(
+        activated_uuids(&activations),
+        vec!["uuid-eth2", "uuid-eth3"]
+    );
+}
+
+#[test]
+fn test_reapply_active_ports_of_new_controller() {
+    let eth1 = gen_nm_conn("uuid-eth1", "eth1", "802-3-ethernet", Some("b0"));
+    let eth2 = gen_nm_conn("uuid-eth2", "eth2", "802-3-ethernet", Some("b0"));
+
+    let activations = coalesce_device_activations(
+        &[&eth1, &eth2],
+        &["uuid-eth1"],
+        &[],
+        &["b0"],
+    );
+
+    assert_eq!(activated_uuids(&activations), vec!["uuid-eth1"]);
+}
+
+#[test]
+fn test_activate_device_only_once() {
+    let eth1_a = gen_nm_conn("uuid-a", "eth1", "802-3-ethernet", None);
+    let eth2 = gen_nm_conn("uuid-eth2", "eth2", "802-3-ethernet", None);
+    let eth1_b = gen_nm_conn("uuid-b", "eth1", "802-3-ethernet", None);
+    let eth1_c = gen_nm_conn("uuid-c", "eth1", "802-3-ethernet", None);
+
+    let activations = coalesce_device_activations(
+        &[&eth1_a, &eth2, &eth1_b, &eth1_a, &eth1_c],
+        &[],
+        &[],
+        &[],
+    );
+
+    assert_eq!(activations.len(), 2);
+    assert_eq!(activations[0].nm_conn.uuid(), Some("uuid-c"));
+    assert_eq!(
+        activations[0].pending_uuids,
+        vec!["uuid-a", "uuid-b", "uuid-c"]
+    );
+    assert!(!activations[0].reapply_only);
+    assert_eq!(activations[1].nm_conn.uuid(), Some("uuid-eth2"));
+    assert_eq!(activations[1].pending_uuids, vec!["uuid-eth2"]);
+}
+
+#[test]
+fn test_reapply_device_only_when_all_changes_reapplicable() {
+    let eth1_a = gen_nm_conn("uuid-a", "eth1", "802-3-ethernet", None);
+    let eth1_b = gen_nm_conn("uuid-b", "eth1", "802-3-ethernet", None);
+    let eth2 = gen_nm_conn("uuid-eth2", "eth2", "802-3-ethernet", None);
+
+    let activations = coalesce_device_activations(
+        &[&eth1_a, &eth1_b, &eth2],
+        &["uuid-b", "uuid-eth2"],
+        &["uuid-b", "uuid-eth2"],
+        &[],
+    );
+
+    assert_eq!(activated_uuids(&activations), vec!["uuid-b", "uuid-eth2"]);
+    assert!(!activations[0].reapply_only);
+    assert!(activations[1].reapply_only);
+}
+
+fn gen_vlan_nm_conn(uuid: &str, iface_name: &str, id: u32) -> NmConnection {
+    let mut nm_conn = gen_nm_conn(uuid, iface_name, "vlan", None);
+    let mut nm_vlan_set = NmSettingVlan::default();
+    nm_vlan_set.id = Some(id);
+    nm_conn.vlan = Some(nm_vlan_set);
+    nm_conn
+}
+
+#[test]
+fn test_plan_risk_of_nm_activation() {
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+        - name: eth2
+          type: ethernet
+          state: up
+        - name: eth1.10
+          type: vlan
+          state: up
+          vlan:
+            base-iface: eth1
+            id: 10
+        "#,
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          mtu: 1400
+        - name: eth2
+          type: ethernet
+          state: absent
+        - name: eth1.10
+          type: vlan
+          vlan:
+            base-iface: eth1
+            id: 20
+        "#,
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let cur_eth1 = gen_nm_conn("uuid-eth1", "eth1", "802-3-ethernet", None);
+    let cur_vlan = gen_vlan_nm_conn("uuid-vlan", "eth1.10", 10);
+    let new_vlan = gen_vlan_nm_conn("uuid-vlan", "eth1.10", 20);
+
+    let plan = gen_nm_plan(
+        &merged_state,
+        &[cur_eth1.clone(), new_vlan],
+        &[&cur_eth1, &cur_vlan],
+    );
+
+    assert_eq!(
+        plan.interfaces
+            .iter()
+            .map(|i| (i.name.as_str(), i.risk))
+            .collect::<Vec<_>>(),
+        vec![
+            ("eth2", ChangeRisk::Delete),
+            ("eth1.10", ChangeRisk::Reactivate),
+            ("eth1", ChangeRisk::Flap),
+        ]
+    );
+}
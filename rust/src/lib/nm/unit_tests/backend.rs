@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nm::{backend::get_netlink_new_ifaces, NmBackend},
+    query_apply::NetworkBackend,
+    MergedNetworkState, NetworkState,
+};
+
+#[test]
+fn test_nm_backend_apply_without_checkpoint() {
+    let mut backend = NmBackend::new(true);
+    backend.prepare(60).unwrap();
+    assert_eq!(backend.checkpoint_path(), None);
+
+    // Stage only to avoid changing the host when NetworkManager is running
+    let mut desired = NetworkState::new();
+    desired.set_stage_only(true);
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    // The test environment might have no NetworkManager daemon to talk with,
+    // but the apply should never complain about missing checkpoint.
+    if let Err(e) = backend.apply(&merged_state, 60) {
+        assert!(!e.msg().contains("checkpoint"), "{}", e);
+    }
+    backend
+        .verify(&merged_state, &NetworkState::new(), 60)
+        .unwrap();
+    backend.rollback().unwrap();
+}
+
+#[test]
+fn test_nm_backend_netlink_new_ifaces() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: vcan0
+  type: vcan
+  state: up
+- name: vcan1
+  type: vcan
+  state: up
+- name: vcan2
+  type: vcan
+  state: absent
+- name: dummy0
+  type: dummy
+  state: up
+"#,
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: vcan1
+  type: vcan
+  state: up
+"#,
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert_eq!(
+        get_netlink_new_ifaces(&merged_state),
+        vec!["vcan0".to_string()]
+    );
+}
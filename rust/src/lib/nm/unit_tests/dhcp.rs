@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::nm::query_apply::nm_dhcp_opts_to_nmstate;
+
+fn gen_opts(opts: &[(&str, &str)]) -> HashMap<String, String> {
+    opts.iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_nm_dhcp4_opts_to_nmstate() {
+    let opts = gen_opts(&[
+        ("dhcp_server_identifier", "192.0.2.1"),
+        ("dhcp_lease_time", "3600"),
+        ("expiry", "1760000000"),
+        ("private_82", "01:04:65:74:68:30"),
+        ("ip_address", "192.0.2.100"),
+    ]);
+
+    let lease = nm_dhcp_opts_to_nmstate(&opts, false).unwrap();
+
+    assert_eq!(lease.server_id.as_deref(), Some("192.0.2.1"));
+    assert_eq!(lease.lease_time, Some(3600));
+    assert_eq!(lease.expiry, Some(1760000000));
+    assert_eq!(lease.relay_agent_info.as_deref(), Some("01:04:65:74:68:30"));
+}
+
+#[test]
+fn test_nm_dhcp6_opts_to_nmstate() {
+    let opts = gen_opts(&[
+        (
+            "dhcp6_server_id",
+            "00:01:00:01:2a:3b:4c:5d:52:54:00:12:34:56",
+        ),
+        ("max_life", "7200"),
+        ("expiry", "invalid"),
+    ]);
+
+    let lease = nm_dhcp_opts_to_nmstate(&opts, true).unwrap();
+
+    assert_eq!(
+        lease.server_id.as_deref(),
+        Some("00:01:00:01:2a:3b:4c:5d:52:54:00:12:34:56")
+    );
+    assert_eq!(lease.lease_time, Some(7200));
+    assert_eq!(lease.expiry, None);
+}
+
+#[test]
+fn test_nm_dhcp_opts_to_nmstate_empty() {
+    assert_eq!(nm_dhcp_opts_to_nmstate(&HashMap::new(), false), None);
+    assert_eq!(
+        nm_dhcp_opts_to_nmstate(
+            &gen_opts(&[("ip_address", "192.0.2.1")]),
+            false
+        ),
+        None
+    );
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingConnection, NmSettingIp};
+use crate::nm::query_apply::is_dns_only_changed;
+
+fn gen_test_nm_conn(servers: &[&str], searches: &[&str]) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some("eth1".to_string());
+    nm_conn_set.iface_name = Some("eth1".to_string());
+    nm_conn_set.iface_type = Some("802-3-ethernet".to_string());
+    nm_conn.connection = Some(nm_conn_set);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.addresses = vec!["192.0.2.2/24".to_string()];
+    nm_ip_set.dns = Some(servers.iter().map(|s| s.to_string()).collect());
+    nm_ip_set.dns_search =
+        Some(searches.iter().map(|s| s.to_string()).collect());
+    nm_conn.ipv4 = Some(nm_ip_set);
+    nm_conn
+}
+
+#[test]
+fn test_dns_only_changed_on_server_rotation() {
+    let cur_nm_conn =
+        gen_test_nm_conn(&["192.0.2.53", "198.51.100.53"], &["example.com"]);
+    let new_nm_conn =
+        gen_test_nm_conn(&["198.51.100.53", "192.0.2.53"], &["example.com"]);
+
+    assert!(is_dns_only_changed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_dns_only_changed_on_search_changed() {
+    let mut cur_nm_conn = gen_test_nm_conn(&["192.0.2.53"], &[]);
+    cur_nm_conn.obj_path =
+        "/org/freedesktop/NetworkManager/Settings/1".to_string();
+    let new_nm_conn = gen_test_nm_conn(&["192.0.2.53"], &["example.org"]);
+
+    assert!(is_dns_only_changed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_dns_only_changed_with_other_changes() {
+    let cur_nm_conn = gen_test_nm_conn(&["192.0.2.53"], &[]);
+    let mut new_nm_conn = gen_test_nm_conn(&["198.51.100.53"], &[]);
+    if let Some(nm_ip_set) = new_nm_conn.ipv4.as_mut() {
+        nm_ip_set.addresses = vec!["192.0.2.3/24".to_string()];
+    }
+
+    assert!(!is_dns_only_changed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_dns_only_changed_treat_empty_search_as_unset() {
+    let cur_nm_conn = gen_test_nm_conn(&["192.0.2.53"], &[]);
+    let mut new_nm_conn = cur_nm_conn.clone();
+    if let Some(nm_ip_set) = new_nm_conn.ipv4.as_mut() {
+        nm_ip_set.dns_search = None;
+    }
+
+    assert!(!is_dns_only_changed(&new_nm_conn, &cur_nm_conn));
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmIpRoute, NmSettingIp};
+use crate::nm::query_apply::drain::gen_nm_conn_for_drain;
+
+#[test]
+fn test_drain_raise_route_metric() {
+    let mut nm_conn = NmConnection::default();
+    let mut route = NmIpRoute::default();
+    route.dest = Some("198.51.100.0".to_string());
+    route.prefix = Some(24);
+    route.next_hop = Some("192.0.2.1".to_string());
+    route.metric = Some(100);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.routes = vec![route];
+    nm_ip_set.route_metric = Some(100);
+    nm_conn.ipv4 = Some(nm_ip_set);
+    nm_conn.ipv6 = Some(NmSettingIp::default());
+
+    let nm_conn = gen_nm_conn_for_drain(&nm_conn);
+
+    let nm_ip_set = nm_conn.ipv4.as_ref().unwrap();
+    assert_eq!(nm_ip_set.route_metric, Some(u32::MAX.into()));
+    assert_eq!(nm_ip_set.routes[0].metric, Some(u32::MAX));
+    assert_eq!(nm_ip_set.routes[0].dest.as_deref(), Some("198.51.100.0"));
+    assert_eq!(
+        nm_conn.ipv6.as_ref().unwrap().route_metric,
+        Some(u32::MAX.into())
+    );
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::error::{
+    find_last_dbus_avc_denial, gen_permission_hints, read_file_tail,
+};
+
+const AUDIT_LOG: &str = r#"type=SERVICE_START msg=audit(1680000000.000:100): pid=1 uid=0 msg='unit=NetworkManager comm="systemd"'
+type=AVC msg=audit(1680000001.000:101): avc:  denied  { send_msg } for msg=method_call interface=org.freedesktop.NetworkManager member=GetDevices scontext=system_u:system_r:foo_t:s0 tcontext=system_u:system_r:NetworkManager_t:s0 tclass=dbus permissive=0
+type=AVC msg=audit(1680000002.000:102): avc:  denied  { read } for pid=2 comm="foo" name="bar" scontext=system_u:system_r:foo_t:s0 tclass=file permissive=0
+"#;
+
+#[test]
+fn test_find_last_dbus_avc_denial() {
+    let line = find_last_dbus_avc_denial(AUDIT_LOG).unwrap();
+    assert!(line.contains("audit(1680000001.000:101)"));
+    assert_eq!(find_last_dbus_avc_denial(""), None);
+}
+
+#[test]
+fn test_read_audit_log_tail() {
+    let file_path = std::env::temp_dir()
+        .join(format!("nmstate_test_audit_{}.log", std::process::id()));
+    std::fs::write(&file_path, AUDIT_LOG).unwrap();
+    let file_path = file_path.to_str().unwrap();
+
+    let full = read_file_tail(file_path, u64::MAX).unwrap();
+    let tail = read_file_tail(file_path, 200).unwrap();
+    std::fs::remove_file(file_path).unwrap();
+
+    assert_eq!(full, AUDIT_LOG);
+    // Only the last line fits, the partial line before it is discarded
+    assert!(tail.starts_with("type=AVC msg=audit(1680000002.000:102)"));
+    assert_eq!(find_last_dbus_avc_denial(&tail), None);
+}
+
+#[test]
+fn test_read_unreadable_audit_log() {
+    assert_eq!(read_file_tail("/nonexistent/audit.log", 200), None);
+}
+
+#[test]
+fn test_permission_hints_non_root() {
+    let hints = gen_permission_hints("Not authorized", false, false, None);
+    assert_eq!(hints.len(), 1);
+    assert!(hints[0].contains("not running as root"));
+}
+
+#[test]
+fn test_permission_hints_polkit() {
+    let hints = gen_permission_hints(
+        "Not authorized to control networking.",
+        true,
+        false,
+        None,
+    );
+    assert_eq!(hints.len(), 1);
+    assert!(hints[0].contains("polkit"));
+}
+
+#[test]
+fn test_permission_hints_selinux() {
+    let hints = gen_permission_hints("Rejected send message", true, true, None);
+    assert_eq!(hints.len(), 1);
+    assert!(hints[0].contains("ausearch"));
+
+    let avc_denial = find_last_dbus_avc_denial(AUDIT_LOG);
+    let hints =
+        gen_permission_hints("Rejected send message", true, true, avc_denial);
+    assert_eq!(hints.len(), 1);
+    assert!(hints[0].contains("tclass=dbus"));
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmSetting8021X;
+
+#[test]
+fn test_802_1x_cert_glib_bytes() {
+    let file_path = "/etc/pki/802-1x-test/ca.crt";
+    let uri = "pkcs11:token=nmstate;object=client";
+
+    assert_eq!(
+        NmSetting8021X::cert_to_glib_bytes(file_path),
+        NmSetting8021X::file_path_to_glib_bytes(file_path)
+    );
+    assert_eq!(
+        NmSetting8021X::cert_to_glib_bytes(uri),
+        b"pkcs11:token=nmstate;object=client\0".to_vec()
+    );
+    assert_eq!(
+        NmSetting8021X::glib_bytes_to_file_path(
+            &NmSetting8021X::cert_to_glib_bytes(file_path)
+        )
+        .unwrap(),
+        file_path
+    );
+    assert_eq!(
+        NmSetting8021X::glib_bytes_to_file_path(
+            &NmSetting8021X::cert_to_glib_bytes(uri)
+        )
+        .unwrap(),
+        uri
+    );
+}
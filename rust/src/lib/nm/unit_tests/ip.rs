@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{
+    NmConnection, NmSettingConnection, NmSettingIp, NmSettingIpMethod,
+};
+use crate::nm::query_apply::apply::gen_nm_plan;
+use crate::nm::settings::iface_to_nm_connections;
+use crate::{ChangeRisk, InterfaceIpAddr, MergedNetworkState, NetworkState};
+
+fn gen_merged_state_with_external_addr(
+    desired_yaml: &str,
+) -> MergedNetworkState {
+    let mut current: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: "192.0.2.1"
+              prefix-length: 24
+            - ip: "192.0.2.100"
+              prefix-length: 32
+          ipv6:
+            enabled: true
+            dhcp: false
+            autoconf: false
+            address:
+            - ip: "2001:db8::1"
+              prefix-length: 64
+            - ip: "2001:db8::100"
+              prefix-length: 128
+        "#,
+    )
+    .unwrap();
+    let base_iface = current
+        .interfaces
+        .kernel_ifaces
+        .get_mut("eth1")
+        .unwrap()
+        .base_iface_mut();
+    if let Some(ipv4) = base_iface.ipv4.as_mut() {
+        ipv4.owned_addresses = Some(vec![InterfaceIpAddr {
+            ip: "192.0.2.1".parse().unwrap(),
+            prefix_length: 24,
+            mptcp_flags: None,
+        }]);
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_mut() {
+        ipv6.owned_addresses = Some(vec![InterfaceIpAddr {
+            ip: "2001:db8::1".parse().unwrap(),
+            prefix_length: 64,
+            mptcp_flags: None,
+        }]);
+    }
+    let desired: NetworkState = serde_yaml::from_str(desired_yaml).unwrap();
+    MergedNetworkState::new(desired, current, false, false).unwrap()
+}
+
+#[test]
+fn test_nm_ip_method_with_only_external_addresses_left() {
+    let merged_state = gen_merged_state_with_external_addr(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address: []
+          ipv6:
+            enabled: true
+            dhcp: false
+            autoconf: false
+            address: []
+        "#,
+    );
+    let mut merged_iface =
+        merged_state.interfaces.kernel_ifaces["eth1"].clone();
+    // Generate profile from merged interface holding the preserved addresses
+    merged_iface.for_apply = Some(merged_iface.merged.clone());
+
+    let nm_conns =
+        iface_to_nm_connections(&merged_iface, &merged_state, &[], &[], false)
+            .unwrap();
+
+    let nm_ipv4 = nm_conns[0].ipv4.as_ref().unwrap();
+    assert_eq!(nm_ipv4.method, Some(NmSettingIpMethod::Disabled));
+    assert!(nm_ipv4.addresses.is_empty());
+    let nm_ipv6 = nm_conns[0].ipv6.as_ref().unwrap();
+    assert_eq!(nm_ipv6.method, Some(NmSettingIpMethod::LinkLocal));
+    assert!(nm_ipv6.addresses.is_empty());
+}
+
+#[test]
+fn test_nm_ip_external_addresses_not_in_profile() {
+    let merged_state = gen_merged_state_with_external_addr(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: "192.0.2.2"
+              prefix-length: 24
+        "#,
+    );
+    let mut merged_iface =
+        merged_state.interfaces.kernel_ifaces["eth1"].clone();
+    // Generate profile from merged interface holding the preserved addresses
+    merged_iface.for_apply = Some(merged_iface.merged.clone());
+
+    let nm_conns =
+        iface_to_nm_connections(&merged_iface, &merged_state, &[], &[], false)
+            .unwrap();
+
+    let nm_ipv4 = nm_conns[0].ipv4.as_ref().unwrap();
+    assert_eq!(nm_ipv4.method, Some(NmSettingIpMethod::Manual));
+    assert_eq!(nm_ipv4.addresses, vec!["192.0.2.2/24".to_string()]);
+}
+
+fn gen_nm_conn_with_addrs(addrs: &[&str]) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.uuid = Some("uuid-eth1".to_string());
+    nm_conn_set.iface_name = Some("eth1".to_string());
+    nm_conn_set.iface_type = Some("802-3-ethernet".to_string());
+    nm_conn.connection = Some(nm_conn_set);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.method = Some(NmSettingIpMethod::Manual);
+    nm_ip_set.addresses = addrs.iter().map(|a| a.to_string()).collect();
+    nm_conn.ipv4 = Some(nm_ip_set);
+    nm_conn
+}
+
+// Activation flushes the IP addresses not added by nmstate until restored
+// after activation, hence active profile with only IP addresses changed is
+// reapplied instead.
+#[test]
+fn test_reapply_active_profile_with_only_ip_addresses_changed() {
+    let merged_state = gen_merged_state_with_external_addr(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: "192.0.2.2"
+              prefix-length: 24
+        "#,
+    );
+    let cur_nm_conn = gen_nm_conn_with_addrs(&["192.0.2.1/24"]);
+    let new_nm_conn = gen_nm_conn_with_addrs(&["192.0.2.2/24"]);
+
+    let plan =
+        gen_nm_plan(&merged_state, &[new_nm_conn.clone()], &[&cur_nm_conn]);
+    assert_eq!(plan.interfaces[0].name, "eth1");
+    assert_eq!(plan.interfaces[0].risk, ChangeRisk::Reapply);
+
+    // Inactive profile still requires activation
+    let plan = gen_nm_plan(&merged_state, &[new_nm_conn], &[]);
+    assert_eq!(plan.interfaces[0].risk, ChangeRisk::Flap);
+}
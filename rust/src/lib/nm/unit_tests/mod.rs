@@ -1,2 +1,36 @@
+#[cfg(all(test, feature = "query_apply"))]
+mod activation;
+#[cfg(all(test, feature = "query_apply"))]
+mod backend;
+#[cfg(all(test, feature = "query_apply"))]
+mod dhcp;
+#[cfg(all(test, feature = "query_apply"))]
+mod dns;
+#[cfg(all(test, feature = "query_apply"))]
+mod drain;
+#[cfg(all(test, feature = "query_apply"))]
+mod error;
+#[cfg(test)]
+mod ieee8021x;
+#[cfg(all(test, feature = "query_apply"))]
+mod ip;
+#[cfg(all(test, feature = "query_apply"))]
+mod multi_connect;
+#[cfg(all(test, feature = "query_apply"))]
+mod nm_conf;
+#[cfg(all(test, feature = "query_apply"))]
+mod permissions;
+#[cfg(all(test, feature = "query_apply"))]
+mod profile_uuid;
 #[cfg(test)]
 mod profiles;
+#[cfg(all(test, feature = "query_apply"))]
+mod route;
+#[cfg(all(test, feature = "query_apply"))]
+mod saved;
+#[cfg(all(test, feature = "query_apply"))]
+mod team;
+#[cfg(all(test, feature = "query_apply"))]
+mod user;
+#[cfg(test)]
+mod version;
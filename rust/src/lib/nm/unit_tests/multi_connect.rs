@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingConnection};
+use crate::nm::query_apply::get_multi_connect;
+use crate::MultiConnect;
+
+#[test]
+fn test_get_multi_connect() {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    assert_eq!(get_multi_connect(&nm_conn), None);
+
+    nm_conn_set.multi_connect = Some(3);
+    nm_conn.connection = Some(nm_conn_set.clone());
+    assert_eq!(get_multi_connect(&nm_conn), Some(MultiConnect::Multiple));
+
+    nm_conn_set.multi_connect = Some(0);
+    nm_conn.connection = Some(nm_conn_set);
+    assert_eq!(get_multi_connect(&nm_conn), None);
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_conf::{
+    nm_conf_from_str, nm_conf_to_string, nmstate_nm_conf_from_str,
+    nmstate_nm_conf_to_string,
+};
+use crate::NetworkManagerConfig;
+
+#[test]
+fn test_nm_conf_to_string() {
+    let conf: NetworkManagerConfig = serde_yaml::from_str(
+        r#"---
+dns: systemd-resolved
+connectivity-uri: http://check.example.org/
+no-auto-default:
+- eth1
+- mac:00:23:45:67:89:ab
+profile-naming: site-{type}-{name}
+"#,
+    )
+    .unwrap();
+
+    let nm_conf = nm_conf_to_string(&conf).unwrap();
+    assert_eq!(
+        nm_conf,
+        r#"# Generated by nmstate, do not edit
+
+[main]
+dns=systemd-resolved
+no-auto-default=eth1,mac:00:23:45:67:89:ab
+
+[connectivity]
+uri=http://check.example.org/
+"#
+    );
+    let mut expected_conf = conf.clone();
+    expected_conf.profile_naming = None;
+    assert_eq!(nm_conf_from_str(&nm_conf), expected_conf);
+
+    let nmstate_nm_conf = nmstate_nm_conf_to_string(&conf).unwrap();
+    assert!(!nmstate_nm_conf.contains("dns"));
+    assert_eq!(
+        nmstate_nm_conf_from_str(&nmstate_nm_conf)
+            .unwrap()
+            .profile_naming
+            .as_deref(),
+        Some("site-{type}-{name}")
+    );
+}
+
+#[test]
+fn test_nm_conf_profile_naming_only() {
+    let conf: NetworkManagerConfig = serde_yaml::from_str(
+        r#"---
+profile-naming: nmstate-prefix
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(nm_conf_to_string(&conf), None);
+    assert_eq!(
+        nmstate_nm_conf_to_string(&conf).unwrap(),
+        "# Generated by nmstate, do not edit\nprofile-naming: nmstate-prefix\n"
+    );
+}
+
+#[test]
+fn test_nm_conf_from_str_ignore_unknown() {
+    let conf = nm_conf_from_str(
+        r#"
+[main]
+plugins=keyfile
+no-auto-default=eth1; eth2
+
+[connectivity]
+interval=300
+"#,
+    );
+
+    assert_eq!(conf.dns, None);
+    assert_eq!(conf.connectivity_uri, None);
+    assert_eq!(
+        conf.no_auto_default,
+        Some(vec!["eth1".to_string(), "eth2".to_string()])
+    );
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingConnection};
+use crate::nm::query_apply::get_permissions;
+
+#[test]
+fn test_get_permissions() {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    assert_eq!(get_permissions(&nm_conn), None);
+
+    nm_conn_set.permissions = Some(vec![
+        "user:bob:".to_string(),
+        "user:alice:".to_string(),
+        "group:wheel:".to_string(),
+    ]);
+    nm_conn.connection = Some(nm_conn_set.clone());
+    assert_eq!(
+        get_permissions(&nm_conn),
+        Some(vec!["alice".to_string(), "bob".to_string()])
+    );
+
+    nm_conn_set.permissions = Some(Vec::new());
+    nm_conn.connection = Some(nm_conn_set);
+    assert_eq!(get_permissions(&nm_conn), None);
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingConnection};
+use crate::nm::settings::iface_to_nm_connections;
+use crate::{ErrorKind, MergedNetworkState, NetworkState};
+
+const UUID1: &str = "8aca0200-accc-4d13-a62f-3c89a6da53c5";
+const UUID2: &str = "1c646761-efcc-4d33-a0d9-cb3c1c2d3309";
+
+fn gen_eth_nm_conn(uuid: &str, iface_name: &str) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some(iface_name.to_string());
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_name = Some(iface_name.to_string());
+    nm_conn_set.iface_type = Some("802-3-ethernet".to_string());
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn.obj_path =
+        format!("/org/freedesktop/NetworkManager/Settings/{iface_name}");
+    nm_conn
+}
+
+fn gen_merged_state(profile_uuid: &str) -> MergedNetworkState {
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+        - name: eth2
+          type: ethernet
+          state: up
+        "#,
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(&format!(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          profile-uuid: {profile_uuid}
+        "#,
+    ))
+    .unwrap();
+    MergedNetworkState::new(desired, current, false, false).unwrap()
+}
+
+#[test]
+fn test_pin_profile_uuid_recreate_profile() {
+    let merged_state = gen_merged_state(&UUID2.to_uppercase());
+    let merged_iface =
+        merged_state.interfaces.kernel_ifaces.get("eth1").unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_iface,
+        &merged_state,
+        &[gen_eth_nm_conn(UUID1, "eth1")],
+        &[],
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(nm_conns[0].uuid(), Some(UUID2));
+    assert_eq!(nm_conns[0].iface_name(), Some("eth1"));
+    assert!(nm_conns[0].obj_path.is_empty());
+}
+
+#[test]
+fn test_pin_profile_uuid_unchanged() {
+    let merged_state = gen_merged_state(UUID1);
+    let merged_iface =
+        merged_state.interfaces.kernel_ifaces.get("eth1").unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_iface,
+        &merged_state,
+        &[gen_eth_nm_conn(UUID1, "eth1")],
+        &[],
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(nm_conns[0].uuid(), Some(UUID1));
+    assert!(!nm_conns[0].obj_path.is_empty());
+}
+
+#[test]
+fn test_pin_profile_uuid_used_by_other_iface() {
+    let merged_state = gen_merged_state(UUID2);
+    let merged_iface =
+        merged_state.interfaces.kernel_ifaces.get("eth1").unwrap();
+
+    let result = iface_to_nm_connections(
+        merged_iface,
+        &merged_state,
+        &[
+            gen_eth_nm_conn(UUID1, "eth1"),
+            gen_eth_nm_conn(UUID2, "eth2"),
+        ],
+        &[],
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
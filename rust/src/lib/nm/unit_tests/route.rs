@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{
+    NmConnection, NmIpRoute, NmSettingConnection, NmSettingIp,
+};
+use crate::nm::query_apply::{is_route_only_changed, is_route_removed};
+
+fn gen_test_nm_conn(routes: Vec<NmIpRoute>) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some("eth1".to_string());
+    nm_conn_set.iface_name = Some("eth1".to_string());
+    nm_conn_set.iface_type = Some("802-3-ethernet".to_string());
+    nm_conn.connection = Some(nm_conn_set);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.routes = routes;
+    nm_conn.ipv4 = Some(nm_ip_set);
+    nm_conn
+}
+
+fn gen_test_nm_route(dest: &str) -> NmIpRoute {
+    let mut route = NmIpRoute::default();
+    route.dest = Some(dest.to_string());
+    route.prefix = Some(24);
+    route.next_hop = Some("192.0.2.1".to_string());
+    route
+}
+
+#[test]
+fn test_route_only_changed_on_route_added() {
+    let cur_nm_conn = gen_test_nm_conn(vec![gen_test_nm_route("198.51.100.0")]);
+    let new_nm_conn = gen_test_nm_conn(vec![
+        gen_test_nm_route("198.51.100.0"),
+        gen_test_nm_route("203.0.113.0"),
+    ]);
+
+    assert!(is_route_only_changed(&new_nm_conn, &cur_nm_conn));
+    assert!(!is_route_removed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_route_only_changed_ignores_obj_path() {
+    let mut cur_nm_conn = gen_test_nm_conn(Vec::new());
+    cur_nm_conn.obj_path =
+        "/org/freedesktop/NetworkManager/Settings/1".to_string();
+    let new_nm_conn = gen_test_nm_conn(vec![gen_test_nm_route("203.0.113.0")]);
+
+    assert!(is_route_only_changed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_route_only_changed_with_other_changes() {
+    let cur_nm_conn = gen_test_nm_conn(Vec::new());
+    let mut new_nm_conn =
+        gen_test_nm_conn(vec![gen_test_nm_route("203.0.113.0")]);
+    if let Some(nm_ip_set) = new_nm_conn.ipv4.as_mut() {
+        nm_ip_set.addresses = vec!["192.0.2.2/24".to_string()];
+    }
+
+    assert!(!is_route_only_changed(&new_nm_conn, &cur_nm_conn));
+}
+
+#[test]
+fn test_route_only_changed_without_change() {
+    let cur_nm_conn = gen_test_nm_conn(vec![gen_test_nm_route("198.51.100.0")]);
+    let new_nm_conn = cur_nm_conn.clone();
+
+    assert!(!is_route_only_changed(&new_nm_conn, &cur_nm_conn));
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{
+    NmConnection, NmIpRoute, NmSettingBond, NmSettingConnection, NmSettingIp,
+    NmSettingIpMethod, NmSettingVlan,
+};
+use crate::nm::saved::nm_saved_conns_to_nmstate;
+use crate::{BondMode, Interface, InterfaceState, InterfaceType};
+
+fn gen_nm_conn(
+    name: &str,
+    nm_iface_type: &str,
+    uuid: &str,
+    controller: Option<&str>,
+) -> NmConnection {
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some(name.to_string());
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_name = Some(name.to_string());
+    nm_conn_set.iface_type = Some(nm_iface_type.to_string());
+    nm_conn_set.controller = controller.map(|c| c.to_string());
+    let mut nm_conn = NmConnection::default();
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn
+}
+
+#[test]
+fn test_nm_saved_conns_to_nmstate() {
+    let mut bond_conn = gen_nm_conn(
+        "bond0",
+        "bond",
+        "9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0001",
+        None,
+    );
+    let mut nm_bond_set = NmSettingBond::default();
+    nm_bond_set
+        .options
+        .insert("mode".to_string(), "active-backup".to_string());
+    bond_conn.bond = Some(nm_bond_set);
+    let mut nm_route = NmIpRoute::default();
+    nm_route.dest = Some("198.51.100.0".to_string());
+    nm_route.prefix = Some(24);
+    nm_route.next_hop = Some("192.0.2.254".to_string());
+    nm_route.table = Some(100);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.method = Some(NmSettingIpMethod::Manual);
+    nm_ip_set.addresses = vec!["192.0.2.1/24".to_string()];
+    nm_ip_set.routes = vec![nm_route];
+    bond_conn.ipv4 = Some(nm_ip_set);
+
+    let eth1_conn = gen_nm_conn(
+        "eth1",
+        "802-3-ethernet",
+        "9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0002",
+        Some("9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0001"),
+    );
+    let eth2_conn = gen_nm_conn(
+        "eth2",
+        "802-3-ethernet",
+        "9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0003",
+        Some("bond0"),
+    );
+    let mut vlan_conn = gen_nm_conn(
+        "bond0.101",
+        "vlan",
+        "9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0004",
+        None,
+    );
+    let mut nm_vlan_set = NmSettingVlan::default();
+    nm_vlan_set.parent = Some("9ad0fa5d-2a2e-4a14-8f2c-1b9a5f2d0001".into());
+    nm_vlan_set.id = Some(101);
+    vlan_conn.vlan = Some(nm_vlan_set);
+    if let Some(nm_conn_set) = vlan_conn.connection.as_mut() {
+        nm_conn_set.autoconnect = Some(false);
+    }
+
+    let net_state = nm_saved_conns_to_nmstate(&[
+        vlan_conn, eth2_conn, eth1_conn, bond_conn,
+    ]);
+
+    let bond_iface = net_state
+        .interfaces
+        .get_iface("bond0", InterfaceType::Bond)
+        .unwrap();
+    if let Interface::Bond(bond_iface) = bond_iface {
+        let bond_conf = bond_iface.bond.as_ref().unwrap();
+        assert_eq!(bond_conf.mode, Some(BondMode::ActiveBackup));
+        assert_eq!(
+            bond_conf.port.as_deref(),
+            Some(["eth1".to_string(), "eth2".to_string()].as_slice())
+        );
+    } else {
+        panic!("Expecting bond interface, got {:?}", bond_iface);
+    }
+    let ipv4 = bond_iface.base_iface().ipv4.as_ref().unwrap();
+    let addr = &ipv4.addresses.as_ref().unwrap()[0];
+    assert_eq!(addr.ip.to_string(), "192.0.2.1");
+    assert_eq!(addr.prefix_length, 24);
+
+    let eth1_iface = net_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(eth1_iface.base_iface().controller.as_deref(), Some("bond0"));
+
+    let vlan_iface = net_state
+        .interfaces
+        .get_iface("bond0.101", InterfaceType::Vlan)
+        .unwrap();
+    assert_eq!(vlan_iface.base_iface().state, InterfaceState::Down);
+    if let Interface::Vlan(vlan_iface) = vlan_iface {
+        let vlan_conf = vlan_iface.vlan.as_ref().unwrap();
+        assert_eq!(vlan_conf.base_iface, "bond0");
+        assert_eq!(vlan_conf.id, 101);
+    } else {
+        panic!("Expecting vlan interface, got {:?}", vlan_iface);
+    }
+
+    let routes = net_state.routes.config.as_ref().unwrap();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].destination.as_deref(), Some("198.51.100.0/24"));
+    assert_eq!(routes[0].next_hop_iface.as_deref(), Some("bond0"));
+    assert_eq!(routes[0].table_id, Some(100));
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{
+    NmConnection, NmSettingConnection, NmSettingIp, NmSettingIpMethod,
+};
+use crate::nm::team::nm_team_conns_to_bond;
+use crate::{
+    BondFailOverMac, BondLacpRate, BondMode, BondPrimaryReselect,
+    BondXmitHashPolicy, ErrorKind, Interface,
+};
+
+const TEAM_UUID: &str = "4a4b3c2d-9e8f-4a1b-8c7d-6e5f4a3b0001";
+
+fn gen_nm_conn(
+    name: &str,
+    nm_iface_type: &str,
+    uuid: &str,
+    controller: Option<&str>,
+) -> NmConnection {
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some(name.to_string());
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_name = Some(name.to_string());
+    nm_conn_set.iface_type = Some(nm_iface_type.to_string());
+    if let Some(ctrl) = controller {
+        nm_conn_set.controller = Some(ctrl.to_string());
+        nm_conn_set.controller_type = Some("team".to_string());
+    }
+    let mut nm_conn = NmConnection::default();
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn
+}
+
+fn gen_team_conns(
+    team_config: &str,
+    port_configs: &[(&str, Option<&str>)],
+) -> Vec<NmConnection> {
+    let mut team_conn = gen_nm_conn("team0", "team", TEAM_UUID, None);
+    let mut nm_team_set = team_conn.team.take().unwrap_or_default();
+    nm_team_set.config = Some(team_config.to_string());
+    team_conn.team = Some(nm_team_set);
+    let mut nm_ip_set = NmSettingIp::default();
+    nm_ip_set.method = Some(NmSettingIpMethod::Manual);
+    nm_ip_set.addresses = vec!["192.0.2.1/24".to_string()];
+    team_conn.ipv4 = Some(nm_ip_set);
+
+    let mut ret = vec![team_conn];
+    for (i, (port_name, port_config)) in port_configs.iter().enumerate() {
+        let mut port_conn = gen_nm_conn(
+            port_name,
+            "802-3-ethernet",
+            &format!("4a4b3c2d-9e8f-4a1b-8c7d-6e5f4a3b010{i}"),
+            // Controller could be referred by name or UUID
+            Some(if i % 2 == 0 { TEAM_UUID } else { "team0" }),
+        );
+        if let Some(port_config) = port_config {
+            let mut nm_team_port_set =
+                port_conn.team_port.take().unwrap_or_default();
+            nm_team_port_set.config = Some(port_config.to_string());
+            port_conn.team_port = Some(nm_team_port_set);
+        }
+        ret.push(port_conn);
+    }
+    ret
+}
+
+#[test]
+fn test_team_activebackup_to_bond() {
+    let nm_conns = gen_team_conns(
+        r#"{
+            "runner": {"name": "activebackup", "hwaddr_policy": "by_active"},
+            "link_watch": {"name": "ethtool", "delay_up": 150}
+        }"#,
+        &[
+            ("eth2", Some(r#"{"prio": 100}"#)),
+            ("eth1", Some(r#"{"prio": -10, "sticky": true}"#)),
+        ],
+    );
+
+    let net_state = nm_team_conns_to_bond(&nm_conns).unwrap();
+
+    let bond_iface = match net_state.interfaces.kernel_ifaces.get("team0") {
+        Some(Interface::Bond(i)) => i,
+        _ => panic!("Failed to find bond team0 in {:?}", net_state),
+    };
+    let bond_conf = bond_iface.bond.as_ref().unwrap();
+    let opts = bond_conf.options.as_ref().unwrap();
+    assert_eq!(bond_conf.mode, Some(BondMode::ActiveBackup));
+    assert_eq!(
+        bond_conf.port,
+        Some(vec!["eth1".to_string(), "eth2".to_string()])
+    );
+    assert_eq!(opts.fail_over_mac, Some(BondFailOverMac::Active));
+    assert_eq!(opts.primary.as_deref(), Some("eth1"));
+    assert_eq!(opts.primary_reselect, Some(BondPrimaryReselect::Failure));
+    assert_eq!(opts.miimon, Some(100));
+    assert_eq!(opts.updelay, Some(200));
+    assert_eq!(
+        bond_iface
+            .base
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.addresses.as_ref())
+            .map(|a| a.len()),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_team_lacp_to_bond() {
+    let nm_conns = gen_team_conns(
+        r#"{
+            "runner": {
+                "name": "lacp",
+                "fast_rate": true,
+                "min_ports": 2,
+                "tx_hash": ["eth", "ipv4", "tcp"]
+            },
+            "ports": {"eth2": {"prio": 10}}
+        }"#,
+        &[("eth1", None), ("eth2", None)],
+    );
+
+    let net_state = nm_team_conns_to_bond(&nm_conns).unwrap();
+
+    let bond_iface = match net_state.interfaces.kernel_ifaces.get("team0") {
+        Some(Interface::Bond(i)) => i,
+        _ => panic!("Failed to find bond team0 in {:?}", net_state),
+    };
+    let bond_conf = bond_iface.bond.as_ref().unwrap();
+    let opts = bond_conf.options.as_ref().unwrap();
+    assert_eq!(bond_conf.mode, Some(BondMode::LACP));
+    assert_eq!(opts.lacp_rate, Some(BondLacpRate::Fast));
+    assert_eq!(opts.min_links, Some(2));
+    assert_eq!(opts.xmit_hash_policy, Some(BondXmitHashPolicy::Layer34));
+    assert_eq!(opts.primary, None);
+}
+
+#[test]
+fn test_team_random_runner_not_supported() {
+    let nm_conns =
+        gen_team_conns(r#"{"runner": {"name": "random"}}"#, &[("eth1", None)]);
+
+    let result = nm_team_conns_to_bond(&nm_conns);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingUser};
+use crate::nm::query_apply::{get_description, get_metadata};
+use crate::nm::settings::gen_nm_user_setting;
+use crate::Interface;
+
+#[test]
+fn test_get_metadata() {
+    let mut nm_conn = NmConnection::default();
+    assert_eq!(get_metadata(&nm_conn), None);
+
+    let mut nm_setting = NmSettingUser::default();
+    nm_setting.data = Some(HashMap::from([
+        (
+            "nmstate.interface.description".to_string(),
+            "test".to_string(),
+        ),
+        (
+            "nmstate.interface.label.owner".to_string(),
+            "operator-a".to_string(),
+        ),
+    ]));
+    nm_conn.user = Some(nm_setting);
+    assert_eq!(
+        get_metadata(&nm_conn).and_then(|m| m.labels),
+        Some(HashMap::from([(
+            "owner".to_string(),
+            "operator-a".to_string()
+        )]))
+    );
+}
+
+#[test]
+fn test_labels_override_and_preserve_other_user_data() {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_setting = NmSettingUser::default();
+    nm_setting.data = Some(HashMap::from([
+        (
+            "nmstate.interface.description".to_string(),
+            "test".to_string(),
+        ),
+        (
+            "nmstate.interface.label.owner".to_string(),
+            "operator-a".to_string(),
+        ),
+        ("other.tool.key".to_string(), "abc".to_string()),
+    ]));
+    nm_conn.user = Some(nm_setting);
+
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+metadata:
+  labels:
+    role: storage
+"#,
+    )
+    .unwrap();
+    gen_nm_user_setting(&iface, &mut nm_conn);
+
+    assert_eq!(get_description(&nm_conn), Some("test".to_string()));
+    assert_eq!(
+        get_metadata(&nm_conn).and_then(|m| m.labels),
+        Some(HashMap::from([("role".to_string(), "storage".to_string())]))
+    );
+    assert_eq!(
+        nm_conn
+            .user
+            .as_ref()
+            .and_then(|s| s.data.as_ref())
+            .and_then(|d| d.get("other.tool.key")),
+        Some(&"abc".to_string())
+    );
+}
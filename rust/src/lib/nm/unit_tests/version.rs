@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::version::{NmPlugins, NmVersion, NM_VERSION_MPTCP};
+
+#[test]
+fn test_nm_version_parse() {
+    let version = NmVersion::parse("1.43.7-dev").unwrap();
+    assert_eq!(version.major, 1);
+    assert_eq!(version.minor, 43);
+    assert_eq!(version.to_string(), "1.43.7-dev");
+    assert!(NmVersion::parse("").is_none());
+    assert!(NmVersion::parse("abc").is_none());
+}
+
+#[test]
+fn test_nm_version_compare() {
+    assert!(NmVersion::parse("1.40.0")
+        .unwrap()
+        .is_newer_or_equal(NM_VERSION_MPTCP));
+    assert!(NmVersion::parse("2.0.0")
+        .unwrap()
+        .is_newer_or_equal(NM_VERSION_MPTCP));
+    assert!(!NmVersion::parse("1.38.6")
+        .unwrap()
+        .is_newer_or_equal(NM_VERSION_MPTCP));
+}
+
+#[test]
+fn test_nm_plugins_parse() {
+    let file_names: Vec<String> = [
+        "libnm-device-plugin-wifi.so",
+        "libnm-device-plugin-ovs.so",
+        "libnm-device-plugin-team.so",
+        "libnm-settings-plugin-ifcfg-rh.so",
+        "libnm-ppp-plugin.so",
+        "README",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let plugins = NmPlugins::parse(file_names.as_slice());
+
+    assert_eq!(plugins.device, vec!["ovs", "team", "wifi"]);
+    assert_eq!(plugins.settings, vec!["ifcfg-rh", "keyfile"]);
+}
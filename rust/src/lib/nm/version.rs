@@ -2,6 +2,62 @@
 
 use crate::NmstateError;
 
+#[cfg(feature = "query_apply")]
+pub(crate) const NM_VERSION_GLOBAL_DNS: (u32, u32) = (1, 2);
+#[cfg(feature = "query_apply")]
+pub(crate) const NM_VERSION_VETH: (u32, u32) = (1, 30);
+#[cfg(feature = "query_apply")]
+pub(crate) const NM_VERSION_MPTCP: (u32, u32) = (1, 40);
+
+#[cfg(feature = "query_apply")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct NmVersion {
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    raw: String,
+}
+
+#[cfg(feature = "query_apply")]
+impl NmVersion {
+    // NetworkManager version string is like `1.42.2` or `1.43.7-dev`,
+    // only major and minor are used for feature checking.
+    pub(crate) fn parse(version: &str) -> Option<Self> {
+        let mut items = version.split('.');
+        let major = items.next()?.parse::<u32>().ok()?;
+        let minor = items.next()?.parse::<u32>().ok()?;
+        Some(Self {
+            major,
+            minor,
+            raw: version.to_string(),
+        })
+    }
+
+    pub(crate) fn is_newer_or_equal(&self, (major, minor): (u32, u32)) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+#[cfg(feature = "query_apply")]
+impl std::fmt::Display for NmVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+#[cfg(feature = "query_apply")]
+pub(crate) fn nm_version() -> Result<NmVersion, NmstateError> {
+    let version = crate::nm::nm_dbus::NmApi::new()
+        .map_err(crate::nm::error::nm_error_to_nmstate)?
+        .version()
+        .map_err(crate::nm::error::nm_error_to_nmstate)?;
+    NmVersion::parse(&version).ok_or_else(|| {
+        NmstateError::new(
+            crate::ErrorKind::PluginFailure,
+            format!("Invalid NetworkManager version string: {version}"),
+        )
+    })
+}
+
 // This helper function will help us to avoid introducing new dependencies to
 // the project.
 #[cfg(feature = "query_apply")]
@@ -37,3 +93,81 @@ pub(crate) fn nm_supports_accept_all_mac_addresses_mode(
 ) -> Result<bool, NmstateError> {
     Ok(true)
 }
+
+#[cfg(feature = "query_apply")]
+const NM_PLUGIN_LIB_DIRS: [&str; 2] = ["/usr/lib64", "/usr/lib"];
+#[cfg(feature = "query_apply")]
+const NM_DEVICE_PLUGIN_PREFIX: &str = "libnm-device-plugin-";
+#[cfg(feature = "query_apply")]
+const NM_SETTINGS_PLUGIN_PREFIX: &str = "libnm-settings-plugin-";
+// The keyfile settings plugin is built into NetworkManager daemon
+#[cfg(feature = "query_apply")]
+const NM_BUILTIN_SETTINGS_PLUGIN: &str = "keyfile";
+
+#[cfg(feature = "query_apply")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct NmPlugins {
+    pub(crate) device: Vec<String>,
+    pub(crate) settings: Vec<String>,
+}
+
+#[cfg(feature = "query_apply")]
+impl NmPlugins {
+    // NetworkManager only loads plugins from `<libdir>/NetworkManager/<full
+    // version>`, the multiarch libdir like `/usr/lib/x86_64-linux-gnu` is
+    // also searched. Return None if plugin folder not found.
+    pub(crate) fn retrieve(version: &NmVersion) -> Option<Self> {
+        let mut lib_dirs: Vec<std::path::PathBuf> = NM_PLUGIN_LIB_DIRS
+            .iter()
+            .map(std::path::PathBuf::from)
+            .collect();
+        if let Ok(entries) = std::fs::read_dir("/usr/lib") {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().contains("-linux-") {
+                    lib_dirs.push(entry.path());
+                }
+            }
+        }
+        for lib_dir in lib_dirs {
+            let plugin_dir =
+                lib_dir.join("NetworkManager").join(version.to_string());
+            if let Ok(entries) = std::fs::read_dir(&plugin_dir) {
+                log::debug!(
+                    "Found NetworkManager plugin folder {}",
+                    plugin_dir.display()
+                );
+                let file_names: Vec<String> = entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect();
+                return Some(Self::parse(file_names.as_slice()));
+            }
+        }
+        None
+    }
+
+    pub(crate) fn parse(file_names: &[String]) -> Self {
+        let mut ret = Self {
+            device: Vec::new(),
+            settings: vec![NM_BUILTIN_SETTINGS_PLUGIN.to_string()],
+        };
+        for file_name in file_names {
+            let name = match file_name.strip_suffix(".so") {
+                Some(n) => n,
+                None => continue,
+            };
+            if let Some(plugin) = name.strip_prefix(NM_DEVICE_PLUGIN_PREFIX) {
+                ret.device.push(plugin.to_string());
+            } else if let Some(plugin) =
+                name.strip_prefix(NM_SETTINGS_PLUGIN_PREFIX)
+            {
+                ret.settings.push(plugin.to_string());
+            }
+        }
+        ret.device.sort_unstable();
+        ret.device.dedup();
+        ret.settings.sort_unstable();
+        ret.settings.dedup();
+        ret
+    }
+}
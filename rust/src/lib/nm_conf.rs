@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, NmstateError};
+
+const NM_DNS_MODES: [&str; 4] =
+    ["default", "dnsmasq", "systemd-resolved", "none"];
+
+pub(crate) const NM_PROFILE_NAMING_IFACE_NAME: &str = "iface-name";
+pub(crate) const NM_PROFILE_NAMING_NMSTATE_PREFIX: &str = "nmstate-prefix";
+pub(crate) const NM_PROFILE_NAMING_NAME: &str = "{name}";
+pub(crate) const NM_PROFILE_NAMING_TYPE: &str = "{type}";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Global configuration of NetworkManager daemon stored in
+/// `/etc/NetworkManager/conf.d/99-nmstate.conf` which is owned by nmstate.
+/// NetworkManager is reloaded after changed. Options defined in other
+/// configuration files are not shown. The nmstate only options like
+/// `profile-naming` are stored in `/etc/nmstate/networkmanager.yml` instead,
+/// changing them does not reload NetworkManager.
+/// Setting option to empty string or empty list will remove it from
+/// nmstate owned configuration file.
+/// Not supported in memory only mode.
+///
+/// Example yaml output of [crate::NetworkState] with NetworkManager global
+/// configuration:
+/// ```yml
+/// networkmanager:
+///   dns: systemd-resolved
+///   connectivity-uri: http://check.example.org/
+///   no-auto-default:
+///   - eth1
+///   - mac:00:23:45:67:89:ab
+///   profile-naming: site-{type}-{name}
+/// ```
+pub struct NetworkManagerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// DNS processing mode, stored as `dns` of `[main]` section.
+    /// Supported values are `default`, `dnsmasq`, `systemd-resolved` and
+    /// `none`.
+    pub dns: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// URI for checking internet connectivity, stored as `uri` of
+    /// `[connectivity]` section.
+    pub connectivity_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Devices for which NetworkManager should not create default wired
+    /// connection, stored as `no-auto-default` of `[main]` section.
+    /// Please refer to `NetworkManager.conf(5)` for supported format of
+    /// device specification.
+    pub no_auto_default: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Naming convention of NetworkManager connections created by nmstate,
+    /// stored in `/etc/nmstate/networkmanager.yml` as it is not a
+    /// NetworkManager option. Existing connections are not renamed.
+    /// Supported values are:
+    ///  * `iface-name`: Use interface name, the default.
+    ///  * `nmstate-prefix`: Use `nmstate-<iface>`.
+    ///  * Custom template containing `{name}` and optionally `{type}`, for
+    ///    example `site-{type}-{name}`.
+    ///
+    /// The interface name used above has `-br`, `-port` and `-if` suffix for
+    /// OVS bridge, OVS port and OVS interface respectively.
+    pub profile_naming: Option<String>,
+}
+
+impl NetworkManagerConfig {
+    pub fn is_none(&self) -> bool {
+        self.dns.is_none()
+            && self.connectivity_uri.is_none()
+            && self.no_auto_default.is_none()
+            && self.profile_naming.is_none()
+    }
+
+    fn validate(&self) -> Result<(), NmstateError> {
+        if let Some(dns) = self.dns.as_deref() {
+            if !dns.is_empty() && !NM_DNS_MODES.contains(&dns) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Unsupported networkmanager dns mode {dns}, \
+                        supported: {}",
+                        NM_DNS_MODES.join(", ")
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if let Some(uri) = self.connectivity_uri.as_deref() {
+            if uri.contains(char::is_whitespace) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid networkmanager connectivity-uri '{uri}': \
+                        whitespace is not allowed"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        for spec in self.no_auto_default.as_deref().unwrap_or_default() {
+            if spec.is_empty()
+                || spec.contains(',')
+                || spec.contains(char::is_whitespace)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid device specification '{spec}' in \
+                        networkmanager no-auto-default"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if let Some(naming) = self.profile_naming.as_deref() {
+            if !naming.is_empty() && !is_valid_profile_naming(naming) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid networkmanager profile-naming '{naming}', \
+                        supported: {NM_PROFILE_NAMING_IFACE_NAME}, \
+                        {NM_PROFILE_NAMING_NMSTATE_PREFIX} or template \
+                        containing {NM_PROFILE_NAMING_NAME} and optionally \
+                        {NM_PROFILE_NAMING_TYPE}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // Overlay the other on top of self with empty string or empty list
+    // removing the option.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        if let Some(dns) = other.dns.as_ref() {
+            self.dns = if dns.is_empty() {
+                None
+            } else {
+                Some(dns.clone())
+            };
+        }
+        if let Some(uri) = other.connectivity_uri.as_ref() {
+            self.connectivity_uri = if uri.is_empty() {
+                None
+            } else {
+                Some(uri.clone())
+            };
+        }
+        if let Some(specs) = other.no_auto_default.as_ref() {
+            self.no_auto_default = if specs.is_empty() {
+                None
+            } else {
+                Some(specs.clone())
+            };
+        }
+        if let Some(naming) = other.profile_naming.as_ref() {
+            self.profile_naming = if naming.is_empty() {
+                None
+            } else {
+                Some(naming.clone())
+            };
+        }
+    }
+}
+
+// The template should contain the interface name to avoid name conflict,
+// the leading and trailing whitespace is not allowed as it will be lost in
+// configuration file.
+fn is_valid_profile_naming(naming: &str) -> bool {
+    if [
+        NM_PROFILE_NAMING_IFACE_NAME,
+        NM_PROFILE_NAMING_NMSTATE_PREFIX,
+    ]
+    .contains(&naming)
+    {
+        return true;
+    }
+    naming.contains(NM_PROFILE_NAMING_NAME)
+        && naming.trim() == naming
+        && !naming.contains(char::is_control)
+        && !naming
+            .replace(NM_PROFILE_NAMING_NAME, "")
+            .replace(NM_PROFILE_NAMING_TYPE, "")
+            .contains(['{', '}'])
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct MergedNetworkManagerConfig {
+    pub(crate) desired: Option<NetworkManagerConfig>,
+    pub(crate) current: Option<NetworkManagerConfig>,
+}
+
+impl MergedNetworkManagerConfig {
+    pub(crate) fn new(
+        desired: Option<NetworkManagerConfig>,
+        current: Option<NetworkManagerConfig>,
+        memory_only: bool,
+    ) -> Result<Self, NmstateError> {
+        if let Some(desired) = desired.as_ref() {
+            if memory_only && !desired.is_none() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    "The networkmanager section is not supported in memory \
+                    only mode"
+                        .to_string(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            desired.validate()?;
+        }
+        Ok(Self { desired, current })
+    }
+
+    // The full configuration to store, None means no change required.
+    pub(crate) fn for_apply(&self) -> Option<NetworkManagerConfig> {
+        let desired = self.desired.as_ref()?;
+        let current = self.current.clone().unwrap_or_default();
+        let mut ret = current.clone();
+        ret.merge(desired);
+        if ret == current {
+            None
+        } else {
+            Some(ret)
+        }
+    }
+
+    // The profile naming after desired configuration applied, None means
+    // using interface name.
+    pub(crate) fn profile_naming(&self) -> Option<&str> {
+        match self
+            .desired
+            .as_ref()
+            .and_then(|d| d.profile_naming.as_deref())
+        {
+            Some(naming) => Some(naming),
+            None => self
+                .current
+                .as_ref()
+                .and_then(|c| c.profile_naming.as_deref()),
+        }
+        .filter(|n| !n.is_empty())
+    }
+}
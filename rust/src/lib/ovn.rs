@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ErrorKind, InterfaceType, MergedInterfaces, NmstateError, OvsDbGlobalConfig,
+};
+
+const OVN_BRIDGE_MAPPINGS: &str = "ovn-bridge-mappings";
+const OVN_ENCAP_IP: &str = "ovn-encap-ip";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// OVN chassis configuration stored in `external_ids` of OpenvSwitch
+/// database global configuration. Nmstate validates the referenced OVS
+/// bridges exist in the same state.
+///
+/// Example yaml output of [crate::NetworkState] with OVN configuration:
+/// ```yml
+/// ovn:
+///   bridge-mappings:
+///   - localnet: physnet1
+///     bridge: br-ex
+///   encap-ip: 192.0.2.1
+/// ```
+pub struct OvnConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Mappings from OVN logical network name to OVS bridge, stored as
+    /// `ovn-bridge-mappings` external ID. Mappings not mentioned are
+    /// preserved. Set to empty list to remove all mappings.
+    pub bridge_mappings: Option<Vec<OvnBridgeMapping>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Tunnel endpoint IP address of this chassis, stored as
+    /// `ovn-encap-ip` external ID.
+    pub encap_ip: Option<IpAddr>,
+}
+
+impl OvnConfiguration {
+    pub fn is_none(&self) -> bool {
+        self.bridge_mappings.is_none() && self.encap_ip.is_none()
+    }
+
+    pub(crate) fn from_ovsdb(ovsdb: &OvsDbGlobalConfig) -> Self {
+        let mut ret = Self::default();
+        if let Some(eids) = ovsdb.external_ids.as_ref() {
+            if let Some(Some(mappings)) = eids.get(OVN_BRIDGE_MAPPINGS) {
+                ret.bridge_mappings = Some(
+                    parse_bridge_mappings(mappings)
+                        .into_iter()
+                        .map(|(localnet, bridge)| OvnBridgeMapping {
+                            localnet,
+                            bridge: Some(bridge),
+                            state: None,
+                        })
+                        .collect(),
+                );
+            }
+            if let Some(Some(ip)) = eids.get(OVN_ENCAP_IP) {
+                ret.encap_ip = IpAddr::from_str(ip).ok();
+            }
+        }
+        ret
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct OvnBridgeMapping {
+    /// OVN logical network name.
+    pub localnet: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// OVS bridge name, required unless `state: absent`.
+    pub bridge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Default is [OvnBridgeMappingState::Present].
+    pub state: Option<OvnBridgeMappingState>,
+}
+
+impl OvnBridgeMapping {
+    fn is_absent(&self) -> bool {
+        self.state == Some(OvnBridgeMappingState::Absent)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum OvnBridgeMappingState {
+    /// Add or modify the mapping.
+    Present,
+    /// Remove the mapping of specified `localnet`.
+    Absent,
+}
+
+// Fold desired OVN configuration into `external_ids` of desired OVSDB global
+// configuration, so it is applied and verified along with OVSDB.
+pub(crate) fn ovn_to_ovsdb(
+    desired_ovn: &OvnConfiguration,
+    desired_ovsdb: &mut OvsDbGlobalConfig,
+    current_ovsdb: &OvsDbGlobalConfig,
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NmstateError> {
+    let mut eids: HashMap<String, Option<String>> = HashMap::new();
+    if let Some(mappings) = desired_ovn.bridge_mappings.as_ref() {
+        let mut new_mappings: BTreeMap<String, String> = if mappings.is_empty()
+        {
+            BTreeMap::new()
+        } else {
+            current_ovsdb
+                .external_ids
+                .as_ref()
+                .and_then(|e| e.get(OVN_BRIDGE_MAPPINGS))
+                .and_then(|m| m.as_deref())
+                .map(parse_bridge_mappings)
+                .unwrap_or_default()
+        };
+        for mapping in mappings {
+            if mapping.is_absent() {
+                new_mappings.remove(&mapping.localnet);
+                continue;
+            }
+            let bridge = match mapping.bridge.as_deref() {
+                Some(b) => b,
+                None => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "OVN bridge mapping of localnet {} has no bridge \
+                            defined",
+                            mapping.localnet
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            };
+            if !merged_ifaces
+                .user_ifaces
+                .get(&(bridge.to_string(), InterfaceType::OvsBridge))
+                .map(|i| !i.merged.is_absent())
+                .unwrap_or_default()
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "OVN bridge mapping of localnet {} is referring to \
+                        OVS bridge {bridge} which does not exist",
+                        mapping.localnet
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            new_mappings.insert(mapping.localnet.clone(), bridge.to_string());
+        }
+        eids.insert(
+            OVN_BRIDGE_MAPPINGS.to_string(),
+            if new_mappings.is_empty() {
+                None
+            } else {
+                Some(
+                    new_mappings
+                        .iter()
+                        .map(|(l, b)| format!("{l}:{b}"))
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )
+            },
+        );
+    }
+    if let Some(ip) = desired_ovn.encap_ip.as_ref() {
+        eids.insert(OVN_ENCAP_IP.to_string(), Some(ip.to_string()));
+    }
+
+    if desired_ovsdb.external_ids.is_none() {
+        desired_ovsdb.prop_list.push("external_ids");
+    }
+    let desired_eids =
+        desired_ovsdb.external_ids.get_or_insert_with(HashMap::new);
+    for (k, v) in eids {
+        if desired_eids.contains_key(&k) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The {k} external ID of ovs-db is managed by ovn \
+                    section, please do not define it in both places"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        desired_eids.insert(k, v);
+    }
+    Ok(())
+}
+
+// The `ovn-bridge-mappings` is in the format of
+// `physnet1:br-ex,physnet2:br-2`.
+fn parse_bridge_mappings(mappings: &str) -> BTreeMap<String, String> {
+    mappings
+        .split(',')
+        .filter_map(|m| m.trim().split_once(':'))
+        .map(|(l, b)| (l.to_string(), b.to_string()))
+        .collect()
+}
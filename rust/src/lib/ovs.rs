@@ -7,13 +7,19 @@ use serde::{Deserialize, Deserializer, Serialize};
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
 #[non_exhaustive]
 pub struct OvsDbGlobalConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::serializer::option_map_sorted"
+    )]
     // When the value been set as None, specified key will be removed instead
     // of merging.
     // To remove all settings of external_ids or other_config, use empty
     // HashMap
     pub external_ids: Option<HashMap<String, Option<String>>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::serializer::option_map_sorted"
+    )]
     pub other_config: Option<HashMap<String, Option<String>>>,
     #[serde(skip)]
     pub(crate) prop_list: Vec<&'static str>,
@@ -53,9 +59,15 @@ impl<'de> Deserialize<'de> for OvsDbGlobalConfig {
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
 #[non_exhaustive]
 pub struct OvsDbIfaceConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::serializer::option_map_sorted"
+    )]
     pub external_ids: Option<HashMap<String, Option<String>>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::serializer::option_map_sorted"
+    )]
     /// OpenvSwitch specific `other_config`. Please refer to
     /// manpage `ovs-vswitchd.conf.db(5)` for more detail.
     /// When setting to None, nmstate will try to preserve current
@@ -8,7 +8,7 @@ use serde_json::Value;
 use crate::{
     BridgePortTunkTag, BridgePortVlanConfig, BridgePortVlanMode,
     BridgePortVlanRange, Interface, InterfaceType, Interfaces, NetworkState,
-    NmstateError, OvsBridgeBondConfig, OvsBridgeBondMode,
+    NmstateError, OvnConfiguration, OvsBridgeBondConfig, OvsBridgeBondMode,
     OvsBridgeBondPortConfig, OvsBridgeConfig, OvsBridgeInterface,
     OvsBridgeOptions, OvsBridgePortConfig, OvsDbIfaceConfig, OvsDpdkConfig,
     OvsInterface, OvsPatchConfig, UnknownInterface,
@@ -72,6 +72,10 @@ pub(crate) fn ovsdb_retrieve() -> Result<NetworkState, NmstateError> {
     }
 
     ret.ovsdb = cli.get_ovsdb_global_conf()?;
+    ret.ovn = OvnConfiguration::from_ovsdb(&ret.ovsdb);
+    if !ret.ovn.is_none() {
+        ret.prop_list.push("ovn");
+    }
 
     Ok(ret)
 }
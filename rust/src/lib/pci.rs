@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Kernel driver binding of PCI device, for example binding NIC to
+/// `vfio-pci` for DPDK. Once bound to `vfio-pci`, the network interface of
+/// the NIC is removed by kernel, hence the PCI device is identified by its
+/// PCI address instead of interface name.
+/// The binding is persisted in the format of `driverctl` to
+/// `/etc/driverctl.d/pci-<pci_address>` which requires `driverctl` to be
+/// installed to restore the binding after reboot.
+/// Persistence is skipped in memory only and kernel only mode.
+///
+/// Only PCI devices with driver override are shown in query.
+///
+/// Example yaml output of [crate::NetworkState] with PCI device bound to
+/// `vfio-pci`:
+/// ```yml
+/// pci-devices:
+/// - pci-address: 0000:3b:00.1
+///   driver-override: vfio-pci
+///   driver: vfio-pci
+/// ```
+pub struct PciDeviceConfig {
+    /// PCI address in the format of `<domain>:<bus>:<device>.<function>`,
+    /// for example `0000:3b:00.1`.
+    pub pci_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Kernel driver forced to bind. Setting to empty string will remove
+    /// the override and return the device to its default kernel driver.
+    pub driver_override: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. Kernel driver currently bound to this device.
+    /// Ignored when applying.
+    pub driver: Option<String>,
+}
+
+impl PciDeviceConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Lower case the PCI address and remove query only property
+    fn sanitize(&mut self) -> Result<(), NmstateError> {
+        self.pci_address.make_ascii_lowercase();
+        if !is_valid_pci_address(self.pci_address.as_str()) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid PCI address '{}', correct format is \
+                    '<domain>:<bus>:<device>.<function>', for example \
+                    '0000:3b:00.1'",
+                    self.pci_address
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(driver) = self.driver_override.as_deref() {
+            if driver.contains(|c: char| c.is_whitespace() || c == '/') {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid driver-override '{driver}' for PCI device {}",
+                        self.pci_address
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        self.driver = None;
+        Ok(())
+    }
+}
+
+// The format is `DDDD:BB:DD.F` in hex
+pub(crate) fn is_valid_pci_address(address: &str) -> bool {
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit())
+    };
+    let parts: Vec<&str> = address.split(':').collect();
+    if let [domain, bus, dev_fn] = parts.as_slice() {
+        if let Some((dev, func)) = dev_fn.split_once('.') {
+            return is_hex(domain, 4)
+                && is_hex(bus, 2)
+                && is_hex(dev, 2)
+                && is_hex(func, 1);
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct MergedPciDevices {
+    pub(crate) desired: Vec<PciDeviceConfig>,
+    pub(crate) current: Vec<PciDeviceConfig>,
+}
+
+impl MergedPciDevices {
+    pub(crate) fn new(
+        desired: Option<Vec<PciDeviceConfig>>,
+        current: Option<Vec<PciDeviceConfig>>,
+    ) -> Result<Self, NmstateError> {
+        let mut desired = desired.unwrap_or_default();
+        for dev in desired.iter_mut() {
+            dev.sanitize()?;
+        }
+        for (i, dev) in desired.iter().enumerate() {
+            if desired[..i]
+                .iter()
+                .any(|d| d.pci_address == dev.pci_address)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "PCI device {} is defined more than once",
+                        dev.pci_address
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(Self {
+            desired,
+            current: current.unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn get_current(
+        &self,
+        pci_address: &str,
+    ) -> Option<&PciDeviceConfig> {
+        self.current.iter().find(|c| c.pci_address == pci_address)
+    }
+
+    // Return PCI devices with driver override defined
+    pub(crate) fn for_apply(&self) -> Vec<&PciDeviceConfig> {
+        self.desired
+            .iter()
+            .filter(|d| d.driver_override.is_some())
+            .collect()
+    }
+}
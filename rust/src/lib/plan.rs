@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::InterfaceType;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Actions planned for applying a desired state without touching the system,
+/// generated by [crate::NetworkState::gen_plan()].
+///
+/// Each changed interface is classified by the disruption risk of the
+/// action, so user can judge whether a maintenance window is required before
+/// applying.
+pub struct NetworkPlan {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub interfaces: Vec<InterfacePlan>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct InterfacePlan {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub iface_type: InterfaceType,
+    pub risk: ChangeRisk,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Disruption risk of planned action, ordered from least to most disruptive.
+pub enum ChangeRisk {
+    /// Changes will be reapplied to the interface without link down.
+    Reapply,
+    /// Link might go down briefly, for example activating a new interface or
+    /// the reapply failed and fallback to activation.
+    Flap,
+    /// Interface will be deactivated and then activated again.
+    Reactivate,
+    /// Interface will be deleted or deactivated.
+    Delete,
+}
+
+impl std::fmt::Display for ChangeRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Reapply => "reapply",
+                Self::Flap => "flap",
+                Self::Reactivate => "reactivate",
+                Self::Delete => "delete",
+            }
+        )
+    }
+}
+
+impl NetworkPlan {
+    pub fn is_empty(&self) -> bool {
+        self.interfaces.is_empty()
+    }
+
+    /// The most disruptive risk among all planned actions.
+    pub fn max_risk(&self) -> Option<ChangeRisk> {
+        self.interfaces.iter().map(|i| i.risk).max()
+    }
+}
+
+#[cfg(feature = "query_apply")]
+impl NetworkPlan {
+    // Backend neutral classification:
+    //  * Absent or down interface is `Delete`.
+    //  * Interface not up yet is `Flap`.
+    //  * Other changes are `Reapply`.
+    pub(crate) fn new(merged_state: &crate::MergedNetworkState) -> Self {
+        let mut ret = Self::default();
+        for merged_iface in
+            merged_state.interfaces.iter().filter(|i| i.is_changed())
+        {
+            let iface = &merged_iface.merged;
+            let cur_is_up =
+                merged_iface.current.as_ref().map(|i| i.is_up()) == Some(true);
+            let risk = if iface.is_absent() || iface.is_down() {
+                if merged_iface.current.is_none() {
+                    continue;
+                }
+                ChangeRisk::Delete
+            } else if cur_is_up {
+                ChangeRisk::Reapply
+            } else {
+                ChangeRisk::Flap
+            };
+            ret.push(iface.name(), iface.iface_type(), risk);
+        }
+        ret.sort();
+        ret
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        name: &str,
+        iface_type: InterfaceType,
+        risk: ChangeRisk,
+    ) {
+        self.interfaces.push(InterfacePlan {
+            name: name.to_string(),
+            iface_type,
+            risk,
+        });
+    }
+
+    // Most disruptive action first
+    pub(crate) fn sort(&mut self) {
+        self.interfaces.sort_unstable_by(|a, b| {
+            b.risk
+                .cmp(&a.risk)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.iface_type.cmp(&b.iface_type))
+        });
+    }
+}
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nispor::{
+        nispor_apply, nispor_apply_accept_all_mac_addresses,
+        nispor_apply_batadv, nispor_apply_can, nispor_apply_channels,
+        nispor_apply_coalesce_queues, nispor_apply_eee,
+        nispor_apply_eswitch_mode, nispor_apply_fdb, nispor_apply_flow_rules,
+        nispor_apply_gtp, nispor_apply_ipvlan, nispor_apply_l2tpv3,
+        nispor_apply_link_tuning, nispor_apply_mcast_groups,
+        nispor_apply_netkit, nispor_apply_pci_devices,
+        nispor_apply_sit_6rd_prefix, nispor_apply_wol, nispor_apply_xfrm,
+        set_running_hostname,
+    },
+    DnsState, ErrorKind, MergedNetworkState, NetworkPlan, NetworkState,
+    NmstateError,
+};
+
+/// Network backend used by the query and apply engine of [NetworkState].
+///
+/// Unless [NetworkBackend::include_kernel_state()] returns false, the kernel
+/// network state retrieved by nispor is used as the base layer of current
+/// state, the state queried from backend is merged on top of it.
+///
+/// The apply engine invokes backend in the order of:
+///  * [NetworkBackend::prepare()]
+///  * [NetworkBackend::apply()] followed by [NetworkBackend::verify()], both
+///    might be retried on failure.
+///  * [NetworkBackend::commit()] on success or [NetworkBackend::rollback()]
+///    on failure.
+///
+/// Besides the built-in backends chosen by [NetworkState::apply()] and
+/// [NetworkState::retrieve()], custom backend could be used via
+/// [NetworkState::apply_to_backend()] and
+/// [NetworkState::retrieve_from_backend()].
+///
+/// Only available for feature `query_apply`.
+pub trait NetworkBackend {
+    fn name(&self) -> &'static str;
+
+    /// Whether to use kernel network state as base layer of current state.
+    /// When false, the state returned by [NetworkBackend::query()] is used
+    /// as full current state.
+    fn include_kernel_state(&self) -> bool {
+        true
+    }
+
+    /// Query the network state managed by this backend.
+    /// When `unprivileged` is true, information requiring root permission
+    /// should be omitted with warning instead of failing.
+    fn query(
+        &mut self,
+        running_config_only: bool,
+        unprivileged: bool,
+    ) -> Result<NetworkState, NmstateError>;
+
+    /// Query the DNS state only, used by
+    /// [NetworkState::retrieve_section()] to skip full query.
+    fn query_dns(
+        &mut self,
+        _running_config_only: bool,
+    ) -> Result<DnsState, NmstateError> {
+        Ok(DnsState::new())
+    }
+
+    /// Query the configuration persisted by this backend which will be
+    /// activated after reboot, regardless of the live network state.
+    fn query_saved(&mut self) -> Result<NetworkState, NmstateError> {
+        let e = NmstateError::new(
+            ErrorKind::NotSupportedError,
+            format!(
+                "Querying saved configuration is not supported by {} backend",
+                self.name()
+            ),
+        );
+        log::error!("{}", e);
+        Err(e)
+    }
+
+    /// Prepare for applying, e.g. create checkpoint for rollback.
+    fn prepare(&mut self, timeout: u32) -> Result<(), NmstateError>;
+
+    fn apply(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        timeout: u32,
+    ) -> Result<(), NmstateError>;
+
+    /// Verify the current state against the merged state.
+    fn verify(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        current: &NetworkState,
+        _timeout: u32,
+    ) -> Result<(), NmstateError> {
+        merged_state.verify(current)
+    }
+
+    /// Classify the actions required for applying the merged state by
+    /// disruption risk without changing anything.
+    fn plan(
+        &mut self,
+        merged_state: &MergedNetworkState,
+    ) -> Result<NetworkPlan, NmstateError> {
+        Ok(NetworkPlan::new(merged_state))
+    }
+
+    fn commit(&mut self) -> Result<(), NmstateError>;
+
+    fn rollback(&mut self) -> Result<(), NmstateError>;
+
+    /// The checkpoint created by [NetworkBackend::prepare()] and not yet
+    /// committed or rolled back, if backend supports checkpoint.
+    fn checkpoint_path(&self) -> Option<String> {
+        None
+    }
+
+    /// How many times should [NetworkBackend::apply()] be tried.
+    fn apply_retry_count(&self) -> usize {
+        1
+    }
+}
+
+/// Apply network state to kernel directly via nispor without any daemon.
+#[derive(Debug, Default)]
+pub(crate) struct KernelBackend;
+
+impl NetworkBackend for KernelBackend {
+    fn name(&self) -> &'static str {
+        "kernel"
+    }
+
+    // The kernel state is always retrieved as base layer
+    fn query(
+        &mut self,
+        _running_config_only: bool,
+        _unprivileged: bool,
+    ) -> Result<NetworkState, NmstateError> {
+        Ok(NetworkState::new())
+    }
+
+    // TODO: Need checkpoint for kernel only mode
+    fn prepare(&mut self, _timeout: u32) -> Result<(), NmstateError> {
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        _timeout: u32,
+    ) -> Result<(), NmstateError> {
+        // Kernel only mode does not persist PCI driver binding
+        nispor_apply_pci_devices(merged_state, false)?;
+        nispor_apply_eswitch_mode(merged_state)?;
+        nispor_apply_gtp(merged_state)?;
+        nispor_apply_xfrm(merged_state)?;
+        nispor_apply_ipvlan(merged_state)?;
+        nispor_apply_l2tpv3(merged_state)?;
+        nispor_apply_netkit(merged_state)?;
+        nispor_apply_batadv(merged_state)?;
+        nispor_apply_can(merged_state)?;
+        nispor_apply(merged_state)?;
+        nispor_apply_accept_all_mac_addresses(merged_state)?;
+        nispor_apply_fdb(merged_state)?;
+        nispor_apply_mcast_groups(merged_state)?;
+        nispor_apply_link_tuning(merged_state)?;
+        nispor_apply_eee(merged_state)?;
+        nispor_apply_sit_6rd_prefix(merged_state)?;
+        nispor_apply_wol(merged_state)?;
+        nispor_apply_channels(merged_state)?;
+        nispor_apply_coalesce_queues(merged_state)?;
+        nispor_apply_flow_rules(merged_state)?;
+        apply_running_hostname(merged_state)
+    }
+
+    fn commit(&mut self) -> Result<(), NmstateError> {
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), NmstateError> {
+        log::warn!("Rollback is not supported by kernel only mode");
+        Ok(())
+    }
+}
+
+pub(crate) fn apply_running_hostname(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    if let Some(running_hostname) = merged_state
+        .hostname
+        .desired
+        .as_ref()
+        .and_then(|c| c.running.as_ref())
+    {
+        set_running_hostname(running_hostname)?;
+    }
+    Ok(())
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BaseInterface, InterfaceType, OvsDbIfaceConfig};
+use std::collections::HashMap;
+
+use crate::{BaseInterface, InterfaceType, MultiConnect, OvsDbIfaceConfig};
 
 impl BaseInterface {
     pub(crate) fn sanitize_current_for_verify(&mut self) {
@@ -20,9 +22,26 @@ impl BaseInterface {
         if self.ovsdb.is_none() {
             self.ovsdb = Some(OvsDbIfaceConfig::new_empty());
         }
+        // multicast_groups None equal to empty
+        if self.multicast_groups.is_none() {
+            self.multicast_groups = Some(Vec::new());
+        }
+        // permissions None equal to empty
+        if self.permissions.is_none() {
+            self.permissions = Some(Vec::new());
+        }
+        // labels None equal to empty
+        let metadata = self.metadata.get_or_insert_with(Default::default);
+        if metadata.labels.is_none() {
+            metadata.labels = Some(HashMap::new());
+        }
     }
 
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        // NetworkManager does not store default value of multi-connect
+        if self.multi_connect == Some(MultiConnect::Default) {
+            self.multi_connect = None;
+        }
         if let Some(ipv4_conf) = self.ipv4.as_mut() {
             ipv4_conf.sanitize_desired_for_verify();
         }
@@ -32,6 +51,9 @@ impl BaseInterface {
         if let Some(mptcp_conf) = self.mptcp.as_mut() {
             mptcp_conf.sanitize_desired_for_verify();
         }
+        if let Some(ieee8021x_conf) = self.ieee8021x.as_mut() {
+            ieee8021x_conf.sanitize_desired_for_verify();
+        }
     }
 
     pub(crate) fn update(&mut self, other: &BaseInterface) {
@@ -58,6 +80,18 @@ impl BaseInterface {
         if other.prop_list.contains(&"max_mtu") {
             self.max_mtu = other.max_mtu;
         }
+        if other.prop_list.contains(&"link_stats") {
+            self.link_stats = other.link_stats.clone();
+        }
+        if other.prop_list.contains(&"pci_hints") {
+            self.pci_hints = other.pci_hints.clone();
+        }
+        if other.prop_list.contains(&"connectivity") {
+            self.connectivity = other.connectivity.clone();
+        }
+        if other.prop_list.contains(&"multicast_groups") {
+            self.multicast_groups = other.multicast_groups.clone();
+        }
         if other.prop_list.contains(&"mac_address") {
             self.mac_address = other.mac_address.clone();
         }
@@ -91,6 +125,18 @@ impl BaseInterface {
         if other.prop_list.contains(&"wait_ip") {
             self.wait_ip = other.wait_ip;
         }
+        if other.prop_list.contains(&"multi_connect") {
+            self.multi_connect = other.multi_connect;
+        }
+        if other.prop_list.contains(&"permissions") {
+            self.permissions = other.permissions.clone();
+        }
+        if other.prop_list.contains(&"metadata") {
+            self.metadata = other.metadata.clone();
+        }
+        if other.prop_list.contains(&"profile_uuid") {
+            self.profile_uuid = other.profile_uuid.clone();
+        }
 
         if other.prop_list.contains(&"ipv4") {
             if let Some(ref other_ipv4) = other.ipv4 {
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BatadvConfig, BatadvInterface};
+
+impl BatadvInterface {
+    pub(crate) fn update_batadv(&mut self, other: &BatadvInterface) {
+        if let Some(other_conf) = other.batadv.as_ref() {
+            if let Some(self_conf) = self.batadv.as_mut() {
+                self_conf.update(other_conf);
+            } else {
+                self.batadv = other.batadv.clone();
+            }
+        }
+    }
+
+    // The hard interfaces queried from kernel are sorted by name
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(hard_ifaces) =
+            self.batadv.as_mut().and_then(|c| c.hard_ifaces.as_mut())
+        {
+            hard_ifaces.sort_unstable();
+        }
+    }
+}
+
+impl BatadvConfig {
+    fn update(&mut self, other: &Self) {
+        if other.routing_algorithm.is_some() {
+            self.routing_algorithm = other.routing_algorithm;
+        }
+        if other.gw_mode.is_some() {
+            self.gw_mode = other.gw_mode;
+        }
+        if other.hard_ifaces.is_some() {
+            self.hard_ifaces.clone_from(&other.hard_ifaces);
+        }
+    }
+}
@@ -10,6 +10,31 @@ impl BondInterface {
             self.bond = other.bond.clone();
         }
     }
+
+    // Kernel reports bond ports in its own order, sort current ports
+    // configuration following the order of desired ones.
+    pub(crate) fn sort_ports_config_as_desired(&self, current: &mut Self) {
+        let des_port_confs = match self
+            .bond
+            .as_ref()
+            .and_then(|bond_conf| bond_conf.ports_config.as_ref())
+        {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(cur_port_confs) = current
+            .bond
+            .as_mut()
+            .and_then(|bond_conf| bond_conf.ports_config.as_mut())
+        {
+            cur_port_confs.sort_by_key(|cur_port_conf| {
+                des_port_confs
+                    .iter()
+                    .position(|p| p.name == cur_port_conf.name)
+                    .unwrap_or(des_port_confs.len())
+            });
+        }
+    }
 }
 
 impl BondConfig {
@@ -26,6 +51,9 @@ impl BondConfig {
             if let Some(port) = other.port.as_ref() {
                 self.port = Some(port.clone());
             }
+            if let Some(ports_config) = other.ports_config.as_ref() {
+                self.ports_config = Some(ports_config.clone());
+            }
         }
     }
 }
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CanConfig, CanInterface};
+
+impl CanInterface {
+    pub(crate) fn update_can(&mut self, other: &CanInterface) {
+        if let Some(can_conf) = &mut self.can {
+            can_conf.update(other.can.as_ref());
+        } else {
+            self.can = other.can.clone();
+        }
+    }
+}
+
+impl CanConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.bitrate.is_some() {
+                self.bitrate = other.bitrate;
+            }
+            if other.sample_point.is_some() {
+                self.sample_point = other.sample_point;
+            }
+            if other.restart_ms.is_some() {
+                self.restart_ms = other.restart_ms;
+            }
+            if other.fd.is_some() {
+                self.fd = other.fd;
+            }
+            if other.data_bitrate.is_some() {
+                self.data_bitrate = other.data_bitrate;
+            }
+            if other.data_sample_point.is_some() {
+                self.data_sample_point = other.data_sample_point;
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use crate::{
+    nm::{
+        nm_version, NmPlugins, NM_VERSION_GLOBAL_DNS, NM_VERSION_MPTCP,
+        NM_VERSION_VETH,
+    },
+    ovsdb::ovsdb_is_running,
+    NmstateCapabilities, NmstateError,
+};
+
+const KERNEL_VERSION_PATH: &str = "/proc/sys/kernel/osrelease";
+
+// Tuple of kernel feature name and the path only exists when it is available
+const KERNEL_FEATURES: [(&str, &str); 8] = [
+    ("batman-adv", "/sys/module/batman_adv"),
+    ("bonding", "/sys/module/bonding"),
+    ("bridge", "/sys/module/bridge"),
+    ("macsec", "/sys/module/macsec"),
+    ("mptcp", "/proc/sys/net/mptcp"),
+    ("openvswitch", "/sys/module/openvswitch"),
+    ("team", "/sys/module/team"),
+    ("vrf", "/sys/module/vrf"),
+];
+
+/// Probe features supported by the running host.
+/// When NetworkManager is not running, all NetworkManager based features are
+/// reported as unsupported instead of failing.
+/// Only available for feature `query_apply`.
+pub fn capabilities() -> Result<NmstateCapabilities, NmstateError> {
+    let mut ret = NmstateCapabilities::new();
+    ret.nmstate_version = env!("CARGO_PKG_VERSION").to_string();
+    ret.ovs = ovsdb_is_running();
+    ret.kernel_version = std::fs::read_to_string(KERNEL_VERSION_PATH)
+        .ok()
+        .map(|v| v.trim().to_string());
+    ret.kernel_features = KERNEL_FEATURES
+        .iter()
+        .filter(|(_, path)| Path::new(path).exists())
+        .map(|(name, _)| name.to_string())
+        .collect();
+    match nm_version() {
+        Ok(version) => {
+            ret.mptcp = version.is_newer_or_equal(NM_VERSION_MPTCP);
+            ret.global_dns = version.is_newer_or_equal(NM_VERSION_GLOBAL_DNS);
+            ret.veth = version.is_newer_or_equal(NM_VERSION_VETH);
+            if let Some(plugins) = NmPlugins::retrieve(&version) {
+                ret.nm_device_plugins = Some(plugins.device);
+                ret.nm_settings_plugins = Some(plugins.settings);
+            }
+            ret.nm_version = Some(version.to_string());
+        }
+        Err(e) => {
+            log::info!("Failed to retrieve NetworkManager version: {}", e);
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetworkDrift, NetworkState, NmstateError};
+
+impl NetworkDrift {
+    /// Retrieve the difference between live network state and the
+    /// configuration saved in NetworkManager connection profiles.
+    /// Only available for feature `query_apply`.
+    pub fn retrieve() -> Result<Self, NmstateError> {
+        let mut live = NetworkState::new();
+        live.set_running_config_only(true);
+        live.retrieve()?;
+        let mut saved = NetworkState::new();
+        saved.set_saved_config_only(true);
+        saved.retrieve()?;
+        Ok(Self::new(&live, &saved))
+    }
+}
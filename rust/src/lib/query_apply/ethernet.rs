@@ -12,6 +12,12 @@ impl EthernetInterface {
         {
             sriov_conf.sanitize_desired_for_verify();
         }
+        // Kernel only reports the wake-on-lan password with root privilege
+        if let Some(wol_conf) =
+            self.ethernet.as_mut().and_then(|e| e.wake_on_lan.as_mut())
+        {
+            wol_conf.password = None;
+        }
     }
 
     pub(crate) fn sriov_is_enabled(&self) -> bool {
@@ -60,6 +66,9 @@ impl EthernetConfig {
             } else {
                 self.sr_iov = other.sr_iov.clone()
             }
+            if other.wake_on_lan.is_some() {
+                self.wake_on_lan.clone_from(&other.wake_on_lan);
+            }
         }
     }
 }
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{EthtoolQueueCoalesceConfig, Interface};
+
+impl Interface {
+    // Queues holding the same coalescing settings as the device-wide ones are
+    // not included in query result, hence we fill them with device-wide
+    // settings(kernel treat unsupported ones as 0). The undesired queues are
+    // removed from current.
+    pub(crate) fn process_ethtool_coalesce_queues(&self, current: &mut Self) {
+        let des_queues = match self
+            .base_iface()
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.coalesce.as_ref())
+            .and_then(|c| c.queues.as_ref())
+        {
+            Some(q) => q,
+            None => return,
+        };
+        let cur_coalesce = match current
+            .base_iface_mut()
+            .ethtool
+            .as_mut()
+            .and_then(|e| e.coalesce.as_mut())
+        {
+            Some(c) => c,
+            None => return,
+        };
+        let cur_queues = cur_coalesce.queues.take().unwrap_or_default();
+        let mut new_queues = Vec::new();
+        for des_queue in des_queues {
+            new_queues.push(
+                match cur_queues.iter().find(|q| q.queue == des_queue.queue) {
+                    Some(q) => q.clone(),
+                    None => {
+                        let mut q =
+                            EthtoolQueueCoalesceConfig::new(des_queue.queue);
+                        q.rx_usecs = cur_coalesce.rx_usecs.or(Some(0));
+                        q.rx_frames = cur_coalesce.rx_frames.or(Some(0));
+                        q.tx_usecs = cur_coalesce.tx_usecs.or(Some(0));
+                        q.tx_frames = cur_coalesce.tx_frames.or(Some(0));
+                        q
+                    }
+                },
+            );
+        }
+        cur_coalesce.queues = Some(new_queues);
+    }
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GeneveConfig, GeneveInterface};
+
+impl GeneveInterface {
+    pub(crate) fn update_geneve(&mut self, other: &GeneveInterface) {
+        if let Some(geneve_conf) = &mut self.geneve {
+            geneve_conf.update(other.geneve.as_ref());
+        } else {
+            self.geneve = other.geneve.clone();
+        }
+    }
+}
+
+impl GeneveConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            self.id = other.id;
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.dst_port.is_some() {
+                self.dst_port = other.dst_port;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+            if other.tos.is_some() {
+                self.tos = other.tos;
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GreConfig, GreInterface, GreTapInterface};
+
+impl GreInterface {
+    pub(crate) fn update_gre(&mut self, other: &GreInterface) {
+        if let Some(gre_conf) = &mut self.gre {
+            gre_conf.update(other.gre.as_ref());
+        } else {
+            self.gre = other.gre.clone();
+        }
+    }
+}
+
+impl GreTapInterface {
+    pub(crate) fn update_gretap(&mut self, other: &GreTapInterface) {
+        if let Some(gre_conf) = &mut self.gretap {
+            gre_conf.update(other.gretap.as_ref());
+        } else {
+            self.gretap = other.gretap.clone();
+        }
+    }
+}
+
+impl GreConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ikey.is_some() {
+                self.ikey = other.ikey;
+            }
+            if other.okey.is_some() {
+                self.okey = other.okey;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GsmConfig, GsmInterface};
+
+impl GsmInterface {
+    pub(crate) fn update_gsm(&mut self, other: &GsmInterface) {
+        if let Some(gsm_conf) = &mut self.gsm {
+            gsm_conf.update(other.gsm.as_ref());
+        } else {
+            self.gsm = other.gsm.clone();
+        }
+    }
+
+    // The PIN and password might be hidden by nmstate in current state, and
+    // NetworkManager might not be permitted to provide the secrets back.
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(gsm_conf) = self.gsm.as_mut() {
+            gsm_conf.pin = None;
+            gsm_conf.password = None;
+        }
+    }
+}
+
+impl GsmConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.apn.is_some() {
+                self.apn = other.apn.clone();
+            }
+            if other.pin.is_some() {
+                self.pin = other.pin.clone();
+            }
+            if other.username.is_some() {
+                self.username = other.username.clone();
+            }
+            if other.password.is_some() {
+                self.password = other.password.clone();
+            }
+            if other.roaming.is_some() {
+                self.roaming = other.roaming;
+            }
+            if other.bearer.is_some() {
+                self.bearer = other.bearer.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GtpConfig, GtpInterface};
+
+impl GtpInterface {
+    pub(crate) fn update_gtp(&mut self, other: &GtpInterface) {
+        if let Some(gtp_conf) = &mut self.gtp {
+            gtp_conf.update(other.gtp.as_ref());
+        } else {
+            self.gtp = other.gtp.clone();
+        }
+    }
+}
+
+impl GtpConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.role.is_some() {
+                self.role = other.role;
+            }
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.external_teid.is_some() {
+                self.external_teid = other.external_teid;
+            }
+        }
+    }
+}
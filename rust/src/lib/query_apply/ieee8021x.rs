@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::process::Command;
+
+use crate::{ErrorKind, Ieee8021XConfig, NmstateError};
+
+const ENROLLMENT_IFACE_ENV: &str = "NMSTATE_INTERFACE";
+
+impl Ieee8021XConfig {
+    // The enrollment command is not stored by backend.
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        self.enrollment_command = None;
+    }
+
+    // Invoke the enrollment command via `sh -c` with interface name stored
+    // in `NMSTATE_INTERFACE` environment variable.
+    pub(crate) fn run_enrollment_command(
+        &self,
+        iface_name: &str,
+    ) -> Result<(), NmstateError> {
+        let cmd = match self.enrollment_command.as_deref() {
+            Some(c) if !c.is_empty() => c,
+            _ => return Ok(()),
+        };
+        log::info!(
+            "Running 802.1x enrollment command of interface {iface_name}: \
+            {cmd}"
+        );
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .env(ENROLLMENT_IFACE_ENV, iface_name)
+            .output()
+            .map_err(|e| {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to invoke 802.1x enrollment command of \
+                        interface {iface_name}: {e}"
+                    ),
+                );
+                log::error!("{}", e);
+                e
+            })?;
+        if !output.status.success() {
+            let e = NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!(
+                    "802.1x enrollment command of interface {iface_name} \
+                    failed with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
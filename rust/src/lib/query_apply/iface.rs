@@ -23,11 +23,39 @@ impl Interface {
         if let Interface::Ethernet(iface) = self {
             iface.sanitize_desired_for_verify();
         }
+        if let Interface::MacSec(iface) = self {
+            iface.sanitize_desired_for_verify();
+        }
+        if let Interface::Pppoe(iface) = self {
+            iface.sanitize_desired_for_verify();
+        }
+        if let Interface::Wifi(iface) = self {
+            iface.sanitize_desired_for_verify();
+        }
+        if let Interface::Gsm(iface) = self {
+            iface.sanitize_desired_for_verify();
+        }
+        if let Interface::Batadv(iface) = self {
+            iface.sanitize_desired_for_verify();
+        }
     }
 
     pub(crate) fn verify(&self, current: &Self) -> Result<(), NmstateError> {
         let mut current = current.clone();
         self.process_allow_extra_address(&mut current);
+        self.process_ethtool_coalesce_queues(&mut current);
+        if let (
+            Interface::LinuxBridge(des_iface),
+            Interface::LinuxBridge(cur_iface),
+        ) = (self, &mut current)
+        {
+            des_iface.verify_fdb(cur_iface)?;
+        }
+        if let (Interface::Bond(des_iface), Interface::Bond(cur_iface)) =
+            (self, &mut current)
+        {
+            des_iface.sort_ports_config_as_desired(cur_iface);
+        }
 
         let self_value = serde_json::to_value(self)?;
         let current_value = serde_json::to_value(&current)?;
@@ -206,13 +234,236 @@ impl Interface {
                     );
                 }
             }
-            Self::Unknown(_) | Self::Dummy(_) | Self::Loopback(_) => (),
+            Self::WireGuard(iface) => {
+                if let Self::WireGuard(other_iface) = other {
+                    iface.update_wireguard(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::MacSec(iface) => {
+                if let Self::MacSec(other_iface) = other {
+                    iface.update_macsec(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Gre(iface) => {
+                if let Self::Gre(other_iface) = other {
+                    iface.update_gre(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::GreTap(iface) => {
+                if let Self::GreTap(other_iface) = other {
+                    iface.update_gretap(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::IpIp(iface) => {
+                if let Self::IpIp(other_iface) = other {
+                    iface.update_ipip(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Sit(iface) => {
+                if let Self::Sit(other_iface) = other {
+                    iface.update_sit(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Geneve(iface) => {
+                if let Self::Geneve(other_iface) = other {
+                    iface.update_geneve(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Gtp(iface) => {
+                if let Self::Gtp(other_iface) = other {
+                    iface.update_gtp(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Xfrm(iface) => {
+                if let Self::Xfrm(other_iface) = other {
+                    iface.update_xfrm(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Tun(iface) => {
+                if let Self::Tun(other_iface) = other {
+                    iface.update_tun(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Can(iface) => {
+                if let Self::Can(other_iface) = other {
+                    iface.update_can(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Pppoe(iface) => {
+                if let Self::Pppoe(other_iface) = other {
+                    iface.update_pppoe(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Wifi(iface) => {
+                if let Self::Wifi(other_iface) = other {
+                    iface.update_wifi(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Gsm(iface) => {
+                if let Self::Gsm(other_iface) = other {
+                    iface.update_gsm(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Vti(iface) => {
+                if let Self::Vti(other_iface) = other {
+                    iface.update_vti(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Vti6(iface) => {
+                if let Self::Vti6(other_iface) = other {
+                    iface.update_vti6(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::IpVlan(iface) => {
+                if let Self::IpVlan(other_iface) = other {
+                    iface.update_ipvlan(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::L2tpv3(iface) => {
+                if let Self::L2tpv3(other_iface) = other {
+                    iface.update_l2tpv3(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Netkit(iface) => {
+                if let Self::Netkit(other_iface) = other {
+                    iface.update_netkit(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Batadv(iface) => {
+                if let Self::Batadv(other_iface) = other {
+                    iface.update_batadv(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Unknown(_)
+            | Self::Dummy(_)
+            | Self::Loopback(_)
+            | Self::Vcan(_) => (),
         }
     }
 }
 
 impl InterfaceType {
-    pub(crate) const SUPPORTED_LIST: [InterfaceType; 14] = [
+    pub(crate) const SUPPORTED_LIST: [InterfaceType; 35] = [
         InterfaceType::Bond,
         InterfaceType::LinuxBridge,
         InterfaceType::Dummy,
@@ -227,5 +478,26 @@ impl InterfaceType {
         InterfaceType::InfiniBand,
         InterfaceType::Loopback,
         InterfaceType::Vrf,
+        InterfaceType::WireGuard,
+        InterfaceType::MacSec,
+        InterfaceType::Gre,
+        InterfaceType::GreTap,
+        InterfaceType::IpIp,
+        InterfaceType::Sit,
+        InterfaceType::Geneve,
+        InterfaceType::Gtp,
+        InterfaceType::Xfrm,
+        InterfaceType::Tun,
+        InterfaceType::Can,
+        InterfaceType::Vcan,
+        InterfaceType::Pppoe,
+        InterfaceType::Wifi,
+        InterfaceType::Gsm,
+        InterfaceType::Vti,
+        InterfaceType::Vti6,
+        InterfaceType::IpVlan,
+        InterfaceType::L2tpv3,
+        InterfaceType::Netkit,
+        InterfaceType::Batadv,
     ];
 }
@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+use std::net::IpAddr;
+
 use crate::{
-    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    ip::is_ipv6_unicast_link_local, ErrorKind, Interface, InterfaceType,
+    Interfaces, Ipv6LinkLocalVerify, MergedInterface, MergedInterfaces,
     NmstateError,
 };
 
@@ -62,6 +66,20 @@ impl Interfaces {
             iface.base_iface_mut().controller = None;
         }
     }
+
+    pub(crate) fn has_bridge_fdb(&self) -> bool {
+        self.kernel_ifaces.values().any(|iface| {
+            if let Interface::LinuxBridge(br_iface) = iface {
+                br_iface
+                    .bridge
+                    .as_ref()
+                    .and_then(|b| b.fdb.as_ref())
+                    .is_some()
+            } else {
+                false
+            }
+        })
+    }
 }
 
 fn find_unknown_type_port<'a>(
@@ -117,6 +135,11 @@ impl MergedInterfaces {
         let mut current = current.clone();
         current.remove_ignored_ifaces(self.ignored_ifaces.as_slice());
         current.remove_unknown_type_port();
+        let scope = self.get_verify_scope(&current);
+        current.kernel_ifaces.retain(|name, _| scope.contains(name));
+        current
+            .user_ifaces
+            .retain(|(name, _), _| scope.contains(name));
         for iface in current
             .kernel_ifaces
             .values_mut()
@@ -127,6 +150,24 @@ impl MergedInterfaces {
         }
 
         for des_iface in self.iter().filter(|i| i.is_desired()) {
+            for (ctrl_name, port_name) in self.get_detached_ports(des_iface) {
+                if current
+                    .kernel_ifaces
+                    .get(port_name)
+                    .and_then(|p| p.base_iface().controller.as_deref())
+                    == Some(ctrl_name)
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::VerificationError,
+                        format!(
+                            "Interface {port_name} is still attached to \
+                            controller {ctrl_name} after detached"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
             let mut iface = if let Some(i) = des_iface.for_verify.as_ref() {
                 i.clone()
             } else {
@@ -167,4 +208,161 @@ impl MergedInterfaces {
         }
         Ok(())
     }
+
+    // Only desired interfaces and the interfaces they depend on(controller,
+    // ports, parent and SR-IOV VFs) are verified, hence changes to unrelated
+    // interfaces during apply will not fail the verification. The ports
+    // detached from desired controller are included to verify they are
+    // detached.
+    pub(crate) fn get_verify_scope(
+        &self,
+        current: &Interfaces,
+    ) -> HashSet<String> {
+        let mut scope = HashSet::new();
+        for (merged_iface, iface) in self
+            .iter()
+            .filter(|i| i.is_desired())
+            .filter_map(|i| i.for_verify.as_ref().map(|v| (i, v)))
+        {
+            scope.insert(iface.name().to_string());
+            if let Some(ctrl) = iface.base_iface().controller.as_ref() {
+                scope.insert(ctrl.to_string());
+            }
+            if let Some(parent) = iface.parent() {
+                scope.insert(parent.to_string());
+            }
+            for port in iface.ports().unwrap_or_default() {
+                scope.insert(port.to_string());
+            }
+            for (_, port) in self.get_detached_ports(merged_iface) {
+                scope.insert(port.to_string());
+            }
+            let cur_iface =
+                match current.get_iface(iface.name(), iface.iface_type()) {
+                    Some(i) => i,
+                    None => continue,
+                };
+            for port in cur_iface.ports().unwrap_or_default() {
+                scope.insert(port.to_string());
+            }
+            if let Interface::Ethernet(eth_iface) = cur_iface {
+                if let Some(vfs) = eth_iface
+                    .ethernet
+                    .as_ref()
+                    .and_then(|e| e.sr_iov.as_ref())
+                    .and_then(|s| s.vfs.as_ref())
+                {
+                    for vf in vfs {
+                        scope.insert(vf.iface_name.to_string());
+                    }
+                }
+            }
+        }
+        scope
+    }
+
+    // Ports of controller before apply which are not in desired port list,
+    // returned as (controller, port). Ports moved to other controller or
+    // desired with other controller are not included.
+    fn get_detached_ports<'a>(
+        &self,
+        merged_iface: &'a MergedInterface,
+    ) -> Vec<(&'a str, &'a str)> {
+        let des_ports = match merged_iface
+            .for_verify
+            .as_ref()
+            .filter(|i| i.is_up())
+            .and_then(|i| i.ports())
+        {
+            Some(p) => p,
+            None => return Vec::new(),
+        };
+        let ctrl_name = merged_iface.merged.name();
+        merged_iface
+            .current
+            .as_ref()
+            .and_then(|i| i.ports())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|port| !des_ports.contains(port))
+            .filter(|port| {
+                self.kernel_ifaces
+                    .get(*port)
+                    .and_then(|p| p.for_verify.as_ref())
+                    .and_then(|p| p.base_iface().controller.as_deref())
+                    .map(|c| c.is_empty() || c == ctrl_name)
+                    .unwrap_or(true)
+            })
+            .map(|port| (ctrl_name, port))
+            .collect()
+    }
+
+    // Kernel reports IPv6 as disabled when interface holds no IPv6 address,
+    // which happens when driver delays the generation of IPv6 link local
+    // address.
+    pub(crate) fn process_ipv6_link_local_for_verify(
+        &self,
+        current: &mut Interfaces,
+        mode: Ipv6LinkLocalVerify,
+    ) -> Result<(), NmstateError> {
+        for des_iface in self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_desired())
+            .filter_map(|i| i.for_verify.as_ref())
+            .filter(|i| {
+                i.is_up()
+                    && i.base_iface().ipv6.as_ref().map(|i| i.enabled)
+                        == Some(true)
+            })
+        {
+            let cur_ipv6 = match current
+                .kernel_ifaces
+                .get_mut(des_iface.name())
+                .and_then(|i| i.base_iface_mut().ipv6.as_mut())
+            {
+                Some(i) => i,
+                None => continue,
+            };
+            let has_link_local = cur_ipv6
+                .addresses
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .any(|a| match a.ip {
+                    IpAddr::V6(ip) => is_ipv6_unicast_link_local(&ip),
+                    IpAddr::V4(_) => false,
+                });
+            match mode {
+                // Kernel has IPv6 enabled when `addresses` is queried
+                Ipv6LinkLocalVerify::Skip
+                    if !cur_ipv6.enabled
+                        && cur_ipv6.prop_list.contains(&"addresses") =>
+                {
+                    log::debug!(
+                        "Treating interface {} as IPv6 enabled as it is \
+                        still waiting for IPv6 link local address",
+                        des_iface.name()
+                    );
+                    cur_ipv6.enabled = true;
+                }
+                Ipv6LinkLocalVerify::Require
+                    if cur_ipv6.enabled && !has_link_local =>
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::VerificationError,
+                        format!(
+                            "Interface {} has no IPv6 link local \
+                            address yet",
+                            des_iface.name()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
 }
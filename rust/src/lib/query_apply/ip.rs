@@ -11,12 +11,16 @@ impl InterfaceIpv4 {
         }
     }
 
-    // Sort addresses and dedup
+    // * Sort addresses and dedup
+    // * Treat `route-table: 0` as not defined
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         if let Some(addrs) = self.addresses.as_mut() {
             addrs.sort_unstable();
             addrs.dedup();
         }
+        if self.route_table == Some(0) {
+            self.route_table = None;
+        }
     }
     pub(crate) fn update(&mut self, other: &Self) {
         if other.prop_list.contains(&"enabled") {
@@ -50,12 +54,27 @@ impl InterfaceIpv4 {
         if other.prop_list.contains(&"auto_table_id") {
             self.auto_table_id = other.auto_table_id;
         }
+        if other.prop_list.contains(&"route_table") {
+            self.route_table = other.route_table;
+        }
         if other.prop_list.contains(&"allow_extra_address") {
             self.allow_extra_address = other.allow_extra_address;
         }
         if other.prop_list.contains(&"auto_route_metric") {
             self.auto_route_metric = other.auto_route_metric;
         }
+        if other.prop_list.contains(&"exact") {
+            self.exact = other.exact;
+        }
+        if other.prop_list.contains(&"owned_addresses") {
+            self.owned_addresses = other.owned_addresses.clone();
+        }
+        if other.prop_list.contains(&"owned_routes") {
+            self.owned_routes = other.owned_routes.clone();
+        }
+        if other.prop_list.contains(&"dhcp_lease") {
+            self.dhcp_lease = other.dhcp_lease.clone();
+        }
 
         for other_prop_name in &other.prop_list {
             if !self.prop_list.contains(other_prop_name) {
@@ -79,7 +98,8 @@ impl InterfaceIpv6 {
         }
     }
 
-    // Sort addresses and dedup
+    // * Sort addresses and dedup
+    // * Treat `route-table: 0` as not defined
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         if let Some(addrs) = self.addresses.as_mut() {
             addrs.sort_unstable();
@@ -88,6 +108,9 @@ impl InterfaceIpv6 {
                 self.addresses = None;
             }
         }
+        if self.route_table == Some(0) {
+            self.route_table = None;
+        }
     }
     pub(crate) fn update(&mut self, other: &Self) {
         if other.prop_list.contains(&"enabled") {
@@ -120,6 +143,9 @@ impl InterfaceIpv6 {
         if other.prop_list.contains(&"auto_table_id") {
             self.auto_table_id = other.auto_table_id;
         }
+        if other.prop_list.contains(&"route_table") {
+            self.route_table = other.route_table;
+        }
         if other.prop_list.contains(&"dns") {
             self.dns = other.dns.clone();
         }
@@ -135,6 +161,18 @@ impl InterfaceIpv6 {
         if other.prop_list.contains(&"token") {
             self.token = other.token.clone();
         }
+        if other.prop_list.contains(&"exact") {
+            self.exact = other.exact;
+        }
+        if other.prop_list.contains(&"owned_addresses") {
+            self.owned_addresses = other.owned_addresses.clone();
+        }
+        if other.prop_list.contains(&"owned_routes") {
+            self.owned_routes = other.owned_routes.clone();
+        }
+        if other.prop_list.contains(&"dhcp_lease") {
+            self.dhcp_lease = other.dhcp_lease.clone();
+        }
         for other_prop_name in &other.prop_list {
             if !self.prop_list.contains(other_prop_name) {
                 self.prop_list.push(other_prop_name);
@@ -144,8 +182,8 @@ impl InterfaceIpv6 {
 }
 
 impl Interface {
-    // * If `allow_extra_address: true`, remove current IP address if not found
-    //   in desired.
+    // * If `allow_extra_address: true` and `exact` is not true, remove current
+    //   IP address if not found in desired.
     pub(crate) fn process_allow_extra_address(&self, current: &mut Self) {
         if let (Some(des_ip), Some(cur_ip)) = (
             self.base_iface().ipv4.as_ref(),
@@ -154,7 +192,7 @@ impl Interface {
             if let (Some(des_ip_addrs), Some(cur_ip_addrs)) =
                 (des_ip.addresses.as_ref(), cur_ip.addresses.as_mut())
             {
-                if des_ip.allow_extra_address {
+                if des_ip.allow_extra_address && des_ip.exact != Some(true) {
                     cur_ip_addrs.retain(|i| des_ip_addrs.contains(i))
                 }
             }
@@ -166,7 +204,7 @@ impl Interface {
             if let (Some(des_ip_addrs), Some(cur_ip_addrs)) =
                 (des_ip.addresses.as_ref(), cur_ip.addresses.as_mut())
             {
-                if des_ip.allow_extra_address {
+                if des_ip.allow_extra_address && des_ip.exact != Some(true) {
                     cur_ip_addrs.retain(|i| des_ip_addrs.contains(i))
                 }
             }
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{IpIpInterface, IpTunnelConfig, SitInterface};
+
+impl IpIpInterface {
+    pub(crate) fn update_ipip(&mut self, other: &IpIpInterface) {
+        if let Some(tun_conf) = &mut self.ipip {
+            tun_conf.update(other.ipip.as_ref());
+        } else {
+            self.ipip = other.ipip.clone();
+        }
+    }
+}
+
+impl SitInterface {
+    pub(crate) fn update_sit(&mut self, other: &SitInterface) {
+        if let Some(tun_conf) = &mut self.sit {
+            tun_conf.update(other.sit.as_ref());
+        } else {
+            self.sit = other.sit.clone();
+        }
+    }
+}
+
+impl IpTunnelConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+            if other.pmtudisc.is_some() {
+                self.pmtudisc = other.pmtudisc;
+            }
+            if other.sixrd_prefix.is_some() {
+                self.sixrd_prefix = other.sixrd_prefix.clone();
+            }
+        }
+    }
+}
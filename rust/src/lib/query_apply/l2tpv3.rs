@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::L2tpv3Interface;
+
+impl L2tpv3Interface {
+    // The tunnel and session of L2TPv3 pseudowire cannot be modified, hence
+    // we do not merge the L2TPv3 configuration.
+    pub(crate) fn update_l2tpv3(&mut self, other: &L2tpv3Interface) {
+        if other.l2tpv3.is_some() {
+            self.l2tpv3.clone_from(&other.l2tpv3);
+        }
+    }
+}
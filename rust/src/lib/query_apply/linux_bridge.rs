@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BridgePortVlanConfig, LinuxBridgeConfig, LinuxBridgeInterface};
+use crate::{
+    BridgePortVlanConfig, ErrorKind, LinuxBridgeConfig, LinuxBridgeInterface,
+    NmstateError,
+};
 
 impl LinuxBridgeInterface {
     pub(crate) const INTEGER_ROUNDED_OPTIONS: [&'static str; 5] = [
@@ -31,6 +34,58 @@ impl LinuxBridgeInterface {
         }
     }
 
+    // Current FDB also contains learned entries and static entries not
+    // mentioned in desired, hence we only check desired entries here and
+    // copy desired FDB to current for the remaining generic verification.
+    pub(crate) fn verify_fdb(
+        &self,
+        current: &mut Self,
+    ) -> Result<(), NmstateError> {
+        let des_entries =
+            match self.bridge.as_ref().and_then(|b| b.fdb.as_ref()) {
+                Some(e) => e,
+                None => return Ok(()),
+            };
+        let cur_entries = current
+            .bridge
+            .as_ref()
+            .and_then(|b| b.fdb.as_deref())
+            .unwrap_or_default();
+        for des_entry in des_entries {
+            let found = cur_entries.iter().any(|cur_entry| {
+                des_entry.is_match(cur_entry) && cur_entry.learned != Some(true)
+            });
+            if found == des_entry.is_absent() {
+                let e = NmstateError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification failure: FDB entry {} port {}{}{} \
+                        of linux bridge {} {}",
+                        des_entry.mac_address,
+                        des_entry.port,
+                        des_entry
+                            .vlan
+                            .map(|v| format!(" vlan {v}"))
+                            .unwrap_or_default(),
+                        des_entry
+                            .remote
+                            .as_ref()
+                            .map(|r| format!(" remote {r}"))
+                            .unwrap_or_default(),
+                        self.base.name,
+                        if found { "still exists" } else { "not found" }
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if let Some(br_conf) = current.bridge.as_mut() {
+            br_conf.fdb = Some(des_entries.clone());
+        }
+        Ok(())
+    }
+
     pub(crate) fn update_bridge(&mut self, other: &LinuxBridgeInterface) {
         if let Some(br_conf) = &mut self.bridge {
             br_conf.update(other.bridge.as_ref());
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MacSecConfig, MacSecInterface};
+
+impl MacSecInterface {
+    pub(crate) fn update_macsec(&mut self, other: &MacSecInterface) {
+        if let Some(macsec_conf) = &mut self.macsec {
+            macsec_conf.update(other.macsec.as_ref());
+        } else {
+            self.macsec = other.macsec.clone();
+        }
+    }
+
+    // The MKA keys are only stored in NetworkManager profile for
+    // wpa_supplicant, kernel has no knowledge of them.
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(macsec_conf) = self.macsec.as_mut() {
+            macsec_conf.mka_cak = None;
+            macsec_conf.mka_ckn = None;
+        }
+    }
+}
+
+impl MacSecConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if !other.base_iface.is_empty() {
+                self.base_iface = other.base_iface.clone();
+            }
+            if other.encrypt.is_some() {
+                self.encrypt = other.encrypt;
+            }
+            if other.port.is_some() {
+                self.port = other.port;
+            }
+            if other.validation.is_some() {
+                self.validation = other.validation;
+            }
+            if other.send_sci.is_some() {
+                self.send_sci = other.send_sci;
+            }
+            if other.mka_cak.is_some() {
+                self.mka_cak = other.mka_cak.clone();
+            }
+            if other.mka_ckn.is_some() {
+                self.mka_ckn = other.mka_ckn.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::backend::NetworkBackend;
+use crate::{
+    DnsClientState, DnsState, ErrorKind, HostNameState, Interfaces,
+    MergedNetworkState, NetworkPlan, NetworkState, NmstateError,
+    OvsDbGlobalConfig, RouteRuleEntry, Routes,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Phase of [MockBackend] which could be configured to fail via
+/// [MockBackend::set_failure()].
+pub enum MockPhase {
+    Query,
+    Prepare,
+    Apply,
+    Verify,
+    Commit,
+    Rollback,
+}
+
+impl std::fmt::Display for MockPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Query => "query",
+                Self::Prepare => "prepare",
+                Self::Apply => "apply",
+                Self::Verify => "verify",
+                Self::Commit => "commit",
+                Self::Rollback => "rollback",
+            }
+        )
+    }
+}
+
+/// In-memory network backend for testing applications embedding nmstate
+/// without touching the network of the host.
+///
+/// The mock backend holds a [NetworkState] as current state. Applying
+/// desired state via [NetworkState::apply_to_mock()] merges the desired
+/// state into it with the same validation, verification, commit and
+/// rollback semantics of real backends. Failure could be injected at chosen
+/// [MockPhase].
+///
+/// Only available for feature `mock`.
+///
+/// Example:
+/// ```rust
+/// use nmstate::{ErrorKind, MockBackend, MockPhase, NetworkState};
+///
+/// let mut mock = MockBackend::new(NetworkState::new());
+/// let desired: NetworkState = serde_yaml::from_str(
+///     r#"---
+///     interfaces:
+///     - name: dummy1
+///       type: dummy
+///       state: up
+///     "#,
+/// )
+/// .unwrap();
+/// desired.apply_to_mock(&mut mock).unwrap();
+/// let cur_ifaces = &mock.state().interfaces;
+/// assert!(cur_ifaces.get_iface("dummy1", "dummy".into()).is_some());
+///
+/// mock.set_failure(MockPhase::Apply, ErrorKind::PluginFailure);
+/// assert!(desired.apply_to_mock(&mut mock).is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockBackend {
+    state: NetworkState,
+    checkpoint: Option<NetworkState>,
+    failure: Option<(MockPhase, ErrorKind)>,
+}
+
+impl MockBackend {
+    /// Create mock backend using specified state as current network state.
+    pub fn new(mut state: NetworkState) -> Self {
+        state.prop_list =
+            vec!["hostname", "interfaces", "routes", "rules", "dns", "ovsdb"];
+        Self {
+            state,
+            checkpoint: None,
+            failure: None,
+        }
+    }
+
+    /// Current network state held by mock backend.
+    pub fn state(&self) -> &NetworkState {
+        &self.state
+    }
+
+    /// Fail the specified phase with error of specified kind until
+    /// [MockBackend::clear_failure()] is invoked.
+    pub fn set_failure(&mut self, phase: MockPhase, kind: ErrorKind) {
+        self.failure = Some((phase, kind));
+    }
+
+    pub fn clear_failure(&mut self) {
+        self.failure = None;
+    }
+
+    fn check_failure(&self, phase: MockPhase) -> Result<(), NmstateError> {
+        match self.failure {
+            Some((fail_phase, kind)) if fail_phase == phase => {
+                let e = NmstateError::new(
+                    kind,
+                    format!("Mock backend failure injected at {phase} phase"),
+                );
+                log::error!("{}", e);
+                Err(e)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl NetworkBackend for MockBackend {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn include_kernel_state(&self) -> bool {
+        false
+    }
+
+    fn query(
+        &mut self,
+        _running_config_only: bool,
+        _unprivileged: bool,
+    ) -> Result<NetworkState, NmstateError> {
+        self.check_failure(MockPhase::Query)?;
+        Ok(self.state.clone())
+    }
+
+    // Mock backend has no difference between saved and running state
+    fn query_saved(&mut self) -> Result<NetworkState, NmstateError> {
+        self.check_failure(MockPhase::Query)?;
+        Ok(self.state.clone())
+    }
+
+    fn prepare(&mut self, _timeout: u32) -> Result<(), NmstateError> {
+        self.check_failure(MockPhase::Prepare)?;
+        self.checkpoint = Some(self.state.clone());
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        _timeout: u32,
+    ) -> Result<(), NmstateError> {
+        self.check_failure(MockPhase::Apply)?;
+        let mut state = NetworkState::new();
+        state.prop_list = self.state.prop_list.clone();
+        state.interfaces = gen_mock_ifaces(merged_state, &self.state);
+        state.routes = gen_mock_routes(merged_state);
+        state.rules.config = Some(gen_mock_rules(merged_state, &self.state));
+        state.dns = gen_mock_dns(merged_state, &self.state);
+        state.hostname = gen_mock_hostname(merged_state, &self.state);
+        state.ovsdb = gen_mock_ovsdb(merged_state);
+        self.state = state;
+        Ok(())
+    }
+
+    fn verify(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        current: &NetworkState,
+        _timeout: u32,
+    ) -> Result<(), NmstateError> {
+        self.check_failure(MockPhase::Verify)?;
+        merged_state.verify(current)
+    }
+
+    fn commit(&mut self) -> Result<(), NmstateError> {
+        self.check_failure(MockPhase::Commit)?;
+        self.checkpoint = None;
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), NmstateError> {
+        self.check_failure(MockPhase::Rollback)?;
+        if let Some(state) = self.checkpoint.take() {
+            self.state = state;
+        }
+        Ok(())
+    }
+}
+
+fn gen_mock_ifaces(
+    merged_state: &MergedNetworkState,
+    cur_state: &NetworkState,
+) -> Interfaces {
+    let mut ifaces = Interfaces::new();
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .chain(merged_state.interfaces.user_ifaces.values())
+        .filter(|i| !i.merged.is_absent())
+    {
+        ifaces.push(merged_iface.merged.clone());
+    }
+    // Ignored interfaces are not included in merged state, preserve them
+    // as it was.
+    for (iface_name, iface_type) in
+        merged_state.interfaces.ignored_ifaces.as_slice()
+    {
+        if let Some(iface) = cur_state
+            .interfaces
+            .get_iface(iface_name, iface_type.clone())
+        {
+            ifaces.push(iface.clone());
+        }
+    }
+    ifaces
+}
+
+fn gen_mock_routes(merged_state: &MergedNetworkState) -> Routes {
+    let mut rts: Vec<_> = merged_state
+        .routes
+        .indexed
+        .values()
+        .flatten()
+        .chain(merged_state.routes.external.iter())
+        .cloned()
+        .collect();
+    rts.sort_unstable();
+    let mut routes = Routes::new();
+    routes.running = Some(rts.clone());
+    routes.config = Some(rts);
+    routes
+}
+
+fn gen_mock_rules(
+    merged_state: &MergedNetworkState,
+    cur_state: &NetworkState,
+) -> Vec<RouteRuleEntry> {
+    let absent_rules: Vec<&RouteRuleEntry> = merged_state
+        .rules
+        .for_apply
+        .iter()
+        .filter(|r| r.is_absent())
+        .collect();
+    let mut rules: Vec<RouteRuleEntry> = cur_state
+        .rules
+        .config
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| !absent_rules.iter().any(|a| a.is_match(r)))
+        .cloned()
+        .collect();
+    for rule in merged_state
+        .rules
+        .for_apply
+        .iter()
+        .filter(|r| !r.is_absent())
+    {
+        if !rules.contains(rule) {
+            rules.push(rule.clone());
+        }
+    }
+    rules
+}
+
+fn gen_mock_dns(
+    merged_state: &MergedNetworkState,
+    cur_state: &NetworkState,
+) -> DnsState {
+    if !merged_state.dns.is_changed() {
+        return cur_state.dns.clone();
+    }
+    let mut dns_conf = DnsClientState::new();
+    dns_conf.server = Some(merged_state.dns.servers.clone());
+    dns_conf.search = Some(merged_state.dns.searches.clone());
+    let mut dns = DnsState::new();
+    dns.running = Some(dns_conf.clone());
+    dns.config = Some(dns_conf);
+    dns
+}
+
+fn gen_mock_hostname(
+    merged_state: &MergedNetworkState,
+    cur_state: &NetworkState,
+) -> Option<HostNameState> {
+    let mut hostname = cur_state.hostname.clone();
+    if let Some(des_hostname) = merged_state.hostname.desired.as_ref() {
+        if let Some(h) = hostname.as_mut() {
+            h.update(des_hostname);
+        } else {
+            hostname = Some(des_hostname.clone());
+        }
+    }
+    hostname
+}
+
+fn gen_mock_ovsdb(merged_state: &MergedNetworkState) -> OvsDbGlobalConfig {
+    let mut ovsdb = OvsDbGlobalConfig::default();
+    ovsdb.external_ids = Some(
+        merged_state
+            .ovsdb
+            .external_ids
+            .iter()
+            .filter(|(_, v)| v.is_some())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    ovsdb.other_config = Some(
+        merged_state
+            .ovsdb
+            .other_config
+            .iter()
+            .filter(|(_, v)| v.is_some())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    );
+    ovsdb.prop_list = vec!["external_ids", "other_config"];
+    ovsdb
+}
+
+impl NetworkState {
+    /// Retrieve the `NetworkState` from [MockBackend].
+    /// Only available for feature `mock`.
+    pub fn retrieve_from_mock(
+        &mut self,
+        mock: &mut MockBackend,
+    ) -> Result<&mut Self, NmstateError> {
+        self.retrieve_with_backend(mock, false)?;
+        self.interfaces.hide_controller_prop();
+        if self.minimal {
+            self.hide_defaults();
+        }
+        Ok(self)
+    }
+
+    /// Apply the `NetworkState` to [MockBackend].
+    /// Only available for feature `mock`.
+    pub fn apply_to_mock(
+        &self,
+        mock: &mut MockBackend,
+    ) -> Result<(), NmstateError> {
+        self.apply_with_backend(mock)
+    }
+
+    /// Generate the plan of applying the `NetworkState` to [MockBackend].
+    /// Only available for feature `mock`.
+    pub fn gen_plan_from_mock(
+        &self,
+        mock: &mut MockBackend,
+    ) -> Result<NetworkPlan, NmstateError> {
+        self.gen_plan_with_backend(mock)
+    }
+}
@@ -1,23 +1,56 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod backend;
 mod base;
+mod batadv;
 mod bond;
+mod can;
+mod capabilities;
 mod dns;
+mod drift;
 mod ethernet;
+mod ethtool;
+mod geneve;
+mod gre;
+mod gsm;
+mod gtp;
 mod hostname;
+mod ieee8021x;
 mod iface;
 mod infiniband;
 mod inter_ifaces;
 mod ip;
+mod ip_tunnel;
+mod ipvlan;
+mod l2tpv3;
 mod linux_bridge;
 mod mac_vlan;
 mod mac_vtap;
+mod macsec;
+#[cfg(feature = "mock")]
+mod mock;
 mod mptcp;
 mod net_state;
+mod netkit;
+mod nm_conf;
 mod ovs;
+mod pci;
+mod pppoe;
 mod route;
 mod route_rule;
 mod sriov;
+mod team;
+mod tun;
 mod vlan;
 mod vrf;
+mod vti;
 mod vxlan;
+mod wifi;
+mod wireguard;
+mod xfrm;
+
+pub(crate) use backend::apply_running_hostname;
+pub use backend::NetworkBackend;
+pub use capabilities::capabilities;
+#[cfg(feature = "mock")]
+pub use mock::{MockBackend, MockPhase};
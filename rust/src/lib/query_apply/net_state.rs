@@ -1,21 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::backend::{KernelBackend, NetworkBackend};
 use crate::{
-    nispor::{nispor_apply, nispor_retrieve, set_running_hostname},
-    nm::{
-        nm_apply, nm_checkpoint_create, nm_checkpoint_destroy,
-        nm_checkpoint_rollback, nm_checkpoint_timeout_extend, nm_retrieve,
-    },
-    ovsdb::{ovsdb_apply, ovsdb_is_running, ovsdb_retrieve},
-    MergedNetworkState, NetworkState, NmstateError,
+    nispor::{enter_netns, get_hostname_state, get_routes, nispor_retrieve},
+    nm::{nm_checkpoint_destroy, nm_checkpoint_rollback, NmBackend},
+    ovsdb::{ovsdb_is_running, ovsdb_retrieve},
+    ErrorKind, Guardrails, Ipv6LinkLocalVerify, MergedNetworkState,
+    NetworkPlan, NetworkState, NetworkStateSection, NmstateError,
+    OvsDbGlobalConfig,
 };
 
 const DEFAULT_ROLLBACK_TIMEOUT: u32 = 60;
 const VERIFY_RETRY_INTERVAL_MILLISECONDS: u64 = 1000;
 const VERIFY_RETRY_COUNT: usize = 5;
 const VERIFY_RETRY_COUNT_SRIOV: usize = 60;
-const VERIFY_RETRY_COUNT_KERNEL_MODE: usize = 5;
-const RETRY_NM_COUNT: usize = 2;
 const RETRY_NM_INTERVAL_MILLISECONDS: u64 = 2000;
 
 const MAX_SUPPORTED_INTERFACES: usize = 1000;
@@ -37,38 +35,115 @@ impl NetworkState {
 
     /// Retrieve the `NetworkState`.
     /// Only available for feature `query_apply`.
+    /// When running as non-root user, information requiring privilege is
+    /// omitted with warning instead of failing the whole query.
     pub fn retrieve(&mut self) -> Result<&mut Self, NmstateError> {
-        self.retrieve_full()?;
+        if let Some(netns) = self.netns.clone() {
+            self.retrieve_netns(netns)?;
+        } else {
+            let mut backend = self.backend();
+            self.retrieve_with_backend(
+                backend.as_mut(),
+                !nix::unistd::geteuid().is_root(),
+            )?;
+        }
         self.interfaces.hide_controller_prop();
+        if self.minimal {
+            self.hide_defaults();
+        }
         Ok(self)
     }
 
-    pub(crate) fn retrieve_full(&mut self) -> Result<&mut Self, NmstateError> {
-        let state = nispor_retrieve(self.running_config_only)?;
-        if state.prop_list.contains(&"hostname") {
-            self.hostname = state.hostname;
-        }
-        if state.prop_list.contains(&"interfaces") {
-            self.interfaces = state.interfaces;
-        }
-        if state.prop_list.contains(&"routes") {
-            self.routes = state.routes;
-        }
-        if state.prop_list.contains(&"rules") {
-            self.rules = state.rules;
+    /// Retrieve the specified section of `NetworkState` only, other sections
+    /// are untouched. This is faster than [NetworkState::retrieve()] as the
+    /// enumeration of network interfaces is skipped.
+    /// Not available for `saved config only` mode.
+    /// Only available for feature `query_apply`.
+    pub fn retrieve_section(
+        &mut self,
+        section: NetworkStateSection,
+    ) -> Result<&mut Self, NmstateError> {
+        if self.saved_config_only {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Retrieving a single section is not supported in saved \
+                config only mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
         }
-        if ovsdb_is_running() {
-            match ovsdb_retrieve() {
-                Ok(ovsdb_state) => self.update_state(&ovsdb_state),
-                Err(e) => {
-                    log::warn!("Failed to retrieve OVS DB state: {}", e);
+        match section {
+            NetworkStateSection::Routes => {
+                self.routes = get_routes(self.running_config_only);
+                if !self.preserve_kernel_order {
+                    self.routes.sort_for_output();
                 }
             }
+            NetworkStateSection::Dns => {
+                self.dns =
+                    self.backend().query_dns(self.running_config_only)?;
+            }
+            NetworkStateSection::HostName => {
+                self.hostname = get_hostname_state();
+            }
+            NetworkStateSection::OvsDb => {
+                self.ovsdb = if ovsdb_is_running() {
+                    ovsdb_retrieve()?.ovsdb
+                } else {
+                    OvsDbGlobalConfig::default()
+                };
+            }
         }
-        if !self.kernel_only {
-            let nm_state = nm_retrieve(self.running_config_only)?;
-            // TODO: Priority handling
-            self.update_state(&nm_state);
+        Ok(self)
+    }
+
+    // Network namespace is per-thread, hence query the kernel state in a
+    // dedicated thread to leave the network namespace of caller untouched.
+    fn retrieve_netns(&mut self, netns: String) -> Result<(), NmstateError> {
+        let mut net_state = self.clone();
+        let unprivileged = !nix::unistd::geteuid().is_root();
+        let result = std::thread::spawn(
+            move || -> Result<NetworkState, NmstateError> {
+                enter_netns(netns.as_str())?;
+                net_state
+                    .retrieve_with_backend(&mut KernelBackend, unprivileged)?;
+                Ok(net_state)
+            },
+        )
+        .join();
+        match result {
+            Ok(net_state) => {
+                *self = net_state?;
+                Ok(())
+            }
+            Err(_) => {
+                let e = NmstateError::new(
+                    ErrorKind::Bug,
+                    "Thread retrieving network namespace state panicked"
+                        .to_string(),
+                );
+                log::error!("{}", e);
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) fn retrieve_with_backend(
+        &mut self,
+        backend: &mut dyn NetworkBackend,
+        unprivileged: bool,
+    ) -> Result<&mut Self, NmstateError> {
+        if unprivileged && self.include_secrets {
+            log::warn!(
+                "Secrets are only available to root user, ignoring the \
+                request of including secrets"
+            );
+        }
+        if self.saved_config_only {
+            self.set_base_state(backend.query_saved()?);
+        } else {
+            self.retrieve_live_state(backend, unprivileged)?;
         }
         if !self.include_secrets {
             self.hide_secrets();
@@ -77,13 +152,76 @@ impl NetworkState {
         self.interfaces
             .user_ifaces
             .retain(|_, iface| !iface.is_ignore());
+        if !self.preserve_kernel_order {
+            self.routes.sort_for_output();
+            self.rules.sort_for_output();
+        }
 
         Ok(self)
     }
 
+    fn retrieve_live_state(
+        &mut self,
+        backend: &mut dyn NetworkBackend,
+        unprivileged: bool,
+    ) -> Result<(), NmstateError> {
+        if backend.include_kernel_state() {
+            self.set_base_state(nispor_retrieve(
+                self.running_config_only,
+                self.include_fdb,
+            )?);
+            // OVS daemon only runs in the network namespace of host
+            if self.netns.is_none() && ovsdb_is_running() {
+                match ovsdb_retrieve() {
+                    Ok(ovsdb_state) => self.update_state(&ovsdb_state),
+                    Err(e) => {
+                        log::warn!("Failed to retrieve OVS DB state: {}", e);
+                    }
+                }
+            }
+        }
+        match backend.query(self.running_config_only, unprivileged) {
+            // TODO: Priority handling
+            Ok(backend_state) => {
+                if backend.include_kernel_state() {
+                    self.update_state(&backend_state);
+                } else {
+                    self.set_base_state(backend_state);
+                }
+            }
+            Err(e)
+                if unprivileged && e.kind() == ErrorKind::PermissionError =>
+            {
+                log::warn!(
+                    "Omitting {} information due to permission failure \
+                    of non-root user: {}",
+                    backend.name(),
+                    e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
     /// Apply the `NetworkState`.
+    /// On failure, the NetworkManager backend rolls back to the state before
+    /// apply, except these changes done via netlink: the CAN bit timing, the
+    /// eSwitch mode and the properties of existing GTP, XFRM, L2TPv3, netkit,
+    /// batman-adv and vcan interfaces. The new interfaces of these types are
+    /// removed.
     /// Only available for feature `query_apply`.
     pub fn apply(&self) -> Result<(), NmstateError> {
+        self.apply_and_get_checkpoint().map(|_| ())
+    }
+
+    /// Apply the `NetworkState` and return the checkpoint left for
+    /// [NetworkState::checkpoint_commit()] or
+    /// [NetworkState::checkpoint_rollback()] when commit is skipped.
+    /// Only available for feature `query_apply`.
+    pub fn apply_and_get_checkpoint(
+        &self,
+    ) -> Result<Option<String>, NmstateError> {
         if self.interfaces.kernel_ifaces.len()
             + self.interfaces.user_ifaces.len()
             >= MAX_SUPPORTED_INTERFACES
@@ -94,25 +232,127 @@ impl NetworkState {
                 MAX_SUPPORTED_INTERFACES,
             );
         }
-        if !self.kernel_only {
-            self.apply_with_nm_backend()
+        if self.migrate_team && (self.kernel_only || self.memory_only) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Migrating team to bond is not supported in kernel only or \
+                memory only mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(netns) = self.netns.as_deref() {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Applying state to network namespace {netns} is not \
+                    supported, network namespace is read only"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.no_checkpoint && self.no_commit {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Cannot skip commit without checkpoint as the uncommitted \
+                change is rolled back by checkpoint timeout"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.stage_only
+            && (self.kernel_only || self.memory_only || self.migrate_team)
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Staging profiles is not supported in kernel only, \
+                memory only or team migration mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        let mut backend = self.backend();
+        self.apply_with_backend(backend.as_mut())?;
+        Ok(if self.no_commit {
+            backend.checkpoint_path()
         } else {
-            // TODO: Need checkpoint for kernel only mode
-            self.apply_without_nm_backend()
+            None
+        })
+    }
+
+    /// Generate the actions required for applying this `NetworkState`
+    /// classified by disruption risk, without changing anything.
+    /// Only available for feature `query_apply`.
+    pub fn gen_plan(&self) -> Result<NetworkPlan, NmstateError> {
+        self.gen_plan_with_backend(self.backend().as_mut())
+    }
+
+    /// Retrieve the `NetworkState` from specified [NetworkBackend].
+    /// Only available for feature `query_apply`.
+    pub fn retrieve_from_backend(
+        &mut self,
+        backend: &mut dyn NetworkBackend,
+    ) -> Result<&mut Self, NmstateError> {
+        self.retrieve_with_backend(backend, !nix::unistd::geteuid().is_root())?;
+        self.interfaces.hide_controller_prop();
+        if self.minimal {
+            self.hide_defaults();
+        }
+        Ok(self)
+    }
+
+    /// Apply the `NetworkState` using specified [NetworkBackend] instead of
+    /// the built-in NetworkManager or kernel backend.
+    /// Only available for feature `query_apply`.
+    pub fn apply_to_backend(
+        &self,
+        backend: &mut dyn NetworkBackend,
+    ) -> Result<(), NmstateError> {
+        self.apply_with_backend(backend)
+    }
+
+    pub(crate) fn gen_plan_with_backend(
+        &self,
+        backend: &mut dyn NetworkBackend,
+    ) -> Result<NetworkPlan, NmstateError> {
+        let mut cur_net_state = NetworkState::new();
+        cur_net_state.set_kernel_only(self.kernel_only);
+        cur_net_state.set_include_secrets(true);
+        cur_net_state.set_include_fdb(self.interfaces.has_bridge_fdb());
+        cur_net_state.retrieve_with_backend(backend, false)?;
+
+        let mut desired = self.clone();
+        if desired.guardrails.is_none() {
+            desired.guardrails = Guardrails::load_host_guardrails()?;
         }
+        let merged_state = MergedNetworkState::new(
+            desired,
+            cur_net_state,
+            false,
+            self.memory_only,
+        )?;
+        backend.plan(&merged_state)
     }
 
-    fn apply_with_nm_backend(&self) -> Result<(), NmstateError> {
+    pub(crate) fn apply_with_backend(
+        &self,
+        backend: &mut dyn NetworkBackend,
+    ) -> Result<(), NmstateError> {
         let mut cur_net_state = NetworkState::new();
         cur_net_state.set_kernel_only(self.kernel_only);
         cur_net_state.set_include_secrets(true);
-        if let Err(e) = cur_net_state.retrieve_full() {
+        cur_net_state.set_include_fdb(self.interfaces.has_bridge_fdb());
+        if let Err(e) = cur_net_state.retrieve_with_backend(backend, false) {
             if e.kind().can_retry() {
                 log::info!("Retrying on: {}", e);
                 std::thread::sleep(std::time::Duration::from_millis(
                     RETRY_NM_INTERVAL_MILLISECONDS,
                 ));
-                cur_net_state.retrieve_full()?;
+                cur_net_state.retrieve_with_backend(backend, false)?;
             } else {
                 return Err(e);
             }
@@ -130,22 +370,7 @@ impl NetworkState {
             };
 
         let timeout = self.timeout.unwrap_or(DEFAULT_ROLLBACK_TIMEOUT);
-        let checkpoint = match nm_checkpoint_create(timeout) {
-            Ok(c) => c,
-            Err(e) => {
-                if e.kind().can_retry() {
-                    log::info!("Retrying on: {}", e);
-                    std::thread::sleep(std::time::Duration::from_millis(
-                        RETRY_NM_INTERVAL_MILLISECONDS,
-                    ));
-                    nm_checkpoint_create(timeout)?
-                } else {
-                    return Err(e);
-                }
-            }
-        };
-
-        log::info!("Created checkpoint {}", &checkpoint);
+        backend.prepare(timeout)?;
 
         let verify_count = if pf_state.is_some() {
             VERIFY_RETRY_COUNT_SRIOV
@@ -153,7 +378,7 @@ impl NetworkState {
             VERIFY_RETRY_COUNT
         };
 
-        with_nm_checkpoint(&checkpoint, self.no_commit, || {
+        let result = (|| {
             if let Some(pf_state) = pf_state {
                 let pf_merged_state = MergedNetworkState::new(
                     pf_state,
@@ -161,64 +386,86 @@ impl NetworkState {
                     false,
                     self.memory_only,
                 )?;
-                self.apply_with_nm_backend_and_under_checkpoint(
+                self.apply_with_backend_and_verify(
+                    backend,
                     &pf_merged_state,
                     &cur_net_state,
-                    &checkpoint,
                     verify_count,
                 )?;
                 // Refresh current state
-                cur_net_state.retrieve_full()?;
+                cur_net_state.retrieve_with_backend(backend, false)?;
             }
 
             self.interfaces.check_sriov_capability()?;
+            let mut desired = self.clone();
+            if desired.guardrails.is_none() {
+                desired.guardrails = Guardrails::load_host_guardrails()?;
+            }
             let merged_state = MergedNetworkState::new(
-                self.clone(),
+                desired,
                 cur_net_state.clone(),
                 false,
                 self.memory_only,
             )?;
 
-            self.apply_with_nm_backend_and_under_checkpoint(
+            self.apply_with_backend_and_verify(
+                backend,
                 &merged_state,
                 &cur_net_state,
-                &checkpoint,
                 verify_count,
             )
-        })
+        })();
+
+        match result {
+            Ok(()) => {
+                if !self.no_commit {
+                    backend.commit()?;
+                } else {
+                    log::info!(
+                        "Skipping commit for {} backend",
+                        backend.name()
+                    );
+                }
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(e) = backend.rollback() {
+                    log::warn!(
+                        "Rollback of {} backend failed: {}",
+                        backend.name(),
+                        e
+                    );
+                }
+                Err(e)
+            }
+        }
     }
 
-    fn apply_with_nm_backend_and_under_checkpoint(
+    fn apply_with_backend_and_verify(
         &self,
+        backend: &mut dyn NetworkBackend,
         merged_state: &MergedNetworkState,
         cur_net_state: &Self,
-        checkpoint: &str,
         retry_count: usize,
     ) -> Result<(), NmstateError> {
         let timeout = self.timeout.unwrap_or(DEFAULT_ROLLBACK_TIMEOUT);
-        // NM might have unknown race problem found by verify stage,
-        // we try to apply the state again if so.
-        with_retry(RETRY_NM_INTERVAL_MILLISECONDS, RETRY_NM_COUNT, || {
-            nm_checkpoint_timeout_extend(checkpoint, timeout)?;
-            nm_apply(merged_state, checkpoint, timeout)?;
-            if merged_state.is_global_ovsdb_changed() && ovsdb_is_running() {
-                ovsdb_apply(merged_state)?;
-            }
-            if let Some(running_hostname) =
-                self.hostname.as_ref().and_then(|c| c.running.as_ref())
-            {
-                set_running_hostname(running_hostname)?;
-            }
-            if !self.no_verify {
+        let apply_retry_count = backend.apply_retry_count();
+        with_retry(RETRY_NM_INTERVAL_MILLISECONDS, apply_retry_count, || {
+            backend.apply(merged_state, timeout)?;
+            if !self.no_verify && !self.stage_only {
                 with_retry(
                     VERIFY_RETRY_INTERVAL_MILLISECONDS,
                     retry_count,
                     || {
-                        nm_checkpoint_timeout_extend(checkpoint, timeout)?;
                         let mut new_cur_net_state = cur_net_state.clone();
                         new_cur_net_state.set_include_secrets(true);
-                        new_cur_net_state.retrieve_full()?;
-                        merged_state.verify(&new_cur_net_state)
+                        new_cur_net_state
+                            .retrieve_with_backend(backend, false)?;
+                        backend.verify(
+                            merged_state,
+                            &new_cur_net_state,
+                            timeout,
+                        )
                     },
                 )
             } else {
@@ -227,37 +474,45 @@ impl NetworkState {
         })
     }
 
-    fn apply_without_nm_backend(&self) -> Result<(), NmstateError> {
-        let mut cur_net_state = NetworkState::new();
-        cur_net_state.set_kernel_only(self.kernel_only);
-        cur_net_state.set_include_secrets(true);
-        cur_net_state.retrieve_full()?;
-
-        let merged_state = MergedNetworkState::new(
-            self.clone(),
-            cur_net_state.clone(),
-            false,
-            self.memory_only,
-        )?;
-
-        nispor_apply(&merged_state)?;
-        if let Some(running_hostname) =
-            self.hostname.as_ref().and_then(|c| c.running.as_ref())
-        {
-            set_running_hostname(running_hostname)?;
-        }
-        if !self.no_verify {
-            with_retry(
-                VERIFY_RETRY_INTERVAL_MILLISECONDS,
-                VERIFY_RETRY_COUNT_KERNEL_MODE,
-                || {
-                    let mut new_cur_net_state = cur_net_state.clone();
-                    new_cur_net_state.retrieve_full()?;
-                    merged_state.verify(&new_cur_net_state)
-                },
-            )
+    fn backend(&self) -> Box<dyn NetworkBackend> {
+        if self.kernel_only {
+            Box::new(KernelBackend)
         } else {
-            Ok(())
+            Box::new(NmBackend::new(self.no_checkpoint))
+        }
+    }
+
+    // Override with the full state of specified layer.
+    fn set_base_state(&mut self, state: Self) {
+        if state.prop_list.contains(&"hostname") {
+            self.hostname = state.hostname;
+        }
+        if state.prop_list.contains(&"interfaces") {
+            self.interfaces = state.interfaces;
+        }
+        if state.prop_list.contains(&"routes") {
+            self.routes = state.routes;
+        }
+        if state.prop_list.contains(&"rules") {
+            self.rules = state.rules;
+        }
+        if state.prop_list.contains(&"dns") {
+            self.dns = state.dns;
+        }
+        if state.prop_list.contains(&"ovsdb") {
+            self.ovsdb = state.ovsdb;
+        }
+        if state.prop_list.contains(&"ovn") {
+            self.ovn = state.ovn;
+        }
+        if state.prop_list.contains(&"connectivity") {
+            self.connectivity = state.connectivity;
+        }
+        if state.prop_list.contains(&"networkmanager") {
+            self.networkmanager = state.networkmanager;
+        }
+        if state.prop_list.contains(&"pci_devices") {
+            self.pci_devices = state.pci_devices;
         }
     }
 
@@ -280,34 +535,17 @@ impl NetworkState {
         if other.prop_list.contains(&"ovsdb") {
             self.ovsdb = other.ovsdb.clone();
         }
-    }
-}
-
-fn with_nm_checkpoint<T>(
-    checkpoint: &str,
-    no_commit: bool,
-    func: T,
-) -> Result<(), NmstateError>
-where
-    T: FnOnce() -> Result<(), NmstateError>,
-{
-    match func() {
-        Ok(()) => {
-            if !no_commit {
-                nm_checkpoint_destroy(checkpoint)?;
-
-                log::info!("Destroyed checkpoint {}", checkpoint);
-            } else {
-                log::info!("Skipping commit for checkpoint {}", checkpoint);
-            }
-            Ok(())
+        if other.prop_list.contains(&"ovn") {
+            self.ovn = other.ovn.clone();
         }
-        Err(e) => {
-            if let Err(e) = nm_checkpoint_rollback(checkpoint) {
-                log::warn!("nm_checkpoint_rollback() failed: {}", e);
-            }
-            log::info!("Rollbacked to checkpoint {}", checkpoint);
-            Err(e)
+        if other.prop_list.contains(&"connectivity") {
+            self.connectivity = other.connectivity;
+        }
+        if other.prop_list.contains(&"networkmanager") {
+            self.networkmanager = other.networkmanager.clone();
+        }
+        if other.prop_list.contains(&"pci_devices") {
+            self.pci_devices = other.pci_devices.clone();
         }
     }
 }
@@ -315,10 +553,10 @@ where
 fn with_retry<T>(
     interval_ms: u64,
     count: usize,
-    func: T,
+    mut func: T,
 ) -> Result<(), NmstateError>
 where
-    T: FnOnce() -> Result<(), NmstateError> + Copy,
+    T: FnMut() -> Result<(), NmstateError>,
 {
     let mut cur_count = 0usize;
     while cur_count < count {
@@ -341,9 +579,21 @@ where
 }
 
 impl MergedNetworkState {
-    fn verify(&self, current: &NetworkState) -> Result<(), NmstateError> {
+    pub(crate) fn verify(
+        &self,
+        current: &NetworkState,
+    ) -> Result<(), NmstateError> {
         self.hostname.verify(current.hostname.as_ref())?;
-        self.interfaces.verify(&current.interfaces)?;
+        if self.ipv6_link_local_verify == Ipv6LinkLocalVerify::Auto {
+            self.interfaces.verify(&current.interfaces)?;
+        } else {
+            let mut cur_ifaces = current.interfaces.clone();
+            self.interfaces.process_ipv6_link_local_for_verify(
+                &mut cur_ifaces,
+                self.ipv6_link_local_verify,
+            )?;
+            self.interfaces.verify(&cur_ifaces)?;
+        }
         let ignored_kernel_ifaces: Vec<&str> = self
             .interfaces
             .ignored_ifaces
@@ -357,7 +607,13 @@ impl MergedNetworkState {
         self.rules
             .verify(&current.rules, ignored_kernel_ifaces.as_slice())?;
         self.dns.verify(&current.dns)?;
-        self.ovsdb.verify(&current.ovsdb)?;
+        // Global OVS DB might be changed by others during apply
+        if self.prop_list.contains(&"ovsdb") {
+            self.ovsdb.verify(&current.ovsdb)?;
+        }
+        self.networkmanager
+            .verify(current.networkmanager.as_ref())?;
+        self.pci_devices.verify(current.pci_devices.as_deref())?;
         Ok(())
     }
 }
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetkitConfig, NetkitInterface};
+
+impl NetkitInterface {
+    pub(crate) fn update_netkit(&mut self, other: &NetkitInterface) {
+        if let Some(other_conf) = other.netkit.as_ref() {
+            if let Some(self_conf) = self.netkit.as_mut() {
+                self_conf.update(other_conf);
+            } else {
+                self.netkit = other.netkit.clone();
+            }
+        }
+    }
+}
+
+impl NetkitConfig {
+    fn update(&mut self, other: &Self) {
+        self.peer.clone_from(&other.peer);
+        if other.mode.is_some() {
+            self.mode = other.mode;
+        }
+        if other.policy.is_some() {
+            self.policy = other.policy;
+        }
+        if other.peer_policy.is_some() {
+            self.peer_policy = other.peer_policy;
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedNetworkManagerConfig, NetworkManagerConfig, NmstateError,
+};
+
+impl MergedNetworkManagerConfig {
+    pub(crate) fn verify(
+        &self,
+        current: Option<&NetworkManagerConfig>,
+    ) -> Result<(), NmstateError> {
+        let desired = if let Some(d) = &self.desired {
+            d
+        } else {
+            return Ok(());
+        };
+        let current = current.cloned().unwrap_or_default();
+        let mut expected = current.clone();
+        expected.merge(desired);
+        if expected != current {
+            let e = NmstateError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Verification fail, desire networkmanager: {desired:?}, \
+                    current: {current:?}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, MergedPciDevices, NmstateError, PciDeviceConfig};
+
+impl MergedPciDevices {
+    pub(crate) fn verify(
+        &self,
+        current: Option<&[PciDeviceConfig]>,
+    ) -> Result<(), NmstateError> {
+        let current = current.unwrap_or_default();
+        for des_dev in self.desired.iter() {
+            let des_driver = match des_dev.driver_override.as_deref() {
+                Some(d) => d,
+                None => continue,
+            };
+            let cur_dev = current
+                .iter()
+                .find(|c| c.pci_address == des_dev.pci_address);
+            let cur_override = cur_dev
+                .and_then(|c| c.driver_override.as_deref())
+                .unwrap_or_default();
+            let cur_driver = cur_dev.and_then(|c| c.driver.as_deref());
+            if des_driver != cur_override
+                || (!des_driver.is_empty() && Some(des_driver) != cur_driver)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification fail, desire PCI device {} \
+                        driver-override '{}', current driver-override '{}' \
+                        and bound driver '{}'",
+                        des_dev.pci_address,
+                        des_driver,
+                        cur_override,
+                        cur_driver.unwrap_or_default()
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PppoeConfig, PppoeInterface};
+
+impl PppoeInterface {
+    pub(crate) fn update_pppoe(&mut self, other: &PppoeInterface) {
+        if let Some(pppoe_conf) = &mut self.pppoe {
+            pppoe_conf.update(other.pppoe.as_ref());
+        } else {
+            self.pppoe = other.pppoe.clone();
+        }
+    }
+
+    // The password might be hidden by nmstate in current state, and
+    // NetworkManager might not be permitted to provide the secret back.
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(pppoe_conf) = self.pppoe.as_mut() {
+            pppoe_conf.password = None;
+        }
+    }
+}
+
+impl PppoeConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if !other.parent.is_empty() {
+                self.parent = other.parent.clone();
+            }
+            if other.username.is_some() {
+                self.username = other.username.clone();
+            }
+            if other.password.is_some() {
+                self.password = other.password.clone();
+            }
+            if other.service.is_some() {
+                self.service = other.service.clone();
+            }
+        }
+    }
+}
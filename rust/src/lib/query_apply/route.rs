@@ -65,10 +65,11 @@ impl MergedRoutes {
                 {
                     continue;
                 }
-                if let Some(cur_rt) = cur_routes
-                    .as_slice()
-                    .iter()
-                    .find(|cur_rt| rt.is_match(cur_rt))
+                // Routes not added by nmstate are preserved
+                if let Some(cur_rt) =
+                    cur_routes.as_slice().iter().find(|cur_rt| {
+                        rt.is_match(cur_rt) && !self.external.contains(cur_rt)
+                    })
                 {
                     return Err(NmstateError::new(
                         ErrorKind::VerificationError,
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ErrorKind, Interface, InterfaceType, Interfaces, NmstateError, SrIovConfig,
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterface,
+    NmstateError, SrIovConfig, SrIovVfConfig,
 };
 
 impl SrIovConfig {
@@ -89,3 +90,35 @@ impl SrIovConfig {
         Ok(())
     }
 }
+
+impl MergedInterface {
+    // The VFs in current state which will be removed by decreasing
+    // `total-vfs` of this PF.
+    pub(crate) fn get_sriov_vfs_to_remove(&self) -> Vec<&SrIovVfConfig> {
+        let des_total_vfs = match self.for_apply.as_ref().filter(|i| i.is_up())
+        {
+            Some(Interface::Ethernet(i)) => i
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.sr_iov.as_ref())
+                .and_then(|s| s.total_vfs),
+            _ => None,
+        };
+        let des_total_vfs = match des_total_vfs {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        match self.current.as_ref() {
+            Some(Interface::Ethernet(i)) => i
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.sr_iov.as_ref())
+                .and_then(|s| s.vfs.as_ref())
+                .map(|vfs| {
+                    vfs.iter().filter(|vf| vf.id >= des_total_vfs).collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+}
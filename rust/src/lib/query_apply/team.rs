@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{nm::nm_retrieve_team_as_bond, NetworkState, NmstateError};
+
+impl NetworkState {
+    /// Generate the bond configuration equivalent to team profiles stored in
+    /// NetworkManager. The teamd runner is mapped to bond mode, link watcher
+    /// and port flags are mapped to bond options. Settings without bond
+    /// equivalent are ignored with warning.
+    /// Applying the returned state deletes the team profiles and creates the
+    /// bonds within single checkpoint.
+    /// Only available for feature `query_apply`.
+    pub fn team_to_bond() -> Result<Self, NmstateError> {
+        let mut net_state = nm_retrieve_team_as_bond()?;
+        net_state.set_migrate_team(true);
+        Ok(net_state)
+    }
+}
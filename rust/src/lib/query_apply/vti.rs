@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Vti6Interface, VtiConfig, VtiInterface};
+
+impl VtiInterface {
+    pub(crate) fn update_vti(&mut self, other: &VtiInterface) {
+        if let Some(vti_conf) = &mut self.vti {
+            vti_conf.update(other.vti.as_ref());
+        } else {
+            self.vti = other.vti.clone();
+        }
+    }
+}
+
+impl Vti6Interface {
+    pub(crate) fn update_vti6(&mut self, other: &Vti6Interface) {
+        if let Some(vti_conf) = &mut self.vti6 {
+            vti_conf.update(other.vti6.as_ref());
+        } else {
+            self.vti6 = other.vti6.clone();
+        }
+    }
+}
+
+impl VtiConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ikey.is_some() {
+                self.ikey = other.ikey;
+            }
+            if other.okey.is_some() {
+                self.okey = other.okey;
+            }
+        }
+    }
+}
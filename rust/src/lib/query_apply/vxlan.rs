@@ -22,6 +22,10 @@ impl VxlanConfig {
             self.local = other.local;
             self.remote = other.remote;
             self.dst_port = other.dst_port;
+            // NetworkManager does not support flood remotes
+            if other.flood_remotes.is_some() {
+                self.flood_remotes = other.flood_remotes.clone();
+            }
         }
     }
 }
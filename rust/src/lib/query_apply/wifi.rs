@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{WifiConfig, WifiInterface};
+
+impl WifiInterface {
+    pub(crate) fn update_wifi(&mut self, other: &WifiInterface) {
+        if let Some(wifi_conf) = &mut self.wifi {
+            wifi_conf.update(other.wifi.as_ref());
+        } else {
+            self.wifi = other.wifi.clone();
+        }
+    }
+
+    // The pre-shared key might be hidden by nmstate in current state, and
+    // NetworkManager might not be permitted to provide the secret back.
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(sec_conf) =
+            self.wifi.as_mut().and_then(|c| c.security.as_mut())
+        {
+            sec_conf.psk = None;
+        }
+    }
+}
+
+impl WifiConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.mode.is_some() {
+                self.mode = other.mode;
+            }
+            if !other.ssid.is_empty() {
+                self.ssid = other.ssid.clone();
+            }
+            if other.band.is_some() {
+                self.band = other.band;
+            }
+            if other.channel.is_some() {
+                self.channel = other.channel;
+            }
+            if other.hidden.is_some() {
+                self.hidden = other.hidden;
+            }
+            if other.security.is_some() {
+                self.security = other.security.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{WireGuardConfig, WireGuardInterface};
+
+impl WireGuardInterface {
+    pub(crate) fn update_wireguard(&mut self, other: &WireGuardInterface) {
+        if let Some(wg_conf) = &mut self.wireguard {
+            wg_conf.update(other.wireguard.as_ref());
+        } else {
+            self.wireguard = other.wireguard.clone();
+        }
+    }
+}
+
+impl WireGuardConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.private_key.is_some() {
+                self.private_key = other.private_key.clone();
+            }
+            if other.listen_port.is_some() {
+                self.listen_port = other.listen_port;
+            }
+            if other.fwmark.is_some() {
+                self.fwmark = other.fwmark;
+            }
+            if other.peers.is_some() {
+                self.peers = other.peers.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::XfrmInterface;
+
+impl XfrmInterface {
+    pub(crate) fn update_xfrm(&mut self, other: &XfrmInterface) {
+        if let Some(xfrm_conf) = &mut self.xfrm {
+            if let Some(other_conf) = other.xfrm.as_ref() {
+                xfrm_conf.if_id = other_conf.if_id;
+                if other_conf.parent.is_some() {
+                    xfrm_conf.parent.clone_from(&other_conf.parent);
+                }
+            }
+        } else {
+            self.xfrm = other.xfrm.clone();
+        }
+    }
+}
@@ -1,13 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::{hash_map::Entry, HashMap, HashSet};
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    ip::{is_ipv6_addr, sanitize_ip_network},
+    ip::{is_ipv6_addr, is_ipv6_unicast_link_local, sanitize_ip_network},
     ErrorKind, InterfaceType, MergedInterfaces, NmstateError,
 };
 
@@ -26,7 +26,9 @@ pub struct Routes {
     ///  * mrouted
     ///  * keepalived
     ///  * babel
+    ///  * zebra, bird, bgp, ospf, isis and rip (routing daemons)
     ///
+    /// The origin of each route is stored in [RouteEntry.protocol].
     /// Ignored when applying.
     pub running: Option<Vec<RouteEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -48,6 +50,31 @@ pub struct Routes {
     ///     state: absent
     /// ```
     ///
+    /// Or remove all routes in route table 100 regardless their destination
+    /// and next hop:
+    /// ```yaml
+    /// routes:
+    ///   config:
+    ///   - table-id: 100
+    ///     state: absent
+    /// ```
+    ///
+    /// The wildcard matching applies to `destination`, `next-hop-interface`,
+    /// `next-hop-address`, `table-id`, `metric`, `weight` and `protocol`.
+    /// The [RouteEntry::USE_DEFAULT_ROUTE_TABLE] and
+    /// [RouteEntry::USE_DEFAULT_METRIC] are also treated as wildcard for
+    /// absent route.
+    ///
+    /// When backend tracks the routes stored in its configuration(e.g.
+    /// NetworkManager), absent route without `destination` defined only
+    /// removes routes added by nmstate, routes added by other tools are
+    /// preserved.
+    ///
+    /// When `exact: true` is set in `ipv4` or `ipv6` section of the next hop
+    /// interface, the desired routes of that IP family are the complete route
+    /// list of that interface, all other routes of it will be removed
+    /// including the ones not added by nmstate.
+    ///
     /// To change a route entry, you need to delete old one and add new one(can
     /// be in single transaction).
     pub config: Option<Vec<RouteEntry>>,
@@ -58,6 +85,17 @@ impl Routes {
         Self::default()
     }
 
+    // Sort routes by route table, destination and metric to have stable
+    // output regardless the order provided by kernel or backend.
+    pub(crate) fn sort_for_output(&mut self) {
+        if let Some(rts) = self.running.as_mut() {
+            rts.sort_by(|a, b| a.output_sort_key().cmp(&b.output_sort_key()));
+        }
+        if let Some(rts) = self.config.as_mut() {
+            rts.sort_by(|a, b| a.output_sort_key().cmp(&b.output_sort_key()));
+        }
+    }
+
     /// TODO: hide it, internal only
     pub fn is_empty(&self) -> bool {
         self.running.is_none() && self.config.is_none()
@@ -67,7 +105,11 @@ impl Routes {
         // All desire non-absent route should have next hop interface
         if let Some(config_routes) = self.config.as_ref() {
             for route in config_routes.iter() {
-                if !route.is_absent() && route.next_hop_iface.is_none() {
+                route.validate_link_local_next_hop()?;
+                if !route.is_absent()
+                    && route.next_hop_iface.is_none()
+                    && route.next_hop_scope_iface().is_none()
+                {
                     return Err(NmstateError::new(
                         ErrorKind::NotImplementedError,
                         format!(
@@ -79,6 +121,47 @@ impl Routes {
                 if let Some(dst) = route.destination.as_deref() {
                     validate_route_dst(dst)?;
                 }
+                if !route.is_absent() {
+                    if let Some(protocol) = route.protocol {
+                        if !protocol.is_static() {
+                            return Err(NmstateError::new(
+                                ErrorKind::InvalidArgument,
+                                format!(
+                                    "Only static or boot route protocol \
+                                    is allowed for desired route, \
+                                    but got {protocol}: {route}"
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Resolve the `next-hop-interface` and the scope of `next-hop-address`
+    // defined as interface reference into interface name.
+    pub(crate) fn resolve_iface_refs(
+        &mut self,
+        merged_ifaces: &MergedInterfaces,
+    ) -> Result<(), NmstateError> {
+        for rt in self.config.iter_mut().flatten() {
+            if let Some(via) = rt.next_hop_iface.as_deref() {
+                if let Some(iface_name) =
+                    merged_ifaces.resolve_iface_ref(via)?
+                {
+                    rt.next_hop_iface = Some(iface_name);
+                }
+            }
+            if let Some((addr, scope)) =
+                rt.next_hop_addr.as_deref().and_then(|a| a.split_once('%'))
+            {
+                if let Some(iface_name) =
+                    merged_ifaces.resolve_iface_ref(scope)?
+                {
+                    rt.next_hop_addr = Some(format!("{addr}%{iface_name}"));
+                }
             }
         }
         Ok(())
@@ -99,6 +182,77 @@ impl Default for RouteState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// The origin of route entry.
+pub enum RouteProtocol {
+    /// Route installed by kernel, for example, the direct route of IP
+    /// address.
+    Kernel,
+    /// Route installed during boot, often used by `iproute` command.
+    Boot,
+    /// Route installed by administrator or network backend.
+    Static,
+    /// Route learned from DHCP server.
+    Dhcp,
+    /// Route learned from IPv6 router advertisement.
+    Ra,
+    /// Route installed by multicast routing daemon.
+    Mrouted,
+    /// Route installed by keepalived daemon.
+    Keepalived,
+    /// Route installed by Babel routing daemon.
+    Babel,
+    /// Route installed by Zebra daemon of Quagga or FRR.
+    Zebra,
+    /// Route installed by BIRD routing daemon.
+    Bird,
+    /// Route learned from BGP.
+    Bgp,
+    /// Route learned from OSPF.
+    Ospf,
+    /// Route learned from IS-IS.
+    Isis,
+    /// Route learned from RIP.
+    Rip,
+    /// Unknown route protocol.
+    Unknown,
+}
+
+impl std::fmt::Display for RouteProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Kernel => "kernel",
+                Self::Boot => "boot",
+                Self::Static => "static",
+                Self::Dhcp => "dhcp",
+                Self::Ra => "ra",
+                Self::Mrouted => "mrouted",
+                Self::Keepalived => "keepalived",
+                Self::Babel => "babel",
+                Self::Zebra => "zebra",
+                Self::Bird => "bird",
+                Self::Bgp => "bgp",
+                Self::Ospf => "ospf",
+                Self::Isis => "isis",
+                Self::Rip => "rip",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+impl RouteProtocol {
+    /// Whether route of this protocol is owned by nmstate.
+    pub fn is_static(&self) -> bool {
+        matches!(self, Self::Static | Self::Boot)
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
@@ -119,6 +273,11 @@ pub struct RouteEntry {
     /// Route next hop interface name.
     /// Serialize and deserialize to/from `next-hop-interface`.
     /// Mandatory for every non-absent routes.
+    /// When applying, the interface could also be referred by its MAC
+    /// address as `mac:<MAC>` or by its label as `label:<key>=<value>`,
+    /// which will be resolved to the name of the only matching interface, so
+    /// the same desired state could be used on hosts with different NIC
+    /// names.
     pub next_hop_iface: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -128,6 +287,12 @@ pub struct RouteEntry {
     /// Serialize and deserialize to/from `next-hop-address`.
     /// When setting this as empty string for absent route, it will only delete
     /// routes __without__ `next-hop-address`.
+    /// IPv6 link-local next hop address(`fe80::/10`) is only valid within
+    /// the scope of [RouteEntry::next_hop_iface], hence non-absent route
+    /// with link-local next hop must have `next-hop-interface` defined. The
+    /// interface could also be defined as scope ID, for example
+    /// `fe80::1%eth1`, which will be sanitized to `next-hop-address: fe80::1`
+    /// and `next-hop-interface: eth1`.
     pub next_hop_addr: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -154,6 +319,13 @@ pub struct RouteEntry {
         deserialize_with = "crate::deserializer::option_u16_or_string"
     )]
     pub weight: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The origin of this route. Always set by
+    /// [crate::NetworkState::retrieve()].
+    /// When applying, only [RouteProtocol::Static] or [RouteProtocol::Boot]
+    /// is allowed for non-absent route. For absent route, `None` means
+    /// wildcard matching routes of any protocol nmstate owns.
+    pub protocol: Option<RouteProtocol>,
 }
 
 impl RouteEntry {
@@ -195,11 +367,46 @@ impl RouteEntry {
         if self.weight.is_some() && self.weight != other.weight {
             return false;
         }
-        true
+        // Kernel might change the metric of desired route(e.g. IPv6 route
+        // with metric 0 will be shown as 1024), hence only absent route
+        // matches on metric.
+        if self.is_absent()
+            && self.metric.is_some()
+            && self.metric != Some(RouteEntry::USE_DEFAULT_METRIC)
+            && self.metric != other.metric
+        {
+            return false;
+        }
+        match (self.protocol, other.protocol) {
+            (Some(protocol), Some(other_protocol))
+                if protocol != other_protocol =>
+            {
+                false
+            }
+            // Absent route without protocol should not remove routes
+            // managed by other daemons
+            (None, Some(other_protocol))
+                if self.is_absent() && !other_protocol.is_static() =>
+            {
+                false
+            }
+            _ => true,
+        }
     }
 
     // Return tuple of (no_absent, is_ipv4, table_id, next_hop_iface,
     // destination, next_hop_addr, weight)
+    fn output_sort_key(&self) -> (u32, &str, Option<i64>, &str, &str, u16) {
+        (
+            self.table_id.unwrap_or(RouteEntry::USE_DEFAULT_ROUTE_TABLE),
+            self.destination.as_deref().unwrap_or(""),
+            self.metric,
+            self.next_hop_iface.as_deref().unwrap_or(""),
+            self.next_hop_addr.as_deref().unwrap_or(""),
+            self.weight.unwrap_or_default(),
+        )
+    }
+
     fn sort_key(&self) -> (bool, bool, u32, &str, &str, &str, u16) {
         (
             !matches!(self.state, Some(RouteState::Absent)),
@@ -232,6 +439,17 @@ impl RouteEntry {
                 }
             }
         }
+        if let Some(iface) = self.next_hop_scope_iface() {
+            let iface = iface.to_string();
+            if self.next_hop_iface.is_none() {
+                self.next_hop_iface = Some(iface);
+            }
+            self.next_hop_addr = self
+                .next_hop_addr
+                .as_deref()
+                .and_then(|a| a.split('%').next())
+                .map(|a| a.to_string());
+        }
         if let Some(via) = self.next_hop_addr.as_ref() {
             let new_via = format!("{}", via.parse::<std::net::IpAddr>()?);
             if via != &new_via {
@@ -243,6 +461,10 @@ impl RouteEntry {
                 self.next_hop_addr = Some(new_via);
             }
         }
+        // Route created by nmstate is always using static protocol
+        if !self.is_absent() {
+            self.protocol = None;
+        }
         if let Some(weight) = self.weight {
             if !(1..=256).contains(&weight) {
                 return Err(NmstateError::new(
@@ -266,10 +488,94 @@ impl RouteEntry {
         Ok(())
     }
 
+    // Absent route without any property will match all routes.
+    fn is_wildcard_all(&self) -> bool {
+        self.destination.as_deref().unwrap_or_default().is_empty()
+            && self.next_hop_iface.is_none()
+            && self.next_hop_addr.is_none()
+            && self
+                .table_id
+                .map(|t| t == RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+                .unwrap_or(true)
+            && self
+                .metric
+                .map(|m| m == RouteEntry::USE_DEFAULT_METRIC)
+                .unwrap_or(true)
+            && self.weight.is_none()
+            && self.protocol.is_none()
+    }
+
     pub(crate) fn is_ipv6(&self) -> bool {
         self.destination.as_ref().map(|d| is_ipv6_addr(d.as_str()))
             == Some(true)
     }
+
+    // Return the interface name stored as scope ID of next hop address,
+    // e.g. `eth1` for `fe80::1%eth1`.
+    fn next_hop_scope_iface(&self) -> Option<&str> {
+        self.next_hop_addr
+            .as_deref()
+            .and_then(|a| a.split_once('%'))
+            .map(|(_, iface)| iface)
+            .filter(|iface| !iface.is_empty())
+    }
+
+    fn validate_link_local_next_hop(&self) -> Result<(), NmstateError> {
+        let via = match self.next_hop_addr.as_deref() {
+            Some(v) if !v.is_empty() => v,
+            _ => return Ok(()),
+        };
+        let (addr, scope_iface) = match via.split_once('%') {
+            Some((addr, iface)) => (addr, Some(iface)),
+            None => (via, None),
+        };
+        let is_link_local = match Ipv6Addr::from_str(addr) {
+            Ok(ip) => is_ipv6_unicast_link_local(&ip),
+            Err(_) => false,
+        };
+        if let Some(scope_iface) = scope_iface {
+            if !is_link_local || scope_iface.is_empty() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid route next-hop-address {via}, only IPv6 \
+                        link-local address is allowed to have scope ID, \
+                        the correct format should be 'fe80::1%eth1': {self}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let Some(iface) = self.next_hop_iface.as_deref() {
+                if iface != scope_iface {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The scope ID of route next-hop-address {via} \
+                            does not match its next-hop-interface {iface}: \
+                            {self}"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        } else if is_link_local
+            && !self.is_absent()
+            && self.next_hop_iface.is_none()
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Route with IPv6 link-local next-hop-address {via} \
+                    must have next-hop-interface defined: {self}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
 }
 
 // For Vec::dedup()
@@ -320,6 +626,9 @@ impl std::fmt::Display for RouteEntry {
         if let Some(v) = self.weight {
             props.push(format!("weight: {v}"));
         }
+        if let Some(v) = self.protocol {
+            props.push(format!("protocol: {v}"));
+        }
 
         write!(f, "{}", props.join(" "))
     }
@@ -331,6 +640,9 @@ pub(crate) struct MergedRoutes {
     pub(crate) route_changed_ifaces: Vec<String>,
     pub(crate) desired: Routes,
     pub(crate) current: Routes,
+    // Current routes not added by nmstate, they are preserved but not
+    // stored into backend configuration.
+    pub(crate) external: Vec<RouteEntry>,
 }
 
 impl MergedRoutes {
@@ -372,6 +684,23 @@ impl MergedRoutes {
             .map(|i| i.merged.name())
             .collect();
 
+        // Absent route without destination defined only removes routes added
+        // by nmstate unless next hop interface is exact.
+        let is_removed = |absent_rt: &RouteEntry, rt: &RouteEntry| {
+            absent_rt.is_match(rt)
+                && (absent_rt.destination.is_some()
+                    || is_exact_route_iface(merged_ifaces, rt)
+                    || !is_external_route(merged_ifaces, rt))
+        };
+        // Desired routes are the complete route list of exact interfaces
+        let is_pruned = |rt: &RouteEntry| {
+            desired.config.is_some()
+                && is_exact_route_iface(merged_ifaces, rt)
+                && !desired_routes
+                    .iter()
+                    .any(|d| !d.is_absent() && d.is_match(rt))
+        };
+
         // Interface has route added.
         for rt in desired_routes
             .as_slice()
@@ -418,9 +747,15 @@ impl MergedRoutes {
         for absent_rt in
             desired_routes.as_slice().iter().filter(|rt| rt.is_absent())
         {
+            if absent_rt.is_wildcard_all() {
+                log::warn!(
+                    "Absent route '{absent_rt}' has no property defined, \
+                    it will remove all routes"
+                );
+            }
             if let Some(cur_rts) = current.config.as_ref() {
                 for rt in cur_rts {
-                    if absent_rt.is_match(rt) {
+                    if is_removed(absent_rt, rt) {
                         if let Some(via) = rt.next_hop_iface.as_ref() {
                             changed_ifaces.insert(via.as_str());
                         }
@@ -430,6 +765,7 @@ impl MergedRoutes {
         }
 
         let mut flattend_routes: Vec<RouteEntry> = Vec::new();
+        let mut external: Vec<RouteEntry> = Vec::new();
 
         if let Some(cur_rts) = current.config.as_ref() {
             for rt in cur_rts {
@@ -446,12 +782,20 @@ impl MergedRoutes {
                             .as_slice()
                             .iter()
                             .filter(|r| r.is_absent())
-                            .any(|absent_rt| absent_rt.is_match(rt))
+                            .any(|absent_rt| is_removed(absent_rt, rt))
                         {
                             continue;
                         }
+                        if is_pruned(rt) {
+                            changed_ifaces.insert(via.as_str());
+                            continue;
+                        }
 
-                        flattend_routes.push(rt.clone());
+                        if is_external_route(merged_ifaces, rt) {
+                            external.push(rt.clone());
+                        } else {
+                            flattend_routes.push(rt.clone());
+                        }
                     }
                 }
             }
@@ -468,6 +812,9 @@ impl MergedRoutes {
 
         flattend_routes.sort_unstable();
         flattend_routes.dedup();
+        external.sort_unstable();
+        external.dedup();
+        external.retain(|rt| !flattend_routes.contains(rt));
 
         let mut indexed: HashMap<String, Vec<RouteEntry>> = HashMap::new();
 
@@ -490,6 +837,7 @@ impl MergedRoutes {
             desired,
             current,
             route_changed_ifaces,
+            external,
         })
     }
 
@@ -508,9 +856,27 @@ impl MergedRoutes {
             })
             .collect();
 
+        for rt in self.desired.config.as_deref().unwrap_or_default().iter() {
+            if let Some(via) = rt
+                .next_hop_iface
+                .as_deref()
+                .filter(|i| !rt.is_absent() && ignored_ifaces.contains(i))
+            {
+                log::warn!(
+                    "Ignoring desired route '{rt}' as its next hop \
+                    interface {via} is ignored"
+                );
+            }
+        }
         for iface in ignored_ifaces.as_slice() {
             self.indexed.remove(&iface.to_string());
         }
+        self.external.retain(|rt| {
+            rt.next_hop_iface
+                .as_deref()
+                .map(|i| !ignored_ifaces.contains(&i))
+                .unwrap_or(true)
+        });
         self.route_changed_ifaces
             .retain(|n| !ignored_ifaces.contains(&n.as_str()));
     }
@@ -520,6 +886,61 @@ impl MergedRoutes {
     }
 }
 
+// Only when backend tracks the routes of next hop interface, the route not
+// found in backend configuration is considered as external.
+// Whether `exact: true` is desired in the IP section of route next hop
+// interface matching the IP family of route.
+fn is_exact_route_iface(
+    merged_ifaces: &MergedInterfaces,
+    rt: &RouteEntry,
+) -> bool {
+    let des_iface = match rt
+        .next_hop_iface
+        .as_deref()
+        .and_then(|i| merged_ifaces.kernel_ifaces.get(i))
+        .and_then(|i| i.desired.as_ref())
+    {
+        Some(i) => i.base_iface(),
+        None => return false,
+    };
+    if rt.is_ipv6() {
+        des_iface.ipv6.as_ref().and_then(|i| i.exact) == Some(true)
+    } else {
+        des_iface.ipv4.as_ref().and_then(|i| i.exact) == Some(true)
+    }
+}
+
+fn is_external_route(
+    merged_ifaces: &MergedInterfaces,
+    rt: &RouteEntry,
+) -> bool {
+    let cur_iface = match rt
+        .next_hop_iface
+        .as_deref()
+        .and_then(|i| merged_ifaces.kernel_ifaces.get(i))
+        .and_then(|i| i.current.as_ref())
+    {
+        Some(i) => i.base_iface(),
+        None => return false,
+    };
+    let owned_rts_v4 = cur_iface
+        .ipv4
+        .as_ref()
+        .and_then(|i| i.owned_routes.as_ref());
+    let owned_rts_v6 = cur_iface
+        .ipv6
+        .as_ref()
+        .and_then(|i| i.owned_routes.as_ref());
+    if owned_rts_v4.is_none() && owned_rts_v6.is_none() {
+        return false;
+    }
+    !owned_rts_v4
+        .into_iter()
+        .chain(owned_rts_v6)
+        .flatten()
+        .any(|owned_rt| owned_rt.is_match(rt))
+}
+
 // Validating if the route destination network is valid,
 // 0.0.0.0/8 and its subnet cannot be used as the route destination network
 fn validate_route_dst(dst: &str) -> Result<(), NmstateError> {
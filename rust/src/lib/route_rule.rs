@@ -31,6 +31,8 @@ pub struct RouteRules {
     ///     - state: absent
     ///       route-table: 500
     /// ```
+    /// Route rules could also be removed by priority range via
+    /// [RouteRuleEntry.priority_range].
     pub config: Option<Vec<RouteRuleEntry>>,
 }
 
@@ -39,9 +41,26 @@ impl RouteRules {
         Self::default()
     }
 
+    // Sort route rules by priority to have stable output regardless the
+    // order provided by kernel or backend.
+    pub(crate) fn sort_for_output(&mut self) {
+        if let Some(rules) = self.config.as_mut() {
+            rules.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.cmp(b)));
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.config.is_none()
     }
+
+    pub(crate) fn validate(&self) -> Result<(), NmstateError> {
+        if let Some(rules) = self.config.as_ref() {
+            for rule in rules {
+                rule.validate_priority_range()?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -120,6 +139,69 @@ pub struct RouteRuleEntry {
     /// Incoming interface.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub iif: Option<String>,
+    /// Only valid for absent route rule to match route rules with priority
+    /// in specified range. Cannot be used along with `priority`.
+    /// Serialize and deserialize to/from `priority-range`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_range: Option<RouteRulePriorityRange>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Range of route rule priority, both ends are inclusive. `None` means
+/// unbounded.
+/// For example, this [crate::NetworkState] will delete all route rules with
+/// priority between 100 and 200:
+/// ```yml
+/// ---
+/// route-rules:
+///   config:
+///     - state: absent
+///       priority-range:
+///         min: 100
+///         max: 200
+/// ```
+pub struct RouteRulePriorityRange {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_i64_or_string"
+    )]
+    /// Minimum priority.
+    pub min: Option<i64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_i64_or_string"
+    )]
+    /// Maximum priority.
+    pub max: Option<i64>,
+}
+
+impl RouteRulePriorityRange {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn contains(&self, priority: i64) -> bool {
+        self.min.map(|min| priority >= min).unwrap_or(true)
+            && self.max.map(|max| priority <= max).unwrap_or(true)
+    }
+}
+
+impl std::fmt::Display for RouteRulePriorityRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.min.map(|v| v.to_string()).unwrap_or_default(),
+            self.max.map(|v| v.to_string()).unwrap_or_default()
+        )
+    }
 }
 
 impl RouteRuleEntry {
@@ -197,6 +279,47 @@ impl RouteRuleEntry {
         Ok(())
     }
 
+    fn validate_priority_range(&self) -> Result<(), NmstateError> {
+        if let Some(range) = self.priority_range.as_ref() {
+            if !self.is_absent() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "priority-range is only allowed in absent route \
+                        rule '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if self.priority.is_some() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "priority-range cannot be used along with \
+                        priority '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let (Some(min), Some(max)) = (range.min, range.max) {
+                if min > max {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The min of priority-range should not be \
+                            bigger than max '{self}'"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn is_absent(&self) -> bool {
         matches!(self.state, Some(RouteRuleState::Absent))
     }
@@ -272,6 +395,11 @@ impl RouteRuleEntry {
         {
             return false;
         }
+        if let Some(range) = self.priority_range.as_ref() {
+            if !range.contains(other.priority.unwrap_or_default()) {
+                return false;
+            }
+        }
         if self.table_id.is_some()
             && self.table_id != Some(RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE)
             && self.table_id != other.table_id
@@ -444,6 +572,9 @@ impl std::fmt::Display for RouteRuleEntry {
         if let Some(v) = self.action.as_ref() {
             props.push(format!("action: {v}"));
         }
+        if let Some(v) = self.priority_range.as_ref() {
+            props.push(format!("priority-range: {v}"));
+        }
         write!(f, "{}", props.join(" "))
     }
 }
@@ -504,6 +635,7 @@ impl MergedRouteRules {
         desired: RouteRules,
         current: RouteRules,
     ) -> Result<Self, NmstateError> {
+        desired.validate()?;
         let mut for_apply: Vec<RouteRuleEntry> = Vec::new();
         let mut merged_rules: Vec<RouteRuleEntry> = Vec::new();
 
@@ -564,10 +696,17 @@ impl MergedRouteRules {
 
         self.for_apply.retain(|rule| {
             if let Some(iif) = rule.iif.as_ref() {
-                !ignored_ifaces.contains(&iif.as_str())
-            } else {
-                true
+                if ignored_ifaces.contains(&iif.as_str()) {
+                    if !rule.is_absent() {
+                        log::warn!(
+                            "Ignoring desired route rule '{rule}' as its \
+                            iif interface {iif} is ignored"
+                        );
+                    }
+                    return false;
+                }
             }
+            true
         })
     }
 
@@ -1,4 +1,6 @@
-use serde::Serializer;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Serialize, Serializer};
 
 pub(crate) fn is_option_string_empty(data: &Option<String>) -> bool {
     if let Some(s) = data {
@@ -21,3 +23,21 @@ where
         serializer.serialize_none()
     }
 }
+
+// Serialize HashMap in the order of sorted keys to have stable output.
+pub(crate) fn option_map_sorted<S, V>(
+    data: &Option<HashMap<String, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    if let Some(v) = data {
+        v.iter()
+            .collect::<BTreeMap<&String, &V>>()
+            .serialize(serializer)
+    } else {
+        serializer.serialize_none()
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    InterfaceType, MergedNetworkState, NetworkBackend, NetworkState,
+    NmstateError,
+};
+
+#[derive(Debug, Default)]
+struct RecordBackend {
+    applied: Option<NetworkState>,
+    committed: bool,
+}
+
+impl NetworkBackend for RecordBackend {
+    fn name(&self) -> &'static str {
+        "record"
+    }
+
+    fn include_kernel_state(&self) -> bool {
+        false
+    }
+
+    fn query(
+        &mut self,
+        _running_config_only: bool,
+        _unprivileged: bool,
+    ) -> Result<NetworkState, NmstateError> {
+        let mut state = NetworkState::new();
+        state.prop_list = vec!["interfaces", "routes"];
+        Ok(state)
+    }
+
+    fn prepare(&mut self, _timeout: u32) -> Result<(), NmstateError> {
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        merged_state: &MergedNetworkState,
+        _timeout: u32,
+    ) -> Result<(), NmstateError> {
+        let mut state = NetworkState::new();
+        state.interfaces = merged_state.interfaces_for_apply();
+        state.routes = merged_state.routes_for_apply();
+        self.applied = Some(state);
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), NmstateError> {
+        self.committed = true;
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), NmstateError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_apply_to_custom_backend() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: dummy1
+          type: dummy
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: 192.0.2.1
+              prefix-length: 24
+        routes:
+          config:
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.254
+            next-hop-interface: dummy1
+        "#,
+    )
+    .unwrap();
+    desired.set_verify_change(false);
+    let mut backend = RecordBackend::default();
+
+    desired.apply_to_backend(&mut backend).unwrap();
+
+    assert!(backend.committed);
+    let applied = backend.applied.unwrap();
+    assert!(applied
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .is_some());
+    let rts = applied.routes.config.unwrap();
+    assert_eq!(rts.len(), 1);
+    assert_eq!(rts[0].destination.as_deref(), Some("198.51.100.0/24"));
+}
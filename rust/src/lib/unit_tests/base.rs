@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::BaseInterface;
+use crate::{
+    BaseInterface, ErrorKind, Interfaces, MergedInterfaces, MultiConnect,
+};
 
 #[test]
 fn test_base_iface_stringlized_attributes() {
@@ -28,3 +30,322 @@ mac-address: "d4:ee:07:25:42:5a"
     iface.sanitize(true).unwrap();
     assert_eq!(iface.mac_address, Some(String::from("D4:EE:07:25:42:5A")));
 }
+
+#[test]
+fn test_base_iface_multi_connect() {
+    let iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+multi-connect: manual-multiple
+"#,
+    )
+    .unwrap();
+    assert_eq!(iface.multi_connect, Some(MultiConnect::ManualMultiple));
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("multi-connect: manual-multiple"));
+}
+
+#[test]
+fn test_base_iface_link_stats_ignored_when_applying() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+link-stats:
+  carrier: true
+  carrier-changes: 4
+  carrier-up-count: 2
+  carrier-down-count: 2
+  negotiated-speed: 1000
+  configured-speed: 10000
+"#,
+    )
+    .unwrap();
+    let link_stats = iface.link_stats.as_ref().unwrap();
+    assert_eq!(link_stats.carrier, Some(true));
+    assert_eq!(link_stats.carrier_changes, Some(4));
+    assert_eq!(link_stats.negotiated_speed, Some(1000));
+    assert_eq!(link_stats.configured_speed, Some(10000));
+
+    iface.sanitize(true).unwrap();
+    assert_eq!(iface.link_stats, None);
+}
+
+#[test]
+fn test_base_iface_pci_hints_ignored_when_applying() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+pci-hints:
+  pci-address: "0000:3b:00.0"
+  numa-node: 1
+  local-cpus: 16-31,48-63
+  irq-count: 65
+"#,
+    )
+    .unwrap();
+    let pci_hints = iface.pci_hints.as_ref().unwrap();
+    assert_eq!(pci_hints.pci_address.as_deref(), Some("0000:3b:00.0"));
+    assert_eq!(pci_hints.numa_node, Some(1));
+    assert_eq!(pci_hints.local_cpus.as_deref(), Some("16-31,48-63"));
+    assert_eq!(pci_hints.irq_count, Some(65));
+
+    iface.sanitize(true).unwrap();
+    assert_eq!(iface.pci_hints, None);
+}
+
+#[test]
+fn test_base_iface_multicast_groups_sorted() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+multicast-groups:
+  - ff02::fb
+  - 239.1.1.2
+  - 224.0.0.251
+  - 239.1.1.2
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    assert_eq!(
+        iface.multicast_groups,
+        Some(vec![
+            "224.0.0.251".parse().unwrap(),
+            "239.1.1.2".parse().unwrap(),
+            "ff02::fb".parse().unwrap(),
+        ])
+    );
+}
+
+#[test]
+fn test_base_iface_multicast_groups_invalid() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+multicast-groups:
+  - 239.1.1.2
+  - 192.0.2.1
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_base_iface_permissions_sorted() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+permissions:
+  - bob
+  - alice
+  - bob
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    assert_eq!(
+        iface.permissions,
+        Some(vec!["alice".to_string(), "bob".to_string()])
+    );
+}
+
+#[test]
+fn test_base_iface_permissions_invalid_user() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+permissions:
+  - "user:alice:"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_base_iface_accept_all_mac_addresses_gen_conf() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  accept-all-mac-addresses: true
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("[ethernet]\naccept-all-mac-addresses=1\n"));
+}
+
+#[test]
+fn test_base_iface_metadata_invalid_label_key() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+metadata:
+  labels:
+    "owner:name": abc
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_base_iface_drain_delay_not_serialized() {
+    let iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+state: down
+drain-delay: "5"
+"#,
+    )
+    .unwrap();
+    assert_eq!(iface.drain_delay, Some(5));
+    assert!(!serde_yaml::to_string(&iface).unwrap().contains("drain"));
+}
+
+#[test]
+fn test_base_iface_drain_delay_kept_for_apply() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: down
+  drain-delay: 5
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let apply_iface = merged_ifaces.kernel_ifaces["eth1"]
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert_eq!(apply_iface.base_iface().drain_delay, Some(5));
+}
+
+#[test]
+fn test_base_iface_link_tuning_stringlized() {
+    let iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+txqueuelen: "10000"
+gro-flush-timeout: "20000"
+napi-defer-hard-irqs: "2"
+"#,
+    )
+    .unwrap();
+    assert_eq!(iface.txqueuelen, Some(10000));
+    assert_eq!(iface.gro_flush_timeout, Some(20000));
+    assert_eq!(iface.napi_defer_hard_irqs, Some(2));
+}
+
+#[test]
+fn test_base_iface_napi_defer_hard_irqs_too_big() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+napi-defer-hard-irqs: 2147483648
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_base_iface_verify_txqueuelen_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  txqueuelen: 10000
+  gro-flush-timeout: 20000
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  txqueuelen: 1000
+  gro-flush-timeout: 20000
+  napi-defer-hard-irqs: 0
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_base_iface_profile_uuid_normalized() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+profile-uuid: "8ACA0200ACCC4D13A62F3C89A6DA53C5"
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    assert_eq!(
+        iface.profile_uuid.as_deref(),
+        Some("8aca0200-accc-4d13-a62f-3c89a6da53c5")
+    );
+}
+
+#[test]
+fn test_base_iface_invalid_profile_uuid() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+profile-uuid: "not-a-uuid"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BatadvGwMode, BatadvInterface, BatadvRoutingAlgorithm, ErrorKind, Interface,
+};
+
+#[test]
+fn test_batadv_deserialize() {
+    let iface: BatadvInterface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  routing-algorithm: batman-v
+  gw-mode: server
+  hard-ifaces:
+  - eth1
+  - mesh0
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.batadv.unwrap();
+    assert_eq!(
+        conf.routing_algorithm,
+        Some(BatadvRoutingAlgorithm::BatmanV)
+    );
+    assert_eq!(conf.gw_mode, Some(BatadvGwMode::Server));
+    assert_eq!(
+        conf.hard_ifaces,
+        Some(vec!["eth1".to_string(), "mesh0".to_string()])
+    );
+}
+
+#[test]
+fn test_batadv_hard_iface_is_itself() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  hard-ifaces:
+  - bat0
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_batadv_duplicate_hard_iface() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  hard-ifaces:
+  - eth1
+  - eth2
+  - eth1
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("eth1"));
+    }
+}
+
+#[test]
+fn test_batadv_update_keep_current_hard_ifaces() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  routing-algorithm: batman-iv
+  gw-mode: "off"
+  hard-ifaces:
+  - eth1
+"#,
+    )
+    .unwrap();
+    let new_iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  gw-mode: client
+"#,
+    )
+    .unwrap();
+
+    iface.update(&new_iface);
+
+    if let Interface::Batadv(iface) = iface {
+        let conf = iface.batadv.unwrap();
+        assert_eq!(
+            conf.routing_algorithm,
+            Some(BatadvRoutingAlgorithm::BatmanIv)
+        );
+        assert_eq!(conf.gw_mode, Some(BatadvGwMode::Client));
+        assert_eq!(conf.hard_ifaces, Some(vec!["eth1".to_string()]));
+    } else {
+        panic!("Expecting batadv interface but got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_batadv_routing_algorithm_change_need_recreate() {
+    let des_iface: BatadvInterface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+batadv:
+  routing-algorithm: batman-v
+"#,
+    )
+    .unwrap();
+    let cur_iface: BatadvInterface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+batadv:
+  routing-algorithm: batman-iv
+  hard-ifaces:
+  - eth1
+"#,
+    )
+    .unwrap();
+
+    assert!(des_iface.is_link_changed(&cur_iface));
+    assert!(!cur_iface.is_link_changed(&cur_iface));
+}
+
+#[test]
+fn test_batadv_verify_unordered_hard_ifaces() {
+    let mut des_iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  hard-ifaces:
+  - mesh0
+  - eth1
+"#,
+    )
+    .unwrap();
+    let mut cur_iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bat0
+type: batadv
+state: up
+batadv:
+  routing-algorithm: batman-iv
+  gw-mode: "off"
+  hard-ifaces:
+  - eth1
+  - mesh0
+"#,
+    )
+    .unwrap();
+
+    des_iface.sanitize_desired_for_verify();
+    cur_iface.sanitize_current_for_verify();
+    des_iface.verify(&cur_iface).unwrap();
+}
@@ -4,7 +4,7 @@ use crate::{
     BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
     BondFailOverMac, BondInterface, BondLacpRate, BondMode,
     BondPrimaryReselect, BondXmitHashPolicy, ErrorKind, Interface, Interfaces,
-    MergedInterface,
+    MergedInterface, MergedInterfaces,
 };
 
 #[test]
@@ -220,6 +220,7 @@ link-aggregation:
     ad_user_port_key: "16"
     all_slaves_active: "1"
     arp_interval: "100"
+    arp_missed_max: "5"
     arp_validate: "1"
     downdelay: "50"
     fail_over_mac: "1"
@@ -247,6 +248,7 @@ link-aggregation:
         Some(BondAllPortsActive::Delivered)
     );
     assert_eq!(bond_opts.arp_interval, Some(100));
+    assert_eq!(bond_opts.arp_missed_max, Some(5));
     assert_eq!(bond_opts.arp_validate, Some(BondArpValidate::Active));
     assert_eq!(bond_opts.downdelay, Some(50));
     assert_eq!(bond_opts.fail_over_mac, Some(BondFailOverMac::Active));
@@ -267,6 +269,79 @@ link-aggregation:
     assert_eq!(bond_opts.use_carrier, Some(false));
 }
 
+#[test]
+fn test_bond_ns_ip6_target_compressed() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: active-backup
+  options:
+    arp_interval: 100
+    ns_ip6_target: "2001:db8:0:0::1, 2001:DB8::0002"
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    if let Interface::Bond(bond_iface) = iface {
+        let bond_opts = bond_iface.bond.unwrap().options.unwrap();
+        assert_eq!(
+            bond_opts.ns_ip6_target.as_deref(),
+            Some("2001:db8::1,2001:db8::2")
+        );
+    } else {
+        panic!("Expecting bond interface, got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_bond_invalid_ns_ip6_target() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: active-backup
+  options:
+    arp_interval: 100
+    ns_ip6_target: "2001:db8::1,192.0.2.1"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("192.0.2.1"));
+    }
+}
+
+#[test]
+fn test_bond_arp_missed_max_zero() {
+    let des_iface: Interface = serde_yaml::from_str(
+        r#"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: active-backup
+  options:
+    arp_interval: 100
+    arp_missed_max: 0
+"#,
+    )
+    .unwrap();
+    let mut merged_iface = MergedInterface::new(Some(des_iface), None).unwrap();
+    let result = merged_iface.post_inter_ifaces_process_bond();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
 #[test]
 fn test_integer_bond_mode() {
     let ifaces: Interfaces = serde_yaml::from_str(
@@ -598,3 +673,168 @@ fn test_disable_balance_slb_valid_override_current() {
         MergedInterface::new(Some(des_iface), Some(cur_iface)).unwrap();
     merged_iface.post_inter_ifaces_process_bond().unwrap();
 }
+
+#[test]
+fn test_bond_ports_config_without_port() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+        name: bond99
+        type: bond
+        state: up
+        link-aggregation:
+          mode: active-backup
+          ports-config:
+          - name: eth2
+            priority: 10
+          - name: eth1
+            queue-id: "1"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(iface.ports(), Some(vec!["eth2", "eth1"]));
+
+    iface.sanitize(true).unwrap();
+    if let Interface::Bond(bond_iface) = iface {
+        let bond_conf = bond_iface.bond.as_ref().unwrap();
+        assert_eq!(
+            bond_conf.port,
+            Some(vec!["eth1".to_string(), "eth2".to_string()])
+        );
+        let port_confs = bond_conf.ports_config.as_ref().unwrap();
+        assert_eq!(port_confs[0].name, "eth2");
+        assert_eq!(port_confs[0].priority, Some(10));
+        assert_eq!(port_confs[1].queue_id, Some(1));
+    } else {
+        panic!("Failed to find bond interface")
+    }
+}
+
+#[test]
+fn test_bond_ports_config_conflict_with_port() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+        name: bond99
+        type: bond
+        state: up
+        link-aggregation:
+          mode: active-backup
+          port:
+          - eth1
+          - eth2
+          ports-config:
+          - name: eth1
+            priority: 10
+        "#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_bond_ports_config_changed_port_marked_as_changed() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+        - name: bond99
+          type: bond
+          state: up
+          link-aggregation:
+            ports-config:
+            - name: eth1
+              priority: 10
+            - name: eth2
+              priority: 0
+        "#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+        - name: eth1
+          type: ethernet
+          state: up
+          controller: bond99
+        - name: eth2
+          type: ethernet
+          state: up
+          controller: bond99
+        - name: bond99
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth1
+            - eth2
+            ports-config:
+            - name: eth1
+              priority: 0
+              queue-id: 0
+            - name: eth2
+              priority: 0
+              queue-id: 0
+        "#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    assert!(merged_ifaces.kernel_ifaces["eth1"].is_changed());
+    assert!(!merged_ifaces.kernel_ifaces["eth2"].is_changed());
+}
+
+#[test]
+fn test_bond_ports_config_verify_in_desired_order() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+        - name: bond99
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            ports-config:
+            - name: eth2
+              priority: 20
+            - name: eth1
+              priority: 10
+        "#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+        - name: eth1
+          type: ethernet
+          state: up
+          controller: bond99
+        - name: eth2
+          type: ethernet
+          state: up
+          controller: bond99
+        - name: bond99
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth1
+            - eth2
+            ports-config:
+            - name: eth1
+              priority: 10
+              queue-id: 0
+            - name: eth2
+              priority: 20
+              queue-id: 0
+        "#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
@@ -719,3 +719,184 @@ fn test_bridge_sanitize_group_forward_mask_and_group_fwd_mask() {
     assert_eq!(desired_old, expected);
     assert_eq!(desired_new, expected);
 }
+
+#[test]
+fn test_linux_bridge_not_exact_preserve_current_ports() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    exact: false
+    port:
+    - name: eth2
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let mut ports = merged_ifaces
+        .get_iface("br0", InterfaceType::LinuxBridge)
+        .unwrap()
+        .merged
+        .ports()
+        .unwrap();
+    ports.sort_unstable();
+    assert_eq!(ports, vec!["eth1", "eth2"]);
+    assert!(merged_ifaces.kernel_ifaces["eth1"].for_apply.is_none());
+}
+
+#[test]
+fn test_linux_bridge_fdb_invalid_mac() {
+    let mut iface: LinuxBridgeInterface = serde_yaml::from_str(
+        r#"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  fdb:
+  - mac-address: 00:23:45:67:89:ZZ
+    port: eth1
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+fn gen_fdb_ifaces(br_yaml: &str) -> Interfaces {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: vxlan0
+  type: vxlan
+  state: up
+  vxlan:
+    base-iface: eth1
+    id: 100
+    destination-port: 4789
+"#,
+    )
+    .unwrap();
+    ifaces.push(serde_yaml::from_str(br_yaml).unwrap());
+    ifaces
+}
+
+#[test]
+fn test_linux_bridge_verify_fdb() {
+    let des_ifaces = gen_fdb_ifaces(
+        r#"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  port:
+  - name: eth1
+  - name: vxlan0
+  fdb:
+  - mac-address: 00:23:45:67:89:1a
+    port: eth1
+    vlan: 10
+  - mac-address: 00:00:00:00:00:00
+    port: vxlan0
+    remote: 192.0.2.1
+  - mac-address: 00:23:45:67:89:1B
+    port: eth1
+    state: absent
+"#,
+    );
+    let cur_ifaces = gen_fdb_ifaces(
+        r#"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  port:
+  - name: eth1
+  - name: vxlan0
+  fdb:
+  - mac-address: 00:00:00:00:00:00
+    port: vxlan0
+    remote: 192.0.2.1
+    learned: false
+  - mac-address: 00:23:45:67:89:1A
+    port: eth1
+    vlan: 10
+    learned: false
+  - mac-address: 00:23:45:67:89:1C
+    port: eth1
+    learned: true
+"#,
+    );
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_linux_bridge_verify_fdb_absent_entry_still_exists() {
+    let des_ifaces = gen_fdb_ifaces(
+        r#"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  port:
+  - name: eth1
+  fdb:
+  - mac-address: 00:23:45:67:89:1A
+    port: eth1
+    state: absent
+"#,
+    );
+    let cur_ifaces = gen_fdb_ifaces(
+        r#"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  port:
+  - name: eth1
+  fdb:
+  - mac-address: 00:23:45:67:89:1A
+    port: eth1
+    learned: false
+"#,
+    );
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
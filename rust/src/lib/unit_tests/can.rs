@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CanInterface, ErrorKind, Interface, InterfaceType};
+
+#[test]
+fn test_can_stringlized_attributes() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: "500000"
+  sample-point: "875"
+  restart-ms: "100"
+  fd: "true"
+  data-bitrate: "2000000"
+  data-sample-point: "750"
+"#,
+    )
+    .unwrap();
+    let can_conf = iface.can.as_ref().unwrap();
+
+    assert_eq!(can_conf.bitrate, Some(500000));
+    assert_eq!(can_conf.sample_point, Some(875));
+    assert_eq!(can_conf.restart_ms, Some(100));
+    assert_eq!(can_conf.fd, Some(true));
+    assert_eq!(can_conf.data_bitrate, Some(2000000));
+    assert_eq!(can_conf.data_sample_point, Some(750));
+}
+
+#[test]
+fn test_can_and_vcan_is_virtual() {
+    let ifaces: Vec<Interface> = serde_yaml::from_str(
+        r#"---
+- name: can0
+  type: can
+  state: up
+  can:
+    bitrate: 500000
+- name: vcan0
+  type: vcan
+  state: up
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(ifaces[0].iface_type(), InterfaceType::Can);
+    assert!(!ifaces[0].is_virtual());
+    assert_eq!(ifaces[1].iface_type(), InterfaceType::Vcan);
+    assert_eq!(ifaces[1].iface_type().to_string(), "vcan");
+    assert!(ifaces[1].is_virtual());
+}
+
+#[test]
+fn test_can_invalid_sample_point() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 500000
+  sample-point: 1000
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_can_data_bitrate_without_fd() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 500000
+  data-bitrate: 2000000
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, InterfaceType, NetworkState};
+
+#[test]
+fn test_canonicalize_state() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+        dns-resolver:
+          config:
+            server:
+            - 2001:db8:0::53
+            - 192.0.2.53
+        route-rules:
+          config:
+          - ip-to: 2001:db8:b:0::/64
+            priority: 1001
+            route-table: 200
+          - ip-from: 192.0.2.0/24
+            priority: 1000
+            route-table: 200
+        routes:
+          config:
+          - destination: 2001:db8:a:0::/64
+            next-hop-address: 2001:db8:1:0::1
+            next-hop-interface: eth1
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.1
+            next-hop-interface: eth1
+        interfaces:
+        - name: eth1
+          type: ethernet
+          mac-address: 00:23:45:67:89:1A
+          accept-all-mac-addresses: false
+          lldp:
+            enabled: false
+        - name: bond0
+          type: bond
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth3
+            - eth2
+        "#,
+    )
+    .unwrap();
+
+    state.canonicalize();
+
+    let expected: NetworkState = serde_yaml::from_str(
+        r#"---
+        dns-resolver:
+          config:
+            server:
+            - 2001:db8::53
+            - 192.0.2.53
+        route-rules:
+          config:
+          - ip-to: 2001:db8:b::/64
+            priority: 1001
+            route-table: 200
+          - ip-from: 192.0.2.0/24
+            priority: 1000
+            route-table: 200
+        routes:
+          config:
+          - destination: 2001:db8:a::/64
+            next-hop-address: 2001:db8:1::1
+            next-hop-interface: eth1
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.1
+            next-hop-interface: eth1
+        interfaces:
+        - name: eth1
+          type: ethernet
+          mac-address: 00:23:45:67:89:1a
+        - name: bond0
+          type: bond
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth2
+            - eth3
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_yaml::to_string(&state).unwrap(),
+        serde_yaml::to_string(&expected).unwrap()
+    );
+    let eth1 = state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(eth1.base_iface().lldp, None);
+}
+
+#[test]
+fn test_canonicalize_keep_invalid_ip_and_scope_id() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+        dns-resolver:
+          config:
+            server:
+            - fe80:0::1%eth1
+            - not-an-ip
+        "#,
+    )
+    .unwrap();
+
+    state.canonicalize();
+
+    assert_eq!(
+        state.dns.config.unwrap().server.unwrap(),
+        vec!["fe80::1%eth1".to_string(), "not-an-ip".to_string()]
+    );
+}
+
+#[test]
+fn test_ovsdb_serialize_sorted() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+        ovs-db:
+          external_ids:
+            c: "3"
+            a: "1"
+            b: "2"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&state.ovsdb).unwrap(),
+        r#"{"external_ids":{"a":"1","b":"2","c":"3"}}"#
+    );
+}
+
+#[test]
+fn test_hide_defaults() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+        dns-resolver:
+          running:
+            server:
+            - 192.0.2.53
+        routes:
+          running:
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.1
+            next-hop-interface: eth1
+        interfaces:
+        - name: eth1
+          type: ethernet
+          mtu: 1500
+          min-mtu: 68
+          max-mtu: 9000
+          wait-ip: any
+          ethernet:
+            auto-negotiation: true
+            speed: 1000
+            duplex: full
+        - name: eth2
+          type: ethernet
+          mtu: 9000
+          ethernet:
+            auto-negotiation: false
+            speed: 100
+        - name: ib0
+          type: infiniband
+          mtu: 1500
+        "#,
+    )
+    .unwrap();
+
+    state.hide_defaults();
+
+    assert!(state.routes.running.is_none());
+    assert!(state.dns.running.is_none());
+    let eth1 = state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    let base_iface = eth1.base_iface();
+    assert_eq!(base_iface.mtu, None);
+    assert_eq!(base_iface.min_mtu, None);
+    assert_eq!(base_iface.max_mtu, None);
+    assert_eq!(base_iface.wait_ip, None);
+    if let Interface::Ethernet(iface) = eth1 {
+        assert!(iface.ethernet.is_none());
+    } else {
+        panic!("Expecting ethernet interface, got {:?}", eth1);
+    }
+
+    let eth2 = state
+        .interfaces
+        .get_iface("eth2", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(eth2.base_iface().mtu, Some(9000));
+    if let Interface::Ethernet(iface) = eth2 {
+        let eth_conf = iface.ethernet.as_ref().unwrap();
+        assert_eq!(eth_conf.auto_neg, Some(false));
+        assert_eq!(eth_conf.speed, Some(100));
+    } else {
+        panic!("Expecting ethernet interface, got {:?}", eth2);
+    }
+
+    let ib0 = state
+        .interfaces
+        .get_iface("ib0", InterfaceType::InfiniBand)
+        .unwrap();
+    assert_eq!(ib0.base_iface().mtu, Some(1500));
+}
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ConnectivityState, InterfaceType, MergedNetworkState, NetworkState,
+};
+
+const QUERIED_STATE: &str = r#"---
+connectivity: portal
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  connectivity:
+    ipv4: portal
+    ipv6: none
+"#;
+
+#[test]
+fn test_connectivity_ignored_during_apply() {
+    let desired: NetworkState = serde_yaml::from_str(QUERIED_STATE).unwrap();
+    assert_eq!(desired.connectivity, Some(ConnectivityState::Portal));
+
+    let current: NetworkState = serde_yaml::from_str(QUERIED_STATE).unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let iface = merged_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .and_then(|i| i.for_apply.as_ref())
+        .unwrap();
+
+    assert_eq!(iface.base_iface().connectivity, None);
+}
+
+#[test]
+fn test_connectivity_hidden_in_minimal_output() {
+    let mut state: NetworkState = serde_yaml::from_str(QUERIED_STATE).unwrap();
+
+    state.hide_defaults();
+
+    assert_eq!(state.connectivity, None);
+    assert_eq!(
+        state.interfaces.kernel_ifaces["eth1"]
+            .base_iface()
+            .connectivity,
+        None
+    );
+}
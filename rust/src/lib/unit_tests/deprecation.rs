@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DeprecationWarning, Interface, NetworkState};
+
+#[test]
+fn test_deprecated_bond_ports() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  link-aggregation:
+    mode: balance-rr
+    ports:
+    - eth1
+    - eth2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        net_state.deprecation_warnings(),
+        &[DeprecationWarning {
+            path: "interfaces[bond0].link-aggregation.ports".to_string(),
+            replacement: "interfaces[bond0].link-aggregation.port".to_string(),
+        }]
+    );
+    if let Some(Interface::Bond(iface)) =
+        net_state.interfaces.kernel_ifaces.get("bond0")
+    {
+        assert_eq!(iface.ports(), Some(vec!["eth1", "eth2"]));
+    } else {
+        panic!("Failed to find bond0 in {:?}", net_state);
+    }
+}
+
+#[test]
+fn test_deprecated_ovs_bond_ports_in_list() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: br0
+  type: ovs-bridge
+  bridge:
+    ports:
+    - name: ovs0
+    - name: bond1
+      link-aggregation:
+        mode: balance-slb
+        ports:
+        - name: eth1
+        - name: eth2
+"#,
+    )
+    .unwrap();
+
+    let paths: Vec<&str> = net_state
+        .deprecation_warnings()
+        .iter()
+        .map(|w| w.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "interfaces[br0].bridge.ports",
+            "interfaces[br0].bridge.port[1].link-aggregation.ports",
+        ]
+    );
+}
+
+#[test]
+fn test_no_deprecation_warning() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - eth1
+"#,
+    )
+    .unwrap();
+
+    assert!(net_state.deprecation_warnings().is_empty());
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{InterfaceType, NetworkDrift, NetworkState, RouteState};
+
+const SAVED_STATE: &str = r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: down
+"#;
+
+#[test]
+fn test_drift_no_change() {
+    let saved: NetworkState = serde_yaml::from_str(SAVED_STATE).unwrap();
+    let drift = NetworkDrift::new(&saved, &saved);
+    assert!(drift.is_empty());
+    assert!(drift.persist_state().interfaces.is_empty());
+    assert!(drift.revert_state().routes.config.is_none());
+}
+
+#[test]
+fn test_drift_unsaved_address_and_route() {
+    let saved: NetworkState = serde_yaml::from_str(SAVED_STATE).unwrap();
+    let live: NetworkState = serde_yaml::from_str(
+        r#"---
+        routes:
+          config:
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.254
+            next-hop-interface: eth1
+            metric: 100
+            table-id: 254
+          - destination: 203.0.113.0/24
+            next-hop-address: 192.0.2.254
+            next-hop-interface: eth1
+            metric: 100
+            table-id: 254
+          - destination: 203.0.113.0/24
+            next-hop-address: 192.0.2.254
+            next-hop-interface: eth2
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: 192.0.2.1
+              prefix-length: 24
+            - ip: 192.0.2.100
+              prefix-length: 32
+          ipv6:
+            enabled: true
+            address:
+            - ip: fe80::1
+              prefix-length: 64
+        - name: eth2
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            address:
+            - ip: 192.0.2.2
+              prefix-length: 24
+        "#,
+    )
+    .unwrap();
+
+    let drift = NetworkDrift::new(&live, &saved);
+
+    assert_eq!(drift.interfaces.len(), 1);
+    assert_eq!(drift.interfaces[0].name, "eth1");
+    assert_eq!(drift.interfaces[0].unsaved_addresses.len(), 1);
+    assert_eq!(
+        drift.interfaces[0].unsaved_addresses[0].ip.to_string(),
+        "192.0.2.100"
+    );
+    assert!(drift.interfaces[0].missing_addresses.is_empty());
+    assert_eq!(drift.unsaved_routes.len(), 1);
+    assert_eq!(
+        drift.unsaved_routes[0].destination.as_deref(),
+        Some("203.0.113.0/24")
+    );
+    assert!(drift.missing_routes.is_empty());
+
+    let persist_state = drift.persist_state();
+    let eth1 = persist_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(
+        eth1.base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.addresses.as_ref())
+            .map(|a| a.len()),
+        Some(2)
+    );
+    let persist_routes = persist_state.routes.config.as_ref().unwrap();
+    assert_eq!(persist_routes.len(), 1);
+    assert_eq!(persist_routes[0].state, None);
+
+    let revert_state = drift.revert_state();
+    let eth1 = revert_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(
+        eth1.base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.addresses.as_ref())
+            .map(|a| a.len()),
+        Some(1)
+    );
+    let revert_routes = revert_state.routes.config.as_ref().unwrap();
+    assert_eq!(revert_routes.len(), 1);
+    assert_eq!(revert_routes[0].state, Some(RouteState::Absent));
+}
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ErrorKind, EthernetInterface, InterfaceType, Interfaces, MergedInterfaces,
+    ErrorKind, EthernetInterface, Interface, InterfaceType, Interfaces,
+    MergedInterfaces, NetworkState,
 };
 
 #[test]
@@ -198,3 +199,277 @@ fn test_new_veth_without_peer_config() {
         assert!(e.msg().contains("Veth interface veth1 does not exist"));
     }
 }
+
+#[test]
+fn test_veth_include_peer_conf() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: veth1
+  type: veth
+  state: up
+  veth:
+    peer: veth1peer
+    peer-mtu: 9000
+    peer-ipv4:
+      enabled: true
+      dhcp: false
+      address:
+      - ip: 192.0.2.2
+        prefix-length: 24
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false)
+            .unwrap();
+
+    let peer_iface = merged_ifaces
+        .get_iface("veth1peer", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    let veth1_iface = merged_ifaces
+        .get_iface("veth1", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+
+    assert!(peer_iface.is_up());
+    assert_eq!(peer_iface.base_iface().mtu, Some(9000));
+    assert!(peer_iface.base_iface().ipv4.as_ref().unwrap().enabled);
+    if let crate::Interface::Ethernet(eth_iface) = veth1_iface {
+        let veth_conf = eth_iface.veth.as_ref().unwrap();
+        assert_eq!(veth_conf.peer_mtu, None);
+        assert_eq!(veth_conf.peer_ipv4, None);
+    } else {
+        panic!("Expecting ethernet interface, got {:?}", veth1_iface);
+    }
+}
+
+#[test]
+fn test_veth_peer_conf_conflict() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: veth1
+  type: veth
+  state: up
+  veth:
+    peer: veth1peer
+    peer-mtu: 9000
+- name: veth1peer
+  type: veth
+  state: up
+  mtu: 1500
+  veth:
+    peer: veth1
+"#,
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("conflicting MTU"));
+    }
+}
+
+#[test]
+fn test_veth_absent_with_peer_up() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: veth1
+  type: veth
+  state: absent
+- name: veth1peer
+  type: veth
+  state: up
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: veth1
+  type: veth
+  state: up
+  veth:
+    peer: veth1peer
+- name: veth1peer
+  type: veth
+  state: up
+  veth:
+    peer: veth1
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("is marked as absent"));
+    }
+}
+
+#[test]
+fn test_usb_gadget_pin_current_mac() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: usb0
+  type: ethernet
+  state: up
+  ethernet:
+    usb-role: host
+- name: usb1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: usb0
+  type: ethernet
+  state: up
+  mac-address: 02:1A:2B:3C:4D:5E
+  ethernet:
+    usb-role: gadget
+- name: usb1
+  type: ethernet
+  state: up
+  mac-address: 02:1A:2B:3C:4D:5F
+  ethernet:
+    usb-role: host
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let gadget_iface = merged_ifaces
+        .get_iface("usb0", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    let host_iface = merged_ifaces
+        .get_iface("usb1", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(
+        gadget_iface.base_iface().mac_address.as_deref(),
+        Some("02:1A:2B:3C:4D:5E")
+    );
+    if let Interface::Ethernet(iface) = gadget_iface {
+        assert_eq!(iface.ethernet.as_ref().and_then(|e| e.usb_role), None);
+    } else {
+        panic!("Expecting ethernet interface, got {:?}", gadget_iface);
+    }
+    assert_eq!(host_iface.base_iface().mac_address, None);
+}
+
+#[test]
+fn test_ethernet_wol_stringlized_and_password_uppercase() {
+    let mut iface: EthernetInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ethernet:
+  wake-on-lan:
+    magic: "true"
+    unicast: "false"
+    broadcast: "no"
+    password: "aa:bb:cc:00:11:22"
+"#,
+    )
+    .unwrap();
+
+    iface.sanitize().unwrap();
+
+    let wol_conf = iface.ethernet.unwrap().wake_on_lan.unwrap();
+    assert_eq!(wol_conf.magic, Some(true));
+    assert_eq!(wol_conf.unicast, Some(false));
+    assert_eq!(wol_conf.broadcast, Some(false));
+    assert_eq!(wol_conf.password.as_deref(), Some("AA:BB:CC:00:11:22"));
+}
+
+#[test]
+fn test_ethernet_wol_invalid_password() {
+    let mut iface: EthernetInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ethernet:
+  wake-on-lan:
+    magic: true
+    password: "abc"
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethernet_wol_password_without_magic() {
+    let mut iface: EthernetInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ethernet:
+  wake-on-lan:
+    unicast: true
+    password: "00:11:22:33:44:55"
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("magic"));
+    }
+}
+
+#[test]
+fn test_ethernet_wol_hide_password() {
+    let mut iface: EthernetInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ethernet:
+  wake-on-lan:
+    magic: true
+    password: "00:11:22:33:44:55"
+"#,
+    )
+    .unwrap();
+
+    iface.hide_secrets();
+
+    let wol_conf = iface.ethernet.unwrap().wake_on_lan.unwrap();
+    assert_eq!(
+        wol_conf.password.as_deref(),
+        Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+    );
+    assert_eq!(wol_conf.magic, Some(true));
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::EthernetInterface;
+use crate::{ErrorKind, EthernetInterface, Interfaces, MergedInterfaces};
 
 #[test]
 fn test_ethtool_stringlized_attributes() {
@@ -113,3 +113,500 @@ ethtool:
     assert_eq!(ring.tx, Some(206));
     assert_eq!(ring.tx_max, Some(207));
 }
+
+#[test]
+fn test_ethtool_eee_stringlized_attributes() {
+    let iface: EthernetInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ethtool:
+  eee:
+    enabled: "yes"
+    advertise:
+    - 1000baseT/Full
+    - 100baseT/Full
+"#,
+    )
+    .unwrap();
+
+    let eee_conf = iface.base.ethtool.unwrap().eee.unwrap();
+
+    assert_eq!(eee_conf.enabled, Some(true));
+    assert_eq!(
+        eee_conf.advertise,
+        Some(vec![
+            "1000baseT/Full".to_string(),
+            "100baseT/Full".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_ethtool_eee_verify_ignore_advertise_order_and_duplicate() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    eee:
+      enabled: true
+      advertise:
+      - 100baseT/Full
+      - 1000baseT/Full
+      - 100baseT/Full
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    eee:
+      enabled: true
+      advertise:
+      - 1000baseT/Full
+      - 100baseT/Full
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_ethtool_eee_verify_advertise_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    eee:
+      advertise:
+      - 1000baseT/Full
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    eee:
+      enabled: true
+      advertise:
+      - 100baseT/Full
+      - 1000baseT/Full
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    assert!(merged_ifaces.verify(&cur_ifaces).is_err());
+}
+
+fn gen_channels_cur_ifaces() -> Interfaces {
+    serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      rx: 0
+      rx-max: 0
+      tx: 0
+      tx-max: 0
+      other: 1
+      other-max: 1
+      combined: 4
+      combined-max: 8
+- name: eth2
+  type: ethernet
+  state: up
+  ethtool:
+    feature:
+      rx-gro: true
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ethtool_channels_within_max() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      combined: "8"
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces = MergedInterfaces::new(
+        des_ifaces,
+        gen_channels_cur_ifaces(),
+        false,
+        false,
+    )
+    .unwrap();
+    let iface = merged_ifaces.kernel_ifaces["eth1"]
+        .for_apply
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(
+        iface
+            .base_iface()
+            .ethtool
+            .as_ref()
+            .and_then(|e| e.channels.as_ref())
+            .and_then(|c| c.combined),
+        Some(8)
+    );
+}
+
+#[test]
+fn test_ethtool_channels_exceed_max() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      rx: 2
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_channels_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_channels_not_supported() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth2
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      combined: 2
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_channels_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_coalesce_queues_within_desired_channels() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      combined: 8
+    coalesce:
+      queues:
+      - queue: 6
+        rx-usecs: 8
+"#,
+    )
+    .unwrap();
+
+    MergedInterfaces::new(des_ifaces, gen_channels_cur_ifaces(), false, false)
+        .unwrap();
+}
+
+#[test]
+fn test_ethtool_coalesce_queue_not_exist() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    coalesce:
+      queues:
+      - queue: 4
+        rx-usecs: 8
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_channels_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_coalesce_queue_duplicate() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    coalesce:
+      queues:
+      - queue: 1
+        rx-usecs: 8
+      - queue: 1
+        tx-usecs: 8
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_channels_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_coalesce_queues_verify_device_wide() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    coalesce:
+      queues:
+      - queue: 2
+        rx-usecs: 64
+      - queue: 0
+        rx-usecs: 8
+        tx-frames: 0
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    channels:
+      combined: 4
+      combined-max: 8
+    coalesce:
+      rx-usecs: 8
+      queues:
+      - queue: 1
+        rx-usecs: 32
+        rx-frames: 0
+        tx-usecs: 0
+        tx-frames: 0
+      - queue: 2
+        rx-usecs: 64
+        rx-frames: 0
+        tx-usecs: 0
+        tx-frames: 0
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+fn gen_flow_rules_cur_ifaces() -> Interfaces {
+    serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    flow-rules:
+    - id: 1
+      flow-type: tcp4
+      dst-ip: 192.0.2.1
+      dst-port: 80
+      action: queue
+      queue: 2
+    - id: 2
+      flow-type: udp6
+      src-ip: 2001:db8::/64
+      action: drop
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ethtool_flow_rules_verify_ignore_order_and_ip_format() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    flow-rules:
+    - id: "2"
+      flow-type: udp6
+      src-ip: 2001:db8::1/64
+      action: drop
+    - id: 1
+      flow-type: tcp4
+      dst-ip: 192.0.2.1/32
+      dst-port: 80
+      action: queue
+      queue: 2
+"#,
+    )
+    .unwrap();
+    let cur_ifaces = gen_flow_rules_cur_ifaces();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_ethtool_flow_rules_missing_queue() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    flow-rules:
+    - id: 1
+      flow-type: tcp4
+      dst-port: 80
+      action: queue
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_flow_rules_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_flow_rules_ip_family_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    flow-rules:
+    - id: 1
+      flow-type: tcp6
+      dst-ip: 192.0.2.1
+      action: drop
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_flow_rules_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ethtool_flow_rules_duplicate_id() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    flow-rules:
+    - id: 1
+      flow-type: tcp4
+      action: drop
+    - id: 1
+      flow-type: udp4
+      action: drop
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        des_ifaces,
+        gen_flow_rules_cur_ifaces(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GeneveInterface, Interface, InterfaceType};
+
+#[test]
+fn test_geneve_stringlized_attributes() {
+    let iface: GeneveInterface = serde_yaml::from_str(
+        r#"---
+name: geneve1
+type: geneve
+state: up
+geneve:
+  id: "1001"
+  remote: 192.0.2.2
+  destination-port: "6081"
+  ttl: "64"
+  tos: "16"
+"#,
+    )
+    .unwrap();
+    let geneve_conf = iface.geneve.unwrap();
+
+    assert_eq!(geneve_conf.id, 1001);
+    assert_eq!(geneve_conf.dst_port, Some(6081));
+    assert_eq!(geneve_conf.ttl, Some(64));
+    assert_eq!(geneve_conf.tos, Some(16));
+}
+
+#[test]
+fn test_geneve_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: geneve1
+type: geneve
+state: up
+geneve:
+  id: 1001
+  remote: 2001:db8::1
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Geneve);
+    assert_eq!(iface.iface_type().to_string(), "geneve");
+}
+
+#[test]
+fn test_geneve_id_out_of_range() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: geneve1
+type: geneve
+state: up
+geneve:
+  id: 16777216
+  remote: 192.0.2.2
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_geneve_gen_conf_geneve_section() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: geneve1
+  type: geneve
+  state: up
+  geneve:
+    id: 1001
+    remote: 192.0.2.2
+    destination-port: 6081
+    ttl: 64
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=geneve\n"));
+    assert!(content.contains(
+        "[geneve]\ndestination-port=6081\nid=1001\nremote=192.0.2.2\nttl=64\n"
+    ));
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GreInterface, Interface, InterfaceType};
+
+#[test]
+fn test_gre_stringlized_attributes() {
+    let iface: GreInterface = serde_yaml::from_str(
+        r#"---
+name: gre1
+type: gre
+state: up
+gre:
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  ikey: "100"
+  okey: "200"
+  ttl: "64"
+"#,
+    )
+    .unwrap();
+    let gre_conf = iface.gre.unwrap();
+
+    assert_eq!(gre_conf.ikey, Some(100));
+    assert_eq!(gre_conf.okey, Some(200));
+    assert_eq!(gre_conf.ttl, Some(64));
+}
+
+#[test]
+fn test_gretap_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gretap1
+type: gretap
+state: up
+gretap:
+  remote: 192.0.2.2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::GreTap);
+    assert_eq!(iface.iface_type().to_string(), "gretap");
+}
+
+#[test]
+fn test_gre_key_expanded_to_ikey_okey() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gre1
+type: gre
+state: up
+gre:
+  remote: 192.0.2.2
+  key: 100
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+
+    if let Interface::Gre(iface) = iface {
+        let gre_conf = iface.gre.unwrap();
+        assert_eq!(gre_conf.key, None);
+        assert_eq!(gre_conf.ikey, Some(100));
+        assert_eq!(gre_conf.okey, Some(100));
+    } else {
+        panic!("Expecting GRE interface, got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_gre_key_conflict_with_okey() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gre1
+type: gre
+state: up
+gre:
+  remote: 192.0.2.2
+  key: 100
+  okey: 200
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_gre_ipv6_remote_not_supported() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gre1
+type: gre
+state: up
+gre:
+  remote: 2001:db8::1
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_gre_gen_conf_ip_tunnel_section() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: gre1
+  type: gre
+  state: up
+  gre:
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    key: 100
+    ttl: 64
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=ip-tunnel\n"));
+    assert!(content.contains(
+        "[ip-tunnel]\ninput-key=100\nlocal=192.0.2.1\nmode=2\n\
+        output-key=100\nremote=192.0.2.2\nttl=64\n"
+    ));
+    assert!(!content.contains("[ethernet]"));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_gretap_gen_conf_ip_tunnel_mode() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: gretap1
+  type: gretap
+  state: up
+  gretap:
+    local: 192.0.2.1
+    remote: 192.0.2.2
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content
+        .contains("[ip-tunnel]\nlocal=192.0.2.1\nmode=10\nremote=192.0.2.2\n"));
+}
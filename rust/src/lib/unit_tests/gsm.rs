@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    NetworkState,
+};
+
+#[test]
+fn test_gsm_deserialize_wwan() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: cdc-wdm0
+type: wwan
+state: up
+gsm:
+  apn: internet.example.net
+  pin: "1234"
+  username: user
+  password: secret
+  roaming: "false"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Gsm);
+    assert_eq!(iface.iface_type().to_string(), "gsm");
+    if let Interface::Gsm(iface) = iface {
+        let gsm_conf = iface.gsm.unwrap();
+        assert_eq!(gsm_conf.apn.as_deref(), Some("internet.example.net"));
+        assert_eq!(gsm_conf.pin.as_deref(), Some("1234"));
+        assert_eq!(gsm_conf.username.as_deref(), Some("user"));
+        assert_eq!(gsm_conf.password.as_deref(), Some("secret"));
+        assert_eq!(gsm_conf.roaming, Some(false));
+    } else {
+        panic!("Expecting GSM interface");
+    }
+}
+
+#[test]
+fn test_gsm_invalid_pin() {
+    for pin in ["123", "123456789", "12a4"] {
+        let mut iface: Interface = serde_yaml::from_str(&format!(
+            r#"---
+name: cdc-wdm0
+type: gsm
+state: up
+gsm:
+  pin: "{pin}"
+"#
+        ))
+        .unwrap();
+        let result = iface.sanitize(true);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_gsm_apn_with_whitespace() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: cdc-wdm0
+type: gsm
+state: up
+gsm:
+  apn: "internet example"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_gsm_ignore_bearer_in_desired() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: cdc-wdm0
+type: gsm
+state: up
+gsm:
+  apn: internet.example.net
+  bearer:
+    connected: true
+    apn: internet.example.net
+    operator-code: "310260"
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+
+    if let Interface::Gsm(iface) = iface {
+        assert_eq!(iface.gsm.unwrap().bearer, None);
+    } else {
+        panic!("Expecting GSM interface");
+    }
+}
+
+#[test]
+fn test_gsm_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: cdc-wdm0
+  type: gsm
+  state: up
+  gsm:
+    apn: internet.example.net
+    pin: "1234"
+    username: user
+    password: secret
+"#,
+    )
+    .unwrap();
+    state.hide_secrets();
+
+    if let Some(Interface::Gsm(iface)) =
+        state.interfaces.kernel_ifaces.get("cdc-wdm0")
+    {
+        let gsm_conf = iface.gsm.as_ref().unwrap();
+        assert_eq!(
+            gsm_conf.pin.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(
+            gsm_conf.password.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(gsm_conf.username.as_deref(), Some("user"));
+    } else {
+        panic!("Expecting GSM interface cdc-wdm0");
+    }
+}
+
+#[test]
+fn test_gsm_verify_ignore_secrets_and_bearer() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: cdc-wdm0
+  type: gsm
+  state: up
+  gsm:
+    apn: internet.example.net
+    pin: "1234"
+    password: secret
+    roaming: false
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: cdc-wdm0
+  type: gsm
+  state: up
+  gsm:
+    apn: internet.example.net
+    pin: <_password_hid_by_nmstate>
+    roaming: false
+    bearer:
+      connected: true
+      apn: internet.example.net
+      operator-code: "310260"
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_gsm_gen_conf() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: cdc-wdm0
+  type: gsm
+  state: up
+  gsm:
+    apn: internet.example.net
+    pin: "1234"
+    roaming: false
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=gsm\n"));
+    assert!(content.contains(
+        "[gsm]\napn=internet.example.net\nhome-only=true\npin=1234\n\
+        pin-flags=0\n"
+    ));
+    assert!(!content.contains("[ethernet]"));
+}
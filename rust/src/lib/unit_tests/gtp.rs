@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GtpInterface, GtpRole, Interface, InterfaceType};
+
+#[test]
+fn test_gtp_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gtp0
+type: gtp
+state: up
+gtp:
+  role: sgsn
+  local: 2001:db8::1
+  external-teid: true
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Gtp);
+    assert_eq!(iface.iface_type().to_string(), "gtp");
+    if let Interface::Gtp(gtp_iface) = iface {
+        let gtp_conf = gtp_iface.gtp.unwrap();
+        assert_eq!(gtp_conf.role, Some(GtpRole::Sgsn));
+        assert_eq!(gtp_conf.local, Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(gtp_conf.external_teid, Some(true));
+    } else {
+        panic!("Should be GTP interface");
+    }
+}
+
+#[test]
+fn test_gtp_internal_teid_not_supported() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: gtp0
+type: gtp
+state: up
+gtp:
+  external-teid: false
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_gtp_role_change_requires_recreation() {
+    let cur_iface: GtpInterface = serde_yaml::from_str(
+        r#"---
+name: gtp0
+type: gtp
+state: up
+gtp:
+  role: ggsn
+  local: 192.0.2.1
+  external-teid: true
+"#,
+    )
+    .unwrap();
+    let des_iface: GtpInterface = serde_yaml::from_str(
+        r#"---
+name: gtp0
+type: gtp
+state: up
+gtp:
+  role: sgsn
+"#,
+    )
+    .unwrap();
+    let same_iface: GtpInterface = serde_yaml::from_str(
+        r#"---
+name: gtp0
+type: gtp
+state: up
+gtp:
+  local: 192.0.2.1
+"#,
+    )
+    .unwrap();
+
+    assert!(des_iface.is_link_changed(&cur_iface));
+    assert!(!same_iface.is_link_changed(&cur_iface));
+}
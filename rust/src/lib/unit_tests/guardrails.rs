@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Guardrails, MergedNetworkState, NetworkState};
+
+const GUARDRAILS: &str = r#"---
+interfaces:
+- name: mgmt0
+  deny-delete: true
+- name: uplink*
+  min-mtu: 1500
+  max-mtu: 9000
+"#;
+
+fn gen_current() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: mgmt0
+  type: ethernet
+  state: up
+  mtu: 1500
+- name: uplink0
+  type: ethernet
+  state: up
+  mtu: 1500
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_guardrails_deny_delete() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: mgmt0
+  type: ethernet
+  state: absent
+"#,
+    )
+    .unwrap();
+    desired.set_guardrails(Guardrails::new_from_yaml(GUARDRAILS).unwrap());
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("mgmt0 is protected from deletion"));
+    }
+}
+
+#[test]
+fn test_guardrails_min_mtu_with_wildcard() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: uplink0
+  type: ethernet
+  state: up
+  mtu: 1400
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1400
+"#,
+    )
+    .unwrap();
+    desired.set_guardrails(Guardrails::new_from_yaml(GUARDRAILS).unwrap());
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("uplink0 MTU 1400 is smaller than 1500"));
+        assert!(!e.msg().contains("eth1"));
+    }
+}
+
+#[test]
+fn test_guardrails_pass() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: uplink0
+  type: ethernet
+  state: up
+  mtu: 9000
+- name: eth1
+  type: ethernet
+  state: absent
+"#,
+    )
+    .unwrap();
+    desired.set_guardrails(Guardrails::new_from_yaml(GUARDRAILS).unwrap());
+
+    MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+}
+
+#[test]
+fn test_guardrails_invalid_yaml() {
+    let result = Guardrails::new_from_yaml(
+        r#"---
+interfaces:
+- name: mgmt0
+  deny-remove: true
+"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Ieee8021XConfig};
+
+#[test]
+fn test_ieee8021x_enrollment_command() {
+    let conf: Ieee8021XConfig = serde_yaml::from_str(
+        r#"---
+identity: client.example.org
+enrollment-command: test "$NMSTATE_INTERFACE" = eth1
+"#,
+    )
+    .unwrap();
+
+    conf.run_enrollment_command("eth1").unwrap();
+
+    let result = conf.run_enrollment_command("eth2");
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::PluginFailure);
+    }
+}
+
+#[test]
+fn test_ieee8021x_enrollment_command_not_verified() {
+    let mut conf: Ieee8021XConfig = serde_yaml::from_str(
+        r#"---
+identity: client.example.org
+private-key: pkcs11:token=nmstate;object=client
+enrollment-command: /usr/libexec/enroll-8021x
+"#,
+    )
+    .unwrap();
+
+    conf.sanitize_desired_for_verify();
+
+    assert_eq!(conf.enrollment_command, None);
+    assert_eq!(
+        conf.private_key.as_deref(),
+        Some("pkcs11:token=nmstate;object=client")
+    );
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, InterfaceType, MergedNetworkState, NetworkState};
+
+fn gen_current() -> NetworkState {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: false
+    address:
+    - ip: 2001:db8::2
+      prefix-length: 64
+- name: eth1.10
+  type: vlan
+  state: up
+  mac-address: 00:23:45:67:89:1A
+  vlan:
+    base-iface: eth1
+    id: 10
+- name: eth2
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1B
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.3
+      prefix-length: 24
+  metadata:
+    labels:
+      role: storage
+- name: eth3
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1C
+  metadata:
+    labels:
+      role: storage
+"#,
+    )
+    .unwrap();
+    // The `permanent-mac-address` is not deserializable
+    for iface in state.interfaces.kernel_ifaces.values_mut() {
+        if iface.iface_type() == InterfaceType::Ethernet {
+            let base_iface = iface.base_iface_mut();
+            base_iface.permanent_mac_address = base_iface.mac_address.clone();
+        }
+    }
+    state
+}
+
+#[test]
+fn test_route_next_hop_iface_ref_by_mac() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.1
+    next-hop-interface: mac:00:23:45:67:89:1a
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+    let rts = merged_state.routes.desired.config.unwrap();
+
+    assert_eq!(rts[0].next_hop_iface.as_deref(), Some("eth1"));
+}
+
+#[test]
+fn test_route_next_hop_iface_ref_by_label() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth3
+  type: ethernet
+  metadata:
+    labels:
+      role: backup
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.1
+    next-hop-interface: label:role=storage
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+    let rts = merged_state.routes.desired.config.unwrap();
+
+    assert_eq!(rts[0].next_hop_iface.as_deref(), Some("eth2"));
+}
+
+#[test]
+fn test_route_next_hop_iface_ref_ambiguous() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.1
+    next-hop-interface: label:role=storage
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("eth2, eth3"));
+    }
+}
+
+#[test]
+fn test_route_next_hop_iface_ref_not_found() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.1
+    next-hop-interface: mac:00:23:45:67:89:FF
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_dns_ipv6_link_local_srv_iface_ref() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+dns-resolver:
+  config:
+    server:
+    - fe80::deef:1%mac:00:23:45:67:89:1A
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+
+    assert_eq!(
+        merged_state.dns.servers,
+        vec!["fe80::deef:1%eth1".to_string()]
+    );
+}
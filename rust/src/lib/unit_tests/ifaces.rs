@@ -355,3 +355,250 @@ fn test_ifaces_iter_mut() {
     assert_eq!(ifaces_vec[0].base_iface().mtu, Some(1280));
     assert_eq!(ifaces_vec[1].base_iface().mtu, Some(1280));
 }
+
+#[test]
+fn test_ifaces_serialize_sorted_by_name_and_type() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+        - name: br0
+          type: ovs-interface
+        - name: eth1
+          type: ethernet
+        - name: br0
+          type: ovs-bridge
+        - name: bond0
+          type: bond
+        "#,
+    )
+    .unwrap();
+
+    let names: Vec<(&str, InterfaceType)> = ifaces
+        .to_vec()
+        .iter()
+        .map(|i| (i.name(), i.iface_type()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("bond0", InterfaceType::Bond),
+            ("br0", InterfaceType::OvsBridge),
+            ("br0", InterfaceType::OvsInterface),
+            ("eth1", InterfaceType::Ethernet),
+        ]
+    );
+}
+
+#[test]
+fn test_mtu_propagate_to_ports_and_vlan() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 1500
+- name: eth2
+  type: ethernet
+  mtu: 1500
+- name: bond0
+  type: bond
+  mtu: 1500
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+- name: eth1.10
+  type: vlan
+  mtu: 1500
+  vlan:
+    base-iface: eth1
+    id: 10
+- name: bond0.100
+  type: vlan
+  mtu: 1500
+  vlan:
+    base-iface: bond0
+    id: 100
+"#,
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: bond0
+  type: bond
+  mtu: 9000
+  mtu-propagate: true
+- name: eth2
+  type: ethernet
+  mtu: 4000
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let eth1 = merged_ifaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    let eth2 = merged_ifaces
+        .get_iface("eth2", InterfaceType::Ethernet)
+        .unwrap();
+    let vlan = merged_ifaces
+        .get_iface("bond0.100", InterfaceType::Vlan)
+        .unwrap();
+    let eth_vlan = merged_ifaces
+        .get_iface("eth1.10", InterfaceType::Vlan)
+        .unwrap();
+    let bond = merged_ifaces
+        .get_iface("bond0", InterfaceType::Bond)
+        .unwrap();
+
+    assert_eq!(
+        eth1.for_apply.as_ref().unwrap().base_iface().mtu,
+        Some(9000)
+    );
+    assert_eq!(
+        vlan.for_apply.as_ref().unwrap().base_iface().mtu,
+        Some(9000)
+    );
+    // Desired MTU is not overridden
+    assert_eq!(
+        eth2.for_apply.as_ref().unwrap().base_iface().mtu,
+        Some(4000)
+    );
+    // Propagation is not recursive
+    assert!(!eth_vlan.is_changed());
+    assert_eq!(
+        bond.for_apply.as_ref().unwrap().base_iface().mtu_propagate,
+        None
+    );
+}
+
+#[test]
+fn test_mtu_propagate_not_set() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 1500
+- name: eth1.10
+  type: vlan
+  mtu: 1500
+  vlan:
+    base-iface: eth1
+    id: 10
+"#,
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 9000
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let vlan = merged_ifaces
+        .get_iface("eth1.10", InterfaceType::Vlan)
+        .unwrap();
+
+    assert!(!vlan.is_changed());
+}
+
+#[test]
+fn test_mtu_bigger_than_kernel_max_mtu() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 1500
+  min-mtu: 68
+  max-mtu: 9000
+"#,
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 90000
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("[68, 9000]"));
+    }
+}
+
+#[test]
+fn test_mtu_without_kernel_max_mtu_limitation() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: dummy1
+  type: dummy
+  mtu: 1500
+  min-mtu: 0
+  max-mtu: 0
+"#,
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: dummy1
+  type: dummy
+  mtu: 65535
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_mtu_propagate_bigger_than_port_max_mtu() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  mtu: 1500
+  min-mtu: 68
+  max-mtu: 1500
+- name: bond0
+  type: bond
+  mtu: 1500
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+"#,
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: bond0
+  type: bond
+  mtu: 9000
+  mtu-propagate: true
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("eth1"));
+    }
+}
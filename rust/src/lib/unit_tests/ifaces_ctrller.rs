@@ -139,13 +139,154 @@ fn test_ifaces_up_order_nested_5_depth_worst_case() {
     ifaces.push(br0);
     ifaces.push(br4);
 
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, gen_test_eth_ifaces(), false, false)
+            .unwrap();
+
+    for (iface_name, up_priority) in [
+        ("br4", 0),
+        ("br0", 1),
+        ("br1", 2),
+        ("br2", 3),
+        ("br3", 4),
+        ("p1", 5),
+        ("p2", 5),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            merged_ifaces.kernel_ifaces[*iface_name]
+                .for_apply
+                .as_ref()
+                .unwrap()
+                .base_iface()
+                .up_priority,
+            *up_priority
+        );
+    }
+}
+
+#[test]
+fn test_ifaces_up_order_bridge_over_bond_over_vlan() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: p1.10
+  type: vlan
+  vlan:
+    base-iface: p1
+    id: 10
+- name: p1
+  type: ethernet
+- name: bond0
+  type: bond
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - p1.10
+- name: br1
+  type: linux-bridge
+  bridge:
+    port:
+    - name: bond0
+- name: br0
+  type: linux-bridge
+  bridge:
+    port:
+    - name: br1"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false)
+            .unwrap();
+
+    // The VLAN should be activated after both its parent and its controller
+    for (iface_name, up_priority) in [
+        ("br0", 0),
+        ("p1", 0),
+        ("br1", 1),
+        ("bond0", 2),
+        ("p1.10", 3),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            merged_ifaces.kernel_ifaces[*iface_name]
+                .for_apply
+                .as_ref()
+                .unwrap()
+                .base_iface()
+                .up_priority,
+            *up_priority
+        );
+    }
+}
+
+#[test]
+fn test_ifaces_up_order_vlan_over_ovs_internal_iface() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: ovs0.10
+  type: vlan
+  vlan:
+    base-iface: ovs0
+    id: 10
+- name: ovs0
+  type: ovs-interface
+- name: br0
+  type: ovs-bridge
+  bridge:
+    port:
+    - name: ovs0
+    - name: p1"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false)
+            .unwrap();
+
+    let ovs_iface = merged_ifaces
+        .get_iface("ovs0", InterfaceType::OvsInterface)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    let vlan_iface = merged_ifaces
+        .get_iface("ovs0.10", InterfaceType::Vlan)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(ovs_iface.base_iface().up_priority, 1);
+    assert_eq!(vlan_iface.base_iface().up_priority, 2);
+}
+
+#[test]
+fn test_ifaces_up_order_dependency_loop() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: vlan10
+  type: vlan
+  vlan:
+    base-iface: vlan20
+    id: 10
+- name: vlan20
+  type: vlan
+  vlan:
+    base-iface: vlan10
+    id: 20"#,
+    )
+    .unwrap();
+
     let result =
-        MergedInterfaces::new(ifaces, gen_test_eth_ifaces(), false, false);
+        MergedInterfaces::new(desired, Interfaces::new(), false, false);
 
     assert!(result.is_err());
-
     if let Err(e) = result {
         assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("vlan10 -> vlan20 -> vlan10"));
     }
 }
 
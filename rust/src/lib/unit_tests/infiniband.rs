@@ -374,3 +374,279 @@ fn test_ib_port_of_active_backup_bond_mode_in_desire() {
         Some(&InterfaceType::Bond)
     );
 }
+
+#[test]
+fn test_ib_pkey_iface_is_virtual() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+- name: mlx5_ib2.8001
+  type: infiniband
+  infiniband:
+    pkey: "0x8001"
+    mode: "datagram"
+    base-iface: "mlx5_ib2"
+"#,
+    )
+    .unwrap();
+
+    assert!(!ifaces
+        .get_iface("mlx5_ib2", InterfaceType::InfiniBand)
+        .unwrap()
+        .is_virtual());
+    assert!(ifaces
+        .get_iface("mlx5_ib2.8001", InterfaceType::InfiniBand)
+        .unwrap()
+        .is_virtual());
+}
+
+#[test]
+fn test_ib_reserved_pkey() {
+    for pkey in ["0x0", "0x8000"] {
+        let mut iface: Interface = serde_yaml::from_str(&format!(
+            r#"---
+name: mlx5_ib2.8000
+type: infiniband
+infiniband:
+  pkey: "{pkey}"
+  mode: "datagram"
+  base-iface: "mlx5_ib2"
+"#
+        ))
+        .unwrap();
+
+        let result = iface.sanitize(true);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_ib_pkey_without_base_iface() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: mlx5_ib2.8001
+type: infiniband
+infiniband:
+  pkey: "0x8001"
+  mode: "datagram"
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ib_base_iface_without_pkey() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: mlx5_ib2.8001
+type: infiniband
+infiniband:
+  mode: "datagram"
+  base-iface: "mlx5_ib2"
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ib_mix_with_ethernet_in_bond() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - mlx5_ib2
+    - eth1
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("cannot mix InfiniBand ports mlx5_ib2"));
+    }
+}
+
+#[test]
+fn test_ib_datagram_mode_mtu_exceed_max() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  mtu: 9000
+  infiniband:
+    mode: "datagram"
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  mtu: 2044
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("exceeds the maximum 4092"));
+    }
+}
+
+#[test]
+fn test_ib_connected_mode_allow_large_mtu() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  mtu: 65520
+  infiniband:
+    mode: "connected"
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  mtu: 2044
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+"#,
+    )
+    .unwrap();
+
+    MergedInterfaces::new(desired, current, false, false).unwrap();
+}
+
+#[test]
+fn test_ib_bond_mtu_exceed_max_of_ports() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: bond0
+  type: bond
+  state: up
+  mtu: 9000
+  link-aggregation:
+    mode: active-backup
+    port:
+    - mlx5_ib2
+    - mlx5_ib3
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  infiniband:
+    pkey: "0xffff"
+    mode: "connected"
+- name: mlx5_ib3
+  type: infiniband
+  state: up
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("bond0"));
+        assert!(e.msg().contains("exceeds the maximum 4092"));
+    }
+}
+
+#[test]
+fn test_ib_vlan_on_top_of_ib_bond() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: bond0.100
+  type: vlan
+  state: up
+  vlan:
+    base-iface: bond0
+    id: 100
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: mlx5_ib2
+  type: infiniband
+  state: up
+  infiniband:
+    pkey: "0xffff"
+    mode: "datagram"
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - mlx5_ib2
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("pkey sub-interface"));
+    }
+}
@@ -1,8 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    unit_tests::testlib::new_eth_iface, BaseInterface, ErrorKind, Interface,
-    InterfaceState, Interfaces, MergedInterfaces,
+    unit_tests::testlib::new_eth_iface, BaseInterface, DhcpLease, ErrorKind,
+    Interface, InterfaceIpAddr, InterfaceIpv4, InterfaceState, Interfaces,
+    MergedInterfaces,
 };
 
 fn gen_test_eth_ifaces() -> Interfaces {
@@ -280,3 +281,321 @@ fn test_ipv6_verify_emtpy() {
 
     merged_ifaces.verify(&cur_ifaces).unwrap();
 }
+
+fn gen_cur_iface_with_external_addr() -> Interfaces {
+    let mut current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: "192.0.2.1"
+      prefix-length: 24
+    - ip: "192.0.2.100"
+      prefix-length: 32
+"#,
+    )
+    .unwrap();
+    if let Some(ipv4) = current
+        .kernel_ifaces
+        .get_mut("eth1")
+        .and_then(|i| i.base_iface_mut().ipv4.as_mut())
+    {
+        ipv4.owned_addresses = Some(vec![InterfaceIpAddr {
+            ip: "192.0.2.1".parse().unwrap(),
+            prefix_length: 24,
+            mptcp_flags: None,
+        }]);
+    }
+    current
+}
+
+#[test]
+fn test_ip_preserve_external_address() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: "192.0.2.2"
+      prefix-length: 24
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces = MergedInterfaces::new(
+        desired,
+        gen_cur_iface_with_external_addr(),
+        false,
+        false,
+    )
+    .unwrap();
+    let ipv4 = merged_ifaces.kernel_ifaces["eth1"]
+        .merged
+        .base_iface()
+        .ipv4
+        .clone()
+        .unwrap();
+
+    let addrs: Vec<String> = ipv4
+        .addresses
+        .as_deref()
+        .unwrap()
+        .iter()
+        .map(|a| format!("{}/{}", a.ip, a.prefix_length))
+        .collect();
+    assert_eq!(addrs, vec!["192.0.2.2/24", "192.0.2.100/32"]);
+    assert_eq!(ipv4.external_addresses.len(), 1);
+    assert_eq!(ipv4.external_addresses[0].ip.to_string(), "192.0.2.100");
+}
+
+#[test]
+fn test_ip_exact_not_preserve_external_address() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    exact: true
+    address:
+    - ip: "192.0.2.2"
+      prefix-length: 24
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces = MergedInterfaces::new(
+        desired,
+        gen_cur_iface_with_external_addr(),
+        false,
+        false,
+    )
+    .unwrap();
+    let ipv4 = merged_ifaces.kernel_ifaces["eth1"]
+        .merged
+        .base_iface()
+        .ipv4
+        .clone()
+        .unwrap();
+
+    assert_eq!(ipv4.addresses.as_deref().unwrap().len(), 1);
+    assert!(ipv4.external_addresses.is_empty());
+}
+
+#[test]
+fn test_ipv4_exact_verify_extra_address() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    exact: true
+    address:
+    - ip: "192.168.1.1"
+      prefix-length: 24
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: "192.168.1.1"
+      prefix-length: 24
+    - ip: "192.168.1.2"
+      prefix-length: 24
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false)
+            .unwrap();
+
+    let result = merged_ifaces.verify(&current);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_dhcp_lease_ignored_when_applying() {
+    let mut ipv4: InterfaceIpv4 = serde_yaml::from_str(
+        r#"---
+enabled: true
+dhcp: true
+dhcp-lease:
+  server-id: 192.0.2.1
+  lease-time: 3600
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        ipv4.dhcp_lease,
+        Some(DhcpLease {
+            server_id: Some("192.0.2.1".to_string()),
+            lease_time: Some(3600),
+            ..Default::default()
+        })
+    );
+
+    ipv4.sanitize(true).unwrap();
+
+    assert_eq!(ipv4.dhcp_lease, None);
+}
+
+#[cfg(feature = "query_apply")]
+fn gen_ipv6_link_local_test_ifaces() -> (MergedInterfaces, Interfaces) {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv6:
+            enabled: true
+            dhcp: false
+            autoconf: false
+        ",
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv6:
+            enabled: false
+            address: []
+        ",
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current.clone(), false, false).unwrap();
+    (merged_ifaces, current)
+}
+
+#[test]
+#[cfg(feature = "query_apply")]
+fn test_ipv6_link_local_verify_skip() {
+    let (merged_ifaces, mut current) = gen_ipv6_link_local_test_ifaces();
+
+    merged_ifaces
+        .process_ipv6_link_local_for_verify(
+            &mut current,
+            crate::Ipv6LinkLocalVerify::Skip,
+        )
+        .unwrap();
+
+    let iface = current.kernel_ifaces.get("eth1").unwrap();
+    assert!(iface.base_iface().ipv6.as_ref().unwrap().enabled);
+}
+
+#[test]
+#[cfg(feature = "query_apply")]
+fn test_ipv6_link_local_verify_require() {
+    let (merged_ifaces, _) = gen_ipv6_link_local_test_ifaces();
+    let mut current: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv6:
+            enabled: true
+            address:
+            - ip: 2001:db8:1::1
+              prefix-length: 64
+        ",
+    )
+    .unwrap();
+
+    let result = merged_ifaces.process_ipv6_link_local_for_verify(
+        &mut current,
+        crate::Ipv6LinkLocalVerify::Require,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_ip_route_table() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  dhcp: true
+  route-table: "100"
+ipv6:
+  enabled: true
+  dhcp: false
+  autoconf: false
+  address:
+  - ip: "2001:db8:1::1"
+    prefix-length: 64
+  route-table: 200
+"#,
+    )
+    .unwrap();
+
+    iface.sanitize(true).unwrap();
+
+    assert_eq!(iface.ipv4.as_ref().unwrap().route_table, Some(100));
+    assert_eq!(iface.ipv6.as_ref().unwrap().route_table, Some(200));
+}
+
+#[test]
+fn test_ip_route_table_conflict_with_auto_table_id() {
+    let mut ipv4: InterfaceIpv4 = serde_yaml::from_str(
+        r#"---
+enabled: true
+dhcp: true
+auto-route-table-id: 100
+route-table: 200
+"#,
+    )
+    .unwrap();
+
+    let result = ipv4.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_route_table_ignored_when_disabled() {
+    let mut ipv4: InterfaceIpv4 = serde_yaml::from_str(
+        r#"---
+enabled: false
+route-table: 200
+"#,
+    )
+    .unwrap();
+
+    ipv4.sanitize(true).unwrap();
+
+    assert_eq!(ipv4.route_table, None);
+}
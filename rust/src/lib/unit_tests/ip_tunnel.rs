@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, InterfaceType, SitInterface};
+
+#[test]
+fn test_sit_stringlized_attributes() {
+    let iface: SitInterface = serde_yaml::from_str(
+        r#"---
+name: sit1
+type: sit
+state: up
+sit:
+  local: 192.0.2.1
+  ttl: "64"
+  pmtudisc: "true"
+"#,
+    )
+    .unwrap();
+    let sit_conf = iface.sit.unwrap();
+
+    assert_eq!(sit_conf.ttl, Some(64));
+    assert_eq!(sit_conf.pmtudisc, Some(true));
+}
+
+#[test]
+fn test_ipip_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipip1
+type: ipip
+state: up
+ipip:
+  remote: 192.0.2.2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::IpIp);
+    assert_eq!(iface.iface_type().to_string(), "ipip");
+}
+
+#[test]
+fn test_sit_6rd_prefix_normalized() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: sit1
+type: sit
+state: up
+sit:
+  local: 192.0.2.1
+  6rd-prefix: 2001:db8:ffff::1/32
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+
+    if let Interface::Sit(iface) = iface {
+        assert_eq!(
+            iface.sit.unwrap().sixrd_prefix.as_deref(),
+            Some("2001:db8::/32")
+        );
+    } else {
+        panic!("Expecting SIT interface, got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_sit_invalid_6rd_prefix() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: sit1
+type: sit
+state: up
+sit:
+  6rd-prefix: 192.0.2.0/24
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipip_6rd_prefix_not_allowed() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipip1
+type: ipip
+state: up
+ipip:
+  remote: 192.0.2.2
+  6rd-prefix: 2001:db8::/32
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipip_pmtudisc_disabled_with_ttl() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipip1
+type: ipip
+state: up
+ipip:
+  remote: 192.0.2.2
+  ttl: 64
+  pmtudisc: false
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_ipip_gen_conf_ip_tunnel_section() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: ipip1
+  type: ipip
+  state: up
+  ipip:
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    ttl: 64
+    pmtudisc: true
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=ip-tunnel\n"));
+    assert!(content.contains(
+        "[ip-tunnel]\nlocal=192.0.2.1\nmode=1\npath-mtu-discovery=true\n\
+        remote=192.0.2.2\nttl=64\n"
+    ));
+    assert!(!content.contains("[ethernet]"));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_sit_gen_conf_ip_tunnel_mode() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: sit1
+  type: sit
+  state: up
+  sit:
+    local: 192.0.2.1
+    6rd-prefix: 2001:db8::/32
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("[ip-tunnel]\nlocal=192.0.2.1\nmode=3\n"));
+    assert!(!content.contains("6rd"));
+}
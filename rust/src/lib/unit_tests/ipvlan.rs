@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, IpVlanInterface,
+    IpVlanMode, MergedInterfaces,
+};
+
+#[test]
+fn test_ipvlan_stringlized_attributes() {
+    let iface: IpVlanInterface = serde_yaml::from_str(
+        r#"---
+name: ipvlan0
+type: ipvlan
+state: up
+ipvlan:
+  base-iface: "eth1"
+  mode: "l3s"
+  private: "true"
+  vepa: "false"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.ipvlan.unwrap();
+    assert_eq!(conf.base_iface, "eth1");
+    assert_eq!(conf.mode, IpVlanMode::L3s);
+    assert_eq!(conf.private, Some(true));
+    assert_eq!(conf.vepa, Some(false));
+}
+
+#[test]
+fn test_ipvlan_parent() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipvlan0
+type: ipvlan
+state: up
+ipvlan:
+  base-iface: eth1
+  mode: l2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::IpVlan);
+    assert_eq!(iface.parent(), Some("eth1"));
+}
+
+#[test]
+fn test_ipvlan_private_and_vepa_conflict() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipvlan0
+type: ipvlan
+state: up
+ipvlan:
+  base-iface: eth1
+  mode: l2
+  private: true
+  vepa: true
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipvlan_verify_mode_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l3
+    private: false
+    vepa: false
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_ipvlan_merge_keep_current_flags() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l3
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l3
+    private: true
+    vepa: false
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_ipvlan_gen_conf() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l3s
+    vepa: true
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=ipvlan\n"));
+    assert!(content.contains("[ipvlan]\nmode=3\nparent=eth1\nvepa=true\n"));
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, L2tpv3Encap,
+    MergedInterfaces,
+};
+
+fn sanitize_l2tpv3_yaml(l2tpv3_yaml: &str) -> Result<(), crate::NmstateError> {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: l2tpeth0
+type: l2tpv3
+state: up
+l2tpv3:
+{l2tpv3_yaml}"#
+    ))
+    .unwrap();
+    iface.sanitize(true)
+}
+
+#[test]
+fn test_l2tpv3_stringlized_attributes() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: l2tpeth0
+type: l2tpv3
+state: up
+l2tpv3:
+  tunnel-id: "1000"
+  peer-tunnel-id: "2000"
+  session-id: "10"
+  peer-session-id: "20"
+  encap: udp
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  udp-source-port: "5000"
+  udp-destination-port: "5001"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::L2tpv3);
+    assert_eq!(iface.iface_type().to_string(), "l2tpv3");
+    if let Interface::L2tpv3(l2tpv3_iface) = iface {
+        let conf = l2tpv3_iface.l2tpv3.unwrap();
+        assert_eq!(conf.tunnel_id, Some(1000));
+        assert_eq!(conf.peer_tunnel_id, Some(2000));
+        assert_eq!(conf.session_id, Some(10));
+        assert_eq!(conf.peer_session_id, Some(20));
+        assert_eq!(conf.encap, Some(L2tpv3Encap::Udp));
+        assert_eq!(conf.local, Some("192.0.2.1".parse().unwrap()));
+        assert_eq!(conf.remote, Some("192.0.2.2".parse().unwrap()));
+        assert_eq!(conf.udp_source_port, Some(5000));
+        assert_eq!(conf.udp_destination_port, Some(5001));
+    } else {
+        panic!("Should be L2TPv3 interface");
+    }
+}
+
+#[test]
+fn test_l2tpv3_ip_encap_valid() {
+    sanitize_l2tpv3_yaml(
+        r#"  tunnel-id: 1000
+  peer-tunnel-id: 2000
+  session-id: 10
+  peer-session-id: 20
+  encap: ip
+  local: 2001:db8::1
+  remote: 2001:db8::2
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_l2tpv3_zero_session_id() {
+    let result = sanitize_l2tpv3_yaml(
+        r#"  tunnel-id: 1000
+  peer-tunnel-id: 2000
+  session-id: 0
+  peer-session-id: 20
+  encap: ip
+  local: 192.0.2.1
+  remote: 192.0.2.2
+"#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_l2tpv3_address_family_mismatch() {
+    let result = sanitize_l2tpv3_yaml(
+        r#"  tunnel-id: 1000
+  peer-tunnel-id: 2000
+  session-id: 10
+  peer-session-id: 20
+  encap: ip
+  local: 192.0.2.1
+  remote: 2001:db8::2
+"#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_l2tpv3_udp_port_with_ip_encap() {
+    let result = sanitize_l2tpv3_yaml(
+        r#"  tunnel-id: 1000
+  peer-tunnel-id: 2000
+  session-id: 10
+  peer-session-id: 20
+  encap: ip
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  udp-source-port: 5000
+"#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_l2tpv3_udp_encap_missing_port() {
+    // Encapsulation default to UDP
+    let result = sanitize_l2tpv3_yaml(
+        r#"  tunnel-id: 1000
+  peer-tunnel-id: 2000
+  session-id: 10
+  peer-session-id: 20
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  udp-source-port: 5000
+"#,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_l2tpv3_verify_session_id_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: l2tpeth0
+  type: l2tpv3
+  state: up
+  l2tpv3:
+    tunnel-id: 1000
+    peer-tunnel-id: 2000
+    session-id: 11
+    peer-session-id: 20
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    udp-source-port: 5000
+    udp-destination-port: 5000
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: l2tpeth0
+  type: l2tpv3
+  state: up
+  l2tpv3:
+    tunnel-id: 1000
+    peer-tunnel-id: 2000
+    session-id: 10
+    peer-session-id: 20
+    encap: udp
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    udp-source-port: 5000
+    udp-destination-port: 5000
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, Interfaces, MacSecInterface, MacSecValidate,
+    MergedInterfaces, NetworkState,
+};
+
+const MKA_CAK: &str = "50b71a8ef0bd5751ea76de6d6c98c03a";
+const MKA_CKN: &str =
+    "f2b4297d39da7330910a74abc0449feb45b5c0b9fc23df1430e1898fcf1c4550";
+
+#[test]
+fn test_macsec_stringlized_attributes() {
+    let iface: MacSecInterface = serde_yaml::from_str(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  base-iface: eth1
+  encrypt: "true"
+  port: "10"
+  validation: check
+  send-sci: "false"
+"#,
+    )
+    .unwrap();
+    let macsec_conf = iface.macsec.unwrap();
+
+    assert_eq!(macsec_conf.base_iface, "eth1");
+    assert_eq!(macsec_conf.encrypt, Some(true));
+    assert_eq!(macsec_conf.port, Some(10));
+    assert_eq!(macsec_conf.validation, Some(MacSecValidate::Check));
+    assert_eq!(macsec_conf.send_sci, Some(false));
+}
+
+#[test]
+fn test_macsec_mka_cak_without_ckn() {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  base-iface: eth1
+  mka-cak: {MKA_CAK}
+"#
+    ))
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_invalid_mka_cak() {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  base-iface: eth1
+  mka-cak: 50b71a8ef0bd5751ea76de6d6c98c0
+  mka-ckn: {MKA_CKN}
+"#
+    ))
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("mka-cak"));
+    }
+}
+
+#[test]
+fn test_macsec_invalid_port() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  base-iface: eth1
+  port: 0
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(&format!(
+        r#"---
+interfaces:
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    mka-cak: {MKA_CAK}
+    mka-ckn: {MKA_CKN}
+"#
+    ))
+    .unwrap();
+    state.hide_secrets();
+
+    if let Some(Interface::MacSec(iface)) =
+        state.interfaces.kernel_ifaces.get("macsec0")
+    {
+        let macsec_conf = iface.macsec.as_ref().unwrap();
+        assert_eq!(
+            macsec_conf.mka_cak.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(macsec_conf.mka_ckn.as_deref(), Some(MKA_CKN));
+    } else {
+        panic!("Expecting MACsec interface macsec0");
+    }
+}
+
+#[test]
+fn test_macsec_verify_ignore_mka_keys() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(&format!(
+        r#"---
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    encrypt: true
+    port: 1
+    validation: strict
+    send-sci: true
+    mka-cak: {MKA_CAK}
+    mka-ckn: {MKA_CKN}
+"#
+    ))
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    encrypt: true
+    port: 1
+    validation: strict
+    send-sci: true
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_macsec_verify_kernel_mismatch() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    encrypt: true
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    encrypt: false
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    let result = merged_ifaces.verify(&cur_ifaces);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_macsec_gen_conf() {
+    let state: NetworkState = serde_yaml::from_str(&format!(
+        r#"---
+interfaces:
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    base-iface: eth1
+    encrypt: true
+    port: 1
+    validation: strict
+    send-sci: true
+    mka-cak: {MKA_CAK}
+    mka-ckn: {MKA_CKN}
+"#
+    ))
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=macsec\n"));
+    assert!(content.contains(&format!(
+        "[macsec]\nencrypt=true\nmka-cak={MKA_CAK}\nmka-ckn={MKA_CKN}\n\
+        mode=0\nparent=eth1\nport=1\nsend-sci=true\nvalidation=2\n"
+    )));
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ChangeRisk, ErrorKind, InterfaceType, MockBackend, MockPhase, NetworkState,
+};
+
+fn gen_mock() -> MockBackend {
+    MockBackend::new(
+        serde_yaml::from_str(
+            r#"---
+            interfaces:
+            - name: eth1
+              type: ethernet
+              state: up
+            "#,
+        )
+        .unwrap(),
+    )
+}
+
+fn gen_desired() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: dummy1
+          type: dummy
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: 192.0.2.1
+              prefix-length: 24
+        routes:
+          config:
+          - destination: 198.51.100.0/24
+            next-hop-address: 192.0.2.254
+            next-hop-interface: dummy1
+        dns-resolver:
+          config:
+            server:
+            - 192.0.2.53
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_mock_apply_and_retrieve() {
+    let mut mock = gen_mock();
+    gen_desired().apply_to_mock(&mut mock).unwrap();
+
+    let mut cur_state = NetworkState::new();
+    cur_state.retrieve_from_mock(&mut mock).unwrap();
+
+    assert!(cur_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .is_some());
+    let iface = cur_state
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .unwrap();
+    assert_eq!(
+        iface
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .unwrap()
+            .addresses
+            .as_ref()
+            .unwrap()[0]
+            .ip
+            .to_string(),
+        "192.0.2.1"
+    );
+    assert_eq!(cur_state.routes.config.as_ref().unwrap().len(), 1);
+    assert_eq!(
+        cur_state.dns.config.as_ref().unwrap().server,
+        Some(vec!["192.0.2.53".to_string()])
+    );
+}
+
+#[test]
+fn test_mock_apply_absent() {
+    let mut mock = gen_mock();
+    gen_desired().apply_to_mock(&mut mock).unwrap();
+
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: dummy1
+          type: dummy
+          state: absent
+        "#,
+    )
+    .unwrap();
+    desired.apply_to_mock(&mut mock).unwrap();
+
+    assert!(mock
+        .state()
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .is_none());
+    // Routes to removed interface should be removed also
+    assert_eq!(mock.state().routes.config.as_deref(), Some(&[][..]));
+}
+
+#[test]
+fn test_mock_rollback_on_apply_failure() {
+    let mut mock = gen_mock();
+    let old_state = mock.state().clone();
+    mock.set_failure(MockPhase::Apply, ErrorKind::InvalidArgument);
+
+    let result = gen_desired().apply_to_mock(&mut mock);
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+    assert_eq!(mock.state(), &old_state);
+}
+
+#[test]
+fn test_mock_rollback_on_verify_failure() {
+    let mut mock = gen_mock();
+    let old_state = mock.state().clone();
+    mock.set_failure(MockPhase::Verify, ErrorKind::InvalidArgument);
+
+    let result = gen_desired().apply_to_mock(&mut mock);
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+    assert_eq!(mock.state(), &old_state);
+
+    mock.clear_failure();
+    gen_desired().apply_to_mock(&mut mock).unwrap();
+    assert!(mock
+        .state()
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .is_some());
+}
+
+#[test]
+fn test_mock_query_failure() {
+    let mut mock = gen_mock();
+    mock.set_failure(MockPhase::Query, ErrorKind::PermissionError);
+
+    let mut cur_state = NetworkState::new();
+    let result = cur_state.retrieve_from_mock(&mut mock);
+
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::PermissionError);
+}
+
+#[test]
+fn test_mock_gen_plan() {
+    let mut mock = MockBackend::new(
+        serde_yaml::from_str(
+            r#"---
+            interfaces:
+            - name: eth1
+              type: ethernet
+              state: up
+            - name: eth2
+              type: ethernet
+              state: up
+            "#,
+        )
+        .unwrap(),
+    );
+    let old_state = mock.state().clone();
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          mtu: 1400
+        - name: eth2
+          type: ethernet
+          state: absent
+        - name: dummy1
+          type: dummy
+          state: up
+        "#,
+    )
+    .unwrap();
+
+    let plan = desired.gen_plan_from_mock(&mut mock).unwrap();
+
+    assert_eq!(
+        plan.interfaces
+            .iter()
+            .map(|i| (i.name.as_str(), i.risk))
+            .collect::<Vec<_>>(),
+        vec![
+            ("eth2", ChangeRisk::Delete),
+            ("dummy1", ChangeRisk::Flap),
+            ("eth1", ChangeRisk::Reapply),
+        ]
+    );
+    assert_eq!(plan.max_risk(), Some(ChangeRisk::Delete));
+    assert_eq!(mock.state(), &old_state);
+}
@@ -1,16 +1,44 @@
+#[cfg(all(test, feature = "query_apply"))]
+mod backend;
 #[cfg(test)]
 mod base;
 #[cfg(test)]
+mod batadv;
+#[cfg(test)]
 mod bond;
 #[cfg(test)]
 mod bridge;
 #[cfg(test)]
+mod can;
+#[cfg(test)]
+mod canonicalize;
+#[cfg(test)]
+mod connectivity;
+#[cfg(test)]
+mod deprecation;
+#[cfg(test)]
 mod dns;
 #[cfg(test)]
+mod drift;
+#[cfg(test)]
 mod ethernet;
 #[cfg(test)]
 mod ethtool;
 #[cfg(test)]
+mod geneve;
+#[cfg(test)]
+mod gre;
+#[cfg(test)]
+mod gsm;
+#[cfg(test)]
+mod gtp;
+#[cfg(test)]
+mod guardrails;
+#[cfg(test)]
+mod ieee8021x;
+#[cfg(test)]
+mod iface_ref;
+#[cfg(test)]
 mod ifaces;
 #[cfg(test)]
 mod ifaces_ctrller;
@@ -19,24 +47,44 @@ mod infiniband;
 #[cfg(test)]
 mod ip;
 #[cfg(test)]
+mod ip_tunnel;
+#[cfg(test)]
+mod ipvlan;
+#[cfg(test)]
+mod l2tpv3;
+#[cfg(test)]
 mod lldp;
 #[cfg(test)]
 mod mac_vlan;
 #[cfg(test)]
 mod mac_vtap;
 #[cfg(test)]
+mod macsec;
+#[cfg(all(test, feature = "mock"))]
+mod mock;
+#[cfg(test)]
 mod mptcp;
 #[cfg(test)]
 mod net_state;
 #[cfg(test)]
+mod netkit;
+#[cfg(test)]
 mod nm;
 #[cfg(test)]
+mod nm_conf;
+#[cfg(test)]
+mod ovn;
+#[cfg(test)]
 mod ovs;
 #[cfg(test)]
 mod ovsdb;
 #[cfg(test)]
+mod pci;
+#[cfg(test)]
 mod policy;
 #[cfg(test)]
+mod pppoe;
+#[cfg(test)]
 mod route;
 #[cfg(test)]
 mod route_rule;
@@ -45,8 +93,20 @@ mod sriov;
 #[cfg(test)]
 mod testlib;
 #[cfg(test)]
+mod tun;
+#[cfg(test)]
+mod validation_profile;
+#[cfg(test)]
 mod vlan;
 #[cfg(test)]
 mod vrf;
 #[cfg(test)]
+mod vti;
+#[cfg(test)]
 mod vxlan;
+#[cfg(test)]
+mod wifi;
+#[cfg(test)]
+mod wireguard;
+#[cfg(test)]
+mod xfrm;
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetworkState;
+use crate::{ErrorKind, InterfaceType, NetworkState};
 
 #[test]
 fn test_invalid_top_key() {
@@ -23,3 +23,253 @@ fn test_invalid_top_type() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_parse_strict_valid_yaml_and_json() {
+    let state = NetworkState::parse_strict(
+        br#"---
+interfaces:
+- name: dummy1
+  type: dummy
+  state: up
+"#,
+    )
+    .unwrap();
+    assert!(state
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .is_some());
+
+    let state = NetworkState::parse_strict(
+        br#"{"interfaces": [{"name": "dummy1", "type": "dummy"}]}"#,
+    )
+    .unwrap();
+    assert!(state
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .is_some());
+}
+
+#[test]
+fn test_parse_strict_invalid_content() {
+    for content in [
+        &b"\xff\xfe\x00"[..],
+        b"",
+        b"- a",
+        b"invalid_key: abc",
+        b"interfaces: [{name: eth1, mtu: abc}]",
+    ] {
+        let result = NetworkState::parse_strict(content);
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_parse_strict_invalid_state() {
+    let content = br#"---
+routes:
+  config:
+  - destination: 2001:db8:a::/64
+    next-hop-address: fe80::1
+"#;
+    let result = NetworkState::parse_strict(content);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+    // Error should be deterministic
+    assert_eq!(
+        NetworkState::parse_strict(content).unwrap_err().msg(),
+        NetworkState::parse_strict(content).unwrap_err().msg(),
+    );
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_stage_only_in_kernel_mode() {
+    let mut net_state = NetworkState::new();
+    net_state.set_kernel_only(true);
+    net_state.set_stage_only(true);
+
+    let result = net_state.apply();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_no_checkpoint_with_no_commit() {
+    let mut net_state = NetworkState::new();
+    net_state.set_checkpoint(false);
+    net_state.set_commit(false);
+
+    let result = net_state.apply();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_apply_to_netns_refused() {
+    let mut net_state = NetworkState::new();
+    net_state.set_kernel_only(true);
+    net_state.set_netns(Some("ns1"));
+
+    let result = net_state.apply();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+#[cfg(feature = "query_apply")]
+fn test_verify_ignore_unrelated_changes() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+ovs-db:
+  external_ids:
+    a: A0
+"#,
+    )
+    .unwrap();
+    let new_current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: down
+ovs-db:
+  external_ids:
+    a: A1
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        crate::MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    merged_state.verify(&new_current).unwrap();
+}
+
+#[test]
+#[cfg(feature = "query_apply")]
+fn test_verify_port_failed_to_detach() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+"#,
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+- name: eth1
+  type: ethernet
+  state: up
+  controller: bond0
+- name: eth2
+  type: ethernet
+  state: up
+  controller: bond0
+"#,
+    )
+    .unwrap();
+    // The bond reports detached port while eth2 still attached
+    let new_current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+- name: eth1
+  type: ethernet
+  state: up
+  controller: bond0
+- name: eth2
+  type: ethernet
+  state: up
+  controller: bond0
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        crate::MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert!(merged_state
+        .interfaces
+        .get_verify_scope(&new_current.interfaces)
+        .contains("eth2"));
+
+    let result = merged_state.verify(&new_current);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("eth2"));
+    }
+
+    let mut new_current = new_current;
+    new_current
+        .interfaces
+        .kernel_ifaces
+        .get_mut("eth2")
+        .unwrap()
+        .base_iface_mut()
+        .controller = None;
+    merged_state.verify(&new_current).unwrap();
+}
+
+#[test]
+#[cfg(feature = "query_apply")]
+fn test_retrieve_section_in_saved_config_only_mode() {
+    let mut state = NetworkState::new();
+    state.set_saved_config_only(true);
+    let result = state.retrieve_section(crate::NetworkStateSection::Routes);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    NetkitInterface, NetkitMode, NetkitPolicy,
+};
+
+#[test]
+fn test_netkit_deserialize() {
+    let iface: NetkitInterface = serde_yaml::from_str(
+        r#"---
+name: nk0
+type: netkit
+state: up
+netkit:
+  peer: nk0.ep
+  mode: l2
+  policy: pass
+  peer-policy: drop
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.netkit.unwrap();
+    assert_eq!(conf.peer, "nk0.ep");
+    assert_eq!(conf.mode, Some(NetkitMode::L2));
+    assert_eq!(conf.policy, Some(NetkitPolicy::Pass));
+    assert_eq!(conf.peer_policy, Some(NetkitPolicy::Drop));
+}
+
+#[test]
+fn test_netkit_peer_is_itself() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: nk0
+type: netkit
+state: up
+netkit:
+  peer: nk0
+"#,
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_netkit_change_peer() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+  netkit:
+    peer: newpeer
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0.ep
+    mode: l3
+    policy: pass
+    peer-policy: pass
+- name: nk0.ep
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0
+    mode: l3
+    policy: pass
+    peer-policy: pass
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let old_peer_iface = merged_ifaces
+        .get_iface("nk0.ep", InterfaceType::Netkit)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert!(old_peer_iface.is_absent());
+
+    let merged_iface = merged_ifaces
+        .get_iface("nk0", InterfaceType::Netkit)
+        .unwrap();
+    if let (
+        Some(Interface::Netkit(apply_iface)),
+        Some(Interface::Netkit(cur_iface)),
+    ) = (
+        merged_iface.for_apply.as_ref(),
+        merged_iface.current.as_ref(),
+    ) {
+        assert!(apply_iface.is_link_changed(cur_iface));
+    } else {
+        panic!("Expecting netkit interface, got {:?}", merged_iface);
+    }
+}
+
+#[test]
+fn test_netkit_absent_remove_peer() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: absent
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0.ep
+- name: nk0.ep
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let peer_iface = merged_ifaces
+        .get_iface("nk0.ep", InterfaceType::Netkit)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert!(peer_iface.is_absent());
+}
+
+#[test]
+fn test_netkit_absent_with_peer_up() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: absent
+- name: nk0.ep
+  type: netkit
+  state: up
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0.ep
+- name: nk0.ep
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0
+"#,
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_new_netkit_without_peer_config() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+"#,
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_new_netkit_pair_with_peer_defined_by_other_end() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: nk0
+  type: netkit
+  state: up
+  netkit:
+    peer: nk0.ep
+- name: nk0.ep
+  type: netkit
+  state: up
+"#,
+    )
+    .unwrap();
+
+    assert!(
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false)
+            .is_ok()
+    );
+}
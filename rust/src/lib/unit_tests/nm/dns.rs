@@ -92,6 +92,39 @@ fn test_dns_ipv6_link_local_iface_has_ipv6_disabled() {
     }
 }
 
+#[test]
+fn test_dns_ipv6_link_local_iface_ignored() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+        dns-resolver:
+          config:
+            server:
+            - fe80::deef:1%eth1
+        "#,
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+        interfaces:
+          - name: eth1
+            type: ethernet
+            state: ignore
+            ipv6:
+              enabled: true
+              autoconf: false
+              dhcp: false
+        "#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("ignored"));
+    }
+}
+
 #[test]
 fn test_two_dns_ipv6_link_local_iface() {
     let desired: NetworkState = serde_yaml::from_str(
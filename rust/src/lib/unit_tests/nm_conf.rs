@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedNetworkState, NetworkManagerConfig, NetworkState,
+};
+
+fn gen_current() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+networkmanager:
+  dns: dnsmasq
+  no-auto-default:
+  - eth1
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_nm_conf_merge_and_remove() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  connectivity-uri: http://check.example.org/
+  no-auto-default: []
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+    let nm_conf = merged_state.networkmanager.for_apply().unwrap();
+
+    assert_eq!(nm_conf.dns.as_deref(), Some("dnsmasq"));
+    assert_eq!(
+        nm_conf.connectivity_uri.as_deref(),
+        Some("http://check.example.org/")
+    );
+    assert_eq!(nm_conf.no_auto_default, None);
+}
+
+#[test]
+fn test_nm_conf_no_change() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  dns: dnsmasq
+"#,
+    )
+    .unwrap();
+    let current = gen_current();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current.clone(), false, false)
+            .unwrap();
+
+    assert_eq!(merged_state.networkmanager.for_apply(), None);
+    merged_state
+        .networkmanager
+        .verify(current.networkmanager.as_ref())
+        .unwrap();
+}
+
+#[test]
+fn test_nm_conf_verify_fail() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  dns: systemd-resolved
+"#,
+    )
+    .unwrap();
+    let current = gen_current();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current.clone(), false, false)
+            .unwrap();
+    let result = merged_state
+        .networkmanager
+        .verify(current.networkmanager.as_ref());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_nm_conf_invalid_dns_mode() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  dns: bind
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_nm_conf_memory_only() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  dns: dnsmasq
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_nm_conf_invalid_profile_naming() {
+    for naming in ["nmstate", "site-{iface}-{name}", " {name}"] {
+        let mut desired = NetworkState::new();
+        desired.networkmanager = Some(NetworkManagerConfig {
+            profile_naming: Some(naming.to_string()),
+            ..Default::default()
+        });
+
+        let result =
+            MergedNetworkState::new(desired, gen_current(), false, false);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_nm_conf_profile_naming_from_current() {
+    let mut current = gen_current();
+    current.networkmanager.as_mut().unwrap().profile_naming =
+        Some("nmstate-prefix".to_string());
+
+    let merged_state = MergedNetworkState::new(
+        NetworkState::new(),
+        current.clone(),
+        false,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        merged_state.networkmanager.profile_naming(),
+        Some("nmstate-prefix")
+    );
+
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  profile-naming: ""
+"#,
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert_eq!(merged_state.networkmanager.profile_naming(), None);
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_nm_conf_profile_naming_gen_conf() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+networkmanager:
+  profile-naming: site-{type}-{name}
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let mut file_names: Vec<&str> = confs["NetworkManager"]
+        .iter()
+        .map(|(n, _)| n.as_str())
+        .collect();
+    file_names.sort_unstable();
+
+    assert_eq!(
+        file_names,
+        vec![
+            "site-ethernet-eth1.nmconnection",
+            "site-ovs-bridge-br0-br.nmconnection",
+            "site-ovs-port-eth1-port.nmconnection",
+        ]
+    );
+}
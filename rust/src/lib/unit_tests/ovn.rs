@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, MergedNetworkState, NetworkState, OvnConfiguration,
+    OvsDbGlobalConfig,
+};
+
+fn gen_current() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+ovs-db:
+  external_ids:
+    ovn-bridge-mappings: "physnet0:br0,physnet1:br0"
+    hostname: host1
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ovn_bridge_mappings_merge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+ovn:
+  bridge-mappings:
+  - localnet: physnet0
+    state: absent
+  - localnet: physnet2
+    bridge: br0
+  encap-ip: 192.0.2.1
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+
+    let eids = &merged_state.ovsdb.external_ids;
+    assert_eq!(
+        eids.get("ovn-bridge-mappings"),
+        Some(&Some("physnet1:br0,physnet2:br0".to_string()))
+    );
+    assert_eq!(
+        eids.get("ovn-encap-ip"),
+        Some(&Some("192.0.2.1".to_string()))
+    );
+    assert_eq!(eids.get("hostname"), Some(&Some("host1".to_string())));
+}
+
+#[test]
+fn test_ovn_bridge_mappings_remove_all() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+ovn:
+  bridge-mappings: []
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+
+    assert!(!merged_state
+        .ovsdb
+        .external_ids
+        .contains_key("ovn-bridge-mappings"));
+}
+
+#[test]
+fn test_ovn_bridge_mappings_missing_bridge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+ovn:
+  bridge-mappings:
+  - localnet: physnet2
+    bridge: br1
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ovn_conflict_with_ovsdb_external_ids() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+ovs-db:
+  external_ids:
+    ovn-encap-ip: 192.0.2.2
+ovn:
+  encap-ip: 192.0.2.1
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ovn_from_ovsdb() {
+    let ovsdb: OvsDbGlobalConfig = serde_yaml::from_str(
+        r#"---
+external_ids:
+  ovn-bridge-mappings: "physnet1:br-ex,physnet0:br0"
+  ovn-encap-ip: 2001:db8::1
+"#,
+    )
+    .unwrap();
+
+    let ovn = OvnConfiguration::from_ovsdb(&ovsdb);
+
+    assert_eq!(
+        serde_yaml::to_string(&ovn).unwrap(),
+        r#"bridge-mappings:
+- localnet: physnet0
+  bridge: br0
+- localnet: physnet1
+  bridge: br-ex
+encap-ip: 2001:db8::1
+"#
+    );
+}
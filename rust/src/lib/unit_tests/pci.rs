@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, MergedNetworkState, NetworkState};
+
+fn gen_current() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+pci-devices:
+- pci-address: 0000:3b:00.1
+  driver-override: vfio-pci
+  driver: vfio-pci
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_pci_device_for_apply() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+pci-devices:
+- pci-address: 0000:3B:00.0
+  driver-override: vfio-pci
+  driver: ixgbe
+- pci-address: 0000:3b:00.1
+  driver-override: ""
+- pci-address: 0000:3b:00.2
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+    let pci_devs = merged_state.pci_devices.for_apply();
+
+    assert_eq!(pci_devs.len(), 2);
+    assert_eq!(pci_devs[0].pci_address, "0000:3b:00.0");
+    assert_eq!(pci_devs[0].driver_override.as_deref(), Some("vfio-pci"));
+    assert_eq!(pci_devs[0].driver, None);
+    assert_eq!(pci_devs[1].pci_address, "0000:3b:00.1");
+    assert_eq!(pci_devs[1].driver_override.as_deref(), Some(""));
+}
+
+#[test]
+fn test_pci_device_invalid_address() {
+    for address in ["3b:00.1", "0000:3b:00", "0000:3g:00.1", "0000:3b:00.10"] {
+        let mut desired = NetworkState::new();
+        desired.prop_list.push("pci_devices");
+        desired.pci_devices = Some(
+            serde_yaml::from_str(&format!(
+                "- pci-address: \"{address}\"\n  driver-override: vfio-pci"
+            ))
+            .unwrap(),
+        );
+        let result =
+            MergedNetworkState::new(desired, gen_current(), false, false);
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_pci_device_duplicate() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+pci-devices:
+- pci-address: 0000:3b:00.0
+  driver-override: vfio-pci
+- pci-address: 0000:3B:00.0
+  driver-override: ""
+"#,
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, gen_current(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("defined more than once"));
+    }
+}
+
+#[test]
+fn test_pci_device_verify() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+pci-devices:
+- pci-address: 0000:3b:00.1
+  driver-override: vfio-pci
+- pci-address: 0000:3b:00.0
+  driver-override: ""
+"#,
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current(), false, false).unwrap();
+    merged_state.verify(&gen_current()).unwrap();
+
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+pci-devices:
+- pci-address: 0000:3b:00.1
+  driver-override: vfio-pci
+  driver: ixgbe
+"#,
+    )
+    .unwrap();
+    let result = merged_state.verify(&current);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
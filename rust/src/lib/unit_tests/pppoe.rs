@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    NetworkState, PppoeInterface,
+};
+
+#[test]
+fn test_pppoe_deserialize() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ppp0
+type: pppoe
+state: up
+pppoe:
+  parent: eth1
+  username: user@isp
+  password: secret
+  service: isp-service
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Pppoe);
+    assert_eq!(iface.iface_type().to_string(), "pppoe");
+    assert_eq!(iface.parent(), Some("eth1"));
+    if let Interface::Pppoe(iface) = iface {
+        let pppoe_conf = iface.pppoe.unwrap();
+        assert_eq!(pppoe_conf.username.as_deref(), Some("user@isp"));
+        assert_eq!(pppoe_conf.password.as_deref(), Some("secret"));
+        assert_eq!(pppoe_conf.service.as_deref(), Some("isp-service"));
+    } else {
+        panic!("Expecting PPPoE interface");
+    }
+}
+
+#[test]
+fn test_pppoe_empty_username() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ppp0
+type: pppoe
+state: up
+pppoe:
+  parent: eth1
+  username: ""
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_pppoe_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: ppp0
+  type: pppoe
+  state: up
+  pppoe:
+    parent: eth1
+    username: user@isp
+    password: secret
+"#,
+    )
+    .unwrap();
+    state.hide_secrets();
+
+    if let Some(Interface::Pppoe(iface)) =
+        state.interfaces.kernel_ifaces.get("ppp0")
+    {
+        let pppoe_conf = iface.pppoe.as_ref().unwrap();
+        assert_eq!(
+            pppoe_conf.password.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(pppoe_conf.username.as_deref(), Some("user@isp"));
+    } else {
+        panic!("Expecting PPPoE interface ppp0");
+    }
+}
+
+#[test]
+fn test_pppoe_up_after_parent() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: ppp0
+  type: pppoe
+  state: up
+  pppoe:
+    parent: eth1
+    username: user@isp
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let eth1_priority = merged_ifaces.kernel_ifaces["eth1"]
+        .for_apply
+        .as_ref()
+        .unwrap()
+        .base_iface()
+        .up_priority;
+    let ppp0_priority = merged_ifaces.kernel_ifaces["ppp0"]
+        .for_apply
+        .as_ref()
+        .unwrap()
+        .base_iface()
+        .up_priority;
+    assert!(eth1_priority < ppp0_priority);
+}
+
+#[test]
+fn test_pppoe_verify_ignore_password() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: ppp0
+  type: pppoe
+  state: up
+  pppoe:
+    parent: eth1
+    username: user@isp
+    password: secret
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: ppp0
+  type: pppoe
+  state: up
+  pppoe:
+    parent: eth1
+    username: user@isp
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_pppoe_update() {
+    let mut iface: PppoeInterface = serde_yaml::from_str(
+        r#"---
+name: ppp0
+type: pppoe
+state: up
+pppoe:
+  parent: eth1
+  username: user@isp
+  service: isp-service
+"#,
+    )
+    .unwrap();
+    let other: PppoeInterface = serde_yaml::from_str(
+        r#"---
+name: ppp0
+type: pppoe
+state: up
+pppoe:
+  password: secret
+"#,
+    )
+    .unwrap();
+    iface.update_pppoe(&other);
+    let pppoe_conf = iface.pppoe.unwrap();
+
+    assert_eq!(pppoe_conf.parent, "eth1");
+    assert_eq!(pppoe_conf.username.as_deref(), Some("user@isp"));
+    assert_eq!(pppoe_conf.password.as_deref(), Some("secret"));
+    assert_eq!(pppoe_conf.service.as_deref(), Some("isp-service"));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_pppoe_gen_conf() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: ppp0
+  type: pppoe
+  state: up
+  mtu: 1492
+  pppoe:
+    parent: eth1
+    username: user@isp
+    password: secret
+    service: isp-service
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=pppoe\n"));
+    assert!(content.contains(
+        "[pppoe]\nparent=eth1\npassword=secret\npassword-flags=0\n\
+        service=isp-service\nusername=user@isp\n"
+    ));
+    assert!(content.contains("[ppp]\nmtu=1492\n"));
+    assert!(!content.contains("[ethernet]"));
+}
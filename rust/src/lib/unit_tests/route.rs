@@ -3,11 +3,13 @@
 use crate::{
     unit_tests::testlib::{
         gen_merged_ifaces_for_route_test, gen_route_entry,
-        gen_test_route_entries, gen_test_routes_conf, TEST_IPV4_ADDR1,
-        TEST_IPV4_NET1, TEST_IPV6_ADDR1, TEST_IPV6_ADDR2, TEST_IPV6_NET1,
-        TEST_IPV6_NET2, TEST_NIC, TEST_ROUTE_METRIC,
+        gen_test_route_entries, gen_test_routes_conf, new_eth_iface,
+        new_test_nic_with_static_ip, TEST_IPV4_ADDR1, TEST_IPV4_NET1,
+        TEST_IPV6_ADDR1, TEST_IPV6_ADDR2, TEST_IPV6_NET1, TEST_IPV6_NET2,
+        TEST_NIC, TEST_ROUTE_METRIC,
     },
-    ErrorKind, InterfaceType, MergedRoutes, RouteEntry, RouteState, Routes,
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    MergedRoutes, RouteEntry, RouteState, Routes,
 };
 
 #[test]
@@ -18,6 +20,7 @@ fn test_sort_uniqe_routes() {
     let cur_routes = Routes {
         running: None,
         config: Some(test_routes.clone()),
+        ..Default::default()
     };
 
     let mut dup_route_entry =
@@ -33,6 +36,7 @@ fn test_sort_uniqe_routes() {
     let des_routes = Routes {
         running: None,
         config: Some(test_routes.clone()),
+        ..Default::default()
     };
 
     let merged_ifaces = gen_merged_ifaces_for_route_test();
@@ -432,3 +436,408 @@ fn test_route_matching_empty_via_with_none() {
     assert!(!absent_route.is_match(&not_match_route));
     assert!(!absent_route.is_match(&match_route));
 }
+
+#[test]
+fn test_route_absent_match_protocol() {
+    let absent_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        next-hop-interface: eth1
+        protocol: boot
+        state: absent
+        "#,
+    )
+    .unwrap();
+    let boot_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 192.0.2.0/24
+        next-hop-interface: eth1
+        protocol: boot
+        "#,
+    )
+    .unwrap();
+    let static_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 192.0.2.0/24
+        next-hop-interface: eth1
+        protocol: static
+        "#,
+    )
+    .unwrap();
+    assert!(absent_route.is_match(&boot_route));
+    assert!(!absent_route.is_match(&static_route));
+}
+
+#[test]
+fn test_route_desired_with_non_static_protocol() {
+    let routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- destination: 198.51.100.0/24
+  next-hop-address: 192.0.2.1
+  next-hop-interface: eth1
+  protocol: bgp
+"#,
+    )
+    .unwrap();
+    let result = routes.validate();
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn test_route_absent_wildcard_table_id() {
+    let absent_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        table-id: 100
+        state: absent
+        "#,
+    )
+    .unwrap();
+    let mut route = gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    route.table_id = Some(100);
+    let mut route_ipv6 =
+        gen_route_entry(TEST_IPV6_NET1, TEST_NIC, TEST_IPV6_ADDR1);
+    route_ipv6.table_id = Some(100);
+    let mut route_main_table =
+        gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    route_main_table.table_id = Some(254);
+
+    assert!(absent_route.is_match(&route));
+    assert!(absent_route.is_match(&route_ipv6));
+    assert!(!absent_route.is_match(&route_main_table));
+}
+
+#[test]
+fn test_route_absent_wildcard_metric() {
+    let absent_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        next-hop-interface: eth1
+        metric: 500
+        state: absent
+        "#,
+    )
+    .unwrap();
+    let mut route = gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    assert!(!absent_route.is_match(&route));
+    route.metric = Some(500);
+    assert!(absent_route.is_match(&route));
+}
+
+#[test]
+fn test_route_absent_wildcard_removes_all_routes_in_table() {
+    let mut cur_route_entries = gen_test_route_entries();
+    for rt in cur_route_entries.iter_mut() {
+        rt.table_id = Some(100);
+    }
+    let cur_routes = Routes {
+        running: None,
+        config: Some(cur_route_entries),
+        ..Default::default()
+    };
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- table-id: 100
+  state: absent
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces = gen_merged_ifaces_for_route_test();
+    let merged_routes =
+        MergedRoutes::new(des_routes, cur_routes, &merged_ifaces).unwrap();
+
+    assert_eq!(
+        merged_routes.route_changed_ifaces,
+        vec![TEST_NIC.to_string()]
+    );
+    assert!(merged_routes.indexed.is_empty());
+}
+
+#[test]
+fn test_route_link_local_next_hop_without_iface() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- destination: ::/0
+  next-hop-address: fe80::1
+"#,
+    )
+    .unwrap();
+    let result = des_routes.validate();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_link_local_next_hop_with_scope_id() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- destination: ::/0
+  next-hop-address: FE80::1%eth1
+"#,
+    )
+    .unwrap();
+    des_routes.validate().unwrap();
+    let mut route = des_routes.config.unwrap()[0].clone();
+    route.sanitize().unwrap();
+    assert_eq!(route.next_hop_addr.as_deref(), Some("fe80::1"));
+    assert_eq!(route.next_hop_iface.as_deref(), Some(TEST_NIC));
+
+    let mut cur_route = gen_route_entry("::/0", TEST_NIC, "fe80::1");
+    cur_route.metric = Some(1024);
+    assert!(route.is_match(&cur_route));
+}
+
+#[test]
+fn test_route_link_local_next_hop_scope_id_mismatch() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- destination: ::/0
+  next-hop-interface: eth2
+  next-hop-address: fe80::1%eth1
+"#,
+    )
+    .unwrap();
+    let result = des_routes.validate();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_scope_id_on_global_next_hop() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+config:
+- destination: ::/0
+  next-hop-address: 2001:db8:1::1%eth1
+"#,
+    )
+    .unwrap();
+    let result = des_routes.validate();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_routes_sort_for_output() {
+    let mut routes: Routes = serde_yaml::from_str(
+        r#"
+        running:
+        - destination: 198.51.100.0/24
+          next-hop-interface: eth1
+          metric: 200
+          table-id: 254
+        - destination: 0.0.0.0/0
+          next-hop-interface: eth1
+          metric: 100
+          table-id: 254
+        - destination: 198.51.100.0/24
+          next-hop-interface: eth1
+          metric: 100
+          table-id: 254
+        - destination: 0.0.0.0/0
+          next-hop-interface: eth1
+          metric: 100
+          table-id: 100
+        "#,
+    )
+    .unwrap();
+
+    routes.sort_for_output();
+
+    let rts = routes.running.unwrap();
+    assert_eq!(rts[0].table_id, Some(100));
+    assert_eq!(rts[1].destination.as_deref(), Some("0.0.0.0/0"));
+    assert_eq!(rts[2].metric, Some(100));
+    assert_eq!(rts[3].metric, Some(200));
+}
+
+#[test]
+fn test_route_wildcard_absent_preserve_external_route() {
+    let mut current = Interfaces::new();
+    let mut cur_iface = new_test_nic_with_static_ip();
+    let owned_rt = gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    if let Some(ipv4) = cur_iface.base_iface_mut().ipv4.as_mut() {
+        ipv4.owned_routes = Some(vec![owned_rt.clone()]);
+    }
+    if let Some(ipv6) = cur_iface.base_iface_mut().ipv6.as_mut() {
+        ipv6.owned_routes = Some(Vec::new());
+    }
+    current.push(cur_iface);
+    let mut desired = Interfaces::new();
+    desired.push(new_test_nic_with_static_ip());
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+
+    let external_rt =
+        gen_route_entry(TEST_IPV6_NET2, TEST_NIC, TEST_IPV6_ADDR2);
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![owned_rt, external_rt.clone()]),
+        ..Default::default()
+    };
+
+    let mut absent_route = RouteEntry::new();
+    absent_route.state = Some(RouteState::Absent);
+    absent_route.next_hop_iface = Some(TEST_NIC.to_string());
+    let mut absent_routes = Routes::new();
+    absent_routes.config = Some(vec![absent_route]);
+
+    let merged_routes =
+        MergedRoutes::new(absent_routes, cur_routes, &merged_ifaces).unwrap();
+
+    assert!(!merged_routes.indexed.contains_key(TEST_NIC));
+    assert_eq!(merged_routes.external, vec![external_rt.clone()]);
+
+    let after_apply = Routes {
+        running: None,
+        config: Some(vec![external_rt]),
+        ..Default::default()
+    };
+    merged_routes.verify(&after_apply, &[]).unwrap();
+}
+
+fn new_test_nic_with_exact_ip() -> Interface {
+    let mut iface = new_test_nic_with_static_ip();
+    if let Some(ipv4) = iface.base_iface_mut().ipv4.as_mut() {
+        ipv4.exact = Some(true);
+    }
+    if let Some(ipv6) = iface.base_iface_mut().ipv6.as_mut() {
+        ipv6.exact = Some(true);
+    }
+    iface
+}
+
+#[test]
+fn test_route_exact_remove_undesired_routes() {
+    let desired_rt = gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![
+            desired_rt.clone(),
+            gen_route_entry(TEST_IPV6_NET2, TEST_NIC, TEST_IPV6_ADDR2),
+            gen_route_entry(TEST_IPV6_NET1, "eth2", TEST_IPV6_ADDR1),
+        ]),
+        ..Default::default()
+    };
+    let mut des_routes = Routes::new();
+    des_routes.config = Some(vec![desired_rt.clone()]);
+
+    let mut eth2_iface = new_test_nic_with_static_ip();
+    eth2_iface.base_iface_mut().name = "eth2".to_string();
+    let mut current = Interfaces::new();
+    current.push(new_eth_iface(TEST_NIC));
+    current.push(eth2_iface);
+    let mut desired = Interfaces::new();
+    desired.push(new_test_nic_with_exact_ip());
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+    let merged_routes =
+        MergedRoutes::new(des_routes, cur_routes, &merged_ifaces).unwrap();
+
+    assert_eq!(merged_routes.indexed[TEST_NIC], vec![desired_rt]);
+    assert_eq!(merged_routes.indexed["eth2"].len(), 1);
+    assert_eq!(
+        merged_routes.route_changed_ifaces,
+        vec![TEST_NIC.to_string()]
+    );
+}
+
+#[test]
+fn test_route_exact_wildcard_absent_remove_external_route() {
+    let mut current = Interfaces::new();
+    let mut cur_iface = new_test_nic_with_static_ip();
+    if let Some(ipv6) = cur_iface.base_iface_mut().ipv6.as_mut() {
+        ipv6.owned_routes = Some(Vec::new());
+    }
+    current.push(cur_iface);
+    let mut desired = Interfaces::new();
+    desired.push(new_test_nic_with_exact_ip());
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![gen_route_entry(
+            TEST_IPV6_NET2,
+            TEST_NIC,
+            TEST_IPV6_ADDR2,
+        )]),
+        ..Default::default()
+    };
+    let mut absent_route = RouteEntry::new();
+    absent_route.state = Some(RouteState::Absent);
+    absent_route.next_hop_iface = Some(TEST_NIC.to_string());
+    let mut absent_routes = Routes::new();
+    absent_routes.config = Some(vec![absent_route]);
+
+    let merged_routes =
+        MergedRoutes::new(absent_routes, cur_routes, &merged_ifaces).unwrap();
+
+    assert!(!merged_routes.indexed.contains_key(TEST_NIC));
+    assert!(merged_routes.external.is_empty());
+    assert_eq!(
+        merged_routes.route_changed_ifaces,
+        vec![TEST_NIC.to_string()]
+    );
+}
+
+#[test]
+fn test_route_absent_without_protocol_match_static_only() {
+    let absent_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        next-hop-interface: eth1
+        state: absent
+        "#,
+    )
+    .unwrap();
+    let static_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 192.0.2.0/24
+        next-hop-interface: eth1
+        protocol: static
+        "#,
+    )
+    .unwrap();
+    let bgp_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        protocol: bgp
+        "#,
+    )
+    .unwrap();
+    assert!(absent_route.is_match(&static_route));
+    assert!(!absent_route.is_match(&bgp_route));
+}
+
+#[test]
+fn test_route_exact_without_routes_section_keep_routes() {
+    let cur_rt = gen_route_entry(TEST_IPV6_NET2, TEST_NIC, TEST_IPV6_ADDR2);
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![cur_rt.clone()]),
+        ..Default::default()
+    };
+    let mut current = Interfaces::new();
+    current.push(new_eth_iface(TEST_NIC));
+    let mut desired = Interfaces::new();
+    desired.push(new_test_nic_with_exact_ip());
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+
+    let merged_routes =
+        MergedRoutes::new(Routes::new(), cur_routes, &merged_ifaces).unwrap();
+
+    assert_eq!(merged_routes.indexed[TEST_NIC], vec![cur_rt]);
+    assert!(merged_routes.route_changed_ifaces.is_empty());
+}
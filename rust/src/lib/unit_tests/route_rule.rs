@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    unit_tests::testlib::gen_test_rule_entries, MergedRouteRules,
+    unit_tests::testlib::gen_test_rule_entries, ErrorKind, MergedRouteRules,
     RouteRuleEntry, RouteRules,
 };
 
@@ -320,3 +320,93 @@ fn test_route_rule_auto_priority_increasing_from_empty() {
     );
     assert_eq!(rules[2].priority, Some(30002));
 }
+
+#[test]
+fn test_route_rule_absent_priority_range() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - state: absent
+          priority-range:
+            min: 100
+            max: 200
+        "#,
+    )
+    .unwrap();
+    let cur_rules: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - ip-to: 192.168.2.30/32
+          priority: 99
+          route-table: 200
+          family: ipv4
+        - ip-to: 192.168.2.31/32
+          priority: 100
+          route-table: 200
+          family: ipv4
+        - ip-to: 192.168.2.32/32
+          priority: 200
+          route-table: 200
+          family: ipv4
+        - ip-to: 192.168.2.33/32
+          priority: 201
+          route-table: 200
+          family: ipv4
+        "#,
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(des_rules, cur_rules).unwrap();
+
+    let mut rules = merged.for_apply;
+    rules.sort_unstable();
+
+    assert_eq!(rules.len(), 2);
+    assert!(rules[0].is_absent());
+    assert_eq!(rules[0].priority, Some(100));
+    assert!(rules[1].is_absent());
+    assert_eq!(rules[1].priority, Some(200));
+}
+
+#[test]
+fn test_route_rule_priority_range_not_allowed_in_non_absent() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - ip-to: 192.168.2.30
+          route-table: 200
+          priority-range:
+            min: 100
+        "#,
+    )
+    .unwrap();
+
+    let result = MergedRouteRules::new(des_rules, RouteRules::new());
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn test_route_rules_sort_for_output() {
+    let mut rules: RouteRules = serde_yaml::from_str(
+        r#"
+        config:
+        - ip-from: 192.0.2.0/24
+          priority: 1002
+          route-table: 100
+        - ip-to: 2001:db8::/64
+          priority: 1000
+          route-table: 100
+        - ip-from: 198.51.100.0/24
+          priority: 1001
+          route-table: 100
+        "#,
+    )
+    .unwrap();
+
+    rules.sort_for_output();
+
+    let priorities: Vec<Option<i64>> =
+        rules.config.unwrap().iter().map(|r| r.priority).collect();
+    assert_eq!(priorities, vec![Some(1000), Some(1001), Some(1002)]);
+}
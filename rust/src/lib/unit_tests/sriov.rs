@@ -344,6 +344,132 @@ fn test_resolve_sriov_port_name_ovs_bond() {
     assert_eq!(ports, vec!["eth2", "eth1v1"]);
 }
 
+fn gen_sriov_switchdev_current_ifaces() -> Interfaces {
+    serde_yaml::from_str::<Interfaces>(
+        r#"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ethernet:
+            sr-iov:
+              total-vfs: 2
+              vfs:
+              - id: 0
+                representor: eth1_0
+              - id: 1
+        - name: eth1_0
+          type: ethernet
+          state: up
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_resolve_sriov_representor_name() {
+    let current = gen_sriov_switchdev_current_ifaces();
+    let mut desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+        - name: sriov-rep:eth1:0
+          type: ethernet
+          state: up
+          mtu: 9000
+        - name: ovs-br0
+          type: ovs-bridge
+          state: up
+          bridge:
+            port:
+            - name: sriov-rep:eth1:0
+        "#,
+    )
+    .unwrap();
+    desired.resolve_sriov_reference(&current).unwrap();
+    let rep_iface = desired
+        .get_iface("eth1_0", InterfaceType::Ethernet)
+        .unwrap();
+    let br_iface = desired
+        .get_iface("ovs-br0", InterfaceType::OvsBridge)
+        .unwrap();
+    assert_eq!(rep_iface.base_iface().mtu, Some(9000));
+    assert_eq!(br_iface.ports().unwrap(), vec!["eth1_0"]);
+}
+
+#[test]
+fn test_failed_to_resolve_sriov_representor_name() {
+    let current = gen_sriov_switchdev_current_ifaces();
+    let mut desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+        - name: sriov-rep:eth1:1
+          type: ethernet
+          state: up
+        "#,
+    )
+    .unwrap();
+    let result = desired.resolve_sriov_reference(&current);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("representor"));
+    }
+}
+
+#[test]
+fn test_sriov_vf_query_only_props_ignored_in_desired() {
+    let mut iface = serde_yaml::from_str::<Interface>(
+        r#"---
+        name: eth1
+        type: ethernet
+        state: up
+        ethernet:
+          sr-iov:
+            total-vfs: 2
+            vfs:
+            - id: 0
+              representor: eth1_0
+              pci-address: 0000:3b:02.0
+              driver: vfio-pci
+        "#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    if let Interface::Ethernet(eth_iface) = iface {
+        let vfs = eth_iface.ethernet.unwrap().sr_iov.unwrap().vfs.unwrap();
+        assert_eq!(vfs[0].representor, None);
+        assert_eq!(vfs[0].pci_address, None);
+        assert_eq!(vfs[0].driver, None);
+    } else {
+        panic!("Expecting ethernet interface, got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_sriov_vf_min_tx_rate_larger_than_max() {
+    let mut iface = serde_yaml::from_str::<Interface>(
+        r#"---
+        name: eth1
+        type: ethernet
+        state: up
+        ethernet:
+          sr-iov:
+            total-vfs: 2
+            vfs:
+            - id: 0
+              min-tx-rate: 100
+              max-tx-rate: 0
+            - id: 1
+              min-tx-rate: 200
+              max-tx-rate: 100
+        "#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("VF 1 of interface eth1"));
+    }
+}
+
 #[test]
 fn test_verify_sriov_port_name_linux_bridge() {
     let pre_apply_current = gen_sriov_current_ifaces();
@@ -789,3 +915,73 @@ fn test_sriov_vf_revert_to_default() {
         panic!("Expecting a Ethernet interface, but got {:?}", verify_iface);
     }
 }
+
+#[test]
+fn test_verify_sriov_eswitch_mode() {
+    let gen_ifaces = |mode: &str| {
+        serde_yaml::from_str::<Interfaces>(&format!(
+            r#"---
+            - name: eth1
+              type: ethernet
+              state: up
+              ethernet:
+                sr-iov:
+                  total-vfs: 0
+                  eswitch-mode: {mode}
+            "#
+        ))
+        .unwrap()
+    };
+    let current = gen_ifaces("legacy");
+    let desired = gen_ifaces("switchdev");
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current.clone(), false, false).unwrap();
+
+    let result = merged_ifaces.verify(&current);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::VerificationError);
+    merged_ifaces.verify(&gen_ifaces("switchdev")).unwrap();
+}
+
+#[test]
+fn test_sriov_vfs_to_remove_on_total_vfs_decrease() {
+    let current = serde_yaml::from_str::<Interfaces>(
+        r#"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ethernet:
+            sr-iov:
+              total-vfs: 4
+              vfs:
+              - id: 0
+              - id: 1
+              - id: 2
+              - id: 3
+        "#,
+    )
+    .unwrap();
+    let desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+        - name: eth1
+          type: ethernet
+          state: up
+          ethernet:
+            sr-iov:
+              total-vfs: 2
+        "#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+
+    let vfs_to_remove = merged_ifaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap()
+        .get_sriov_vfs_to_remove();
+
+    assert_eq!(
+        vfs_to_remove.iter().map(|vf| vf.id).collect::<Vec<u32>>(),
+        vec![2, 3]
+    );
+}
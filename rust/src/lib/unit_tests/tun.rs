@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, InterfaceType, TunInterface, TunMode};
+
+#[test]
+fn test_tun_stringlized_attributes() {
+    let iface: TunInterface = serde_yaml::from_str(
+        r#"---
+name: tap0
+type: tun
+state: up
+tun:
+  mode: tap
+  owner: "1000"
+  group: "1001"
+  multi-queue: "true"
+  pi: "false"
+"#,
+    )
+    .unwrap();
+    let tun_conf = iface.tun.unwrap();
+
+    assert_eq!(tun_conf.mode, Some(TunMode::Tap));
+    assert_eq!(tun_conf.owner, Some(1000));
+    assert_eq!(tun_conf.group, Some(1001));
+    assert_eq!(tun_conf.multi_queue, Some(true));
+    assert_eq!(tun_conf.pi, Some(false));
+}
+
+#[test]
+fn test_tun_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: tun0
+type: tun
+state: up
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Tun);
+    assert_eq!(iface.iface_type().to_string(), "tun");
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_tun_gen_conf_tun_section() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: tap0
+  type: tun
+  state: up
+  tun:
+    mode: tap
+    owner: 1000
+    multi-queue: true
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=tun\n"));
+    assert!(content.contains("[tun]\nmode=2\nmulti-queue=true\nowner=1000\n"));
+}
@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetworkState, ValidationProfile};
+
+const DUAL_STACK_STATE: &str = r#"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.53
+    - 2001:db8::53
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: true
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 198.51.100.1
+      prefix-length: 24
+  ipv6:
+    enabled: false
+"#;
+
+#[test]
+fn test_validate_profile_ipv6_only() {
+    let net_state: NetworkState =
+        serde_yaml::from_str(DUAL_STACK_STATE).unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::Ipv6Only);
+
+    let reasons: Vec<(Option<&str>, &str)> = violations
+        .iter()
+        .map(|v| (v.interface.as_deref(), v.reason.as_str()))
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (Some("eth1"), "DHCPv4 is enabled"),
+            (Some("eth2"), "Static IPv4 address is configured"),
+            (Some("eth2"), "IPv6 is disabled"),
+            (None, "IPv4 DNS server 192.0.2.53 is configured"),
+            (
+                Some("eth1"),
+                "IPv4 route 'destination: 0.0.0.0/0 next-hop-interface: eth1 \
+                next-hop-address: 192.0.2.254' found"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_profile_dual_stack() {
+    let net_state: NetworkState =
+        serde_yaml::from_str(DUAL_STACK_STATE).unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::DualStack);
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].interface.as_deref(), Some("eth2"));
+}
+
+#[test]
+fn test_validate_profile_ipv6_missing_ra() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: false
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: false
+"#,
+    )
+    .unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::Ipv6Only);
+
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].reason.contains("router advertisement"));
+}
+
+#[test]
+fn test_validate_profile_dpdk_no_dpdk_iface() {
+    let net_state: NetworkState =
+        serde_yaml::from_str(DUAL_STACK_STATE).unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::DpdkReady);
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].interface, None);
+    assert_eq!(violations[0].reason, "No OVS DPDK interface is configured");
+}
+
+#[test]
+fn test_validate_profile_dpdk_ready() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+ovs-db:
+  other_config:
+    dpdk-init: "true"
+pci-devices:
+- pci-address: 0000:3B:00.1
+  driver-override: vfio-pci
+interfaces:
+- name: ovs0
+  type: ovs-interface
+  state: up
+  dpdk:
+    devargs: "0000:3b:00.1,txq_inline=128"
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    options:
+      datapath: netdev
+    port:
+    - name: ovs0
+"#,
+    )
+    .unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::DpdkReady);
+
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_validate_profile_dpdk_not_ready() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+ovs-db:
+  other_config:
+    hw-offload: "true"
+pci-devices:
+- pci-address: 0000:3b:00.2
+  driver-override: vfio-pci
+  driver: ixgbevf
+interfaces:
+- name: ovs0
+  type: ovs-interface
+  state: up
+  dpdk:
+    devargs: "0000:3b:00.1"
+- name: ovs1
+  type: ovs-interface
+  state: up
+  dpdk:
+    devargs: "0000:3b:00.2"
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: ovs0
+    - name: ovs1
+- name: eth1
+  type: ethernet
+  state: up
+  ethernet:
+    sr-iov:
+      total-vfs: 1
+      vfs:
+      - id: 0
+        pci-address: 0000:3b:00.2
+"#,
+    )
+    .unwrap();
+
+    let violations = net_state.validate_profile(ValidationProfile::DpdkReady);
+
+    let reasons: Vec<(Option<&str>, &str)> = violations
+        .iter()
+        .map(|v| (v.interface.as_deref(), v.reason.as_str()))
+        .collect();
+    assert_eq!(reasons.len(), 6);
+    assert_eq!(reasons[0].0, None);
+    assert!(reasons[0].1.contains("dpdk-init"));
+    assert_eq!(reasons[1].0, Some("br0"));
+    assert!(reasons[1].1.contains("ovs0"));
+    assert_eq!(reasons[2].0, Some("br0"));
+    assert!(reasons[2].1.contains("ovs1"));
+    assert_eq!(reasons[3].0, Some("ovs0"));
+    assert!(reasons[3].1.contains("driver-override: vfio-pci"));
+    assert_eq!(reasons[4].0, Some("ovs1"));
+    assert!(reasons[4].1.contains("bound to ixgbevf"));
+    assert!(reasons[4].1.contains("intel_iommu=on"));
+    assert_eq!(reasons[5].0, Some("eth1"));
+    assert!(reasons[5].1.contains("switchdev"));
+}
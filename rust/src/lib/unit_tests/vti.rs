@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, InterfaceType, VtiInterface};
+
+#[test]
+fn test_vti_stringlized_attributes() {
+    let iface: VtiInterface = serde_yaml::from_str(
+        r#"---
+name: vti1
+type: vti
+state: up
+vti:
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  ikey: "100"
+  okey: "200"
+"#,
+    )
+    .unwrap();
+    let vti_conf = iface.vti.unwrap();
+
+    assert_eq!(vti_conf.ikey, Some(100));
+    assert_eq!(vti_conf.okey, Some(200));
+}
+
+#[test]
+fn test_vti6_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: vti6-1
+type: vti6
+state: up
+vti6:
+  local: 2001:db8::1
+  remote: 2001:db8::2
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Vti6);
+    assert_eq!(iface.iface_type().to_string(), "vti6");
+}
+
+#[test]
+fn test_vti_ipv6_address_not_allowed() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: vti1
+type: vti
+state: up
+vti:
+  local: 192.0.2.1
+  remote: 2001:db8::2
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_vti6_ipv4_address_not_allowed() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: vti6-1
+type: vti6
+state: up
+vti6:
+  local: 192.0.2.1
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_vti_update() {
+    let mut iface: VtiInterface = serde_yaml::from_str(
+        r#"---
+name: vti1
+type: vti
+state: up
+vti:
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  ikey: 100
+"#,
+    )
+    .unwrap();
+    let other: VtiInterface = serde_yaml::from_str(
+        r#"---
+name: vti1
+type: vti
+state: up
+vti:
+  ikey: 101
+  okey: 101
+"#,
+    )
+    .unwrap();
+    iface.update_vti(&other);
+    let vti_conf = iface.vti.unwrap();
+
+    assert_eq!(vti_conf.local, Some("192.0.2.1".parse().unwrap()));
+    assert_eq!(vti_conf.remote, Some("192.0.2.2".parse().unwrap()));
+    assert_eq!(vti_conf.ikey, Some(101));
+    assert_eq!(vti_conf.okey, Some(101));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_vti_gen_conf() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: vti1
+  type: vti
+  state: up
+  vti:
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    ikey: 100
+    okey: 100
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=ip-tunnel\n"));
+    assert!(content.contains(
+        "[ip-tunnel]\ninput-key=100\nlocal=192.0.2.1\nmode=5\n\
+        output-key=100\nremote=192.0.2.2\n"
+    ));
+    assert!(!content.contains("[ethernet]"));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_vti6_gen_conf_ip_tunnel_mode() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: vti6-1
+  type: vti6
+  state: up
+  vti6:
+    local: 2001:db8::1
+    remote: 2001:db8::2
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains(
+        "[ip-tunnel]\nlocal=2001:db8::1\nmode=9\nremote=2001:db8::2\n"
+    ));
+}
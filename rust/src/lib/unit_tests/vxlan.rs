@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::VxlanInterface;
+use crate::{Interface, VxlanInterface};
 
 #[test]
 fn test_vxlan_stringlized_attributes() {
@@ -47,3 +47,36 @@ vxlan:
         Some(std::net::IpAddr::V4("1.2.3.4".parse().unwrap()))
     );
 }
+
+#[test]
+fn test_vxlan_flood_remotes_sorted() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: vxlan1
+type: vxlan
+state: up
+vxlan:
+  id: 101
+  local: 192.0.2.250
+  flood-remotes:
+  - 192.0.2.3
+  - 192.0.2.1
+  - 192.0.2.3
+"#,
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    let vxlan_conf = if let Interface::Vxlan(vxlan_iface) = iface {
+        vxlan_iface.vxlan.unwrap()
+    } else {
+        panic!("Should be VXLAN interface");
+    };
+
+    assert_eq!(
+        vxlan_conf.flood_remotes,
+        Some(vec![
+            "192.0.2.1".parse().unwrap(),
+            "192.0.2.3".parse().unwrap()
+        ])
+    );
+}
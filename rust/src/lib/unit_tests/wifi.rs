@@ -0,0 +1,510 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    NetworkState, WifiBand, WifiInterface, WifiKeyMgmt, WifiMode,
+    WifiSecretFlag,
+};
+
+#[test]
+fn test_wifi_deserialize() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: edge-net
+  band: 5ghz
+  hidden: "true"
+  security:
+    key-mgmt: wpa-psk
+    psk: "12345678"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Wifi);
+    assert_eq!(iface.iface_type().to_string(), "wifi");
+    assert!(!iface.is_virtual());
+    if let Interface::Wifi(iface) = iface {
+        let wifi_conf = iface.wifi.unwrap();
+        assert_eq!(wifi_conf.ssid, "edge-net");
+        assert_eq!(wifi_conf.band, Some(WifiBand::Band5G));
+        assert_eq!(wifi_conf.hidden, Some(true));
+        let sec_conf = wifi_conf.security.unwrap();
+        assert_eq!(sec_conf.key_mgmt, WifiKeyMgmt::WpaPsk);
+        assert_eq!(sec_conf.psk.as_deref(), Some("12345678"));
+    } else {
+        panic!("Expecting Wi-Fi interface");
+    }
+}
+
+#[test]
+fn test_wifi_invalid_psk() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: wpa-psk
+    psk: "1234"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("psk"));
+    }
+}
+
+#[test]
+fn test_wifi_psk_with_wpa_eap() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: wpa-eap
+    psk: "12345678"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_wifi_ssid_too_long() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: abcdefghijklmnopqrstuvwxyz0123456789
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_wifi_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    security:
+      key-mgmt: wpa-psk
+      psk: "12345678"
+"#,
+    )
+    .unwrap();
+    state.hide_secrets();
+
+    if let Some(Interface::Wifi(iface)) =
+        state.interfaces.kernel_ifaces.get("wlan0")
+    {
+        let sec_conf = iface.wifi.as_ref().unwrap().security.as_ref().unwrap();
+        assert_eq!(
+            sec_conf.psk.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+    } else {
+        panic!("Expecting Wi-Fi interface wlan0");
+    }
+}
+
+#[test]
+fn test_wifi_verify_ignore_psk() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    security:
+      key-mgmt: wpa-psk
+      psk: "12345678"
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    hidden: false
+    security:
+      key-mgmt: wpa-psk
+      psk: <_password_hid_by_nmstate>
+"#,
+    )
+    .unwrap();
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_wifi_update_keep_security() {
+    let mut iface: WifiInterface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: wpa-eap
+"#,
+    )
+    .unwrap();
+    let other: WifiInterface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+wifi:
+  band: 2.4ghz
+"#,
+    )
+    .unwrap();
+    iface.update_wifi(&other);
+    let wifi_conf = iface.wifi.unwrap();
+
+    assert_eq!(wifi_conf.ssid, "edge-net");
+    assert_eq!(wifi_conf.band, Some(WifiBand::Band2G4));
+    assert_eq!(
+        wifi_conf.security.map(|s| s.key_mgmt),
+        Some(WifiKeyMgmt::WpaEap)
+    );
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_wifi_gen_conf() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    band: 5ghz
+    hidden: true
+    security:
+      key-mgmt: wpa-psk
+      psk: "12345678"
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=802-11-wireless\n"));
+    assert!(content.contains(
+        "[wifi]\nband=a\nhidden=true\nmode=infrastructure\nssid=edge-net\n"
+    ));
+    assert!(
+        content.contains("[wifi-security]\nkey-mgmt=wpa-psk\npsk-flags=1\n")
+    );
+    assert!(!content.contains("[ethernet]"));
+}
+
+#[test]
+fn test_wifi_ft_key_mgmt_rejected() {
+    for (ft_key_mgmt, key_mgmt) in [
+        ("ft-psk", "wpa-psk"),
+        ("ft-sae", "sae"),
+        ("ft-eap", "wpa-eap"),
+    ] {
+        let result = NetworkState::new_from_yaml(&format!(
+            r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  wifi:
+    ssid: edge-net
+    security:
+      key-mgmt: {ft_key_mgmt}
+"#
+        ));
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+            assert!(e.msg().contains(ft_key_mgmt));
+            assert!(e.msg().contains(&format!("please use {key_mgmt}")));
+        }
+    }
+}
+
+#[test]
+fn test_wifi_key_mgmt_serialize() {
+    let iface: WifiInterface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: wpa-eap-suite-b-192
+"#,
+    )
+    .unwrap();
+
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("key-mgmt: wpa-eap-suite-b-192"));
+}
+
+#[test]
+fn test_wifi_psk_with_wpa_eap_suite_b_192() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: wpa-eap-suite-b-192
+    psk: "12345678"
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("wpa-eap-suite-b-192"));
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_wifi_gen_conf_sae() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    security:
+      key-mgmt: sae
+      psk: "wpa3"
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(
+        content.contains("[wifi-security]\nkey-mgmt=sae\npmf=3\npsk-flags=1\n")
+    );
+}
+
+#[test]
+fn test_wifi_ap_deserialize() {
+    let iface: WifiInterface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+wifi:
+  mode: ap
+  ssid: edge-mgmt
+  band: 2.4ghz
+  channel: "6"
+"#,
+    )
+    .unwrap();
+    let wifi_conf = iface.wifi.unwrap();
+
+    assert_eq!(wifi_conf.mode, Some(WifiMode::Ap));
+    assert_eq!(wifi_conf.band, Some(WifiBand::Band2G4));
+    assert_eq!(wifi_conf.channel, Some(6));
+}
+
+#[test]
+fn test_wifi_channel_without_band() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  ssid: edge-net
+  channel: 6
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("band"));
+    }
+}
+
+#[test]
+fn test_wifi_ap_with_wpa_eap() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+wifi:
+  mode: ap
+  ssid: edge-mgmt
+  security:
+    key-mgmt: wpa-eap
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("wpa-eap"));
+    }
+}
+
+#[test]
+fn test_wifi_ap_with_dhcp() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+state: up
+ipv4:
+  enabled: true
+  dhcp: true
+wifi:
+  mode: ap
+  ssid: edge-mgmt
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("DHCP"));
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_wifi_gen_conf_ap() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  state: up
+  ipv4:
+    enabled: true
+  wifi:
+    mode: ap
+    ssid: edge-mgmt
+    band: 2.4ghz
+    channel: 6
+    security:
+      key-mgmt: wpa-psk
+      psk: "12345678"
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content
+        .contains("[wifi]\nband=bg\nchannel=6\nmode=ap\nssid=edge-mgmt\n"));
+    assert!(content.contains("[ipv4]\n"));
+    assert!(content.contains("method=shared\n"));
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_wifi_gen_conf_psk_not_saved() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wlan0
+  type: wifi
+  state: up
+  wifi:
+    ssid: edge-net
+    security:
+      key-mgmt: wpa-psk
+      psk: "12345678"
+      psk-flags: not-saved
+"#,
+    )
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(
+        content.contains("[wifi-security]\nkey-mgmt=wpa-psk\npsk-flags=2\n")
+    );
+    assert!(!content.contains("12345678"));
+}
+
+#[test]
+fn test_wifi_psk_flags_deserialize() {
+    let iface: WifiInterface = serde_yaml::from_str(
+        r#"---
+name: wlan0
+type: wifi
+wifi:
+  ssid: edge-net
+  security:
+    key-mgmt: sae
+    psk-flags: agent-owned
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        iface
+            .wifi
+            .and_then(|c| c.security)
+            .and_then(|s| s.psk_flags),
+        Some(WifiSecretFlag::AgentOwned)
+    );
+}
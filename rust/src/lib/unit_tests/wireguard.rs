@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, NetworkState, WireGuardInterface};
+
+const PEER1_PUB_KEY: &str = "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=";
+const PEER2_PUB_KEY: &str = "TrMvSoP4jYQlY6RIzBgbssQqY3vxI2Pi+y71lOWWXX0=";
+
+#[test]
+fn test_wireguard_stringlized_attributes() {
+    let iface: WireGuardInterface = serde_yaml::from_str(&format!(
+        r#"---
+name: wg0
+type: wireguard
+state: up
+wireguard:
+  listen-port: "51820"
+  fwmark: "100"
+  peers:
+  - public-key: {PEER1_PUB_KEY}
+    persistent-keepalive: "25"
+"#
+    ))
+    .unwrap();
+    let wg_conf = iface.wireguard.unwrap();
+
+    assert_eq!(wg_conf.listen_port, Some(51820));
+    assert_eq!(wg_conf.fwmark, Some(100));
+    assert_eq!(wg_conf.peers.unwrap()[0].persistent_keepalive, Some(25));
+}
+
+#[test]
+fn test_wireguard_allowed_ips_sanitized() {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: wg0
+type: wireguard
+state: up
+wireguard:
+  peers:
+  - public-key: {PEER1_PUB_KEY}
+    allowed-ips:
+    - 10.0.0.2
+    - 2001:db8:0::/64
+"#
+    ))
+    .unwrap();
+    iface.sanitize(true).unwrap();
+
+    if let Interface::WireGuard(iface) = iface {
+        let peers = iface.wireguard.unwrap().peers.unwrap();
+        assert_eq!(
+            peers[0].allowed_ips.as_deref(),
+            Some(
+                vec!["10.0.0.2/32".to_string(), "2001:db8::/64".to_string()]
+                    .as_slice()
+            )
+        );
+    } else {
+        panic!("Expecting WireGuard interface, got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_wireguard_invalid_allowed_ips() {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: wg0
+type: wireguard
+state: up
+wireguard:
+  peers:
+  - public-key: {PEER1_PUB_KEY}
+    allowed-ips:
+    - 10.0.0.256/32
+"#
+    ))
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_wireguard_duplicate_peers() {
+    let mut iface: Interface = serde_yaml::from_str(&format!(
+        r#"---
+name: wg0
+type: wireguard
+state: up
+wireguard:
+  peers:
+  - public-key: {PEER1_PUB_KEY}
+  - public-key: {PEER2_PUB_KEY}
+  - public-key: {PEER1_PUB_KEY}
+"#
+    ))
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+        assert!(e.msg().contains(PEER1_PUB_KEY));
+    }
+}
+
+#[test]
+fn test_wireguard_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(&format!(
+        r#"---
+interfaces:
+- name: wg0
+  type: wireguard
+  state: up
+  wireguard:
+    private-key: cFYlcO7Lri1pMWUybomXzDWKsqxJFLUUxqWB8ilJgGQ=
+    peers:
+    - public-key: {PEER1_PUB_KEY}
+      preshared-key: 4Ur7pWuLBMf1DgDsGNw7ZGMxBLh6pr8DEsIgNeR3ec0=
+    - public-key: {PEER2_PUB_KEY}
+"#
+    ))
+    .unwrap();
+    state.hide_secrets();
+
+    if let Some(Interface::WireGuard(iface)) =
+        state.interfaces.kernel_ifaces.get("wg0")
+    {
+        let wg_conf = iface.wireguard.as_ref().unwrap();
+        let peers = wg_conf.peers.as_ref().unwrap();
+        assert_eq!(
+            wg_conf.private_key.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(
+            peers[0].preshared_key.as_deref(),
+            Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        );
+        assert_eq!(peers[1].preshared_key, None);
+    } else {
+        panic!("Expecting WireGuard interface wg0");
+    }
+}
+
+#[test]
+#[cfg(feature = "gen_conf")]
+fn test_wireguard_gen_conf_peer_sections() {
+    let state: NetworkState = serde_yaml::from_str(&format!(
+        r#"---
+interfaces:
+- name: wg0
+  type: wireguard
+  state: up
+  mtu: 1420
+  wireguard:
+    private-key: cFYlcO7Lri1pMWUybomXzDWKsqxJFLUUxqWB8ilJgGQ=
+    listen-port: 51820
+    peers:
+    - public-key: {PEER1_PUB_KEY}
+      endpoint: 192.0.2.1:51820
+      allowed-ips:
+      - 10.0.0.2/32
+      - 10.0.1.0/24
+"#
+    ))
+    .unwrap();
+    let confs = state.gen_conf().unwrap();
+    let (_, content) = &confs["NetworkManager"][0];
+
+    assert!(content.contains("type=wireguard\n"));
+    assert!(content.contains(
+        "[wireguard]\nlisten-port=51820\nmtu=1420\n\
+        private-key=cFYlcO7Lri1pMWUybomXzDWKsqxJFLUUxqWB8ilJgGQ=\n"
+    ));
+    assert!(content.contains(&format!(
+        "[wireguard-peer.{PEER1_PUB_KEY}]\n\
+        allowed-ips=10.0.0.2/32;10.0.1.0/24\nendpoint=192.0.2.1:51820\n"
+    )));
+    assert!(!content.contains("[ethernet]"));
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, InterfaceType, XfrmInterface};
+
+#[test]
+fn test_xfrm_stringlized_attributes() {
+    let iface: XfrmInterface = serde_yaml::from_str(
+        r#"---
+name: ipsec1
+type: xfrm
+state: up
+xfrm:
+  if-id: "100"
+  parent: eth1
+"#,
+    )
+    .unwrap();
+    let xfrm_conf = iface.xfrm.as_ref().unwrap();
+
+    assert_eq!(xfrm_conf.if_id, 100);
+    assert_eq!(iface.parent(), Some("eth1"));
+}
+
+#[test]
+fn test_xfrm_type_from_str() {
+    let iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipsec1
+type: xfrm
+state: up
+xfrm:
+  if-id: 100
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Xfrm);
+    assert_eq!(iface.iface_type().to_string(), "xfrm");
+    assert_eq!(iface.parent(), None);
+}
+
+#[test]
+fn test_xfrm_if_id_zero() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r#"---
+name: ipsec1
+type: xfrm
+state: up
+xfrm:
+  if-id: 0
+"#,
+    )
+    .unwrap();
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pci::is_valid_pci_address, ErrorKind, Interface, InterfaceType,
+    NetworkState, NmstateError,
+};
+
+// Userspace drivers DPDK PMD could use without kernel network driver
+const DPDK_PCI_DRIVERS: [&str; 3] = ["vfio-pci", "uio_pci_generic", "igb_uio"];
+const VFIO_PCI_DRIVER: &str = "vfio-pci";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Validation profile used by [NetworkState::validate_profile()] for auditing
+/// network state during IPv6 migration or before deploying DPDK workload.
+pub enum ValidationProfile {
+    /// Flag configurations depending on IPv4.
+    /// Deserialize and serialize from/to `ipv6-only`.
+    Ipv6Only,
+    /// Flag configurations only holding single IP stack.
+    /// Deserialize and serialize from/to `dual-stack`.
+    DualStack,
+    /// Flag configurations preventing OVS-DPDK from using the NICs: missing
+    /// DPDK interface, OVS DPDK not initialized, OVS bridge not using
+    /// `netdev` datapath, PCI device not bound to userspace driver and SR-IOV
+    /// PF not in `switchdev` eswitch mode when OVS hardware offload is
+    /// enabled. Hugepage allocation is not checked.
+    /// Deserialize and serialize from/to `dpdk-ready`.
+    DpdkReady,
+}
+
+impl std::fmt::Display for ValidationProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Ipv6Only => "ipv6-only",
+                Self::DualStack => "dual-stack",
+                Self::DpdkReady => "dpdk-ready",
+            }
+        )
+    }
+}
+
+impl FromStr for ValidationProfile {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, NmstateError> {
+        match s.to_lowercase().as_str() {
+            "ipv6-only" => Ok(Self::Ipv6Only),
+            "dual-stack" => Ok(Self::DualStack),
+            "dpdk-ready" => Ok(Self::DpdkReady),
+            _ => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid validation profile {s}, expecting \
+                    ipv6-only, dual-stack or dpdk-ready"
+                ),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Configuration incompatible with specified [ValidationProfile].
+pub struct ProfileViolation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Interface holding the incompatible configuration. None for global
+    /// configurations like DNS, routes and route rules.
+    pub interface: Option<String>,
+    /// Human readable reason.
+    pub reason: String,
+}
+
+impl ProfileViolation {
+    fn new(interface: Option<&str>, reason: String) -> Self {
+        Self {
+            interface: interface.map(|i| i.to_string()),
+            reason,
+        }
+    }
+}
+
+impl NetworkState {
+    /// Check this state against specified [ValidationProfile], return the
+    /// list of configurations incompatible with it. Both desired state and
+    /// state retrieved by [NetworkState::retrieve()] are supported.
+    pub fn validate_profile(
+        &self,
+        profile: ValidationProfile,
+    ) -> Vec<ProfileViolation> {
+        let mut ret = Vec::new();
+        if profile == ValidationProfile::DpdkReady {
+            validate_dpdk(self, &mut ret);
+            return ret;
+        }
+        let mut ifaces: Vec<&Interface> = self
+            .interfaces
+            .kernel_ifaces
+            .values()
+            .filter(|i| {
+                !i.is_absent()
+                    && i.iface_type() != InterfaceType::Loopback
+                    && !matches!(
+                        i.base_iface().controller.as_deref(),
+                        Some(c) if !c.is_empty()
+                    )
+            })
+            .collect();
+        ifaces.sort_unstable_by_key(|i| i.name());
+
+        for iface in ifaces {
+            validate_iface(iface, profile, &mut ret);
+        }
+        validate_dns(self, profile, &mut ret);
+
+        if profile == ValidationProfile::Ipv6Only {
+            for rt in self
+                .routes
+                .config
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter(|r| !r.is_absent() && !r.is_ipv6())
+            {
+                ret.push(ProfileViolation::new(
+                    rt.next_hop_iface.as_deref(),
+                    format!("IPv4 route '{rt}' found"),
+                ));
+            }
+            for rule in self
+                .rules
+                .config
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter(|r| !r.is_absent() && !r.is_ipv6())
+            {
+                ret.push(ProfileViolation::new(
+                    None,
+                    format!("IPv4 route rule '{rule}' found"),
+                ));
+            }
+        }
+        ret
+    }
+}
+
+fn validate_iface(
+    iface: &Interface,
+    profile: ValidationProfile,
+    ret: &mut Vec<ProfileViolation>,
+) {
+    let base_iface = iface.base_iface();
+    let name = Some(iface.name());
+    let ipv4_enabled = base_iface.is_ipv4_enabled();
+    let ipv6_enabled = base_iface.is_ipv6_enabled();
+
+    match profile {
+        ValidationProfile::Ipv6Only => {
+            if let Some(ipv4) = base_iface.ipv4.as_ref().filter(|i| i.enabled) {
+                if ipv4.dhcp == Some(true) {
+                    ret.push(ProfileViolation::new(
+                        name,
+                        "DHCPv4 is enabled".to_string(),
+                    ));
+                }
+                if ipv4.addresses.as_ref().map(|a| !a.is_empty()) == Some(true)
+                {
+                    ret.push(ProfileViolation::new(
+                        name,
+                        "Static IPv4 address is configured".to_string(),
+                    ));
+                }
+            }
+            if ipv4_enabled && !ipv6_enabled {
+                ret.push(ProfileViolation::new(
+                    name,
+                    "IPv6 is disabled".to_string(),
+                ));
+            }
+        }
+        ValidationProfile::DualStack => {
+            if ipv4_enabled && !ipv6_enabled {
+                ret.push(ProfileViolation::new(
+                    name,
+                    "IPv6 is disabled while IPv4 is enabled".to_string(),
+                ));
+            }
+            if ipv6_enabled && !ipv4_enabled {
+                ret.push(ProfileViolation::new(
+                    name,
+                    "IPv4 is disabled while IPv6 is enabled".to_string(),
+                ));
+            }
+        }
+        ValidationProfile::DpdkReady => (),
+    }
+
+    if let Some(ipv6) = base_iface.ipv6.as_ref().filter(|i| i.enabled) {
+        if ipv6.autoconf != Some(true)
+            && ipv6.dhcp != Some(true)
+            && ipv6.addresses.as_ref().map(|a| a.is_empty()) != Some(false)
+        {
+            ret.push(ProfileViolation::new(
+                name,
+                "IPv6 has neither router advertisement(autoconf), DHCPv6 \
+                nor static address configured"
+                    .to_string(),
+            ));
+        }
+    }
+}
+
+fn validate_dns(
+    net_state: &NetworkState,
+    profile: ValidationProfile,
+    ret: &mut Vec<ProfileViolation>,
+) {
+    let servers = match net_state
+        .dns
+        .config
+        .as_ref()
+        .or(net_state.dns.running.as_ref())
+        .and_then(|c| c.server.as_deref())
+    {
+        Some(s) if !s.is_empty() => s,
+        _ => return,
+    };
+    let ipv4_servers: Vec<&str> = servers
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| Ipv4Addr::from_str(s).is_ok())
+        .collect();
+    match profile {
+        ValidationProfile::Ipv6Only => {
+            for srv in ipv4_servers {
+                ret.push(ProfileViolation::new(
+                    None,
+                    format!("IPv4 DNS server {srv} is configured"),
+                ));
+            }
+        }
+        ValidationProfile::DualStack => {
+            if ipv4_servers.len() == servers.len() {
+                ret.push(ProfileViolation::new(
+                    None,
+                    "No IPv6 DNS server is configured".to_string(),
+                ));
+            }
+        }
+        ValidationProfile::DpdkReady => (),
+    }
+}
+
+fn validate_dpdk(net_state: &NetworkState, ret: &mut Vec<ProfileViolation>) {
+    let mut dpdk_ifaces: Vec<(&str, &str)> = net_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| !i.is_absent())
+        .filter_map(|i| {
+            if let Interface::OvsInterface(ovs_iface) = i {
+                ovs_iface
+                    .dpdk
+                    .as_ref()
+                    .map(|d| (i.name(), d.devargs.as_str()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    dpdk_ifaces.sort_unstable();
+
+    if dpdk_ifaces.is_empty() {
+        ret.push(ProfileViolation::new(
+            None,
+            "No OVS DPDK interface is configured".to_string(),
+        ));
+        return;
+    }
+
+    let ovs_other_config = net_state.ovsdb.other_config.as_ref();
+    let get_ovs_other_config = |key: &str| {
+        ovs_other_config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_deref())
+    };
+    if get_ovs_other_config("dpdk-init") != Some("true") {
+        ret.push(ProfileViolation::new(
+            None,
+            "OVS DPDK is not initialized, please set `dpdk-init: \"true\"` \
+            in `other_config` of `ovs-db`"
+                .to_string(),
+        ));
+    }
+
+    let mut ovs_brs: Vec<&Interface> = net_state
+        .interfaces
+        .user_ifaces
+        .values()
+        .filter(|i| i.iface_type() == InterfaceType::OvsBridge)
+        .filter(|i| !i.is_absent())
+        .collect();
+    ovs_brs.sort_unstable_by_key(|i| i.name());
+    for br_iface in ovs_brs {
+        let datapath = if let Interface::OvsBridge(i) = br_iface {
+            i.bridge
+                .as_ref()
+                .and_then(|b| b.options.as_ref())
+                .and_then(|o| o.datapath.as_deref())
+        } else {
+            None
+        };
+        let ports = br_iface.ports().unwrap_or_default();
+        for (iface_name, _) in dpdk_ifaces
+            .iter()
+            .filter(|(iface_name, _)| ports.contains(iface_name))
+        {
+            if datapath != Some("netdev") {
+                ret.push(ProfileViolation::new(
+                    Some(br_iface.name()),
+                    format!(
+                        "OVS bridge holding DPDK interface {iface_name} \
+                        should use `netdev` datapath"
+                    ),
+                ));
+            }
+        }
+    }
+
+    for (iface_name, devargs) in dpdk_ifaces {
+        validate_dpdk_pci_device(net_state, iface_name, devargs, ret);
+    }
+
+    if get_ovs_other_config("hw-offload") == Some("true") {
+        validate_switchdev(net_state, ret);
+    }
+}
+
+fn validate_dpdk_pci_device(
+    net_state: &NetworkState,
+    iface_name: &str,
+    devargs: &str,
+    ret: &mut Vec<ProfileViolation>,
+) {
+    // The devargs might hold extra options like `0000:3b:00.1,txq_inline=128`
+    let pci_address =
+        devargs.split(',').next().unwrap_or_default().to_lowercase();
+    if !is_valid_pci_address(&pci_address) {
+        return;
+    }
+    let pci_dev = net_state
+        .pci_devices
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .find(|d| d.pci_address.to_lowercase() == pci_address);
+    let driver_override = pci_dev
+        .and_then(|d| d.driver_override.as_deref())
+        .unwrap_or_default();
+    if !DPDK_PCI_DRIVERS.contains(&driver_override) {
+        ret.push(ProfileViolation::new(
+            Some(iface_name),
+            format!(
+                "PCI device {pci_address} is not bound to userspace driver, \
+                please set `driver-override: {VFIO_PCI_DRIVER}` in \
+                `pci-devices` unless NIC is using bifurcated driver like \
+                mlx5_core"
+            ),
+        ));
+    } else if is_queried_pci_devices(net_state) {
+        let driver = pci_dev.and_then(|d| d.driver.as_deref());
+        if driver != Some(driver_override) {
+            let mut reason = format!(
+                "PCI device {pci_address} is bound to {} instead of \
+                {driver_override}",
+                driver.unwrap_or("no driver")
+            );
+            if driver_override == VFIO_PCI_DRIVER {
+                reason.push_str(
+                    ", please make sure IOMMU is enabled by kernel arguments \
+                    `intel_iommu=on iommu=pt` or `amd_iommu=on iommu=pt`",
+                );
+            }
+            ret.push(ProfileViolation::new(Some(iface_name), reason));
+        }
+    }
+}
+
+// The query only `driver` property is only available in state retrieved
+// from the host.
+fn is_queried_pci_devices(net_state: &NetworkState) -> bool {
+    net_state
+        .pci_devices
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .any(|d| d.driver.is_some())
+}
+
+// OVS hardware offload requires SR-IOV PF in switchdev eswitch mode which
+// is only detectable from VF representor in state retrieved from the host.
+fn validate_switchdev(
+    net_state: &NetworkState,
+    ret: &mut Vec<ProfileViolation>,
+) {
+    let mut pf_names: Vec<&str> = Vec::new();
+    for iface in net_state.interfaces.kernel_ifaces.values() {
+        if let Interface::Ethernet(eth_iface) = iface {
+            let vfs = eth_iface
+                .ethernet
+                .as_ref()
+                .and_then(|e| e.sr_iov.as_ref())
+                .and_then(|s| s.vfs.as_deref())
+                .unwrap_or_default();
+            if vfs.iter().any(|vf| vf.pci_address.is_some())
+                && vfs.iter().all(|vf| vf.representor.is_none())
+            {
+                pf_names.push(iface.name());
+            }
+        }
+    }
+    pf_names.sort_unstable();
+    for pf_name in pf_names {
+        ret.push(ProfileViolation::new(
+            Some(pf_name),
+            "SR-IOV VFs have no representor while OVS hw-offload is \
+            enabled, please change eswitch mode of PF to switchdev via \
+            `devlink dev eswitch set pci/<pci_address> mode switchdev`"
+                .to_string(),
+        ));
+    }
+}
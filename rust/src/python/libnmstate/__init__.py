@@ -5,6 +5,7 @@ from .netapplier import commit
 from .netapplier import rollback
 from .netinfo import show
 from .netinfo import show_running_config
+from .netinfo import show_saved_config
 from .prettystate import PrettyState
 from .nmpolicy import gen_net_state_from_policy
 
@@ -18,6 +19,7 @@ __all__ = [
     "rollback",
     "show",
     "show_running_config",
+    "show_saved_config",
 ]
 
 __version__ = "2.2.9"
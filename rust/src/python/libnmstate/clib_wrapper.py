@@ -39,6 +39,7 @@ NMSTATE_FLAG_INCLUDE_SECRETS = 1 << 4
 NMSTATE_FLAG_NO_COMMIT = 1 << 5
 NMSTATE_FLAG_MEMORY_ONLY = 1 << 6
 NMSTATE_FLAG_RUNNING_CONFIG_ONLY = 1 << 7
+NMSTATE_FLAG_SAVED_CONFIG_ONLY = 1 << 9
 NMSTATE_PASS = 0
 
 
@@ -47,6 +48,7 @@ def retrieve_net_state_json(
     include_status_data=False,
     include_secrets=False,
     running_config_only=False,
+    saved_config_only=False,
 ):
     c_err_msg = c_char_p()
     c_err_kind = c_char_p()
@@ -61,6 +63,8 @@ def retrieve_net_state_json(
         flags |= NMSTATE_FLAG_INCLUDE_SECRETS
     if running_config_only:
         flags |= NMSTATE_FLAG_RUNNING_CONFIG_ONLY
+    if saved_config_only:
+        flags |= NMSTATE_FLAG_SAVED_CONFIG_ONLY
 
     rc = lib.nmstate_net_state_retrieve(
         flags,
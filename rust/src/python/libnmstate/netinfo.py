@@ -36,3 +36,12 @@ def show_running_config(include_secrets=False):
             running_config_only=True,
         )
     )
+
+
+def show_saved_config(include_secrets=False):
+    return json.loads(
+        retrieve_net_state_json(
+            include_secrets=include_secrets,
+            saved_config_only=True,
+        )
+    )
@@ -133,6 +133,7 @@ class InterfaceIP:
     AUTO_ROUTE_METRIC = "auto-route-metric"
     MPTCP_FLAGS = "mptcp-flags"
     ALLOW_EXTRA_ADDRESS = "allow-extra-address"
+    EXACT = "exact"
 
 
 class InterfaceIPv4(InterfaceIP):
@@ -173,6 +174,7 @@ class Bridge:
     OPTIONS_SUBTREE = "options"
     PORT_SUBTREE = "port"
     PORTS_SUBTREE = "ports"
+    EXACT = "exact"
 
     class Port:
         NAME = "name"